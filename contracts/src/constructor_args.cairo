@@ -0,0 +1,53 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+pub trait IConstructorArgs<TContractState> {
+    fn get_owner(self: @TContractState) -> ContractAddress;
+    fn get_values(self: @TContractState) -> Array<felt252>;
+}
+
+/// Contract that can only be deployed with constructor calldata: an owner, which must not be
+/// zero, and an array of values, stored so that the calldata the constructor received can be
+/// read back.
+#[starknet::contract]
+mod ConstructorArgs {
+    use core::num::traits::Zero;
+    use starknet::ContractAddress;
+    use starknet::storage::Map;
+
+    #[storage]
+    struct Storage {
+        owner: ContractAddress,
+        values_len: u32,
+        values: Map<u32, felt252>,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, owner: ContractAddress, values: Array<felt252>) {
+        assert(!owner.is_zero(), 'owner is zero');
+        self.owner.write(owner);
+        self.values_len.write(values.len());
+        let mut index = 0;
+        while index < values.len() {
+            self.values.write(index, *values.at(index));
+            index += 1;
+        };
+    }
+
+    #[abi(embed_v0)]
+    impl ConstructorArgsImpl of super::IConstructorArgs<ContractState> {
+        fn get_owner(self: @ContractState) -> ContractAddress {
+            self.owner.read()
+        }
+
+        fn get_values(self: @ContractState) -> Array<felt252> {
+            let mut values = array![];
+            let mut index = 0;
+            while index < self.values_len.read() {
+                values.append(self.values.read(index));
+                index += 1;
+            };
+            values
+        }
+    }
+}
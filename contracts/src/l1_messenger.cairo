@@ -0,0 +1,35 @@
+#[starknet::interface]
+pub trait IL1Messenger<TContractState> {
+    fn withdraw(ref self: TContractState, to_address: felt252, amounts: Array<felt252>);
+}
+
+/// Sends L2 -> L1 messages, so that `messages_sent` of receipts and the settlement of messages on
+/// L1 can be tested.
+#[starknet::contract]
+mod L1Messenger {
+    use starknet::SyscallResultTrait;
+    use starknet::syscalls::send_message_to_l1_syscall;
+
+    #[storage]
+    struct Storage {}
+
+    #[abi(embed_v0)]
+    impl L1MessengerImpl of super::IL1Messenger<ContractState> {
+        /// Sends one message to `to_address` per amount, in order, with the payload
+        /// `[index, amount]`.
+        fn withdraw(ref self: ContractState, to_address: felt252, amounts: Array<felt252>) {
+            let mut amounts = amounts;
+            let mut index: felt252 = 0;
+            loop {
+                match amounts.pop_front() {
+                    Option::Some(amount) => {
+                        send_message_to_l1_syscall(to_address, array![index, amount].span())
+                            .unwrap_syscall();
+                        index += 1;
+                    },
+                    Option::None => { break; },
+                };
+            };
+        }
+    }
+}
@@ -29,7 +29,14 @@ mod smpl19;
 mod smpl20;
 mod smpl21;
 mod smpl22;
+mod smpl23;
+mod syscall_heavy;
+mod resource_generator;
+mod upgradeable;
+mod upgradeable_account;
+mod l1_messenger;
 mod exec_acc;
+mod constructor_args;
 
 /// Paymaster implementation.
 mod paymaster {
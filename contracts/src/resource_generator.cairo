@@ -0,0 +1,68 @@
+#[starknet::interface]
+pub trait IResourceGenerator<TContractState> {
+    fn emit_events(ref self: TContractState, count: u32);
+    fn write_storage(ref self: TContractState, count: u32);
+    fn get_round(self: @TContractState) -> felt252;
+}
+
+/// Contract producing as many events or storage writes as asked for in a single call, to probe the
+/// limits a node puts on the resources of a transaction.
+#[starknet::contract]
+mod ResourceGenerator {
+    use starknet::storage::Map;
+
+    #[storage]
+    struct Storage {
+        round: felt252,
+        cells: Map<u32, felt252>,
+    }
+
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        Generated: Generated,
+    }
+
+    #[derive(Drop, starknet::Event)]
+    struct Generated {
+        #[key]
+        index: u32,
+        round: felt252,
+    }
+
+    #[abi(embed_v0)]
+    impl ResourceGeneratorImpl of super::IResourceGenerator<ContractState> {
+        fn emit_events(ref self: ContractState, count: u32) {
+            let round = self.next_round();
+            let mut index = 0;
+            while index < count {
+                self.emit(Generated { index, round });
+                index += 1;
+            };
+        }
+
+        /// Writes the cells `0..count`, with the value of a new round so that every write changes
+        /// the state.
+        fn write_storage(ref self: ContractState, count: u32) {
+            let round = self.next_round();
+            let mut index = 0;
+            while index < count {
+                self.cells.write(index, round);
+                index += 1;
+            };
+        }
+
+        fn get_round(self: @ContractState) -> felt252 {
+            self.round.read()
+        }
+    }
+
+    #[generate_trait]
+    impl InternalImpl of InternalTrait {
+        fn next_round(ref self: ContractState) -> felt252 {
+            let round = self.round.read() + 1;
+            self.round.write(round);
+            round
+        }
+    }
+}
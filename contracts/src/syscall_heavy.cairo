@@ -0,0 +1,56 @@
+#[starknet::interface]
+pub trait ISyscallHeavy<TContractState> {
+    fn keccak_rounds(ref self: TContractState, rounds: u32) -> u256;
+    fn secp256k1_rounds(ref self: TContractState, rounds: u32) -> u256;
+    fn get_last_result(self: @TContractState) -> u256;
+}
+
+/// Contract whose cost is dominated by expensive syscalls, to check that execution resources and
+/// fees scale with the number of syscalls.
+#[starknet::contract]
+mod SyscallHeavy {
+    use core::keccak::keccak_u256s_be_inputs;
+    use starknet::SyscallResultTrait;
+    use starknet::secp256_trait::{Secp256Trait, Secp256PointTrait};
+    use starknet::secp256k1::Secp256k1Point;
+
+    #[storage]
+    struct Storage {
+        last_result: u256,
+    }
+
+    #[abi(embed_v0)]
+    impl SyscallHeavyImpl of super::ISyscallHeavy<ContractState> {
+        /// Chains `rounds` keccak syscalls, each hashing the previous digest.
+        fn keccak_rounds(ref self: ContractState, rounds: u32) -> u256 {
+            let mut digest: u256 = self.last_result.read() + 1;
+            let mut round = 0;
+            while round < rounds {
+                digest = keccak_u256s_be_inputs(array![digest].span());
+                round += 1;
+            };
+            self.last_result.write(digest);
+            digest
+        }
+
+        /// Chains `rounds` secp256k1 multiplications of the generator, each by the `x` coordinate
+        /// of the previous point.
+        fn secp256k1_rounds(ref self: ContractState, rounds: u32) -> u256 {
+            let generator = Secp256Trait::<Secp256k1Point>::get_generator_point();
+            let mut scalar: u256 = self.last_result.read() + 2;
+            let mut round = 0;
+            while round < rounds {
+                let point = generator.mul(scalar).unwrap_syscall();
+                let (x, _) = point.get_coordinates().unwrap_syscall();
+                scalar = x;
+                round += 1;
+            };
+            self.last_result.write(scalar);
+            scalar
+        }
+
+        fn get_last_result(self: @ContractState) -> u256 {
+            self.last_result.read()
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use starknet::ClassHash;
+
+#[starknet::interface]
+pub trait IUpgradeable<TContractState> {
+    fn upgrade(ref self: TContractState, new_class_hash: ClassHash);
+    fn version(self: @TContractState) -> felt252;
+}
+
+/// First version of a contract that replaces its own class, so that the class replacement path of
+/// the state diff can be exercised.
+#[starknet::contract]
+mod UpgradeableV1 {
+    use starknet::{ClassHash, SyscallResultTrait};
+    use starknet::syscalls::replace_class_syscall;
+
+    #[storage]
+    struct Storage {}
+
+    #[abi(embed_v0)]
+    impl UpgradeableImpl of super::IUpgradeable<ContractState> {
+        fn upgrade(ref self: ContractState, new_class_hash: ClassHash) {
+            replace_class_syscall(new_class_hash).unwrap_syscall();
+        }
+
+        fn version(self: @ContractState) -> felt252 {
+            1
+        }
+    }
+}
+
+/// Second version of the contract, only differing from the first one in the version it reports.
+#[starknet::contract]
+mod UpgradeableV2 {
+    use starknet::{ClassHash, SyscallResultTrait};
+    use starknet::syscalls::replace_class_syscall;
+
+    #[storage]
+    struct Storage {}
+
+    #[abi(embed_v0)]
+    impl UpgradeableImpl of super::IUpgradeable<ContractState> {
+        fn upgrade(ref self: ContractState, new_class_hash: ClassHash) {
+            replace_class_syscall(new_class_hash).unwrap_syscall();
+        }
+
+        fn version(self: @ContractState) -> felt252 {
+            2
+        }
+    }
+}
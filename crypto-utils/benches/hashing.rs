@@ -0,0 +1,38 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use crypto_utils::curve::signer::compute_hash_on_elements;
+use crypto_utils::hash::{pedersen_hash_batch, poseidon_hash_batch, poseidon_hash_many};
+use starknet_types_core::felt::Felt;
+
+/// Roughly the size of the sierra program of a large contract.
+const SIERRA_PROGRAM_LENGTH: u64 = 20_000;
+
+fn felts(len: u64) -> Vec<Felt> {
+    (0..len).map(|i| Felt::from(i * 0x9e37_79b9 + 1)).collect()
+}
+
+fn poseidon(c: &mut Criterion) {
+    let program = felts(SIERRA_PROGRAM_LENGTH);
+    c.bench_function("poseidon_hash_many/sierra_program", |b| b.iter(|| poseidon_hash_many(black_box(&program))));
+
+    let mut group = c.benchmark_group("poseidon_hash_batch");
+    for count in [16u64, 256, 4096] {
+        let arrays: Vec<Vec<Felt>> = (0..count).map(|i| felts(8 + i % 8)).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(count), &arrays, |b, arrays| {
+            b.iter(|| poseidon_hash_batch(arrays.iter().map(Vec::as_slice)))
+        });
+    }
+    group.finish();
+}
+
+fn pedersen(c: &mut Criterion) {
+    let calldata = felts(256);
+    c.bench_function("compute_hash_on_elements/calldata", |b| {
+        b.iter(|| compute_hash_on_elements(black_box(&calldata)))
+    });
+
+    let arrays: Vec<Vec<Felt>> = (0..256).map(|_| felts(16)).collect();
+    c.bench_function("pedersen_hash_batch/256", |b| b.iter(|| pedersen_hash_batch(arrays.iter().map(Vec::as_slice))));
+}
+
+criterion_group!(benches, poseidon, pedersen);
+criterion_main!(benches);
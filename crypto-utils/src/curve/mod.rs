@@ -1 +1,2 @@
+pub mod secp;
 pub mod signer;
@@ -0,0 +1,257 @@
+use core::fmt::{Display, Formatter, Result as CoreResult};
+use k256::{ecdsa::signature::hazmat::PrehashVerifier, pkcs8::DecodePublicKey};
+use starknet_types_core::felt::Felt;
+
+/// ECDSA signature over secp256k1 or secp256r1, with `r` and `s` as big-endian 256-bit integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecpSignature {
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+    /// Parity of the `y` coordinate of the signature's `R` point.
+    pub y_parity: bool,
+}
+
+impl SecpSignature {
+    /// Signature as passed to account contracts: `[r.low, r.high, s.low, s.high]` followed by the
+    /// `y` parity when `with_parity` is set (as Ethereum-style accounts expect).
+    pub fn to_felts(&self, with_parity: bool) -> Vec<Felt> {
+        let [r_low, r_high] = u256_to_felts(&self.r);
+        let [s_low, s_high] = u256_to_felts(&self.s);
+        let mut felts = vec![r_low, r_high, s_low, s_high];
+        if with_parity {
+            felts.push(Felt::from(self.y_parity as u8));
+        }
+        felts
+    }
+}
+
+/// Uncompressed public key point on secp256k1 or secp256r1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecpPublicKey {
+    pub x: [u8; 32],
+    pub y: [u8; 32],
+}
+
+impl SecpPublicKey {
+    /// Public key as passed to account contracts: `[x.low, x.high, y.low, y.high]`.
+    pub fn to_felts(&self) -> Vec<Felt> {
+        let [x_low, x_high] = u256_to_felts(&self.x);
+        let [y_low, y_high] = u256_to_felts(&self.y);
+        vec![x_low, x_high, y_low, y_high]
+    }
+
+    /// SEC1 uncompressed encoding, `0x04 || x || y`.
+    pub fn to_sec1_bytes(&self) -> Vec<u8> {
+        [&[0x04][..], &self.x, &self.y].concat()
+    }
+}
+
+#[derive(Debug)]
+pub enum SecpError {
+    /// The private key is zero or not lower than the curve order.
+    InvalidPrivateKey,
+    /// The public key is not a valid point on the curve.
+    InvalidPublicKey,
+    /// `r` or `s` is zero or not lower than the curve order.
+    InvalidSignature,
+    SigningFailed,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SecpError {}
+
+impl Display for SecpError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> CoreResult {
+        match self {
+            Self::InvalidPrivateKey => write!(f, "invalid secp private key"),
+            Self::InvalidPublicKey => write!(f, "invalid secp public key"),
+            Self::InvalidSignature => write!(f, "invalid secp signature"),
+            Self::SigningFailed => write!(f, "secp signing failed"),
+        }
+    }
+}
+
+/// Signs the transaction hash, used as a 32-byte big-endian prehash, on secp256k1. The signature is
+/// normalized to low `s`.
+pub fn secp256k1_sign(private_key: &[u8; 32], message_hash: &Felt) -> Result<SecpSignature, SecpError> {
+    let signing_key = k256::ecdsa::SigningKey::from_slice(private_key).map_err(|_| SecpError::InvalidPrivateKey)?;
+    let (signature, recovery_id) =
+        signing_key.sign_prehash_recoverable(&message_hash.to_bytes_be()).map_err(|_| SecpError::SigningFailed)?;
+
+    let (r, s) = signature.split_bytes();
+    Ok(SecpSignature { r: r.into(), s: s.into(), y_parity: recovery_id.is_y_odd() })
+}
+
+pub fn secp256k1_get_public_key(private_key: &[u8; 32]) -> Result<SecpPublicKey, SecpError> {
+    let signing_key = k256::ecdsa::SigningKey::from_slice(private_key).map_err(|_| SecpError::InvalidPrivateKey)?;
+    let point = signing_key.verifying_key().to_encoded_point(false);
+    sec1_point_to_public_key(point.as_bytes())
+}
+
+/// Verifies a secp256k1 signature; both low and high `s` are accepted, as account contracts do.
+pub fn secp256k1_verify(
+    public_key: &SecpPublicKey,
+    message_hash: &Felt,
+    signature: &SecpSignature,
+) -> Result<bool, SecpError> {
+    let verifying_key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&public_key.to_sec1_bytes())
+        .map_err(|_| SecpError::InvalidPublicKey)?;
+    let signature =
+        k256::ecdsa::Signature::from_scalars(signature.r, signature.s).map_err(|_| SecpError::InvalidSignature)?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    Ok(verifying_key.verify_prehash(&message_hash.to_bytes_be(), &signature).is_ok())
+}
+
+/// Signs the transaction hash, used as a 32-byte big-endian prehash, on secp256r1. The signature is
+/// normalized to low `s`.
+pub fn secp256r1_sign(private_key: &[u8; 32], message_hash: &Felt) -> Result<SecpSignature, SecpError> {
+    let signing_key = p256::ecdsa::SigningKey::from_slice(private_key).map_err(|_| SecpError::InvalidPrivateKey)?;
+    let (signature, recovery_id) =
+        signing_key.sign_prehash_recoverable(&message_hash.to_bytes_be()).map_err(|_| SecpError::SigningFailed)?;
+
+    // Unlike secp256k1, the generic signer does not normalize `s`; negating `s` flips the parity.
+    let (signature, y_parity) = match signature.normalize_s() {
+        Some(normalized) => (normalized, !recovery_id.is_y_odd()),
+        None => (signature, recovery_id.is_y_odd()),
+    };
+
+    let (r, s) = signature.split_bytes();
+    Ok(SecpSignature { r: r.into(), s: s.into(), y_parity })
+}
+
+pub fn secp256r1_get_public_key(private_key: &[u8; 32]) -> Result<SecpPublicKey, SecpError> {
+    let signing_key = p256::ecdsa::SigningKey::from_slice(private_key).map_err(|_| SecpError::InvalidPrivateKey)?;
+    let point = signing_key.verifying_key().to_encoded_point(false);
+    sec1_point_to_public_key(point.as_bytes())
+}
+
+/// Verifies a secp256r1 signature; both low and high `s` are accepted, as account contracts do.
+pub fn secp256r1_verify(
+    public_key: &SecpPublicKey,
+    message_hash: &Felt,
+    signature: &SecpSignature,
+) -> Result<bool, SecpError> {
+    let verifying_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&public_key.to_sec1_bytes())
+        .map_err(|_| SecpError::InvalidPublicKey)?;
+    let signature =
+        p256::ecdsa::Signature::from_scalars(signature.r, signature.s).map_err(|_| SecpError::InvalidSignature)?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    Ok(verifying_key.verify_prehash(&message_hash.to_bytes_be(), &signature).is_ok())
+}
+
+/// Public key of a DER encoded `SubjectPublicKeyInfo` on secp256k1, the form key management
+/// services export public keys in.
+pub fn secp256k1_public_key_from_der(spki: &[u8]) -> Result<SecpPublicKey, SecpError> {
+    let verifying_key =
+        k256::ecdsa::VerifyingKey::from_public_key_der(spki).map_err(|_| SecpError::InvalidPublicKey)?;
+    sec1_point_to_public_key(verifying_key.to_encoded_point(false).as_bytes())
+}
+
+/// Public key of a DER encoded `SubjectPublicKeyInfo` on secp256r1.
+pub fn secp256r1_public_key_from_der(spki: &[u8]) -> Result<SecpPublicKey, SecpError> {
+    let verifying_key =
+        p256::ecdsa::VerifyingKey::from_public_key_der(spki).map_err(|_| SecpError::InvalidPublicKey)?;
+    sec1_point_to_public_key(verifying_key.to_encoded_point(false).as_bytes())
+}
+
+/// Converts a DER encoded secp256k1 signature of `message_hash`, as key management services return
+/// them, to the form [secp256k1_sign] produces: `s` normalized to low and the `y` parity, which DER
+/// doesn't carry, recovered against `public_key`.
+pub fn secp256k1_signature_from_der(
+    public_key: &SecpPublicKey,
+    message_hash: &Felt,
+    der: &[u8],
+) -> Result<SecpSignature, SecpError> {
+    let signature = k256::ecdsa::Signature::from_der(der).map_err(|_| SecpError::InvalidSignature)?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    let prehash = message_hash.to_bytes_be();
+    let sec1 = public_key.to_sec1_bytes();
+    let y_parity = [false, true]
+        .into_iter()
+        .find(|is_y_odd| {
+            let recovery_id = k256::ecdsa::RecoveryId::new(*is_y_odd, false);
+            k256::ecdsa::VerifyingKey::recover_from_prehash(&prehash, &signature, recovery_id)
+                .is_ok_and(|key| key.to_encoded_point(false).as_bytes() == sec1.as_slice())
+        })
+        .ok_or(SecpError::InvalidSignature)?;
+
+    let (r, s) = signature.split_bytes();
+    Ok(SecpSignature { r: r.into(), s: s.into(), y_parity })
+}
+
+/// Converts a DER encoded secp256r1 signature of `message_hash` to the form [secp256r1_sign]
+/// produces, see [secp256k1_signature_from_der].
+pub fn secp256r1_signature_from_der(
+    public_key: &SecpPublicKey,
+    message_hash: &Felt,
+    der: &[u8],
+) -> Result<SecpSignature, SecpError> {
+    let signature = p256::ecdsa::Signature::from_der(der).map_err(|_| SecpError::InvalidSignature)?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    let prehash = message_hash.to_bytes_be();
+    let sec1 = public_key.to_sec1_bytes();
+    let y_parity = [false, true]
+        .into_iter()
+        .find(|is_y_odd| {
+            // `p256` doesn't re-export the recovery id, it's the same `ecdsa` type `k256` exports.
+            let recovery_id = k256::ecdsa::RecoveryId::new(*is_y_odd, false);
+            p256::ecdsa::VerifyingKey::recover_from_prehash(&prehash, &signature, recovery_id)
+                .is_ok_and(|key| key.to_encoded_point(false).as_bytes() == sec1.as_slice())
+        })
+        .ok_or(SecpError::InvalidSignature)?;
+
+    let (r, s) = signature.split_bytes();
+    Ok(SecpSignature { r: r.into(), s: s.into(), y_parity })
+}
+
+fn sec1_point_to_public_key(point: &[u8]) -> Result<SecpPublicKey, SecpError> {
+    // Uncompressed SEC1 points are `0x04 || x || y`.
+    if point.len() != 65 || point[0] != 0x04 {
+        return Err(SecpError::InvalidPublicKey);
+    }
+    let mut public_key = SecpPublicKey { x: [0; 32], y: [0; 32] };
+    public_key.x.copy_from_slice(&point[1..33]);
+    public_key.y.copy_from_slice(&point[33..]);
+    Ok(public_key)
+}
+
+/// Splits a big-endian 256-bit integer into its `[low, high]` 128-bit limbs.
+fn u256_to_felts(value: &[u8; 32]) -> [Felt; 2] {
+    [Felt::from_bytes_be_slice(&value[16..]), Felt::from_bytes_be_slice(&value[..16])]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_der_signatures_like_local_ones() {
+        let private_key = [7u8; 32];
+        let message_hash = Felt::from_hex("0x2d5f6a3e1c7b9a8d4e3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a3b2c1").unwrap();
+
+        // `SubjectPublicKeyInfo` header of an uncompressed secp256k1 point.
+        let spki_header = hex("3056301006072a8648ce3d020106052b8104000a034200");
+        let public_key = secp256k1_get_public_key(&private_key).unwrap();
+        let spki = [spki_header, public_key.to_sec1_bytes()].concat();
+        assert_eq!(secp256k1_public_key_from_der(&spki).unwrap(), public_key);
+
+        let signing_key = k256::ecdsa::SigningKey::from_slice(&private_key).unwrap();
+        let (signature, _) = signing_key.sign_prehash_recoverable(&message_hash.to_bytes_be()).unwrap();
+        let converted = secp256k1_signature_from_der(&public_key, &message_hash, signature.to_der().as_bytes());
+        assert_eq!(converted.unwrap(), secp256k1_sign(&private_key, &message_hash).unwrap());
+
+        let signing_key = p256::ecdsa::SigningKey::from_slice(&private_key).unwrap();
+        let public_key = secp256r1_get_public_key(&private_key).unwrap();
+        let (signature, _) = signing_key.sign_prehash_recoverable(&message_hash.to_bytes_be()).unwrap();
+        let converted = secp256r1_signature_from_der(&public_key, &message_hash, signature.to_der().as_bytes());
+        assert_eq!(converted.unwrap(), secp256r1_sign(&private_key, &message_hash).unwrap());
+    }
+
+    fn hex(value: &str) -> Vec<u8> {
+        (0..value.len()).step_by(2).map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap()).collect()
+    }
+}
@@ -0,0 +1,112 @@
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+/// Computes the Poseidon hash of an array of field elements.
+pub fn poseidon_hash_many(data: &[Felt]) -> Felt {
+    let mut hasher = PoseidonHasher::new();
+    hasher.update_many(data);
+    hasher.finalize()
+}
+
+/// Computes the Poseidon hash of each array, reusing a single sponge state for the whole batch.
+pub fn poseidon_hash_batch<'a, I>(arrays: I) -> Vec<Felt>
+where
+    I: IntoIterator<Item = &'a [Felt]>,
+{
+    let mut hasher = PoseidonHasher::new();
+    arrays
+        .into_iter()
+        .map(|array| {
+            hasher.update_many(array);
+            hasher.finalize_reset()
+        })
+        .collect()
+}
+
+/// Computes the Pedersen hash chain of each array, as
+/// [`compute_hash_on_elements`](crate::curve::signer::compute_hash_on_elements) does.
+pub fn pedersen_hash_batch<'a, I>(arrays: I) -> Vec<Felt>
+where
+    I: IntoIterator<Item = &'a [Felt]>,
+{
+    arrays
+        .into_iter()
+        .map(|array| {
+            let hash = array.iter().fold(Felt::ZERO, |hash, element| Pedersen::hash(&hash, element));
+            Pedersen::hash(&hash, &Felt::from(array.len()))
+        })
+        .collect()
+}
+
+/// Incremental Poseidon hasher. Elements are absorbed into the sponge as they come, so no input is
+/// buffered. The result of [`PoseidonHasher::finalize`] equals [`poseidon_hash_many`] over all the
+/// elements passed to [`PoseidonHasher::update`].
+#[derive(Debug, Default, Clone)]
+pub struct PoseidonHasher {
+    state: [Felt; 3],
+    /// First element of a pair not yet absorbed; the sponge absorbs two elements per permutation.
+    pending: Option<Felt>,
+}
+
+impl PoseidonHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, element: Felt) {
+        match self.pending.take() {
+            Some(pending) => {
+                self.state[0] += pending;
+                self.state[1] += element;
+                Poseidon::hades_permutation(&mut self.state);
+            }
+            None => self.pending = Some(element),
+        }
+    }
+
+    pub fn update_many(&mut self, elements: &[Felt]) {
+        for element in elements {
+            self.update(*element);
+        }
+    }
+
+    pub fn finalize(mut self) -> Felt {
+        self.finalize_reset()
+    }
+
+    /// Returns the hash and resets the hasher, so it can be reused for the next input.
+    pub fn finalize_reset(&mut self) -> Felt {
+        // Pad with a single one, as `Poseidon::hash_array` does.
+        match self.pending.take() {
+            Some(pending) => {
+                self.state[0] += pending;
+                self.state[1] += Felt::ONE;
+            }
+            None => self.state[0] += Felt::ONE,
+        }
+        Poseidon::hades_permutation(&mut self.state);
+
+        let hash = self.state[0];
+        self.state = [Felt::ZERO; 3];
+        hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::signer::compute_hash_on_elements;
+
+    #[test]
+    fn batch_hashes_match_single_hashes() {
+        let arrays: Vec<Vec<Felt>> = (0..5u64).map(|len| (0..len).map(Felt::from).collect()).collect();
+
+        let poseidon = poseidon_hash_batch(arrays.iter().map(Vec::as_slice));
+        let pedersen = pedersen_hash_batch(arrays.iter().map(Vec::as_slice));
+
+        for (index, array) in arrays.iter().enumerate() {
+            assert_eq!(poseidon[index], Poseidon::hash_array(array));
+            assert_eq!(pedersen[index], compute_hash_on_elements(array));
+        }
+    }
+}
@@ -1 +1,3 @@
 pub mod curve;
+pub mod hash;
+pub mod protocol;
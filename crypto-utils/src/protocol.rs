@@ -0,0 +1,251 @@
+//! Constants of the Starknet protocol that transaction, class and contract address hashes are built
+//! from. The hashing code of the workspace reads them from here, so that a protocol version changing
+//! one of them, like the resources of the fee market, is handled by adding a [VersionedConstants]
+//! entry rather than by editing every hash function.
+
+use std::fmt;
+
+use starknet_types_core::felt::{Felt, NonZeroFelt};
+use starknet_types_core::hash::{Poseidon, StarkHash};
+
+/// Cairo string for "invoke"
+pub const PREFIX_INVOKE: Felt =
+    Felt::from_raw([513398556346534256, 18446744073709551615, 18446744073709551615, 18443034532770911073]);
+
+/// Cairo string for "declare"
+pub const PREFIX_DECLARE: Felt =
+    Felt::from_raw([191557713328401194, 18446744073709551615, 18446744073709551615, 17542456862011667323]);
+
+/// Cairo string for "deploy_account"
+pub const PREFIX_DEPLOY_ACCOUNT: Felt =
+    Felt::from_raw([461298303000467581, 18446744073709551615, 18443211694809419988, 3350261884043292318]);
+
+/// Cairo string for "STARKNET_CONTRACT_ADDRESS"
+pub const PREFIX_CONTRACT_ADDRESS: Felt =
+    Felt::from_raw([533439743893157637, 8635008616843941496, 17289941567720117366, 3829237882463328880]);
+
+/// Cairo string for "CONTRACT_CLASS_V0.1.0"
+pub const PREFIX_CONTRACT_CLASS_V0_1_0: Felt =
+    Felt::from_raw([37302452645455172, 18446734822722598327, 15539482671244488427, 5800711240972404213]);
+
+/// Cairo string for "COMPILED_CLASS_V1"
+pub const PREFIX_COMPILED_CLASS_V1: Felt =
+    Felt::from_raw([324306817650036332, 18446744073709549462, 1609463842841646376, 2291010424822318237]);
+
+/// 2 ** 251 - 256, the bound contract addresses are reduced by.
+pub const ADDR_BOUND: NonZeroFelt =
+    NonZeroFelt::from_raw([576459263475590224, 18446744073709255680, 160989183, 18446743986131443745]);
+
+/// Shift of the nonce data availability mode next to the fee one in V3 transaction hashes.
+pub const DATA_AVAILABILITY_MODE_BITS: u8 = 32;
+
+/// Version field of a transaction of `version`. Transactions signed for estimates and simulations
+/// only have the version offset by 2 ** 128, so that their signature can't be replayed.
+pub fn transaction_version(version: u64, query_only: bool) -> Felt {
+    let version = Felt::from(version);
+    if query_only {
+        version + Felt::from(u128::MAX) + Felt::ONE
+    } else {
+        version
+    }
+}
+
+/// Resource bounded by V3 transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    L1Gas,
+    L2Gas,
+    L1DataGas,
+}
+
+impl Resource {
+    /// Name of the resource in the hash of its bounds.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Resource::L1Gas => "L1_GAS",
+            Resource::L2Gas => "L2_GAS",
+            Resource::L1DataGas => "L1_DATA",
+        }
+    }
+
+    /// Bounds of the resource as SNIP-8 hashes them, `name || max_amount || max_price_per_unit`
+    /// with the name in the first 8 bytes.
+    pub fn bounds(self, max_amount: u64, max_price_per_unit: u128) -> Felt {
+        let name = self.name().as_bytes();
+        let mut bytes = [0u8; 32];
+        bytes[8 - name.len()..8].copy_from_slice(name);
+        bytes[8..16].copy_from_slice(&max_amount.to_be_bytes());
+        bytes[16..].copy_from_slice(&max_price_per_unit.to_be_bytes());
+        Felt::from_bytes_be(&bytes)
+    }
+}
+
+/// Prefixes of the transaction hashes of a protocol version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashPrefixes {
+    pub invoke: Felt,
+    pub declare: Felt,
+    pub deploy_account: Felt,
+}
+
+/// Prefixes of every protocol version so far.
+pub const HASH_PREFIXES: HashPrefixes =
+    HashPrefixes { invoke: PREFIX_INVOKE, declare: PREFIX_DECLARE, deploy_account: PREFIX_DEPLOY_ACCOUNT };
+
+/// Constants that differ between protocol versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionedConstants {
+    /// First protocol version, `[major, minor, patch]`, the constants apply to.
+    pub since: [u32; 3],
+    pub prefixes: HashPrefixes,
+    /// Resources bounded by V3 transactions, in the order their bounds are hashed after the tip.
+    pub resources: &'static [Resource],
+}
+
+impl VersionedConstants {
+    /// Hash of the fee fields of a V3 transaction: `tip` followed by the bounds of each of
+    /// [resources](Self::resources). `bounds` holds `(resource, max_amount, max_price_per_unit)`
+    /// entries for the resources the transaction pays for; the others are hashed with zero bounds.
+    pub fn fee_data_hash(&self, tip: Felt, bounds: &[(Resource, u64, u128)]) -> Felt {
+        let mut fee_data = vec![tip];
+        for resource in self.resources {
+            let (max_amount, max_price_per_unit) = bounds
+                .iter()
+                .find(|(bounded, ..)| bounded == resource)
+                .map(|(_, max_amount, max_price_per_unit)| (*max_amount, *max_price_per_unit))
+                .unwrap_or_default();
+            fee_data.push(resource.bounds(max_amount, max_price_per_unit));
+        }
+        Poseidon::hash_array(&fee_data)
+    }
+}
+
+/// Constants of every protocol version with V3 transactions, oldest first.
+pub static VERSIONED_CONSTANTS: [VersionedConstants; 4] = [
+    VersionedConstants { since: [0, 13, 0], prefixes: HASH_PREFIXES, resources: &[Resource::L1Gas, Resource::L2Gas] },
+    VersionedConstants { since: [0, 13, 1], prefixes: HASH_PREFIXES, resources: &[Resource::L1Gas, Resource::L2Gas] },
+    VersionedConstants { since: [0, 13, 2], prefixes: HASH_PREFIXES, resources: &[Resource::L1Gas, Resource::L2Gas] },
+    VersionedConstants {
+        since: [0, 13, 4],
+        prefixes: HASH_PREFIXES,
+        resources: &[Resource::L1Gas, Resource::L2Gas, Resource::L1DataGas],
+    },
+];
+
+/// Protocol version of the v0.7 RPC, which the transactions built and verified by the workspace
+/// follow.
+pub const TARGET_PROTOCOL_VERSION: [u32; 3] = [0, 13, 2];
+
+/// Constants of protocol `version`; versions older than every entry get the oldest one.
+pub fn versioned_constants(version: [u32; 3]) -> &'static VersionedConstants {
+    VERSIONED_CONSTANTS.iter().rev().find(|constants| constants.since <= version).unwrap_or(&VERSIONED_CONSTANTS[0])
+}
+
+/// Starknet protocol version `major.minor.patch`, optionally followed by a fourth component for
+/// hotfixes such as `0.13.1.1`, as passed on the command line or reported in a block's
+/// `starknet_version`. Versions compare component by component, `0.13.1 < 0.13.1.1 < 0.13.2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtocolVersion([u32; 4]);
+
+impl ProtocolVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32, hotfix: u32) -> Self {
+        Self([major, minor, patch, hotfix])
+    }
+
+    /// Parses 3 or 4 dot-separated decimal components, without any prefix or suffix.
+    pub fn parse(version: &str) -> Option<Self> {
+        let parts = version
+            .split('.')
+            .map(|part| part.parse::<u32>().ok().filter(|_| part.bytes().all(|byte| byte.is_ascii_digit())))
+            .collect::<Option<Vec<_>>>()?;
+        if !(3..=4).contains(&parts.len()) {
+            return None;
+        }
+        let mut components = [0; 4];
+        components[..parts.len()].copy_from_slice(&parts);
+        Some(Self(components))
+    }
+
+    /// `[major, minor, patch]`, which the [VersionedConstants] are keyed by.
+    pub const fn release(&self) -> [u32; 3] {
+        [self.0[0], self.0[1], self.0[2]]
+    }
+
+    pub const fn hotfix(&self) -> u32 {
+        self.0[3]
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [major, minor, patch, hotfix] = self.0;
+        write!(f, "{major}.{minor}.{patch}")?;
+        if hotfix != 0 {
+            write!(f, ".{hotfix}")?;
+        }
+        Ok(())
+    }
+}
+
+/// First protocol version that bounds `resource`, if any.
+pub fn first_version_bounding(resource: Resource) -> Option<[u32; 3]> {
+    VERSIONED_CONSTANTS
+        .iter()
+        .find(|constants| constants.resources.contains(&resource))
+        .map(|constants| constants.since)
+}
+
+/// Constants of [TARGET_PROTOCOL_VERSION].
+pub fn target_constants() -> &'static VersionedConstants {
+    versioned_constants(TARGET_PROTOCOL_VERSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_protocol_encodings() {
+        // 2 ** 128 + 1, as hard-coded by the hash functions before.
+        let query_version_one = Felt::from_raw([576460752142433776, 18446744073709551584, 17407, 18446744073700081633]);
+        assert_eq!(transaction_version(1, true), query_version_one);
+        assert_eq!(transaction_version(3, false), Felt::THREE);
+
+        let bounds = Resource::L1Gas.bounds(0x10, 0x20);
+        let mut expected = [0u8; 32];
+        expected[2..8].copy_from_slice(b"L1_GAS");
+        expected[15] = 0x10;
+        expected[31] = 0x20;
+        assert_eq!(bounds, Felt::from_bytes_be(&expected));
+
+        assert_eq!(versioned_constants([0, 13, 1]).resources, &[Resource::L1Gas, Resource::L2Gas]);
+        assert_eq!(versioned_constants([0, 13, 4]).resources.last(), Some(&Resource::L1DataGas));
+        assert_eq!(versioned_constants([0, 12, 0]).since, [0, 13, 0]);
+        assert_eq!(target_constants().prefixes, HASH_PREFIXES);
+
+        assert_eq!(first_version_bounding(Resource::L1DataGas), Some([0, 13, 4]));
+
+        let l2_unbounded = [Resource::L1Gas.bounds(0x10, 0x20), Resource::L2Gas.bounds(0, 0)];
+        assert_eq!(
+            target_constants().fee_data_hash(Felt::from(7), &[(Resource::L1Gas, 0x10, 0x20)]),
+            Poseidon::hash_array(&[Felt::from(7), l2_unbounded[0], l2_unbounded[1]])
+        );
+    }
+
+    #[test]
+    fn parses_and_orders_protocol_versions() {
+        let version = ProtocolVersion::parse("0.13.1.1").unwrap();
+        assert_eq!(version, ProtocolVersion::new(0, 13, 1, 1));
+        assert_eq!(version.release(), [0, 13, 1]);
+        assert_eq!(version.to_string(), "0.13.1.1");
+        assert_eq!(ProtocolVersion::parse("0.13.4").unwrap().to_string(), "0.13.4");
+
+        assert!(ProtocolVersion::parse("0.13.1") < ProtocolVersion::parse("0.13.1.1"));
+        assert!(ProtocolVersion::parse("0.13.1.1") < ProtocolVersion::parse("0.13.2"));
+        assert!(ProtocolVersion::parse("0.9.1") < ProtocolVersion::parse("0.13.0"));
+
+        for invalid in ["", "0.13", "0.13.1.1.1", "v0.13.1", "0.13.x", "0.+13.1", "0.13.1-rc.1"] {
+            assert_eq!(ProtocolVersion::parse(invalid), None, "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! # Test Case Registration
+//!
+//! `#[openrpc_test(suite = "openrpc", name = "estimate_fee_wei")]` marks the `RunnableTrait`
+//! implementation of a test case. The `openrpc-testgen` build script collects every `test_*.rs`
+//! file carrying the attribute into the suite of its directory, so the case does not have to be
+//! declared in the suite's `mod.rs`, and the runner selects cases by their registered names.
+//!
+//! The attribute itself keeps the implementation as is and exposes the registration as
+//! `TestCase::SUITE` and `TestCase::NAME`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{meta::ParseNestedMeta, parse_macro_input, spanned::Spanned, Error, ItemImpl, LitStr};
+
+#[derive(Default)]
+struct TestArgs {
+    suite: Option<LitStr>,
+    name: Option<LitStr>,
+}
+
+impl TestArgs {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("suite") {
+            self.suite = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("unsupported openrpc_test property, expected `suite` or `name`"));
+        }
+        Ok(())
+    }
+}
+
+#[proc_macro_attribute]
+pub fn openrpc_test(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut test_args = TestArgs::default();
+    let parser = syn::meta::parser(|meta| test_args.parse(meta));
+    parse_macro_input!(args with parser);
+    let item = parse_macro_input!(item as ItemImpl);
+
+    match expand(test_args, item) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(args: TestArgs, item: ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
+    let is_runnable = item
+        .trait_
+        .as_ref()
+        .and_then(|(_, path, _)| path.segments.last())
+        .is_some_and(|segment| segment.ident == "RunnableTrait");
+    if !is_runnable {
+        return Err(Error::new(
+            item.span(),
+            "openrpc_test must be placed on an `impl RunnableTrait for TestCase` block",
+        ));
+    }
+
+    let suite = args.suite.ok_or_else(|| Error::new(item.span(), "openrpc_test is missing `suite = \"...\"`"))?;
+    let name = args.name.ok_or_else(|| Error::new(item.span(), "openrpc_test is missing `name = \"...\"`"))?;
+    for value in [&suite, &name] {
+        let value_str = value.value();
+        if value_str.is_empty() || !value_str.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(Error::new(value.span(), "expected a non-empty snake_case identifier"));
+        }
+    }
+
+    let self_ty = &item.self_ty;
+    Ok(quote! {
+        #item
+
+        impl #self_ty {
+            pub const SUITE: &'static str = #suite;
+            pub const NAME: &'static str = #name;
+        }
+    })
+}
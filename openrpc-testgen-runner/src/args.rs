@@ -1,32 +1,497 @@
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use openrpc_testgen::utils::network_registry::Network;
+#[cfg(feature = "orchestrator")]
+use openrpc_testgen::utils::orchestrator::NodeKind;
+use openrpc_testgen::utils::response_normalizers::MethodNormalizers;
+use openrpc_testgen::utils::v7::accounts::utils::faucet::FaucetKind;
+use openrpc_testgen::utils::v7::endpoints::block_selector::BlockSelector;
 use starknet_types_core::felt::Felt;
 use url::Url;
 
 #[derive(Parser, Debug, Clone)]
 #[command(version, about, long_about = None, disable_version_flag = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    #[arg(
+        long,
+        env,
+        global = true,
+        help = "JSON file of options by long name, with ${VAR} replaced by environment variables; \
+                the command line and the environment take precedence"
+    )]
+    pub config: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        global = true,
+        requires = "config",
+        help = "Network of --config whose options override the shared ones of the file"
+    )]
+    pub config_network: Option<String>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Run test suites against a node
+    Run(Box<Args>),
+    /// List the test cases of the suites, as selected by --filter
+    List(ListArgs),
+    /// Send the same read requests to several nodes and compare their responses
+    Diff(DiffArgs),
+    /// Run the suites against every network of --config, one after the other, and combine the reports
+    Multi(MultiArgs),
+}
+
+#[derive(clap::Args, Debug, Clone)]
 pub struct Args {
     #[arg(
         long,
         env,
+        alias = "url",
         help = "Space-separated URLs of the L2 nodes (e.g. 'http://127.0.0.1:5050 http://127.0.0.1:5050')",
         value_delimiter = ' '
     )]
     pub urls: Vec<Url>,
 
-    #[arg(long, env, help = "Address of an account that would pay for fees")]
-    pub paymaster_account_address: Felt,
+    #[arg(
+        long,
+        env,
+        help = "Space-separated relative odds of each node of --urls, in order, receiving the transactions of the \
+                executable accounts [default: 1 each]",
+        value_delimiter = ' '
+    )]
+    pub node_weights: Vec<u32>,
 
-    #[arg(long, env, help = "Private Key of an account that would pay for fees")]
-    pub paymaster_private_key: Felt,
+    #[arg(
+        long,
+        env,
+        required_unless_present = "use_predeployed_accounts",
+        help = "Address of an account that would pay for fees"
+    )]
+    pub paymaster_account_address: Option<Felt>,
 
-    #[arg(long, env, help = "Universal Deployer Contract address")]
-    pub udc_address: Felt,
+    #[arg(
+        long,
+        env,
+        required_unless_present = "use_predeployed_accounts",
+        help = "Private Key of an account that would pay for fees"
+    )]
+    pub paymaster_private_key: Option<Felt>,
 
-    #[arg(long, env, help = "Class hash of account contract")]
-    pub account_class_hash: Felt,
+    #[arg(
+        long,
+        env,
+        conflicts_with_all = ["paymaster_account_address", "paymaster_private_key"],
+        help = "Pay for fees with the first predeployed account of the first node, read from devnet's \
+                /predeployed_accounts or katana's dev_predeployedAccounts"
+    )]
+    pub use_predeployed_accounts: bool,
+
+    #[arg(
+        long,
+        env,
+        help = "Class hash of the accounts the suites and test cases deploy, registered as the network's default \
+                account class [default: of --network]"
+    )]
+    pub account_class_hash: Option<Felt>,
+
+    #[arg(
+        long,
+        env,
+        value_enum,
+        default_value = "devnet",
+        help = "Network the node runs, which the chain name, token and UDC addresses default to"
+    )]
+    pub network: Network,
+
+    #[arg(long, env, help = "Universal Deployer Contract address [default: of --network]")]
+    pub udc_address: Option<Felt>,
+
+    #[arg(long, env, help = "Chain name the node must report, e.g. SN_SEPOLIA [default: of --network]")]
+    pub expected_chain_name: Option<String>,
+
+    #[arg(long, env, help = "STRK token address [default: of --network]")]
+    pub strk_address: Option<Felt>,
+
+    #[arg(long, env, help = "ETH token address [default: of --network]")]
+    pub eth_address: Option<Felt>,
+
+    #[arg(long, env, help = "Address of the sequencer fees are paid to [default: of --network]")]
+    pub sequencer_address: Option<Felt>,
+
+    #[arg(long, env, value_enum, help = "Fund the paymaster from this faucet before the suites run")]
+    pub faucet: Option<FaucetKind>,
+
+    #[arg(long, env, help = "URL of the faucet [default: the first node, for the devnet faucet]")]
+    pub faucet_url: Option<Url>,
+
+    #[arg(long, env, help = "API key of the HTTP faucet")]
+    pub faucet_api_key: Option<String>,
+
+    #[arg(long, env, default_value = "x-api-key", help = "Header the API key of the HTTP faucet is sent in")]
+    pub faucet_api_key_header: String,
+
+    #[arg(
+        long,
+        env,
+        default_value_t = 100_000_000_000_000_000_000,
+        help = "Amount of each fee token requested from the faucet, in wei and fri"
+    )]
+    pub faucet_amount: u128,
+
+    #[arg(
+        long,
+        env,
+        requires = "faucet",
+        help = "Top the paymaster accounts up with --faucet-amount from the faucet whenever a fee token balance drops \
+                below this, in wei and fri"
+    )]
+    pub top_up_threshold: Option<u128>,
 
-    #[arg(short, long, value_enum)]
+    #[arg(short, long, env, value_enum, help = "Space-separated suites to run", value_delimiter = ' ')]
     pub suite: Vec<Suite>,
+
+    #[arg(
+        long,
+        env,
+        help = "Space-separated patterns; only test cases whose 'suite::name' contains one of them are run",
+        value_delimiter = ' '
+    )]
+    pub filter: Vec<String>,
+
+    #[arg(
+        long,
+        env,
+        help = "Estimate fees and simulate transactions without broadcasting them; test cases and suite setups \
+                stop at their first transaction and are reported as skipped"
+    )]
+    pub dry_run: bool,
+
+    #[arg(long, env, help = "Write the outcome of every test case here as a JUnit XML report")]
+    pub report: Option<PathBuf>,
+
+    #[arg(long, env, help = "Write the outcome of every test case here as JSON")]
+    pub json_report: Option<PathBuf>,
+
+    #[arg(long, env, default_value = "snapshots", help = "Directory of the golden files of response snapshots")]
+    pub snapshot_dir: PathBuf,
+
+    #[arg(long, env, help = "Record the response snapshots again instead of comparing them with the golden files")]
+    pub update_snapshots: bool,
+
+    #[arg(
+        long,
+        env,
+        value_enum,
+        default_value = "latest",
+        help = "Space-separated blocks the v0.7 endpoint checks read state at; the checks run once for each of them",
+        value_delimiter = ' '
+    )]
+    pub block_selector: Vec<BlockSelector>,
+
+    #[arg(long, env, help = "Wait for the node to come back when it becomes unreachable between test cases")]
+    pub resilience: bool,
+
+    #[arg(
+        long,
+        env,
+        default_value_t = 300,
+        help = "How long the node may be unreachable in resilience mode, in seconds"
+    )]
+    pub max_downtime_secs: u64,
+
+    #[arg(long, env, help = "Idle connections kept open per node by the HTTP client shared by the run")]
+    pub http_pool_max_idle_per_host: Option<usize>,
+
+    #[arg(long, env, help = "How long an idle connection of the HTTP client is kept open, in seconds")]
+    pub http_pool_idle_timeout_secs: Option<u64>,
+
+    #[arg(long, env, help = "Interval of the TCP keep-alive probes of the HTTP client, in seconds")]
+    pub http_tcp_keepalive_secs: Option<u64>,
+
+    #[arg(long, env, help = "Speak HTTP/2 to the nodes from the start, for nodes serving HTTP/2 without TLS")]
+    pub http2_prior_knowledge: bool,
+
+    #[arg(long, env, help = "Timeout of every HTTP request to the nodes, in seconds")]
+    pub http_timeout_secs: Option<u64>,
+
+    #[arg(long, env, help = "Timeout of connecting to the nodes, in seconds")]
+    pub http_connect_timeout_secs: Option<u64>,
+
+    #[arg(
+        long,
+        env,
+        help = "Send every request to the nodes through this proxy, http://, https://, socks5:// or socks5h:// with \
+                credentials in the URL if needed"
+    )]
+    pub proxy: Option<Url>,
+
+    #[arg(long, env, help = "Docker container of the node, restarted before the test cases matching --restart-before")]
+    pub restart_container: Option<String>,
+
+    #[arg(
+        long,
+        env,
+        help = "Space-separated patterns; the node is restarted before test cases whose 'suite::name' contains one",
+        value_delimiter = ' '
+    )]
+    pub restart_before: Vec<String>,
+
+    #[arg(
+        long,
+        env,
+        help = "Record the fee estimate of every sent transaction and write how it compares with the actual fee here"
+    )]
+    pub fee_accuracy_report: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        help = "Record how long every sent transaction takes to be accepted on L2 and write a histogram here"
+    )]
+    pub latency_report: Option<PathBuf>,
+
+    #[arg(long, env, help = "Verify the hash of every class the suites touch and write the results here")]
+    pub class_verification_report: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        help = "Check that every class declared during the run is served by getClass and listed by exactly one state \
+                update, and write the results here"
+    )]
+    pub declared_classes_report: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        help = "Run finality_progression, which waits for L1 acceptance of a transaction, and write its timings here"
+    )]
+    pub finality_report: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        default_value_t = 3600,
+        help = "How long finality_progression waits for L1 acceptance once a transaction is accepted on L2, in seconds"
+    )]
+    pub finality_timeout_secs: u64,
+
+    #[arg(long, env, help = "Have the devnet accept the block of finality_progression on L1 instead of waiting")]
+    pub simulate_settlement: bool,
+
+    #[arg(
+        long,
+        env,
+        help = "Run resource_limits, which ramps the events and storage writes of an invoke, and write the limits here"
+    )]
+    pub resource_limits_report: Option<PathBuf>,
+
+    #[arg(long, env, default_value_t = 16, help = "Events or storage writes resource_limits starts its ramp with")]
+    pub resource_limits_start: u32,
+
+    #[arg(long, env, default_value_t = 65536, help = "Events or storage writes resource_limits stops its ramp at")]
+    pub resource_limits_max: u32,
+
+    #[arg(long, env, help = "Record the inclusion order of mempool_ordering's transactions with increasing tips here")]
+    pub ordering_report: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        help = "Run minimal_fee_bounds, which searches the smallest gas bounds the node accepts, and compare them with \
+                the estimates here"
+    )]
+    pub fee_search_report: Option<PathBuf>,
+
+    #[arg(long, env, help = "Record the size of every response by method, as received and decoded, and write it here")]
+    pub response_size_report: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        help = "Record the time of every request by method, split into serialization, connection and wait for \
+                the first byte, download and deserialization, and of DNS lookups by host, and write it here"
+    )]
+    pub timing_report: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        help = "Capture every request and response, headers and bodies included, and write them here as a HAR file"
+    )]
+    pub har: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        help = "Save the passed test cases, the fixtures and the account nonces here as the run progresses"
+    )]
+    pub checkpoint: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        requires = "checkpoint",
+        help = "Resume from --checkpoint, skipping the test cases that passed and reusing the fixtures"
+    )]
+    pub resume: bool,
+
+    #[arg(long, env, help = "Multiplier of the estimated fee of V1 and V2 transactions [default: 1.1]")]
+    pub fee_estimate_multiplier: Option<f64>,
+
+    #[arg(long, env, help = "Multiplier of the estimated gas of V3 transactions [default: 1.5]")]
+    pub gas_estimate_multiplier: Option<f64>,
+
+    #[arg(long, env, help = "Multiplier of the estimated gas price of V3 transactions [default: 1.5]")]
+    pub gas_price_estimate_multiplier: Option<f64>,
+
+    #[arg(long, env, help = "Max fee of every V1 and V2 transaction, instead of estimating it")]
+    pub max_fee: Option<Felt>,
+
+    #[arg(long, env, help = "Gas of every V3 transaction, instead of estimating it")]
+    pub gas: Option<u64>,
+
+    #[arg(long, env, help = "Gas price of every V3 transaction, instead of estimating it")]
+    pub gas_price: Option<u128>,
+
+    #[cfg(feature = "orchestrator")]
+    #[arg(long, env, value_enum, help = "Launch this node in docker, run the suites against it and remove it")]
+    pub launch_node: Option<NodeKind>,
+
+    #[cfg(feature = "orchestrator")]
+    #[arg(long, env, default_value = "latest", help = "Image tag of the launched node")]
+    pub node_version: String,
+
+    #[cfg(feature = "orchestrator")]
+    #[arg(long, env, help = "Image of the launched node, instead of the default one of its kind")]
+    pub node_image: Option<String>,
+
+    #[cfg(feature = "orchestrator")]
+    #[arg(
+        long,
+        env,
+        allow_hyphen_values = true,
+        help = "Space-separated arguments of the launched node, replacing the defaults of its kind",
+        value_delimiter = ' '
+    )]
+    pub node_args: Vec<String>,
+
+    #[cfg(feature = "orchestrator")]
+    #[arg(long, env, help = "Launch Anvil next to the devnet and relay the devnet's L1 messages to it")]
+    pub launch_l1: bool,
+
+    #[cfg(feature = "reexecution")]
+    #[arg(
+        long,
+        env,
+        help = "Re-execute the blocks of the run with t8n, compare the receipts and write the report here"
+    )]
+    pub reexecution_report: Option<PathBuf>,
+
+    #[cfg(feature = "aws_kms")]
+    #[arg(
+        long,
+        env,
+        help = "Id, ARN or alias of a secp256k1 or secp256r1 AWS KMS key to health-check before the suites run, \
+                with the credentials of the environment; the suites still sign with the paymaster private key"
+    )]
+    pub check_aws_kms_key_id: Option<String>,
+
+    #[cfg(feature = "gcp_kms")]
+    #[arg(
+        long,
+        env,
+        help = "Resource name of a secp256k1 or secp256r1 GCP Cloud KMS key version to health-check before the \
+                suites run; the suites still sign with the paymaster private key"
+    )]
+    pub check_gcp_kms_key_version: Option<String>,
+
+    #[cfg(feature = "gcp_kms")]
+    #[arg(long, env, requires = "check_gcp_kms_key_version", help = "Access token of the GCP Cloud KMS key")]
+    pub gcp_kms_access_token: Option<String>,
+
+    #[cfg(feature = "gcp_kms")]
+    #[arg(
+        long,
+        env,
+        requires = "check_gcp_kms_key_version",
+        conflicts_with = "gcp_kms_access_token",
+        help = "Command printing access tokens of the GCP Cloud KMS key, e.g. 'gcloud auth print-access-token', \
+                run again when a token is rejected [default: the metadata server]"
+    )]
+    pub gcp_kms_access_token_command: Option<String>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ListArgs {
+    #[arg(short, long, value_enum, help = "Suites to list the test cases of, all of them by default")]
+    pub suite: Vec<Suite>,
+
+    #[arg(
+        long,
+        env,
+        help = "Space-separated patterns; only test cases whose 'suite::name' contains one of them are listed",
+        value_delimiter = ' '
+    )]
+    pub filter: Vec<String>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct DiffArgs {
+    #[arg(
+        long,
+        env = "DIFF_URLS",
+        required = true,
+        help = "Comma-separated URLs of the nodes; the responses of the first one are the reference",
+        value_delimiter = ','
+    )]
+    pub urls: Vec<Url>,
+
+    #[arg(long, env, default_value_t = 10, help = "Number of the latest blocks all nodes have to compare")]
+    pub blocks: u64,
+
+    #[arg(
+        long,
+        env = "DIFF_NORMALIZE",
+        help = "Space-separated METHOD=NORMALIZER[,NORMALIZER...] replacing the normalizers applied to the responses \
+                of a method before comparing them ('*' for all methods); normalizers are hex, sort-events, \
+                sort-state-diff and strip:<field>",
+        value_delimiter = ' '
+    )]
+    pub normalize: Vec<MethodNormalizers>,
+
+    #[arg(long, env, help = "Write the differences here")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct MultiArgs {
+    #[arg(
+        long,
+        env = "MULTI_NETWORKS",
+        help = "Space-separated networks of --config to run against, all of them by default",
+        value_delimiter = ' '
+    )]
+    pub networks: Vec<String>,
+
+    #[arg(long, env, help = "Write the outcomes of the test cases here as JSON, keyed by network")]
+    pub combined_report: PathBuf,
+
+    #[arg(
+        long,
+        env,
+        help = "Write the outcomes of the test cases here as JUnit XML, with one testsuite per network and suite"
+    )]
+    pub combined_junit_report: Option<PathBuf>,
+
+    #[arg(last = true, help = "Options of the run passed on to the run against every network")]
+    pub run_args: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, clap::ValueEnum)]
@@ -37,3 +502,16 @@ pub enum Suite {
     KatanaNoFee,
     KatanaNoAccountValidation,
 }
+
+impl Suite {
+    /// Directory of the suite without the `suite_` prefix, as in the test case catalogue.
+    pub fn root_suite(&self) -> &'static str {
+        match self {
+            Suite::OpenRpc => "openrpc",
+            Suite::Katana => "katana",
+            Suite::KatanaNoMining => "katana_no_mining",
+            Suite::KatanaNoFee => "katana_no_fee",
+            Suite::KatanaNoAccountValidation => "katana_no_account_validation",
+        }
+    }
+}
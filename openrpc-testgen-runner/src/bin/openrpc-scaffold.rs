@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use openrpc_testgen::utils::scaffold::{check_scaffolding, generate_scaffolding, OpenRpcDocument, ScaffoldOptions};
+use tracing::{error, info, warn};
+
+/// Generates endpoint stubs and test case skeletons for the methods of the OpenRPC document that
+/// the provider or the suite do not cover yet.
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None, disable_version_flag = true)]
+struct Args {
+    #[arg(long, env, help = "Path to the OpenRPC document, e.g. starknet_api_openrpc.json of the spec release")]
+    spec: PathBuf,
+
+    #[arg(long, env, default_value = "openrpc-testgen/src/suite_openrpc", help = "Suite to scaffold test cases in")]
+    suite_dir: PathBuf,
+
+    #[arg(long, env, default_value = "openrpc-testgen/src/utils/v7/providers/provider.rs")]
+    provider_path: PathBuf,
+
+    #[arg(long, env, default_value = "openrpc-testgen/src/utils/v7/endpoints/scaffold.rs")]
+    endpoints_path: PathBuf,
+
+    #[arg(long, help = "Only report uncovered methods, failing if there are any")]
+    check: bool,
+}
+
+fn main() -> ExitCode {
+    tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).init();
+
+    let args = Args::parse();
+    let document = match OpenRpcDocument::from_file(&args.spec) {
+        Ok(document) => document,
+        Err(e) => {
+            error!("Could not read OpenRPC document {}: {}", args.spec.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let options = ScaffoldOptions {
+        provider_path: args.provider_path,
+        endpoints_path: args.endpoints_path,
+        suite_dir: args.suite_dir,
+    };
+
+    let result =
+        if args.check { check_scaffolding(&document, &options) } else { generate_scaffolding(&document, &options) };
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            error!("Scaffolding failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for method in &report.stubbed_methods {
+        warn!("{} is not implemented by the provider", method);
+    }
+    for method in &report.untested_methods {
+        warn!("{} is not called by any test in {}", method, options.suite_dir.display());
+    }
+    for path in &report.created_tests {
+        info!("Created test case skeleton {}", path.display());
+    }
+
+    if report.is_up_to_date() {
+        info!("All {} methods of the OpenRPC document {} are covered", document.methods.len(), document.info.version);
+        ExitCode::SUCCESS
+    } else if args.check {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,308 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::Parser;
+use openrpc_testgen::utils::{
+    network_registry::{network_registry, set_network_registry, Network, NetworkRegistry},
+    request_timing::{enable_request_timing, write_timing_report},
+    soak::{run_soak, SoakConfig},
+    v7::{
+        accounts::{
+            creation::helpers::get_chain_id,
+            single_owner::{ExecutionEncoding, SingleOwnerAccount},
+            top_up::{TopUpAccount, TopUpPolicy},
+            utils::{
+                faucet::{AnyFaucet, FaucetKind},
+                predeployed::predeployed_single_owner_accounts,
+            },
+        },
+        endpoints::utils::get_selector_from_name,
+        providers::jsonrpc::{transports::set_shared_client_config, HttpClientConfig, HttpTransport, JsonRpcClient},
+        signers::{key_pair::SigningKey, local_wallet::LocalWallet},
+    },
+    workload::{Workload, WorkloadConfig, WorkloadMix, WorkloadTargets},
+};
+use starknet_types_core::felt::Felt;
+use tracing::{error, info};
+use url::Url;
+
+/// Continuously submits a light transaction mix to a node for hours, periodically checking nonce,
+/// balance, block number and receipt invariants, for long running node stability testing.
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None, disable_version_flag = true)]
+struct Args {
+    #[arg(long, env, help = "URL of the L2 node")]
+    url: Url,
+
+    #[arg(
+        long,
+        env,
+        required_unless_present = "use_predeployed_accounts",
+        help = "Address of a funded account used by nothing else during the run"
+    )]
+    account_address: Option<Felt>,
+
+    #[arg(long, env, required_unless_present = "use_predeployed_accounts", help = "Private key of the account")]
+    private_key: Option<Felt>,
+
+    #[arg(
+        long,
+        env,
+        conflicts_with_all = ["account_address", "private_key"],
+        help = "Soak with the last predeployed account of the node, which the test suites don't pay with"
+    )]
+    use_predeployed_accounts: bool,
+
+    #[arg(long, env, default_value_t = 4 * 60 * 60, help = "How long to submit transactions for, in seconds")]
+    duration_secs: u64,
+
+    #[arg(long, env, default_value_t = 1000, help = "Pause between two transactions, in milliseconds")]
+    send_interval_ms: u64,
+
+    #[arg(long, env, default_value_t = 60, help = "Time between two invariant checks, in seconds")]
+    check_interval_secs: u64,
+
+    #[arg(long, env, default_value = "soak-report.json", help = "JSON report, rewritten after every check")]
+    report_path: PathBuf,
+
+    #[arg(
+        long,
+        env,
+        value_enum,
+        default_value = "devnet",
+        help = "Network the node runs, which the token and UDC addresses default to"
+    )]
+    network: Network,
+
+    #[arg(long, env, help = "STRK token address [default: of --network]")]
+    strk_address: Option<Felt>,
+
+    #[arg(long, env, help = "ETH token address [default: of --network]")]
+    eth_address: Option<Felt>,
+
+    #[arg(long, env, help = "Universal Deployer Contract address [default: of --network]")]
+    udc_address: Option<Felt>,
+
+    #[arg(
+        long,
+        env,
+        help = "Sends a workload generated with this mix instead of the default self-transfers, e.g. \
+                transfer=70,deploy=20,heavy-computation=10. Declares are not supported in soak mode"
+    )]
+    workload_mix: Option<WorkloadMix>,
+
+    #[arg(long, env, default_value_t = 0, help = "Seed of the generated workload")]
+    workload_seed: u64,
+
+    #[arg(long, env, default_value_t = 1000, help = "Transactions of the generated workload, sent in a loop")]
+    workload_length: usize,
+
+    #[arg(long, env, help = "Workload file to replay, or where the workload generated with --workload-mix is written")]
+    workload_path: Option<PathBuf>,
+
+    #[arg(long, env, help = "Class without constructor arguments deployed by the deploys of the workload")]
+    deploy_class_hash: Option<Felt>,
+
+    #[arg(long, env, help = "Contract called by the heavy computations of the workload")]
+    heavy_computation_address: Option<Felt>,
+
+    #[arg(
+        long,
+        env,
+        default_value = "write_storage",
+        help = "Entry point of the heavy computation contract, called with the number of iterations"
+    )]
+    heavy_computation_entry_point: String,
+
+    #[arg(long, env, value_enum, requires = "top_up_threshold", help = "Faucet the account is topped up from")]
+    faucet: Option<FaucetKind>,
+
+    #[arg(long, env, help = "URL of the faucet [default: --url, for the devnet faucet]")]
+    faucet_url: Option<Url>,
+
+    #[arg(long, env, help = "API key of the HTTP faucet")]
+    faucet_api_key: Option<String>,
+
+    #[arg(long, env, default_value = "x-api-key", help = "Header the API key of the HTTP faucet is sent in")]
+    faucet_api_key_header: String,
+
+    #[arg(
+        long,
+        env,
+        requires = "faucet",
+        help = "Top the account up from the faucet whenever a fee token balance drops below this, in wei and fri"
+    )]
+    top_up_threshold: Option<u128>,
+
+    #[arg(
+        long,
+        env,
+        default_value_t = 100_000_000_000_000_000_000,
+        help = "Amount of a fee token requested on each top-up, in wei and fri"
+    )]
+    top_up_amount: u128,
+
+    #[arg(
+        long,
+        env,
+        help = "Record the time of every request by method, split into serialization, connection and wait for \
+                the first byte, download and deserialization, and of DNS lookups by host, and write it here"
+    )]
+    timing_report: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).init();
+
+    let args = Args::parse();
+    if args.timing_report.is_some() {
+        let client_config = HttpClientConfig { time_dns_lookups: true, ..Default::default() };
+        if let Err(e) = set_shared_client_config(&client_config) {
+            error!("Could not build the HTTP client: {}", e);
+            return ExitCode::FAILURE;
+        }
+        enable_request_timing();
+    }
+    let network = NetworkRegistry::new(args.network);
+    set_network_registry(NetworkRegistry {
+        strk_address: args.strk_address.unwrap_or(network.strk_address),
+        eth_address: args.eth_address.unwrap_or(network.eth_address),
+        udc_address: args.udc_address.unwrap_or(network.udc_address),
+        ..network
+    });
+    let registry = network_registry();
+
+    let account = match (args.account_address, args.private_key) {
+        _ if args.use_predeployed_accounts => match predeployed_single_owner_accounts(args.url.clone()).await {
+            Ok(mut accounts) => match accounts.pop() {
+                Some(account) => account,
+                None => {
+                    error!("{} has no predeployed accounts", args.url);
+                    return ExitCode::FAILURE;
+                }
+            },
+            Err(e) => {
+                error!("Could not get the predeployed accounts of {}: {}", args.url, e);
+                return ExitCode::FAILURE;
+            }
+        },
+        (Some(account_address), Some(private_key)) => {
+            let provider = JsonRpcClient::new(HttpTransport::new(args.url.clone()));
+            let chain_id = match get_chain_id(&provider).await {
+                Ok(chain_id) => chain_id,
+                Err(e) => {
+                    error!("Could not get the chain id of {}: {}", args.url, e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            SingleOwnerAccount::new(
+                provider,
+                LocalWallet::from(SigningKey::from_secret_scalar(private_key)),
+                account_address,
+                chain_id,
+                ExecutionEncoding::New,
+            )
+        }
+        _ => {
+            error!("--account-address and --private-key are required without --use-predeployed-accounts");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let workload = match (args.workload_mix, &args.workload_path) {
+        (Some(mix), path) => {
+            let workload = Workload::generate(WorkloadConfig {
+                seed: args.workload_seed,
+                length: args.workload_length,
+                mix,
+                declarable_classes: 0,
+            });
+            if let Some(path) = path {
+                if let Err(e) = workload.write(path) {
+                    error!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
+            Some(workload)
+        }
+        (None, Some(path)) => match Workload::read(path) {
+            Ok(workload) => Some(workload),
+            Err(e) => {
+                error!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        (None, None) => None,
+    };
+    let mut workload_targets = WorkloadTargets::new(registry.strk_address, registry.udc_address);
+    workload_targets.deploy_class_hash = args.deploy_class_hash;
+    if let Some(address) = args.heavy_computation_address {
+        match get_selector_from_name(&args.heavy_computation_entry_point) {
+            Ok(selector) => workload_targets.heavy_computation = Some((address, selector)),
+            Err(e) => {
+                error!("Invalid heavy computation entry point {}: {}", args.heavy_computation_entry_point, e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let top_up = match (args.faucet, args.top_up_threshold) {
+        (Some(kind), Some(threshold)) => {
+            let url = match (args.faucet_url, kind) {
+                (Some(url), _) => url,
+                (None, FaucetKind::Devnet) => args.url.clone(),
+                (None, FaucetKind::Http) => {
+                    error!("--faucet http requires --faucet-url");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let faucet = AnyFaucet::new(kind, url, args.faucet_api_key, args.faucet_api_key_header);
+            Some(
+                TopUpAccount::new(account.clone(), faucet)
+                    .with_policy(TopUpPolicy::strk(registry.strk_address, threshold, args.top_up_amount))
+                    .with_policy(TopUpPolicy::eth(registry.eth_address, threshold, args.top_up_amount)),
+            )
+        }
+        _ => None,
+    };
+
+    let config = SoakConfig {
+        duration: Duration::from_secs(args.duration_secs),
+        send_interval: Duration::from_millis(args.send_interval_ms),
+        check_interval: Duration::from_secs(args.check_interval_secs),
+        report_path: args.report_path,
+        strk_address: registry.strk_address,
+        eth_address: registry.eth_address,
+        workload,
+        workload_targets,
+        top_up,
+    };
+
+    let result = run_soak(&account, &config).await;
+    write_timing_report(args.timing_report.as_deref());
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            error!("Soak run aborted: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    info!(
+        "Soak run finished after {}s: {} transactions sent, {} failed, {} top-ups, {} checks, report in {}",
+        report.elapsed_secs,
+        report.transactions_sent,
+        report.transactions_failed,
+        report.top_ups,
+        report.checks_run,
+        config.report_path.display()
+    );
+    if report.violations.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        error!("{} invariant violations, see the report", report.violations.len());
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,227 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use openrpc_testgen::utils::{
+    chain_id::encode_chain_name,
+    network_registry::{network_registry, set_network_registry, Network, NetworkRegistry},
+    request_timing::{enable_request_timing, write_timing_report},
+    v7::{
+        accounts::single_owner::{ExecutionEncoding, SingleOwnerAccount},
+        endpoints::{declare_contract::get_compiled_contract, utils::get_selector_from_name},
+        providers::jsonrpc::{transports::set_shared_client_config, HttpClientConfig, HttpTransport, JsonRpcClient},
+        signers::{key_pair::SigningKey, local_wallet::LocalWallet},
+    },
+    workload::{write_t8n_transactions, Workload, WorkloadConfig, WorkloadMix, WorkloadTargets},
+};
+use starknet_types_core::felt::Felt;
+use tracing::{error, info};
+use url::Url;
+
+/// Node the signing account is built with. Signing doesn't contact the node, it is never used.
+const UNUSED_NODE_URL: &str = "http://127.0.0.1:5050";
+
+/// Generates a deterministic synthetic workload, or reads a generated one, and signs it offline
+/// into a transaction file for t8n.
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None, disable_version_flag = true)]
+struct Args {
+    #[arg(
+        long,
+        env,
+        help = "Mix of the generated workload, e.g. transfer=70,deploy=10,declare=5,heavy-computation=15"
+    )]
+    workload_mix: Option<WorkloadMix>,
+
+    #[arg(long, env, default_value_t = 0, help = "Seed of the generated workload")]
+    workload_seed: u64,
+
+    #[arg(long, env, default_value_t = 100, help = "Transactions of the generated workload")]
+    workload_length: usize,
+
+    #[arg(long, env, help = "Workload file to sign, or where the workload generated with --workload-mix is written")]
+    workload_path: Option<PathBuf>,
+
+    #[arg(long, env, help = "Where the signed transactions are written, as t8n --txns-path input")]
+    t8n_txns_path: PathBuf,
+
+    #[arg(long, env, help = "Address of the account sending the transactions, e.g. a predeployed t8n account")]
+    account_address: Felt,
+
+    #[arg(long, env, help = "Private key of the account")]
+    private_key: Felt,
+
+    #[arg(long, env, help = "Chain id the transactions are signed for [default: of --network]")]
+    chain_id: Option<Felt>,
+
+    #[arg(long, env, default_value_t = Felt::ZERO, help = "Nonce of the first transaction")]
+    nonce: Felt,
+
+    #[arg(long, env, default_value_t = 1_000_000, help = "L1 gas bound of every transaction")]
+    gas: u64,
+
+    #[arg(long, env, default_value_t = 100_000_000_000, help = "L1 gas price bound of every transaction, in fri")]
+    gas_price: u128,
+
+    #[arg(
+        long,
+        env,
+        value_enum,
+        default_value = "devnet",
+        help = "Network the transactions target, which the token and UDC addresses default to"
+    )]
+    network: Network,
+
+    #[arg(long, env, help = "STRK token address [default: of --network]")]
+    strk_address: Option<Felt>,
+
+    #[arg(long, env, help = "Universal Deployer Contract address [default: of --network]")]
+    udc_address: Option<Felt>,
+
+    #[arg(long, env, help = "Class without constructor arguments deployed by the deploys of the workload")]
+    deploy_class_hash: Option<Felt>,
+
+    #[arg(long, env, value_delimiter = ' ', help = "Sierra artifacts of the classes declared by the workload")]
+    declare_sierra_paths: Vec<PathBuf>,
+
+    #[arg(
+        long,
+        env,
+        value_delimiter = ' ',
+        help = "CASM artifacts of the classes declared by the workload, in the order of --declare-sierra-paths"
+    )]
+    declare_casm_paths: Vec<PathBuf>,
+
+    #[arg(long, env, help = "Contract called by the heavy computations of the workload")]
+    heavy_computation_address: Option<Felt>,
+
+    #[arg(
+        long,
+        env,
+        default_value = "write_storage",
+        help = "Entry point of the heavy computation contract, called with the number of iterations"
+    )]
+    heavy_computation_entry_point: String,
+
+    #[arg(
+        long,
+        env,
+        help = "Record the time of every request by method, split into serialization, connection and wait for \
+                the first byte, download and deserialization, and of DNS lookups by host, and write it here"
+    )]
+    timing_report: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).init();
+
+    let args = Args::parse();
+    if args.timing_report.is_some() {
+        let client_config = HttpClientConfig { time_dns_lookups: true, ..Default::default() };
+        if let Err(e) = set_shared_client_config(&client_config) {
+            error!("Could not build the HTTP client: {}", e);
+            return ExitCode::FAILURE;
+        }
+        enable_request_timing();
+    }
+    if args.declare_sierra_paths.len() != args.declare_casm_paths.len() {
+        error!("Every --declare-sierra-paths artifact needs a matching --declare-casm-paths artifact");
+        return ExitCode::FAILURE;
+    }
+
+    let network = NetworkRegistry::new(args.network);
+    set_network_registry(NetworkRegistry {
+        strk_address: args.strk_address.unwrap_or(network.strk_address),
+        udc_address: args.udc_address.unwrap_or(network.udc_address),
+        ..network
+    });
+
+    let chain_id = match args.chain_id.map_or_else(|| encode_chain_name(&network_registry().chain_name), Ok) {
+        Ok(chain_id) => chain_id,
+        Err(e) => {
+            error!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut targets = WorkloadTargets::new(network_registry().strk_address, network_registry().udc_address);
+    targets.deploy_class_hash = args.deploy_class_hash;
+    for (sierra_path, casm_path) in args.declare_sierra_paths.iter().zip(&args.declare_casm_paths) {
+        match get_compiled_contract(sierra_path.clone(), casm_path.clone()).await {
+            Ok(class) => targets.declare_classes.push(class),
+            Err(e) => {
+                error!("Could not read class {}: {}", sierra_path.display(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    if let Some(address) = args.heavy_computation_address {
+        match get_selector_from_name(&args.heavy_computation_entry_point) {
+            Ok(selector) => targets.heavy_computation = Some((address, selector)),
+            Err(e) => {
+                error!("Invalid heavy computation entry point {}: {}", args.heavy_computation_entry_point, e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let workload = match (args.workload_mix, &args.workload_path) {
+        (Some(mix), path) => {
+            let workload = Workload::generate(WorkloadConfig {
+                seed: args.workload_seed,
+                length: args.workload_length,
+                mix,
+                declarable_classes: targets.declare_classes.len(),
+            });
+            if let Some(path) = path {
+                if let Err(e) = workload.write(path) {
+                    error!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
+            workload
+        }
+        (None, Some(path)) => match Workload::read(path) {
+            Ok(workload) => workload,
+            Err(e) => {
+                error!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        (None, None) => {
+            error!("Either --workload-mix or --workload-path is required");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let provider = match Url::parse(UNUSED_NODE_URL) {
+        Ok(url) => JsonRpcClient::new(HttpTransport::new(url)),
+        Err(e) => {
+            error!("Invalid node url {}: {}", UNUSED_NODE_URL, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let account = SingleOwnerAccount::new(
+        provider,
+        LocalWallet::from(SigningKey::from_secret_scalar(args.private_key)),
+        args.account_address,
+        chain_id,
+        ExecutionEncoding::New,
+    );
+    let transactions = match workload.t8n_transactions(&account, &targets, args.nonce, args.gas, args.gas_price).await {
+        Ok(transactions) => transactions,
+        Err(e) => {
+            error!("Could not sign the workload: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    write_timing_report(args.timing_report.as_deref());
+    if let Err(e) = write_t8n_transactions(&args.t8n_txns_path, &transactions) {
+        error!("{}", e);
+        return ExitCode::FAILURE;
+    }
+
+    info!("Signed {} workload transactions into {}", transactions.len(), args.t8n_txns_path.display());
+    ExitCode::SUCCESS
+}
@@ -1,5 +1,17 @@
-use args::{Args, Suite};
-use clap::Parser;
+use args::{Args, Cli, Command, DiffArgs, ListArgs, MultiArgs, Suite};
+use clap::{Parser, ValueEnum};
+#[cfg(feature = "orchestrator")]
+use openrpc_testgen::utils::orchestrator::{L1Stack, L1StackSpec, NodeKind, NodeSpec, OrchestratorError, RunningNode};
+#[cfg(feature = "reexecution")]
+use openrpc_testgen::utils::reexecution::reexecute_blocks;
+#[cfg(feature = "aws_kms")]
+use openrpc_testgen::utils::v7::signers::aws_kms::AwsKmsSigner;
+#[cfg(feature = "gcp_kms")]
+use openrpc_testgen::utils::v7::signers::gcp_kms::{
+    CommandAccessToken, GcpKmsSigner, MetadataServerAccessToken, StaticAccessToken,
+};
+#[cfg(any(feature = "aws_kms", feature = "gcp_kms"))]
+use openrpc_testgen::utils::v7::signers::{kms::KmsSignerError, signer::RawSigner};
 #[allow(unused_imports)]
 use openrpc_testgen::{
     suite_katana::{SetupInput as SetupInputKatana, TestSuiteKatana},
@@ -9,31 +21,618 @@ use openrpc_testgen::{
     suite_katana_no_fee::{SetupInput as SetupInputKatanaNoFee, TestSuiteKatanaNoFee},
     suite_katana_no_mining::{SetupInput as SetupInputKatanaNoMining, TestSuiteKatanaNoMining},
     suite_openrpc::{SetupInput, TestSuiteOpenRpc},
+    utils::{
+        account_pool::{set_pool_top_up, PoolTopUp},
+        checkpoint::{record_nonce, set_checkpoint},
+        class_verification::{class_verification_report, enable_class_verification},
+        config::{load_config, load_config_networks, load_network_config},
+        declared_classes::{declared_classes_report, enable_declared_class_registry},
+        dry_run::enable_dry_run,
+        fee_accuracy::{enable_fee_accuracy_tracking, fee_accuracy_report},
+        fee_overrides::{set_fee_overrides, FeeOverrides},
+        fee_search::{enable_fee_search, fee_search_report},
+        finality::{finality_report, set_finality_config, FinalityConfig},
+        inclusion_latency::{enable_inclusion_latency_tracking, inclusion_latency_report},
+        log_capture::LogCaptureLayer,
+        mempool_ordering::{enable_ordering_tracking, ordering_report},
+        network_registry::{network_registry, set_network_registry, NetworkRegistry},
+        network_runs::{CombinedReport, NetworkRun},
+        node_diff::diff_nodes,
+        random_single_owner_account::set_account_weights,
+        request_timing::{enable_request_timing, write_timing_report},
+        resilience::{set_resilience_config, ResilienceConfig, RestartHook},
+        resource_limits::{resource_limits_report, set_resource_limits_config, ResourceLimitsConfig},
+        response_normalizers::ResponseNormalizers,
+        response_sizes::{enable_response_size_tracking, response_size_report},
+        secrets::{register_secret, RedactingWriter},
+        snapshot::{set_snapshot_config, SnapshotConfig},
+        spec_version::{detect_spec_version, RpcSpecVersion},
+        test_filter::{selected_test_cases, set_test_filter},
+        test_report::{
+            enable_test_report, read_json_report, record_test_case, test_case_results, write_json_report,
+            write_junit_report, TestOutcome,
+        },
+        traffic_capture::{enable_traffic_capture, traffic_capture},
+        v7::{
+            accounts::{
+                top_up::TopUpPolicy,
+                utils::{
+                    faucet::{AnyFaucet, Faucet, FaucetKind},
+                    predeployed::fetch_predeployed_accounts,
+                },
+            },
+            endpoints::test_rpc_endpoints_v0_0_7,
+            providers::{
+                jsonrpc::{transports::set_shared_client_config, HttpClientConfig, HttpTransport, JsonRpcClient},
+                provider::Provider,
+            },
+        },
+    },
     RunnableTrait,
 };
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag, PriceUnit};
 use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
 use tracing::{error, info};
+use tracing_subscriber::{filter::LevelFilter, prelude::*};
 pub mod args;
 
+const AMOUNT_PER_TEST: Felt = Felt::from_hex_unchecked("0xfffffffffffffff");
+const SAMPLE_CONTRACT_1_SIERRA_PATH: &str =
+    "target/dev/contracts_contracts_sample_contract_1_HelloStarknet.contract_class.json";
+const SAMPLE_CONTRACT_1_CASM_PATH: &str =
+    "target/dev/contracts_contracts_sample_contract_1_HelloStarknet.compiled_contract_class.json";
+const SAMPLE_CONTRACT_2_SIERRA_PATH: &str =
+    "target/dev/contracts_contracts_sample_contract_2_HelloStarknet.contract_class.json";
+const SAMPLE_CONTRACT_2_CASM_PATH: &str =
+    "target/dev/contracts_contracts_sample_contract_2_HelloStarknet.compiled_contract_class.json";
+
+/// Node launched for the run by the orchestrator, alone or with an L1 to relay its messages to.
+#[cfg(feature = "orchestrator")]
+enum LaunchedTarget {
+    Node(RunningNode),
+    L1Stack(L1Stack),
+}
+
+#[cfg(feature = "orchestrator")]
+impl LaunchedTarget {
+    fn url(&self) -> url::Url {
+        match self {
+            LaunchedTarget::Node(node) => node.url.clone(),
+            LaunchedTarget::L1Stack(stack) => stack.devnet.url.clone(),
+        }
+    }
+
+    async fn teardown(self) -> Result<(), OrchestratorError> {
+        match self {
+            LaunchedTarget::Node(node) => node.teardown().await,
+            LaunchedTarget::L1Stack(stack) => stack.teardown().await,
+        }
+    }
+}
+
 #[tokio::main]
-#[allow(unused_variables, unused_mut)]
 async fn main() {
-    tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).init();
+    // Logs go to stdout, and those of every test case into its entry of the report as well.
+    tracing_subscriber::registry()
+        .with(LevelFilter::INFO)
+        .with(tracing_subscriber::fmt::layer().with_writer(|| RedactingWriter::new(std::io::stdout())))
+        .with(LogCaptureLayer)
+        .init();
+
+    // Options of the config file are passed on as environment variables, so that the command line
+    // and the environment take precedence over them.
+    let mut config_variables = Vec::new();
+    if let Some(path) = pre_parsed_option("--config", "CONFIG").map(PathBuf::from) {
+        let options = match pre_parsed_option("--config-network", "CONFIG_NETWORK") {
+            Some(network) => load_network_config(&path, &network.to_string_lossy()),
+            None => load_config(&path),
+        };
+        match options {
+            Ok(options) => {
+                for option in options {
+                    if option.secret {
+                        register_secret(&option.value);
+                    }
+                    if std::env::var_os(option.env_name()).is_none() {
+                        std::env::set_var(option.env_name(), &option.value);
+                        config_variables.push(option.env_name());
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Could not load config {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run(args) => run(*args).await,
+        Command::List(args) => list(args),
+        Command::Diff(args) => diff(args).await,
+        Command::Multi(args) => multi(cli.config, args, &config_variables),
+    }
+}
+
+/// `--config` and `--config-network` are read before the other options are parsed, as they provide
+/// their values.
+fn pre_parsed_option(name: &str, env_name: &str) -> Option<OsString> {
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+        if let Some(value) = arg.to_str().and_then(|arg| arg.strip_prefix(name)?.strip_prefix('=')) {
+            return Some(OsString::from(value));
+        }
+    }
+    std::env::var_os(env_name)
+}
+
+/// Prints the `suite::name` of the selected test cases, one per line.
+fn list(args: ListArgs) {
+    set_test_filter(args.filter);
+    let suites = if args.suite.is_empty() { Suite::value_variants().to_vec() } else { args.suite };
+    for suite in suites {
+        for entry in selected_test_cases(suite.root_suite()) {
+            println!("{}::{}", entry.suite, entry.name);
+        }
+    }
+}
+
+/// Runs the suites against every selected network of the config file, each in a run of its own
+/// process, and combines the outcomes of their test cases. `config_variables` are the variables set
+/// from the shared options of the file, which must not take precedence over those of a network.
+fn multi(config: Option<PathBuf>, args: MultiArgs, config_variables: &[String]) {
+    let Some(config) = config else {
+        error!("multi runs the suites against the networks of --config, which is not set");
+        std::process::exit(1);
+    };
+    let networks = if args.networks.is_empty() {
+        match load_config_networks(&config) {
+            Ok(networks) => networks,
+            Err(e) => {
+                error!("Could not load config {}: {}", config.display(), e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        args.networks
+    };
+    if networks.is_empty() {
+        error!("Config {} defines no networks to run against", config.display());
+        std::process::exit(1);
+    }
+    let runner = match std::env::current_exe() {
+        Ok(runner) => runner,
+        Err(e) => {
+            error!("Could not find the runner executable: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut report = CombinedReport::default();
+    for network in networks {
+        info!("Running the suites against {}", network);
+        let results_path =
+            std::env::temp_dir().join(format!("openrpc-testgen-{}-{}.json", std::process::id(), network));
+        let mut command = std::process::Command::new(&runner);
+        for variable in config_variables {
+            command.env_remove(variable);
+        }
+        let exit_code = match command
+            .arg("--config")
+            .arg(&config)
+            .arg("--config-network")
+            .arg(&network)
+            .arg("run")
+            .arg("--json-report")
+            .arg(&results_path)
+            .args(&args.run_args)
+            .status()
+        {
+            Ok(status) => status.code(),
+            Err(e) => {
+                error!("Could not run the suites against {}: {}", network, e);
+                None
+            }
+        };
+        let results = match read_json_report(&results_path) {
+            Ok(results) => results,
+            Err(e) => {
+                error!("Could not read the outcomes of the run against {}: {}", network, e);
+                Vec::new()
+            }
+        };
+        let _ = std::fs::remove_file(&results_path);
+        report.networks.insert(network, NetworkRun::new(exit_code, results));
+    }
+
+    report.log_summary();
+    if let Err(e) = report.write(&args.combined_report) {
+        error!("Could not write combined report {}: {}", args.combined_report.display(), e);
+    }
+    if let Some(path) = &args.combined_junit_report {
+        if let Err(e) = report.write_junit(path) {
+            error!("Could not write combined JUnit report {}: {}", path.display(), e);
+        }
+    }
+    std::process::exit(if report.is_failure() { 1 } else { 0 });
+}
+
+async fn diff(args: DiffArgs) {
+    let mut normalizers = ResponseNormalizers::default();
+    for method_normalizers in args.normalize {
+        normalizers.set(method_normalizers);
+    }
+    match diff_nodes(&args.urls, args.blocks, &normalizers).await {
+        Ok(report) => {
+            report.log_summary();
+            if let Some(path) = &args.output {
+                if let Err(e) = report.write(path) {
+                    error!("Could not write node differences {}: {}", path.display(), e);
+                }
+            }
+            std::process::exit(if report.differences.is_empty() { 0 } else { 1 });
+        }
+        Err(e) => {
+            error!("Could not compare the nodes: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
 
-    let args = Args::parse();
+/// Faucet of the run options, the devnet one minting on the first node unless `--faucet-url` is set.
+fn faucet(args: &Args) -> Result<Option<AnyFaucet>, String> {
+    let Some(kind) = args.faucet else {
+        return Ok(None);
+    };
+    let url = match (&args.faucet_url, kind) {
+        (Some(url), _) => url.clone(),
+        (None, FaucetKind::Devnet) => args.urls[0].clone(),
+        (None, FaucetKind::Http) => return Err("--faucet http requires --faucet-url".to_string()),
+    };
+    Ok(Some(AnyFaucet::new(kind, url, args.faucet_api_key.clone(), args.faucet_api_key_header.clone())))
+}
+
+/// Address and private key of the paymaster account, the first predeployed account of the first
+/// node with `--use-predeployed-accounts`.
+async fn paymaster(args: &Args) -> Result<(Felt, Felt), String> {
+    if !args.use_predeployed_accounts {
+        return args
+            .paymaster_account_address
+            .zip(args.paymaster_private_key)
+            .ok_or_else(|| "--paymaster-account-address and --paymaster-private-key are required".to_string());
+    }
+    let accounts = fetch_predeployed_accounts(args.urls[0].clone())
+        .await
+        .map_err(|e| format!("Could not fetch the predeployed accounts of {}: {}", args.urls[0], e))?;
+    accounts
+        .into_iter()
+        .find_map(|account| account.private_key.map(|private_key| (account.address, private_key)))
+        .ok_or_else(|| format!("{} has no predeployed account with a private key", args.urls[0]))
+}
+
+#[allow(unused_variables, unused_mut)]
+async fn run(mut args: Args) {
+    // A predeployed paymaster key is registered as soon as it is fetched, below.
+    if let Some(private_key) = args.paymaster_private_key {
+        register_secret(&format!("{:#x}", private_key));
+    }
+    if let Some(password) = args.proxy.as_ref().and_then(url::Url::password) {
+        register_secret(password);
+    }
+    if let Some(api_key) = &args.faucet_api_key {
+        register_secret(api_key);
+    }
+    // Tokens printed by --gcp-kms-access-token-command are registered by the signer as they come.
+    #[cfg(feature = "gcp_kms")]
+    if let Some(token) = &args.gcp_kms_access_token {
+        register_secret(token);
+    }
+    // Every provider of the run shares this client and its connection pool.
+    let client_config = HttpClientConfig {
+        pool_max_idle_per_host: args.http_pool_max_idle_per_host,
+        pool_idle_timeout: args.http_pool_idle_timeout_secs.map(Duration::from_secs),
+        tcp_keepalive: args.http_tcp_keepalive_secs.map(Duration::from_secs),
+        http2_prior_knowledge: args.http2_prior_knowledge,
+        timeout: args.http_timeout_secs.map(Duration::from_secs),
+        connect_timeout: args.http_connect_timeout_secs.map(Duration::from_secs),
+        time_dns_lookups: args.timing_report.is_some(),
+        proxy: args.proxy.clone(),
+    };
+    if let Err(e) = set_shared_client_config(&client_config) {
+        error!("Could not build the HTTP client: {}", e);
+        std::process::exit(1);
+    }
+    #[cfg(feature = "orchestrator")]
+    let launched_target = match args.launch_node {
+        Some(kind) => {
+            let spec = NodeSpec {
+                image: args.node_image.clone(),
+                args: (!args.node_args.is_empty()).then(|| args.node_args.clone()),
+                ..NodeSpec::new(kind, args.node_version.clone())
+            };
+            if args.launch_l1 && kind != NodeKind::Devnet {
+                error!("--launch-l1 relays messages through the devnet postman, it requires --launch-node devnet");
+                std::process::exit(1);
+            }
+            let launched = if args.launch_l1 {
+                L1Stack::launch(&L1StackSpec { devnet: spec, ..L1StackSpec::new(args.node_version.clone()) })
+                    .await
+                    .map(LaunchedTarget::L1Stack)
+            } else {
+                RunningNode::launch(&spec).await.map(LaunchedTarget::Node)
+            };
+            match launched {
+                Ok(target) => {
+                    args.urls = vec![target.url()];
+                    Some(target)
+                }
+                Err(e) => {
+                    error!("Could not launch {:?} node: {}", kind, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => None,
+    };
+    let (paymaster_account_address, paymaster_private_key) = match paymaster(&args).await {
+        Ok(paymaster) => paymaster,
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if args.use_predeployed_accounts {
+        register_secret(&format!("{:#x}", paymaster_private_key));
+    }
+
+    #[cfg(feature = "aws_kms")]
+    if let Some(key_id) = &args.check_aws_kms_key_id {
+        check_kms_signer(key_id, AwsKmsSigner::from_env(key_id.as_str()).await).await;
+    }
+    #[cfg(feature = "gcp_kms")]
+    if let Some(key_version) = &args.check_gcp_kms_key_version {
+        match (&args.gcp_kms_access_token, &args.gcp_kms_access_token_command) {
+            (Some(token), _) => {
+                let signer = GcpKmsSigner::new(key_version.as_str(), StaticAccessToken(token.clone())).await;
+                check_kms_signer(key_version, signer).await;
+            }
+            (None, Some(command)) => {
+                let signer = GcpKmsSigner::new(key_version.as_str(), CommandAccessToken(command.clone())).await;
+                check_kms_signer(key_version, signer).await;
+            }
+            (None, None) => {
+                let signer = GcpKmsSigner::new(key_version.as_str(), MetadataServerAccessToken).await;
+                check_kms_signer(key_version, signer).await;
+            }
+        }
+    }
+
+    set_test_filter(args.filter.clone());
+    if !args.node_weights.is_empty() {
+        if args.node_weights.len() != args.urls.len() || args.node_weights.iter().all(|weight| *weight == 0) {
+            error!("--node-weights needs one weight for each of --urls, and one of them above 0");
+            std::process::exit(1);
+        }
+        set_account_weights(args.node_weights.clone());
+    }
+    let network = NetworkRegistry::new(args.network);
+    set_network_registry(NetworkRegistry {
+        chain_name: args.expected_chain_name.clone().unwrap_or(network.chain_name),
+        strk_address: args.strk_address.unwrap_or(network.strk_address),
+        eth_address: args.eth_address.unwrap_or(network.eth_address),
+        udc_address: args.udc_address.unwrap_or(network.udc_address),
+        default_account_class_hash: args.account_class_hash.unwrap_or(network.default_account_class_hash),
+        sequencer_address: args.sequencer_address.unwrap_or(network.sequencer_address),
+        ..network
+    });
+    let account_class_hash = network_registry().default_account_class_hash;
+    set_snapshot_config(SnapshotConfig { dir: args.snapshot_dir.clone(), update: args.update_snapshots });
+    if args.resilience || args.restart_container.is_some() {
+        set_resilience_config(ResilienceConfig {
+            max_downtime: Duration::from_secs(args.max_downtime_secs),
+            restart_hook: args
+                .restart_container
+                .clone()
+                .map(|container| RestartHook { container, before_cases: args.restart_before.clone() }),
+            ..ResilienceConfig::new(args.urls.clone())
+        });
+    }
+    let default_fees = FeeOverrides::default();
+    set_fee_overrides(FeeOverrides {
+        fee_estimate_multiplier: args.fee_estimate_multiplier.unwrap_or(default_fees.fee_estimate_multiplier),
+        gas_estimate_multiplier: args.gas_estimate_multiplier.unwrap_or(default_fees.gas_estimate_multiplier),
+        gas_price_estimate_multiplier: args
+            .gas_price_estimate_multiplier
+            .unwrap_or(default_fees.gas_price_estimate_multiplier),
+        max_fee: args.max_fee,
+        gas: args.gas,
+        gas_price: args.gas_price,
+    });
+    if args.fee_accuracy_report.is_some() {
+        enable_fee_accuracy_tracking();
+    }
+    if args.latency_report.is_some() {
+        enable_inclusion_latency_tracking();
+    }
+    if args.class_verification_report.is_some() {
+        enable_class_verification();
+    }
+    if args.declared_classes_report.is_some() {
+        enable_declared_class_registry();
+    }
+    if args.finality_report.is_some() {
+        set_finality_config(FinalityConfig {
+            timeout: Duration::from_secs(args.finality_timeout_secs),
+            poll_interval: Duration::from_secs(10),
+            simulated_settlement: args.simulate_settlement.then(|| args.urls[0].clone()),
+        });
+    }
+    if args.ordering_report.is_some() {
+        enable_ordering_tracking();
+    }
+    if args.fee_search_report.is_some() {
+        enable_fee_search();
+    }
+    if args.response_size_report.is_some() {
+        enable_response_size_tracking();
+    }
+    if args.timing_report.is_some() {
+        enable_request_timing();
+    }
+    if args.har.is_some() {
+        enable_traffic_capture();
+    }
+    if args.resource_limits_report.is_some() {
+        set_resource_limits_config(ResourceLimitsConfig {
+            start: args.resource_limits_start,
+            max: args.resource_limits_max,
+        });
+    }
+    if args.dry_run {
+        enable_dry_run();
+    }
+    if let Some(path) = &args.checkpoint {
+        if let Err(e) = set_checkpoint(path.clone(), args.resume) {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+        record_paymaster_nonce(&args.urls[0], paymaster_account_address).await;
+    }
+    if args.report.is_some() || args.json_report.is_some() {
+        enable_test_report();
+    }
     let mut failed_tests: HashMap<String, HashMap<String, String>> = HashMap::new(); // Suite -> {TestName -> ErrorMessage}
 
+    // A public network must serve the wallet account classes, or the node runs another network.
+    if !network_registry().account_classes.is_empty() {
+        let provider = JsonRpcClient::new(HttpTransport::new(args.urls[0].clone()));
+        let failures = network_registry().verify_classes(&provider).await;
+        if !failures.is_empty() {
+            for failure in &failures {
+                error!("{}", failure);
+            }
+            error!("The node doesn't serve the account classes of {:?}, check --network", args.network);
+            std::process::exit(1);
+        }
+        info!("The node serves the {} account classes of {:?}", network_registry().account_classes.len(), args.network);
+    }
+    match faucet(&args) {
+        Ok(Some(faucet)) => {
+            for unit in [PriceUnit::Fri, PriceUnit::Wei] {
+                match faucet.fund(paymaster_account_address, args.faucet_amount, unit).await {
+                    Ok(()) => info!("💧 Funded the paymaster with {} {:?}", args.faucet_amount, unit),
+                    Err(e) => error!("Could not fund the paymaster with {:?}: {}", unit, e),
+                }
+            }
+            if let Some(threshold) = args.top_up_threshold {
+                let registry = network_registry();
+                set_pool_top_up(PoolTopUp {
+                    faucet,
+                    policies: vec![
+                        TopUpPolicy::strk(registry.strk_address, threshold, args.faucet_amount),
+                        TopUpPolicy::eth(registry.eth_address, threshold, args.faucet_amount),
+                    ],
+                });
+            }
+        }
+        Ok(None) if !network_registry().faucets.is_empty() => {
+            info!("The paymaster can be funded at {}", network_registry().faucets.join(", "));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    }
+
+    // Classes declared from here on land in the blocks after the current one.
+    let first_declaration_block = match &args.declared_classes_report {
+        Some(_) => match JsonRpcClient::new(HttpTransport::new(args.urls[0].clone())).block_number().await {
+            Ok(block_number) => Some(block_number + 1),
+            Err(e) => {
+                error!("Could not get the block number to check declared classes from: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    // Blocks produced from here on are re-executed after the run.
+    #[cfg(feature = "reexecution")]
+    let first_reexecuted_block = match &args.reexecution_report {
+        Some(_) => match JsonRpcClient::new(HttpTransport::new(args.urls[0].clone())).block_number().await {
+            Ok(block_number) => Some(block_number + 1),
+            Err(e) => {
+                error!("Could not get the block number to re-execute from: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
     for suite in args.suite {
         match suite {
             Suite::OpenRpc => {
                 #[cfg(feature = "openrpc")]
                 {
+                    match detect_spec_version(args.urls[0].clone()).await {
+                        Ok(RpcSpecVersion::V0_7) => {
+                            info!("Node reports spec version 0.7, running v0.7 endpoint checks");
+                            for block_selector in args.block_selector.iter().copied() {
+                                let started = std::time::Instant::now();
+                                let result = test_rpc_endpoints_v0_0_7(
+                                    args.urls[0].clone(),
+                                    SAMPLE_CONTRACT_1_SIERRA_PATH,
+                                    SAMPLE_CONTRACT_1_CASM_PATH,
+                                    SAMPLE_CONTRACT_2_SIERRA_PATH,
+                                    SAMPLE_CONTRACT_2_CASM_PATH,
+                                    Some(account_class_hash),
+                                    Some(paymaster_account_address),
+                                    Some(paymaster_private_key),
+                                    Some(network_registry().strk_address),
+                                    Some(network_registry().eth_address),
+                                    Some(AMOUNT_PER_TEST),
+                                    block_selector,
+                                )
+                                .await;
+                                let name = format!("endpoints_{}", block_selector);
+                                let outcome = match result {
+                                    Ok(_) => TestOutcome::Passed,
+                                    Err(e) => {
+                                        let error_msg = format!(
+                                            "Error while running v0.7 endpoint checks at {} block: {}",
+                                            block_selector, e
+                                        );
+                                        error!("{}", error_msg);
+                                        failed_tests
+                                            .entry("OpenRpc v0.7".to_string())
+                                            .or_default()
+                                            .insert(name.clone(), error_msg.clone());
+                                        TestOutcome::Failed(error_msg)
+                                    }
+                                };
+                                record_test_case("openrpc_v0_7", &name, outcome, started.elapsed(), Vec::new());
+                            }
+                            continue;
+                        }
+                        Ok(RpcSpecVersion::V0_8) => {}
+                        Err(e) => error!("Could not detect node spec version, assuming 0.8: {}", e),
+                    }
+
                     let suite_openrpc_input = SetupInput {
                         urls: args.urls.clone(),
-                        paymaster_account_address: args.paymaster_account_address.clone(),
-                        paymaster_private_key: args.paymaster_private_key.clone(),
-                        udc_address: args.udc_address.clone(),
-                        account_class_hash: args.account_class_hash.clone(),
+                        paymaster_account_address,
+                        paymaster_private_key,
+                        udc_address: network_registry().udc_address,
+                        account_class_hash,
                     };
                     if let Err(e) = TestSuiteOpenRpc::run(&suite_openrpc_input).await {
                         if let openrpc_testgen::utils::v7::endpoints::errors::OpenRpcTestGenError::TestSuiteFailure {
@@ -41,7 +640,10 @@ async fn main() {
                         } = e
                         {
                             failed_tests.insert("OpenRpc".to_string(), suite_failed_tests);
-                        } else {
+                        } else if !matches!(
+                            e,
+                            openrpc_testgen::utils::v7::endpoints::errors::OpenRpcTestGenError::DryRun(_)
+                        ) {
                             error!("Error while running TestSuiteOpenRpc: {}", e);
                         }
                     }
@@ -56,10 +658,10 @@ async fn main() {
                 {
                     let suite_katana_input = SetupInputKatana {
                         urls: args.urls.clone(),
-                        paymaster_account_address: args.paymaster_account_address.clone(),
-                        paymaster_private_key: args.paymaster_private_key.clone(),
-                        udc_address: args.udc_address.clone(),
-                        account_class_hash: args.account_class_hash.clone(),
+                        paymaster_account_address,
+                        paymaster_private_key,
+                        udc_address: network_registry().udc_address,
+                        account_class_hash,
                     };
                     if let Err(e) = TestSuiteKatana::run(&suite_katana_input).await {
                         if let openrpc_testgen::utils::v7::endpoints::errors::OpenRpcTestGenError::TestSuiteFailure {
@@ -67,7 +669,10 @@ async fn main() {
                         } = e
                         {
                             failed_tests.insert("Katana".to_string(), suite_failed_tests);
-                        } else {
+                        } else if !matches!(
+                            e,
+                            openrpc_testgen::utils::v7::endpoints::errors::OpenRpcTestGenError::DryRun(_)
+                        ) {
                             error!("Error while running TestSuiteKatana: {}", e);
                         }
                     }
@@ -82,10 +687,10 @@ async fn main() {
                 {
                     let suite_katana_no_mining_input = SetupInputKatanaNoMining {
                         urls: args.urls.clone(),
-                        paymaster_account_address: args.paymaster_account_address.clone(),
-                        paymaster_private_key: args.paymaster_private_key.clone(),
-                        udc_address: args.udc_address.clone(),
-                        account_class_hash: args.account_class_hash.clone(),
+                        paymaster_account_address,
+                        paymaster_private_key,
+                        udc_address: network_registry().udc_address,
+                        account_class_hash,
                     };
                     if let Err(e) = TestSuiteKatanaNoMining::run(&suite_katana_no_mining_input).await {
                         if let openrpc_testgen::utils::v7::endpoints::errors::OpenRpcTestGenError::TestSuiteFailure {
@@ -93,7 +698,10 @@ async fn main() {
                         } = e
                         {
                             failed_tests.insert("KatanaNoMining".to_string(), suite_failed_tests);
-                        } else {
+                        } else if !matches!(
+                            e,
+                            openrpc_testgen::utils::v7::endpoints::errors::OpenRpcTestGenError::DryRun(_)
+                        ) {
                             error!("Error while running TestSuiteKatanaNoMining: {}", e);
                         }
                     }
@@ -108,10 +716,10 @@ async fn main() {
                 {
                     let suite_katana_no_fee_input = SetupInputKatanaNoFee {
                         urls: args.urls.clone(),
-                        paymaster_account_address: args.paymaster_account_address.clone(),
-                        paymaster_private_key: args.paymaster_private_key.clone(),
-                        udc_address: args.udc_address.clone(),
-                        account_class_hash: args.account_class_hash.clone(),
+                        paymaster_account_address,
+                        paymaster_private_key,
+                        udc_address: network_registry().udc_address,
+                        account_class_hash,
                     };
                     if let Err(e) = TestSuiteKatanaNoFee::run(&suite_katana_no_fee_input).await {
                         if let openrpc_testgen::utils::v7::endpoints::errors::OpenRpcTestGenError::TestSuiteFailure {
@@ -119,7 +727,10 @@ async fn main() {
                         } = e
                         {
                             failed_tests.insert("KatanaNoFee".to_string(), suite_failed_tests);
-                        } else {
+                        } else if !matches!(
+                            e,
+                            openrpc_testgen::utils::v7::endpoints::errors::OpenRpcTestGenError::DryRun(_)
+                        ) {
                             error!("Error while running TestSuiteKatanaNoFee: {}", e);
                         }
                     }
@@ -134,10 +745,10 @@ async fn main() {
                 {
                     let suite_katana_no_account_validation_input = SetupInputKatanaNoAccountValidation {
                         urls: args.urls.clone(),
-                        paymaster_account_address: args.paymaster_account_address.clone(),
-                        paymaster_private_key: args.paymaster_private_key.clone(),
-                        udc_address: args.udc_address.clone(),
-                        account_class_hash: args.account_class_hash.clone(),
+                        paymaster_account_address,
+                        paymaster_private_key,
+                        udc_address: network_registry().udc_address,
+                        account_class_hash,
                     };
                     if let Err(e) =
                         TestSuiteKatanaNoAccountValidation::run(&suite_katana_no_account_validation_input).await
@@ -147,7 +758,10 @@ async fn main() {
                         } = e
                         {
                             failed_tests.insert("KatanaNoAccountValidation".to_string(), suite_failed_tests);
-                        } else {
+                        } else if !matches!(
+                            e,
+                            openrpc_testgen::utils::v7::endpoints::errors::OpenRpcTestGenError::DryRun(_)
+                        ) {
                             error!("Error while running TestSuiteKatanaNoAccountValidation: {}", e);
                         }
                     }
@@ -160,6 +774,167 @@ async fn main() {
         }
     }
 
+    if let Some(path) = &args.fee_accuracy_report {
+        // Receipts are fetched before a launched node is removed.
+        let provider = JsonRpcClient::new(HttpTransport::new(args.urls[0].clone()));
+        if let Some(report) = fee_accuracy_report(&provider).await {
+            report.log_summary();
+            if let Err(e) = report.write(path) {
+                error!("Could not write fee accuracy report {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    if let Some(path) = &args.class_verification_report {
+        let provider = JsonRpcClient::new(HttpTransport::new(args.urls[0].clone()));
+        if let Some(report) = class_verification_report(&provider).await {
+            report.log_summary();
+            if let Err(e) = report.write(path) {
+                error!("Could not write class verification report {}: {}", path.display(), e);
+            }
+            let failures: HashMap<String, String> = report
+                .failures
+                .iter()
+                .map(|failure| (format!("class {:#x}", failure.class_hash), failure.reason.clone()))
+                .collect();
+            record_test_case("runner", "class_verification", check_outcome(&failures), Duration::ZERO, Vec::new());
+            if !failures.is_empty() {
+                failed_tests.insert("ClassVerification".to_string(), failures);
+            }
+        }
+    }
+
+    if let Some((path, first_block)) = args.declared_classes_report.as_ref().zip(first_declaration_block) {
+        let provider = JsonRpcClient::new(HttpTransport::new(args.urls[0].clone()));
+        match declared_classes_report(&provider, first_block).await {
+            Some(Ok(report)) => {
+                report.log_summary();
+                if let Err(e) = report.write(path) {
+                    error!("Could not write declared classes report {}: {}", path.display(), e);
+                }
+                let failures: HashMap<String, String> = report
+                    .failures
+                    .iter()
+                    .map(|failure| (format!("class {:#x}", failure.class_hash), failure.reason.clone()))
+                    .collect();
+                record_test_case("runner", "declared_classes", check_outcome(&failures), Duration::ZERO, Vec::new());
+                if !failures.is_empty() {
+                    failed_tests.insert("DeclaredClasses".to_string(), failures);
+                }
+            }
+            Some(Err(e)) => error!("Could not check the declared classes: {}", e),
+            None => {}
+        }
+    }
+
+    #[cfg(feature = "reexecution")]
+    if let Some((path, first_block)) = args.reexecution_report.as_ref().zip(first_reexecuted_block) {
+        let provider = JsonRpcClient::new(HttpTransport::new(args.urls[0].clone()));
+        let result = match provider.block_number().await {
+            Ok(last_block) => reexecute_blocks(&args.urls[0], first_block, last_block).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        match result {
+            Ok(report) => {
+                if let Err(e) = report.write(path) {
+                    error!("Could not write re-execution report {}: {}", path.display(), e);
+                }
+                let mismatches: HashMap<String, String> = report
+                    .mismatches
+                    .iter()
+                    .map(|mismatch| {
+                        (
+                            format!(
+                                "block {} transaction {:#x} {}",
+                                mismatch.block_number, mismatch.transaction_hash, mismatch.field
+                            ),
+                            format!("node {} vs re-execution {}", mismatch.node, mismatch.reexecution),
+                        )
+                    })
+                    .collect();
+                record_test_case("runner", "reexecution", check_outcome(&mismatches), Duration::ZERO, Vec::new());
+                if !mismatches.is_empty() {
+                    failed_tests.insert("Reexecution".to_string(), mismatches);
+                }
+            }
+            Err(e) => error!("Could not re-execute the blocks of the run: {}", e),
+        }
+    }
+
+    if let Some((path, report)) = args.latency_report.as_ref().zip(inclusion_latency_report()) {
+        report.log_summary();
+        if let Err(e) = report.write(path) {
+            error!("Could not write inclusion latency report {}: {}", path.display(), e);
+        }
+    }
+
+    if let Some((path, report)) = args.finality_report.as_ref().zip(finality_report()) {
+        report.log_summary();
+        if let Err(e) = report.write(path) {
+            error!("Could not write finality report {}: {}", path.display(), e);
+        }
+    }
+
+    if let Some((path, report)) = args.resource_limits_report.as_ref().zip(resource_limits_report()) {
+        report.log_summary();
+        if let Err(e) = report.write(path) {
+            error!("Could not write resource limits report {}: {}", path.display(), e);
+        }
+    }
+
+    if let Some((path, report)) = args.ordering_report.as_ref().zip(ordering_report()) {
+        report.log_summary();
+        if let Err(e) = report.write(path) {
+            error!("Could not write ordering report {}: {}", path.display(), e);
+        }
+    }
+
+    if let Some((path, report)) = args.fee_search_report.as_ref().zip(fee_search_report()) {
+        report.log_summary();
+        if let Err(e) = report.write(path) {
+            error!("Could not write fee search report {}: {}", path.display(), e);
+        }
+    }
+
+    if let Some((path, report)) = args.response_size_report.as_ref().zip(response_size_report()) {
+        report.log_summary();
+        if let Err(e) = report.write(path) {
+            error!("Could not write response size report {}: {}", path.display(), e);
+        }
+    }
+
+    write_timing_report(args.timing_report.as_deref());
+
+    if let Some((path, har)) = args.har.as_ref().zip(traffic_capture()) {
+        har.log_summary();
+        if let Err(e) = har.write(path) {
+            error!("Could not write HAR file {}: {}", path.display(), e);
+        }
+    }
+
+    if let Some((path, results)) = args.report.as_ref().zip(test_case_results()) {
+        if let Err(e) = write_junit_report(path, &results) {
+            error!("Could not write JUnit report {}: {}", path.display(), e);
+        }
+    }
+    if let Some((path, results)) = args.json_report.as_ref().zip(test_case_results()) {
+        if let Err(e) = write_json_report(path, &results) {
+            error!("Could not write JSON report {}: {}", path.display(), e);
+        }
+    }
+
+    if args.checkpoint.is_some() {
+        record_paymaster_nonce(&args.urls[0], paymaster_account_address).await;
+    }
+
+    // `std::process::exit` skips destructors, the node has to be removed before.
+    #[cfg(feature = "orchestrator")]
+    if let Some(target) = launched_target {
+        if let Err(e) = target.teardown().await {
+            error!("Could not remove the launched node: {}", e);
+        }
+    }
+
     if !failed_tests.is_empty() {
         error!("Summary of failed tests:");
         for (suite_name, tests) in &failed_tests {
@@ -174,3 +949,44 @@ async fn main() {
         std::process::exit(0);
     }
 }
+
+/// Health check of a KMS key: has it sign a probe hash, which also checks the signature against the
+/// public key of the key, and logs the public key its secp account is deployed with. Stops the run
+/// if it can't sign. The suites don't sign with the key, they keep using the paymaster private key.
+#[cfg(any(feature = "aws_kms", feature = "gcp_kms"))]
+async fn check_kms_signer<S: RawSigner>(key: &str, signer: Result<S, KmsSignerError>) {
+    let signed = match signer {
+        Ok(signer) => {
+            signer.sign_hash_raw(&Felt::ONE).await.map(|_| signer.public_key_felts()).map_err(|e| e.to_string())
+        }
+        Err(e) => Err(e.to_string()),
+    };
+    match signed {
+        Ok(public_key) => info!("🔑 KMS key {} signs, public key {:?}", key, public_key),
+        Err(e) => {
+            error!("KMS key {} can't sign: {}", key, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Records the nonce of the paymaster account in the checkpoint, which discards the progress of a
+/// resumed run if the node was reset since.
+async fn record_paymaster_nonce(url: &url::Url, paymaster_address: Felt) {
+    let provider = JsonRpcClient::new(HttpTransport::new(url.clone()));
+    match provider.get_nonce(BlockId::Tag(BlockTag::Pending), paymaster_address).await {
+        Ok(nonce) => record_nonce(paymaster_address, nonce),
+        Err(e) => error!("Could not get the nonce of the paymaster account for the checkpoint: {}", e),
+    }
+}
+
+/// Outcome of a check run after the suites, failed by any of its `failures`.
+fn check_outcome(failures: &HashMap<String, String>) -> TestOutcome {
+    let mut failures: Vec<String> = failures.iter().map(|(name, reason)| format!("{name}: {reason}")).collect();
+    failures.sort();
+    if failures.is_empty() {
+        TestOutcome::Passed
+    } else {
+        TestOutcome::Failed(failures.join("\n"))
+    }
+}
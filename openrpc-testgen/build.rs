@@ -8,6 +8,23 @@
 //! - **Test Suites**: Directories prefixed with `suite_`.
 //! - **Test Cases**: Modules prefixed with `test_`.
 //! - **Nested Suites**: Detected recursively in directories and `mod.rs` files.
+//! - **Registered Test Cases**: `test_` files whose `RunnableTrait` implementation carries
+//!   `#[openrpc_test(suite = "...", name = "...")]`. They are declared by the generated code when
+//!   the suite's `mod.rs` does not declare them, and run under their registered name.
+//! - **Filtering**: Every test case is run only if selected by `utils::test_filter`.
+//! - **Checkpoints**: Test cases recorded as passed by `utils::checkpoint` are skipped when a run
+//!   is resumed, and those that pass are recorded.
+//! - **Resilience**: Before every test case `utils::resilience` waits for the nodes to be reachable
+//!   when resilience mode is enabled, and restarts a node before selected cases. A failed case is run
+//!   again once if a node was down after it.
+//! - **Hooks**: Every test case runs between the `setup_case` and `teardown_case` hooks of
+//!   `RunnableTrait`, and every suite ends with the `teardown` of `SetupableTrait`. Teardowns run
+//!   even when the cases failed.
+//! - **Reporting**: Every test case runs in a `test_case` span with the suite and case as fields.
+//!   Its outcome, duration and the logs `utils::log_capture` captured in the span are recorded with
+//!   `utils::test_report`.
+//! - **Catalogue**: Every test case of every suite, compiled in or not, is listed in
+//!   `test_catalogue.rs`, which `utils::test_filter` includes.
 //!
 //! ## Structure
 //! - **Root Directory**: Contains `suite_` directories.
@@ -17,7 +34,14 @@
 use std::env;
 use std::fs::{self, read_to_string, File};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// A test case as listed in the catalogue.
+struct CatalogueEntry {
+    root_suite: String,
+    suite: String,
+    name: String,
+}
 
 /// Main function for the build script.
 /// - Processes all `suite_` directories in the `src` directory.
@@ -35,14 +59,16 @@ fn main() {
     }
 
     // Process each root suite directory in `src`
+    let mut catalogue = Vec::new();
     for entry in fs::read_dir(src_dir).expect("Could not read src directory") {
         let entry = entry.expect("Could not read directory entry");
         let path = entry.path();
         if path.is_dir() && path.file_name().and_then(|s| s.to_str()).map(|s| s.starts_with("suite_")) == Some(true) {
-            let root_output_type = process_module_directory(&path, &out_dir, None);
-            process_directory_recursively(&path, &out_dir, Some(&root_output_type));
+            let root_output_type = process_module_directory(&path, &out_dir, None, &mut catalogue);
+            process_directory_recursively(&path, &out_dir, Some(&root_output_type), &mut catalogue);
         }
     }
+    write_catalogue(&out_dir, catalogue);
 
     println!("cargo:rerun-if-changed=src");
 }
@@ -53,13 +79,19 @@ fn main() {
 /// - `dir`: The directory to process.
 /// - `out_dir`: The output directory for generated files.
 /// - `parent_output_type`: The `Output` type of the parent test suite.
-fn process_directory_recursively(dir: &Path, out_dir: &str, parent_output_type: Option<&str>) {
+/// - `catalogue`: The test cases found so far.
+fn process_directory_recursively(
+    dir: &Path,
+    out_dir: &str,
+    parent_output_type: Option<&str>,
+    catalogue: &mut Vec<CatalogueEntry>,
+) {
     for entry in fs::read_dir(dir).expect("Could not read directory") {
         let entry = entry.expect("Could not read directory entry");
         let path = entry.path();
         if path.is_dir() && path.file_name().and_then(|s| s.to_str()).map(|s| s.starts_with("suite_")) == Some(true) {
-            let current_output_type = process_module_directory(&path, out_dir, parent_output_type);
-            process_directory_recursively(&path, out_dir, Some(&current_output_type));
+            let current_output_type = process_module_directory(&path, out_dir, parent_output_type, catalogue);
+            process_directory_recursively(&path, out_dir, Some(&current_output_type), catalogue);
         }
     }
 }
@@ -70,10 +102,16 @@ fn process_directory_recursively(dir: &Path, out_dir: &str, parent_output_type:
 /// - `module_path`: The path to the suite directory.
 /// - `out_dir`: The output directory for generated files.
 /// - `parent_output_type`: The `Output` type of the parent test suite.
+/// - `catalogue`: The test cases found so far, the cases of the suite are added to it.
 ///
 /// # Returns
 /// The `Output` type of the current suite.
-fn process_module_directory(module_path: &Path, out_dir: &str, parent_output_type: Option<&str>) -> String {
+fn process_module_directory(
+    module_path: &Path,
+    out_dir: &str,
+    parent_output_type: Option<&str>,
+    catalogue: &mut Vec<CatalogueEntry>,
+) -> String {
     let module_name = module_path.strip_prefix("src").unwrap().to_str().unwrap();
     let module_name_safe = module_name.replace("/", "_");
 
@@ -92,7 +130,25 @@ fn process_module_directory(module_path: &Path, out_dir: &str, parent_output_typ
     let struct_name = find_testsuite_struct_in_file(&main_file_path)
         .expect("Expected a struct starting with 'TestSuite' in mod.rs, but none was found");
 
-    let (test_cases, nested_suites) = partition_modules(&main_file_path);
+    let (mut test_cases, nested_suites) = partition_modules(&main_file_path);
+
+    let suite_name = suite_name(module_path);
+    let registered_tests = find_registered_tests(module_path);
+    for registered in &registered_tests {
+        if registered.suite != suite_name {
+            panic!(
+                "{} is registered in suite `{}` but is located in suite `{}`",
+                registered.path.display(),
+                registered.suite,
+                suite_name
+            );
+        }
+        if !test_cases.contains(&registered.module) {
+            let path = fs::canonicalize(&registered.path).expect("Could not resolve registered test case path");
+            writeln!(file, "#[path = {:?}]\npub mod {};\n", path.to_str().unwrap(), registered.module).unwrap();
+            test_cases.push(registered.module.clone());
+        }
+    }
 
     writeln!(file, "impl crate::RunnableTrait for {}::{} {{", module_prefix, struct_name).unwrap();
 
@@ -121,27 +177,117 @@ fn process_module_directory(module_path: &Path, out_dir: &str, parent_output_typ
         file,
         "        let data = match {}::{}::setup(input).await {{
                 Ok(data) => data,
+                Err(_) if crate::utils::dry_run::take_stopped() => {{
+                    tracing::info!(\"Setup of {}::{} stopped before broadcasting, dry run.\");
+                    crate::utils::test_report::record_test_case(
+                        \"{}\",
+                        \"setup\",
+                        crate::utils::test_report::TestOutcome::Skipped,
+                        std::time::Duration::ZERO,
+                        Vec::new(),
+                    );
+                    return Err(crate::utils::dry_run::DryRunError::SetupNotBroadcast.into());
+                }}
                 Err(e) => {{
                     tracing::error!(\"Setup failed with error: {{:?}}\", e);
-                    failed_tests.insert(\"setup\".to_string(), format!(\"Setup failed: {{:?}}\", e));
+                    failed_tests.insert(\"{}/setup\".to_string(), format!(\"Setup failed: {{:?}}\", e));
+                    crate::utils::test_report::record_test_case(
+                        \"{}\",
+                        \"setup\",
+                        crate::utils::test_report::TestOutcome::Failed(format!(\"Setup failed: {{:?}}\", e)),
+                        std::time::Duration::ZERO,
+                        Vec::new(),
+                    );
                     return Err(crate::utils::v7::endpoints::errors::OpenRpcTestGenError::TestSuiteFailure {{ failed_tests }});
                 }}
             }};",
-        module_prefix, struct_name
+        module_prefix, struct_name, module_prefix, struct_name, suite_name, module_name, suite_name
     )
     .unwrap();
 
+    let root_suite = module_name.split('/').next().unwrap().trim_start_matches("suite_").to_string();
     for test_name in test_cases {
+        let registered = registered_tests.iter().find(|registered| registered.module == test_name);
+        let registered_name = if registered.is_some() {
+            format!("{}::{}::TestCase::NAME", module_prefix, test_name)
+        } else {
+            format!("{:?}", test_name.trim_start_matches("test_"))
+        };
+        // Failures are keyed by path, as nested suites report theirs into the same map.
+        let test_path = format!("{module_name}/{test_name}");
+        catalogue.push(CatalogueEntry {
+            root_suite: root_suite.clone(),
+            suite: suite_name.clone(),
+            name: registered.map_or_else(|| test_name.trim_start_matches("test_").to_string(), |r| r.name.clone()),
+        });
         writeln!(
             file,
-            "        if let Err(e) = {}::{}::TestCase::run(&data).await {{
-                let error_msg = format!(\"✗ Test case src/{} failed with runtime error: {{:?}}\", e);
+            "        let started = std::time::Instant::now();
+            let span = tracing::info_span!(\"test_case\", suite = \"{suite_name}\", case = {registered_name});
+            let outcome = tracing::Instrument::instrument(async {{
+            if !crate::utils::test_filter::is_selected(\"{suite_name}\", {registered_name}) {{
+                tracing::info!(\"Test case src/{test_name} skipped by filter.\");
+                crate::utils::test_report::TestOutcome::Skipped
+            }} else if crate::utils::checkpoint::is_already_passed(\"{suite_name}\", {registered_name}) {{
+                tracing::info!(\"Test case src/{test_name} skipped, it passed before the run was resumed.\");
+                crate::utils::test_report::TestOutcome::Skipped
+            }} else if let Err(e) = crate::utils::resilience::before_test_case(\"{suite_name}\", {registered_name}).await {{
+                let error_msg = format!(\"✗ Test case src/{test_name} could not start, node unavailable: {{:?}}\", e);
+                tracing::error!(\"{{}}\", error_msg.red());
+                failed_tests.insert(\"{test_path}\".to_string(), error_msg.clone());
+                crate::utils::test_report::TestOutcome::Failed(error_msg)
+            }} else if let Err(e) = <{module_prefix}::{test_name}::TestCase as crate::RunnableTrait>::setup_case(&data).await {{
+                let error_msg = format!(\"✗ Test case src/{test_name} setup failed: {{:?}}\", e);
                 tracing::error!(\"{{}}\", error_msg.red());
-                failed_tests.insert(\"{}\".to_string(), error_msg);
+                failed_tests.insert(\"{test_path}\".to_string(), error_msg.clone());
+                crate::utils::test_report::TestOutcome::Failed(error_msg)
             }} else {{
-                tracing::info!(\"{{}}\", \"✓ Test case src/{} completed successfully.\".green());
-            }}",
-            module_prefix, test_name, test_name, test_name, test_name
+                let (result, teardown) = loop {{
+                    let result = {module_prefix}::{test_name}::TestCase::run(&data).await;
+                    let teardown = <{module_prefix}::{test_name}::TestCase as crate::RunnableTrait>::teardown_case(&data).await;
+                    if (result.is_err() || teardown.is_err())
+                        && crate::utils::resilience::retry_after_failure(\"{suite_name}\", {registered_name}).await
+                    {{
+                        match <{module_prefix}::{test_name}::TestCase as crate::RunnableTrait>::setup_case(&data).await {{
+                            Ok(()) => continue,
+                            Err(e) => break (Err(e), Ok(())),
+                        }}
+                    }}
+                    break (result, teardown);
+                }};
+                let stopped = crate::utils::dry_run::take_stopped() && (result.is_err() || teardown.is_err());
+                let error_msg = match (result, teardown) {{
+                    _ if stopped => None,
+                    (Err(e), teardown) => {{
+                        if let Err(teardown_error) = teardown {{
+                            tracing::error!(\"Teardown of test case src/{test_name} failed: {{:?}}\", teardown_error);
+                        }}
+                        Some(format!(\"✗ Test case src/{test_name} failed with runtime error: {{:?}}\", e))
+                    }}
+                    (Ok(_), Err(e)) => Some(format!(\"✗ Test case src/{test_name} teardown failed: {{:?}}\", e)),
+                    (Ok(_), Ok(())) => None,
+                }};
+                match error_msg {{
+                    Some(error_msg) => {{
+                        tracing::error!(\"{{}}\", error_msg.red());
+                        failed_tests.insert(\"{test_path}\".to_string(), error_msg.clone());
+                        crate::utils::test_report::TestOutcome::Failed(error_msg)
+                    }}
+                    None if stopped => {{
+                        tracing::info!(\"Test case src/{test_name} stopped before broadcasting, dry run.\");
+                        crate::utils::test_report::TestOutcome::Skipped
+                    }}
+                    None => {{
+                        tracing::info!(\"{{}}\", \"✓ Test case src/{test_name} completed successfully.\".green());
+                        crate::utils::checkpoint::record_passed(\"{suite_name}\", {registered_name});
+                        crate::utils::test_report::TestOutcome::Passed
+                    }}
+                }}
+            }} }}, span.clone()).await;
+            let logs = span.id().map(|id| crate::utils::log_capture::take_captured_logs(&id)).unwrap_or_default();
+            crate::utils::test_report::record_test_case(
+                \"{suite_name}\", {registered_name}, outcome, started.elapsed(), logs
+            );"
         )
         .unwrap();
     }
@@ -160,9 +306,21 @@ fn process_module_directory(module_path: &Path, out_dir: &str, parent_output_typ
         ).unwrap();
     }
 
+    // Teardown runs whatever the outcome of the cases, it only misses when the setup failed.
     writeln!(
         file,
-        "        if !failed_tests.is_empty() {{
+        "        if let Err(e) = crate::SetupableTrait::teardown(&data).await {{
+                tracing::error!(\"Teardown failed with error: {{:?}}\", e);
+                failed_tests.insert(\"{module_name}/teardown\".to_string(), format!(\"Teardown failed: {{:?}}\", e));
+                crate::utils::test_report::record_test_case(
+                    \"{suite_name}\",
+                    \"teardown\",
+                    crate::utils::test_report::TestOutcome::Failed(format!(\"Teardown failed: {{:?}}\", e)),
+                    std::time::Duration::ZERO,
+                    Vec::new(),
+                );
+            }}
+        if !failed_tests.is_empty() {{
                 tracing::error!(\"One or more tests in the suite failed: {{:?}}\", failed_tests.keys());
                 return Err(crate::utils::v7::endpoints::errors::OpenRpcTestGenError::TestSuiteFailure {{
                     failed_tests
@@ -179,6 +337,27 @@ fn process_module_directory(module_path: &Path, out_dir: &str, parent_output_typ
     format!("{}::{}", module_prefix, struct_name)
 }
 
+/// Writes `test_catalogue.rs`, listing the test cases of every suite as `TestCaseEntry` values.
+fn write_catalogue(out_dir: &str, mut catalogue: Vec<CatalogueEntry>) {
+    catalogue.sort_by(|a, b| (&a.root_suite, &a.suite, &a.name).cmp(&(&b.root_suite, &b.suite, &b.name)));
+
+    let mut file = File::create(Path::new(out_dir).join("test_catalogue.rs")).expect("Could not create test catalogue");
+    writeln!(
+        file,
+        "// Auto-generated catalogue of the test cases of every suite\npub const TEST_CASES: &[TestCaseEntry] = &["
+    )
+    .unwrap();
+    for entry in catalogue {
+        writeln!(
+            file,
+            "    TestCaseEntry {{ root_suite: {:?}, suite: {:?}, name: {:?} }},",
+            entry.root_suite, entry.suite, entry.name
+        )
+        .unwrap();
+    }
+    writeln!(file, "];").unwrap();
+}
+
 /// Parses a `mod.rs` file to extract test cases and nested suites.
 ///
 /// # Returns
@@ -205,6 +384,78 @@ fn partition_modules(mod_file_path: &Path) -> (Vec<String>, Vec<String>) {
     (test_cases, nested_suites)
 }
 
+/// A `test_` file registered with `#[openrpc_test(suite = "...", name = "...")]`.
+struct RegisteredTest {
+    module: String,
+    path: PathBuf,
+    suite: String,
+    name: String,
+}
+
+/// Name of a suite in `#[openrpc_test]`: its directory name without the `suite_` prefix.
+fn suite_name(module_path: &Path) -> String {
+    let dir_name = module_path.file_name().and_then(|s| s.to_str()).expect("Suite directory name is not valid UTF-8");
+    dir_name.trim_start_matches("suite_").to_string()
+}
+
+/// Finds the `test_*.rs` files in a suite directory that carry the `#[openrpc_test]` attribute.
+fn find_registered_tests(module_path: &Path) -> Vec<RegisteredTest> {
+    let mut registered_tests = Vec::new();
+    for entry in fs::read_dir(module_path).expect("Could not read suite directory") {
+        let path = entry.expect("Could not read directory entry").path();
+        let Some(module) = path.file_stem().and_then(|s| s.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if !module.starts_with("test_") || path.extension().and_then(|s| s.to_str()) != Some("rs") {
+            continue;
+        }
+
+        let content = read_to_string(&path).expect("Could not read test case file");
+        if let Some((suite, name)) = parse_openrpc_test_attribute(&content) {
+            registered_tests.push(RegisteredTest { module, path, suite, name });
+        }
+    }
+    registered_tests.sort_by(|a, b| a.module.cmp(&b.module));
+
+    let mut names: Vec<&str> = registered_tests.iter().map(|registered| registered.name.as_str()).collect();
+    names.sort_unstable();
+    if let Some(duplicate) = names.windows(2).find(|pair| pair[0] == pair[1]) {
+        panic!("Test case name `{}` is registered twice in {}", duplicate[0], module_path.display());
+    }
+
+    registered_tests
+}
+
+/// Extracts `suite` and `name` from the `#[openrpc_test(...)]` attribute of a test case file.
+fn parse_openrpc_test_attribute(content: &str) -> Option<(String, String)> {
+    // Files that don't parse are reported by the compiler.
+    let file = syn::parse_file(content).ok()?;
+    let attribute = file.items.iter().find_map(|item| match item {
+        syn::Item::Impl(item) => item
+            .attrs
+            .iter()
+            .find(|attr| attr.path().segments.last().is_some_and(|segment| segment.ident == "openrpc_test")),
+        _ => None,
+    })?;
+
+    let mut suite = None;
+    let mut name = None;
+    attribute
+        .parse_nested_meta(|meta| {
+            let value: syn::LitStr = meta.value()?.parse()?;
+            if meta.path.is_ident("suite") {
+                suite = Some(value.value());
+            } else if meta.path.is_ident("name") {
+                name = Some(value.value());
+            }
+            Ok(())
+        })
+        .ok()?;
+
+    // Missing and invalid properties are reported by the attribute macro itself.
+    Some((suite?, name?))
+}
+
 /// Finds the struct name starting with `TestSuite` in the given file.
 ///
 /// # Returns
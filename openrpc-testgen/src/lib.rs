@@ -1,4 +1,8 @@
+//! Test suites for Starknet JSON-RPC nodes, and the framework they are written with. Projects
+//! writing their own test cases against this crate start from [prelude].
+
 use std::future::Future;
+use utils::account_pool::ExclusiveAccount;
 use utils::v7::{
     accounts::single_owner::SingleOwnerAccount,
     endpoints::errors::OpenRpcTestGenError,
@@ -7,6 +11,8 @@ use utils::v7::{
 };
 
 pub mod macros;
+pub use openrpc_testgen_macros::openrpc_test;
+pub mod prelude;
 #[cfg(feature = "katana")]
 pub mod suite_katana;
 #[cfg(feature = "katana_no_account_validation")]
@@ -24,11 +30,29 @@ pub trait RunnableTrait: Sized {
     type Input;
 
     fn run(input: &Self::Input) -> impl Future<Output = Result<Self, OpenRpcTestGenError>>;
+
+    /// Runs before the test case, e.g. to deploy its fixtures. A failure fails the case without
+    /// running it.
+    fn setup_case(_input: &Self::Input) -> impl Future<Output = Result<(), OpenRpcTestGenError>> {
+        async { Ok(()) }
+    }
+
+    /// Runs after the test case whenever [RunnableTrait::setup_case] succeeded, even if the case
+    /// failed.
+    fn teardown_case(_input: &Self::Input) -> impl Future<Output = Result<(), OpenRpcTestGenError>> {
+        async { Ok(()) }
+    }
 }
 pub trait SetupableTrait: Sized {
     type Input;
 
     fn setup(input: &Self::Input) -> impl Future<Output = Result<Self, OpenRpcTestGenError>>;
+
+    /// Runs once after the test cases and nested suites of the suite, whether they passed or not,
+    /// e.g. to drain the test accounts back to the paymaster.
+    fn teardown(&self) -> impl Future<Output = Result<(), OpenRpcTestGenError>> {
+        async { Ok(()) }
+    }
 }
 
 pub trait RandomizableAccountsTrait {
@@ -36,4 +60,12 @@ pub trait RandomizableAccountsTrait {
     fn random_accounts(
         &self,
     ) -> Result<SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>, OpenRpcTestGenError>;
+
+    /// Takes an account out of the selection until the returned [ExclusiveAccount] is dropped, for
+    /// test cases changing the account in ways other cases can't share, e.g. upgrading its class.
+    /// Selections that can't reserve accounts keep the default, which fails.
+    #[allow(clippy::result_large_err)]
+    fn exclusive_account(&self) -> Result<ExclusiveAccount, OpenRpcTestGenError> {
+        Err(OpenRpcTestGenError::Other("The accounts of this selection can't be reserved".to_string()))
+    }
 }
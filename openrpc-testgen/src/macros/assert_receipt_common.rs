@@ -0,0 +1,62 @@
+/// Checks the envelope every receipt of an accepted transaction shares, whatever its type, and
+/// returns an `AssertionNoPanicError` on the first mismatch:
+/// * the transaction hash is the one the receipt was queried for,
+/// * the transaction is accepted on L2 or L1, or has the given finality status,
+/// * a non-zero fee was charged.
+///
+/// # Arguments
+/// * `common` - The `common_receipt_properties` of the receipt.
+/// * `transaction_hash` - Hash the receipt was queried for.
+/// * `finality_status` (optional) - Expected `TxnFinalityStatus`.
+///
+/// # Examples
+/// ```ignore
+/// let receipt = match provider.get_transaction_receipt(hash).await? {
+///     TxnReceipt::Invoke(receipt) => receipt,
+///     _ => return Err(OpenRpcTestGenError::CallError(CallError::UnexpectedReceiptType)),
+/// };
+/// assert_receipt_common!(receipt.common_receipt_properties, hash, TxnFinalityStatus::L2);
+/// ```
+#[macro_export]
+macro_rules! assert_receipt_common {
+    ($common:expr, $transaction_hash:expr $(,)?) => {{
+        let (common, transaction_hash) = (&$common, &$transaction_hash);
+        $crate::assert_result!(
+            common.transaction_hash == *transaction_hash,
+            format!("Receipt transaction hash expected: {:?}, actual: {:?}", transaction_hash, common.transaction_hash)
+        );
+        $crate::assert_matches_result!(
+            common.finality_status,
+            ::starknet_types_rpc::TxnFinalityStatus::L2 | ::starknet_types_rpc::TxnFinalityStatus::L1,
+            "Receipt of {:?} is not accepted",
+            transaction_hash
+        );
+        $crate::assert_result!(
+            common.actual_fee.amount > ::starknet_types_core::felt::Felt::ZERO,
+            format!("Receipt of {:?} has no actual fee: {:?}", transaction_hash, common.actual_fee)
+        );
+    }};
+    ($common:expr, $transaction_hash:expr, $finality_status:expr $(,)?) => {{
+        let (common, finality_status) = (&$common, &$finality_status);
+        $crate::assert_receipt_common!(*common, $transaction_hash);
+        $crate::assert_result!(
+            common.finality_status == *finality_status,
+            format!("Receipt finality status expected: {:?}, actual: {:?}", finality_status, common.finality_status)
+        );
+    }};
+}
+
+/// Checks that the receipt of a transaction which is no longer pending names its block.
+///
+/// # Arguments
+/// * `block_info` - The `ReceiptBlockInfo` of the receipt.
+#[macro_export]
+macro_rules! assert_receipt_block_info {
+    ($block_info:expr $(,)?) => {{
+        let block_info = &$block_info;
+        $crate::assert_result!(
+            block_info.block_hash.is_some() && block_info.block_number.is_some(),
+            format!("Receipt of a transaction in a block has no block fields: {:?}", block_info)
+        );
+    }};
+}
@@ -1,5 +1,6 @@
 pub mod assert_eq_result;
 pub mod assert_matches_result;
 pub mod assert_provider_starknet_err;
+pub mod assert_receipt_common;
 pub mod assert_result;
 pub mod macros_errors;
@@ -0,0 +1,33 @@
+//! Everything a test case written outside of this crate typically needs, in one import:
+//!
+//! ```
+//! use openrpc_testgen::prelude::*;
+//! ```
+//!
+//! This covers the provider and its errors, accounts and the account factories, signers, the
+//! helpers declaring classes and waiting for transactions, the suite traits and the assertion
+//! macros. Anything else is still reachable under [crate::utils].
+
+pub use starknet_types_core::felt::Felt;
+pub use starknet_types_rpc::{BlockId, BlockTag};
+
+pub use crate::utils::account_pool::{AccountPool, ExclusiveAccount};
+pub use crate::utils::random_single_owner_account::RandomSingleOwnerAccount;
+pub use crate::utils::v7::accounts::account::{Account, AccountError, ConnectedAccount, ExecutionEncoder};
+pub use crate::utils::v7::accounts::call::Call;
+pub use crate::utils::v7::accounts::creation::create::{create_account, AccountType};
+pub use crate::utils::v7::accounts::factory::{
+    argent::ArgentAccountFactory, braavos::BraavosAccountFactory, open_zeppelin::OpenZeppelinAccountFactory,
+    AccountFactory,
+};
+pub use crate::utils::v7::accounts::single_owner::{ExecutionEncoding, SingleOwnerAccount};
+pub use crate::utils::v7::endpoints::declare_contract::get_compiled_contract;
+pub use crate::utils::v7::endpoints::errors::OpenRpcTestGenError;
+pub use crate::utils::v7::endpoints::utils::{get_selector_from_name, wait_for_sent_transaction};
+pub use crate::utils::v7::providers::jsonrpc::{HttpTransport, JsonRpcClient, StarknetError};
+pub use crate::utils::v7::providers::provider::{Provider, ProviderError};
+pub use crate::utils::v7::signers::key_pair::SigningKey;
+pub use crate::utils::v7::signers::local_wallet::LocalWallet;
+pub use crate::utils::v7::signers::signer::{RawSigner, Signer};
+pub use crate::{assert_eq_result, assert_matches_result, assert_result};
+pub use crate::{openrpc_test, RandomizableAccountsTrait, RunnableTrait, SetupableTrait};
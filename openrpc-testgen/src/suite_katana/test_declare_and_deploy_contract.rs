@@ -1,20 +1,23 @@
 use crate::{
     assert_eq_result, assert_matches_result, assert_result,
-    utils::v7::{
-        accounts::{
-            account::{Account, ConnectedAccount},
-            call::Call,
-            deployment::helpers::get_contract_address,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                declare_contract::prepare_contract_declaration_params,
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
         },
-        endpoints::{
-            declare_contract::prepare_contract_declaration_params,
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, wait_for_sent_transaction},
-        },
-        providers::provider::Provider,
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
+use starknet_hive_hashing::address::contract_address;
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{BlockId, BlockTag, DeclareTxnReceipt, TxnReceipt};
 
@@ -65,15 +68,13 @@ impl RunnableTrait for TestCase {
         .concat();
 
         // pre-compute the contract address of the would-be deployed contract
-        let address = get_contract_address(Felt::ZERO, declare_res.class_hash, &ctor_args, Felt::ZERO);
+        let address = contract_address(Felt::ZERO, declare_res.class_hash, &ctor_args, Felt::ZERO);
 
         let res = test_input
             .random_paymaster_account
             .execute_v1(vec![Call {
                 calldata,
-                to: Felt::from_hex_unchecked(
-                    "0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf", //DEFAULT_UDC_ADDRESS
-                ),
+                to: network_registry().udc_address,
                 selector: get_selector_from_name("deployContract")?,
             }])
             .send()
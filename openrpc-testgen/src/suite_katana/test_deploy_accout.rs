@@ -1,27 +1,27 @@
 use crate::{
     assert_eq_result, assert_matches_result,
-    utils::v7::{
-        accounts::{
-            account::{Account, ConnectedAccount},
-            call::Call,
-            deployment::helpers::get_contract_address,
-            factory::{open_zeppelin::OpenZeppelinAccountFactory, AccountFactory},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+                factory::{open_zeppelin::OpenZeppelinAccountFactory, AccountFactory},
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
+            signers::{key_pair::SigningKey, local_wallet::LocalWallet, signer::Signer},
         },
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, wait_for_sent_transaction},
-        },
-        providers::provider::Provider,
-        signers::{key_pair::SigningKey, local_wallet::LocalWallet, signer::Signer},
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
+use starknet_hive_hashing::address::contract_address;
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{BlockId, BlockTag, DeployAccountTxnReceipt, TxnReceipt};
 
-const DEFAULT_ACCOUNT_CLASS_HASH: Felt =
-    Felt::from_hex_unchecked("0x07dc7899aa655b0aae51eadff6d801a58e97dd99cf4666ee59e704249e51adf2");
-
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -35,10 +35,10 @@ impl RunnableTrait for TestCase {
 
         // Precompute the contract address of the new account with the given parameters:
         let signer = LocalWallet::from(SigningKey::from_random());
-        let class_hash = DEFAULT_ACCOUNT_CLASS_HASH;
+        let class_hash = network_registry().default_account_class_hash;
         let salt = Felt::from_hex_unchecked("0x123");
         let ctor_args = [signer.get_public_key().await?.scalar()];
-        let computed_address = get_contract_address(salt, class_hash, &ctor_args, Felt::ZERO);
+        let computed_address = contract_address(salt, class_hash, &ctor_args, Felt::ZERO);
 
         // send enough tokens to the new_account's address just to send the deploy account tx
         let amount = Felt::from_hex_unchecked("0x1ba32524a30000");
@@ -46,7 +46,7 @@ impl RunnableTrait for TestCase {
 
         let transfer_execution = funding_account
             .execute_v1(vec![Call {
-                to: Felt::from_hex("0x49D36570D4E46F48E99674BD3FCC84644DDD6B96F7C741B1562B82F9E004DC7")?,
+                to: network_registry().eth_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![recipient, amount, Felt::ZERO],
             }])
@@ -1,16 +1,19 @@
 use crate::{
     assert_matches_result,
-    utils::v7::{
-        accounts::{
-            account::{Account, AccountError, ConnectedAccount},
-            call::Call,
-            creation::create::{create_account, AccountType},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, AccountError, ConnectedAccount},
+                call::Call,
+                creation::create::{create_account, AccountType},
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::{jsonrpc::StarknetError, provider::ProviderError},
         },
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, wait_for_sent_transaction},
-        },
-        providers::{jsonrpc::StarknetError, provider::ProviderError},
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
@@ -39,7 +42,7 @@ impl RunnableTrait for TestCase {
 
         let transfer_execution = account
             .execute_v1(vec![Call {
-                to: Felt::from_hex("0x49D36570D4E46F48E99674BD3FCC84644DDD6B96F7C741B1562B82F9E004DC7")?,
+                to: network_registry().eth_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account_data.address, transfer_amount, Felt::ZERO],
             }])
@@ -1,10 +1,13 @@
 use crate::{
     assert_eq_result,
-    utils::v7::{
-        accounts::{account::Account, call::Call},
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, wait_for_sent_transaction},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{account::Account, call::Call},
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
         },
     },
     RandomizableAccountsTrait, RunnableTrait,
@@ -20,9 +23,7 @@ impl RunnableTrait for TestCase {
     type Input = super::TestSuiteKatana;
     async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
         let account = test_input.random_paymaster_account.random_accounts()?;
-        let to = Felt::from_hex_unchecked(
-            "0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d", //DEFAULT_STRK_FEE_TOKEN_ADDRESS
-        );
+        let to = network_registry().strk_address;
         let selector = get_selector_from_name("transfer")?;
         let calldata = vec![Felt::from_hex_unchecked("0x1"), Felt::from_hex_unchecked("0x1"), Felt::ZERO];
 
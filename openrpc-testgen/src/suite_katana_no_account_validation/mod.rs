@@ -12,6 +12,7 @@ use url::Url;
 
 use crate::{
     utils::{
+        account_pool::AccountPool,
         random_single_owner_account::RandomSingleOwnerAccount,
         v7::{
             accounts::{
@@ -45,7 +46,7 @@ pub mod test_send_txs_with_invalid_signature;
 
 #[derive(Clone, Debug)]
 pub struct TestSuiteKatanaNoAccountValidation {
-    pub random_paymaster_account: RandomSingleOwnerAccount,
+    pub random_paymaster_account: AccountPool,
     pub paymaster_private_key: Felt,
     pub random_executable_account: RandomSingleOwnerAccount,
     pub account_class_hash: Felt,
@@ -190,8 +191,8 @@ impl SetupableTrait for TestSuiteKatanaNoAccountValidation {
             executable_accounts.push(executable_account);
         }
 
-        let random_executable_account = RandomSingleOwnerAccount { accounts: executable_accounts };
-        let random_paymaster_account = RandomSingleOwnerAccount { accounts: paymaster_accounts };
+        let random_executable_account = RandomSingleOwnerAccount::new(executable_accounts);
+        let random_paymaster_account = AccountPool::new(paymaster_accounts);
 
         let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(
             PathBuf::from_str("target/dev/contracts_contracts_sample_contract_1_HelloStarknet.contract_class.json")?,
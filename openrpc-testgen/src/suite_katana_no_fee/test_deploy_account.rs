@@ -1,23 +1,23 @@
 use crate::{
     assert_eq_result, assert_matches_result,
-    utils::v7::{
-        accounts::{
-            account::ConnectedAccount,
-            deployment::helpers::get_contract_address,
-            factory::{open_zeppelin::OpenZeppelinAccountFactory, AccountFactory},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::ConnectedAccount,
+                factory::{open_zeppelin::OpenZeppelinAccountFactory, AccountFactory},
+            },
+            endpoints::{errors::OpenRpcTestGenError, utils::wait_for_sent_transaction},
+            providers::provider::Provider,
+            signers::{key_pair::SigningKey, local_wallet::LocalWallet, signer::Signer},
         },
-        endpoints::{errors::OpenRpcTestGenError, utils::wait_for_sent_transaction},
-        providers::provider::Provider,
-        signers::{key_pair::SigningKey, local_wallet::LocalWallet, signer::Signer},
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
+use starknet_hive_hashing::address::contract_address;
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{BlockId, BlockTag, DeployAccountTxnReceipt, TxnReceipt};
 
-const DEFAULT_ACCOUNT_CLASS_HASH: Felt =
-    Felt::from_hex_unchecked("0x07dc7899aa655b0aae51eadff6d801a58e97dd99cf4666ee59e704249e51adf2");
-
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -31,14 +31,14 @@ impl RunnableTrait for TestCase {
 
         // Precompute the contract address of the new account with the given parameters:
         let signer = LocalWallet::from(SigningKey::from_random());
-        let class_hash = DEFAULT_ACCOUNT_CLASS_HASH;
+        let class_hash = network_registry().default_account_class_hash;
         let salt = Felt::from_hex_unchecked("0x456");
 
         let factory = OpenZeppelinAccountFactory::new(class_hash, chain_id, &signer, &provider).await?;
         let res = factory.deploy_v1(salt).send().await?;
 
         let ctor_args = [signer.get_public_key().await?.scalar()];
-        let computed_address = get_contract_address(salt, class_hash, &ctor_args, Felt::ZERO);
+        let computed_address = contract_address(salt, class_hash, &ctor_args, Felt::ZERO);
 
         // the contract address in the send tx result must be the same as the computed one
         assert_eq_result!(res.contract_address, computed_address);
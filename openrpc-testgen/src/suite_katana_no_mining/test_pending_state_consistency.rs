@@ -0,0 +1,125 @@
+use crate::{
+    assert_result, openrpc_test,
+    utils::v7::{
+        accounts::{
+            account::{Account, ConnectedAccount},
+            call::Call,
+        },
+        endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+        providers::{
+            jsonrpc::{HttpTransport, JsonRpcClient},
+            provider::Provider,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+use super::wait_for_sent_transaction_katana;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag, MaybePendingBlockWithTxHashes, MaybePendingStateUpdate};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+/// Polls of the pending views before the transaction shows in all of them.
+const MAX_POLLS: usize = 60;
+
+/// Whether each of the pending views reflects the transaction, in the order they are read.
+#[derive(Debug, PartialEq, Eq)]
+struct PendingViews {
+    nonce_advanced: bool,
+    status_known: bool,
+    in_pending_block: bool,
+    in_pending_state_diff: bool,
+}
+
+impl PendingViews {
+    async fn fetch(
+        provider: &JsonRpcClient<HttpTransport>,
+        sender: Felt,
+        expected_nonce: Felt,
+        transaction_hash: Felt,
+    ) -> Result<Self, OpenRpcTestGenError> {
+        let pending = BlockId::Tag(BlockTag::Pending);
+
+        let nonce_advanced = provider.get_nonce(pending.clone(), sender).await? == expected_nonce;
+        let status_known = provider.get_transaction_status(transaction_hash).await.is_ok();
+        let in_pending_block = match provider.get_block_with_tx_hashes(pending.clone()).await? {
+            MaybePendingBlockWithTxHashes::Pending(block) => block.transactions.contains(&transaction_hash),
+            MaybePendingBlockWithTxHashes::Block(_) => false,
+        };
+        let in_pending_state_diff = match provider.get_state_update(pending).await? {
+            MaybePendingStateUpdate::Pending(update) => update
+                .state_diff
+                .nonces
+                .iter()
+                .any(|update| update.contract_address == Some(sender) && update.nonce == Some(expected_nonce)),
+            MaybePendingStateUpdate::Block(_) => false,
+        };
+
+        Ok(Self { nonce_advanced, status_known, in_pending_block, in_pending_state_diff })
+    }
+
+    fn flags(&self) -> [bool; 4] {
+        [self.nonce_advanced, self.status_known, self.in_pending_block, self.in_pending_state_diff]
+    }
+
+    fn all(&self) -> bool {
+        self.flags().iter().all(|flag| *flag)
+    }
+
+    /// The transaction may be received while the views are read, but once a view shows it, every
+    /// view read after it must show it too.
+    fn consistent(&self) -> bool {
+        self.flags().windows(2).all(|pair| !pair[0] || pair[1])
+    }
+}
+
+#[openrpc_test(suite = "katana_no_mining", name = "pending_state_consistency")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteKatanaNoMining;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let provider = account.provider().clone();
+        let sender = account.address();
+
+        let increase_balance_call = Call {
+            to: test_input.deployed_contract_address,
+            selector: get_selector_from_name("increase_balance")?,
+            calldata: vec![Felt::from_hex("0x50")?],
+        };
+
+        let nonce = account.get_nonce().await?;
+        let expected_nonce = nonce + Felt::ONE;
+        let transaction_hash =
+            account.execute_v1(vec![increase_balance_call]).nonce(nonce).send().await?.transaction_hash;
+
+        // Blocks are not mined, so the transaction stays pending while the views are polled.
+        let mut polls = 0;
+        loop {
+            let views = PendingViews::fetch(&provider, sender, expected_nonce, transaction_hash).await?;
+            assert_result!(views.consistent(), format!("Pending views disagree on the transaction: {:?}", views));
+            if views.all() {
+                break;
+            }
+            polls += 1;
+            assert_result!(polls < MAX_POLLS, format!("Transaction is still missing from pending views: {:?}", views));
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+
+        wait_for_sent_transaction_katana(transaction_hash, &account).await?;
+        test_input.dev_client.generate_block().await?;
+
+        // Once mined, the transaction leaves the pending block and state diff together, while the
+        // pending nonce, built on the latest block, stays advanced.
+        let views = PendingViews::fetch(&provider, sender, expected_nonce, transaction_hash).await?;
+        assert_result!(
+            views.nonce_advanced && views.status_known && !views.in_pending_block && !views.in_pending_state_diff,
+            format!("Mined transaction is still in some pending views: {:?}", views)
+        );
+
+        Ok(Self {})
+    }
+}
@@ -6,6 +6,9 @@ use url::Url;
 
 use crate::{
     utils::{
+        account_pool::AccountPool,
+        checkpoint::{fixture, record_fixture},
+        dry_run::is_dry_run,
         random_single_owner_account::RandomSingleOwnerAccount,
         v7::{
             accounts::{
@@ -26,7 +29,7 @@ use crate::{
             },
             providers::{
                 jsonrpc::{HttpTransport, JsonRpcClient},
-                provider::ProviderError,
+                provider::{Provider, ProviderError},
             },
             signers::{key_pair::SigningKey, local_wallet::LocalWallet},
         },
@@ -35,17 +38,18 @@ use crate::{
 };
 
 pub mod suite_deploy;
+pub mod suite_syscalls;
 pub mod test_block_hash_and_number;
 pub mod test_declare_txn_v2;
 pub mod test_declare_txn_v3;
 pub mod test_declare_v3_trace;
 pub mod test_deploy_account_outside_execution;
+pub mod test_deploy_account_receipt_v1;
+pub mod test_deploy_account_receipt_v3;
 pub mod test_deploy_account_trace;
 pub mod test_deploy_account_v1;
 pub mod test_deploy_account_v3;
 pub mod test_erc20_transfer_outside_execution;
-pub mod test_estimate_fee_fri;
-pub mod test_estimate_fee_wei;
 pub mod test_get_block_number;
 pub mod test_get_block_txn_count;
 pub mod test_get_block_with_receipts_declare;
@@ -86,10 +90,14 @@ pub mod test_spec_version;
 pub mod test_syncing;
 pub mod test_trace_block_txn_declare;
 pub mod test_trace_block_txn_deploy_acc;
+pub mod test_upgrade_account_class;
+
+/// Checkpoint fixture holding the address and private key of the executable account.
+const EXECUTABLE_ACCOUNT_FIXTURE: &str = "openrpc_executable_account";
 
 #[derive(Clone, Debug)]
 pub struct TestSuiteOpenRpc {
-    pub random_paymaster_account: RandomSingleOwnerAccount,
+    pub random_paymaster_account: AccountPool,
     pub paymaster_private_key: Felt,
     pub random_executable_account: RandomSingleOwnerAccount,
     pub executable_private_key: Felt,
@@ -110,13 +118,6 @@ impl SetupableTrait for TestSuiteOpenRpc {
     type Input = SetupInput;
 
     async fn setup(setup_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
-        let (executable_account_flattened_sierra_class, executable_account_compiled_class_hash) =
-            get_compiled_contract(
-                PathBuf::from_str("target/dev/contracts_MyAccountExec.contract_class.json")?,
-                PathBuf::from_str("target/dev/contracts_MyAccountExec.compiled_contract_class.json")?,
-            )
-            .await?;
-
         let provider = JsonRpcClient::new(HttpTransport::new(setup_input.urls[0].clone()));
         let chain_id = get_chain_id(&provider).await?;
 
@@ -131,70 +132,35 @@ impl SetupableTrait for TestSuiteOpenRpc {
         );
         paymaster_account.set_block_id(BlockId::Tag(BlockTag::Pending));
 
-        let declare_executable_account_hash = match paymaster_account
-            .declare_v3(executable_account_flattened_sierra_class.clone(), executable_account_compiled_class_hash)
-            .send()
-            .await
-        {
-            Ok(result) => {
-                wait_for_sent_transaction(result.transaction_hash, &paymaster_account).await?;
-                Ok(result.class_hash)
-            }
-            Err(AccountError::Signing(sign_error)) => {
-                if sign_error.to_string().contains("is already declared") {
-                    Ok(parse_class_hash_from_error(&sign_error.to_string())?)
-                } else {
-                    Err(OpenRpcTestGenError::RunnerError(RunnerError::AccountFailure(format!(
-                        "Transaction execution error: {}",
-                        sign_error
-                    ))))
-                }
-            }
-
-            Err(AccountError::Provider(ProviderError::Other(starkneterror))) => {
-                if starkneterror.to_string().contains("is already declared") {
-                    Ok(parse_class_hash_from_error(&starkneterror.to_string())?)
-                } else {
-                    Err(OpenRpcTestGenError::RunnerError(RunnerError::AccountFailure(format!(
-                        "Transaction execution error: {}",
-                        starkneterror
-                    ))))
-                }
+        // A resumed run reuses the executable account of the checkpoint, as long as it is still deployed.
+        let reused_executable_account = match fixture(EXECUTABLE_ACCOUNT_FIXTURE).as_deref() {
+            Some(&[address, private_key])
+                if provider.get_class_hash_at(BlockId::Tag(BlockTag::Pending), address).await.is_ok() =>
+            {
+                Some((address, SigningKey::from_secret_scalar(private_key)))
             }
-            Err(e) => {
-                let full_error_message = format!("{:?}", e);
-                if full_error_message.contains("is already declared") {
-                    Ok(extract_class_hash_from_error(&full_error_message)?)
-                } else {
-                    Err(OpenRpcTestGenError::AccountError(AccountError::Other(full_error_message)))
-                }
+            _ => None,
+        };
+        let (executable_address, executable_signing_key) = match reused_executable_account {
+            Some(executable_account) => executable_account,
+            // A dry run can't deploy the executable account, the paymaster stands in for it: the cases
+            // only reading still run and those broadcasting are stopped as usual.
+            None if is_dry_run() => (setup_input.paymaster_account_address, paymaster_private_key),
+            None => {
+                let executable_account =
+                    deploy_executable_account(&provider, &paymaster_account, setup_input.udc_address).await?;
+                record_fixture(
+                    EXECUTABLE_ACCOUNT_FIXTURE,
+                    vec![executable_account.0, executable_account.1.secret_scalar()],
+                );
+                executable_account
             }
-        }?;
-
-        let executable_account_data =
-            create_account(&provider, AccountType::Oz, Option::None, Some(declare_executable_account_hash)).await?;
-
-        let deploy_executable_account_call: Call = Call {
-            to: setup_input.udc_address,
-            selector: get_selector_from_name("deployContract")?,
-            calldata: vec![
-                declare_executable_account_hash,
-                executable_account_data.salt,
-                Felt::ZERO,
-                Felt::ONE,
-                SigningKey::verifying_key(&executable_account_data.signing_key).scalar(),
-            ],
         };
 
-        let deploy_executable_account_result =
-            paymaster_account.execute_v3(vec![deploy_executable_account_call]).send().await?;
-
-        wait_for_sent_transaction(deploy_executable_account_result.transaction_hash, &paymaster_account).await?;
-
         let mut executable_account = SingleOwnerAccount::new(
             provider.clone(),
-            LocalWallet::from(executable_account_data.signing_key),
-            executable_account_data.address,
+            LocalWallet::from(executable_signing_key),
+            executable_address,
             chain_id,
             ExecutionEncoding::New,
         );
@@ -217,8 +183,8 @@ impl SetupableTrait for TestSuiteOpenRpc {
 
             let executable_account = SingleOwnerAccount::new(
                 provider.clone(),
-                LocalWallet::from(executable_account_data.signing_key),
-                executable_account_data.address,
+                LocalWallet::from(executable_signing_key),
+                executable_address,
                 chain_id,
                 ExecutionEncoding::New,
             );
@@ -228,14 +194,128 @@ impl SetupableTrait for TestSuiteOpenRpc {
         }
 
         Ok(Self {
-            random_executable_account: RandomSingleOwnerAccount { accounts: executable_accounts },
-            random_paymaster_account: RandomSingleOwnerAccount { accounts: paymaster_accounts },
+            random_executable_account: RandomSingleOwnerAccount::new(executable_accounts),
+            random_paymaster_account: AccountPool::new(paymaster_accounts),
             paymaster_private_key: setup_input.paymaster_private_key,
-            executable_private_key: executable_account_data.signing_key.secret_scalar(),
+            executable_private_key: executable_signing_key.secret_scalar(),
             account_class_hash: setup_input.account_class_hash,
             udc_address: setup_input.udc_address,
         })
     }
 }
 
+/// Declares the executable account class if needed and deploys an account of it through the UDC.
+async fn deploy_executable_account(
+    provider: &JsonRpcClient<HttpTransport>,
+    paymaster_account: &SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
+    udc_address: Felt,
+) -> Result<(Felt, SigningKey), OpenRpcTestGenError> {
+    let (executable_account_flattened_sierra_class, executable_account_compiled_class_hash) = get_compiled_contract(
+        PathBuf::from_str("target/dev/contracts_MyAccountExec.contract_class.json")?,
+        PathBuf::from_str("target/dev/contracts_MyAccountExec.compiled_contract_class.json")?,
+    )
+    .await?;
+
+    let declare_executable_account_hash = match paymaster_account
+        .declare_v3(executable_account_flattened_sierra_class.clone(), executable_account_compiled_class_hash)
+        .send()
+        .await
+    {
+        Ok(result) => {
+            wait_for_sent_transaction(result.transaction_hash, paymaster_account).await?;
+            Ok(result.class_hash)
+        }
+        Err(AccountError::Signing(sign_error)) => {
+            if sign_error.to_string().contains("is already declared") {
+                Ok(parse_class_hash_from_error(&sign_error.to_string())?)
+            } else {
+                Err(OpenRpcTestGenError::RunnerError(RunnerError::AccountFailure(format!(
+                    "Transaction execution error: {}",
+                    sign_error
+                ))))
+            }
+        }
+
+        Err(AccountError::Provider(ProviderError::Other(starkneterror))) => {
+            if starkneterror.to_string().contains("is already declared") {
+                Ok(parse_class_hash_from_error(&starkneterror.to_string())?)
+            } else {
+                Err(OpenRpcTestGenError::RunnerError(RunnerError::AccountFailure(format!(
+                    "Transaction execution error: {}",
+                    starkneterror
+                ))))
+            }
+        }
+        Err(e) => {
+            let full_error_message = format!("{:?}", e);
+            if full_error_message.contains("is already declared") {
+                Ok(extract_class_hash_from_error(&full_error_message)?)
+            } else {
+                Err(OpenRpcTestGenError::AccountError(AccountError::Other(full_error_message)))
+            }
+        }
+    }?;
+
+    let executable_account_data =
+        create_account(provider, AccountType::Oz, Option::None, Some(declare_executable_account_hash)).await?;
+
+    let deploy_executable_account_call: Call = Call {
+        to: udc_address,
+        selector: get_selector_from_name("deployContract")?,
+        calldata: vec![
+            declare_executable_account_hash,
+            executable_account_data.salt,
+            Felt::ZERO,
+            Felt::ONE,
+            SigningKey::verifying_key(&executable_account_data.signing_key).scalar(),
+        ],
+    };
+
+    let deploy_executable_account_result =
+        paymaster_account.execute_v3(vec![deploy_executable_account_call]).send().await?;
+
+    wait_for_sent_transaction(deploy_executable_account_result.transaction_hash, paymaster_account).await?;
+
+    Ok((executable_account_data.address, executable_account_data.signing_key))
+}
+
 include!(concat!(env!("OUT_DIR"), "/generated_tests_suite_openrpc.rs"));
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{utils::dry_run::enable_dry_run, utils::mock_server::MockServer, RunnableTrait};
+
+    #[tokio::test]
+    async fn dry_run_setup_broadcasts_nothing_and_read_cases_run() {
+        enable_dry_run();
+        let node = MockServer::json_rpc(|method, _| match method {
+            "starknet_chainId" => Some(json!("0x534e5f5345504f4c4941")),
+            "starknet_specVersion" => Some(json!("0.8.0")),
+            "starknet_syncing" => Some(json!(false)),
+            _ => None,
+        })
+        .await;
+
+        let setup_input = SetupInput {
+            urls: vec![node.url.clone()],
+            paymaster_account_address: Felt::from_hex_unchecked("0x1234"),
+            paymaster_private_key: Felt::from_hex_unchecked("0x5678"),
+            account_class_hash: Felt::from_hex_unchecked("0x9abc"),
+            udc_address: Felt::from_hex_unchecked("0xdef0"),
+        };
+        let suite = TestSuiteOpenRpc::setup(&setup_input).await.unwrap();
+        assert_eq!(suite.random_executable_account.accounts[0].address(), setup_input.paymaster_account_address);
+
+        test_spec_version::TestCase::run(&suite).await.unwrap();
+        test_syncing::TestCase::run(&suite).await.unwrap();
+
+        let methods: Vec<_> =
+            node.requests().iter().map(|request| request.body["method"].as_str().unwrap().to_string()).collect();
+        assert!(methods.iter().all(|method| !method.starts_with("starknet_add")), "{methods:?}");
+        assert!(methods.contains(&"starknet_specVersion".to_string()));
+        assert!(methods.contains(&"starknet_syncing".to_string()));
+    }
+}
@@ -4,7 +4,7 @@ use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{BlockId, ClassAndTxnHash, DeclareTxn, EventFilterWithPageRequest, Txn};
 use tracing::info;
 
-use super::RandomSingleOwnerAccount;
+use super::{AccountPool, RandomSingleOwnerAccount};
 use crate::{
     utils::v7::{
         accounts::account::{Account, AccountError, ConnectedAccount},
@@ -33,7 +33,7 @@ pub mod test_get_txn_receipt_deploy;
 
 #[derive(Clone, Debug)]
 pub struct TestSuiteDeploy {
-    pub random_paymaster_account: RandomSingleOwnerAccount,
+    pub random_paymaster_account: AccountPool,
     pub random_executable_account: RandomSingleOwnerAccount,
     pub declaration_result: ClassAndTxnHash<Felt>,
     pub account_class_hash: Felt,
@@ -2,7 +2,7 @@ use rand::{rngs::StdRng, RngCore, SeedableRng};
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::TxnReceipt;
 
-use super::RandomSingleOwnerAccount;
+use super::{AccountPool, RandomSingleOwnerAccount};
 use crate::{
     utils::v7::{
         accounts::account::ConnectedAccount,
@@ -32,10 +32,12 @@ pub mod test_invoke_contract_v3;
 pub mod test_invoke_v3_trace;
 pub mod test_simulate_invoke_v3_skip_fee;
 pub mod test_simulate_invoke_v3_skip_validate_skip_fee;
+pub mod test_simulate_vs_state_update_invoke_v3;
+pub mod test_simulate_vs_trace_invoke_v3;
 pub mod test_trace_block_txn_invoke;
 
 pub struct TestSuiteContractCalls {
-    pub random_paymaster_account: RandomSingleOwnerAccount,
+    pub random_paymaster_account: AccountPool,
     pub random_executable_account: RandomSingleOwnerAccount,
     pub deployment_receipt: TxnReceipt<Felt>,
     pub deployed_contract_address: Felt,
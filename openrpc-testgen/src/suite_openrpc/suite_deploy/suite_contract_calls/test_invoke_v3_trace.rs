@@ -3,9 +3,12 @@ use crate::utils::v7::endpoints::utils::wait_for_sent_transaction;
 use crate::utils::v7::providers::provider::Provider;
 use crate::{assert_matches_result, assert_result, RandomizableAccountsTrait};
 use crate::{
-    utils::v7::{
-        accounts::call::Call,
-        endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::call::Call,
+            endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+        },
     },
     RunnableTrait,
 };
@@ -14,9 +17,6 @@ use starknet_types_rpc::{
     BlockId, BlockTag, EntryPointType, ExecuteInvocation, FunctionCall, InvokeTransactionTrace, TransactionTrace,
 };
 
-pub const STRK_ERC20_CONTRACT_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
-
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -103,11 +103,11 @@ impl RunnableTrait for TestCase {
                 OpenRpcTestGenError::Other("Deployed contract address not found in storage diffs".to_string())
             })?;
 
-        // index of STRK_ERC20_CONTRACT_ADDRESS in storage_diffs
-        let strk_erc20_index =
-            storage_diff.iter().position(|diff| diff.address == STRK_ERC20_CONTRACT_ADDRESS).ok_or_else(|| {
-                OpenRpcTestGenError::Other("STRK_ERC20_CONTRACT_ADDRESS not found in storage diffs".to_string())
-            })?;
+        // index of the STRK token address in storage_diffs
+        let strk_erc20_index = storage_diff
+            .iter()
+            .position(|diff| diff.address == network_registry().strk_address)
+            .ok_or_else(|| OpenRpcTestGenError::Other("STRK token address not found in storage diffs".to_string()))?;
 
         // Retrieve the first call from function_invocation
         let function_invocation_call = function_invocation
@@ -151,12 +151,13 @@ impl RunnableTrait for TestCase {
             )
         );
 
-        // fee_transfer_invocation STRK_ERC20_CONTRACT_ADDRESS
+        // fee_transfer_invocation STRK token address
         assert_result!(
-            fee_transfer_invocation.function_call.contract_address == STRK_ERC20_CONTRACT_ADDRESS,
+            fee_transfer_invocation.function_call.contract_address == network_registry().strk_address,
             format!(
                 "Contract address mismatch in fee transfer: expected {:?}, but found {:?}",
-                STRK_ERC20_CONTRACT_ADDRESS, fee_transfer_invocation.function_call.contract_address
+                network_registry().strk_address,
+                fee_transfer_invocation.function_call.contract_address
             )
         );
 
@@ -216,20 +217,21 @@ impl RunnableTrait for TestCase {
             format!("Balance mismatch in storage diff: expected {:?}, but found {:?}", balance, storage_balance)
         );
 
-        // Retrieve the storage diff for STRK_ERC20_CONTRACT_ADDRESS
+        // Retrieve the storage diff for the STRK token address
         let strk_erc20_storage_diff = storage_diff.get(strk_erc20_index).ok_or_else(|| {
             OpenRpcTestGenError::Other(format!(
-                "No storage diff entry found for STRK_ERC20_CONTRACT_ADDRESS at index {}",
+                "No storage diff entry found for the STRK token address at index {}",
                 strk_erc20_index
             ))
         })?;
 
-        // Validate the STRK_ERC20_CONTRACT_ADDRESS in the storage diff
+        // Validate the STRK token address in the storage diff
         assert_result!(
-            strk_erc20_storage_diff.address == STRK_ERC20_CONTRACT_ADDRESS,
+            strk_erc20_storage_diff.address == network_registry().strk_address,
             format!(
-                "STRK_ERC20_CONTRACT_ADDRESS mismatch in storage diff: expected {:?}, but found {:?}",
-                STRK_ERC20_CONTRACT_ADDRESS, strk_erc20_storage_diff.address
+                "STRK token address mismatch in storage diff: expected {:?}, but found {:?}",
+                network_registry().strk_address,
+                strk_erc20_storage_diff.address
             )
         );
 
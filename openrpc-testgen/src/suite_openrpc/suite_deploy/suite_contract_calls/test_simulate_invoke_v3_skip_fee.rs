@@ -2,9 +2,12 @@ use crate::utils::v7::accounts::account::{Account, ConnectedAccount};
 use crate::utils::v7::providers::provider::Provider;
 use crate::{assert_eq_result, assert_matches_result, assert_result, RandomizableAccountsTrait};
 use crate::{
-    utils::v7::{
-        accounts::call::Call,
-        endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::call::Call,
+            endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+        },
     },
     RunnableTrait,
 };
@@ -14,9 +17,6 @@ use starknet_types_rpc::{
     SimulateTransactionsResult, TransactionTrace,
 };
 
-pub const STRK_ERC20_CONTRACT_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
-
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -291,10 +291,10 @@ impl RunnableTrait for TestCase {
             )
         );
 
-        // Validate that STRK_ERC20_CONTRACT_ADDRESS is not in storage_diffs
+        // Validate that the STRK token address is not in storage_diffs
         assert!(
-            !storage_diff.iter().any(|diff| diff.address == STRK_ERC20_CONTRACT_ADDRESS),
-            "STRK_ERC20_CONTRACT_ADDRESS should not be in storage diffs"
+            !storage_diff.iter().any(|diff| diff.address == network_registry().strk_address),
+            "STRK token address should not be in storage diffs"
         );
 
         // Validate the contract address in validate_invocation
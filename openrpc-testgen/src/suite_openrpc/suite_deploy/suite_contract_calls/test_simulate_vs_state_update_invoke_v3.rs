@@ -0,0 +1,65 @@
+use crate::utils::network_registry::network_registry;
+use crate::utils::simulation_diff::{
+    block_state_diff_of, compare_state_diffs, fee_transfer_entries, simulated_state_diff,
+};
+use crate::utils::v7::accounts::account::{Account, ConnectedAccount};
+use crate::utils::v7::endpoints::utils::wait_for_sent_transaction;
+use crate::{assert_result, RandomizableAccountsTrait};
+use crate::{
+    utils::v7::{
+        accounts::call::Call,
+        endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+    },
+    RunnableTrait,
+};
+use starknet_types_core::felt::Felt;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteContractCalls;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let increase_balance_call = Call {
+            to: test_input.deployed_contract_address,
+            selector: get_selector_from_name("increase_balance")?,
+            calldata: vec![Felt::from_hex_unchecked("0x123")],
+        };
+
+        let account = test_input.random_paymaster_account.random_accounts()?;
+
+        let simulated = account.execute_v3(vec![increase_balance_call.clone()]).simulate(false, false).await?;
+        let simulated_diff = simulated_state_diff(&simulated).cloned().ok_or_else(|| {
+            OpenRpcTestGenError::Other("State diff is missing in the simulated invoke trace".to_string())
+        })?;
+
+        let invoke_result = account.execute_v3(vec![increase_balance_call]).send().await?;
+        wait_for_sent_transaction(invoke_result.transaction_hash, &account).await?;
+
+        let (block_diff, block_transactions) =
+            block_state_diff_of(account.provider(), invoke_result.transaction_hash).await?.ok_or_else(|| {
+                OpenRpcTestGenError::Other(format!(
+                    "Transaction {:#x} is in neither the latest nor the pending block",
+                    invoke_result.transaction_hash
+                ))
+            })?;
+
+        // The fee of the simulation is an estimate, so the balances moved by the fee transfer differ.
+        let registry = network_registry();
+        let fee_accounts = [account.address(), registry.sequencer_address];
+        let mut ignored = fee_transfer_entries(registry.strk_address, &fee_accounts)?;
+        ignored.extend(fee_transfer_entries(registry.eth_address, &fee_accounts)?);
+
+        let mismatches = compare_state_diffs(&simulated_diff, &block_diff, block_transactions == 1, &ignored);
+        assert_result!(
+            mismatches.is_empty(),
+            format!(
+                "Simulated state diff differs from the state update of the block: {}",
+                mismatches.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+            )
+        );
+
+        Ok(Self {})
+    }
+}
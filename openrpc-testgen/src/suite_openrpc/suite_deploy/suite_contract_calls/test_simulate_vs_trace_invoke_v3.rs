@@ -0,0 +1,219 @@
+use crate::utils::display::{display_address, display_event_keys, display_felts, display_selector};
+use crate::utils::v7::accounts::account::{Account, ConnectedAccount};
+use crate::utils::v7::endpoints::utils::wait_for_sent_transaction;
+use crate::utils::v7::providers::provider::Provider;
+use crate::{assert_result, RandomizableAccountsTrait};
+use crate::{
+    utils::v7::{
+        accounts::call::Call,
+        endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+    },
+    RunnableTrait,
+};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{
+    ExecuteInvocation, FunctionInvocation, InvokeTransactionTrace, SimulateTransactionsResult, TransactionTrace,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteContractCalls;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let increase_balance_call = Call {
+            to: test_input.deployed_contract_address,
+            selector: get_selector_from_name("increase_balance")?,
+            calldata: vec![Felt::from_hex_unchecked("0x123")],
+        };
+
+        let account = test_input.random_paymaster_account.random_accounts()?;
+
+        let simulated = account.execute_v3(vec![increase_balance_call.clone()]).simulate(false, false).await?;
+        let simulated_trace = match simulated {
+            SimulateTransactionsResult { transaction_trace: Some(TransactionTrace::Invoke(trace)), .. } => trace,
+            other => {
+                return Err(OpenRpcTestGenError::Other(format!(
+                    "Expected invoke trace in simulate transaction result, got {:?}",
+                    other
+                )))
+            }
+        };
+
+        let invoke_result = account.execute_v3(vec![increase_balance_call]).send().await?;
+        wait_for_sent_transaction(invoke_result.transaction_hash, &account).await?;
+
+        let executed_trace = match account.provider().trace_transaction(invoke_result.transaction_hash).await? {
+            TransactionTrace::Invoke(trace) => trace,
+            other => {
+                return Err(OpenRpcTestGenError::Other(format!("Expected invoke trace, got {:?}", other)));
+            }
+        };
+
+        let (simulated_execute, executed_execute) = execute_invocations(&simulated_trace, &executed_trace)?;
+        compare_invocations("execute_invocation", simulated_execute, executed_execute)?;
+
+        match (&simulated_trace.validate_invocation, &executed_trace.validate_invocation) {
+            (Some(simulated), Some(executed)) => compare_invocations("validate_invocation", simulated, executed)?,
+            (simulated, executed) => {
+                assert_result!(
+                    simulated.is_none() && executed.is_none(),
+                    format!(
+                        "validate_invocation presence mismatch: simulated {:?}, traced {:?}",
+                        simulated.is_some(),
+                        executed.is_some()
+                    )
+                );
+            }
+        }
+
+        // The fee transfer depends on the actual fee charged, so only its shape is compared.
+        match (&simulated_trace.fee_transfer_invocation, &executed_trace.fee_transfer_invocation) {
+            (Some(simulated), Some(executed)) => {
+                assert_result!(
+                    simulated.function_call.contract_address == executed.function_call.contract_address
+                        && simulated.function_call.entry_point_selector == executed.function_call.entry_point_selector,
+                    format!(
+                        "fee_transfer_invocation target mismatch: simulated {:?}, traced {:?}",
+                        simulated.function_call, executed.function_call
+                    )
+                );
+            }
+            (simulated, executed) => {
+                return Err(OpenRpcTestGenError::Other(format!(
+                    "fee_transfer_invocation expected in both traces: simulated {:?}, traced {:?}",
+                    simulated.is_some(),
+                    executed.is_some()
+                )));
+            }
+        }
+
+        Ok(Self {})
+    }
+}
+
+fn execute_invocations<'a>(
+    simulated: &'a InvokeTransactionTrace<Felt>,
+    executed: &'a InvokeTransactionTrace<Felt>,
+) -> Result<(&'a FunctionInvocation<Felt>, &'a FunctionInvocation<Felt>), OpenRpcTestGenError> {
+    match (&simulated.execute_invocation, &executed.execute_invocation) {
+        (ExecuteInvocation::FunctionInvocation(simulated), ExecuteInvocation::FunctionInvocation(executed)) => {
+            Ok((simulated, executed))
+        }
+        (simulated, executed) => Err(OpenRpcTestGenError::Other(format!(
+            "Expected successful execute_invocation in both traces: simulated {:?}, traced {:?}",
+            simulated, executed
+        ))),
+    }
+}
+
+/// Recursively compares two call trees: entrypoints, calldata, results and emitted events.
+/// Execution resources are not compared since they are not part of the call semantics.
+fn compare_invocations(
+    path: &str,
+    simulated: &FunctionInvocation<Felt>,
+    executed: &FunctionInvocation<Felt>,
+) -> Result<(), OpenRpcTestGenError> {
+    assert_result!(
+        simulated.function_call.contract_address == executed.function_call.contract_address,
+        format!(
+            "{path}: contract address mismatch: simulated {}, traced {}",
+            display_address(None, &simulated.function_call.contract_address),
+            display_address(None, &executed.function_call.contract_address)
+        )
+    );
+    assert_result!(
+        simulated.function_call.entry_point_selector == executed.function_call.entry_point_selector,
+        format!(
+            "{path}: entry point selector mismatch: simulated {}, traced {}",
+            display_selector(&simulated.function_call.entry_point_selector),
+            display_selector(&executed.function_call.entry_point_selector)
+        )
+    );
+    assert_result!(
+        simulated.function_call.calldata == executed.function_call.calldata,
+        format!(
+            "{path}: calldata mismatch: simulated {}, traced {}",
+            display_felts(&simulated.function_call.calldata),
+            display_felts(&executed.function_call.calldata)
+        )
+    );
+    assert_result!(
+        simulated.caller_address == executed.caller_address,
+        format!(
+            "{path}: caller address mismatch: simulated {}, traced {}",
+            display_address(None, &simulated.caller_address),
+            display_address(None, &executed.caller_address)
+        )
+    );
+    assert_result!(
+        simulated.class_hash == executed.class_hash,
+        format!("{path}: class hash mismatch: simulated {:?}, traced {:?}", simulated.class_hash, executed.class_hash)
+    );
+    assert_result!(
+        simulated.entry_point_type == executed.entry_point_type,
+        format!(
+            "{path}: entry point type mismatch: simulated {:?}, traced {:?}",
+            simulated.entry_point_type, executed.entry_point_type
+        )
+    );
+    assert_result!(
+        simulated.result == executed.result,
+        format!(
+            "{path}: result mismatch: simulated {}, traced {}",
+            display_felts(&simulated.result),
+            display_felts(&executed.result)
+        )
+    );
+
+    let simulated_events =
+        serde_json::to_value(&simulated.events).map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+    let executed_events =
+        serde_json::to_value(&executed.events).map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+    assert_result!(
+        simulated.events.len() == executed.events.len(),
+        format!(
+            "{path}: events count mismatch: simulated {}, traced {}",
+            simulated.events.len(),
+            executed.events.len()
+        )
+    );
+    if let (Some(simulated_list), Some(executed_list)) = (simulated_events.as_array(), executed_events.as_array()) {
+        for (index, (simulated_event, executed_event)) in simulated_list.iter().zip(executed_list).enumerate() {
+            let simulated_keys = event_keys(simulated_event)?;
+            let executed_keys = event_keys(executed_event)?;
+            assert_result!(
+                simulated_keys == executed_keys,
+                format!(
+                    "{path}.events[{index}]: keys mismatch: simulated {}, traced {}",
+                    display_event_keys(&simulated_keys),
+                    display_event_keys(&executed_keys)
+                )
+            );
+        }
+    }
+    assert_result!(
+        simulated_events == executed_events,
+        format!("{path}: events mismatch: simulated {}, traced {}", simulated_events, executed_events)
+    );
+
+    assert_result!(
+        simulated.calls.len() == executed.calls.len(),
+        format!(
+            "{path}: nested calls count mismatch: simulated {}, traced {}",
+            simulated.calls.len(),
+            executed.calls.len()
+        )
+    );
+    for (index, (simulated_call, executed_call)) in simulated.calls.iter().zip(executed.calls.iter()).enumerate() {
+        compare_invocations(&format!("{path}.calls[{index}]"), simulated_call, executed_call)?;
+    }
+
+    Ok(())
+}
+
+fn event_keys(event: &serde_json::Value) -> Result<Vec<Felt>, OpenRpcTestGenError> {
+    serde_json::from_value(event.get("keys").cloned().unwrap_or_default())
+        .map_err(|e| OpenRpcTestGenError::Other(e.to_string()))
+}
@@ -1,10 +1,13 @@
 use crate::{
     assert_result,
-    utils::v7::{
-        accounts::account::{starknet_keccak, Account, ConnectedAccount},
-        contract::factory::ContractFactory,
-        endpoints::{errors::OpenRpcTestGenError, utils::wait_for_sent_transaction},
-        providers::provider::Provider,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::account::{starknet_keccak, Account, ConnectedAccount},
+            contract::factory::ContractFactory,
+            endpoints::{errors::OpenRpcTestGenError, utils::wait_for_sent_transaction},
+            providers::provider::Provider,
+        },
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
@@ -12,8 +15,6 @@ use rand::{rngs::StdRng, RngCore, SeedableRng};
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{BlockId, BroadcastedInvokeTxn, BroadcastedTxn, InvokeTxn, MaybePendingBlockWithTxs, Txn};
 use t9n::txn_validation::invoke::verify_invoke_v1_signature;
-const UDC_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
 const MAX_FEE: Felt = Felt::from_hex_unchecked("0x2977");
 
 #[derive(Clone, Debug)]
@@ -123,8 +124,12 @@ impl RunnableTrait for TestCase {
             .ok_or_else(|| OpenRpcTestGenError::Other("Missing last calldata element".to_string()))?;
 
         assert_result!(
-            calldata_second == UDC_ADDRESS,
-            format!("Expected second calldata element to be {:#?}, got {:#?}", UDC_ADDRESS, calldata_second)
+            calldata_second == network_registry().udc_address,
+            format!(
+                "Expected second calldata element to be {:#?}, got {:#?}",
+                network_registry().udc_address,
+                calldata_second
+            )
         );
 
         let calldata_third = *txn
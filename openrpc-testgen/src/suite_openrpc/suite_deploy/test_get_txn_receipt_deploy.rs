@@ -1,22 +1,17 @@
-use crate::assert_result;
+use crate::utils::network_registry::network_registry;
 use crate::utils::v7::accounts::account::{starknet_keccak, Account, ConnectedAccount};
 use crate::utils::v7::contract::factory::ContractFactory;
 use crate::utils::v7::endpoints::errors::CallError;
 use crate::utils::v7::endpoints::utils::wait_for_sent_transaction;
 use crate::utils::v7::providers::provider::Provider;
 use crate::RandomizableAccountsTrait;
+use crate::{assert_receipt_block_info, assert_receipt_common, assert_result};
 use crate::{utils::v7::endpoints::errors::OpenRpcTestGenError, RunnableTrait};
 use rand::rngs::StdRng;
 use rand::{RngCore, SeedableRng};
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{PriceUnit, TxnFinalityStatus, TxnReceipt};
 
-const STRK_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D");
-const SEQUENCER_ADDRESS: Felt = Felt::from_hex_unchecked("0x123");
-const UDC_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
-
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -60,6 +55,15 @@ impl RunnableTrait for TestCase {
         };
 
         let common_receipt_properties = receipt.common_receipt_properties;
+        assert_receipt_common!(common_receipt_properties, invoke_result.transaction_hash, TxnFinalityStatus::L2);
+
+        let block_info = test_input
+            .random_paymaster_account
+            .provider()
+            .get_transaction_receipt_block_info(invoke_result.transaction_hash)
+            .await?;
+        assert_receipt_block_info!(block_info);
+
         let actual_fee = common_receipt_properties.actual_fee;
         assert_result!(
             actual_fee.amount == estimate_fee.overall_fee,
@@ -72,28 +76,11 @@ impl RunnableTrait for TestCase {
             format!("Actual fee unit expected: {:?}, actual: {:?}", expected_unit, actual_fee.unit)
         );
 
-        let expected_finality_status = TxnFinalityStatus::L2;
-        assert_result!(
-            common_receipt_properties.finality_status == expected_finality_status,
-            format!(
-                "Expected finality status: {:?}, actual: {:?}",
-                expected_finality_status, common_receipt_properties.finality_status
-            )
-        );
-
         assert_result!(
             common_receipt_properties.messages_sent.is_empty(),
             format!("Expected no messages sent, actual: {:?}", common_receipt_properties.messages_sent)
         );
 
-        assert_result!(
-            common_receipt_properties.transaction_hash == invoke_result.transaction_hash,
-            format!(
-                "Expected transaction hash: {:?}, actual: {:?}",
-                invoke_result.transaction_hash, common_receipt_properties.transaction_hash
-            )
-        );
-
         let execution_status = match common_receipt_properties.anon {
             starknet_types_rpc::Anonymous::Successful(status) => status.execution_status,
             _ => {
@@ -113,8 +100,12 @@ impl RunnableTrait for TestCase {
 
         let first_event = events.first().ok_or_else(|| OpenRpcTestGenError::Other("Event not found".to_string()))?;
         assert_result!(
-            first_event.from_address == UDC_ADDRESS,
-            format!("Expected event from address to be {:?}, got {:?}", UDC_ADDRESS, first_event.from_address)
+            first_event.from_address == network_registry().udc_address,
+            format!(
+                "Expected event from address to be {:?}, got {:?}",
+                network_registry().udc_address,
+                first_event.from_address
+            )
         );
 
         assert_result!(
@@ -199,8 +190,12 @@ impl RunnableTrait for TestCase {
             .ok_or_else(|| OpenRpcTestGenError::Other("Event missing".to_string()))?;
 
         assert_result!(
-            second_event.from_address == STRK_ADDRESS,
-            format!("Expected event from address to be {:?}, got {:?}", STRK_ADDRESS, second_event.from_address)
+            second_event.from_address == network_registry().strk_address,
+            format!(
+                "Expected event from address to be {:?}, got {:?}",
+                network_registry().strk_address,
+                second_event.from_address
+            )
         );
 
         let second_event_data_first = *second_event
@@ -256,10 +251,11 @@ impl RunnableTrait for TestCase {
             .get(2)
             .ok_or_else(|| OpenRpcTestGenError::Other("Missing second event key".to_string()))?;
         assert_result!(
-            second_event_keys_third == SEQUENCER_ADDRESS,
+            second_event_keys_third == network_registry().sequencer_address,
             format!(
                 "Invalid sequencer address in event keys, expected {}, got {:?}",
-                SEQUENCER_ADDRESS, second_event_keys_third
+                network_registry().sequencer_address,
+                second_event_keys_third
             )
         );
         Ok(Self {})
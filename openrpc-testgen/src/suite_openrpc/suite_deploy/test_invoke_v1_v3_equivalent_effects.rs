@@ -0,0 +1,51 @@
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    assert_result, openrpc_test,
+    utils::{
+        v7::{
+            accounts::call::Call,
+            endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+        },
+        version_replay::replay_v1_v3,
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "deploy", name = "invoke_v1_v3_equivalent_effects")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteDeploy;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let class_hash = test_input.declaration_result.class_hash;
+
+        // A plain storage variable and a map entry, both written on a fresh instance by each version.
+        let scenarios = [
+            ("increase_balance", vec![Felt::from_hex_unchecked("0x123")]),
+            ("deposit_l2", vec![Felt::from_hex_unchecked("0xdeadbeef"), Felt::from_hex_unchecked("0x456")]),
+        ];
+
+        for (entrypoint, calldata) in scenarios {
+            let selector = get_selector_from_name(entrypoint)?;
+            let replay = replay_v1_v3(&account, class_hash, vec![], |instance| {
+                Ok(Call { to: instance, selector, calldata: calldata.clone() })
+            })
+            .await?;
+
+            assert_result!(replay.v1.succeeded, format!("{entrypoint} reverted in the invoke v1"));
+            assert_result!(!replay.v1.storage.is_empty(), format!("{entrypoint} wrote no storage in the invoke v1"));
+
+            let differences = replay.differences();
+            assert_result!(
+                differences.is_empty(),
+                format!("{entrypoint} effects differ between invoke v1 and v3: {}", differences.join("; "))
+            );
+        }
+
+        Ok(Self {})
+    }
+}
@@ -0,0 +1,118 @@
+use std::{fs::File, path::PathBuf, str::FromStr};
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use serde_json::Value;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{
+    BlockId, BlockTag, ExecuteInvocation, FeeEstimate, SimulateTransactionsResult, TransactionTrace,
+};
+
+use super::AccountPool;
+use crate::{
+    utils::v7::{
+        accounts::{
+            account::{Account, ConnectedAccount},
+            call::Call,
+        },
+        contract::{factory::ContractFactory, HashAndFlatten, SierraClass},
+        endpoints::{
+            declare_contract::{get_compiled_contract, RunnerError},
+            errors::OpenRpcTestGenError,
+            utils::{get_selector_from_name, wait_for_sent_transaction},
+        },
+        providers::provider::Provider,
+    },
+    RandomizableAccountsTrait, SetupableTrait,
+};
+
+pub mod test_keccak_resources_scale;
+pub mod test_secp256k1_resources_scale;
+
+const SYSCALL_HEAVY_SIERRA_PATH: &str = "target/dev/contracts_contracts_syscall_heavy_SyscallHeavy.contract_class.json";
+const SYSCALL_HEAVY_CASM_PATH: &str =
+    "target/dev/contracts_contracts_syscall_heavy_SyscallHeavy.compiled_contract_class.json";
+
+#[derive(Clone, Debug)]
+pub struct TestSuiteSyscalls {
+    pub random_paymaster_account: AccountPool,
+    pub syscall_contract_address: Felt,
+}
+
+impl SetupableTrait for TestSuiteSyscalls {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn setup(setup_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let sierra_file = File::open(SYSCALL_HEAVY_SIERRA_PATH)
+            .map_err(|e| RunnerError::ReadFileError(format!("{SYSCALL_HEAVY_SIERRA_PATH}: {e}")))?;
+        let sierra_class: SierraClass = serde_json::from_reader(sierra_file).map_err(RunnerError::from)?;
+        let class_hash = sierra_class.class_hash().map_err(RunnerError::from)?;
+
+        let account = setup_input.random_paymaster_account.random_accounts()?;
+        // The class is shared by all runs against the same node, so it is only declared once.
+        if account.provider().get_class(BlockId::Tag(BlockTag::Pending), class_hash).await.is_err() {
+            let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(
+                PathBuf::from_str(SYSCALL_HEAVY_SIERRA_PATH)?,
+                PathBuf::from_str(SYSCALL_HEAVY_CASM_PATH)?,
+            )
+            .await?;
+            let declaration = account.declare_v3(flattened_sierra_class, compiled_class_hash).send().await?;
+            wait_for_sent_transaction(declaration.transaction_hash, &account).await?;
+        }
+
+        let mut salt_buffer = [0u8; 32];
+        StdRng::from_entropy().fill_bytes(&mut salt_buffer[1..]);
+
+        let factory = ContractFactory::new(class_hash, setup_input.random_paymaster_account.random_accounts()?);
+        let deployment = factory.deploy_v3(vec![], Felt::from_bytes_be(&salt_buffer), true);
+        let syscall_contract_address = deployment.deployed_address();
+        let deployment_result = deployment.send().await?;
+        wait_for_sent_transaction(deployment_result.transaction_hash, &account).await?;
+
+        Ok(Self { random_paymaster_account: setup_input.random_paymaster_account.clone(), syscall_contract_address })
+    }
+}
+
+/// Fee estimate and execution resources of calling `entrypoint` of the syscall heavy contract with
+/// `rounds`, from a simulation of the transaction.
+pub async fn simulate_rounds(
+    test_input: &TestSuiteSyscalls,
+    entrypoint: &str,
+    rounds: u32,
+) -> Result<(FeeEstimate<Felt>, Value), OpenRpcTestGenError> {
+    let call = Call {
+        to: test_input.syscall_contract_address,
+        selector: get_selector_from_name(entrypoint)?,
+        calldata: vec![Felt::from(rounds)],
+    };
+    let simulation =
+        test_input.random_paymaster_account.random_accounts()?.execute_v3(vec![call]).simulate(false, true).await?;
+
+    let (fee_estimation, invoke_trace) = match simulation {
+        SimulateTransactionsResult {
+            fee_estimation: Some(fee_estimation),
+            transaction_trace: Some(TransactionTrace::Invoke(invoke_trace)),
+        } => (fee_estimation, invoke_trace),
+        other => {
+            return Err(OpenRpcTestGenError::Other(format!(
+                "Simulation of {entrypoint}({rounds}) is missing the fee estimation or invoke trace: {other:?}"
+            )))
+        }
+    };
+    if !matches!(invoke_trace.execute_invocation, ExecuteInvocation::FunctionInvocation(_)) {
+        return Err(OpenRpcTestGenError::Other(format!(
+            "Simulation of {entrypoint}({rounds}) reverted: {:?}",
+            invoke_trace.execute_invocation
+        )));
+    }
+
+    let execution_resources = serde_json::to_value(&invoke_trace.execution_resources)
+        .map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+    Ok((fee_estimation, execution_resources))
+}
+
+/// A counter of the execution resources, absent counters being zero.
+pub fn resource(execution_resources: &Value, name: &str) -> u64 {
+    execution_resources.get(name).and_then(Value::as_u64).unwrap_or_default()
+}
+
+include!(concat!(env!("OUT_DIR"), "/generated_tests_suite_openrpc_suite_syscalls.rs"));
@@ -0,0 +1,50 @@
+use crate::{assert_result, utils::v7::endpoints::errors::OpenRpcTestGenError, RunnableTrait};
+
+use super::{resource, simulate_rounds};
+
+const LIGHT_ROUNDS: u32 = 1;
+const HEAVY_ROUNDS: u32 = 32;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteSyscalls;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let (light_fee, light_resources) = simulate_rounds(test_input, "keccak_rounds", LIGHT_ROUNDS).await?;
+        let (heavy_fee, heavy_resources) = simulate_rounds(test_input, "keccak_rounds", HEAVY_ROUNDS).await?;
+
+        // Every keccak syscall is accounted as an application of the keccak builtin.
+        let light_keccak = resource(&light_resources, "keccak_builtin_applications");
+        let heavy_keccak = resource(&heavy_resources, "keccak_builtin_applications");
+        assert_result!(
+            heavy_keccak >= light_keccak + u64::from(HEAVY_ROUNDS - LIGHT_ROUNDS),
+            format!(
+                "Keccak builtin applications do not scale with keccak syscalls: {light_keccak} for {LIGHT_ROUNDS} \
+                 rounds, {heavy_keccak} for {HEAVY_ROUNDS} rounds"
+            )
+        );
+
+        let light_steps = resource(&light_resources, "steps");
+        let heavy_steps = resource(&heavy_resources, "steps");
+        assert_result!(
+            heavy_steps > light_steps,
+            format!(
+                "Steps do not grow with keccak syscalls: {light_steps} for {LIGHT_ROUNDS} rounds, {heavy_steps} for \
+                 {HEAVY_ROUNDS} rounds"
+            )
+        );
+
+        assert_result!(
+            heavy_fee.overall_fee > light_fee.overall_fee,
+            format!(
+                "Fee estimate does not grow with keccak syscalls: {} for {LIGHT_ROUNDS} rounds, {} for \
+                 {HEAVY_ROUNDS} rounds",
+                light_fee.overall_fee, heavy_fee.overall_fee
+            )
+        );
+
+        Ok(Self {})
+    }
+}
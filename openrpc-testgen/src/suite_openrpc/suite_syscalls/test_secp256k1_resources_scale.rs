@@ -0,0 +1,43 @@
+use crate::{assert_result, utils::v7::endpoints::errors::OpenRpcTestGenError, RunnableTrait};
+
+use super::{resource, simulate_rounds};
+
+const LIGHT_ROUNDS: u32 = 1;
+const HEAVY_ROUNDS: u32 = 8;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteSyscalls;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let (light_fee, light_resources) = simulate_rounds(test_input, "secp256k1_rounds", LIGHT_ROUNDS).await?;
+        let (heavy_fee, heavy_resources) = simulate_rounds(test_input, "secp256k1_rounds", HEAVY_ROUNDS).await?;
+
+        // A secp256k1 multiplication costs tens of thousands of steps and range checks, which dwarf
+        // the cost of the surrounding loop.
+        for name in ["steps", "range_check_builtin_applications"] {
+            let light = resource(&light_resources, name);
+            let heavy = resource(&heavy_resources, name);
+            assert_result!(
+                heavy > light * u64::from(HEAVY_ROUNDS) / 2,
+                format!(
+                    "{name} do not scale with secp256k1 syscalls: {light} for {LIGHT_ROUNDS} rounds, {heavy} for \
+                     {HEAVY_ROUNDS} rounds"
+                )
+            );
+        }
+
+        assert_result!(
+            heavy_fee.overall_fee > light_fee.overall_fee,
+            format!(
+                "Fee estimate does not grow with secp256k1 syscalls: {} for {LIGHT_ROUNDS} rounds, {} for \
+                 {HEAVY_ROUNDS} rounds",
+                light_fee.overall_fee, heavy_fee.overall_fee
+            )
+        );
+
+        Ok(Self {})
+    }
+}
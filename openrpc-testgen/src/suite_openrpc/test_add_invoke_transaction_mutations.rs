@@ -0,0 +1,53 @@
+use starknet_types_core::felt::Felt;
+use tracing::info;
+
+use crate::{
+    assert_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        txn_builder::TxnTemplate,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+            providers::provider::Provider,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "add_invoke_transaction_mutations")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let sender = test_input.random_paymaster_account.random_accounts()?;
+        let transfer_to_self = Call {
+            to: network_registry().strk_address,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![sender.address(), Felt::ZERO, Felt::ZERO],
+        };
+        let valid =
+            sender.execute_v3(vec![transfer_to_self]).prepare().await?.get_broadcasted_invoke(false, false).await?;
+        let template = TxnTemplate::new(&valid).map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+
+        // Every mutation breaks a valid transaction in exactly one place, none may be accepted.
+        let mut accepted = vec![];
+        for mutation in template.mutations() {
+            let params = template.params(template.mutate(&[mutation.clone()]));
+            match sender.provider().send_raw(template.method(), params).await {
+                Ok(result) => accepted.push(format!("{mutation:?}: {result}")),
+                Err(e) => info!("{:?} rejected: {}", mutation, e),
+            }
+        }
+
+        assert_result!(accepted.is_empty(), format!("Mutated invoke transactions were accepted: {accepted:#?}"));
+
+        Ok(Self {})
+    }
+}
@@ -3,16 +3,19 @@ use starknet_types_rpc::{BlockId, MaybePendingBlockWithTxs};
 
 use crate::{
     assert_result,
-    utils::v7::{
-        accounts::{
-            account::{Account, ConnectedAccount},
-            call::Call,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::{Provider, ProviderError},
         },
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, wait_for_sent_transaction},
-        },
-        providers::provider::{Provider, ProviderError},
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
@@ -67,7 +70,7 @@ impl RunnableTrait for TestCase {
         let transfer_execution = test_input
             .random_paymaster_account
             .execute_v3(vec![Call {
-                to: Felt::from_hex("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D")?,
+                to: network_registry().strk_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![
                     Felt::from_hex("0xdeadF5A0beefCC1Adead1CDEbeefFB20dead5CD6beefB072dead8F42beef38D")?,
@@ -0,0 +1,70 @@
+use serde::Serialize;
+use serde_json::Value;
+use starknet_types_rpc::{BlockId, BlockTag};
+use tracing::info;
+
+use crate::{
+    assert_eq_result, openrpc_test,
+    utils::v7::{
+        accounts::account::ConnectedAccount,
+        endpoints::errors::OpenRpcTestGenError,
+        providers::{
+            jsonrpc::{HttpTransportConfig, JsonRpcClient},
+            provider::Provider,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+/// How many of the latest blocks are searched for the one with the most transactions.
+const BLOCKS_SCANNED: u64 = 20;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "compressed_responses")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let plain = account.provider();
+        let config = HttpTransportConfig { accept_compressed_responses: true, ..Default::default() };
+        let compressed = JsonRpcClient::new(plain.transport().clone().with_config(config));
+
+        // Large responses are the ones nodes compress, so the fullest of the recent blocks is used.
+        let latest = plain.block_number().await?;
+        let mut fullest = (latest, 0);
+        for block_number in latest.saturating_sub(BLOCKS_SCANNED)..=latest {
+            let count = plain.get_block_transaction_count(BlockId::Number(block_number)).await?;
+            if count > fullest.1 {
+                fullest = (block_number, count);
+            }
+        }
+        let (block_number, transaction_count) = fullest;
+        info!("Comparing block {} with {} transactions", block_number, transaction_count);
+
+        let block_id = BlockId::Number(block_number);
+        assert_eq_result!(
+            to_value(&compressed.get_block_with_txs(block_id.clone()).await?)?,
+            to_value(&plain.get_block_with_txs(block_id).await?)?,
+            "Block {} differs with compressed responses",
+            block_number
+        );
+
+        let class_block = BlockId::Tag(BlockTag::Latest);
+        let class_hash = test_input.account_class_hash;
+        assert_eq_result!(
+            to_value(&compressed.get_class(class_block.clone(), class_hash).await?)?,
+            to_value(&plain.get_class(class_block, class_hash).await?)?,
+            "Class {:#x} differs with compressed responses",
+            class_hash
+        );
+
+        Ok(Self {})
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value, OpenRpcTestGenError> {
+    serde_json::to_value(value).map_err(|e| OpenRpcTestGenError::Other(e.to_string()))
+}
@@ -0,0 +1,153 @@
+use std::{path::PathBuf, str::FromStr};
+
+use reqwest::StatusCode;
+use starknet_types_rpc::{BlockId, BlockTag, BroadcastedDeclareTxn, BroadcastedTxn};
+use tracing::info;
+
+use crate::{
+    assert_matches_result, openrpc_test,
+    utils::{
+        oversized::{inflate_contract_class, MAX_CONTRACT_CLASS_OBJECT_SIZE},
+        v7::{
+            accounts::account::{Account, ConnectedAccount},
+            endpoints::{declare_contract::get_compiled_contract, errors::OpenRpcTestGenError},
+            providers::{
+                jsonrpc::{
+                    transports::http::HttpTransportError, HttpTransportConfig, JsonRpcClient, JsonRpcClientError,
+                    JsonRpcError, StarknetError,
+                },
+                provider::{Provider, ProviderError},
+            },
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const DECLARE_TXN_GAS: u64 = 48000;
+const DECLARE_TXN_GAS_PRICE: u128 = 17;
+/// How far below the class size limit the large, still acceptable, class stays.
+const LARGE_CLASS_MARGIN: usize = 512 * 1024;
+/// Request body limit of the client in the last check, well below the oversized declaration.
+const CLIENT_BODY_LIMIT: usize = 1024 * 1024;
+const PARSE_ERROR: i64 = -32700;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "declare_large_class_transport")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let sender = test_input.random_paymaster_account.random_accounts()?;
+        let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(
+            PathBuf::from_str("target/dev/contracts_contracts_sample_contract_5_HelloStarknet.contract_class.json")?,
+            PathBuf::from_str(
+                "target/dev/contracts_contracts_sample_contract_5_HelloStarknet.compiled_contract_class.json",
+            )?,
+        )
+        .await?;
+        let inflate = |target_size| {
+            inflate_contract_class(flattened_sierra_class.clone(), target_size)
+                .map_err(|e| OpenRpcTestGenError::Other(e.to_string()))
+        };
+        let large_class = inflate(MAX_CONTRACT_CLASS_OBJECT_SIZE - LARGE_CLASS_MARGIN)?;
+        let oversized_class = inflate(MAX_CONTRACT_CLASS_OBJECT_SIZE + 1)?;
+
+        // Resource bounds are set manually so that nothing but the requests below carries the classes.
+        // They are not meant to cover the declaration: the checks below are about the transport.
+        let nonce = sender.get_nonce().await?;
+        let large_estimate = sender
+            .declare_v3(large_class, compiled_class_hash)
+            .nonce(nonce)
+            .gas(DECLARE_TXN_GAS)
+            .gas_price(DECLARE_TXN_GAS_PRICE)
+            .prepared()
+            .map_err(|_| OpenRpcTestGenError::Other("Declaration is missing resource bounds".to_string()))?
+            .get_broadcasted_declare(true, false)
+            .await?;
+        let oversized_declare = sender
+            .declare_v3(oversized_class, compiled_class_hash)
+            .nonce(nonce)
+            .gas(DECLARE_TXN_GAS)
+            .gas_price(DECLARE_TXN_GAS_PRICE)
+            .prepared()
+            .map_err(|_| OpenRpcTestGenError::Other("Declaration is missing resource bounds".to_string()))?
+            .get_declare_request(false, false)
+            .await?;
+
+        for compress_requests in [false, true] {
+            let config = HttpTransportConfig { compress_requests, ..Default::default() };
+            let provider = JsonRpcClient::new(sender.provider().transport().clone().with_config(config));
+
+            // A multi-megabyte class under the limit must reach the node and be estimated.
+            let estimate =
+                provider.estimate_fee_single(large_estimate.clone(), vec![], BlockId::Tag(BlockTag::Pending)).await;
+            match estimate {
+                Ok(_) => {}
+                Err(e) if compress_requests && is_compression_unsupported(&e) => {
+                    info!("Node does not take gzip-compressed requests: {}", e);
+                    continue;
+                }
+                Err(e) => {
+                    return Err(OpenRpcTestGenError::Other(format!(
+                        "Estimate of a declaration of {} bytes failed (compressed: {}): {}",
+                        MAX_CONTRACT_CLASS_OBJECT_SIZE - LARGE_CLASS_MARGIN,
+                        compress_requests,
+                        e
+                    )));
+                }
+            }
+
+            // Over the limit, the request must still reach the node and be answered with a Starknet
+            // error rather than dropped. Nodes checking the fee before the class size reject the
+            // hand-set bounds instead of the class.
+            let result = provider
+                .add_declare_transaction(BroadcastedTxn::Declare(BroadcastedDeclareTxn::V3(oversized_declare.clone())))
+                .await;
+            assert_matches_result!(
+                result,
+                Err(ProviderError::StarknetError(
+                    StarknetError::ContractClassSizeIsTooLarge
+                        | StarknetError::InsufficientMaxFee
+                        | StarknetError::InsufficientAccountBalance
+                )),
+                "Oversized class, compressed: {}",
+                compress_requests
+            );
+        }
+
+        // A client-side limit fails fast instead of sending the body.
+        let config = HttpTransportConfig { max_request_body_size: Some(CLIENT_BODY_LIMIT), ..Default::default() };
+        let provider = JsonRpcClient::new(sender.provider().transport().clone().with_config(config));
+        let result = provider
+            .add_declare_transaction(BroadcastedTxn::Declare(BroadcastedDeclareTxn::V3(oversized_declare)))
+            .await;
+        let error = match result {
+            Err(ProviderError::Other(error)) => error,
+            result => {
+                return Err(OpenRpcTestGenError::Other(format!("Expected a transport error, got {:?}", result)));
+            }
+        };
+        assert_matches_result!(
+            error.as_any().downcast_ref::<JsonRpcClientError<HttpTransportError>>(),
+            Some(JsonRpcClientError::Transport(HttpTransportError::RequestTooLarge { limit: CLIENT_BODY_LIMIT, .. }))
+        );
+
+        Ok(Self {})
+    }
+}
+
+/// Whether the node refused a gzip-encoded body, with a status or by failing to parse it as JSON.
+fn is_compression_unsupported(error: &ProviderError) -> bool {
+    let ProviderError::Other(error) = error else {
+        return false;
+    };
+    match error.as_any().downcast_ref::<JsonRpcClientError<HttpTransportError>>() {
+        Some(JsonRpcClientError::Transport(HttpTransportError::Status { status, .. })) => {
+            matches!(*status, StatusCode::UNSUPPORTED_MEDIA_TYPE | StatusCode::BAD_REQUEST)
+        }
+        Some(JsonRpcClientError::JsonRpc(JsonRpcError { code: PARSE_ERROR, .. })) => true,
+        _ => false,
+    }
+}
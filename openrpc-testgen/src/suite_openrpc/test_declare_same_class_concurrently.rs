@@ -0,0 +1,96 @@
+use std::{fs::File, path::PathBuf, str::FromStr};
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag};
+use tracing::info;
+
+use crate::{
+    assert_eq_result, assert_result, openrpc_test,
+    utils::v7::{
+        accounts::account::{Account, AccountError, ConnectedAccount},
+        contract::{HashAndFlatten, SierraClass},
+        endpoints::{
+            declare_contract::{get_compiled_contract, RunnerError},
+            errors::OpenRpcTestGenError,
+            utils::wait_for_sent_transaction,
+        },
+        providers::{
+            jsonrpc::StarknetError,
+            provider::{Provider, ProviderError},
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const SIERRA_PATH: &str = "target/dev/contracts_contracts_smpl23_HelloStarknet.contract_class.json";
+const CASM_PATH: &str = "target/dev/contracts_contracts_smpl23_HelloStarknet.compiled_contract_class.json";
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "declare_same_class_concurrently")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let sierra_file =
+            File::open(SIERRA_PATH).map_err(|e| RunnerError::ReadFileError(format!("{SIERRA_PATH}: {e}")))?;
+        let sierra_class: SierraClass = serde_json::from_reader(sierra_file).map_err(RunnerError::from)?;
+        let class_hash = sierra_class.class_hash().map_err(RunnerError::from)?;
+
+        let first = test_input.random_paymaster_account.random_accounts()?;
+        let second = test_input.random_executable_account.random_accounts()?;
+        let provider = first.provider();
+
+        // The race can only be run once against a node, the class stays declared afterwards.
+        if provider.get_class(BlockId::Tag(BlockTag::Pending), class_hash).await.is_ok() {
+            info!("Class {:#x} is already declared, declare_same_class_concurrently does not run.", class_hash);
+            return Ok(Self {});
+        }
+
+        let (flattened_sierra_class, compiled_class_hash) =
+            get_compiled_contract(PathBuf::from_str(SIERRA_PATH)?, PathBuf::from_str(CASM_PATH)?).await?;
+
+        // Both declarations are prepared up front, so that neither is estimated against a state in
+        // which the other one already went through.
+        let first_declaration = first.declare_v3(flattened_sierra_class.clone(), compiled_class_hash);
+        let first_prepared = first_declaration.prepare().await?;
+        let resources = first_prepared.get_raw_execution().await;
+        let second_declaration = second
+            .declare_v3(flattened_sierra_class.clone(), compiled_class_hash)
+            .gas(resources.gas())
+            .gas_price(resources.gas_price());
+        let second_prepared = second_declaration.prepare().await?;
+
+        let (first_result, second_result) = tokio::join!(first_prepared.send(), second_prepared.send());
+
+        let mut declared = 0;
+        for (account, result) in [(&first, first_result), (&second, second_result)] {
+            match result {
+                Ok(declaration) => {
+                    assert_eq_result!(declaration.class_hash, class_hash, "Class hash of a declaration");
+                    // Both may be admitted, as long as only one of them is executed.
+                    match wait_for_sent_transaction(declaration.transaction_hash, account).await {
+                        Ok(_) => declared += 1,
+                        Err(e) => info!("Declaration {:#x} lost the race: {}", declaration.transaction_hash, e),
+                    }
+                }
+                Err(AccountError::Provider(ProviderError::StarknetError(StarknetError::ClassAlreadyDeclared))) => {
+                    info!("Declaration from {:#x} rejected, the class is already declared", account.address());
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        assert_eq_result!(declared, 1, "Declarations of the same class executed");
+
+        // The node has to keep serving requests, and the class with the declared program.
+        provider.block_number().await?;
+        let declared_class = provider.get_class(BlockId::Tag(BlockTag::Latest), class_hash).await?;
+        assert_result!(
+            declared_class.sierra_program == flattened_sierra_class.sierra_program,
+            format!("Sierra program of class {:#x} differs from the declared one", class_hash)
+        );
+
+        Ok(Self {})
+    }
+}
@@ -0,0 +1,60 @@
+use std::{path::PathBuf, str::FromStr};
+
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    assert_eq_result, assert_matches_result, openrpc_test,
+    utils::v7::{
+        accounts::account::{Account, AccountError, ConnectedAccount},
+        endpoints::{declare_contract::get_compiled_contract, errors::OpenRpcTestGenError},
+        providers::{jsonrpc::StarknetError, provider::ProviderError},
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const DECLARE_TXN_GAS: u64 = 48000;
+const DECLARE_TXN_GAS_PRICE: u128 = 17;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "declare_v3_compiled_class_hash_mismatch")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        // The class is never declared successfully, so the node compiles it on every run instead of
+        // rejecting it as already declared.
+        let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(
+            PathBuf::from_str("target/dev/contracts_contracts_sample_contract_4_HelloStarknet.contract_class.json")?,
+            PathBuf::from_str(
+                "target/dev/contracts_contracts_sample_contract_4_HelloStarknet.compiled_contract_class.json",
+            )?,
+        )
+        .await?;
+        let wrong_compiled_class_hash = compiled_class_hash + Felt::ONE;
+
+        let sender = test_input.random_paymaster_account.random_accounts()?;
+        let initial_nonce = sender.get_nonce().await?;
+
+        // Resource bounds are set manually to skip fee estimation, so that the transaction reaches
+        // the node's compilation checks.
+        let result = sender
+            .declare_v3(flattened_sierra_class, wrong_compiled_class_hash)
+            .gas(DECLARE_TXN_GAS)
+            .gas_price(DECLARE_TXN_GAS_PRICE)
+            .send()
+            .await;
+
+        assert_matches_result!(
+            result.unwrap_err(),
+            AccountError::Provider(ProviderError::StarknetError(StarknetError::CompiledClassHashMismatch))
+        );
+
+        // A rejected declaration must not consume the nonce.
+        let nonce = sender.get_nonce().await?;
+        assert_eq_result!(nonce, initial_nonce);
+
+        Ok(Self {})
+    }
+}
@@ -0,0 +1,56 @@
+use std::{path::PathBuf, str::FromStr};
+
+use crate::{
+    assert_matches_result, openrpc_test,
+    utils::{
+        oversized::{inflate_contract_class, MAX_CONTRACT_CLASS_OBJECT_SIZE},
+        v7::{
+            accounts::account::{Account, AccountError},
+            endpoints::{declare_contract::get_compiled_contract, errors::OpenRpcTestGenError},
+            providers::{jsonrpc::StarknetError, provider::ProviderError},
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const DECLARE_TXN_GAS: u64 = 48000;
+const DECLARE_TXN_GAS_PRICE: u128 = 17;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "declare_v3_contract_class_size_too_large")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(
+            PathBuf::from_str("target/dev/contracts_contracts_sample_contract_5_HelloStarknet.contract_class.json")?,
+            PathBuf::from_str(
+                "target/dev/contracts_contracts_sample_contract_5_HelloStarknet.compiled_contract_class.json",
+            )?,
+        )
+        .await?;
+        let oversized_class = inflate_contract_class(flattened_sierra_class, MAX_CONTRACT_CLASS_OBJECT_SIZE + 1)
+            .map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+
+        // Resource bounds are set manually to skip fee estimation, which would otherwise send the
+        // oversized class first.
+        let result = test_input
+            .random_paymaster_account
+            .random_accounts()?
+            .declare_v3(oversized_class, compiled_class_hash)
+            .gas(DECLARE_TXN_GAS)
+            .gas_price(DECLARE_TXN_GAS_PRICE)
+            .send()
+            .await;
+
+        // A transport-level failure (e.g. the request body limit of the RPC server) does not match.
+        assert_matches_result!(
+            result.unwrap_err(),
+            AccountError::Provider(ProviderError::StarknetError(StarknetError::ContractClassSizeIsTooLarge))
+        );
+
+        Ok(Self {})
+    }
+}
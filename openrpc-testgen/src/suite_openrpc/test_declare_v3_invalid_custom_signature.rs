@@ -0,0 +1,53 @@
+use std::{path::PathBuf, str::FromStr};
+
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    assert_eq_result, assert_matches_result, openrpc_test,
+    utils::v7::{
+        accounts::account::{Account, AccountError, ConnectedAccount},
+        endpoints::{declare_contract::get_compiled_contract, errors::OpenRpcTestGenError},
+        providers::{jsonrpc::StarknetError, provider::ProviderError},
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "declare_v3_invalid_custom_signature")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        // The declaration is always rejected, so the class stays undeclared across runs.
+        let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(
+            PathBuf::from_str("target/dev/contracts_contracts_sample_contract_4_HelloStarknet.contract_class.json")?,
+            PathBuf::from_str(
+                "target/dev/contracts_contracts_sample_contract_4_HelloStarknet.compiled_contract_class.json",
+            )?,
+        )
+        .await?;
+
+        let sender = test_input.random_paymaster_account.random_accounts()?;
+        let initial_nonce = sender.get_nonce().await?;
+
+        let declaration = sender.declare_v3(flattened_sierra_class, compiled_class_hash);
+        let prepared = declaration.prepare().await?;
+
+        // Properly signed, the prepared declaration passes validation.
+        prepared.simulate(false, false).await?;
+
+        let result = prepared.send_with_custom_signature(vec![Felt::ONE, Felt::TWO]).await;
+        assert_matches_result!(
+            result.unwrap_err(),
+            AccountError::Provider(ProviderError::StarknetError(StarknetError::ValidationFailure(_)))
+        );
+
+        // A rejected declaration must not consume the nonce.
+        let nonce = sender.get_nonce().await?;
+        assert_eq_result!(nonce, initial_nonce);
+
+        Ok(Self {})
+    }
+}
@@ -0,0 +1,103 @@
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag, BroadcastedDeployAccountTxn, BroadcastedTxn};
+use tracing::info;
+
+use crate::{
+    assert_eq_result, assert_matches_result, assert_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+                factory::{open_zeppelin::OpenZeppelinAccountFactory, AccountFactory},
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::{
+                jsonrpc::StarknetError,
+                provider::{Provider, ProviderError},
+            },
+            signers::{key_pair::SigningKey, local_wallet::LocalWallet},
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "deploy_account_after_failed_deployment")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let funding_account = test_input.random_paymaster_account.random_accounts()?;
+        let provider = funding_account.provider().clone();
+        let signer = LocalWallet::from(SigningKey::from_random());
+        let factory = OpenZeppelinAccountFactory::new(
+            test_input.account_class_hash,
+            funding_account.chain_id(),
+            &signer,
+            &provider,
+        )
+        .await?;
+
+        let mut salt_buffer = [0u8; 32];
+        StdRng::from_entropy().fill_bytes(&mut salt_buffer[1..]);
+        let salt = Felt::from_bytes_be(&salt_buffer);
+        let address = factory.deploy_v3(salt).address();
+
+        let transfer = funding_account
+            .execute_v3(vec![Call {
+                to: network_registry().strk_address,
+                selector: get_selector_from_name("transfer")?,
+                calldata: vec![address, Felt::from_hex("0xfffffffffffffff")?, Felt::ZERO],
+            }])
+            .send()
+            .await?;
+        wait_for_sent_transaction(transfer.transaction_hash, &funding_account).await?;
+
+        // Undeployed, the node knows no nonce of the address and the factory falls back to zero.
+        let nonce = provider.get_nonce(BlockId::Tag(BlockTag::Pending), address).await;
+        assert_matches_result!(nonce, Err(ProviderError::StarknetError(StarknetError::ContractNotFound)));
+        assert_eq_result!(factory.deploy_v3(salt).fetch_nonce().await?, Felt::ZERO);
+
+        // The deployment fails validation with a signature of the wrong key.
+        let prepared = factory.deploy_v3(salt).prepare().await?;
+        let mut request = prepared.get_deploy_request(false, false).await?;
+        request.signature = vec![Felt::ONE, Felt::TWO];
+        match provider
+            .add_deploy_account_transaction(BroadcastedTxn::DeployAccount(BroadcastedDeployAccountTxn::V3(request)))
+            .await
+        {
+            Ok(result) => {
+                let outcome = wait_for_sent_transaction(result.transaction_hash, &funding_account).await;
+                assert_result!(outcome.is_err(), "Deployment with an invalid signature succeeded");
+            }
+            Err(e) => info!("Deployment with an invalid signature rejected on submission: {}", e),
+        }
+
+        // A failed deployment included in a block consumes the nonce, a rejected one does not.
+        let nonce_after_failure = factory.deploy_v3(salt).fetch_nonce().await?;
+        info!("Nonce of the account after the failed deployment: {:#x}", nonce_after_failure);
+        assert_result!(
+            nonce_after_failure == Felt::ZERO || nonce_after_failure == Felt::ONE,
+            format!("Unexpected nonce {:#x} after a single failed deployment", nonce_after_failure)
+        );
+
+        let deployment = factory.deploy_v3(salt).nonce(nonce_after_failure).send().await?;
+        assert_eq_result!(deployment.contract_address, address);
+        wait_for_sent_transaction(deployment.transaction_hash, &funding_account).await?;
+
+        let class_hash = provider.get_class_hash_at(BlockId::Tag(BlockTag::Pending), address).await?;
+        assert_eq_result!(class_hash, test_input.account_class_hash);
+        let nonce = provider.get_nonce(BlockId::Tag(BlockTag::Pending), address).await?;
+        assert_eq_result!(nonce, nonce_after_failure + Felt::ONE, "Nonce of the deployed account");
+
+        Ok(Self {})
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     assert_matches_result,
     utils::{
+        network_registry::network_registry,
         outside_execution::{get_current_timestamp, prepare_outside_execution, OutsideExecution},
         v7::{
             accounts::{
@@ -44,7 +45,7 @@ impl RunnableTrait for TestCase {
             .await?;
 
         let udc_call = Call {
-            to: Felt::from_hex_unchecked("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf"),
+            to: network_registry().udc_address,
             selector: get_selector_from_name("deployContract")?,
             calldata: vec![
                 test_input.account_class_hash,
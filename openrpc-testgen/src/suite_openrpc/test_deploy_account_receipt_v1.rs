@@ -0,0 +1,25 @@
+use crate::{
+    utils::{
+        deploy_account_receipt::deploy_account_and_validate_receipt,
+        v7::{accounts::deployment::deploy::DeployAccountVersion, endpoints::errors::OpenRpcTestGenError},
+    },
+    RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        deploy_account_and_validate_receipt(
+            &test_input.random_paymaster_account,
+            test_input.account_class_hash,
+            DeployAccountVersion::V1,
+        )
+        .await?;
+
+        Ok(Self {})
+    }
+}
@@ -1,29 +1,29 @@
 use crate::{
     assert_matches_result, assert_result,
-    utils::v7::{
-        accounts::{
-            account::{Account, ConnectedAccount},
-            call::Call,
-            creation::create::{create_account, AccountType},
-            deployment::{
-                deploy::{deploy_account, DeployAccountVersion},
-                structs::{ValidatedWaitParams, WaitForTx},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+                creation::create::{create_account, AccountType},
+                deployment::{
+                    deploy::{deploy_account, DeployAccountVersion},
+                    structs::{ValidatedWaitParams, WaitForTx},
+                },
             },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, get_storage_var_address, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
         },
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, get_storage_var_address, wait_for_sent_transaction},
-        },
-        providers::provider::Provider,
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{BlockId, BlockTag, DeployAccountTransactionTrace, EntryPointType, TransactionTrace};
 
-pub const STRK_ERC20_CONTRACT_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
-
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -46,7 +46,7 @@ impl RunnableTrait for TestCase {
         let transfer_execution = test_input
             .random_paymaster_account
             .execute_v3(vec![Call {
-                to: Felt::from_hex("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D")?,
+                to: network_registry().strk_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account_data.address, transfer_amount, Felt::ZERO],
             }])
@@ -222,16 +222,17 @@ impl RunnableTrait for TestCase {
             )
         );
 
-        // Validate the contract address in fee_transfer_invocation matches STRK_ERC20_CONTRACT_ADDRESS
+        // Validate the contract address in fee_transfer_invocation matches the STRK token address
         assert_result!(
-            fee_transfer_invocation.function_call.contract_address == STRK_ERC20_CONTRACT_ADDRESS,
+            fee_transfer_invocation.function_call.contract_address == network_registry().strk_address,
             format!(
                 "Contract address mismatch in fee transfer invocation: expected {:?}, but found {:?}",
-                STRK_ERC20_CONTRACT_ADDRESS, fee_transfer_invocation.function_call.contract_address
+                network_registry().strk_address,
+                fee_transfer_invocation.function_call.contract_address
             )
         );
 
-        // Validate the entry point selector in fee_transfer_invocation for STRK_ERC20_CONTRACT_ADDRESS
+        // Validate the entry point selector in fee_transfer_invocation for the STRK token address
         assert_result!(
             fee_transfer_invocation.function_call.entry_point_selector == transfer_selector,
             format!(
@@ -1,20 +1,23 @@
 use crate::{
     assert_result,
-    utils::v7::{
-        accounts::{
-            account::{Account, ConnectedAccount},
-            call::Call,
-            creation::create::{create_account, AccountType},
-            deployment::{
-                deploy::{deploy_account_v1_from_request, get_deploy_account_request, DeployAccountVersion},
-                structs::{ValidatedWaitParams, WaitForTx},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+                creation::create::{create_account, AccountType},
+                deployment::{
+                    deploy::{deploy_account_v1_from_request, get_deploy_account_request, DeployAccountVersion},
+                    structs::{ValidatedWaitParams, WaitForTx},
+                },
             },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
         },
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, wait_for_sent_transaction},
-        },
-        providers::provider::Provider,
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
@@ -23,7 +26,6 @@ use starknet_types_rpc::{BlockId, DeployAccountTxn, MaybePendingBlockWithTxs, Tx
 use t9n::txn_validation::deploy_account::verify_deploy_account_v1_signature;
 
 const DEPLOY_ACCOUNT_MAX_FEE: Felt = Felt::from_hex_unchecked("0x155");
-const ETH: Felt = Felt::from_hex_unchecked("0x49D36570D4E46F48E99674BD3FCC84644DDD6B96F7C741B1562B82F9E004DC7");
 
 #[derive(Clone, Debug)]
 pub struct TestCase {}
@@ -45,7 +47,7 @@ impl RunnableTrait for TestCase {
         let transfer_execution = test_input
             .random_paymaster_account
             .execute_v3(vec![Call {
-                to: ETH,
+                to: network_registry().eth_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account_data.address, transfer_amount, Felt::ZERO],
             }])
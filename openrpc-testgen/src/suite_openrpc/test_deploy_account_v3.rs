@@ -1,20 +1,23 @@
 use crate::{
     assert_result,
-    utils::v7::{
-        accounts::{
-            account::{Account, ConnectedAccount},
-            call::Call,
-            creation::create::{create_account, AccountType},
-            deployment::{
-                deploy::{deploy_account_v3_from_request, get_deploy_account_request, DeployAccountVersion},
-                structs::{ValidatedWaitParams, WaitForTx},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+                creation::create::{create_account, AccountType},
+                deployment::{
+                    deploy::{deploy_account_v3_from_request, get_deploy_account_request, DeployAccountVersion},
+                    structs::{ValidatedWaitParams, WaitForTx},
+                },
             },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
         },
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, wait_for_sent_transaction},
-        },
-        providers::provider::Provider,
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
@@ -23,7 +26,6 @@ use starknet_types_rpc::{BlockId, DaMode, DeployAccountTxn, MaybePendingBlockWit
 use t9n::txn_validation::deploy_account::verify_deploy_account_v3_signature;
 const DEPLOY_ACCOUNT_TXN_GAS: Felt = Felt::from_hex_unchecked("0x1d1");
 const DEPLOY_ACCOUNT_TXN_GAS_PRICE: Felt = Felt::from_hex_unchecked("0x1");
-const STRK: Felt = Felt::from_hex_unchecked("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D");
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -44,7 +46,7 @@ impl RunnableTrait for TestCase {
         let transfer_execution = test_input
             .random_paymaster_account
             .execute_v3(vec![Call {
-                to: STRK,
+                to: network_registry().strk_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account_data.address, transfer_amount, Felt::ZERO],
             }])
@@ -0,0 +1,67 @@
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag, FunctionCall};
+
+use crate::{
+    assert_eq_result, openrpc_test,
+    utils::v7::{
+        accounts::account::{Account, ConnectedAccount},
+        endpoints::{
+            deploy_contract::deploy_contract,
+            errors::OpenRpcTestGenError,
+            utils::{declare_if_missing, get_selector_from_name},
+        },
+        providers::provider::Provider,
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const CONSTRUCTOR_ARGS: &str = "target/dev/contracts_ConstructorArgs";
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "deploy_with_constructor_args")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let class_hash = declare_if_missing(&account, CONSTRUCTOR_ARGS).await?;
+
+        let mut salt_buffer = [0u8; 32];
+        StdRng::from_entropy().fill_bytes(&mut salt_buffer[1..]);
+        let values = vec![Felt::from(7u8), Felt::from(11u8), Felt::from(13u8)];
+        let mut constructor_calldata = vec![account.address(), Felt::from(values.len() as u64)];
+        constructor_calldata.extend(values.iter().copied());
+
+        // The address computed locally and the one in the UDC event are compared by the helper.
+        let deployed =
+            deploy_contract(&account, class_hash, constructor_calldata, Some(Felt::from_bytes_be(&salt_buffer)))
+                .await?;
+
+        let provider = account.provider();
+        let deployed_class_hash = provider.get_class_hash_at(BlockId::Tag(BlockTag::Latest), deployed.address).await?;
+        assert_eq_result!(deployed_class_hash, class_hash, "Class hash of the deployed contract");
+
+        let owner = call(provider, deployed.address, "get_owner").await?;
+        let expected_owner = vec![account.address()];
+        assert_eq_result!(owner, expected_owner, "Owner passed to the constructor");
+
+        let mut expected_values = vec![Felt::from(values.len() as u64)];
+        expected_values.extend(values);
+        let stored_values = call(provider, deployed.address, "get_values").await?;
+        assert_eq_result!(stored_values, expected_values, "Values passed to the constructor");
+
+        Ok(Self {})
+    }
+}
+
+async fn call<P: Provider>(provider: &P, contract_address: Felt, name: &str) -> Result<Vec<Felt>, OpenRpcTestGenError> {
+    Ok(provider
+        .call(
+            FunctionCall { calldata: vec![], contract_address, entry_point_selector: get_selector_from_name(name)? },
+            BlockId::Tag(BlockTag::Latest),
+        )
+        .await?)
+}
@@ -0,0 +1,110 @@
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag};
+use tracing::info;
+
+use crate::{
+    assert_eq_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, AccountError, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::{
+                jsonrpc::StarknetError,
+                provider::{Provider, ProviderError},
+            },
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "double_spend_nonce")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        // Racing the nonce of the account would fail the other cases sending from it, it is
+        // reserved for this case. The same account on two endpoints when more than one is
+        // configured, otherwise both transactions go to the only one.
+        let exclusive = test_input.random_executable_account.exclusive_account()?;
+        let accounts: Vec<_> = test_input
+            .random_executable_account
+            .accounts
+            .iter()
+            .filter(|account| account.address() == exclusive.address())
+            .collect();
+        let first = *accounts
+            .first()
+            .ok_or_else(|| OpenRpcTestGenError::Other("Reserved account not found among the accounts".to_string()))?;
+        let second = *accounts.get(1).unwrap_or(&first);
+        let nonce = first.get_nonce().await?;
+
+        // Transfers of different amounts, so that the transactions differ in hash and only the
+        // nonce makes them conflict.
+        let selector = get_selector_from_name("transfer")?;
+        let transfer = |amount: u64| Call {
+            to: network_registry().strk_address,
+            selector,
+            calldata: vec![first.address(), Felt::from(amount), Felt::ZERO],
+        };
+        let first_prepared = first.execute_v3(vec![transfer(0)]).nonce(nonce).prepare().await?;
+        let resources = first_prepared.get_raw_execution().await;
+        let second_prepared = second
+            .execute_v3(vec![transfer(1)])
+            .nonce(nonce)
+            .gas(resources.gas())
+            .gas_price(resources.gas_price())
+            .prepare()
+            .await?;
+
+        let (first_result, second_result) = tokio::join!(first_prepared.send(), second_prepared.send());
+
+        let mut included = vec![];
+        for (account, result) in [(first, first_result), (second, second_result)] {
+            match result {
+                // Both may be admitted to the mempools of different nodes, as long as only one of
+                // them is executed: the other one has to end up rejected or reverted. A timeout or any
+                // other error fails the case.
+                Ok(invoke) => match wait_for_sent_transaction(invoke.transaction_hash, account).await {
+                    Ok(_) => included.push(invoke.transaction_hash),
+                    Err(
+                        e @ (OpenRpcTestGenError::TransactionRejected(_) | OpenRpcTestGenError::TransactionFailed(_)),
+                    ) => {
+                        info!("Transaction {:#x} lost the nonce race: {}", invoke.transaction_hash, e)
+                    }
+                    Err(e) => return Err(e),
+                },
+                Err(AccountError::Provider(ProviderError::StarknetError(
+                    e @ (StarknetError::DuplicateTx | StarknetError::InvalidTransactionNonce),
+                ))) => {
+                    info!("Transaction with nonce {:#x} rejected: {}", nonce, e);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        assert_eq_result!(included.len(), 1, "Transactions with nonce {:#x} included: {:?}", nonce, included);
+
+        // Every endpoint has to agree that the nonce was used exactly once.
+        for account in [first, second] {
+            wait_for_sent_transaction(included[0], account).await?;
+            let final_nonce = account.provider().get_nonce(BlockId::Tag(BlockTag::Latest), account.address()).await?;
+            assert_eq_result!(
+                final_nonce,
+                nonce + Felt::ONE,
+                "Nonce of {:#x} after the double spend",
+                account.address()
+            );
+        }
+
+        Ok(Self {})
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     utils::{
         conversions::felts_to_biguint::felts_slice_to_biguint,
         get_balance::get_balance,
+        network_registry::network_registry,
         outside_execution::{get_current_timestamp, prepare_outside_execution, OutsideExecution},
         v7::{
             accounts::{
@@ -196,7 +197,7 @@ impl RunnableTrait for TestCase {
             get_balance(
                 test_input.random_paymaster_account.provider(),
                 test_input.random_paymaster_account.random_accounts()?.address(),
-                Felt::from_hex("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d")?,
+                network_registry().strk_address,
                 BlockId::Tag(BlockTag::Pending),
             )
             .await?,
@@ -231,7 +232,7 @@ impl RunnableTrait for TestCase {
             get_balance(
                 test_input.random_paymaster_account.provider(),
                 test_input.random_paymaster_account.random_accounts()?.address(),
-                Felt::from_hex("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d")?,
+                network_registry().strk_address,
                 BlockId::Tag(BlockTag::Pending),
             )
             .await?,
@@ -0,0 +1,69 @@
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    assert_eq_result, assert_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "estimate_and_send_query_versions")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let sender = test_input.random_paymaster_account.random_accounts()?;
+        let transfer_to_self = Call {
+            to: network_registry().strk_address,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![sender.address(), Felt::ZERO, Felt::ZERO],
+        };
+
+        // Estimates and simulations are signed over the query version of the hash and validated by
+        // the account, so they only succeed if the node hashes them with the query version too.
+        let execution = sender.execute_v3(vec![transfer_to_self.clone()]);
+        execution.estimate_fee().await?;
+        execution.simulate(false, false).await?;
+
+        let prepared = execution.prepare().await?;
+        let query_hash = prepared.transaction_hash(true);
+        let expected_hash = prepared.transaction_hash(false);
+        assert_result!(
+            query_hash != expected_hash,
+            format!("Query and plain versions of the V3 invoke share the hash {:#x}", expected_hash)
+        );
+        let result = prepared.send().await?;
+        assert_eq_result!(result.transaction_hash, expected_hash, "Hash of the V3 invoke sent with the plain version");
+        wait_for_sent_transaction(result.transaction_hash, &sender).await?;
+
+        let execution = sender.execute_v1(vec![transfer_to_self]);
+        execution.estimate_fee().await?;
+
+        let prepared = execution.prepare().await?;
+        let query_hash = prepared.transaction_hash(true);
+        let expected_hash = prepared.transaction_hash(false);
+        assert_result!(
+            query_hash != expected_hash,
+            format!("Query and plain versions of the V1 invoke share the hash {:#x}", expected_hash)
+        );
+        let result = prepared.send().await?;
+        assert_eq_result!(result.transaction_hash, expected_hash, "Hash of the V1 invoke sent with the plain version");
+        wait_for_sent_transaction(result.transaction_hash, &sender).await?;
+
+        Ok(Self {})
+    }
+}
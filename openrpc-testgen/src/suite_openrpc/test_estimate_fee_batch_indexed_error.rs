@@ -0,0 +1,64 @@
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    assert_matches_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+            providers::{
+                jsonrpc::StarknetError,
+                provider::{Provider, ProviderError},
+            },
+        },
+    },
+    RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "estimate_fee_batch_indexed_error")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let senders = &test_input.random_paymaster_account.accounts();
+        let sender = senders.first().ok_or_else(|| OpenRpcTestGenError::Other("No paymaster account".to_string()))?;
+        let nonce = sender.get_nonce().await?;
+
+        let transfer = sender
+            .execute_v3(vec![Call {
+                to: network_registry().strk_address,
+                selector: get_selector_from_name("transfer")?,
+                calldata: vec![Felt::from_hex_unchecked("0xdeadbeef"), Felt::ONE, Felt::ZERO],
+            }])
+            .nonce(nonce)
+            .fee_estimate_request()
+            .await?;
+        // Passes validation but fails in `__execute__`, as the token has no such entrypoint.
+        let failing = sender
+            .execute_v3(vec![Call {
+                to: network_registry().strk_address,
+                selector: get_selector_from_name("entrypoint_that_does_not_exist")?,
+                calldata: vec![],
+            }])
+            .nonce(nonce + Felt::ONE)
+            .fee_estimate_request()
+            .await?;
+
+        let result = sender.provider().estimate_fee(vec![transfer, failing], vec![], sender.block_id()).await;
+
+        // The spec reports the index of the first failing transaction of the batch.
+        assert_matches_result!(
+            result.unwrap_err(),
+            ProviderError::StarknetError(StarknetError::TransactionExecutionError(data)) if data.transaction_index == 1
+        );
+
+        Ok(Self {})
+    }
+}
@@ -0,0 +1,67 @@
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    assert_eq_result, assert_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+            providers::provider::Provider,
+        },
+    },
+    RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "estimate_fee_batch_matches_single")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        // Transactions of distinct senders to distinct recipients, so that none of them depends on
+        // the state left by another one of the batch.
+        let senders = &test_input.random_paymaster_account.accounts();
+        assert_result!(senders.len() >= 2, "Batch estimation needs at least two paymaster accounts");
+        let executions: Vec<_> = senders[..2]
+            .iter()
+            .enumerate()
+            .map(|(index, sender)| {
+                let recipient = Felt::from_hex_unchecked("0xdeadbeef") + Felt::from(index);
+                Ok(sender.execute_v3(vec![Call {
+                    to: network_registry().strk_address,
+                    selector: get_selector_from_name("transfer")?,
+                    calldata: vec![recipient, Felt::from(1000 + index), Felt::ZERO],
+                }]))
+            })
+            .collect::<Result<_, OpenRpcTestGenError>>()?;
+
+        let mut requests = Vec::with_capacity(executions.len());
+        let mut single_estimates = Vec::with_capacity(executions.len());
+        for execution in &executions {
+            requests.push(execution.fee_estimate_request().await?);
+            single_estimates.push(execution.estimate_fee().await?);
+        }
+
+        let provider = senders[0].provider();
+        let batch_estimates = provider.estimate_fee(requests, vec![], senders[0].block_id()).await?;
+        assert_eq_result!(batch_estimates.len(), executions.len(), "One estimate per transaction of the batch");
+
+        for (index, (batch, single)) in batch_estimates.iter().zip(&single_estimates).enumerate() {
+            assert_result!(
+                batch.gas_consumed == single.gas_consumed
+                    && batch.data_gas_consumed == single.data_gas_consumed
+                    && batch.overall_fee == single.overall_fee
+                    && batch.unit == single.unit,
+                format!("Estimate of transaction {index} differs in batch: {batch:?}, alone: {single:?}")
+            );
+        }
+
+        Ok(Self {})
+    }
+}
@@ -4,7 +4,7 @@ use starknet_types_core::felt::Felt;
 use starknet_types_rpc::PriceUnit;
 
 use crate::{
-    assert_result,
+    assert_result, openrpc_test,
     utils::v7::{
         accounts::account::Account,
         endpoints::{declare_contract::get_compiled_contract, errors::OpenRpcTestGenError},
@@ -18,6 +18,7 @@ const STRK_GAS_PRICE: Felt = Felt::from_hex_unchecked("0x1");
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
+#[openrpc_test(suite = "openrpc", name = "estimate_fee_fri")]
 impl RunnableTrait for TestCase {
     type Input = super::TestSuiteOpenRpc;
 
@@ -0,0 +1,105 @@
+use std::time::Instant;
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{TxnFinalityStatus, TxnReceipt, TxnStatus};
+use tracing::info;
+
+use crate::{
+    assert_eq_result, openrpc_test,
+    utils::{
+        finality::{accept_on_l1, finality_config, record_finality_timing, FinalityTiming},
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "finality_progression")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let Some(config) = finality_config() else {
+            info!("Finality tracking is not enabled, finality_progression does not run.");
+            return Ok(Self {});
+        };
+
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let provider = account.provider();
+        let transfer_to_self = Call {
+            to: network_registry().strk_address,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![account.address(), Felt::ZERO, Felt::ZERO],
+        };
+
+        let submitted = Instant::now();
+        let transaction_hash = account.execute_v3(vec![transfer_to_self]).send().await?.transaction_hash;
+        wait_for_sent_transaction(transaction_hash, &account).await?;
+        let accepted_on_l2 = submitted.elapsed();
+
+        // Status and receipt have to agree at every step of the progression.
+        let status = provider.get_transaction_status(transaction_hash).await?.finality_status;
+        assert_eq_result!(status, TxnStatus::AcceptedOnL2, "Finality status once accepted");
+        let finality = receipt_finality_status(provider.get_transaction_receipt(transaction_hash).await?);
+        assert_eq_result!(finality, TxnFinalityStatus::L2, "Receipt finality status once accepted");
+
+        if let Some(devnet_url) = &config.simulated_settlement {
+            let block_hash = provider
+                .get_transaction_receipt_block_info(transaction_hash)
+                .await?
+                .block_hash
+                .ok_or_else(|| OpenRpcTestGenError::Other("Accepted transaction is in no block".to_string()))?;
+            accept_on_l1(devnet_url, block_hash).await?;
+        }
+
+        loop {
+            let status = provider.get_transaction_status(transaction_hash).await?.finality_status;
+            if status == TxnStatus::AcceptedOnL1 {
+                break;
+            }
+            assert_eq_result!(status, TxnStatus::AcceptedOnL2, "Finality status before settlement");
+            if submitted.elapsed() > accepted_on_l2 + config.timeout {
+                return Err(OpenRpcTestGenError::Timeout(format!(
+                    "Transaction {:#x} not accepted on L1 within {:?}",
+                    transaction_hash, config.timeout
+                )));
+            }
+            tokio::time::sleep(config.poll_interval).await;
+        }
+        let accepted_on_l1 = submitted.elapsed();
+
+        let finality = receipt_finality_status(provider.get_transaction_receipt(transaction_hash).await?);
+        assert_eq_result!(finality, TxnFinalityStatus::L1, "Receipt finality status once settled");
+
+        record_finality_timing(FinalityTiming {
+            transaction_hash,
+            accepted_on_l2_ms: accepted_on_l2.as_millis() as u64,
+            accepted_on_l1_ms: accepted_on_l1.as_millis() as u64,
+        });
+
+        Ok(Self {})
+    }
+}
+
+fn receipt_finality_status(receipt: TxnReceipt<Felt>) -> TxnFinalityStatus {
+    match receipt {
+        TxnReceipt::Invoke(receipt) => receipt.common_receipt_properties.finality_status,
+        TxnReceipt::L1Handler(receipt) => receipt.common_receipt_properties.finality_status,
+        TxnReceipt::Declare(receipt) => receipt.common_receipt_properties.finality_status,
+        TxnReceipt::Deploy(receipt) => receipt.common_receipt_properties.finality_status,
+        TxnReceipt::DeployAccount(receipt) => receipt.common_receipt_properties.finality_status,
+    }
+}
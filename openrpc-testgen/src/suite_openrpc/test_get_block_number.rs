@@ -2,21 +2,24 @@ use starknet_types_core::felt::Felt;
 
 use crate::{
     assert_result,
-    utils::v7::{
-        accounts::{
-            account::{Account, ConnectedAccount},
-            call::Call,
-            creation::create::{create_account, AccountType},
-            deployment::{
-                deploy::{deploy_account, DeployAccountVersion},
-                structs::{ValidatedWaitParams, WaitForTx},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+                creation::create::{create_account, AccountType},
+                deployment::{
+                    deploy::{deploy_account, DeployAccountVersion},
+                    structs::{ValidatedWaitParams, WaitForTx},
+                },
             },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
         },
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, wait_for_sent_transaction},
-        },
-        providers::provider::Provider,
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
@@ -43,7 +46,7 @@ impl RunnableTrait for TestCase {
         let transfer_execution = test_input
             .random_paymaster_account
             .execute_v3(vec![Call {
-                to: Felt::from_hex("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D")?,
+                to: network_registry().strk_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account.address, transfer_amount, Felt::ZERO],
             }])
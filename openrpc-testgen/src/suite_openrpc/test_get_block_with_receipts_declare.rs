@@ -2,12 +2,16 @@ use std::{path::PathBuf, str::FromStr};
 
 use crate::{
     assert_result,
-    utils::v7::{
-        accounts::account::{starknet_keccak, Account, ConnectedAccount},
-        endpoints::{
-            declare_contract::get_compiled_contract, errors::OpenRpcTestGenError, utils::wait_for_sent_transaction,
+    utils::{
+        network_registry::network_registry,
+        starknet_version::StarknetVersion,
+        v7::{
+            accounts::account::{starknet_keccak, Account, ConnectedAccount},
+            endpoints::{
+                declare_contract::get_compiled_contract, errors::OpenRpcTestGenError, utils::wait_for_sent_transaction,
+            },
+            providers::provider::Provider,
         },
-        providers::provider::Provider,
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
@@ -24,9 +28,6 @@ const GAS_PRICE: Felt = Felt::from_hex_unchecked("0x1e");
 const BLOB_GAS_PRICE: Felt = Felt::from_hex_unchecked("0x28");
 const DECLARE_TXN_GAS: u64 = 48000;
 const DECLARE_TXN_GAS_PRICE: u128 = 17;
-const STRK_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D");
-const SEQUENCER_ADDRESS: Felt = Felt::from_hex_unchecked("0x123");
 
 #[derive(Clone, Debug)]
 pub struct TestCase {}
@@ -114,18 +115,21 @@ impl RunnableTrait for TestCase {
             format!("Mismatch block number: {} != {}", block_header.block_number, block_hash_and_number.block_number)
         );
 
-        assert_result!(
-            block_header.l1_data_gas_price.price_in_fri == STRK_BLOB_GAS_PRICE,
-            format!(
-                "Mismatch l1 data gas price: {} != {}",
-                block_header.l1_data_gas_price.price_in_fri, STRK_BLOB_GAS_PRICE
-            )
-        );
+        // Blob data is only priced from starknet 0.13.1 on.
+        if StarknetVersion::parse(&block_header.starknet_version)?.has_l1_data_gas() {
+            assert_result!(
+                block_header.l1_data_gas_price.price_in_fri == STRK_BLOB_GAS_PRICE,
+                format!(
+                    "Mismatch l1 data gas price: {} != {}",
+                    block_header.l1_data_gas_price.price_in_fri, STRK_BLOB_GAS_PRICE
+                )
+            );
 
-        assert_result!(
-            block_header.l1_data_gas_price.price_in_wei == BLOB_GAS_PRICE,
-            format!("Mismatch gas price: {} != {}", block_header.l1_data_gas_price.price_in_wei, BLOB_GAS_PRICE)
-        );
+            assert_result!(
+                block_header.l1_data_gas_price.price_in_wei == BLOB_GAS_PRICE,
+                format!("Mismatch gas price: {} != {}", block_header.l1_data_gas_price.price_in_wei, BLOB_GAS_PRICE)
+            );
+        }
 
         assert_result!(
             block_header.l1_gas_price.price_in_fri == STRK_GAS_PRICE,
@@ -288,8 +292,12 @@ impl RunnableTrait for TestCase {
             .ok_or_else(|| OpenRpcTestGenError::Other("Event missing".to_string()))?;
 
         assert_result!(
-            event.from_address == STRK_ADDRESS,
-            format!("Expected event from address to be {:?}, got {:?}", STRK_ADDRESS, event.from_address)
+            event.from_address == network_registry().strk_address,
+            format!(
+                "Expected event from address to be {:?}, got {:?}",
+                network_registry().strk_address,
+                event.from_address
+            )
         );
 
         let event_data_first =
@@ -328,8 +336,12 @@ impl RunnableTrait for TestCase {
         let event_keys_third =
             *event.keys.get(2).ok_or_else(|| OpenRpcTestGenError::Other("Missing third event key".to_string()))?;
         assert_result!(
-            event_keys_third == SEQUENCER_ADDRESS,
-            format!("Expected third event key to be {:?}, got {:?}", SEQUENCER_ADDRESS, event_keys_third)
+            event_keys_third == network_registry().sequencer_address,
+            format!(
+                "Expected third event key to be {:?}, got {:?}",
+                network_registry().sequencer_address,
+                event_keys_third
+            )
         );
 
         assert_result!(
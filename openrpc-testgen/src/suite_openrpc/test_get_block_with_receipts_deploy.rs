@@ -2,15 +2,19 @@ use std::{path::PathBuf, str::FromStr};
 
 use crate::{
     assert_result,
-    utils::v7::{
-        accounts::account::{starknet_keccak, Account, ConnectedAccount},
-        contract::factory::ContractFactory,
-        endpoints::{
-            declare_contract::get_compiled_contract,
-            errors::{CallError, OpenRpcTestGenError},
-            utils::wait_for_sent_transaction,
+    utils::{
+        network_registry::network_registry,
+        starknet_version::StarknetVersion,
+        v7::{
+            accounts::account::{starknet_keccak, Account, ConnectedAccount},
+            contract::factory::ContractFactory,
+            endpoints::{
+                declare_contract::get_compiled_contract,
+                errors::{CallError, OpenRpcTestGenError},
+                utils::wait_for_sent_transaction,
+            },
+            providers::provider::Provider,
         },
-        providers::provider::Provider,
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
@@ -28,11 +32,6 @@ const GAS_PRICE: Felt = Felt::from_hex_unchecked("0x1e");
 const BLOB_GAS_PRICE: Felt = Felt::from_hex_unchecked("0x28");
 const DEPLOY_TXN_GAS: u64 = 706;
 const DEPLOY_TXN_GAS_PRICE: u128 = 15;
-const STRK_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D");
-const UDC_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
-const SEQUENCER_ADDRESS: Felt = Felt::from_hex_unchecked("0x123");
 
 #[derive(Clone, Debug)]
 pub struct TestCase {}
@@ -166,21 +165,24 @@ impl RunnableTrait for TestCase {
             )
         );
 
-        assert_result!(
-            block_header.l1_data_gas_price.price_in_fri == STRK_BLOB_GAS_PRICE,
-            format!(
-                "Expected L1 data gas price in FRI to be {}, but got {}.",
-                block_header.l1_data_gas_price.price_in_fri, STRK_BLOB_GAS_PRICE
-            )
-        );
+        // Blob data is only priced from starknet 0.13.1 on.
+        if StarknetVersion::parse(&block_header.starknet_version)?.has_l1_data_gas() {
+            assert_result!(
+                block_header.l1_data_gas_price.price_in_fri == STRK_BLOB_GAS_PRICE,
+                format!(
+                    "Expected L1 data gas price in FRI to be {}, but got {}.",
+                    block_header.l1_data_gas_price.price_in_fri, STRK_BLOB_GAS_PRICE
+                )
+            );
 
-        assert_result!(
-            block_header.l1_data_gas_price.price_in_wei == BLOB_GAS_PRICE,
-            format!(
-                "Expected L1 data gas price in WEI to be {}, but got {}.",
-                block_header.l1_data_gas_price.price_in_wei, BLOB_GAS_PRICE
-            )
-        );
+            assert_result!(
+                block_header.l1_data_gas_price.price_in_wei == BLOB_GAS_PRICE,
+                format!(
+                    "Expected L1 data gas price in WEI to be {}, but got {}.",
+                    block_header.l1_data_gas_price.price_in_wei, BLOB_GAS_PRICE
+                )
+            );
+        }
 
         assert_result!(
             block_header.l1_gas_price.price_in_fri == STRK_GAS_PRICE,
@@ -246,8 +248,12 @@ impl RunnableTrait for TestCase {
             *deploy_calldata.get(1).ok_or_else(|| OpenRpcTestGenError::Other("Missing calldata".to_string()))?;
 
         assert_result!(
-            deploy_calldata_udc == UDC_ADDRESS,
-            format!("Expected UDC address in calldata to be {}, but got {}.", UDC_ADDRESS, deploy_calldata_udc)
+            deploy_calldata_udc == network_registry().udc_address,
+            format!(
+                "Expected UDC address in calldata to be {}, but got {}.",
+                network_registry().udc_address,
+                deploy_calldata_udc
+            )
         );
 
         let keccak_deploy_account = starknet_keccak("deployContract".as_bytes());
@@ -436,8 +442,12 @@ impl RunnableTrait for TestCase {
             .ok_or_else(|| OpenRpcTestGenError::Other("Event missing".to_string()))?;
 
         assert_result!(
-            first_event.from_address == UDC_ADDRESS,
-            format!("Expected event from address to be {:?}, but got {:?}", UDC_ADDRESS, first_event.from_address)
+            first_event.from_address == network_registry().udc_address,
+            format!(
+                "Expected event from address to be {:?}, but got {:?}",
+                network_registry().udc_address,
+                first_event.from_address
+            )
         );
 
         let first_event_data_first = *first_event
@@ -530,8 +540,12 @@ impl RunnableTrait for TestCase {
             .ok_or_else(|| OpenRpcTestGenError::Other("Event missing".to_string()))?;
 
         assert_result!(
-            second_event.from_address == STRK_ADDRESS,
-            format!("Expected event from address to be {:?}, got {:?}", STRK_ADDRESS, second_event.from_address)
+            second_event.from_address == network_registry().strk_address,
+            format!(
+                "Expected event from address to be {:?}, got {:?}",
+                network_registry().strk_address,
+                second_event.from_address
+            )
         );
 
         let second_event_data_first = *second_event
@@ -587,10 +601,11 @@ impl RunnableTrait for TestCase {
             .get(2)
             .ok_or_else(|| OpenRpcTestGenError::Other("Missing second event key".to_string()))?;
         assert_result!(
-            second_event_keys_third == SEQUENCER_ADDRESS,
+            second_event_keys_third == network_registry().sequencer_address,
             format!(
                 "Invalid sequencer address in event keys, expected {}, got {:?}",
-                SEQUENCER_ADDRESS, second_event_keys_third
+                network_registry().sequencer_address,
+                second_event_keys_third
             )
         );
 
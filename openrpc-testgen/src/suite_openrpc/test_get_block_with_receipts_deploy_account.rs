@@ -1,23 +1,27 @@
 use crate::{
     assert_result,
-    utils::v7::{
-        accounts::{
-            account::{starknet_keccak, Account, ConnectedAccount},
-            call::Call,
-            creation::create::{create_account, AccountType},
-            deployment::{
-                deploy::{
-                    deploy_account_v3_from_request, estimate_fee_deploy_account, get_deploy_account_request,
-                    DeployAccountVersion,
+    utils::{
+        network_registry::network_registry,
+        starknet_version::StarknetVersion,
+        v7::{
+            accounts::{
+                account::{starknet_keccak, Account, ConnectedAccount},
+                call::Call,
+                creation::create::{create_account, AccountType},
+                deployment::{
+                    deploy::{
+                        deploy_account_v3_from_request, estimate_fee_deploy_account, get_deploy_account_request,
+                        DeployAccountVersion,
+                    },
+                    structs::{ValidatedWaitParams, WaitForTx},
                 },
-                structs::{ValidatedWaitParams, WaitForTx},
             },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
         },
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, wait_for_sent_transaction},
-        },
-        providers::provider::Provider,
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
@@ -34,9 +38,6 @@ const GAS_PRICE: Felt = Felt::from_hex_unchecked("0x1e");
 const BLOB_GAS_PRICE: Felt = Felt::from_hex_unchecked("0x28");
 const DEPLOY_ACCOUNT_TXN_GAS: u64 = 886;
 const DEPLOY_ACCOUNT_TXN_GAS_PRICE: u128 = 15;
-const STRK_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D");
-const SEQUENCER_ADDRESS: Felt = Felt::from_hex_unchecked("0x123");
 
 #[derive(Clone, Debug)]
 pub struct TestCase {}
@@ -58,7 +59,7 @@ impl RunnableTrait for TestCase {
         let transfer_execution = test_input
             .random_paymaster_account
             .execute_v3(vec![Call {
-                to: Felt::from_hex("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D")?,
+                to: network_registry().strk_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account_data.address, transfer_amount, Felt::ZERO],
             }])
@@ -172,21 +173,24 @@ impl RunnableTrait for TestCase {
             )
         );
 
-        assert_result!(
-            block_header.l1_data_gas_price.price_in_fri == STRK_BLOB_GAS_PRICE,
-            format!(
-                "Expected L1 data gas price in FRI to be {:?}, but got {:?}.",
-                block_header.l1_data_gas_price.price_in_fri, STRK_BLOB_GAS_PRICE
-            )
-        );
+        // Blob data is only priced from starknet 0.13.1 on.
+        if StarknetVersion::parse(&block_header.starknet_version)?.has_l1_data_gas() {
+            assert_result!(
+                block_header.l1_data_gas_price.price_in_fri == STRK_BLOB_GAS_PRICE,
+                format!(
+                    "Expected L1 data gas price in FRI to be {:?}, but got {:?}.",
+                    block_header.l1_data_gas_price.price_in_fri, STRK_BLOB_GAS_PRICE
+                )
+            );
 
-        assert_result!(
-            block_header.l1_data_gas_price.price_in_wei == BLOB_GAS_PRICE,
-            format!(
-                "Expected L1 data gas price in WEI to be {:?}, but got {:?}.",
-                block_header.l1_data_gas_price.price_in_wei, BLOB_GAS_PRICE
-            )
-        );
+            assert_result!(
+                block_header.l1_data_gas_price.price_in_wei == BLOB_GAS_PRICE,
+                format!(
+                    "Expected L1 data gas price in WEI to be {:?}, but got {:?}.",
+                    block_header.l1_data_gas_price.price_in_wei, BLOB_GAS_PRICE
+                )
+            );
+        }
 
         assert_result!(
             block_header.l1_gas_price.price_in_fri == STRK_GAS_PRICE,
@@ -407,8 +411,12 @@ impl RunnableTrait for TestCase {
             .ok_or_else(|| OpenRpcTestGenError::Other("Event missing".to_string()))?;
 
         assert_result!(
-            second_event.from_address == STRK_ADDRESS,
-            format!("Expected event from address to be {:?}, got {:?}", STRK_ADDRESS, second_event.from_address)
+            second_event.from_address == network_registry().strk_address,
+            format!(
+                "Expected event from address to be {:?}, got {:?}",
+                network_registry().strk_address,
+                second_event.from_address
+            )
         );
 
         let second_event_data_first = *second_event
@@ -464,10 +472,11 @@ impl RunnableTrait for TestCase {
             .get(2)
             .ok_or_else(|| OpenRpcTestGenError::Other("Missing second event key".to_string()))?;
         assert_result!(
-            second_event_keys_third == SEQUENCER_ADDRESS,
+            second_event_keys_third == network_registry().sequencer_address,
             format!(
                 "Invalid sequencer address in event keys, expected {:?}, got {:?}",
-                SEQUENCER_ADDRESS, second_event_keys_third
+                network_registry().sequencer_address,
+                second_event_keys_third
             )
         );
 
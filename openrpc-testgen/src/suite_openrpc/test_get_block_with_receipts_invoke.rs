@@ -1,15 +1,19 @@
 use crate::{
     assert_result,
-    utils::v7::{
-        accounts::{
-            account::{starknet_keccak, Account, ConnectedAccount},
-            call::Call,
-        },
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, wait_for_sent_transaction},
+    utils::{
+        network_registry::network_registry,
+        starknet_version::StarknetVersion,
+        v7::{
+            accounts::{
+                account::{starknet_keccak, Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
         },
-        providers::provider::Provider,
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
@@ -26,10 +30,6 @@ const GAS_PRICE: Felt = Felt::from_hex_unchecked("0x1e");
 const BLOB_GAS_PRICE: Felt = Felt::from_hex_unchecked("0x28");
 const INVOKE_TXN_GAS: u64 = 994;
 const INVOKE_TXN_GAS_PRICE: u128 = 15;
-const STRK_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D");
-const SEQUENCER_ADDRESS: Felt = Felt::from_hex_unchecked("0x123");
-const ETH_ADDRESS: Felt = Felt::from_hex_unchecked("0x49D36570D4E46F48E99674BD3FCC84644DDD6B96F7C741B1562B82F9E004DC7");
 
 #[derive(Clone, Debug)]
 pub struct TestCase {}
@@ -47,7 +47,7 @@ impl RunnableTrait for TestCase {
 
         let estimate_fee = sender
             .execute_v3(vec![Call {
-                to: ETH_ADDRESS,
+                to: network_registry().eth_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: transfer_calldata.clone(),
             }])
@@ -56,7 +56,7 @@ impl RunnableTrait for TestCase {
 
         let invoke_request = sender
             .execute_v3(vec![Call {
-                to: ETH_ADDRESS,
+                to: network_registry().eth_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: transfer_calldata.clone(),
             }])
@@ -133,21 +133,24 @@ impl RunnableTrait for TestCase {
             )
         );
 
-        assert_result!(
-            block_header.l1_data_gas_price.price_in_fri == STRK_BLOB_GAS_PRICE,
-            format!(
-                "Expected L1 data gas price in FRI to be {}, but got {}.",
-                block_header.l1_data_gas_price.price_in_fri, STRK_BLOB_GAS_PRICE
-            )
-        );
+        // Blob data is only priced from starknet 0.13.1 on.
+        if StarknetVersion::parse(&block_header.starknet_version)?.has_l1_data_gas() {
+            assert_result!(
+                block_header.l1_data_gas_price.price_in_fri == STRK_BLOB_GAS_PRICE,
+                format!(
+                    "Expected L1 data gas price in FRI to be {}, but got {}.",
+                    block_header.l1_data_gas_price.price_in_fri, STRK_BLOB_GAS_PRICE
+                )
+            );
 
-        assert_result!(
-            block_header.l1_data_gas_price.price_in_wei == BLOB_GAS_PRICE,
-            format!(
-                "Expected L1 data gas price in WEI to be {}, but got {}.",
-                block_header.l1_data_gas_price.price_in_wei, BLOB_GAS_PRICE
-            )
-        );
+            assert_result!(
+                block_header.l1_data_gas_price.price_in_wei == BLOB_GAS_PRICE,
+                format!(
+                    "Expected L1 data gas price in WEI to be {}, but got {}.",
+                    block_header.l1_data_gas_price.price_in_wei, BLOB_GAS_PRICE
+                )
+            );
+        }
 
         assert_result!(
             block_header.l1_gas_price.price_in_fri == STRK_GAS_PRICE,
@@ -220,10 +223,11 @@ impl RunnableTrait for TestCase {
             *invoke_calldata.get(1).ok_or_else(|| OpenRpcTestGenError::Other("Missing calldata".to_string()))?;
 
         assert_result!(
-            invoke_calldata_eth_address == ETH_ADDRESS,
+            invoke_calldata_eth_address == network_registry().eth_address,
             format!(
                 "Expected UDC address in calldata to be {:?}, but got {:?}.",
-                ETH_ADDRESS, invoke_calldata_eth_address
+                network_registry().eth_address,
+                invoke_calldata_eth_address
             )
         );
 
@@ -402,8 +406,12 @@ impl RunnableTrait for TestCase {
             .ok_or_else(|| OpenRpcTestGenError::Other("Event missing".to_string()))?;
 
         assert_result!(
-            first_event.from_address == ETH_ADDRESS,
-            format!("Expected event from address to be {:?}, but got {:?}", ETH_ADDRESS, first_event.from_address)
+            first_event.from_address == network_registry().eth_address,
+            format!(
+                "Expected event from address to be {:?}, but got {:?}",
+                network_registry().eth_address,
+                first_event.from_address
+            )
         );
 
         let first_event_data_first = *first_event
@@ -470,8 +478,12 @@ impl RunnableTrait for TestCase {
             .ok_or_else(|| OpenRpcTestGenError::Other("Event missing".to_string()))?;
 
         assert_result!(
-            second_event.from_address == STRK_ADDRESS,
-            format!("Expected event from address to be {:?}, got {:?}", STRK_ADDRESS, second_event.from_address)
+            second_event.from_address == network_registry().strk_address,
+            format!(
+                "Expected event from address to be {:?}, got {:?}",
+                network_registry().strk_address,
+                second_event.from_address
+            )
         );
 
         let second_event_data_first = *second_event
@@ -527,10 +539,11 @@ impl RunnableTrait for TestCase {
             .get(2)
             .ok_or_else(|| OpenRpcTestGenError::Other("Missing second event key".to_string()))?;
         assert_result!(
-            second_event_keys_third == SEQUENCER_ADDRESS,
+            second_event_keys_third == network_registry().sequencer_address,
             format!(
                 "Invalid sequencer address in event keys, expected {}, got {:?}",
-                SEQUENCER_ADDRESS, second_event_keys_third
+                network_registry().sequencer_address,
+                second_event_keys_third
             )
         );
 
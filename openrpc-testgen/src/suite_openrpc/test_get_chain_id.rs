@@ -1,13 +1,25 @@
 use starknet_types_core::felt::Felt;
 
 use crate::{
-    assert_result,
-    utils::v7::{
-        accounts::account::ConnectedAccount, endpoints::errors::OpenRpcTestGenError, providers::provider::Provider,
+    assert_eq_result, assert_result,
+    utils::{
+        chain_id::{decode_chain_id, expected_chain_name},
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
+        },
     },
-    RunnableTrait,
+    RandomizableAccountsTrait, RunnableTrait,
 };
-const EXPECTED_CHAIN_ID: Felt = Felt::from_hex_unchecked("0x4d41444152415f4445564e4554");
+
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -22,11 +34,46 @@ impl RunnableTrait for TestCase {
         assert_result!(result);
 
         let chain_id = chain_id?;
+        let chain_name = decode_chain_id(chain_id)?;
 
         assert_result!(
-            chain_id == EXPECTED_CHAIN_ID,
-            format!("Mismatch chain id: {:?} != {:?}", chain_id, EXPECTED_CHAIN_ID)
+            chain_name == expected_chain_name(),
+            format!("Mismatch chain id: {:?} ({:#x}) != {:?}", chain_name, chain_id, expected_chain_name())
+        );
+
+        // Transaction hashes are computed with the chain id of the account, a different one than the
+        // node's only shows up as invalid signatures otherwise.
+        let accounts = test_input.random_paymaster_account.accounts().iter();
+        for account in accounts.chain(&test_input.random_executable_account.accounts) {
+            let account_chain_id = account.chain_id();
+            let account_chain_name = decode_chain_id(account_chain_id).unwrap_or_else(|e| e.to_string());
+            assert_result!(
+                account_chain_id == chain_id,
+                format!(
+                    "Account {:#x} computes transaction hashes for chain {:?} while the node is {:?}",
+                    account.address(),
+                    account_chain_name,
+                    chain_name
+                )
+            );
+        }
+
+        let sender = test_input.random_paymaster_account.random_accounts()?;
+        let transfer_to_self = Call {
+            to: network_registry().strk_address,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![sender.address(), Felt::ZERO, Felt::ZERO],
+        };
+        let execution = sender.execute_v3(vec![transfer_to_self]).prepare().await?;
+        let expected_hash = execution.transaction_hash(false);
+        let result = execution.send().await?;
+        assert_eq_result!(
+            result.transaction_hash,
+            expected_hash,
+            "Node computed the transaction hash with another chain id than {:?}",
+            chain_name
         );
+        wait_for_sent_transaction(result.transaction_hash, &sender).await?;
 
         Ok(Self {})
     }
@@ -0,0 +1,80 @@
+use starknet_types_rpc::{BlockId, EventFilterWithPageRequest};
+
+use crate::{
+    assert_result, openrpc_test,
+    utils::{
+        event_backfill::EventBackfill,
+        v7::{
+            accounts::account::ConnectedAccount, endpoints::errors::OpenRpcTestGenError, providers::provider::Provider,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+/// Blocks covered by the comparison, small enough for a single `getEvents` page.
+const RANGE_LENGTH: u64 = 5;
+const SINGLE_SHOT_CHUNK_SIZE: u64 = 1024;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "get_events_backfill")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let provider = account.provider();
+
+        let to_block = provider.block_number().await?;
+        let from_block = to_block.saturating_sub(RANGE_LENGTH - 1);
+
+        let single_shot = provider
+            .get_events(EventFilterWithPageRequest {
+                address: None,
+                from_block: Some(BlockId::Number(from_block)),
+                to_block: Some(BlockId::Number(to_block)),
+                keys: None,
+                chunk_size: SINGLE_SHOT_CHUNK_SIZE,
+                continuation_token: None,
+            })
+            .await?;
+        assert_result!(
+            single_shot.continuation_token.is_none(),
+            format!(
+                "Blocks {from_block}..={to_block} have more than {SINGLE_SHOT_CHUNK_SIZE} events, no single-shot \
+                 reference"
+            )
+        );
+
+        // Tiny pages and segments force both continuation tokens and segment boundaries.
+        let checkpoint_path = std::env::temp_dir().join(format!("get_events_backfill_{}.json", account.address()));
+        let backfilled = EventBackfill::new(provider, from_block, to_block)
+            .chunk_size(2)
+            .blocks_per_query(2)
+            .checkpoint_path(&checkpoint_path)
+            .run()
+            .await?;
+
+        // Compared as JSON, the form in which nodes return the events.
+        let backfilled_json =
+            serde_json::to_value(&backfilled).map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+        let single_shot_json =
+            serde_json::to_value(&single_shot.events).map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+        assert_result!(
+            backfilled_json == single_shot_json,
+            format!(
+                "Paged events differ from the single-shot query over blocks {from_block}..={to_block}: {} paged, {} \
+                 single-shot",
+                backfilled.len(),
+                single_shot.events.len()
+            )
+        );
+        assert_result!(
+            !checkpoint_path.exists(),
+            format!("Checkpoint {} was not removed after the backfill completed", checkpoint_path.display())
+        );
+
+        Ok(Self {})
+    }
+}
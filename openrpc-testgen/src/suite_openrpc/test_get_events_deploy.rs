@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 use std::str::FromStr;
 
+use crate::utils::network_registry::network_registry;
 use crate::utils::v7::accounts::account::{starknet_keccak, Account, ConnectedAccount};
 use crate::utils::v7::contract::factory::ContractFactory;
 use crate::utils::v7::endpoints::declare_contract::get_compiled_contract;
@@ -40,7 +41,7 @@ impl RunnableTrait for TestCase {
         .await?;
 
         let sender = test_input.random_paymaster_account.random_accounts()?;
-        let strk_address = Felt::from_hex("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D")?;
+        let strk_address = network_registry().strk_address;
 
         let factory = ContractFactory::new(declaration_result.class_hash, sender.clone());
         let mut salt_buffer = [0u8; 32];
@@ -7,8 +7,11 @@ use crate::utils::v7::endpoints::declare_contract::get_compiled_contract;
 use crate::utils::v7::endpoints::utils::wait_for_sent_transaction;
 use crate::{assert_result, RandomizableAccountsTrait};
 use crate::{
-    utils::v7::{
-        accounts::account::ConnectedAccount, endpoints::errors::OpenRpcTestGenError, providers::provider::Provider,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::account::ConnectedAccount, endpoints::errors::OpenRpcTestGenError, providers::provider::Provider,
+        },
     },
     RunnableTrait,
 };
@@ -18,9 +21,6 @@ use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{BroadcastedInvokeTxn, BroadcastedTxn, DaMode, InvokeTxn, Txn};
 use t9n::txn_validation::invoke::verify_invoke_v3_signature;
 
-const UDC_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
-
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -123,8 +123,12 @@ impl RunnableTrait for TestCase {
             .ok_or_else(|| OpenRpcTestGenError::Other("Missing last calldata element".to_string()))?;
 
         assert_result!(
-            calldata_second == UDC_ADDRESS,
-            format!("Expected second calldata element to be {:#?}, got {:#?}", UDC_ADDRESS, calldata_second)
+            calldata_second == network_registry().udc_address,
+            format!(
+                "Expected second calldata element to be {:#?}, got {:#?}",
+                network_registry().udc_address,
+                calldata_second
+            )
         );
 
         let calldata_third = *txn
@@ -8,8 +8,11 @@ use crate::utils::v7::accounts::deployment::structs::{ValidatedWaitParams, WaitF
 use crate::utils::v7::endpoints::utils::{get_selector_from_name, wait_for_sent_transaction};
 use crate::{assert_result, RandomizableAccountsTrait};
 use crate::{
-    utils::v7::{
-        accounts::account::ConnectedAccount, endpoints::errors::OpenRpcTestGenError, providers::provider::Provider,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::account::ConnectedAccount, endpoints::errors::OpenRpcTestGenError, providers::provider::Provider,
+        },
     },
     RunnableTrait,
 };
@@ -37,7 +40,7 @@ impl RunnableTrait for TestCase {
         let transfer_execution = test_input
             .random_paymaster_account
             .execute_v3(vec![Call {
-                to: Felt::from_hex("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D")?,
+                to: network_registry().strk_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account_data.address, transfer_amount, Felt::ZERO],
             }])
@@ -3,10 +3,13 @@ use crate::utils::v7::accounts::call::Call;
 use crate::utils::v7::endpoints::utils::wait_for_sent_transaction;
 use crate::{assert_result, RandomizableAccountsTrait};
 use crate::{
-    utils::v7::{
-        accounts::account::ConnectedAccount,
-        endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
-        providers::provider::Provider,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::account::ConnectedAccount,
+            endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+            providers::provider::Provider,
+        },
     },
     RunnableTrait,
 };
@@ -20,7 +23,7 @@ impl RunnableTrait for TestCase {
     type Input = super::TestSuiteOpenRpc;
 
     async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
-        let strk_address = Felt::from_hex("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D")?;
+        let strk_address = network_registry().strk_address;
         let receiptent_address = Felt::from_hex("0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefd3ad")?;
         let transfer_amount = Felt::from_hex("0xfffffffffffffff")?;
         let sender = test_input.random_paymaster_account.random_accounts()?;
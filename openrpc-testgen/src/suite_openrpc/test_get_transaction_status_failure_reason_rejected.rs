@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use starknet_types_core::felt::Felt;
+use tracing::info;
+
+use crate::{
+    assert_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+            providers::provider::Provider,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+/// How many times the status is polled before the transaction counts as never rejected.
+const POLL_ATTEMPTS: u32 = 30;
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "get_transaction_status_failure_reason_rejected")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let sender = test_input.random_paymaster_account.random_accounts()?;
+        let transfer_to_self = Call {
+            to: network_registry().strk_address,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![sender.address(), Felt::ZERO, Felt::ZERO],
+        };
+        let prepared = sender.execute_v3(vec![transfer_to_self]).prepare().await?;
+
+        // Most nodes validate on submission and answer with an error, leaving no status to look at.
+        // Those validating later accept the hash and reject the transaction afterwards.
+        let rejected = match prepared.send_with_custom_signature(vec![Felt::ONE, Felt::TWO]).await {
+            Ok(result) => result,
+            Err(e) => {
+                info!("Invalid signature rejected on submission, there is no status to check: {}", e);
+                return Ok(Self {});
+            }
+        };
+
+        let params = json!({ "transaction_hash": format!("{:#x}", rejected.transaction_hash) });
+        let provider = sender.provider();
+        let mut status = Value::Null;
+        for _ in 0..POLL_ATTEMPTS {
+            status = provider.send_raw("starknet_getTransactionStatus", params.clone()).await?;
+            if status["finality_status"] != json!("RECEIVED") {
+                break;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        assert_result!(
+            status["finality_status"] == json!("REJECTED"),
+            format!("Transaction with an invalid signature was not rejected: {}", status)
+        );
+        assert_result!(
+            matches!(status.get("failure_reason"), Some(Value::String(reason)) if !reason.is_empty()),
+            format!("Status of a rejected transaction carries no failure reason: {}", status)
+        );
+
+        Ok(Self {})
+    }
+}
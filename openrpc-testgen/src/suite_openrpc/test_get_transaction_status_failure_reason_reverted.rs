@@ -0,0 +1,72 @@
+use serde_json::{json, Value};
+use starknet_types_core::felt::Felt;
+
+use crate::{
+    assert_eq_result, assert_matches_result, assert_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "get_transaction_status_failure_reason_reverted")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let sender = test_input.random_paymaster_account.random_accounts()?;
+        let transfer_selector = get_selector_from_name("transfer")?;
+        let transfer_to_self = |amount: Felt| Call {
+            to: network_registry().strk_address,
+            selector: transfer_selector,
+            calldata: vec![sender.address(), amount, Felt::ZERO],
+        };
+
+        // A transfer beyond the balance can't be estimated, it is sent with the bounds of one that
+        // succeeds, with a margin for the revert.
+        let succeeding = sender.execute_v3(vec![transfer_to_self(Felt::ZERO)]).prepare().await?;
+        let bounds = succeeding.get_raw_execution().await;
+        let reverting = sender
+            .execute_v3(vec![transfer_to_self(Felt::from(u128::MAX))])
+            .gas(bounds.gas() * 2)
+            .gas_price(bounds.gas_price())
+            .send()
+            .await?;
+
+        let result = wait_for_sent_transaction(reverting.transaction_hash, &sender).await;
+        assert_matches_result!(result, Err(OpenRpcTestGenError::TransactionFailed(_)));
+
+        let params = json!({ "transaction_hash": format!("{:#x}", reverting.transaction_hash) });
+        let provider = sender.provider();
+        let status = provider.send_raw("starknet_getTransactionStatus", params.clone()).await?;
+        let receipt = provider.send_raw("starknet_getTransactionReceipt", params).await?;
+
+        assert_eq_result!(status["execution_status"], json!("REVERTED"), "Execution status of the status");
+        assert_eq_result!(receipt["execution_status"], json!("REVERTED"), "Execution status of the receipt");
+        assert_result!(
+            matches!(status.get("failure_reason"), Some(Value::String(reason)) if !reason.is_empty()),
+            format!("Status of a reverted transaction carries no failure reason: {}", status)
+        );
+        assert_eq_result!(
+            status["failure_reason"],
+            receipt["revert_reason"],
+            "Failure reason of the status and revert reason of the receipt"
+        );
+
+        Ok(Self {})
+    }
+}
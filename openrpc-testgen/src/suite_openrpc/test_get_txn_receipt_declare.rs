@@ -1,20 +1,17 @@
-use crate::assert_result;
+use crate::utils::network_registry::network_registry;
 use crate::utils::v7::accounts::account::{starknet_keccak, Account, ConnectedAccount};
 use crate::utils::v7::endpoints::declare_contract::get_compiled_contract;
 use crate::utils::v7::endpoints::errors::CallError;
 use crate::utils::v7::endpoints::utils::wait_for_sent_transaction;
 use crate::utils::v7::providers::provider::Provider;
 use crate::RandomizableAccountsTrait;
+use crate::{assert_receipt_block_info, assert_receipt_common, assert_result};
 use crate::{utils::v7::endpoints::errors::OpenRpcTestGenError, RunnableTrait};
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{PriceUnit, TxnFinalityStatus, TxnReceipt};
 use std::path::PathBuf;
 use std::str::FromStr;
 
-const STRK_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D");
-const SEQUENCER_ADDRESS: Felt = Felt::from_hex_unchecked("0x123");
-
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -58,6 +55,15 @@ impl RunnableTrait for TestCase {
         };
 
         let common_receipt_properties = receipt.common_receipt_properties;
+        assert_receipt_common!(common_receipt_properties, declaration_result.transaction_hash, TxnFinalityStatus::L2);
+
+        let block_info = test_input
+            .random_paymaster_account
+            .provider()
+            .get_transaction_receipt_block_info(declaration_result.transaction_hash)
+            .await?;
+        assert_receipt_block_info!(block_info);
+
         let actual_fee = common_receipt_properties.actual_fee;
         assert_result!(
             actual_fee.amount == estimate_fee.overall_fee,
@@ -70,28 +76,11 @@ impl RunnableTrait for TestCase {
             format!("Actual fee unit expected: {:?}, actual: {:?}", expected_unit, actual_fee.unit)
         );
 
-        let expected_finality_status = TxnFinalityStatus::L2;
-        assert_result!(
-            common_receipt_properties.finality_status == expected_finality_status,
-            format!(
-                "Expected finality status: {:?}, actual: {:?}",
-                expected_finality_status, common_receipt_properties.finality_status
-            )
-        );
-
         assert_result!(
             common_receipt_properties.messages_sent.is_empty(),
             format!("Expected no messages sent, actual: {:?}", common_receipt_properties.messages_sent)
         );
 
-        assert_result!(
-            common_receipt_properties.transaction_hash == declaration_result.transaction_hash,
-            format!(
-                "Expected transaction hash: {:?}, actual: {:?}",
-                declaration_result.transaction_hash, common_receipt_properties.transaction_hash
-            )
-        );
-
         let execution_status = match common_receipt_properties.anon {
             starknet_types_rpc::Anonymous::Successful(status) => status.execution_status,
             _ => {
@@ -110,7 +99,7 @@ impl RunnableTrait for TestCase {
         assert_result!(events.len() == 1, format!("Expected 1 event, got {}", events.len()));
 
         let event = events.first().ok_or_else(|| OpenRpcTestGenError::Other("Event not found".to_string()))?;
-        assert_result!(event.from_address == STRK_ADDRESS);
+        assert_result!(event.from_address == network_registry().strk_address);
 
         assert_result!(event.data.len() == 2, format!("Expected 2 data items, got {}", event.data.len()));
 
@@ -152,8 +141,8 @@ impl RunnableTrait for TestCase {
             *event.keys.last().ok_or_else(|| OpenRpcTestGenError::Other("Event key not found".to_string()))?;
 
         assert_result!(
-            event_key_third == SEQUENCER_ADDRESS,
-            format!("Expected event key to be {:?}, got {:?}", SEQUENCER_ADDRESS, event_key_third)
+            event_key_third == network_registry().sequencer_address,
+            format!("Expected event key to be {:?}, got {:?}", network_registry().sequencer_address, event_key_third)
         );
 
         Ok(Self {})
@@ -1,4 +1,4 @@
-use crate::assert_result;
+use crate::utils::network_registry::network_registry;
 use crate::utils::v7::accounts::account::{starknet_keccak, Account, ConnectedAccount};
 use crate::utils::v7::accounts::call::Call;
 use crate::utils::v7::accounts::creation::create::{create_account, AccountType};
@@ -10,14 +10,11 @@ use crate::utils::v7::endpoints::errors::CallError;
 use crate::utils::v7::endpoints::utils::{get_selector_from_name, wait_for_sent_transaction};
 use crate::utils::v7::providers::provider::Provider;
 use crate::RandomizableAccountsTrait;
+use crate::{assert_receipt_block_info, assert_receipt_common, assert_result};
 use crate::{utils::v7::endpoints::errors::OpenRpcTestGenError, RunnableTrait};
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{PriceUnit, TxnFinalityStatus, TxnReceipt};
 
-const STRK_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D");
-const SEQUENCER_ADDRESS: Felt = Felt::from_hex_unchecked("0x123");
-
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -38,7 +35,7 @@ impl RunnableTrait for TestCase {
         let transfer_execution = test_input
             .random_paymaster_account
             .execute_v3(vec![Call {
-                to: Felt::from_hex("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D")?,
+                to: network_registry().strk_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account_data.address, transfer_amount, Felt::ZERO],
             }])
@@ -91,6 +88,15 @@ impl RunnableTrait for TestCase {
         );
 
         let common_receipt_properties = receipt.common_receipt_properties;
+        assert_receipt_common!(common_receipt_properties, deploy_account_hash, TxnFinalityStatus::L2);
+
+        let block_info = test_input
+            .random_paymaster_account
+            .provider()
+            .get_transaction_receipt_block_info(deploy_account_hash)
+            .await?;
+        assert_receipt_block_info!(block_info);
+
         let actual_fee = common_receipt_properties.actual_fee;
         assert_result!(
             actual_fee.amount == estimate_fee.overall_fee,
@@ -103,28 +109,11 @@ impl RunnableTrait for TestCase {
             format!("Actual fee unit expected: {:?}, actual: {:?}", expected_unit, actual_fee.unit)
         );
 
-        let expected_finality_status = TxnFinalityStatus::L2;
-        assert_result!(
-            common_receipt_properties.finality_status == expected_finality_status,
-            format!(
-                "Expected finality status: {:?}, actual: {:?}",
-                expected_finality_status, common_receipt_properties.finality_status
-            )
-        );
-
         assert_result!(
             common_receipt_properties.messages_sent.is_empty(),
             format!("Expected no messages sent, actual: {:?}", common_receipt_properties.messages_sent)
         );
 
-        assert_result!(
-            common_receipt_properties.transaction_hash == deploy_account_hash,
-            format!(
-                "Expected transaction hash: {:?}, actual: {:?}",
-                deploy_account_hash, common_receipt_properties.transaction_hash
-            )
-        );
-
         let execution_status = match common_receipt_properties.anon {
             starknet_types_rpc::Anonymous::Successful(status) => status.execution_status,
             _ => {
@@ -182,8 +171,12 @@ impl RunnableTrait for TestCase {
         let second_event = events.get(1).ok_or_else(|| OpenRpcTestGenError::Other("Event missing".to_string()))?;
 
         assert_result!(
-            second_event.from_address == STRK_ADDRESS,
-            format!("Expected event from address to be {:?}, got {:?}", STRK_ADDRESS, second_event.from_address)
+            second_event.from_address == network_registry().strk_address,
+            format!(
+                "Expected event from address to be {:?}, got {:?}",
+                network_registry().strk_address,
+                second_event.from_address
+            )
         );
 
         let second_event_data_first = *second_event
@@ -239,10 +232,11 @@ impl RunnableTrait for TestCase {
             .get(2)
             .ok_or_else(|| OpenRpcTestGenError::Other("Missing second event key".to_string()))?;
         assert_result!(
-            second_event_keys_third == SEQUENCER_ADDRESS,
+            second_event_keys_third == network_registry().sequencer_address,
             format!(
                 "Invalid sequencer address in event keys, expected {}, got {:?}",
-                SEQUENCER_ADDRESS, second_event_keys_third
+                network_registry().sequencer_address,
+                second_event_keys_third
             )
         );
 
@@ -0,0 +1,57 @@
+use crate::{
+    assert_eq_result, assert_matches_result, openrpc_test,
+    utils::v7::{
+        accounts::account::{Account, ConnectedAccount},
+        endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+        providers::{
+            jsonrpc::StarknetError,
+            provider::{Provider, ProviderError},
+        },
+        signers::{key_pair::SigningKey, local_wallet::LocalWallet, signer::Signer},
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BroadcastedInvokeTxn, BroadcastedTxn, InvokeTxnV0};
+use t9n::txn_hashes::invoke_hash::calculate_invoke_v0_hash;
+
+const INVOKE_V0_MAX_FEE: Felt = Felt::from_hex_unchecked("0x16345785d8a0000");
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "invoke_v0_unsupported")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let sender = test_input.random_executable_account.random_accounts()?;
+        let initial_nonce = sender.get_nonce().await?;
+
+        // A properly signed legacy invoke, an empty multicall, so that the version is the only reason
+        // to reject it.
+        let mut txn = InvokeTxnV0 {
+            calldata: vec![Felt::ZERO],
+            contract_address: sender.address(),
+            entry_point_selector: get_selector_from_name("__execute__")?,
+            max_fee: INVOKE_V0_MAX_FEE,
+            signature: vec![],
+        };
+        let hash = calculate_invoke_v0_hash(&txn, &sender.chain_id())?;
+        let signature = LocalWallet::from(SigningKey::from_secret_scalar(test_input.executable_private_key))
+            .sign_hash(&hash)
+            .await?;
+        txn.signature = vec![signature.r, signature.s];
+
+        // Invoke v0 has been rejected since Starknet 0.11, nodes must report it with the dedicated
+        // error rather than as a validation failure.
+        let result =
+            sender.provider().add_invoke_transaction(BroadcastedTxn::Invoke(BroadcastedInvokeTxn::V0(txn))).await;
+        assert_matches_result!(result.unwrap_err(), ProviderError::StarknetError(StarknetError::UnsupportedTxVersion));
+
+        let nonce = sender.get_nonce().await?;
+        assert_eq_result!(nonce, initial_nonce);
+
+        Ok(Self {})
+    }
+}
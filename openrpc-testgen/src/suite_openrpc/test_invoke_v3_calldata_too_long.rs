@@ -0,0 +1,53 @@
+use crate::{
+    assert_eq_result, assert_matches_result, openrpc_test,
+    utils::{
+        oversized::{oversized_calldata, MAX_CALLDATA_LENGTH},
+        v7::{
+            accounts::{
+                account::{Account, AccountError, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+            providers::{jsonrpc::StarknetError, provider::ProviderError},
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const INVOKE_TXN_GAS: u64 = 100000;
+const INVOKE_TXN_GAS_PRICE: u128 = 17;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "invoke_v3_calldata_too_long")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let sender = test_input.random_paymaster_account.random_accounts()?;
+        let initial_nonce = sender.get_nonce().await?;
+
+        // The transaction is rejected by the node's stateless checks, before the call is executed.
+        let call = Call {
+            to: sender.address(),
+            selector: get_selector_from_name("get_balance")?,
+            calldata: oversized_calldata(MAX_CALLDATA_LENGTH + 1),
+        };
+        let result = sender.execute_v3(vec![call]).gas(INVOKE_TXN_GAS).gas_price(INVOKE_TXN_GAS_PRICE).send().await;
+
+        // The spec has no dedicated error for calldata length, so nodes report it as a validation
+        // failure or an unexpected error; a transport-level failure does not match.
+        assert_matches_result!(
+            result.unwrap_err(),
+            AccountError::Provider(ProviderError::StarknetError(
+                StarknetError::ValidationFailure(_) | StarknetError::UnexpectedError(_)
+            ))
+        );
+
+        let nonce = sender.get_nonce().await?;
+        assert_eq_result!(nonce, initial_nonce);
+
+        Ok(Self {})
+    }
+}
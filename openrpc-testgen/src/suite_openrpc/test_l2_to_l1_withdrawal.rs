@@ -0,0 +1,95 @@
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{Anonymous, TxnReceipt};
+use tracing::info;
+
+use crate::{
+    assert_eq_result, assert_matches_result, assert_result, openrpc_test,
+    utils::{
+        l1_messaging::{
+            consume_message_from_l2, flush_messages, l1_messaging_config, l2_to_l1_message_hash,
+            pending_l2_to_l1_messages, ANVIL_DEFAULT_ACCOUNT,
+        },
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{declare_if_missing, get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
+        },
+        version_replay::deploy_fresh_instance,
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const L1_MESSENGER: &str = "target/dev/contracts_contracts_l1_messenger_L1Messenger";
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+/// Withdrawal messages sent to L1 are listed in order in `messages_sent` of the receipt. With an
+/// L1 stack, they must also reach the core contract once flushed and be consumable there.
+#[openrpc_test(suite = "openrpc", name = "l2_to_l1_withdrawal")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let class_hash = declare_if_missing(&account, L1_MESSENGER).await?;
+        let messenger = deploy_fresh_instance(&account, class_hash, vec![]).await?;
+
+        let amounts = [Felt::from(1000), Felt::from(2000)];
+        let mut calldata = vec![ANVIL_DEFAULT_ACCOUNT, Felt::from(amounts.len())];
+        calldata.extend(amounts);
+        let withdrawal = account
+            .execute_v3(vec![Call { to: messenger, selector: get_selector_from_name("withdraw")?, calldata }])
+            .send()
+            .await?;
+        wait_for_sent_transaction(withdrawal.transaction_hash, &account).await?;
+
+        let receipt = match account.provider().get_transaction_receipt(withdrawal.transaction_hash).await? {
+            TxnReceipt::Invoke(receipt) => receipt.common_receipt_properties,
+            _ => return Err(OpenRpcTestGenError::Other("Expected an invoke transaction receipt".to_string())),
+        };
+        assert_matches_result!(receipt.anon, Anonymous::Successful(_));
+
+        let messages = &receipt.messages_sent;
+        assert_eq_result!(messages.len(), amounts.len(), "Number of messages sent to L1");
+        for (index, (message, amount)) in messages.iter().zip(amounts).enumerate() {
+            assert_eq_result!(message.from_address, messenger, "Sender of message {}", index);
+            assert_eq_result!(message.to_address, ANVIL_DEFAULT_ACCOUNT, "Recipient of message {}", index);
+            assert_eq_result!(message.payload, vec![Felt::from(index), amount], "Payload of message {}", index);
+        }
+
+        let Some(config) = l1_messaging_config() else {
+            info!("No L1 stack configured, l2_to_l1_withdrawal does not check the L1 side.");
+            return Ok(Self {});
+        };
+
+        flush_messages(config).await?;
+        let hashes: Vec<_> = messages
+            .iter()
+            .map(|message| l2_to_l1_message_hash(message.from_address, message.to_address, &message.payload))
+            .collect();
+        for (index, hash) in hashes.iter().enumerate() {
+            let pending = pending_l2_to_l1_messages(config, *hash).await?;
+            assert_eq_result!(pending, Felt::ONE, "Copies of message {} on L1 after the flush", index);
+        }
+
+        let first = &messages[0];
+        consume_message_from_l2(config, first.from_address, first.to_address, &first.payload).await?;
+        assert_result!(
+            pending_l2_to_l1_messages(config, hashes[0]).await? == Felt::ZERO,
+            "First message is still pending on L1 after its consumption"
+        );
+        assert_result!(
+            pending_l2_to_l1_messages(config, hashes[1]).await? == Felt::ONE,
+            "Consuming the first message also consumed the second one"
+        );
+
+        Ok(Self {})
+    }
+}
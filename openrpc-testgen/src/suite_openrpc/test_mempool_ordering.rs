@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, MaybePendingBlockWithTxHashes};
+use tracing::info;
+
+use crate::{
+    openrpc_test,
+    utils::{
+        mempool_ordering::{record_ordering, OrderedTransaction, OrderingObservation},
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::{Provider, ProviderError},
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+/// Largest number of accounts submitting a transaction.
+const MAX_SENDERS: usize = 4;
+/// Difference between the tips of consecutive submissions.
+const TIP_STEP: u64 = 1_000_000;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "mempool_ordering")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        // Transactions of a single account are ordered by nonce, so every one has its own sender.
+        let mut senders = test_input.random_paymaster_account.accounts().to_vec();
+        senders.push(test_input.random_executable_account.random_accounts()?);
+        let mut addresses = HashSet::new();
+        senders.retain(|sender| addresses.insert(sender.address()));
+        senders.truncate(MAX_SENDERS);
+        if senders.len() < 2 {
+            info!("mempool_ordering needs at least two accounts, it does not run.");
+            return Ok(Self {});
+        }
+
+        // All transactions are prepared before the first is submitted, to fit in one block window.
+        let mut prepared = Vec::with_capacity(senders.len());
+        for (index, sender) in senders.iter().enumerate() {
+            let transfer_to_self = Call {
+                to: network_registry().strk_address,
+                selector: get_selector_from_name("transfer")?,
+                calldata: vec![sender.address(), Felt::ZERO, Felt::ZERO],
+            };
+            prepared.push(sender.execute_v3(vec![transfer_to_self]).tip(index as u64 * TIP_STEP).prepare().await?);
+        }
+
+        // Lowest tip first, so that inclusion by priority and by arrival tell apart.
+        let mut transaction_hashes = Vec::with_capacity(prepared.len());
+        for execution in &prepared {
+            transaction_hashes.push(execution.send().await?.transaction_hash);
+        }
+
+        let mut transactions = Vec::with_capacity(prepared.len());
+        for (submission_index, (transaction_hash, sender)) in transaction_hashes.into_iter().zip(&senders).enumerate() {
+            wait_for_sent_transaction(transaction_hash, sender).await?;
+            let provider = sender.provider();
+            let block_number =
+                provider.get_transaction_receipt_block_info(transaction_hash).await?.block_number.ok_or_else(|| {
+                    OpenRpcTestGenError::Other(format!("Accepted transaction {:#x} is in no block", transaction_hash))
+                })?;
+            let block_transactions = match provider.get_block_with_tx_hashes(BlockId::Number(block_number)).await? {
+                MaybePendingBlockWithTxHashes::Block(block) => block.transactions,
+                MaybePendingBlockWithTxHashes::Pending(_) => return Err(ProviderError::UnexpectedPendingBlock.into()),
+            };
+            let block_index =
+                block_transactions.iter().position(|hash| *hash == transaction_hash).ok_or_else(|| {
+                    OpenRpcTestGenError::Other(format!(
+                        "Transaction {:#x} is missing from its block {}",
+                        transaction_hash, block_number
+                    ))
+                })?;
+
+            let raw_execution = prepared[submission_index].get_raw_execution().await;
+            transactions.push(OrderedTransaction {
+                transaction_hash,
+                sender_address: sender.address(),
+                tip: raw_execution.tip(),
+                gas_price: raw_execution.gas_price(),
+                submission_index,
+                block_number,
+                block_index,
+            });
+        }
+
+        let observation = OrderingObservation::new(transactions);
+        info!(
+            "{} transactions included{}, by priority: {}, by submission: {}",
+            observation.transactions.len(),
+            if observation.same_block { " in one block" } else { " across blocks" },
+            observation.by_priority,
+            observation.by_submission
+        );
+        record_ordering(observation);
+
+        Ok(Self {})
+    }
+}
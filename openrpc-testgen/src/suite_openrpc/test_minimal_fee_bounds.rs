@@ -0,0 +1,92 @@
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{ExecuteInvocation, SimulateTransactionsResult, TransactionTrace};
+use tracing::info;
+
+use crate::{
+    assert_result, openrpc_test,
+    utils::{
+        fee_search::{is_fee_search_enabled, minimal_bound, record_fee_search, FeeSearchResult},
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, AccountError, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+            providers::provider::ProviderError,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+/// How many times the estimated gas the search goes up to.
+const MAX_ESTIMATE_MULTIPLE: u64 = 4;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "minimal_fee_bounds")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        if !is_fee_search_enabled() {
+            info!("Fee search is not enabled, minimal_fee_bounds does not run.");
+            return Ok(Self {});
+        }
+
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let transfer_to_self = Call {
+            to: network_registry().strk_address,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![account.address(), Felt::ONE, Felt::ZERO],
+        };
+        let transactions = [
+            ("transfer", vec![transfer_to_self.clone()]),
+            ("transfer_multicall", vec![transfer_to_self.clone(), transfer_to_self.clone(), transfer_to_self]),
+        ];
+
+        for (name, calls) in transactions {
+            // The gas of the estimate as is, the price with the usual margin to stay above the block's.
+            let prepared = account.execute_v3(calls.clone()).gas_estimate_multiplier(1.0).prepare().await?;
+            let estimated = prepared.get_raw_execution().await;
+            let (nonce, estimated_gas, gas_price) = (estimated.nonce(), estimated.gas(), estimated.gas_price());
+
+            let minimal_gas = minimal_bound(1, estimated_gas.max(1) * MAX_ESTIMATE_MULTIPLE, |gas| {
+                let execution = account.execute_v3(calls.clone()).nonce(nonce).gas(gas).gas_price(gas_price);
+                async move {
+                    match execution.simulate(false, false).await {
+                        Ok(SimulateTransactionsResult {
+                            transaction_trace: Some(TransactionTrace::Invoke(trace)),
+                            ..
+                        }) => Ok(matches!(trace.execute_invocation, ExecuteInvocation::FunctionInvocation(_))),
+                        Ok(other) => Err(OpenRpcTestGenError::Other(format!(
+                            "Simulation of {name} is missing the invoke trace: {other:?}"
+                        ))),
+                        // Bounds below the validation or the fee the account can pay are refused outright.
+                        Err(AccountError::Provider(ProviderError::StarknetError(e))) => {
+                            info!("Simulation of {} with gas {} refused: {:?}", name, gas, e);
+                            Ok(false)
+                        }
+                        Err(e) => Err(e.into()),
+                    }
+                }
+            })
+            .await?;
+
+            let result = FeeSearchResult::new(name.to_string(), gas_price, estimated_gas, minimal_gas);
+            info!(
+                "{}: estimated gas {}, minimal gas bound {:?}, estimate ratio {:?}",
+                name, result.estimated_gas, result.minimal_gas, result.estimate_ratio
+            );
+            record_fee_search(result);
+
+            assert_result!(
+                minimal_gas.is_some(),
+                format!("No gas bound up to {MAX_ESTIMATE_MULTIPLE}x the estimate of {name} was accepted")
+            );
+        }
+
+        Ok(Self {})
+    }
+}
@@ -0,0 +1,71 @@
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag};
+use tracing::info;
+
+use crate::{
+    assert_eq_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        resilience::restart_node,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+/// A transaction submitted right before the node restarts is either accepted afterwards, or
+/// dropped without a trace: the nonce must then be unchanged and reusable by a new transaction.
+/// Runs only with a restart hook configured in resilience mode.
+#[openrpc_test(suite = "openrpc", name = "pending_transaction_survives_restart")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let transfer = Call {
+            to: network_registry().strk_address,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![Felt::from_hex_unchecked("0xdeadbeef"), Felt::ONE, Felt::ZERO],
+        };
+
+        let nonce_before = account.provider().get_nonce(BlockId::Tag(BlockTag::Latest), account.address()).await?;
+        let pending = account.execute_v3(vec![transfer.clone()]).send().await?;
+
+        if !restart_node().await? {
+            info!("No restart hook configured, pending_transaction_survives_restart has nothing to check.");
+            wait_for_sent_transaction(pending.transaction_hash, &account).await?;
+            return Ok(Self {});
+        }
+
+        let survived = wait_for_sent_transaction(pending.transaction_hash, &account).await.is_ok();
+        let nonce_after = account.provider().get_nonce(BlockId::Tag(BlockTag::Latest), account.address()).await?;
+
+        if survived {
+            assert_eq_result!(
+                nonce_after,
+                nonce_before + Felt::ONE,
+                "Nonce did not advance although the pending transaction was accepted after the restart"
+            );
+        } else {
+            info!("Pending transaction {:#x} was dropped by the restart", pending.transaction_hash);
+            assert_eq_result!(nonce_after, nonce_before, "Nonce advanced although the pending transaction was dropped");
+
+            let retry = account.execute_v3(vec![transfer]).nonce(nonce_before).send().await?;
+            wait_for_sent_transaction(retry.transaction_hash, &account).await?;
+        }
+
+        Ok(Self {})
+    }
+}
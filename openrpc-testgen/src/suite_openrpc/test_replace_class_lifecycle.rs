@@ -0,0 +1,97 @@
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag, FunctionCall, MaybePendingStateUpdate};
+
+use crate::{
+    assert_eq_result, assert_result, openrpc_test,
+    utils::v7::{
+        accounts::{
+            account::{Account, ConnectedAccount},
+            call::Call,
+        },
+        contract::factory::ContractFactory,
+        endpoints::{
+            errors::OpenRpcTestGenError,
+            utils::{declare_if_missing, get_selector_from_name, wait_for_sent_transaction},
+        },
+        providers::provider::{Provider, ProviderError},
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const UPGRADEABLE_V1: &str = "target/dev/contracts_contracts_upgradeable_UpgradeableV1";
+const UPGRADEABLE_V2: &str = "target/dev/contracts_contracts_upgradeable_UpgradeableV2";
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "replace_class_lifecycle")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let v1_class_hash = declare_if_missing(&account, UPGRADEABLE_V1).await?;
+        let v2_class_hash = declare_if_missing(&account, UPGRADEABLE_V2).await?;
+        assert_result!(v1_class_hash != v2_class_hash, "Both versions of the contract have the same class hash");
+
+        let mut salt_buffer = [0u8; 32];
+        StdRng::from_entropy().fill_bytes(&mut salt_buffer[1..]);
+        let factory = ContractFactory::new(v1_class_hash, account.clone());
+        let deployment = factory.deploy_v3(vec![], Felt::from_bytes_be(&salt_buffer), true);
+        let contract_address = deployment.deployed_address();
+        let deployment_result = deployment.send().await?;
+        wait_for_sent_transaction(deployment_result.transaction_hash, &account).await?;
+
+        let provider = account.provider();
+        assert_eq_result!(version(&account, contract_address).await?, Felt::ONE);
+
+        let upgrade = account
+            .execute_v3(vec![Call {
+                to: contract_address,
+                selector: get_selector_from_name("upgrade")?,
+                calldata: vec![v2_class_hash],
+            }])
+            .send()
+            .await?;
+        wait_for_sent_transaction(upgrade.transaction_hash, &account).await?;
+
+        let class_hash_at = provider.get_class_hash_at(BlockId::Tag(BlockTag::Latest), contract_address).await?;
+        assert_eq_result!(class_hash_at, v2_class_hash, "Class hash of the contract after replace_class");
+        assert_eq_result!(version(&account, contract_address).await?, Felt::TWO);
+
+        let state_update = match provider.get_state_update(BlockId::Tag(BlockTag::Latest)).await? {
+            MaybePendingStateUpdate::Block(block) => block,
+            MaybePendingStateUpdate::Pending(_) => return Err(ProviderError::UnexpectedPendingBlock.into()),
+        };
+        let replaced_classes = &state_update.state_diff.replaced_classes;
+        assert_result!(
+            replaced_classes
+                .iter()
+                .any(|replaced| replaced.contract_address == contract_address && replaced.class_hash == v2_class_hash),
+            format!(
+                "State update has no replaced class {:#x} for contract {:#x}: {:?}",
+                v2_class_hash, contract_address, replaced_classes
+            )
+        );
+
+        Ok(Self {})
+    }
+}
+
+async fn version<A: ConnectedAccount>(account: &A, contract_address: Felt) -> Result<Felt, OpenRpcTestGenError> {
+    account
+        .provider()
+        .call(
+            FunctionCall {
+                calldata: vec![],
+                contract_address,
+                entry_point_selector: get_selector_from_name("version")?,
+            },
+            BlockId::Tag(BlockTag::Pending),
+        )
+        .await?
+        .first()
+        .copied()
+        .ok_or_else(|| OpenRpcTestGenError::Other("version returned no value".to_string()))
+}
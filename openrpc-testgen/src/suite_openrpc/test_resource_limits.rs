@@ -0,0 +1,100 @@
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use starknet_types_core::felt::Felt;
+use tracing::info;
+
+use crate::{
+    assert_result, openrpc_test,
+    utils::{
+        resource_limits::{ramp, record_resource_limit, resource_limits_config, GeneratedResource, ResourceLimit},
+        v7::{
+            accounts::{account::Account, call::Call, single_owner::SingleOwnerAccount},
+            contract::factory::ContractFactory,
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{declare_if_missing, get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::jsonrpc::{HttpTransport, JsonRpcClient},
+            signers::local_wallet::LocalWallet,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const GENERATOR: &str = "target/dev/contracts_contracts_resource_generator_ResourceGenerator";
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "resource_limits")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let Some(config) = resource_limits_config() else {
+            info!("Resource limits probing is not enabled, resource_limits does not run.");
+            return Ok(Self {});
+        };
+
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let generator_address = deploy_generator(&account).await?;
+
+        for resource in GeneratedResource::ALL {
+            let mut limit = ResourceLimit::default();
+            for count in ramp(config.start, config.max) {
+                match generate(&account, generator_address, resource, count).await {
+                    Ok(()) => limit.largest_accepted = Some(count),
+                    Err(e) => {
+                        info!("Invoke generating {} {:?} failed: {}", count, resource, e);
+                        limit.smallest_rejected = Some(count);
+                        limit.rejection = Some(e.to_string());
+                        break;
+                    }
+                }
+            }
+
+            // A rejection at the start of the ramp is a broken setup rather than a limit.
+            assert_result!(
+                limit.largest_accepted.is_some(),
+                format!("Generating {} {:?} already failed: {:?}", config.start, resource, limit.rejection)
+            );
+            record_resource_limit(resource, limit);
+        }
+
+        Ok(Self {})
+    }
+}
+
+async fn deploy_generator(
+    account: &SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
+) -> Result<Felt, OpenRpcTestGenError> {
+    // The class is shared by all runs against the same node, so it is only declared once.
+    let class_hash = declare_if_missing(account, GENERATOR).await?;
+
+    let mut salt_buffer = [0u8; 32];
+    StdRng::from_entropy().fill_bytes(&mut salt_buffer[1..]);
+
+    let factory = ContractFactory::new(class_hash, account.clone());
+    let deployment = factory.deploy_v3(vec![], Felt::from_bytes_be(&salt_buffer), true);
+    let generator_address = deployment.deployed_address();
+    let deployment_result = deployment.send().await?;
+    wait_for_sent_transaction(deployment_result.transaction_hash, account).await?;
+
+    Ok(generator_address)
+}
+
+/// Sends an invoke generating `count` of `resource`, and waits for it to be executed.
+async fn generate(
+    account: &SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
+    generator_address: Felt,
+    resource: GeneratedResource,
+    count: u32,
+) -> Result<(), OpenRpcTestGenError> {
+    let call = Call {
+        to: generator_address,
+        selector: get_selector_from_name(resource.entrypoint())?,
+        calldata: vec![Felt::from(count)],
+    };
+    let result = account.execute_v3(vec![call]).send().await?;
+    wait_for_sent_transaction(result.transaction_hash, account).await?;
+    Ok(())
+}
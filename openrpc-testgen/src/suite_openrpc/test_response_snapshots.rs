@@ -0,0 +1,43 @@
+use serde_json::json;
+use starknet_types_rpc::{BlockId, BlockTag};
+
+use crate::{
+    openrpc_test,
+    utils::{
+        snapshot::{assert_shape_snapshot, assert_snapshot},
+        v7::{endpoints::errors::OpenRpcTestGenError, providers::provider::Provider},
+    },
+    RunnableTrait,
+};
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "response_snapshots")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let provider = test_input.random_paymaster_account.provider();
+
+        // The genesis block does not change between runs against the same network, unlike the
+        // latest one whose transactions depend on the tests that ran before.
+        let genesis = BlockId::Number(0);
+        assert_shape_snapshot(
+            "get_block_with_tx_hashes_genesis",
+            &provider.get_block_with_tx_hashes(genesis.clone()).await?,
+        )?;
+        assert_shape_snapshot("get_block_with_txs_genesis", &provider.get_block_with_txs(genesis.clone()).await?)?;
+        assert_shape_snapshot("get_state_update_genesis", &provider.get_state_update(genesis).await?)?;
+
+        // The typed responses drop the fields they don't know, the raw one keeps them.
+        let raw_genesis =
+            provider.send_raw("starknet_getBlockWithTxHashes", json!({ "block_id": { "block_number": 0 } })).await?;
+        assert_shape_snapshot("get_block_with_tx_hashes_genesis_raw", &raw_genesis)?;
+
+        let account_class = provider.get_class(BlockId::Tag(BlockTag::Latest), test_input.account_class_hash).await?;
+        assert_snapshot("get_class_account", &account_class)?;
+
+        Ok(Self {})
+    }
+}
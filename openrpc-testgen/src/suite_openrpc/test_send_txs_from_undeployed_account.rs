@@ -0,0 +1,136 @@
+use std::{path::PathBuf, str::FromStr};
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag};
+
+use crate::{
+    assert_matches_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, AccountError, ConnectedAccount},
+                call::Call,
+                creation::create::{create_account, AccountType},
+                single_owner::{ExecutionEncoding, SingleOwnerAccount},
+            },
+            contract::factory::ContractFactory,
+            endpoints::{
+                declare_contract::get_compiled_contract,
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::{
+                jsonrpc::StarknetError,
+                provider::{Provider, ProviderError},
+            },
+            signers::local_wallet::LocalWallet,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const INVOKE_TXN_GAS: u64 = 100000;
+const DECLARE_TXN_GAS: u64 = 48000;
+const TXN_GAS_PRICE: u128 = 17;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "send_txs_from_undeployed_account")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let funder = test_input.random_paymaster_account.random_accounts()?;
+        let provider = funder.provider().clone();
+
+        // The counterfactual address of an account that is never deployed.
+        let account_data =
+            create_account(&provider, AccountType::Oz, None, Some(test_input.account_class_hash)).await?;
+        let undeployed = SingleOwnerAccount::new(
+            provider.clone(),
+            LocalWallet::from(account_data.signing_key),
+            account_data.address,
+            funder.chain_id(),
+            ExecutionEncoding::New,
+        );
+
+        let nonce = provider.get_nonce(BlockId::Tag(BlockTag::Latest), account_data.address).await;
+        assert_matches_result!(nonce.unwrap_err(), ProviderError::StarknetError(StarknetError::ContractNotFound));
+
+        // Funding the address makes sure the transactions get past the balance check, so that they
+        // are rejected because there is no account contract to validate them.
+        let funding = funder
+            .execute_v3(vec![Call {
+                to: network_registry().strk_address,
+                selector: get_selector_from_name("transfer")?,
+                calldata: vec![account_data.address, Felt::from_hex("0xfffffffffffffff")?, Felt::ZERO],
+            }])
+            .send()
+            .await?;
+        wait_for_sent_transaction(funding.transaction_hash, &funder).await?;
+
+        // Nonce and resource bounds are set manually, as neither can be fetched or estimated for an
+        // address with no contract.
+        let invoke_result = undeployed
+            .execute_v3(vec![Call {
+                to: network_registry().strk_address,
+                selector: get_selector_from_name("transfer")?,
+                calldata: vec![funder.address(), Felt::ONE, Felt::ZERO],
+            }])
+            .nonce(Felt::ZERO)
+            .gas(INVOKE_TXN_GAS)
+            .gas_price(TXN_GAS_PRICE)
+            .send()
+            .await;
+        assert_matches_result!(
+            invoke_result.unwrap_err(),
+            AccountError::Provider(ProviderError::StarknetError(
+                StarknetError::ContractNotFound | StarknetError::ValidationFailure(_)
+            ))
+        );
+
+        let factory = ContractFactory::new(test_input.account_class_hash, undeployed.clone());
+        let deploy_result = factory
+            .deploy_v3(vec![Felt::ONE], Felt::ZERO, true)
+            .nonce(Felt::ZERO)
+            .gas(INVOKE_TXN_GAS)
+            .gas_price(TXN_GAS_PRICE)
+            .send()
+            .await;
+        assert_matches_result!(
+            deploy_result.unwrap_err(),
+            AccountError::Provider(ProviderError::StarknetError(
+                StarknetError::ContractNotFound | StarknetError::ValidationFailure(_)
+            ))
+        );
+
+        let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(
+            PathBuf::from_str("target/dev/contracts_contracts_sample_contract_4_HelloStarknet.contract_class.json")?,
+            PathBuf::from_str(
+                "target/dev/contracts_contracts_sample_contract_4_HelloStarknet.compiled_contract_class.json",
+            )?,
+        )
+        .await?;
+        let declare_result = undeployed
+            .declare_v3(flattened_sierra_class, compiled_class_hash)
+            .nonce(Felt::ZERO)
+            .gas(DECLARE_TXN_GAS)
+            .gas_price(TXN_GAS_PRICE)
+            .send()
+            .await;
+        assert_matches_result!(
+            declare_result.unwrap_err(),
+            AccountError::Provider(ProviderError::StarknetError(
+                StarknetError::ContractNotFound | StarknetError::ValidationFailure(_)
+            ))
+        );
+
+        // None of the rejected transactions may have deployed anything at the address.
+        let class_hash = provider.get_class_hash_at(BlockId::Tag(BlockTag::Pending), account_data.address).await;
+        assert_matches_result!(class_hash.unwrap_err(), ProviderError::StarknetError(StarknetError::ContractNotFound));
+
+        Ok(Self {})
+    }
+}
@@ -16,9 +16,6 @@ use starknet_types_rpc::{
 };
 use t9n::txn_hashes::declare_hash::class_hash;
 
-pub const STRK_ERC20_CONTRACT_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
-
 #[derive(Clone, Debug)]
 pub struct TestCase {}
 
@@ -1,26 +1,27 @@
 use crate::{
     assert_eq_result, assert_result,
-    utils::v7::{
-        accounts::{
-            account::{Account, ConnectedAccount},
-            call::Call,
-            creation::create::{create_account, AccountType},
-            deployment::{
-                deploy::{estimate_fee_deploy_account, simulate_deploy_account, DeployAccountVersion},
-                structs::{ValidatedWaitParams, WaitForTx},
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+                creation::create::{create_account, AccountType},
+                deployment::{
+                    deploy::{estimate_fee_deploy_account, simulate_deploy_account, DeployAccountVersion},
+                    structs::{ValidatedWaitParams, WaitForTx},
+                },
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, get_storage_var_address, wait_for_sent_transaction},
             },
-        },
-        endpoints::{
-            errors::OpenRpcTestGenError,
-            utils::{get_selector_from_name, get_storage_var_address, wait_for_sent_transaction},
         },
     },
     RandomizableAccountsTrait, RunnableTrait,
 };
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{EntryPointType, SimulateTransactionsResult, TransactionTrace};
-pub const STRK_ERC20_CONTRACT_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
 
 #[derive(Clone, Debug)]
 pub struct TestCase {}
@@ -42,7 +43,7 @@ impl RunnableTrait for TestCase {
         let transfer_execution = test_input
             .random_paymaster_account
             .execute_v3(vec![Call {
-                to: Felt::from_hex("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D")?,
+                to: network_registry().strk_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account_data.address, transfer_amount, Felt::ZERO],
             }])
@@ -0,0 +1,84 @@
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag, MaybePendingBlockWithTxHashes};
+
+use crate::{
+    assert_result, openrpc_test,
+    utils::{
+        starknet_version::{StarknetVersion, StarknetVersionError},
+        v7::{
+            endpoints::errors::OpenRpcTestGenError,
+            providers::provider::{Provider, ProviderError},
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+/// Number of the most recent blocks whose versions are checked.
+const BLOCK_RANGE: u64 = 20;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "starknet_version_per_block")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let provider = test_input.random_paymaster_account.provider();
+        let latest = provider.block_number().await?;
+
+        let mut previous: Option<(u64, StarknetVersion)> = None;
+        for block_number in latest.saturating_sub(BLOCK_RANGE - 1)..=latest {
+            let header = match provider.get_block_with_tx_hashes(BlockId::Number(block_number)).await? {
+                MaybePendingBlockWithTxHashes::Block(block) => block.block_header,
+                MaybePendingBlockWithTxHashes::Pending(_) => return Err(ProviderError::UnexpectedPendingBlock.into()),
+            };
+            if header.starknet_version.is_empty() {
+                return Err(StarknetVersionError::Missing { block_number }.into());
+            }
+            let version = StarknetVersion::parse(&header.starknet_version)?;
+
+            if let Some((previous_number, previous_version)) = previous {
+                assert_result!(
+                    version >= previous_version,
+                    format!(
+                        "Block {} is on starknet version {}, older than the {} of block {}",
+                        block_number, version, previous_version, previous_number
+                    )
+                );
+            }
+
+            // Blob data is only priced from 0.13.1 on, earlier blocks may report it as zero.
+            if version.has_l1_data_gas() {
+                let data_gas_price = header.l1_data_gas_price;
+                assert_result!(
+                    data_gas_price.price_in_wei != Felt::ZERO && data_gas_price.price_in_fri != Felt::ZERO,
+                    format!(
+                        "Block {} on starknet version {} has no L1 data gas price: {:?}",
+                        block_number, version, data_gas_price
+                    )
+                );
+            }
+
+            previous = Some((block_number, version));
+        }
+
+        // Pending blocks are built on the protocol version of the latest block, or a newer one.
+        if let MaybePendingBlockWithTxHashes::Pending(pending) =
+            provider.get_block_with_tx_hashes(BlockId::Tag(BlockTag::Pending)).await?
+        {
+            let version = StarknetVersion::parse(&pending.pending_block_header.starknet_version)?;
+            if let Some((latest_number, latest_version)) = previous {
+                assert_result!(
+                    version >= latest_version,
+                    format!(
+                        "Pending block is on starknet version {}, older than the {} of block {}",
+                        version, latest_version, latest_number
+                    )
+                );
+            }
+        }
+
+        Ok(Self {})
+    }
+}
@@ -7,7 +7,10 @@ use crate::utils::v7::endpoints::utils::wait_for_sent_transaction;
 use crate::utils::v7::providers::provider::Provider;
 use crate::{assert_matches_result, assert_result, RandomizableAccountsTrait};
 use crate::{
-    utils::v7::endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+    utils::{
+        network_registry::network_registry,
+        v7::endpoints::{errors::OpenRpcTestGenError, utils::get_selector_from_name},
+    },
     RunnableTrait,
 };
 use starknet_types_core::felt::Felt;
@@ -15,8 +18,6 @@ use starknet_types_rpc::{
     BlockId, BlockTag, DeclareTransactionTrace, EntryPointType, TraceBlockTransactionsResult, TransactionTrace,
 };
 use t9n::txn_validation::declare::verify_declare_v3_signature;
-pub const STRK_ERC20_CONTRACT_ADDRESS: Felt =
-    Felt::from_hex_unchecked("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
 
 #[derive(Clone, Debug)]
 pub struct TestCase {}
@@ -131,12 +132,13 @@ impl RunnableTrait for TestCase {
             format!("Transaction hash mismatch: expected {:?}, but found {:?}", declare_hash, transaction_hash)
         );
 
-        // fee_transfer_invocation STRK_ERC20_CONTRACT_ADDRESS
+        // fee_transfer_invocation STRK token address
         assert_result!(
-            fee_transfer_invocation.function_call.contract_address == STRK_ERC20_CONTRACT_ADDRESS,
+            fee_transfer_invocation.function_call.contract_address == network_registry().strk_address,
             format!(
                 "Contract address mismatch in fee transfer: expected {:?}, but found {:?}",
-                STRK_ERC20_CONTRACT_ADDRESS, fee_transfer_invocation.function_call.contract_address
+                network_registry().strk_address,
+                fee_transfer_invocation.function_call.contract_address
             )
         );
 
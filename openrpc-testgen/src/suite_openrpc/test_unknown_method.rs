@@ -0,0 +1,42 @@
+use serde_json::json;
+
+use crate::{
+    assert_matches_result, openrpc_test,
+    utils::v7::{
+        accounts::account::ConnectedAccount,
+        endpoints::errors::OpenRpcTestGenError,
+        providers::{
+            jsonrpc::{transports::http::HttpTransportError, JsonRpcClientError, JsonRpcError},
+            provider::{Provider, ProviderError},
+        },
+    },
+    RunnableTrait,
+};
+
+/// JSON-RPC 2.0 code of a method the server does not implement.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "unknown_method")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let result = test_input.random_paymaster_account.provider().send_raw("starknet_notAMethod", json!([])).await;
+
+        let error = match result {
+            Err(ProviderError::Other(error)) => error,
+            result => {
+                return Err(OpenRpcTestGenError::Other(format!("Expected a JSON-RPC error, got {:?}", result)));
+            }
+        };
+        assert_matches_result!(
+            error.as_any().downcast_ref::<JsonRpcClientError<HttpTransportError>>(),
+            Some(JsonRpcClientError::JsonRpc(JsonRpcError { code: METHOD_NOT_FOUND, .. }))
+        );
+
+        Ok(Self {})
+    }
+}
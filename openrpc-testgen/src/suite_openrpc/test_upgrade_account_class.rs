@@ -0,0 +1,113 @@
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag, FunctionCall};
+
+use crate::{
+    assert_eq_result, assert_result, openrpc_test,
+    utils::{
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+                creation::create::{create_account, AccountType},
+                deployment::{
+                    deploy::{deploy_account, DeployAccountVersion},
+                    structs::{ValidatedWaitParams, WaitForTx},
+                },
+                single_owner::{ExecutionEncoding, SingleOwnerAccount},
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{declare_if_missing, get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
+            signers::local_wallet::LocalWallet,
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+const ACCOUNT_V1: &str = "target/dev/contracts_OZAccount";
+const ACCOUNT_V2: &str = "target/dev/contracts_UpgradeableAccountV2";
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "upgrade_account_class")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let paymaster = test_input.random_paymaster_account.random_accounts()?;
+        let v1_class_hash = declare_if_missing(&paymaster, ACCOUNT_V1).await?;
+        let v2_class_hash = declare_if_missing(&paymaster, ACCOUNT_V2).await?;
+        assert_result!(v1_class_hash != v2_class_hash, "Both account classes have the same class hash");
+
+        // A fresh account, as upgrading one of the shared accounts would change it for every case.
+        let provider = paymaster.provider();
+        let account_data = create_account(provider, AccountType::Oz, None, Some(v1_class_hash)).await?;
+        let funding = paymaster
+            .execute_v3(vec![Call {
+                to: network_registry().strk_address,
+                selector: get_selector_from_name("transfer")?,
+                calldata: vec![account_data.address, Felt::from_hex("0xfffffffffffffff")?, Felt::ZERO],
+            }])
+            .send()
+            .await?;
+        wait_for_sent_transaction(funding.transaction_hash, &paymaster).await?;
+
+        let wait_config = WaitForTx { wait: true, wait_params: ValidatedWaitParams::default() };
+        let deploy_hash =
+            deploy_account(provider, paymaster.chain_id(), wait_config, account_data, DeployAccountVersion::V3).await?;
+        wait_for_sent_transaction(deploy_hash, &paymaster).await?;
+
+        let mut account = SingleOwnerAccount::new(
+            provider.clone(),
+            LocalWallet::from(account_data.signing_key),
+            account_data.address,
+            paymaster.chain_id(),
+            ExecutionEncoding::New,
+        );
+        account.set_block_id(BlockId::Tag(BlockTag::Pending));
+
+        let upgrade = account
+            .execute_v3(vec![Call {
+                to: account.address(),
+                selector: get_selector_from_name("upgrade")?,
+                calldata: vec![v2_class_hash],
+            }])
+            .send()
+            .await?;
+        wait_for_sent_transaction(upgrade.transaction_hash, &account).await?;
+
+        let class_hash_at = provider.get_class_hash_at(BlockId::Tag(BlockTag::Latest), account.address()).await?;
+        assert_eq_result!(class_hash_at, v2_class_hash, "Class hash of the account after the upgrade");
+        let account_version = provider
+            .call(
+                FunctionCall {
+                    calldata: vec![],
+                    contract_address: account.address(),
+                    entry_point_selector: get_selector_from_name("account_version")?,
+                },
+                BlockId::Tag(BlockTag::Pending),
+            )
+            .await?;
+        assert_eq_result!(account_version, vec![Felt::TWO], "Version reported by the upgraded account");
+
+        // The upgraded class validates and executes the transactions of the account from now on.
+        let transfer = account
+            .execute_v3(vec![Call {
+                to: network_registry().strk_address,
+                selector: get_selector_from_name("transfer")?,
+                calldata: vec![paymaster.address(), Felt::ONE, Felt::ZERO],
+            }])
+            .send()
+            .await?;
+        wait_for_sent_transaction(transfer.transaction_hash, &account).await?;
+
+        // Deployment, upgrade and transfer.
+        assert_eq_result!(account.get_nonce().await?, Felt::THREE, "Nonce of the upgraded account");
+
+        Ok(Self {})
+    }
+}
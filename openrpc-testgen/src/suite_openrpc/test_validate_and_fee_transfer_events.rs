@@ -0,0 +1,146 @@
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, FunctionInvocation, MaybePendingBlockWithTxHashes, TransactionTrace, TxnReceipt};
+
+use crate::{
+    assert_eq_result, assert_result, openrpc_test,
+    utils::{
+        display::display_felts,
+        network_registry::network_registry,
+        spec_version::RpcSpecVersion,
+        v7::{
+            accounts::{
+                account::{Account, ConnectedAccount},
+                call::Call,
+            },
+            endpoints::{
+                errors::OpenRpcTestGenError,
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::{Provider, ProviderError},
+        },
+    },
+    RandomizableAccountsTrait, RunnableTrait,
+};
+
+/// Event as it shows in a receipt: emitting contract, keys and data.
+type ReceiptEvent = (Felt, Vec<Felt>, Vec<Felt>);
+
+#[derive(Clone, Debug)]
+pub struct TestCase {}
+
+#[openrpc_test(suite = "openrpc", name = "validate_and_fee_transfer_events")]
+impl RunnableTrait for TestCase {
+    type Input = super::TestSuiteOpenRpc;
+
+    async fn run(test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {
+        let account = test_input.random_paymaster_account.random_accounts()?;
+        let provider = account.provider();
+        let spec_version = RpcSpecVersion::from_reported(&provider.spec_version().await?)?;
+
+        let transfer_to_self = Call {
+            to: network_registry().strk_address,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![account.address(), Felt::ZERO, Felt::ZERO],
+        };
+        let transaction_hash = account.execute_v3(vec![transfer_to_self]).send().await?.transaction_hash;
+        wait_for_sent_transaction(transaction_hash, &account).await?;
+
+        let receipt = match provider.get_transaction_receipt(transaction_hash).await? {
+            TxnReceipt::Invoke(receipt) => receipt.common_receipt_properties,
+            other => return Err(OpenRpcTestGenError::Other(format!("Expected invoke receipt, got {:?}", other))),
+        };
+        let receipt_events: Vec<ReceiptEvent> =
+            receipt.events.iter().map(|event| (event.from_address, event.keys.clone(), event.data.clone())).collect();
+
+        let trace = match provider.trace_transaction(transaction_hash).await? {
+            TransactionTrace::Invoke(trace) => trace,
+            other => return Err(OpenRpcTestGenError::Other(format!("Expected invoke trace, got {:?}", other))),
+        };
+        let validate_events = match &trace.validate_invocation {
+            Some(invocation) => invocation_events(invocation)?,
+            None => vec![],
+        };
+        let fee_transfer_events = match &trace.fee_transfer_invocation {
+            Some(invocation) => invocation_events(invocation)?,
+            None => return Err(OpenRpcTestGenError::Other("Trace has no fee_transfer_invocation".to_string())),
+        };
+
+        // Events emitted by `__validate__` come first in the receipt, those of the fee transfer last.
+        if receipts_include_validate_events(spec_version) {
+            let validate_prefix = receipt_events.iter().take(validate_events.len()).cloned().collect::<Vec<_>>();
+            assert_eq_result!(validate_prefix, validate_events, "Receipt events emitted during validation");
+        } else {
+            let included = validate_events.iter().filter(|event| receipt_events.contains(event)).count();
+            assert_eq_result!(included, 0, "Receipt includes events emitted during validation");
+        }
+        let fee_transfer_suffix =
+            receipt_events.iter().skip(receipt_events.len().saturating_sub(fee_transfer_events.len())).cloned();
+        let fee_transfer_suffix = fee_transfer_suffix.collect::<Vec<_>>();
+        assert_eq_result!(fee_transfer_suffix, fee_transfer_events, "Receipt events emitted during the fee transfer");
+
+        // The fee goes from the sender to the sequencer of the block the transaction is in.
+        let block_hash = provider
+            .get_transaction_receipt_block_info(transaction_hash)
+            .await?
+            .block_hash
+            .ok_or_else(|| OpenRpcTestGenError::Other("Accepted transaction is in no block".to_string()))?;
+        let sequencer_address = match provider.get_block_with_tx_hashes(BlockId::Hash(block_hash)).await? {
+            MaybePendingBlockWithTxHashes::Block(block) => block.block_header.sequencer_address,
+            MaybePendingBlockWithTxHashes::Pending(_) => return Err(ProviderError::UnexpectedPendingBlock.into()),
+        };
+
+        let (fee_token, keys, data) = receipt_events
+            .last()
+            .cloned()
+            .ok_or_else(|| OpenRpcTestGenError::Other("Receipt has no events".to_string()))?;
+        let transfer_selector = get_selector_from_name("Transfer")?;
+        let expected_data = vec![account.address(), sequencer_address, receipt.actual_fee.amount, Felt::ZERO];
+        assert_eq_result!(fee_token, network_registry().strk_address, "Token the V3 fee is paid in");
+        assert_eq_result!(keys, vec![transfer_selector], "Keys of the fee transfer event");
+        assert_result!(
+            data == expected_data,
+            format!(
+                "Fee transfer event data {} is not sender, sequencer and actual fee {}",
+                display_felts(&data),
+                display_felts(&expected_data)
+            )
+        );
+
+        Ok(Self {})
+    }
+}
+
+/// Receipts include the events emitted by `__validate__` in every spec version the suite covers.
+fn receipts_include_validate_events(spec_version: RpcSpecVersion) -> bool {
+    match spec_version {
+        RpcSpecVersion::V0_7 | RpcSpecVersion::V0_8 => true,
+    }
+}
+
+/// Events of the invocation and its nested calls, in the order they were emitted.
+fn invocation_events(invocation: &FunctionInvocation<Felt>) -> Result<Vec<ReceiptEvent>, OpenRpcTestGenError> {
+    let mut events = vec![];
+    collect_events(invocation, &mut events)?;
+    events.sort_by_key(|(order, _)| *order);
+    Ok(events.into_iter().map(|(_, event)| event).collect())
+}
+
+fn collect_events(
+    invocation: &FunctionInvocation<Felt>,
+    events: &mut Vec<(u64, ReceiptEvent)>,
+) -> Result<(), OpenRpcTestGenError> {
+    let emitter = invocation.function_call.contract_address;
+    let ordered = serde_json::to_value(&invocation.events).map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+    for event in ordered.as_array().into_iter().flatten() {
+        let field = |name: &str| event.get(name).cloned().unwrap_or_default();
+        let order: u64 =
+            serde_json::from_value(field("order")).map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+        let keys = serde_json::from_value(field("keys")).map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+        let data = serde_json::from_value(field("data")).map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?;
+        events.push((order, (emitter, keys, data)));
+    }
+    for call in &invocation.calls {
+        collect_events(call, events)?;
+    }
+    Ok(())
+}
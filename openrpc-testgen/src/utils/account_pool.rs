@@ -0,0 +1,532 @@
+//! Pool of the accounts paying for the test transactions. Accounts are handed out round-robin, and
+//! the pool keeps track of how each of them is doing: transactions and failures reported by their
+//! users, and the nonce and fee token balances of the last [AccountPool::refresh]. Accounts failing
+//! repeatedly, or running out of funds, are quarantined for a while instead of failing every case
+//! they are picked for. Cases changing an account in ways others can't share, e.g. racing its
+//! nonce, take it out of the rotation as an [ExclusiveAccount] until they are done.
+//!
+//! Transactions built on the pool itself, through its [Account] implementation, are sent from the
+//! account handed out last, so that their address, nonce, signature and provider all come from the
+//! same account. Cases running transactions concurrently take an account each with
+//! [RandomizableAccountsTrait::random_accounts] instead.
+//!
+//! With a [PoolTopUp], every account is wrapped in a [TopUpAccount]: handing an account out checks
+//! its balances in the background, at most every [TOP_UP_INTERVAL], and [AccountPool::refresh] tops
+//! accounts up before quarantining the ones still running low.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag, PriceUnit};
+use tracing::warn;
+
+use crate::RandomizableAccountsTrait;
+
+use super::get_balance::get_balance;
+use super::v7::{
+    accounts::{
+        account::{Account, ConnectedAccount, ExecutionEncoder},
+        call::Call,
+        single_owner::{SignError, SingleOwnerAccount},
+        top_up::{TopUpAccount, TopUpPolicy},
+        utils::faucet::AnyFaucet,
+    },
+    endpoints::errors::OpenRpcTestGenError,
+    providers::{
+        jsonrpc::{HttpTransport, JsonRpcClient},
+        provider::ProviderError,
+    },
+    signers::local_wallet::LocalWallet,
+};
+
+type HiveAccount = SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>;
+
+/// Minimum time between two balance checks of the same account when it is handed out.
+pub const TOP_UP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Faucet and thresholds the accounts of the pools are topped up with.
+#[derive(Debug, Clone)]
+pub struct PoolTopUp {
+    pub faucet: AnyFaucet,
+    pub policies: Vec<TopUpPolicy>,
+}
+
+static POOL_TOP_UP: OnceLock<PoolTopUp> = OnceLock::new();
+
+/// Tops up the accounts of the pools created from now on. Returns `false` if it was already set.
+pub fn set_pool_top_up(top_up: PoolTopUp) -> bool {
+    POOL_TOP_UP.set(top_up).is_ok()
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountPoolConfig {
+    /// Failures in a row after which an account is quarantined.
+    pub max_consecutive_failures: u32,
+    /// How long a quarantined account is left out of the rotation.
+    pub quarantine: Duration,
+    /// Accounts with a fee token balance below this at a refresh are quarantined.
+    pub min_balance: Felt,
+    /// Keeps the balances of the accounts topped up, [set_pool_top_up] by default.
+    pub top_up: Option<PoolTopUp>,
+}
+
+impl Default for AccountPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_consecutive_failures: 3,
+            quarantine: Duration::from_secs(60),
+            min_balance: Felt::ZERO,
+            top_up: POOL_TOP_UP.get().cloned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AccountHealth {
+    pub address: Felt,
+    pub transactions: u64,
+    pub failures: u64,
+    pub consecutive_failures: u32,
+    /// Nonce of the last refresh, advanced by the successes reported since.
+    pub nonce: Option<Felt>,
+    /// Balances by fee token at the last refresh.
+    pub balances: HashMap<Felt, Felt>,
+    pub quarantined_until: Option<Instant>,
+}
+
+impl AccountHealth {
+    pub fn is_quarantined(&self, now: Instant) -> bool {
+        self.quarantined_until.is_some_and(|until| until > now)
+    }
+}
+
+/// Account handed out by the pool, to report the outcome of its transactions with.
+#[derive(Debug, Clone)]
+pub struct AccountLease {
+    pub index: usize,
+    pub account: HiveAccount,
+}
+
+/// Addresses of the accounts taken out of the rotation by [ExclusiveAccount]s. Entries of an
+/// account for each node share its address, so they are reserved together. Clones share the
+/// reservations.
+#[derive(Debug, Clone, Default)]
+pub struct Reservations(Arc<Mutex<HashSet<Felt>>>);
+
+impl Reservations {
+    pub fn is_reserved(&self, address: Felt) -> bool {
+        self.reserved().contains(&address)
+    }
+
+    /// Reserves the first of `candidates` not reserved yet, out of `addresses`. Test cases run one
+    /// after the other, so the last free address can be reserved too: the cases sharing the
+    /// accounts only miss it while the case holding it runs.
+    pub fn reserve(&self, addresses: &[Felt], candidates: impl IntoIterator<Item = Felt>) -> Option<Felt> {
+        let mut reserved = self.reserved();
+        let address =
+            candidates.into_iter().find(|candidate| addresses.contains(candidate) && !reserved.contains(candidate))?;
+        reserved.insert(address);
+        Some(address)
+    }
+
+    fn release(&self, address: Felt) {
+        self.reserved().remove(&address);
+    }
+
+    fn reserved(&self) -> MutexGuard<'_, HashSet<Felt>> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Account reserved for a single test case, handed out to no other case until dropped.
+#[derive(Debug)]
+pub struct ExclusiveAccount {
+    account: HiveAccount,
+    reservations: Reservations,
+}
+
+impl ExclusiveAccount {
+    pub fn new(account: HiveAccount, reservations: Reservations) -> Self {
+        Self { account, reservations }
+    }
+}
+
+impl Deref for ExclusiveAccount {
+    type Target = HiveAccount;
+
+    fn deref(&self) -> &Self::Target {
+        &self.account
+    }
+}
+
+impl Drop for ExclusiveAccount {
+    fn drop(&mut self) {
+        self.reservations.release(self.account.address());
+    }
+}
+
+#[derive(Debug)]
+struct PoolInner {
+    accounts: Vec<HiveAccount>,
+    /// The accounts wrapped for topping up, empty without [AccountPoolConfig::top_up].
+    top_ups: Vec<TopUpAccount<AnyFaucet>>,
+    /// When the balances of each account were last checked for a top-up.
+    top_up_checks: Vec<Mutex<Option<Instant>>>,
+    health: Vec<Mutex<AccountHealth>>,
+    next: AtomicUsize,
+    /// Index of the account handed out last, which the [Account] implementation of the pool uses.
+    current: AtomicUsize,
+    config: AccountPoolConfig,
+    reservations: Reservations,
+}
+
+/// Clones share the rotation and the health of the accounts.
+#[derive(Debug, Clone)]
+pub struct AccountPool {
+    inner: Arc<PoolInner>,
+}
+
+impl AccountPool {
+    pub fn new(accounts: Vec<HiveAccount>) -> Self {
+        Self::with_config(accounts, AccountPoolConfig::default())
+    }
+
+    pub fn with_config(accounts: Vec<HiveAccount>, config: AccountPoolConfig) -> Self {
+        let health = accounts
+            .iter()
+            .map(|account| Mutex::new(AccountHealth { address: account.address(), ..Default::default() }))
+            .collect();
+        let top_ups: Vec<TopUpAccount<AnyFaucet>> =
+            match &config.top_up {
+                Some(top_up) => accounts
+                    .iter()
+                    .map(|account| {
+                        top_up.policies.iter().fold(
+                            TopUpAccount::new(account.clone(), top_up.faucet.clone()),
+                            |top_up_account, policy| top_up_account.with_policy(policy.clone()),
+                        )
+                    })
+                    .collect(),
+                None => Vec::new(),
+            };
+        let top_up_checks = top_ups.iter().map(|_| Mutex::new(None)).collect();
+        let reservations = Reservations::default();
+        Self {
+            inner: Arc::new(PoolInner {
+                accounts,
+                top_ups,
+                top_up_checks,
+                health,
+                next: AtomicUsize::new(0),
+                current: AtomicUsize::new(0),
+                config,
+                reservations,
+            }),
+        }
+    }
+
+    pub fn accounts(&self) -> &[HiveAccount] {
+        &self.inner.accounts
+    }
+
+    /// Hands out the next account of the rotation that is neither quarantined nor reserved.
+    #[allow(clippy::result_large_err)]
+    pub fn acquire(&self) -> Result<AccountLease, OpenRpcTestGenError> {
+        let index = self.next_index()?;
+        self.schedule_top_up(index);
+        self.inner.current.store(index, Ordering::Relaxed);
+        Ok(AccountLease { index, account: self.inner.accounts[index].clone() })
+    }
+
+    /// Tops up the fee token balances of the account at `index` that are below their threshold.
+    pub async fn top_up(&self, index: usize) {
+        if let Some(top_up) = self.inner.top_ups.get(index) {
+            *lock(&self.inner.top_up_checks[index]) = Some(Instant::now());
+            top_up_balances(top_up).await;
+        }
+    }
+
+    /// Checks the balances of the account at `index` in the background, unless they were checked
+    /// less than [TOP_UP_INTERVAL] ago.
+    fn schedule_top_up(&self, index: usize) {
+        let Some(top_up) = self.inner.top_ups.get(index) else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        {
+            let mut checked = lock(&self.inner.top_up_checks[index]);
+            if checked.is_some_and(|checked| checked.elapsed() < TOP_UP_INTERVAL) {
+                return;
+            }
+            *checked = Some(Instant::now());
+        }
+        let top_up = top_up.clone();
+        runtime.spawn(async move { top_up_balances(&top_up).await });
+    }
+
+    pub fn report_success(&self, lease: &AccountLease) {
+        let mut health = self.health_of(lease.index);
+        health.transactions += 1;
+        health.consecutive_failures = 0;
+        health.quarantined_until = None;
+        health.nonce = health.nonce.map(|nonce| nonce + Felt::ONE);
+    }
+
+    pub fn report_failure(&self, lease: &AccountLease, reason: &str) {
+        let mut health = self.health_of(lease.index);
+        health.transactions += 1;
+        health.failures += 1;
+        health.consecutive_failures += 1;
+        // Whether the failed transaction consumed the nonce is unknown until the next refresh.
+        health.nonce = None;
+        if health.consecutive_failures >= self.inner.config.max_consecutive_failures {
+            warn!(
+                "Quarantining account {:#x} after {} failures in a row, last one: {}",
+                health.address, health.consecutive_failures, reason
+            );
+            health.quarantined_until = Some(Instant::now() + self.inner.config.quarantine);
+        }
+    }
+
+    /// Runs `f` with the next account and reports its outcome.
+    pub async fn run<T, F, Fut>(&self, f: F) -> Result<T, OpenRpcTestGenError>
+    where
+        F: FnOnce(HiveAccount) -> Fut,
+        Fut: Future<Output = Result<T, OpenRpcTestGenError>>,
+    {
+        let lease = self.acquire()?;
+        let result = f(lease.account.clone()).await;
+        match &result {
+            Ok(_) => self.report_success(&lease),
+            Err(e) => self.report_failure(&lease, &e.to_string()),
+        }
+        result
+    }
+
+    /// Fetches the nonce and `fee_tokens` balances of every account, quarantining the accounts
+    /// with a balance below [AccountPoolConfig::min_balance]. Accounts are topped up first.
+    pub async fn refresh(&self, fee_tokens: &[Felt]) -> Result<(), OpenRpcTestGenError> {
+        for (index, account) in self.inner.accounts.iter().enumerate() {
+            self.top_up(index).await;
+            let nonce = account.get_nonce().await?;
+            let mut balances = HashMap::new();
+            for token in fee_tokens {
+                let balance =
+                    get_balance(account.provider().clone(), account.address(), *token, BlockId::Tag(BlockTag::Pending))
+                        .await?;
+                balances.insert(*token, balance.first().copied().unwrap_or_default());
+            }
+
+            let mut health = self.health_of(index);
+            health.nonce = Some(nonce);
+            if let Some((token, balance)) =
+                balances.iter().find(|(_, balance)| **balance < self.inner.config.min_balance)
+            {
+                warn!("Quarantining account {:#x}, its balance of {:#x} is {}", health.address, token, balance);
+                health.quarantined_until = Some(Instant::now() + self.inner.config.quarantine);
+            }
+            health.balances = balances;
+        }
+        Ok(())
+    }
+
+    pub fn health(&self) -> Vec<AccountHealth> {
+        (0..self.inner.accounts.len()).map(|index| self.health_of(index).clone()).collect()
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn next_index(&self) -> Result<usize, OpenRpcTestGenError> {
+        let len = self.inner.accounts.len();
+        if len == 0 {
+            return Err(OpenRpcTestGenError::EmptyUrlList("Accounts list is empty - no urls.".to_string()));
+        }
+
+        self.available_indices()
+            .find(|index| !self.inner.reservations.is_reserved(self.inner.accounts[*index].address()))
+            .ok_or_else(|| {
+                OpenRpcTestGenError::Other(format!("All {len} accounts of the pool are quarantined or reserved"))
+            })
+    }
+
+    /// Accounts that are not quarantined, in the order of the rotation.
+    fn available_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        let len = self.inner.accounts.len();
+        let now = Instant::now();
+        let start = self.inner.next.fetch_add(1, Ordering::Relaxed);
+        (0..len)
+            .map(move |offset| (start + offset) % len)
+            .filter(move |index| !self.health_of(*index).is_quarantined(now))
+    }
+
+    /// The account handed out last, the one transactions built on the pool itself are sent from.
+    fn current(&self) -> &HiveAccount {
+        &self.inner.accounts[self.inner.current.load(Ordering::Relaxed)]
+    }
+
+    fn health_of(&self, index: usize) -> MutexGuard<'_, AccountHealth> {
+        lock(&self.inner.health[index])
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+async fn top_up_balances(top_up: &TopUpAccount<AnyFaucet>) {
+    for unit in [PriceUnit::Fri, PriceUnit::Wei] {
+        if let Err(e) = top_up.ensure_funded(unit).await {
+            warn!("Could not top up account {:#x}: {}", top_up.address(), e);
+        }
+    }
+}
+
+impl RandomizableAccountsTrait for AccountPool {
+    fn random_accounts(&self) -> Result<HiveAccount, OpenRpcTestGenError> {
+        self.acquire().map(|lease| lease.account)
+    }
+
+    fn exclusive_account(&self) -> Result<ExclusiveAccount, OpenRpcTestGenError> {
+        let accounts = &self.inner.accounts;
+        let addresses: Vec<Felt> = accounts.iter().map(|account| account.address()).collect();
+        let candidates: Vec<usize> = self.available_indices().collect();
+        let index = self
+            .inner
+            .reservations
+            .reserve(&addresses, candidates.iter().map(|index| addresses[*index]))
+            .and_then(|address| candidates.into_iter().find(|index| addresses[*index] == address))
+            .ok_or_else(|| OpenRpcTestGenError::Other("No account of the pool can be reserved".to_string()))?;
+        self.schedule_top_up(index);
+        Ok(ExclusiveAccount::new(accounts[index].clone(), self.inner.reservations.clone()))
+    }
+}
+
+impl Account for AccountPool {
+    type SignError = SignError<<LocalWallet as crate::utils::v7::signers::signer::Signer>::SignError>;
+
+    fn address(&self) -> Felt {
+        self.current().address()
+    }
+
+    fn chain_id(&self) -> Felt {
+        self.current().chain_id()
+    }
+
+    async fn sign_execution_v1(
+        &self,
+        execution: &crate::utils::v7::accounts::account::RawExecutionV1,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        self.current().sign_execution_v1(execution, query_only).await
+    }
+
+    async fn sign_execution_v3(
+        &self,
+        execution: &crate::utils::v7::accounts::account::RawExecutionV3,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        self.current().sign_execution_v3(execution, query_only).await
+    }
+
+    async fn sign_declaration_v2(
+        &self,
+        declaration: &crate::utils::v7::accounts::account::RawDeclarationV2,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        self.current().sign_declaration_v2(declaration, query_only).await
+    }
+
+    async fn sign_declaration_v3(
+        &self,
+        declaration: &crate::utils::v7::accounts::account::RawDeclarationV3,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        self.current().sign_declaration_v3(declaration, query_only).await
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        self.current().is_signer_interactive()
+    }
+}
+
+impl ExecutionEncoder for AccountPool {
+    fn encode_calls(&self, calls: &[Call]) -> Vec<Felt> {
+        self.current().encode_calls(calls)
+    }
+}
+
+impl ConnectedAccount for AccountPool {
+    type Provider = JsonRpcClient<HttpTransport>;
+
+    fn provider(&self) -> &Self::Provider {
+        self.current().provider()
+    }
+
+    fn block_id(&self) -> BlockId<Felt> {
+        self.current().block_id()
+    }
+
+    async fn get_nonce(&self) -> Result<Felt, ProviderError> {
+        self.current().get_nonce().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserves_free_addresses_until_released() {
+        let reservations = Reservations::default();
+        let addresses = [Felt::ONE, Felt::TWO];
+
+        assert_eq!(reservations.reserve(&addresses, [Felt::TWO, Felt::ONE]), Some(Felt::TWO));
+        assert!(reservations.is_reserved(Felt::TWO));
+        assert_eq!(reservations.reserve(&addresses, [Felt::TWO, Felt::ONE]), Some(Felt::ONE));
+        assert_eq!(reservations.reserve(&addresses, addresses), None);
+        assert_eq!(reservations.reserve(&addresses, [Felt::THREE]), None);
+
+        reservations.release(Felt::TWO);
+        assert!(!reservations.is_reserved(Felt::TWO));
+        assert_eq!(reservations.reserve(&addresses, addresses), Some(Felt::TWO));
+    }
+
+    #[test]
+    fn reserves_the_only_account() {
+        let pool = AccountPool::new(vec![account(Felt::ONE)]);
+
+        let exclusive = pool.exclusive_account().unwrap();
+        assert_eq!(exclusive.address(), Felt::ONE);
+        assert!(pool.random_accounts().is_err());
+
+        drop(exclusive);
+        assert_eq!(pool.random_accounts().unwrap().address(), Felt::ONE);
+    }
+
+    #[test]
+    fn sends_transactions_of_the_pool_from_the_account_handed_out_last() {
+        let pool = AccountPool::new(vec![account(Felt::ONE), account(Felt::TWO)]);
+
+        for _ in 0..4 {
+            let handed_out = pool.random_accounts().unwrap().address();
+            assert_eq!(pool.address(), handed_out);
+            assert_eq!(pool.address(), handed_out);
+        }
+    }
+
+    fn account(address: Felt) -> HiveAccount {
+        SingleOwnerAccount::new(
+            JsonRpcClient::new(HttpTransport::new(url::Url::parse("http://127.0.0.1:5050").unwrap())),
+            LocalWallet::from(crate::utils::v7::signers::key_pair::SigningKey::from_secret_scalar(Felt::ONE)),
+            address,
+            Felt::ONE,
+            crate::utils::v7::accounts::single_owner::ExecutionEncoding::New,
+        )
+    }
+}
@@ -0,0 +1,65 @@
+//! Chain ids as names: a chain id is the short string of its network name, e.g. `SN_SEPOLIA` is
+//! `0x534e5f5345504f4c4941`. The name the node must report is configured per network, and the
+//! chain id is decoded to compare it, so that a mismatch reads as a network name instead of a felt.
+
+use starknet_types_core::felt::Felt;
+use thiserror::Error;
+
+use super::network_registry::network_registry;
+
+/// Chain name of the devnets the suites run against by default.
+pub const DEFAULT_CHAIN_NAME: &str = "MADARA_DEVNET";
+
+/// Longest short string a felt holds.
+const MAX_CHAIN_NAME_LENGTH: usize = 31;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ChainIdError {
+    #[error("Chain id {0:#x} is not a short string of printable ASCII characters")]
+    NotAShortString(Felt),
+    #[error("Chain name {0:?} is not at most 31 printable ASCII characters")]
+    InvalidName(String),
+}
+
+/// Chain name of the network of the run, [DEFAULT_CHAIN_NAME] unless another network is set.
+pub fn expected_chain_name() -> &'static str {
+    &network_registry().chain_name
+}
+
+/// Decodes the chain id to the network name it encodes, e.g. `SN_MAIN`.
+pub fn decode_chain_id(chain_id: Felt) -> Result<String, ChainIdError> {
+    let bytes = chain_id.to_bytes_be();
+    let name: Vec<u8> = bytes.iter().copied().skip_while(|byte| *byte == 0).collect();
+    if name.is_empty() || !name.iter().all(|byte| byte.is_ascii_graphic()) {
+        return Err(ChainIdError::NotAShortString(chain_id));
+    }
+    Ok(name.into_iter().map(char::from).collect())
+}
+
+/// Encodes a network name to its chain id.
+pub fn encode_chain_name(name: &str) -> Result<Felt, ChainIdError> {
+    if name.is_empty() || name.len() > MAX_CHAIN_NAME_LENGTH || !name.bytes().all(|byte| byte.is_ascii_graphic()) {
+        return Err(ChainIdError::InvalidName(name.to_string()));
+    }
+    Ok(Felt::from_bytes_be_slice(name.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_and_encodes_network_names() {
+        let sepolia = Felt::from_hex_unchecked("0x534e5f5345504f4c4941");
+        assert_eq!(decode_chain_id(sepolia).unwrap(), "SN_SEPOLIA");
+        assert_eq!(encode_chain_name("SN_SEPOLIA").unwrap(), sepolia);
+        assert_eq!(
+            decode_chain_id(Felt::from_hex_unchecked("0x4d41444152415f4445564e4554")).unwrap(),
+            DEFAULT_CHAIN_NAME
+        );
+
+        assert_eq!(decode_chain_id(Felt::ZERO), Err(ChainIdError::NotAShortString(Felt::ZERO)));
+        assert_eq!(decode_chain_id(Felt::from(0x0a41u64)), Err(ChainIdError::NotAShortString(Felt::from(0x0a41u64))));
+        assert!(encode_chain_name(&"A".repeat(32)).is_err());
+    }
+}
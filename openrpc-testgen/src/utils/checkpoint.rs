@@ -0,0 +1,168 @@
+//! Checkpoints of a run: the test cases that passed, the fixtures the suite setups created and the
+//! nonces of the accounts, saved after every change so that a run interrupted hours into a testnet
+//! run can be resumed. A resumed run skips the cases that already passed and the setups reuse their
+//! fixtures instead of deploying them again.
+//!
+//! The checkpoint holds the private key of the executable account fixture, a throwaway key the
+//! setup generated. It is written as is, redacting it would make the fixture unusable, but on unix
+//! the file is only readable by its owner.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
+use thiserror::Error;
+use tracing::{info, warn};
+
+static CHECKPOINT: OnceLock<Mutex<State>> = OnceLock::new();
+
+#[derive(Debug, Error)]
+pub enum CheckpointError {
+    #[error("Could not read checkpoint {path}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("Could not parse checkpoint {path}: {source}")]
+    Parse { path: PathBuf, source: serde_json::Error },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// `suite::name` of the test cases that passed.
+    pub passed: BTreeSet<String>,
+    /// Values of the fixtures by name, e.g. the address and private key of an account.
+    pub fixtures: BTreeMap<String, Vec<Felt>>,
+    /// Nonces of the accounts by address, as last seen.
+    pub nonces: BTreeMap<String, Felt>,
+}
+
+impl Checkpoint {
+    pub fn load(path: &Path) -> Result<Self, CheckpointError> {
+        let contents =
+            fs::read_to_string(path).map_err(|source| CheckpointError::Read { path: path.to_path_buf(), source })?;
+        serde_json::from_str(&contents).map_err(|source| CheckpointError::Parse { path: path.to_path_buf(), source })
+    }
+
+    /// Writes to a temporary file first, so that an interruption never leaves a truncated checkpoint.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        // A leftover temporary file keeps its permissions when reopened, remove it so it is recreated below.
+        match fs::remove_file(&temporary) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+            _ => {}
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&temporary)?;
+        file.write_all((serde_json::to_string_pretty(self)? + "\n").as_bytes())?;
+        fs::rename(&temporary, path)
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    path: PathBuf,
+    checkpoint: Checkpoint,
+}
+
+impl State {
+    fn save(&self) {
+        if let Err(e) = self.checkpoint.save(&self.path) {
+            warn!("Could not save checkpoint {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+fn state() -> Option<std::sync::MutexGuard<'static, State>> {
+    CHECKPOINT.get().map(|state| state.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+}
+
+/// Saves the progress of the run to `path`, resuming from the checkpoint already there if `resume`
+/// is set. Returns `Ok(false)` if checkpoints were already enabled.
+pub fn set_checkpoint(path: PathBuf, resume: bool) -> Result<bool, CheckpointError> {
+    let checkpoint = if resume && path.exists() { Checkpoint::load(&path)? } else { Checkpoint::default() };
+    if resume {
+        info!(
+            "Resuming from checkpoint {}: {} passed test cases, {} fixtures",
+            path.display(),
+            checkpoint.passed.len(),
+            checkpoint.fixtures.len()
+        );
+    }
+    Ok(CHECKPOINT.set(Mutex::new(State { path, checkpoint })).is_ok())
+}
+
+/// Whether the test case passed before the run was resumed.
+pub fn is_already_passed(suite: &str, name: &str) -> bool {
+    state().is_some_and(|state| state.checkpoint.passed.contains(&format!("{suite}::{name}")))
+}
+
+/// Records a passed test case, does nothing unless checkpoints are enabled.
+pub fn record_passed(suite: &str, name: &str) {
+    if let Some(mut state) = state() {
+        if state.checkpoint.passed.insert(format!("{suite}::{name}")) {
+            state.save();
+        }
+    }
+}
+
+/// Values of the fixture recorded under `name`, if any.
+pub fn fixture(name: &str) -> Option<Vec<Felt>> {
+    state()?.checkpoint.fixtures.get(name).cloned()
+}
+
+/// Records the values of a fixture, does nothing unless checkpoints are enabled.
+pub fn record_fixture(name: &str, values: Vec<Felt>) {
+    if let Some(mut state) = state() {
+        if state.checkpoint.fixtures.get(name) != Some(&values) {
+            state.checkpoint.fixtures.insert(name.to_string(), values);
+            state.save();
+        }
+    }
+}
+
+/// Records the nonce of an account, does nothing unless checkpoints are enabled. Nonces never
+/// decrease, so one lower than recorded means the node was reset since: the fixtures are gone and
+/// the cases that passed have to run again, the progress of the resumed run is discarded.
+pub fn record_nonce(address: Felt, nonce: Felt) {
+    let Some(mut state) = state() else {
+        return;
+    };
+    let address = format!("{address:#x}");
+    if state.checkpoint.nonces.get(&address).is_some_and(|recorded| nonce < *recorded) {
+        warn!("Nonce of {} went back to {:#x}, the node was reset since the checkpoint, starting over", address, nonce);
+        state.checkpoint = Checkpoint::default();
+    }
+    state.checkpoint.nonces.insert(address, nonce);
+    state.save();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_and_loads_checkpoints() {
+        let path = std::env::temp_dir().join(format!("checkpoint-{}.json", std::process::id()));
+        let checkpoint = Checkpoint {
+            passed: BTreeSet::from(["openrpc::get_nonce".to_string()]),
+            fixtures: BTreeMap::from([("executable_account".to_string(), vec![Felt::ONE, Felt::TWO])]),
+            nonces: BTreeMap::from([(format!("{:#x}", Felt::from(3)), Felt::from(42))]),
+        };
+        checkpoint.save(&path).unwrap();
+        assert_eq!(Checkpoint::load(&path).unwrap(), checkpoint);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
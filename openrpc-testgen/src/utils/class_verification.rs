@@ -0,0 +1,138 @@
+//! Local verification of on-chain classes: a class is downloaded with `starknet_getClass` or
+//! `starknet_getClassAt`, rebuilt into a [SierraClass] and its hash computed again, which must be
+//! the hash the node serves it under. Once enabled, every class hash the suites declare or query is
+//! recorded, and all of them are verified at the end of the run.
+
+use std::collections::BTreeSet;
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{v0_7_1::ContractClass, BlockId, BlockTag};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use super::secrets::write_redacted;
+use super::v7::{
+    contract::{ComputeClassHashError, HashAndFlatten, SierraClass, SierraClassDebugInfo},
+    providers::provider::{Provider, ProviderError},
+};
+
+#[derive(Debug, Error)]
+pub enum ClassVerificationError {
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    #[error("ABI of the class could not be parsed: {0}")]
+    Abi(#[from] serde_json::Error),
+    #[error("Class hash could not be computed: {0}")]
+    ComputeClassHash(#[from] ComputeClassHashError),
+    #[error("Class served under {expected:#x} hashes to {computed:#x}")]
+    HashMismatch { expected: Felt, computed: Felt },
+}
+
+static TOUCHED_CLASSES: OnceLock<Mutex<BTreeSet<Felt>>> = OnceLock::new();
+
+/// Starts recording the class hashes the suites touch. Returns `false` if it was already enabled.
+pub fn enable_class_verification() -> bool {
+    TOUCHED_CLASSES.set(Mutex::new(BTreeSet::new())).is_ok()
+}
+
+/// Records a class hash to verify at the end of the run, does nothing unless verification is enabled.
+pub fn record_class_hash(class_hash: Felt) {
+    if let Some(classes) = TOUCHED_CLASSES.get() {
+        classes.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(class_hash);
+    }
+}
+
+/// Downloads the class declared under `class_hash`.
+pub async fn download_class<P: Provider>(
+    provider: &P,
+    block_id: BlockId<Felt>,
+    class_hash: Felt,
+) -> Result<SierraClass, ClassVerificationError> {
+    to_sierra_class(provider.get_class(block_id, class_hash).await?)
+}
+
+/// Downloads the class of the contract deployed at `contract_address`.
+pub async fn download_class_at<P: Provider>(
+    provider: &P,
+    block_id: BlockId<Felt>,
+    contract_address: Felt,
+) -> Result<SierraClass, ClassVerificationError> {
+    to_sierra_class(provider.get_class_at(block_id, contract_address).await?)
+}
+
+/// Downloads the class declared under `class_hash` and checks that it hashes to `class_hash`.
+pub async fn verify_class<P: Provider>(
+    provider: &P,
+    block_id: BlockId<Felt>,
+    class_hash: Felt,
+) -> Result<SierraClass, ClassVerificationError> {
+    let class = download_class(provider, block_id, class_hash).await?;
+    let computed = class.class_hash()?;
+    if computed != class_hash {
+        return Err(ClassVerificationError::HashMismatch { expected: class_hash, computed });
+    }
+    Ok(class)
+}
+
+/// Rebuilds the class as declared, the debug info is not part of the hash and isn't served back.
+fn to_sierra_class(class: ContractClass<Felt>) -> Result<SierraClass, ClassVerificationError> {
+    let abi = match class.abi.as_deref() {
+        Some(abi) if !abi.is_empty() => serde_json::from_str(abi)?,
+        _ => vec![],
+    };
+    Ok(SierraClass {
+        sierra_program: class.sierra_program,
+        sierra_program_debug_info: SierraClassDebugInfo {
+            type_names: vec![],
+            libfunc_names: vec![],
+            user_func_names: vec![],
+        },
+        contract_class_version: class.contract_class_version,
+        entry_points_by_type: class.entry_points_by_type,
+        abi,
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassVerificationFailure {
+    pub class_hash: Felt,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClassVerificationReport {
+    pub verified: Vec<Felt>,
+    pub failures: Vec<ClassVerificationFailure>,
+}
+
+impl ClassVerificationReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        info!("Verified {} classes, {} failed", self.verified.len() + self.failures.len(), self.failures.len());
+        for failure in &self.failures {
+            warn!("  {:#x}: {}", failure.class_hash, failure.reason);
+        }
+    }
+}
+
+/// Verifies every recorded class against the pending state. Returns `None` unless verification
+/// is enabled.
+pub async fn class_verification_report<P: Provider>(provider: &P) -> Option<ClassVerificationReport> {
+    let classes = TOUCHED_CLASSES.get()?.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+
+    let mut report = ClassVerificationReport::default();
+    for class_hash in classes {
+        match verify_class(provider, BlockId::Tag(BlockTag::Pending), class_hash).await {
+            Ok(_) => report.verified.push(class_hash),
+            Err(e) => report.failures.push(ClassVerificationFailure { class_hash, reason: e.to_string() }),
+        }
+    }
+    Some(report)
+}
@@ -0,0 +1,260 @@
+//! Run configuration files: a JSON object of runner options keyed by their long name, e.g.
+//! `{ "urls": ["http://127.0.0.1:5050"], "paymaster_private_key": "${PAYMASTER_PRIVATE_KEY}" }`.
+//! `${VAR}` in string values is replaced with the environment variable `VAR`, so that credentials
+//! stay out of the file. Values of options naming a key, token or password, and values written
+//! as `{ "secret": "..." }`, are secrets, to be redacted with [super::secrets].
+//!
+//! The options under [NETWORKS_KEY] are those of the target networks, by name, e.g.
+//! `{ "networks": { "sepolia": { "urls": "..." } } }`. The options of the selected network override
+//! the shared ones, so that one file holds the credentials of every network the suites run against.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+/// Key of the options of every network.
+pub const NETWORKS_KEY: &str = "networks";
+
+/// Options whose name ends with one of these hold secrets.
+const SECRET_SUFFIXES: [&str; 4] = ["private_key", "api_key", "token", "password"];
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Config file {path} is unreadable: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Config file is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Config file must contain a JSON object of options")]
+    NotAnObject,
+    #[error("Config file must map the names of its networks to JSON objects of options")]
+    NetworksNotObjects,
+    #[error("Config file has no options for network {0}")]
+    UnknownNetwork(String),
+    #[error("Option {option} refers to ${{{variable}}}, which is not set")]
+    MissingVariable { option: String, variable: String },
+    #[error("Option {option} has an unterminated ${{...}}")]
+    UnterminatedVariable { option: String },
+    #[error("Option {option} must be a string, a number, a boolean, an array of them or {{ \"secret\": ... }}")]
+    UnsupportedValue { option: String },
+}
+
+/// An option of the config file, with its variables interpolated.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ConfigOption {
+    /// Name of the option with `_` separators, e.g. `paymaster_private_key`.
+    pub name: String,
+    /// Value as on the command line, arrays are joined with spaces.
+    pub value: String,
+    pub secret: bool,
+}
+
+impl ConfigOption {
+    /// Environment variable the runner reads the option from, e.g. `PAYMASTER_PRIVATE_KEY`.
+    pub fn env_name(&self) -> String {
+        self.name.to_uppercase()
+    }
+}
+
+impl fmt::Debug for ConfigOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = if self.secret { "<redacted>" } else { self.value.as_str() };
+        f.debug_struct("ConfigOption").field("name", &self.name).field("value", &value).finish()
+    }
+}
+
+/// Reads the config file at `path`, interpolating variables from the environment.
+pub fn load_config(path: &Path) -> Result<Vec<ConfigOption>, ConfigError> {
+    let content = fs::read_to_string(path).map_err(|source| ConfigError::Io { path: path.to_path_buf(), source })?;
+    parse_config(&content, |variable| std::env::var(variable).ok())
+}
+
+/// Same as [load_config], with the options of `network` overriding the shared ones.
+pub fn load_network_config(path: &Path, network: &str) -> Result<Vec<ConfigOption>, ConfigError> {
+    let content = fs::read_to_string(path).map_err(|source| ConfigError::Io { path: path.to_path_buf(), source })?;
+    parse_network_config(&content, network, |variable| std::env::var(variable).ok())
+}
+
+/// Names of the networks of the config file at `path`, sorted.
+pub fn load_config_networks(path: &Path) -> Result<Vec<String>, ConfigError> {
+    let content = fs::read_to_string(path).map_err(|source| ConfigError::Io { path: path.to_path_buf(), source })?;
+    Ok(networks(&content)?.into_iter().map(|(name, _)| name).collect())
+}
+
+pub fn parse_config(content: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<Vec<ConfigOption>, ConfigError> {
+    let Value::Object(mut options) = serde_json::from_str(content)? else {
+        return Err(ConfigError::NotAnObject);
+    };
+    options.remove(NETWORKS_KEY);
+    parse_options(options, &lookup)
+}
+
+pub fn parse_network_config(
+    content: &str,
+    network: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<ConfigOption>, ConfigError> {
+    let network_options = networks(content)?
+        .into_iter()
+        .find_map(|(name, options)| (name == network).then_some(options))
+        .ok_or_else(|| ConfigError::UnknownNetwork(network.to_string()))?;
+    let network_options = parse_options(network_options, &lookup)?;
+
+    let mut options = parse_config(content, &lookup)?;
+    options.retain(|option| !network_options.iter().any(|network_option| network_option.name == option.name));
+    options.extend(network_options);
+    Ok(options)
+}
+
+fn networks(content: &str) -> Result<Vec<(String, Map<String, Value>)>, ConfigError> {
+    let Value::Object(mut options) = serde_json::from_str(content)? else {
+        return Err(ConfigError::NotAnObject);
+    };
+    match options.remove(NETWORKS_KEY) {
+        None => Ok(Vec::new()),
+        Some(Value::Object(networks)) => networks
+            .into_iter()
+            .map(|(name, options)| match options {
+                Value::Object(options) => Ok((name, options)),
+                _ => Err(ConfigError::NetworksNotObjects),
+            })
+            .collect(),
+        Some(_) => Err(ConfigError::NetworksNotObjects),
+    }
+}
+
+fn parse_options(
+    options: Map<String, Value>,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<Vec<ConfigOption>, ConfigError> {
+    options
+        .into_iter()
+        .map(|(name, value)| {
+            let name = name.replace('-', "_");
+            let (value, marked_secret) = match value {
+                Value::Object(fields) => (secret_value(&name, fields)?, true),
+                value => (value, false),
+            };
+            let value = option_value(&name, &value, lookup)?;
+            let secret = marked_secret || SECRET_SUFFIXES.iter().any(|suffix| name.ends_with(suffix));
+            Ok(ConfigOption { name, value, secret })
+        })
+        .collect()
+}
+
+fn secret_value(name: &str, mut fields: Map<String, Value>) -> Result<Value, ConfigError> {
+    match (fields.remove("secret"), fields.is_empty()) {
+        (Some(value), true) => Ok(value),
+        _ => Err(ConfigError::UnsupportedValue { option: name.to_string() }),
+    }
+}
+
+fn option_value(name: &str, value: &Value, lookup: &impl Fn(&str) -> Option<String>) -> Result<String, ConfigError> {
+    match value {
+        Value::String(text) => interpolate(name, text, lookup),
+        Value::Number(number) => Ok(number.to_string()),
+        Value::Bool(flag) => Ok(flag.to_string()),
+        Value::Array(items) if items.iter().all(|item| !item.is_array() && !item.is_object()) => {
+            let items = items.iter().map(|item| option_value(name, item, lookup)).collect::<Result<Vec<_>, _>>()?;
+            Ok(items.join(" "))
+        }
+        _ => Err(ConfigError::UnsupportedValue { option: name.to_string() }),
+    }
+}
+
+/// Replaces every `${VAR}` of `text` with the value of `VAR`.
+pub fn interpolate(option: &str, text: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String, ConfigError> {
+    let mut interpolated = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        interpolated.push_str(&rest[..start]);
+        let end =
+            rest[start..].find('}').ok_or_else(|| ConfigError::UnterminatedVariable { option: option.to_string() })?;
+        let variable = &rest[start + 2..start + end];
+        let value = lookup(variable).ok_or_else(|| ConfigError::MissingVariable {
+            option: option.to_string(),
+            variable: variable.to_string(),
+        })?;
+        interpolated.push_str(&value);
+        rest = &rest[start + end + 1..];
+    }
+    interpolated.push_str(rest);
+    Ok(interpolated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(variable: &str) -> Option<String> {
+        (variable == "PAYMASTER_KEY").then(|| "0x1234".to_string())
+    }
+
+    #[test]
+    fn interpolates_variables_and_marks_secrets() {
+        let options = parse_config(
+            r#"{
+                "urls": ["http://a:5050", "http://b:5050"],
+                "paymaster-private-key": "${PAYMASTER_KEY}",
+                "udc_address": { "secret": "${PAYMASTER_KEY}" },
+                "resilience": true
+            }"#,
+            lookup,
+        )
+        .unwrap();
+        let option = |name: &str| options.iter().find(|option| option.name == name).unwrap();
+
+        assert_eq!(option("urls").value, "http://a:5050 http://b:5050");
+        assert!(!option("urls").secret);
+        assert_eq!(option("paymaster_private_key").value, "0x1234");
+        assert_eq!(option("paymaster_private_key").env_name(), "PAYMASTER_PRIVATE_KEY");
+        assert!(option("paymaster_private_key").secret);
+        assert_eq!(option("udc_address").value, "0x1234");
+        assert!(option("udc_address").secret);
+        assert_eq!(option("resilience").value, "true");
+        assert!(!format!("{:?}", option("paymaster_private_key")).contains("0x1234"));
+    }
+
+    #[test]
+    fn overrides_shared_options_with_those_of_the_network() {
+        let content = r#"{
+            "suite": "open-rpc",
+            "urls": "http://127.0.0.1:5050",
+            "networks": {
+                "devnet": {},
+                "sepolia": { "urls": "https://sepolia:9545", "paymaster_private_key": "${PAYMASTER_KEY}" }
+            }
+        }"#;
+        let values = |options: Vec<ConfigOption>| -> Vec<(String, String)> {
+            options.into_iter().map(|option| (option.name, option.value)).collect()
+        };
+
+        assert_eq!(
+            values(parse_config(content, lookup).unwrap()),
+            [("suite".into(), "open-rpc".into()), ("urls".into(), "http://127.0.0.1:5050".into())]
+        );
+        assert_eq!(
+            values(parse_network_config(content, "sepolia", lookup).unwrap()),
+            [
+                ("suite".into(), "open-rpc".into()),
+                ("paymaster_private_key".into(), "0x1234".into()),
+                ("urls".into(), "https://sepolia:9545".into())
+            ]
+        );
+        assert!(matches!(parse_network_config(content, "mainnet", lookup), Err(ConfigError::UnknownNetwork(_))));
+        assert_eq!(networks(content).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn rejects_unset_and_unterminated_variables() {
+        assert!(matches!(
+            interpolate("gas", "${UNSET}", lookup),
+            Err(ConfigError::MissingVariable { variable, .. }) if variable == "UNSET"
+        ));
+        assert!(matches!(interpolate("gas", "${PAYMASTER_KEY", lookup), Err(ConfigError::UnterminatedVariable { .. })));
+        assert_eq!(interpolate("gas", "$5 and ${PAYMASTER_KEY}", lookup).unwrap(), "$5 and 0x1234");
+    }
+}
@@ -0,0 +1,139 @@
+//! Registry of the classes declared during a run. Once enabled, every declare transaction the
+//! suites submit is recorded, and at the end of the run the blocks produced since it started are
+//! checked against the registry: each class declared in them must be served by `starknet_getClass`
+//! and listed exactly once by the state updates of those blocks. A class store that loses classes,
+//! or lists a redeclared class again, fails this run-level invariant even when every test passed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag, MaybePendingBlockWithTxHashes, MaybePendingStateUpdate};
+use tracing::{info, warn};
+
+use super::secrets::write_redacted;
+use super::v7::providers::provider::{Provider, ProviderError};
+
+static DECLARED_CLASSES: OnceLock<Mutex<BTreeMap<Felt, Vec<Felt>>>> = OnceLock::new();
+
+/// Starts recording declared classes. Returns `false` if the registry was already enabled.
+pub fn enable_declared_class_registry() -> bool {
+    DECLARED_CLASSES.set(Mutex::new(BTreeMap::new())).is_ok()
+}
+
+/// Records the declare transaction of a class, does nothing unless the registry is enabled.
+pub fn record_declared_class(class_hash: Felt, transaction_hash: Felt) {
+    if let Some(classes) = DECLARED_CLASSES.get() {
+        classes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(class_hash)
+            .or_default()
+            .push(transaction_hash);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeclaredClassFailure {
+    pub class_hash: Felt,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DeclaredClassesReport {
+    pub first_block: u64,
+    pub last_block: u64,
+    pub verified: Vec<Felt>,
+    /// Classes whose declare transactions are in none of the blocks, e.g. as the node rejected them.
+    pub not_included: Vec<Felt>,
+    pub failures: Vec<DeclaredClassFailure>,
+}
+
+impl DeclaredClassesReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        info!(
+            "Checked {} classes declared in blocks {} to {}, {} failed, {} declarations not included",
+            self.verified.len() + self.failures.len(),
+            self.first_block,
+            self.last_block,
+            self.failures.len(),
+            self.not_included.len()
+        );
+        for failure in &self.failures {
+            warn!("  {:#x}: {}", failure.class_hash, failure.reason);
+        }
+    }
+}
+
+/// Checks the classes of the registry against the blocks from `first_block` to the latest one.
+/// Returns `None` unless the registry is enabled.
+pub async fn declared_classes_report<P: Provider>(
+    provider: &P,
+    first_block: u64,
+) -> Option<Result<DeclaredClassesReport, ProviderError>> {
+    let declared = DECLARED_CLASSES.get()?.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    Some(check_declared_classes(provider, first_block, declared).await)
+}
+
+async fn check_declared_classes<P: Provider>(
+    provider: &P,
+    first_block: u64,
+    declared: BTreeMap<Felt, Vec<Felt>>,
+) -> Result<DeclaredClassesReport, ProviderError> {
+    let last_block = provider.block_number().await?;
+
+    // Blocks listing each class, and the transactions of the blocks.
+    let mut listings: BTreeMap<Felt, Vec<u64>> = BTreeMap::new();
+    let mut included = BTreeSet::new();
+    for block_number in first_block..=last_block {
+        let block_id = BlockId::Number(block_number);
+        if let MaybePendingStateUpdate::Block(update) = provider.get_state_update(block_id.clone()).await? {
+            let state_diff = update.state_diff;
+            let class_hashes = state_diff
+                .declared_classes
+                .iter()
+                .filter_map(|declared_class| declared_class.class_hash)
+                .chain(state_diff.deprecated_declared_classes.iter().copied());
+            for class_hash in class_hashes {
+                listings.entry(class_hash).or_default().push(block_number);
+            }
+        }
+        if let MaybePendingBlockWithTxHashes::Block(block) = provider.get_block_with_tx_hashes(block_id).await? {
+            included.extend(block.transactions);
+        }
+    }
+
+    let mut report = DeclaredClassesReport { first_block, last_block, ..Default::default() };
+    for (class_hash, transaction_hashes) in declared {
+        if !transaction_hashes.iter().any(|transaction_hash| included.contains(transaction_hash)) {
+            report.not_included.push(class_hash);
+            continue;
+        }
+
+        let mut reasons = Vec::new();
+        if let Err(e) = provider.get_class(BlockId::Tag(BlockTag::Latest), class_hash).await {
+            reasons.push(format!("not served by getClass: {e}"));
+        }
+        match listings.get(&class_hash).map(Vec::as_slice).unwrap_or_default() {
+            [_] => {}
+            [] => reasons.push("listed by no state update".to_string()),
+            blocks => {
+                reasons.push(format!("listed {} times, by the state updates of blocks {:?}", blocks.len(), blocks))
+            }
+        }
+
+        if reasons.is_empty() {
+            report.verified.push(class_hash);
+        } else {
+            report.failures.push(DeclaredClassFailure { class_hash, reason: reasons.join(", ") });
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,204 @@
+use starknet_hive_hashing::{address::contract_address, resource_bounds::ResourceBoundsBuilder};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{
+    Anonymous, DeployAccountTxn, PriceUnit, Txn, TxnExecutionStatus, TxnFinalityStatus, TxnReceipt, TxnStatus,
+};
+
+use crate::{
+    assert_result,
+    utils::{
+        account_pool::AccountPool,
+        network_registry::network_registry,
+        v7::{
+            accounts::{
+                account::{starknet_keccak, Account, ConnectedAccount},
+                call::Call,
+                creation::create::{create_account, AccountType},
+                deployment::{
+                    deploy::{deploy_account, estimate_fee_deploy_account, DeployAccountVersion},
+                    structs::{ValidatedWaitParams, WaitForTx},
+                },
+            },
+            endpoints::{
+                errors::{CallError, OpenRpcTestGenError},
+                utils::{get_selector_from_name, wait_for_sent_transaction},
+            },
+            providers::provider::Provider,
+        },
+    },
+    RandomizableAccountsTrait,
+};
+
+/// How far, in percent, the fee charged may be from the estimate made just before sending: gas
+/// prices can move between the estimate and the block the deployment lands in.
+const FEE_ESTIMATE_TOLERANCE_PERCENT: u128 = 25;
+
+/// Creates, funds and deploys a fresh OZ account with the given transaction `version`, then
+/// validates every field of the resulting `DEPLOY_ACCOUNT` receipt.
+///
+/// V1 deployments are funded and charged in ETH (`WEI`), V3 deployments in STRK (`FRI`).
+pub async fn deploy_account_and_validate_receipt(
+    paymaster: &AccountPool,
+    account_class_hash: Felt,
+    version: DeployAccountVersion,
+) -> Result<(), OpenRpcTestGenError> {
+    let (fee_token, expected_unit) = match version {
+        DeployAccountVersion::V1 => (network_registry().eth_address, PriceUnit::Wei),
+        DeployAccountVersion::V3 => (network_registry().strk_address, PriceUnit::Fri),
+    };
+
+    let account_data =
+        create_account(paymaster.provider(), AccountType::Oz, Option::None, Some(account_class_hash)).await?;
+
+    let transfer_execution = paymaster
+        .execute_v3(vec![Call {
+            to: fee_token,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![account_data.address, Felt::from_hex("0xfffffffffffffff")?, Felt::ZERO],
+        }])
+        .send()
+        .await?;
+
+    wait_for_sent_transaction(transfer_execution.transaction_hash, &paymaster.random_accounts()?).await?;
+
+    let wait_config = WaitForTx { wait: true, wait_params: ValidatedWaitParams::default() };
+    let estimate_fee = estimate_fee_deploy_account(
+        paymaster.provider(),
+        paymaster.chain_id(),
+        wait_config,
+        account_data,
+        false,
+        version,
+    )
+    .await?;
+
+    let deploy_account_hash =
+        deploy_account(paymaster.provider(), paymaster.chain_id(), wait_config, account_data, version).await?;
+
+    wait_for_sent_transaction(deploy_account_hash, &paymaster.random_accounts()?).await?;
+
+    let receipt = match paymaster.provider().get_transaction_receipt(deploy_account_hash).await? {
+        TxnReceipt::DeployAccount(receipt) => receipt,
+        _ => return Err(OpenRpcTestGenError::CallError(CallError::UnexpectedReceiptType)),
+    };
+
+    let public_key = account_data.signing_key.verifying_key().scalar();
+    let computed_address = contract_address(account_data.salt, account_class_hash, &[public_key], Felt::ZERO);
+    assert_result!(
+        receipt.contract_address == computed_address,
+        format!("Expected computed contract address: {:?}, actual: {:?}", computed_address, receipt.contract_address)
+    );
+    assert_result!(
+        receipt.contract_address == account_data.address,
+        format!("Expected contract address: {:?}, actual: {:?}", account_data.address, receipt.contract_address)
+    );
+
+    let common_receipt_properties = receipt.common_receipt_properties;
+
+    assert_result!(
+        common_receipt_properties.transaction_hash == deploy_account_hash,
+        format!(
+            "Expected transaction hash: {:?}, actual: {:?}",
+            deploy_account_hash, common_receipt_properties.transaction_hash
+        )
+    );
+
+    let actual_fee = common_receipt_properties.actual_fee;
+    assert_result!(
+        actual_fee.unit == expected_unit,
+        format!("Actual fee unit expected: {:?}, actual: {:?}", expected_unit, actual_fee.unit)
+    );
+
+    let max_fee = match paymaster.provider().get_transaction_by_hash(deploy_account_hash).await? {
+        Txn::DeployAccount(DeployAccountTxn::V1(txn)) => fee_amount(txn.max_fee)?,
+        Txn::DeployAccount(DeployAccountTxn::V3(txn)) => ResourceBoundsBuilder::parse(&txn.resource_bounds)
+            .map_err(|e| OpenRpcTestGenError::Other(e.to_string()))?
+            .max_fee()
+            .ok_or_else(|| OpenRpcTestGenError::Other("Resource bounds overflow a u128 max fee".to_string()))?,
+        txn => return Err(OpenRpcTestGenError::UnexpectedTxnType(format!("Expected deploy account, got {txn:?}"))),
+    };
+    let charged = fee_amount(actual_fee.amount)?;
+    assert_result!(charged <= max_fee, format!("Actual fee {charged} exceeds the max fee {max_fee}"));
+
+    let estimate = fee_amount(estimate_fee.overall_fee)?;
+    assert_result!(
+        charged.abs_diff(estimate) * 100 <= estimate * FEE_ESTIMATE_TOLERANCE_PERCENT,
+        format!(
+            "Actual fee {charged} is more than {FEE_ESTIMATE_TOLERANCE_PERCENT}% away from the estimate {estimate}"
+        )
+    );
+
+    assert_result!(
+        common_receipt_properties.execution_resources.steps > 0,
+        format!("Expected non-zero execution steps, actual: {:?}", common_receipt_properties.execution_resources.steps)
+    );
+
+    assert_result!(
+        common_receipt_properties.messages_sent.is_empty(),
+        format!("Expected no messages sent, actual: {:?}", common_receipt_properties.messages_sent)
+    );
+
+    match common_receipt_properties.anon {
+        Anonymous::Successful(status) => {
+            assert_result!(
+                status.execution_status == "SUCCEEDED",
+                format!("Expected execution status to be SUCCEEDED, got {:?}", status.execution_status)
+            );
+        }
+        _ => {
+            return Err(OpenRpcTestGenError::Other("Unexpected execution status type.".to_string()));
+        }
+    }
+
+    // The receipt can be read as soon as the block is accepted on L2, or later once it is on L1.
+    let finality_status = &common_receipt_properties.finality_status;
+    assert_result!(
+        matches!(finality_status, TxnFinalityStatus::L2 | TxnFinalityStatus::L1),
+        format!("Expected accepted finality status, actual: {:?}", finality_status)
+    );
+
+    // The status endpoint must agree with the receipt once the transaction has been accepted.
+    let status = paymaster.provider().get_transaction_status(deploy_account_hash).await?;
+    assert_result!(
+        status.finality_status == TxnStatus::AcceptedOnL2 || status.finality_status == TxnStatus::AcceptedOnL1,
+        format!("Expected accepted finality status, actual: {:?}", status.finality_status)
+    );
+    assert_result!(
+        status.execution_status == Some(TxnExecutionStatus::Succeeded),
+        format!("Expected SUCCEEDED execution status, actual: {:?}", status.execution_status)
+    );
+
+    let events = common_receipt_properties.events;
+    let keccak_transfer = starknet_keccak("Transfer".as_bytes());
+    let fee_transfer = events
+        .iter()
+        .find(|event| {
+            event.from_address == fee_token
+                && event.keys.first() == Some(&keccak_transfer)
+                && event.keys.get(1) == Some(&account_data.address)
+        })
+        .ok_or_else(|| OpenRpcTestGenError::Other("Fee transfer event not found in receipt".to_string()))?;
+
+    let fee_transfer_amount = *fee_transfer
+        .data
+        .first()
+        .ok_or_else(|| OpenRpcTestGenError::Other("Missing fee transfer event data".to_string()))?;
+    assert_result!(
+        fee_transfer_amount == actual_fee.amount,
+        format!("Fee transfer amount expected: {:?}, actual: {:?}", actual_fee.amount, fee_transfer_amount)
+    );
+
+    let keccak_owner_added = starknet_keccak("OwnerAdded".as_bytes());
+    assert_result!(
+        events.iter().any(|event| event.from_address == account_data.address
+            && event.keys.first() == Some(&keccak_owner_added)
+            && event.keys.get(1) == Some(&public_key)),
+        "Expected OwnerAdded event emitted by the deployed account"
+    );
+
+    Ok(())
+}
+
+fn fee_amount(fee: Felt) -> Result<u128, OpenRpcTestGenError> {
+    u128::try_from(fee).map_err(|_| OpenRpcTestGenError::Other(format!("Fee {fee} does not fit a u128")))
+}
@@ -0,0 +1,182 @@
+//! Rendering of felts for assertion messages and reports, see [starknet_hive_hashing::display].
+//! Selectors and event keys are named after the ABIs of the loaded artifacts, and addresses after
+//! the registered contracts and the fee tokens and the UDC of the network registry of the run.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use starknet_hive_hashing::display::display_named;
+pub use starknet_hive_hashing::display::{display_felts, short_hex};
+use starknet_types_core::felt::Felt;
+
+use super::network_registry::network_registry;
+use super::selector_dictionary::SelectorDictionary;
+use super::v7::contract::{AbiEntry, SierraClass};
+
+/// Entrypoints of accounts and tokens, named without having to load their ABIs.
+const COMMON_SELECTORS: [&str; 12] = [
+    "__execute__",
+    "__validate__",
+    "__validate_declare__",
+    "__validate_deploy__",
+    "constructor",
+    "transfer",
+    "transfer_from",
+    "transferFrom",
+    "approve",
+    "balance_of",
+    "balanceOf",
+    "deployContract",
+];
+
+/// Names of contracts, keyed by chain id (`None` for all chains) and address.
+type ContractNames = HashMap<(Option<Felt>, Felt), String>;
+
+/// Names the felts of the run render with.
+struct Registry {
+    selectors: SelectorDictionary,
+    contracts: ContractNames,
+}
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut selectors = SelectorDictionary::new();
+        for name in COMMON_SELECTORS {
+            selectors.insert_entrypoint(name);
+        }
+        RwLock::new(Registry { selectors, contracts: ContractNames::new() })
+    })
+}
+
+/// Adds the ABI of a loaded artifact to the registry, so that its entrypoints and events render
+/// by name in assertion messages and reports.
+pub fn register_sierra_class(class: &SierraClass) {
+    register_abi(&class.abi);
+}
+
+pub fn register_abi(abi: &[AbiEntry]) {
+    if let Ok(mut registry) = registry().write() {
+        registry.selectors.ingest_abi(abi);
+    }
+}
+
+pub fn entrypoint_name(selector: &Felt) -> Option<String> {
+    registry().read().ok()?.selectors.entrypoint_name(selector).map(str::to_string)
+}
+
+/// Name of the event whose first key is `key`.
+pub fn event_name(key: &Felt) -> Option<String> {
+    registry().read().ok()?.selectors.event_name(key).map(str::to_string)
+}
+
+/// Renders a selector or event key by name when it is known, e.g. `transfer (0x83afd3…b482d12e)`.
+pub fn display_selector(selector: &Felt) -> String {
+    display_named(entrypoint_name(selector).or_else(|| event_name(selector)).as_deref(), selector)
+}
+
+/// Makes `address` render as `name`, on the given chain only or on all chains when `chain_id` is
+/// `None`, e.g. for predeployed accounts of a node.
+pub fn register_contract_name(chain_id: Option<Felt>, address: Felt, name: impl Into<String>) {
+    if let Ok(mut registry) = registry().write() {
+        registry.contracts.insert((chain_id, address), name.into());
+    }
+}
+
+/// Name registered for the contract at `address`; chain-specific names take precedence over names
+/// shared by all chains.
+fn registered_contract_name(chain_id: Option<&Felt>, address: &Felt) -> Option<String> {
+    let registry = registry().read().ok()?;
+    chain_id
+        .and_then(|chain_id| registry.contracts.get(&(Some(*chain_id), *address)))
+        .or_else(|| registry.contracts.get(&(None, *address)))
+        .cloned()
+}
+
+/// Fee tokens and the UDC of the network of the run, named on all chains. Read from the registry on
+/// every call, so that they follow [set_network_registry](super::network_registry::set_network_registry).
+fn common_contracts() -> [(Felt, &'static str); 3] {
+    let registry = network_registry();
+    [(registry.eth_address, "ETH"), (registry.strk_address, "STRK"), (registry.udc_address, "UDC")]
+}
+
+/// Name of a known contract at `address`: the names registered with [register_contract_name] take
+/// precedence over those of the fee tokens and the UDC.
+pub fn known_contract_name(chain_id: Option<&Felt>, address: &Felt) -> Option<String> {
+    registered_contract_name(chain_id, address).or_else(|| {
+        common_contracts().into_iter().find(|(common, _)| common == address).map(|(_, name)| name.to_string())
+    })
+}
+
+/// Renders an address by name when it is a known contract, e.g. `STRK (0x4718f5…287c938d)`.
+pub fn display_address(chain_id: Option<&Felt>, address: &Felt) -> String {
+    display_named(known_contract_name(chain_id, address).as_deref(), address)
+}
+
+/// Renders event keys, naming the first one after the event it identifies when its ABI has been
+/// loaded, e.g. `[Transfer (0x99cd8b…2e6196e9), 0x1, 0x2]`.
+pub fn display_event_keys(keys: &[Felt]) -> String {
+    let mut rendered: Vec<String> = keys.iter().map(short_hex).collect();
+    if let (Some(first), Some(name)) = (rendered.first_mut(), keys.first().and_then(event_name)) {
+        *first = format!("{name} ({first})");
+    }
+    format!("[{}]", rendered.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::v7::endpoints::utils::get_selector_from_name;
+
+    #[test]
+    fn common_and_loaded_selectors_are_named() {
+        let transfer = get_selector_from_name("transfer").unwrap();
+        assert_eq!(display_selector(&transfer), "transfer (0x83afd3…b482d12e)");
+
+        let selector = get_selector_from_name("display_test_entrypoint").unwrap();
+        assert_eq!(display_selector(&selector), short_hex(&selector));
+        let abi: Vec<AbiEntry> = serde_json::from_value(json!([{
+            "type": "function",
+            "name": "display_test_entrypoint",
+            "inputs": [],
+            "outputs": [],
+            "state_mutability": "external"
+        }]))
+        .unwrap();
+        register_abi(&abi);
+        assert_eq!(entrypoint_name(&selector).as_deref(), Some("display_test_entrypoint"));
+    }
+
+    #[test]
+    fn chain_specific_contract_names_take_precedence() {
+        let address = Felt::from_hex_unchecked("0x4d15b1a7");
+        let chain_id = Felt::from_hex_unchecked("0x534e5f5345504f4c4941");
+        register_contract_name(None, address, "Shared");
+        register_contract_name(Some(chain_id), address, "Sepolia");
+
+        assert_eq!(known_contract_name(Some(&chain_id), &address).as_deref(), Some("Sepolia"));
+        assert_eq!(known_contract_name(Some(&Felt::ONE), &address).as_deref(), Some("Shared"));
+        assert_eq!(display_address(None, &address), "Shared (0x4d15b1a7)");
+    }
+
+    #[test]
+    fn fee_tokens_and_udc_are_named_after_the_registry() {
+        let registry = network_registry();
+        assert_eq!(known_contract_name(None, &registry.strk_address).as_deref(), Some("STRK"));
+        assert_eq!(display_address(None, &registry.udc_address), format!("UDC ({})", short_hex(&registry.udc_address)));
+
+        let address = Felt::from_hex_unchecked("0x5eed");
+        let chain_id = Felt::from_hex_unchecked("0x5eed");
+        register_contract_name(Some(chain_id), address, "Seeded");
+        assert_eq!(display_address(Some(&chain_id), &address), "Seeded (0x5eed)");
+        assert_eq!(display_address(None, &address), "0x5eed");
+    }
+
+    #[test]
+    fn unknown_event_keys_are_short_hex() {
+        let key = Felt::from_hex_unchecked("0x6f2d1b4f0d1e2c3b4a5968778695a4b3c2d1e0f1a2b3c4d5e6f708192a3b4c");
+        assert_eq!(display_event_keys(&[key, Felt::ONE]), "[0x6f2d1b…192a3b4c, 0x1]");
+    }
+}
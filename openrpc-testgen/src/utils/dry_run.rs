@@ -0,0 +1,66 @@
+//! Dry runs: once enabled, every `add_*_transaction` request is simulated on the pending block
+//! instead of being sent, and fails with [DryRunError::NotBroadcast] when the simulation succeeds.
+//! Transaction builders estimate fees before they get there, so a test case checks reads, fee
+//! estimation and simulation up to its first write, and a run spends no funds. The test case, or
+//! suite setup, that was stopped is reported as skipped rather than failed.
+
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::{BlockId, BlockTag, BroadcastedTxn};
+use thiserror::Error;
+use tracing::info;
+
+use super::v7::providers::provider::{Provider, ProviderError, ProviderImplError};
+
+#[derive(Debug, Error)]
+pub enum DryRunError {
+    #[error("Dry run: {0} transaction simulated but not broadcast")]
+    NotBroadcast(&'static str),
+    #[error("Dry run: suite setup simulated but not broadcast")]
+    SetupNotBroadcast,
+}
+
+impl ProviderImplError for DryRunError {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+static DRY_RUN: OnceLock<()> = OnceLock::new();
+
+/// Whether a transaction was stopped since [take_stopped] was last called.
+static STOPPED: AtomicBool = AtomicBool::new(false);
+
+/// Returns `false` if dry runs were already enabled.
+pub fn enable_dry_run() -> bool {
+    DRY_RUN.set(()).is_ok()
+}
+
+pub fn is_dry_run() -> bool {
+    DRY_RUN.get().is_some()
+}
+
+/// Whether a transaction was stopped before being broadcast since the last call, which tells a
+/// test case stopped by the dry run from one that failed.
+pub fn take_stopped() -> bool {
+    STOPPED.swap(false, Ordering::SeqCst)
+}
+
+/// Simulates the transaction in place of broadcasting it. The simulation error is returned if it
+/// fails, [DryRunError::NotBroadcast] otherwise.
+pub(crate) async fn stop_broadcast<P: Provider>(provider: &P, transaction: BroadcastedTxn<Felt>) -> ProviderError {
+    let kind = match &transaction {
+        BroadcastedTxn::Invoke(_) => "invoke",
+        BroadcastedTxn::Declare(_) => "declare",
+        BroadcastedTxn::DeployAccount(_) => "deploy account",
+    };
+    if let Err(e) = provider.simulate_transactions(BlockId::Tag(BlockTag::Pending), vec![transaction], vec![]).await {
+        return e;
+    }
+    info!("Dry run: {} transaction simulated, not broadcasting it", kind);
+    STOPPED.store(true, Ordering::SeqCst);
+    ProviderError::Other(Box::new(DryRunError::NotBroadcast(kind)))
+}
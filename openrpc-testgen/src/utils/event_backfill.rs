@@ -0,0 +1,222 @@
+//! Paging through `starknet_getEvents` over large block ranges: the range is split in segments that
+//! are each paged with continuation tokens, failed requests are retried, and progress can be
+//! persisted to resume an interrupted backfill.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, EmittedEvent, EventFilterWithPageRequest};
+use thiserror::Error;
+use tracing::warn;
+
+use super::v7::providers::provider::{Provider, ProviderError};
+
+#[derive(Debug, Error)]
+pub enum EventBackfillError {
+    #[error("getEvents failed for blocks {from_block}..={to_block} after {attempts} attempts: {source}")]
+    Provider { from_block: u64, to_block: u64, attempts: u32, source: ProviderError },
+    #[error("Checkpoint {path} is unusable: {source}")]
+    Checkpoint { path: PathBuf, source: io::Error },
+    #[error("Invalid block range {from_block}..={to_block}")]
+    InvalidRange { from_block: u64, to_block: u64 },
+}
+
+/// Progress of a backfill, persisted after every page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackfillCheckpoint {
+    /// First block of the segment being paged.
+    pub segment_start: u64,
+    /// Token of the next page of that segment, `None` at its first page.
+    pub continuation_token: Option<String>,
+    /// Number of events returned so far.
+    pub events_fetched: u64,
+}
+
+pub struct EventBackfill<'a, P> {
+    provider: &'a P,
+    from_block: u64,
+    to_block: u64,
+    address: Option<Felt>,
+    keys: Option<Vec<Vec<Felt>>>,
+    chunk_size: u64,
+    blocks_per_query: u64,
+    max_retries: u32,
+    retry_delay: Duration,
+    checkpoint_path: Option<PathBuf>,
+}
+
+impl<'a, P: Provider> EventBackfill<'a, P> {
+    /// Backfill of all events emitted in blocks `from_block..=to_block`.
+    pub fn new(provider: &'a P, from_block: u64, to_block: u64) -> Self {
+        Self {
+            provider,
+            from_block,
+            to_block,
+            address: None,
+            keys: None,
+            chunk_size: 100,
+            blocks_per_query: 1_000,
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+            checkpoint_path: None,
+        }
+    }
+
+    pub fn address(self, address: Felt) -> Self {
+        Self { address: Some(address), ..self }
+    }
+
+    pub fn keys(self, keys: Vec<Vec<Felt>>) -> Self {
+        Self { keys: Some(keys), ..self }
+    }
+
+    /// Number of events per `getEvents` page.
+    pub fn chunk_size(self, chunk_size: u64) -> Self {
+        Self { chunk_size, ..self }
+    }
+
+    /// Number of blocks covered by the filter of a single query, keeping nodes from scanning the
+    /// whole range for every page.
+    pub fn blocks_per_query(self, blocks_per_query: u64) -> Self {
+        Self { blocks_per_query: blocks_per_query.max(1), ..self }
+    }
+
+    /// Retries of a failed page on rate limiting or transport errors; Starknet errors such as an
+    /// invalid continuation token are not retried.
+    pub fn max_retries(self, max_retries: u32) -> Self {
+        Self { max_retries, ..self }
+    }
+
+    /// Delay before the first retry, doubled for every following one.
+    pub fn retry_delay(self, retry_delay: Duration) -> Self {
+        Self { retry_delay, ..self }
+    }
+
+    /// Persists progress to `path` after every page and resumes from it when it exists. The file
+    /// is removed once the backfill completes.
+    pub fn checkpoint_path(self, path: impl Into<PathBuf>) -> Self {
+        Self { checkpoint_path: Some(path.into()), ..self }
+    }
+
+    /// Fetches all events of the range.
+    pub async fn run(&self) -> Result<Vec<EmittedEvent<Felt>>, EventBackfillError> {
+        let mut events = Vec::new();
+        self.run_with(|page| events.extend_from_slice(page)).await?;
+        Ok(events)
+    }
+
+    /// Passes every page of events to `on_page` as it is fetched, in block order, and returns the
+    /// total number of events. When resuming from a checkpoint, pages before it are not passed
+    /// again.
+    pub async fn run_with(&self, mut on_page: impl FnMut(&[EmittedEvent<Felt>])) -> Result<u64, EventBackfillError> {
+        if self.from_block > self.to_block {
+            return Err(EventBackfillError::InvalidRange { from_block: self.from_block, to_block: self.to_block });
+        }
+
+        let mut checkpoint = match self.load_checkpoint()? {
+            Some(checkpoint) => checkpoint,
+            None => BackfillCheckpoint { segment_start: self.from_block, continuation_token: None, events_fetched: 0 },
+        };
+
+        while checkpoint.segment_start <= self.to_block {
+            let segment_end = checkpoint.segment_start.saturating_add(self.blocks_per_query - 1).min(self.to_block);
+            let chunk = self.get_events_with_retry(checkpoint.segment_start, segment_end, &checkpoint).await?;
+
+            on_page(&chunk.events);
+            checkpoint.events_fetched += chunk.events.len() as u64;
+            match chunk.continuation_token {
+                Some(token) => checkpoint.continuation_token = Some(token),
+                None => {
+                    checkpoint.continuation_token = None;
+                    checkpoint.segment_start = match segment_end.checked_add(1) {
+                        Some(next) => next,
+                        None => break,
+                    };
+                }
+            }
+            self.save_checkpoint(&checkpoint)?;
+        }
+
+        self.remove_checkpoint()?;
+        Ok(checkpoint.events_fetched)
+    }
+
+    async fn get_events_with_retry(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        checkpoint: &BackfillCheckpoint,
+    ) -> Result<starknet_types_rpc::EventsChunk<Felt>, EventBackfillError> {
+        let filter = EventFilterWithPageRequest {
+            address: self.address,
+            from_block: Some(BlockId::Number(from_block)),
+            to_block: Some(BlockId::Number(to_block)),
+            keys: self.keys.clone(),
+            chunk_size: self.chunk_size,
+            continuation_token: checkpoint.continuation_token.clone(),
+        };
+
+        let mut delay = self.retry_delay;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match self.provider.get_events(filter.clone()).await {
+                Ok(chunk) => return Ok(chunk),
+                Err(e @ (ProviderError::RateLimited | ProviderError::Other(_))) if attempts <= self.max_retries => {
+                    warn!("getEvents for blocks {}..={} failed, retrying in {:?}: {}", from_block, to_block, delay, e);
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                Err(source) => return Err(EventBackfillError::Provider { from_block, to_block, attempts, source }),
+            }
+        }
+    }
+
+    fn load_checkpoint(&self) -> Result<Option<BackfillCheckpoint>, EventBackfillError> {
+        let Some(path) = &self.checkpoint_path else {
+            return Ok(None);
+        };
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => return Err(EventBackfillError::Checkpoint { path: path.clone(), source }),
+        };
+        let checkpoint: BackfillCheckpoint = serde_json::from_str(&content)
+            .map_err(|e| EventBackfillError::Checkpoint { path: path.clone(), source: e.into() })?;
+
+        // A checkpoint of another range would silently skip or repeat blocks.
+        if checkpoint.segment_start < self.from_block || checkpoint.segment_start > self.to_block {
+            let message = format!("segment start {} is outside the backfilled range", checkpoint.segment_start);
+            return Err(EventBackfillError::Checkpoint {
+                path: path.clone(),
+                source: io::Error::new(io::ErrorKind::InvalidData, message),
+            });
+        }
+        Ok(Some(checkpoint))
+    }
+
+    fn save_checkpoint(&self, checkpoint: &BackfillCheckpoint) -> Result<(), EventBackfillError> {
+        let Some(path) = &self.checkpoint_path else {
+            return Ok(());
+        };
+        let content = serde_json::to_string(checkpoint)
+            .map_err(|e| EventBackfillError::Checkpoint { path: path.clone(), source: e.into() })?;
+        fs::write(path, content).map_err(|source| EventBackfillError::Checkpoint { path: path.clone(), source })
+    }
+
+    fn remove_checkpoint(&self) -> Result<(), EventBackfillError> {
+        match &self.checkpoint_path {
+            Some(path) => match fs::remove_file(path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    Err(EventBackfillError::Checkpoint { path: path.clone(), source: e })
+                }
+                _ => Ok(()),
+            },
+            None => Ok(()),
+        }
+    }
+}
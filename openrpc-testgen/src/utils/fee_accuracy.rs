@@ -0,0 +1,195 @@
+//! Fee estimate accuracy tracking: once enabled, the fee estimate every transaction was sent with
+//! is recorded, and at the end of the run the estimates are compared with the fees actually charged
+//! in the receipts, per transaction type. Transactions sent with explicit fee settings are not
+//! estimated, so they are not recorded.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{v0_7_1::FeeEstimate, TxnReceipt};
+use tracing::{info, warn};
+
+use super::secrets::write_redacted;
+use super::v7::providers::provider::Provider;
+
+/// Upper bounds of the `actual_fee / estimate` buckets, the last bucket is unbounded.
+const RATIO_BUCKETS: [f64; 6] = [0.5, 0.8, 0.95, 1.05, 1.25, 2.0];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionKind {
+    InvokeV1,
+    InvokeV3,
+    DeclareV2,
+    DeclareV3,
+    DeployAccountV1,
+    DeployAccountV3,
+}
+
+#[derive(Debug, Clone)]
+struct FeeRecord {
+    kind: TransactionKind,
+    transaction_hash: Felt,
+    estimate: Felt,
+}
+
+static FEE_RECORDS: OnceLock<Mutex<Vec<FeeRecord>>> = OnceLock::new();
+
+/// Starts recording fee estimates. Returns `false` if tracking was already enabled.
+pub fn enable_fee_accuracy_tracking() -> bool {
+    FEE_RECORDS.set(Mutex::new(Vec::new())).is_ok()
+}
+
+/// Records the estimate a transaction was sent with, does nothing unless tracking is enabled.
+pub fn record_fee_estimate(kind: TransactionKind, transaction_hash: Felt, fee_estimate: &FeeEstimate<Felt>) {
+    if let Some(records) = FEE_RECORDS.get() {
+        let record = FeeRecord { kind, transaction_hash, estimate: fee_estimate.overall_fee };
+        records.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(record);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeSample {
+    pub transaction_hash: Felt,
+    pub estimate: Felt,
+    pub actual_fee: Felt,
+    /// `actual_fee / estimate`, below 1 when the fee was overestimated.
+    pub ratio: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RatioBucket {
+    /// Upper bound of the ratios in the bucket, `None` for the last one.
+    pub below: Option<f64>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeAccuracyStats {
+    pub transactions: u64,
+    /// Transactions whose receipt could not be fetched, or whose fees don't fit a ratio.
+    pub skipped: u64,
+    pub mean_ratio: Option<f64>,
+    pub buckets: Vec<RatioBucket>,
+    pub worst_overestimate: Option<FeeSample>,
+    pub worst_underestimate: Option<FeeSample>,
+}
+
+impl Default for FeeAccuracyStats {
+    fn default() -> Self {
+        let buckets = RATIO_BUCKETS
+            .iter()
+            .map(|&below| Some(below))
+            .chain([None])
+            .map(|below| RatioBucket { below, count: 0 })
+            .collect();
+        Self {
+            transactions: 0,
+            skipped: 0,
+            mean_ratio: None,
+            buckets,
+            worst_overestimate: None,
+            worst_underestimate: None,
+        }
+    }
+}
+
+impl FeeAccuracyStats {
+    fn add(&mut self, sample: FeeSample) {
+        let bucket = RATIO_BUCKETS.iter().position(|&below| sample.ratio < below).unwrap_or(RATIO_BUCKETS.len());
+        self.buckets[bucket].count += 1;
+
+        let sampled = self.transactions - self.skipped;
+        let mean = self.mean_ratio.unwrap_or(0.0);
+        self.mean_ratio = Some(mean + (sample.ratio - mean) / (sampled + 1) as f64);
+        self.transactions += 1;
+
+        if sample.ratio < 1.0 && self.worst_overestimate.as_ref().is_none_or(|worst| sample.ratio < worst.ratio) {
+            self.worst_overestimate = Some(sample.clone());
+        }
+        if sample.ratio > 1.0 && self.worst_underestimate.as_ref().is_none_or(|worst| sample.ratio > worst.ratio) {
+            self.worst_underestimate = Some(sample);
+        }
+    }
+
+    fn skip(&mut self) {
+        self.transactions += 1;
+        self.skipped += 1;
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FeeAccuracyReport {
+    pub by_kind: BTreeMap<TransactionKind, FeeAccuracyStats>,
+}
+
+impl FeeAccuracyReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        for (kind, stats) in &self.by_kind {
+            info!(
+                "Fee accuracy of {:?}: {} transactions, {} skipped, mean actual/estimate ratio {}",
+                kind,
+                stats.transactions,
+                stats.skipped,
+                stats.mean_ratio.map_or("n/a".to_string(), |ratio| format!("{ratio:.3}"))
+            );
+            if let Some(worst) = &stats.worst_overestimate {
+                info!("  Worst overestimate: {:#x}, ratio {:.3}", worst.transaction_hash, worst.ratio);
+            }
+            if let Some(worst) = &stats.worst_underestimate {
+                info!("  Worst underestimate: {:#x}, ratio {:.3}", worst.transaction_hash, worst.ratio);
+            }
+        }
+    }
+}
+
+/// Fetches the receipts of the recorded transactions and compares their actual fee with the
+/// estimate. Returns `None` unless tracking is enabled.
+pub async fn fee_accuracy_report<P: Provider>(provider: &P) -> Option<FeeAccuracyReport> {
+    let records = FEE_RECORDS.get()?.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+
+    let mut report = FeeAccuracyReport::default();
+    for record in records {
+        let stats = report.by_kind.entry(record.kind).or_default();
+        let actual_fee = match provider.get_transaction_receipt(record.transaction_hash).await {
+            Ok(receipt) => actual_fee(&receipt),
+            Err(e) => {
+                warn!("Could not fetch the receipt of {:#x}: {}", record.transaction_hash, e);
+                stats.skip();
+                continue;
+            }
+        };
+        match (to_f64(actual_fee), to_f64(record.estimate)) {
+            (Some(actual), Some(estimate)) if estimate > 0.0 => stats.add(FeeSample {
+                transaction_hash: record.transaction_hash,
+                estimate: record.estimate,
+                actual_fee,
+                ratio: actual / estimate,
+            }),
+            _ => stats.skip(),
+        }
+    }
+    Some(report)
+}
+
+fn actual_fee(receipt: &TxnReceipt<Felt>) -> Felt {
+    match receipt {
+        TxnReceipt::Declare(receipt) => receipt.common_receipt_properties.actual_fee.amount,
+        TxnReceipt::Deploy(receipt) => receipt.common_receipt_properties.actual_fee.amount,
+        TxnReceipt::DeployAccount(receipt) => receipt.common_receipt_properties.actual_fee.amount,
+        TxnReceipt::Invoke(receipt) => receipt.common_receipt_properties.actual_fee.amount,
+        TxnReceipt::L1Handler(receipt) => receipt.common_receipt_properties.actual_fee.amount,
+    }
+}
+
+fn to_f64(value: Felt) -> Option<f64> {
+    u128::try_from(value).ok().map(|value| value as f64)
+}
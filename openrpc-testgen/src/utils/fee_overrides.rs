@@ -0,0 +1,49 @@
+//! Run-wide defaults of the fee settings of the transaction builders. Runs against congested
+//! networks can raise the estimate multipliers without touching the test cases, and fixed fee
+//! settings make every transaction of a run use deliberately wrong bounds. Settings passed to a
+//! builder explicitly still take precedence.
+
+use std::sync::OnceLock;
+
+use starknet_types_core::felt::Felt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeOverrides {
+    /// Multiplier of the estimated fee of V1 and V2 transactions.
+    pub fee_estimate_multiplier: f64,
+    /// Multiplier of the estimated gas of V3 transactions.
+    pub gas_estimate_multiplier: f64,
+    /// Multiplier of the estimated gas price of V3 transactions.
+    pub gas_price_estimate_multiplier: f64,
+    /// `max_fee` of V1 and V2 transactions, skipping their fee estimation.
+    pub max_fee: Option<Felt>,
+    /// Gas of V3 transactions, skipping their fee estimation.
+    pub gas: Option<u64>,
+    /// Gas price of V3 transactions.
+    pub gas_price: Option<u128>,
+}
+
+impl Default for FeeOverrides {
+    fn default() -> Self {
+        Self {
+            fee_estimate_multiplier: 1.1,
+            gas_estimate_multiplier: 1.5,
+            gas_price_estimate_multiplier: 1.5,
+            max_fee: None,
+            gas: None,
+            gas_price: None,
+        }
+    }
+}
+
+static FEE_OVERRIDES: OnceLock<FeeOverrides> = OnceLock::new();
+
+/// Returns `false` if the overrides have already been set.
+pub fn set_fee_overrides(overrides: FeeOverrides) -> bool {
+    FEE_OVERRIDES.set(overrides).is_ok()
+}
+
+/// Fee settings new builders start from, the defaults unless overrides were set.
+pub fn fee_overrides() -> FeeOverrides {
+    FEE_OVERRIDES.get().copied().unwrap_or_default()
+}
@@ -0,0 +1,136 @@
+//! Minimal fee bounds: [minimal_bound] binary-searches the smallest bound at which a transaction is
+//! still accepted, and the `minimal_fee_bounds` test case uses it with simulations to find the
+//! smallest L1 gas bound of a few invokes. Compared with the node's fee estimate, it tells wallets
+//! how much headroom the estimates of a node carry, or whether they fall short.
+
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use tracing::{info, warn};
+
+use super::secrets::write_redacted;
+
+static RESULTS: OnceLock<Mutex<Vec<FeeSearchResult>>> = OnceLock::new();
+
+/// Starts recording fee searches. Returns `false` if the search was already enabled.
+pub fn enable_fee_search() -> bool {
+    RESULTS.set(Mutex::new(Vec::new())).is_ok()
+}
+
+pub fn is_fee_search_enabled() -> bool {
+    RESULTS.get().is_some()
+}
+
+/// Smallest bound within `low..=high` that `accepts`, assuming any bound above an accepted one is
+/// accepted too. Returns `None` if even `high` is not accepted.
+pub async fn minimal_bound<F, Fut, E>(mut low: u64, mut high: u64, mut accepts: F) -> Result<Option<u64>, E>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<bool, E>>,
+{
+    if low > high || !accepts(high).await? {
+        return Ok(None);
+    }
+    // `high` is accepted throughout the search.
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if accepts(middle).await? {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    Ok(Some(high))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeSearchResult {
+    /// Name of the searched transaction.
+    pub transaction: String,
+    pub gas_price: u128,
+    /// Gas of the node's fee estimate, without margin.
+    pub estimated_gas: u64,
+    /// Smallest gas bound the node accepted, `None` if none up to the searched maximum.
+    pub minimal_gas: Option<u64>,
+    /// How many times the minimal bound the estimate is, below 1 when the estimate falls short.
+    pub estimate_ratio: Option<f64>,
+}
+
+impl FeeSearchResult {
+    pub fn new(transaction: String, gas_price: u128, estimated_gas: u64, minimal_gas: Option<u64>) -> Self {
+        let estimate_ratio = minimal_gas.map(|minimal_gas| estimated_gas as f64 / minimal_gas.max(1) as f64);
+        Self { transaction, gas_price, estimated_gas, minimal_gas, estimate_ratio }
+    }
+}
+
+/// Records a search, does nothing unless the search is enabled.
+pub fn record_fee_search(result: FeeSearchResult) {
+    if let Some(results) = RESULTS.get() {
+        results.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(result);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeSearchReport {
+    pub results: Vec<FeeSearchResult>,
+}
+
+impl FeeSearchReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        for result in &self.results {
+            match (result.minimal_gas, result.estimate_ratio) {
+                (Some(minimal_gas), Some(ratio)) if ratio < 1.0 => warn!(
+                    "{}: estimated gas {} is below the minimal bound {} ({:.3}x)",
+                    result.transaction, result.estimated_gas, minimal_gas, ratio
+                ),
+                (Some(minimal_gas), Some(ratio)) => info!(
+                    "{}: estimated gas {}, minimal bound {} ({:.3}x)",
+                    result.transaction, result.estimated_gas, minimal_gas, ratio
+                ),
+                _ => warn!("{}: no gas bound was accepted, estimated gas {}", result.transaction, result.estimated_gas),
+            }
+        }
+    }
+}
+
+/// Returns `None` unless the search is enabled.
+pub fn fee_search_report() -> Option<FeeSearchReport> {
+    let results = RESULTS.get()?.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    Some(FeeSearchReport { results })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::*;
+
+    fn search(low: u64, high: u64, threshold: u64) -> (Option<u64>, usize) {
+        let mut attempts = 0;
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let bound = runtime
+            .block_on(minimal_bound(low, high, |bound| {
+                attempts += 1;
+                async move { Ok::<_, Infallible>(bound >= threshold) }
+            }))
+            .unwrap();
+        (bound, attempts)
+    }
+
+    #[test]
+    fn finds_the_minimal_accepted_bound() {
+        assert_eq!(search(1, 1_000_000, 12_345).0, Some(12_345));
+        assert_eq!(search(1, 1_000_000, 1).0, Some(1));
+        assert_eq!(search(1, 1_000_000, 1_000_000).0, Some(1_000_000));
+        assert_eq!(search(1, 1_000, 1_001), (None, 1));
+        // Logarithmic in the width of the range, plus the check of `high`.
+        assert!(search(1, 1 << 20, 777).1 <= 22);
+    }
+}
@@ -0,0 +1,96 @@
+//! Finality progression: the `finality_progression` test case follows a transaction from
+//! `ACCEPTED_ON_L2` to `ACCEPTED_ON_L1`, which only happens once its block is settled on L1. That
+//! takes hours on public networks, and never happens on a devnet unless settlement is simulated, so
+//! the case is opt-in and only runs once [set_finality_config] was called.
+
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::json;
+use starknet_types_core::felt::Felt;
+use tracing::info;
+use url::Url;
+
+use super::secrets::write_redacted;
+use super::v7::endpoints::errors::OpenRpcTestGenError;
+use super::v7::providers::jsonrpc::transports::shared_client;
+
+#[derive(Debug, Clone)]
+pub struct FinalityConfig {
+    /// How long the transaction may take to reach `ACCEPTED_ON_L1` once accepted on L2.
+    pub timeout: Duration,
+    pub poll_interval: Duration,
+    /// Devnet asked to mark the block of the transaction as accepted on L1, instead of waiting for
+    /// a real settlement.
+    pub simulated_settlement: Option<Url>,
+}
+
+static FINALITY_CONFIG: OnceLock<FinalityConfig> = OnceLock::new();
+static FINALITY_TIMINGS: Mutex<Vec<FinalityTiming>> = Mutex::new(Vec::new());
+
+/// Enables the finality progression test case. Returns `false` if it was already enabled.
+pub fn set_finality_config(config: FinalityConfig) -> bool {
+    FINALITY_CONFIG.set(config).is_ok()
+}
+
+pub fn finality_config() -> Option<&'static FinalityConfig> {
+    FINALITY_CONFIG.get()
+}
+
+/// Time a transaction took to reach each finality status, from its submission.
+#[derive(Debug, Clone, Serialize)]
+pub struct FinalityTiming {
+    pub transaction_hash: Felt,
+    pub accepted_on_l2_ms: u64,
+    pub accepted_on_l1_ms: u64,
+}
+
+pub fn record_finality_timing(timing: FinalityTiming) {
+    FINALITY_TIMINGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(timing);
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FinalityReport {
+    pub transactions: Vec<FinalityTiming>,
+}
+
+impl FinalityReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        for timing in &self.transactions {
+            info!(
+                "Transaction {:#x} accepted on L2 after {} ms, on L1 after {} ms",
+                timing.transaction_hash, timing.accepted_on_l2_ms, timing.accepted_on_l1_ms
+            );
+        }
+    }
+}
+
+/// Returns `None` unless the finality progression test case is enabled.
+pub fn finality_report() -> Option<FinalityReport> {
+    finality_config()?;
+    let transactions = FINALITY_TIMINGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    Some(FinalityReport { transactions })
+}
+
+/// Has the devnet mark the block with `block_hash`, and the ones before it, as accepted on L1.
+pub async fn accept_on_l1(devnet_url: &Url, block_hash: Felt) -> Result<(), OpenRpcTestGenError> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "devnet_acceptOnL1",
+        "params": { "starting_block_id": { "block_hash": block_hash } },
+    });
+    let response: serde_json::Value =
+        shared_client().post(devnet_url.clone()).json(&request).send().await?.json().await?;
+    if let Some(error) = response.get("error") {
+        return Err(OpenRpcTestGenError::Other(format!("devnet_acceptOnL1 failed: {error}")));
+    }
+    Ok(())
+}
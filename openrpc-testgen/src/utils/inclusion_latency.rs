@@ -0,0 +1,148 @@
+//! Inclusion latency tracking: once enabled, the time from the acceptance of an `add_*_transaction`
+//! request to the first time the transaction is seen `ACCEPTED_ON_L2` is recorded for every
+//! transaction of the run. Inclusion is observed while waiting for the transaction, so latencies
+//! have the resolution of the polling, and transactions nobody waited for are only counted.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use starknet_types_core::felt::Felt;
+use tracing::info;
+
+use super::secrets::write_redacted;
+
+/// Upper bounds of the histogram buckets in milliseconds, the last bucket is unbounded.
+const BUCKETS_MS: [u64; 7] = [500, 1_000, 2_000, 5_000, 10_000, 30_000, 60_000];
+/// Number of the slowest transactions listed in the report.
+const OUTLIERS: usize = 10;
+
+#[derive(Debug, Default)]
+struct LatencyState {
+    submitted: HashMap<Felt, Instant>,
+    included: HashMap<Felt, Duration>,
+}
+
+static LATENCIES: OnceLock<Mutex<LatencyState>> = OnceLock::new();
+
+/// Starts recording inclusion latencies. Returns `false` if tracking was already enabled.
+pub fn enable_inclusion_latency_tracking() -> bool {
+    LATENCIES.set(Mutex::new(LatencyState::default())).is_ok()
+}
+
+/// Records that the node accepted the transaction, does nothing unless tracking is enabled.
+pub fn record_submission(transaction_hash: Felt) {
+    if let Some(state) = LATENCIES.get() {
+        state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .submitted
+            .insert(transaction_hash, Instant::now());
+    }
+}
+
+/// Records that the transaction was seen `ACCEPTED_ON_L2`, only the first observation counts.
+pub fn record_accepted_on_l2(transaction_hash: Felt) {
+    if let Some(state) = LATENCIES.get() {
+        let mut state = state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(submitted) = state.submitted.get(&transaction_hash).copied() {
+            state.included.entry(transaction_hash).or_insert_with(|| submitted.elapsed());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyBucket {
+    /// Upper bound of the latencies in the bucket, `None` for the last one.
+    pub below_ms: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyOutlier {
+    pub transaction_hash: Felt,
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InclusionLatencyReport {
+    pub submitted: u64,
+    /// Submitted transactions that were never seen `ACCEPTED_ON_L2`.
+    pub unobserved: u64,
+    pub p50_ms: Option<u64>,
+    pub p90_ms: Option<u64>,
+    pub p99_ms: Option<u64>,
+    pub max_ms: Option<u64>,
+    pub histogram: Vec<LatencyBucket>,
+    /// Slowest transactions first.
+    pub outliers: Vec<LatencyOutlier>,
+}
+
+impl InclusionLatencyReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        let format_ms = |ms: Option<u64>| ms.map_or("n/a".to_string(), |ms| format!("{ms} ms"));
+        info!(
+            "Inclusion latency of {} transactions ({} unobserved): p50 {}, p90 {}, p99 {}, max {}",
+            self.submitted,
+            self.unobserved,
+            format_ms(self.p50_ms),
+            format_ms(self.p90_ms),
+            format_ms(self.p99_ms),
+            format_ms(self.max_ms)
+        );
+        for outlier in &self.outliers {
+            info!("  {:#x}: {} ms", outlier.transaction_hash, outlier.latency_ms);
+        }
+    }
+}
+
+/// Returns `None` unless tracking is enabled.
+pub fn inclusion_latency_report() -> Option<InclusionLatencyReport> {
+    let state = LATENCIES.get()?.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let mut latencies: Vec<(Felt, u64)> =
+        state.included.iter().map(|(hash, latency)| (*hash, latency.as_millis() as u64)).collect();
+    latencies.sort_by_key(|(_, latency_ms)| std::cmp::Reverse(*latency_ms));
+
+    let mut histogram: Vec<LatencyBucket> = BUCKETS_MS
+        .iter()
+        .map(|&below_ms| Some(below_ms))
+        .chain([None])
+        .map(|below_ms| LatencyBucket { below_ms, count: 0 })
+        .collect();
+    for (_, latency_ms) in &latencies {
+        let bucket = BUCKETS_MS.iter().position(|&below_ms| *latency_ms < below_ms).unwrap_or(BUCKETS_MS.len());
+        histogram[bucket].count += 1;
+    }
+
+    // `latencies` is sorted slowest first.
+    let percentile = |percent: usize| {
+        let slower = latencies.len() * (100 - percent) / 100;
+        latencies.get(slower.min(latencies.len().saturating_sub(1))).map(|(_, latency_ms)| *latency_ms)
+    };
+
+    Some(InclusionLatencyReport {
+        submitted: state.submitted.len() as u64,
+        unobserved: (state.submitted.len() - state.included.len()) as u64,
+        p50_ms: percentile(50),
+        p90_ms: percentile(90),
+        p99_ms: percentile(99),
+        max_ms: latencies.first().map(|(_, latency_ms)| *latency_ms),
+        histogram,
+        outliers: latencies
+            .iter()
+            .take(OUTLIERS)
+            .map(|(transaction_hash, latency_ms)| LatencyOutlier {
+                transaction_hash: *transaction_hash,
+                latency_ms: *latency_ms,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,133 @@
+//! L1 side of the L1 <-> L2 messaging test cases: the Ethereum node, the Starknet core contract
+//! the L2 node settles messages on, and the devnet postman relaying them. Test cases that need L1
+//! check [l1_messaging_config] and only verify the L1 side when a stack is configured.
+
+use std::fmt::Write;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+use starknet_types_core::felt::Felt;
+use url::Url;
+
+use super::v7::endpoints::errors::OpenRpcTestGenError;
+use super::v7::providers::jsonrpc::transports::shared_client;
+
+/// First of the accounts Anvil and Hardhat unlock by default, so it can send transactions without
+/// a signature.
+pub const ANVIL_DEFAULT_ACCOUNT: Felt = Felt::from_hex_unchecked("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+
+#[derive(Debug, Clone)]
+pub struct L1MessagingConfig {
+    /// Ethereum JSON-RPC endpoint.
+    pub l1_url: Url,
+    /// Address of the Starknet core contract, or of its mock, on L1.
+    pub messaging_contract: String,
+    /// Devnet whose `postman` endpoints relay messages between both layers.
+    pub postman_url: Url,
+}
+
+static L1_MESSAGING_CONFIG: OnceLock<L1MessagingConfig> = OnceLock::new();
+
+/// Returns `false` if the configuration has already been set.
+pub fn set_l1_messaging_config(config: L1MessagingConfig) -> bool {
+    L1_MESSAGING_CONFIG.set(config).is_ok()
+}
+
+pub fn l1_messaging_config() -> Option<&'static L1MessagingConfig> {
+    L1_MESSAGING_CONFIG.get()
+}
+
+/// Has the postman relay the pending messages in both directions, returning its report of the
+/// flushed `messages_to_l1`, `messages_to_l2` and `generated_l2_transactions`.
+pub async fn flush_messages(config: &L1MessagingConfig) -> Result<Value, OpenRpcTestGenError> {
+    let url = config.postman_url.join("postman/flush")?;
+    Ok(shared_client().post(url).send().await?.error_for_status()?.json().await?)
+}
+
+/// Hash the core contract identifies an L2 -> L1 message by:
+/// `keccak256(from_address, to_address, payload.len(), payload...)`, each as a 32 bytes word.
+pub fn l2_to_l1_message_hash(from_address: Felt, to_address: Felt, payload: &[Felt]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(from_address.to_bytes_be());
+    hasher.update(to_address.to_bytes_be());
+    hasher.update(Felt::from(payload.len()).to_bytes_be());
+    for word in payload {
+        hasher.update(word.to_bytes_be());
+    }
+    hasher.finalize().into()
+}
+
+/// Number of copies of the message with `message_hash` that reached L1 and were not consumed yet.
+pub async fn pending_l2_to_l1_messages(
+    config: &L1MessagingConfig,
+    message_hash: [u8; 32],
+) -> Result<Felt, OpenRpcTestGenError> {
+    let mut data = function_selector("l2ToL1Messages(bytes32)").to_vec();
+    data.extend(message_hash);
+    let call = json!({ "to": config.messaging_contract, "data": to_hex(&data) });
+    let result = l1_request(config, "eth_call", json!([call, "latest"])).await?;
+    let result =
+        result.as_str().ok_or_else(|| OpenRpcTestGenError::Other(format!("Unexpected eth_call result {result}")))?;
+    Ok(Felt::from_hex(result)?)
+}
+
+/// Consumes the message from `from_address` on L1, as `consumeMessageFromL2` of the core contract
+/// requires, from its recipient `to_address`, which must be an account unlocked on the L1 node.
+pub async fn consume_message_from_l2(
+    config: &L1MessagingConfig,
+    from_address: Felt,
+    to_address: Felt,
+    payload: &[Felt],
+) -> Result<(), OpenRpcTestGenError> {
+    let mut data = function_selector("consumeMessageFromL2(uint256,uint256[])").to_vec();
+    data.extend(from_address.to_bytes_be());
+    // Offset of the dynamic payload array, right after both head words.
+    data.extend(Felt::from(64).to_bytes_be());
+    data.extend(Felt::from(payload.len()).to_bytes_be());
+    for word in payload {
+        data.extend(word.to_bytes_be());
+    }
+
+    // L1 addresses are the last 20 bytes of the word.
+    let sender = to_hex(&to_address.to_bytes_be()[12..]);
+    let transaction = json!({ "from": sender, "to": config.messaging_contract, "data": to_hex(&data) });
+    let transaction_hash = l1_request(config, "eth_sendTransaction", json!([transaction])).await?;
+
+    for _ in 0..30 {
+        let receipt = l1_request(config, "eth_getTransactionReceipt", json!([transaction_hash])).await?;
+        match receipt.get("status").and_then(Value::as_str) {
+            Some("0x1") => return Ok(()),
+            Some(status) => {
+                return Err(OpenRpcTestGenError::Other(format!(
+                    "consumeMessageFromL2 {transaction_hash} failed on L1 with status {status}"
+                )))
+            }
+            None => tokio::time::sleep(Duration::from_secs(1)).await,
+        }
+    }
+    Err(OpenRpcTestGenError::Timeout(format!("consumeMessageFromL2 {transaction_hash} not mined on L1")))
+}
+
+async fn l1_request(config: &L1MessagingConfig, method: &str, params: Value) -> Result<Value, OpenRpcTestGenError> {
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let mut response: Value = shared_client().post(config.l1_url.clone()).json(&request).send().await?.json().await?;
+    if let Some(error) = response.get("error") {
+        return Err(OpenRpcTestGenError::Other(format!("L1 {method} failed: {error}")));
+    }
+    Ok(response["result"].take())
+}
+
+fn function_selector(signature: &str) -> [u8; 4] {
+    let hash = Keccak256::digest(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::from("0x");
+    for byte in bytes {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
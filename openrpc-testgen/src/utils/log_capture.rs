@@ -0,0 +1,173 @@
+//! Log capture: the generated suite code runs every test case in a [TEST_CASE_SPAN] span, with the
+//! suite and the case as fields. Once [LogCaptureLayer] is added to the subscriber, it keeps the
+//! events logged within such a span, along with the fields of the spans nested in it, such as the
+//! hash of the transaction being waited for. The generated code takes them with
+//! [take_captured_logs] and records them with the outcome of the case, so the logs of a failing
+//! case can be read in its report entry rather than in the log of the whole run.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+use std::sync::Mutex;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Name of the span every test case runs in.
+pub const TEST_CASE_SPAN: &str = "test_case";
+
+/// Captured lines by the id of their test case span.
+static CAPTURED: Mutex<BTreeMap<u64, Vec<String>>> = Mutex::new(BTreeMap::new());
+
+/// Layer capturing the events logged within [TEST_CASE_SPAN] spans.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogCaptureLayer;
+
+/// Fields of a span, formatted when it is created or recorded to.
+struct SpanFields(String);
+
+impl<S> Layer<S> for LogCaptureLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() == TEST_CASE_SPAN {
+            captured().insert(id.into_u64(), Vec::new());
+        }
+        let mut fields = FieldFormatter::default();
+        attrs.record(&mut fields);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(fields.fields));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut fields = FieldFormatter::default();
+        values.record(&mut fields);
+        if let Some(span) = ctx.span(id) {
+            if let Some(SpanFields(existing)) = span.extensions_mut().get_mut::<SpanFields>() {
+                existing.push_str(&fields.fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(scope) = ctx.event_scope(event) else {
+            return;
+        };
+        // Spans from the root down to the event, the line goes to the innermost test case span.
+        let spans: Vec<_> = scope.from_root().collect();
+        let Some(start) = spans.iter().rposition(|span| span.name() == TEST_CASE_SPAN) else {
+            return;
+        };
+
+        let mut line = format!("{} ", event.metadata().level());
+        for span in &spans[start + 1..] {
+            let extensions = span.extensions();
+            let fields = extensions.get::<SpanFields>().map_or("", |SpanFields(fields)| fields.trim_start());
+            let _ = write!(line, "{}{{{}}}: ", span.name(), fields);
+        }
+        let mut fields = FieldFormatter::default();
+        event.record(&mut fields);
+        line.push_str(&strip_ansi(&fields.message));
+        line.push_str(&fields.fields);
+
+        if let Some(lines) = captured().get_mut(&spans[start].id().into_u64()) {
+            lines.push(line);
+        }
+    }
+
+    fn on_close(&self, id: Id, _ctx: Context<'_, S>) {
+        captured().remove(&id.into_u64());
+    }
+}
+
+/// Takes the lines captured so far within the test case span with `id`.
+pub fn take_captured_logs(id: &Id) -> Vec<String> {
+    captured().get_mut(&id.into_u64()).map(std::mem::take).unwrap_or_default()
+}
+
+fn captured() -> std::sync::MutexGuard<'static, BTreeMap<u64, Vec<String>>> {
+    CAPTURED.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Default)]
+struct FieldFormatter {
+    message: String,
+    /// Fields other than the message, each prefixed with a space.
+    fields: String,
+}
+
+impl Visit for FieldFormatter {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Removes the color codes test cases decorate their messages with.
+fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skips the parameters up to the final byte of the sequence, e.g. `m`.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::{info, info_span, Instrument};
+    use tracing_subscriber::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn captures_events_within_test_case_spans() {
+        let subscriber = tracing_subscriber::registry().with(LogCaptureLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            let span = info_span!("test_case", suite = "openrpc", case = "get_nonce");
+            {
+                let _entered = span.enter();
+                info!("\x1b[32m✓ passed\x1b[0m");
+                let _waiting = info_span!("wait_for_transaction", transaction_hash = "0x1").entered();
+                info!(attempt = 2, "Retrying");
+            }
+            info!("outside of any test case");
+
+            let logs = take_captured_logs(&span.id().unwrap());
+            assert_eq!(
+                logs,
+                vec!["INFO ✓ passed", "INFO wait_for_transaction{transaction_hash=0x1}: Retrying attempt=2"]
+            );
+            assert!(take_captured_logs(&span.id().unwrap()).is_empty());
+
+            // Futures instrumented with the span are captured as well.
+            let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+            runtime.block_on(async { info!("instrumented") }.instrument(span.clone()));
+            assert_eq!(take_captured_logs(&span.id().unwrap()), vec!["INFO instrumented"]);
+        });
+    }
+}
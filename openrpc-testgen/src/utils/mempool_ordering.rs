@@ -0,0 +1,123 @@
+//! Mempool ordering: the `mempool_ordering` test case submits transactions from distinct accounts
+//! with increasing tips, lowest first, and records where the sequencer included them. A sequencer
+//! prioritizing by fee includes them highest tip first, one serving its mempool in arrival order
+//! lowest tip first. Which one a node does is only recorded, the fee market has to be active for
+//! tips to matter at all.
+
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use starknet_types_core::felt::Felt;
+use tracing::info;
+
+use super::secrets::write_redacted;
+
+static OBSERVATIONS: OnceLock<Mutex<Vec<OrderingObservation>>> = OnceLock::new();
+
+/// Starts recording inclusion orders. Returns `false` if tracking was already enabled.
+pub fn enable_ordering_tracking() -> bool {
+    OBSERVATIONS.set(Mutex::new(Vec::new())).is_ok()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderedTransaction {
+    pub transaction_hash: Felt,
+    pub sender_address: Felt,
+    pub tip: u64,
+    pub gas_price: u128,
+    /// Position in which the transaction was submitted.
+    pub submission_index: usize,
+    pub block_number: u64,
+    /// Position of the transaction in its block.
+    pub block_index: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderingObservation {
+    /// In inclusion order.
+    pub transactions: Vec<OrderedTransaction>,
+    /// Whether all transactions made it into the same block, their order within a block says the
+    /// most about the sequencer.
+    pub same_block: bool,
+    /// Whether transactions with higher tips were included first.
+    pub by_priority: bool,
+    /// Whether transactions were included in the order they were submitted.
+    pub by_submission: bool,
+}
+
+impl OrderingObservation {
+    pub fn new(mut transactions: Vec<OrderedTransaction>) -> Self {
+        transactions.sort_by_key(|transaction| (transaction.block_number, transaction.block_index));
+        let same_block = transactions.windows(2).all(|pair| pair[0].block_number == pair[1].block_number);
+        let by_priority = transactions.windows(2).all(|pair| pair[0].tip >= pair[1].tip);
+        let by_submission = transactions.windows(2).all(|pair| pair[0].submission_index < pair[1].submission_index);
+        Self { transactions, same_block, by_priority, by_submission }
+    }
+}
+
+/// Records an observation, does nothing unless tracking is enabled.
+pub fn record_ordering(observation: OrderingObservation) {
+    if let Some(observations) = OBSERVATIONS.get() {
+        observations.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(observation);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderingReport {
+    pub observations: Vec<OrderingObservation>,
+}
+
+impl OrderingReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        for observation in &self.observations {
+            let tips: Vec<String> = observation.transactions.iter().map(|tx| tx.tip.to_string()).collect();
+            info!(
+                "Transactions included with tips [{}]{}: by priority {}, by submission {}",
+                tips.join(", "),
+                if observation.same_block { " in one block" } else { " across blocks" },
+                observation.by_priority,
+                observation.by_submission
+            );
+        }
+    }
+}
+
+/// Returns `None` unless tracking is enabled.
+pub fn ordering_report() -> Option<OrderingReport> {
+    let observations = OBSERVATIONS.get()?.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    Some(OrderingReport { observations })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(tip: u64, submission_index: usize, block_number: u64, block_index: usize) -> OrderedTransaction {
+        OrderedTransaction {
+            transaction_hash: Felt::from(submission_index),
+            sender_address: Felt::from(submission_index),
+            tip,
+            gas_price: 1,
+            submission_index,
+            block_number,
+            block_index,
+        }
+    }
+
+    #[test]
+    fn classifies_inclusion_order() {
+        let by_priority =
+            OrderingObservation::new(vec![transaction(0, 0, 7, 2), transaction(10, 1, 7, 0), transaction(5, 2, 7, 1)]);
+        assert!(by_priority.same_block && by_priority.by_priority && !by_priority.by_submission);
+        assert_eq!(by_priority.transactions[0].tip, 10);
+
+        let by_submission = OrderingObservation::new(vec![transaction(0, 0, 7, 3), transaction(10, 1, 8, 0)]);
+        assert!(!by_submission.same_block && !by_submission.by_priority && by_submission.by_submission);
+    }
+}
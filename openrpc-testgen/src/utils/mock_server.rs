@@ -0,0 +1,112 @@
+//! HTTP server answering JSON requests with a handler, for the unit tests of code talking to a
+//! node or a faucet without one running.
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use url::Url;
+
+/// A request received by a [MockServer]: its path and JSON body.
+#[derive(Debug, Clone)]
+pub(crate) struct MockRequest {
+    pub path: String,
+    pub body: Value,
+}
+
+pub(crate) struct MockServer {
+    pub url: Url,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+}
+
+impl MockServer {
+    /// Listens on a free local port, answering every request with the JSON `handler` returns.
+    pub async fn start(handler: impl Fn(&MockRequest) -> Value + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(handler);
+
+        let received = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let received = received.clone();
+                let handler = handler.clone();
+                tokio::spawn(async move { answer(stream, |request| handler(request), &received).await });
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Answers JSON-RPC requests with the result `handler` returns for their method and params,
+    /// and with a `Method not found` error when it returns `None`.
+    pub async fn json_rpc(handler: impl Fn(&str, &Value) -> Option<Value> + Send + Sync + 'static) -> Self {
+        Self::start(move |request| {
+            let method = request.body["method"].as_str().unwrap_or_default();
+            match handler(method, &request.body["params"]) {
+                Some(result) => json!({ "jsonrpc": "2.0", "id": request.body["id"], "result": result }),
+                None => json!({
+                    "jsonrpc": "2.0",
+                    "id": request.body["id"],
+                    "error": { "code": -32601, "message": "Method not found" }
+                }),
+            }
+        })
+        .await
+    }
+
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn answer(
+    mut stream: TcpStream,
+    handler: impl Fn(&MockRequest) -> Value,
+    received: &Mutex<Vec<MockRequest>>,
+) -> Option<()> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let path = head.split_whitespace().nth(1).unwrap_or("/").to_string();
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    while buffer.len() < header_end + content_length {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    let body = serde_json::from_slice(&buffer[header_end..]).unwrap_or(Value::Null);
+    let request = MockRequest { path, body };
+    let response = serde_json::to_vec(&handler(&request)).unwrap();
+    received.lock().unwrap().push(request);
+    let head = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.len()
+    );
+    stream.write_all(head.as_bytes()).await.ok()?;
+    stream.write_all(&response).await.ok()?;
+    stream.shutdown().await.ok()
+}
@@ -1,8 +1,58 @@
+pub mod account_pool;
+pub mod chain_id;
+pub mod checkpoint;
+pub mod class_verification;
+pub mod config;
 pub mod conversions;
+pub mod declared_classes;
+#[cfg(feature = "openrpc")]
+pub(crate) mod deploy_account_receipt;
+pub mod display;
+pub mod dry_run;
+pub mod event_backfill;
+pub mod fee_accuracy;
+pub mod fee_overrides;
+pub mod fee_search;
+pub mod finality;
 pub mod get_balance;
 pub mod get_deployed_contract_address;
+pub mod inclusion_latency;
+pub mod l1_messaging;
+pub mod log_capture;
+pub mod mempool_ordering;
+#[cfg(test)]
+pub(crate) mod mock_server;
+pub mod network_registry;
+pub mod network_runs;
+pub mod node_diff;
+#[cfg(feature = "orchestrator")]
+pub mod orchestrator;
 pub mod outside_execution;
+#[cfg(feature = "openrpc")]
+pub(crate) mod oversized;
 pub mod random_single_owner_account;
+#[cfg(feature = "reexecution")]
+pub mod reexecution;
+pub mod request_timing;
+pub mod resilience;
+pub mod resource_limits;
+pub mod response_normalizers;
+pub mod response_sizes;
+pub mod scaffold;
+pub mod secrets;
+pub mod selector_dictionary;
+pub mod simulation_diff;
+pub mod snapshot;
+pub mod soak;
+pub mod spec_version;
 pub mod starknet_hive;
+pub mod starknet_version;
+pub mod test_filter;
+pub mod test_report;
+pub mod traffic_capture;
+pub mod txn_builder;
 pub mod v7;
 pub mod v8;
+#[cfg(feature = "openrpc")]
+pub(crate) mod version_replay;
+pub mod workload;
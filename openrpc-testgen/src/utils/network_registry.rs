@@ -0,0 +1,175 @@
+//! Addresses and class hashes that differ between the networks the suites run against. Test cases
+//! read them from the registry of the run instead of hard-coding the devnet ones, so that pointing
+//! the runner at another network only takes picking it, and overriding whatever differs.
+
+use std::sync::OnceLock;
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{BlockId, BlockTag};
+
+use super::chain_id::DEFAULT_CHAIN_NAME;
+use super::v7::accounts::creation::helpers::{
+    ARGENT_CLASS_HASH, BRAAVOS_BASE_CLASS_HASH, BRAAVOS_CLASS_HASH, OZ_CLASS_HASH,
+};
+use super::v7::providers::provider::Provider;
+
+/// Fee tokens and the UDC are deployed at the same addresses on every public network and devnet.
+const STRK_ADDRESS: Felt =
+    Felt::from_hex_unchecked("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D");
+const ETH_ADDRESS: Felt = Felt::from_hex_unchecked("0x49D36570D4E46F48E99674BD3FCC84644DDD6B96F7C741B1562B82F9E004DC7");
+const UDC_ADDRESS: Felt =
+    Felt::from_hex_unchecked("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
+
+/// OpenZeppelin account declared on the public networks.
+const OZ_ACCOUNT_CLASS_HASH: Felt = Felt::from_hex_unchecked(OZ_CLASS_HASH);
+/// Account the devnets predeploy their accounts with.
+const DEVNET_ACCOUNT_CLASS_HASH: Felt =
+    Felt::from_hex_unchecked("0x07dc7899aa655b0aae51eadff6d801a58e97dd99cf4666ee59e704249e51adf2");
+
+const PUBLIC_SEQUENCER_ADDRESS: Felt =
+    Felt::from_hex_unchecked("0x1176a1bd84444c89232ec27754698e5d2e7e1a7f1539f12027f28b23ec9f3d8");
+
+/// Wallet account classes declared on the public networks, which the accounts of their users run.
+const PUBLIC_ACCOUNT_CLASSES: [AccountClass; 4] = [
+    AccountClass { name: "OpenZeppelin", class_hash: OZ_ACCOUNT_CLASS_HASH },
+    AccountClass { name: "Argent", class_hash: Felt::from_hex_unchecked(ARGENT_CLASS_HASH) },
+    AccountClass { name: "Braavos base", class_hash: Felt::from_hex_unchecked(BRAAVOS_BASE_CLASS_HASH) },
+    AccountClass { name: "Braavos", class_hash: Felt::from_hex_unchecked(BRAAVOS_CLASS_HASH) },
+];
+
+const SEPOLIA_FAUCETS: [&str; 2] =
+    ["https://starknet-faucet.vercel.app", "https://blastapi.io/faucets/starknet-sepolia-strk"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Network {
+    Mainnet,
+    Sepolia,
+    #[default]
+    Devnet,
+    Katana,
+    /// The devnet constants, for a network whose differing constants are all overridden.
+    Custom,
+}
+
+/// An account class the network is expected to have declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountClass {
+    pub name: &'static str,
+    pub class_hash: Felt,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkRegistry {
+    pub network: Network,
+    /// Chain name the node must report, see [super::chain_id].
+    pub chain_name: String,
+    pub strk_address: Felt,
+    pub eth_address: Felt,
+    pub udc_address: Felt,
+    /// Class hash of the accounts the test cases deploy.
+    pub default_account_class_hash: Felt,
+    /// Address blocks are produced by, and fees are transferred to.
+    pub sequencer_address: Felt,
+    /// Account classes checked with `starknet_getClass` before the suites run.
+    pub account_classes: Vec<AccountClass>,
+    /// Pages funding accounts on the network, for when the paymaster runs low.
+    pub faucets: Vec<&'static str>,
+}
+
+impl NetworkRegistry {
+    /// Constants of a known network.
+    pub fn new(network: Network) -> Self {
+        let (chain_name, default_account_class_hash, sequencer_address) = match network {
+            Network::Mainnet => ("SN_MAIN", OZ_ACCOUNT_CLASS_HASH, PUBLIC_SEQUENCER_ADDRESS),
+            Network::Sepolia => ("SN_SEPOLIA", OZ_ACCOUNT_CLASS_HASH, PUBLIC_SEQUENCER_ADDRESS),
+            Network::Devnet | Network::Custom => {
+                (DEFAULT_CHAIN_NAME, DEVNET_ACCOUNT_CLASS_HASH, Felt::from_hex_unchecked("0x123"))
+            }
+            Network::Katana => ("KATANA", DEVNET_ACCOUNT_CLASS_HASH, Felt::ONE),
+        };
+        let (account_classes, faucets) = match network {
+            Network::Mainnet => (PUBLIC_ACCOUNT_CLASSES.to_vec(), Vec::new()),
+            Network::Sepolia => (PUBLIC_ACCOUNT_CLASSES.to_vec(), SEPOLIA_FAUCETS.to_vec()),
+            Network::Devnet | Network::Katana | Network::Custom => (Vec::new(), Vec::new()),
+        };
+        Self {
+            network,
+            chain_name: chain_name.to_string(),
+            strk_address: STRK_ADDRESS,
+            eth_address: ETH_ADDRESS,
+            udc_address: UDC_ADDRESS,
+            default_account_class_hash,
+            sequencer_address,
+            account_classes,
+            faucets,
+        }
+    }
+
+    /// Checks that the node serves the account classes of the network, and the class of the
+    /// accounts the test cases deploy. Returns a description of each class it doesn't serve, e.g.
+    /// because the node runs another network, or isn't synced past the declaration of the class.
+    pub async fn verify_classes<P: Provider>(&self, provider: &P) -> Vec<String> {
+        let default_account_class =
+            AccountClass { name: "default account", class_hash: self.default_account_class_hash };
+        let mut classes = self.account_classes.clone();
+        if !classes.iter().any(|class| class.class_hash == self.default_account_class_hash) {
+            classes.push(default_account_class);
+        }
+
+        let mut failures = Vec::new();
+        for class in classes {
+            if let Err(e) = provider.get_class(BlockId::Tag(BlockTag::Latest), class.class_hash).await {
+                failures.push(format!("{} class {:#x} is not served by getClass: {}", class.name, class.class_hash, e));
+            }
+        }
+        failures
+    }
+}
+
+impl Default for NetworkRegistry {
+    fn default() -> Self {
+        Self::new(Network::default())
+    }
+}
+
+static NETWORK_REGISTRY: OnceLock<NetworkRegistry> = OnceLock::new();
+
+/// Sets the constants of the network of the run. Returns `false` if they have already been set or
+/// read.
+pub fn set_network_registry(registry: NetworkRegistry) -> bool {
+    NETWORK_REGISTRY.set(registry).is_ok()
+}
+
+/// Constants of the network of the run, the devnet ones unless set.
+pub fn network_registry() -> &'static NetworkRegistry {
+    NETWORK_REGISTRY.get_or_init(NetworkRegistry::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::chain_id::encode_chain_name;
+
+    #[test]
+    fn known_networks_have_valid_chain_names() {
+        for network in [Network::Mainnet, Network::Sepolia, Network::Devnet, Network::Katana, Network::Custom] {
+            let registry = NetworkRegistry::new(network);
+            assert!(encode_chain_name(&registry.chain_name).is_ok(), "{:?}", registry);
+        }
+        assert_eq!(
+            encode_chain_name(&NetworkRegistry::new(Network::Mainnet).chain_name).unwrap(),
+            Felt::from_hex_unchecked("0x534e5f4d41494e")
+        );
+        assert_eq!(NetworkRegistry::default(), NetworkRegistry::new(Network::Devnet));
+    }
+
+    #[test]
+    fn sepolia_profile_lists_wallet_classes() {
+        let sepolia = NetworkRegistry::new(Network::Sepolia);
+        let names: Vec<_> = sepolia.account_classes.iter().map(|class| class.name).collect();
+        assert_eq!(names, ["OpenZeppelin", "Argent", "Braavos base", "Braavos"]);
+        assert!(sepolia.account_classes.iter().any(|class| class.class_hash == sepolia.default_account_class_hash));
+        assert!(!sepolia.faucets.is_empty());
+        assert!(NetworkRegistry::new(Network::Devnet).account_classes.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+//! Aggregation of the runs against several networks: the runner runs the suites against every
+//! network of the config file one after the other, each in its own process as the configuration
+//! of a run is global, and combines the outcomes of their test cases by network.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+use tracing::{error, info};
+
+use super::secrets::write_redacted;
+use super::test_report::{write_junit_report, TestCaseResult, TestOutcome};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkRun {
+    /// Exit code of the run, `None` if it could not start or was killed.
+    pub exit_code: Option<i32>,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub results: Vec<TestCaseResult>,
+}
+
+impl NetworkRun {
+    pub fn new(exit_code: Option<i32>, results: Vec<TestCaseResult>) -> Self {
+        let count =
+            |matches: fn(&TestOutcome) -> bool| results.iter().filter(|result| matches(&result.outcome)).count();
+        Self {
+            exit_code,
+            passed: count(|outcome| *outcome == TestOutcome::Passed),
+            failed: count(|outcome| matches!(outcome, TestOutcome::Failed(_))),
+            skipped: count(|outcome| *outcome == TestOutcome::Skipped),
+            results,
+        }
+    }
+
+    /// Whether the run failed, through a test case or otherwise, e.g. an unreachable node.
+    pub fn is_failure(&self) -> bool {
+        self.exit_code != Some(0) || self.failed > 0
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CombinedReport {
+    pub networks: BTreeMap<String, NetworkRun>,
+}
+
+impl CombinedReport {
+    pub fn is_failure(&self) -> bool {
+        self.networks.values().any(NetworkRun::is_failure)
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    /// Writes the test cases of all networks as one JUnit XML report, with the suites of every
+    /// network named `network/suite`.
+    pub fn write_junit(&self, path: &Path) -> io::Result<()> {
+        let results: Vec<TestCaseResult> = self
+            .networks
+            .iter()
+            .flat_map(|(network, run)| {
+                run.results.iter().map(move |result| TestCaseResult {
+                    suite: format!("{network}/{}", result.suite),
+                    ..result.clone()
+                })
+            })
+            .collect();
+        write_junit_report(path, &results)
+    }
+
+    pub fn log_summary(&self) {
+        for (network, run) in &self.networks {
+            let summary = format!(
+                "{}: {} passed, {} failed, {} skipped, exit code {:?}",
+                network, run.passed, run.failed, run.skipped, run.exit_code
+            );
+            if run.is_failure() {
+                error!("{}", summary);
+            } else {
+                info!("{}", summary);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn counts_outcomes_and_failures_by_network() {
+        let result = |outcome| TestCaseResult {
+            suite: "openrpc".to_string(),
+            name: "get_nonce".to_string(),
+            outcome,
+            duration: Duration::ZERO,
+            logs: Vec::new(),
+        };
+        let passing = NetworkRun::new(Some(0), vec![result(TestOutcome::Passed), result(TestOutcome::Skipped)]);
+        assert_eq!((passing.passed, passing.failed, passing.skipped), (1, 0, 1));
+        assert!(!passing.is_failure());
+
+        let mut report = CombinedReport::default();
+        report.networks.insert("devnet".to_string(), passing);
+        assert!(!report.is_failure());
+        // A run that could not report its cases still fails the whole.
+        report.networks.insert("sepolia".to_string(), NetworkRun::new(None, Vec::new()));
+        assert!(report.is_failure());
+    }
+}
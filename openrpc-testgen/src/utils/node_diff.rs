@@ -0,0 +1,131 @@
+//! Differential comparison of nodes: the same read requests are sent to a reference node and to
+//! every other node, and the responses are compared field by field. Only the latest blocks all
+//! nodes have are compared, and hex values are compared by value rather than by formatting. Errors
+//! are compared too, a request failing on one node only is a difference. Every node is queried
+//! through a [CachingProvider], which answers repeated requests for final blocks from memory.
+
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::{info, warn};
+use url::Url;
+
+use super::response_normalizers::ResponseNormalizers;
+use super::secrets::write_redacted;
+use super::snapshot::differences;
+use super::v7::endpoints::errors::OpenRpcTestGenError;
+use super::v7::providers::caching::{CacheStats, CachingProvider};
+use super::v7::providers::jsonrpc::{HttpTransport, JsonRpcClient};
+use super::v7::providers::provider::Provider;
+
+type NodeProvider = CachingProvider<JsonRpcClient<HttpTransport>>;
+
+/// Requests compared once, the block requests are repeated for every compared block.
+const NODE_REQUESTS: [&str; 2] = ["starknet_specVersion", "starknet_chainId"];
+const BLOCK_REQUESTS: [&str; 2] = ["starknet_getBlockWithReceipts", "starknet_getStateUpdate"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseDifference {
+    pub url: String,
+    pub method: String,
+    pub params: Value,
+    /// Paths at which the response differs from the reference one.
+    pub differences: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NodeDiffReport {
+    pub reference: String,
+    pub blocks: Vec<u64>,
+    pub requests: u64,
+    /// Cache hits and misses over all nodes.
+    pub cache: CacheStats,
+    pub differences: Vec<ResponseDifference>,
+}
+
+impl NodeDiffReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        info!(
+            "Compared {} requests over {} blocks with {}: {} differ",
+            self.requests,
+            self.blocks.len(),
+            self.reference,
+            self.differences.len()
+        );
+        info!("Cache: {} hits, {} misses", self.cache.hits, self.cache.misses);
+        for difference in &self.differences {
+            warn!("  {} {} {}:", difference.url, difference.method, difference.params);
+            for path in &difference.differences {
+                warn!("    {}", path);
+            }
+        }
+    }
+}
+
+/// Compares the responses of `urls[1..]` with the ones of `urls[0]`, over the last `blocks` blocks
+/// all of them have.
+pub async fn diff_nodes(
+    urls: &[Url],
+    blocks: u64,
+    normalizers: &ResponseNormalizers,
+) -> Result<NodeDiffReport, OpenRpcTestGenError> {
+    let Some((reference, others)) = urls.split_first().filter(|(_, others)| !others.is_empty()) else {
+        return Err(OpenRpcTestGenError::Other("At least two urls are needed to compare nodes".to_string()));
+    };
+
+    let provider = |url: &Url| CachingProvider::new(JsonRpcClient::new(HttpTransport::new(url.clone())));
+    let reference_provider = provider(reference);
+    let other_providers: Vec<(&Url, NodeProvider)> = others.iter().map(|url| (url, provider(url))).collect();
+
+    let mut latest_common_block = reference_provider.block_number().await?;
+    for (_, provider) in &other_providers {
+        latest_common_block = latest_common_block.min(provider.block_number().await?);
+    }
+
+    let mut report = NodeDiffReport {
+        reference: reference.to_string(),
+        blocks: (latest_common_block.saturating_sub(blocks.saturating_sub(1))..=latest_common_block).collect(),
+        ..Default::default()
+    };
+    let mut requests: Vec<(&str, Value)> = NODE_REQUESTS.iter().map(|method| (*method, json!([]))).collect();
+    for block_number in &report.blocks {
+        for method in BLOCK_REQUESTS {
+            requests.push((method, json!({ "block_id": { "block_number": block_number } })));
+        }
+    }
+
+    for (method, params) in requests {
+        report.requests += 1;
+        let expected = response(&reference_provider, method, &params, normalizers).await;
+        for (url, provider) in &other_providers {
+            let paths = differences(&expected, &response(provider, method, &params, normalizers).await);
+            if !paths.is_empty() {
+                report.differences.push(ResponseDifference {
+                    url: url.to_string(),
+                    method: method.to_string(),
+                    params: params.clone(),
+                    differences: paths,
+                });
+            }
+        }
+    }
+    report.cache = reference_provider.stats();
+    for (_, provider) in &other_providers {
+        report.cache += provider.stats();
+    }
+    Ok(report)
+}
+
+/// Normalized result of the request, or its error as `{ "error": ... }`.
+async fn response(provider: &NodeProvider, method: &str, params: &Value, normalizers: &ResponseNormalizers) -> Value {
+    match provider.send_raw(method, params.clone()).await {
+        Ok(result) => normalizers.normalize(method, result),
+        Err(e) => json!({ "error": e.to_string() }),
+    }
+}
@@ -0,0 +1,361 @@
+//! Launches target nodes in docker containers, so that a compatibility run against a given node
+//! version is self-contained: the container is started, the run waits until its RPC answers, the
+//! suites run against it and the container is removed again.
+//!
+//! An [L1Stack] additionally starts Anvil next to a devnet and has the devnet deploy its mock of the
+//! Starknet core contract there, so that L1 <-> L2 messaging can be tested without manual setup.
+//!
+//! Containers are driven through the `docker` CLI, which must be installed and on the `PATH`.
+
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use thiserror::Error;
+use tokio::process::Command;
+use tracing::{info, warn};
+use url::Url;
+
+use super::{
+    l1_messaging::{set_l1_messaging_config, L1MessagingConfig},
+    v7::providers::{
+        jsonrpc::{transports::shared_client, HttpTransport, JsonRpcClient},
+        provider::Provider,
+    },
+};
+
+#[derive(Debug, Error)]
+pub enum OrchestratorError {
+    #[error("docker {command} failed: {reason}")]
+    Docker { command: String, reason: String },
+    #[error("Node in container {container} was not ready within {}s", timeout.as_secs())]
+    NotReady { container: String, timeout: Duration },
+    #[error("Could not set up L1 messaging: {0}")]
+    Messaging(String),
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum NodeKind {
+    Madara,
+    Katana,
+    Devnet,
+}
+
+impl NodeKind {
+    pub fn image(&self) -> &'static str {
+        match self {
+            NodeKind::Madara => "ghcr.io/madara-alliance/madara",
+            NodeKind::Katana => "ghcr.io/dojoengine/dojo",
+            NodeKind::Devnet => "shardlabs/starknet-devnet-rs",
+        }
+    }
+
+    /// Port the node serves its RPC on inside the container.
+    pub fn rpc_port(&self) -> u16 {
+        match self {
+            NodeKind::Madara => 9944,
+            NodeKind::Katana | NodeKind::Devnet => 5050,
+        }
+    }
+
+    /// Command line starting a local development chain that listens on all interfaces.
+    pub fn default_args(&self) -> Vec<String> {
+        let args: &[&str] = match self {
+            NodeKind::Madara => &["--name", "madara", "--devnet", "--rpc-external"],
+            NodeKind::Katana => &["katana", "--http.addr", "0.0.0.0", "--dev"],
+            NodeKind::Devnet => &["--host", "0.0.0.0", "--seed", "0"],
+        };
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeSpec {
+    pub kind: NodeKind,
+    /// Tag of the image, e.g. `v0.7.0`.
+    pub version: String,
+    /// Replaces the default image of the node kind.
+    pub image: Option<String>,
+    /// Replaces the default arguments of the node kind.
+    pub args: Option<Vec<String>>,
+    pub env: Vec<(String, String)>,
+    /// Name of the container, to reach it from other containers of its network.
+    pub name: Option<String>,
+    /// Docker network the container is attached to.
+    pub network: Option<String>,
+    /// Port of the host the RPC is published on.
+    pub host_port: u16,
+    pub ready_timeout: Duration,
+}
+
+impl NodeSpec {
+    pub fn new(kind: NodeKind, version: impl Into<String>) -> Self {
+        Self {
+            kind,
+            version: version.into(),
+            image: None,
+            args: None,
+            env: Vec::new(),
+            name: None,
+            network: None,
+            host_port: kind.rpc_port(),
+            ready_timeout: Duration::from_secs(120),
+        }
+    }
+
+    pub fn image_reference(&self) -> String {
+        format!("{}:{}", self.image.as_deref().unwrap_or(self.kind.image()), self.version)
+    }
+}
+
+/// A started container, removed on [Container::remove] or, as a fallback, when dropped.
+#[derive(Debug)]
+pub struct Container {
+    pub id: String,
+    removed: bool,
+}
+
+impl Container {
+    /// Runs `docker run --detach` with `options`, followed by the image and its arguments.
+    async fn run(options: Vec<String>) -> Result<Self, OrchestratorError> {
+        let mut args = vec!["run".to_string(), "--detach".to_string()];
+        args.extend(options);
+        Ok(Self { id: docker(&args).await?, removed: false })
+    }
+
+    /// Stops and removes the container.
+    pub async fn remove(mut self) -> Result<(), OrchestratorError> {
+        self.removed = true;
+        docker(&["rm".to_string(), "--force".to_string(), self.id.clone()]).await?;
+        info!("Removed container {}", self.id);
+        Ok(())
+    }
+}
+
+impl Drop for Container {
+    fn drop(&mut self) {
+        if !self.removed {
+            docker_blocking(&["rm", "--force", &self.id]);
+        }
+    }
+}
+
+/// A docker network, removed on [Network::remove] or, as a fallback, when dropped.
+#[derive(Debug)]
+pub struct Network {
+    pub name: String,
+    removed: bool,
+}
+
+impl Network {
+    async fn create(name: String) -> Result<Self, OrchestratorError> {
+        docker(&["network".to_string(), "create".to_string(), name.clone()]).await?;
+        Ok(Self { name, removed: false })
+    }
+
+    pub async fn remove(mut self) -> Result<(), OrchestratorError> {
+        self.removed = true;
+        docker(&["network".to_string(), "rm".to_string(), self.name.clone()]).await?;
+        Ok(())
+    }
+}
+
+impl Drop for Network {
+    fn drop(&mut self) {
+        if !self.removed {
+            docker_blocking(&["network", "rm", &self.name]);
+        }
+    }
+}
+
+/// A launched Starknet node.
+#[derive(Debug)]
+pub struct RunningNode {
+    pub container: Container,
+    pub url: Url,
+}
+
+impl RunningNode {
+    /// Starts a container for `spec` and waits until its RPC answers `starknet_chainId`.
+    pub async fn launch(spec: &NodeSpec) -> Result<Self, OrchestratorError> {
+        let image = spec.image_reference();
+        info!("🐳 Launching {:?} node from {}", spec.kind, image);
+
+        let mut options = vec!["--publish".to_string(), format!("{}:{}", spec.host_port, spec.kind.rpc_port())];
+        for (key, value) in &spec.env {
+            options.push("--env".to_string());
+            options.push(format!("{key}={value}"));
+        }
+        if let Some(name) = &spec.name {
+            options.extend(["--name".to_string(), name.clone()]);
+        }
+        if let Some(network) = &spec.network {
+            options.extend(["--network".to_string(), network.clone()]);
+        }
+        options.push(image);
+        options.extend(spec.args.clone().unwrap_or_else(|| spec.kind.default_args()));
+
+        let container = Container::run(options).await?;
+        let node = Self { container, url: Url::parse(&format!("http://127.0.0.1:{}", spec.host_port))? };
+        node.wait_ready(spec.ready_timeout).await?;
+        info!("Node {} is ready at {}", node.container.id, node.url);
+        Ok(node)
+    }
+
+    async fn wait_ready(&self, timeout: Duration) -> Result<(), OrchestratorError> {
+        let provider = JsonRpcClient::new(HttpTransport::new(self.url.clone()));
+        let started = Instant::now();
+        while started.elapsed() < timeout {
+            if provider.chain_id().await.is_ok() {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        Err(OrchestratorError::NotReady { container: self.container.id.clone(), timeout })
+    }
+
+    /// Stops and removes the container.
+    pub async fn teardown(self) -> Result<(), OrchestratorError> {
+        self.container.remove().await
+    }
+}
+
+pub const ANVIL_IMAGE: &str = "ghcr.io/foundry-rs/foundry";
+const ANVIL_PORT: u16 = 8545;
+
+#[derive(Debug, Clone)]
+pub struct L1StackSpec {
+    /// Tag of the foundry image Anvil is run from.
+    pub anvil_version: String,
+    /// Port of the host the Anvil RPC is published on.
+    pub anvil_host_port: u16,
+    /// The devnet relaying the messages, its name and network are set by the stack.
+    pub devnet: NodeSpec,
+}
+
+impl L1StackSpec {
+    pub fn new(devnet_version: impl Into<String>) -> Self {
+        Self {
+            anvil_version: "latest".to_string(),
+            anvil_host_port: ANVIL_PORT,
+            devnet: NodeSpec::new(NodeKind::Devnet, devnet_version),
+        }
+    }
+}
+
+/// Anvil and a devnet on a shared docker network, the devnet relaying its messages through the
+/// mock Starknet core contract it deployed on Anvil.
+#[derive(Debug)]
+pub struct L1Stack {
+    pub devnet: RunningNode,
+    pub anvil: Container,
+    /// Anvil RPC, as reachable from the host.
+    pub l1_url: Url,
+    /// Address of the mock Starknet core contract on L1.
+    pub messaging_contract: String,
+    // Dropped last, once no container is attached to it anymore.
+    pub network: Network,
+}
+
+impl L1Stack {
+    /// Launches the stack and makes it the L1 messaging setup of the test cases.
+    pub async fn launch(spec: &L1StackSpec) -> Result<Self, OrchestratorError> {
+        let suffix = format!("{}-{}", std::process::id(), spec.anvil_host_port);
+        let network = Network::create(format!("hive-l1-{suffix}")).await?;
+
+        let anvil_name = format!("hive-anvil-{suffix}");
+        info!("🐳 Launching Anvil from {}:{}", ANVIL_IMAGE, spec.anvil_version);
+        let anvil = Container::run(vec![
+            "--name".to_string(),
+            anvil_name.clone(),
+            "--network".to_string(),
+            network.name.clone(),
+            "--publish".to_string(),
+            format!("{}:{}", spec.anvil_host_port, ANVIL_PORT),
+            "--entrypoint".to_string(),
+            "anvil".to_string(),
+            format!("{}:{}", ANVIL_IMAGE, spec.anvil_version),
+            "--host".to_string(),
+            "0.0.0.0".to_string(),
+        ])
+        .await?;
+        let l1_url = Url::parse(&format!("http://127.0.0.1:{}", spec.anvil_host_port))?;
+        wait_for_anvil(&l1_url, &anvil.id, spec.devnet.ready_timeout).await?;
+
+        let devnet_spec = NodeSpec {
+            name: Some(format!("hive-devnet-{suffix}")),
+            network: Some(network.name.clone()),
+            ..spec.devnet.clone()
+        };
+        let devnet = RunningNode::launch(&devnet_spec).await?;
+
+        // The devnet reaches Anvil through the docker network, not through the published port.
+        let response: Value = shared_client()
+            .post(devnet.url.join("postman/load_l1_messaging_contract")?)
+            .json(&json!({ "network_url": format!("http://{anvil_name}:{ANVIL_PORT}") }))
+            .send()
+            .await?
+            .json()
+            .await?;
+        let messaging_contract = response
+            .get("messaging_contract_address")
+            .and_then(Value::as_str)
+            .ok_or_else(|| OrchestratorError::Messaging(format!("unexpected response {response}")))?
+            .to_string();
+        info!("Devnet messaging goes through the core contract mock {} on {}", messaging_contract, l1_url);
+
+        set_l1_messaging_config(L1MessagingConfig {
+            l1_url: l1_url.clone(),
+            messaging_contract: messaging_contract.clone(),
+            postman_url: devnet.url.clone(),
+        });
+        Ok(Self { devnet, anvil, l1_url, messaging_contract, network })
+    }
+
+    /// Removes the containers, then their network.
+    pub async fn teardown(self) -> Result<(), OrchestratorError> {
+        self.devnet.teardown().await?;
+        self.anvil.remove().await?;
+        self.network.remove().await
+    }
+}
+
+async fn wait_for_anvil(url: &Url, container: &str, timeout: Duration) -> Result<(), OrchestratorError> {
+    let client = shared_client();
+    let request = json!({ "jsonrpc": "2.0", "id": 0, "method": "eth_chainId", "params": [] });
+    let started = Instant::now();
+    while started.elapsed() < timeout {
+        if client.post(url.clone()).json(&request).send().await.is_ok_and(|response| response.status().is_success()) {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    Err(OrchestratorError::NotReady { container: container.to_string(), timeout })
+}
+
+/// Runs `docker` with `args`, returning its trimmed standard output.
+async fn docker(args: &[String]) -> Result<String, OrchestratorError> {
+    let command = args.first().cloned().unwrap_or_default();
+    let output = Command::new("docker")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| OrchestratorError::Docker { command: command.clone(), reason: e.to_string() })?;
+    if !output.status.success() {
+        let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(OrchestratorError::Docker { command, reason });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Cleanup from destructors, which cannot await.
+fn docker_blocking(args: &[&str]) {
+    let status = std::process::Command::new("docker").args(args).stdout(Stdio::null()).stderr(Stdio::null()).status();
+    if !matches!(status, Ok(status) if status.success()) {
+        warn!("docker {} failed during cleanup", args.join(" "));
+    }
+}
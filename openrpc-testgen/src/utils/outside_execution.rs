@@ -1,19 +1,13 @@
 use cainome_cairo_serde::CairoSerde;
 use cainome_cairo_serde_derive::CairoSerde;
-use crypto_utils::hash::{poseidon_hash_many, PoseidonHasher};
+use serde_json::json;
 use starknet::core::crypto::ecdsa_sign;
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{BlockId, BlockTag};
+use t9n::typed_data::TypedData;
 
 use super::v7::{accounts::call::Call, endpoints::errors::OpenRpcTestGenError, providers::provider::Provider};
 
-pub const STARKNET_DOMAIN_TYPE_HASH: Felt =
-    Felt::from_hex_unchecked("0x1ff2f602e42168014d405a94f75e8a93d640751d71d16311266e140d8b0a210");
-pub const CALL_TYPE_HASH: Felt =
-    Felt::from_hex_unchecked("0x3635c7f2a7ba93844c0d064e18e487f35ab90f7c39d00f186a781fc3f0c2ca9");
-pub const OUTSIDE_EXECUTION_TYPE_HASH: Felt =
-    Felt::from_hex_unchecked("0x312b56c05a7965066ddbda31c016d8d05afc305071c0ca3cdc2192c3c2f1f0f");
-
 #[derive(Debug, CairoSerde)]
 pub struct OutsideExecution {
     pub caller: Felt,
@@ -23,52 +17,53 @@ pub struct OutsideExecution {
     pub calls: Vec<Call>,
 }
 
-#[derive(Debug, CairoSerde)]
-pub struct StarknetDomain {
-    pub name: Felt,
-    pub version: Felt,
-    pub chain_id: Felt,
-    pub revision: Felt,
-}
-
-pub fn get_starknet_domain_hash(chain_id: Felt) -> Felt {
-    let domain = StarknetDomain {
-        name: Felt::from_bytes_be_slice(b"Account.execute_from_outside"),
-        version: Felt::TWO,
-        chain_id,
-        revision: Felt::ONE,
-    };
-
-    let domain_vec = vec![STARKNET_DOMAIN_TYPE_HASH, domain.name, domain.version, domain.chain_id, domain.revision];
-    poseidon_hash_many(&domain_vec)
-}
-
-pub fn get_outside_execution_hash(outside_execution: &OutsideExecution) -> Felt {
-    let calls_vec = outside_execution.calls.clone();
-    let mut hashed_calls = Vec::<Felt>::new();
-
-    for call in calls_vec {
-        hashed_calls.push(get_call_hash(call));
-    }
-
-    let mut hasher_outside_execution = PoseidonHasher::new();
-    hasher_outside_execution.update(OUTSIDE_EXECUTION_TYPE_HASH);
-    hasher_outside_execution.update(outside_execution.caller);
-    hasher_outside_execution.update(outside_execution.nonce);
-    hasher_outside_execution.update(Felt::from(outside_execution.execute_after));
-    hasher_outside_execution.update(Felt::from(outside_execution.execute_before));
-    hasher_outside_execution.update(poseidon_hash_many(&hashed_calls));
-
-    hasher_outside_execution.finalize()
-}
-
-pub fn get_call_hash(call: Call) -> Felt {
-    let mut hasher_call = PoseidonHasher::new();
-    hasher_call.update(CALL_TYPE_HASH);
-    hasher_call.update(call.to);
-    hasher_call.update(call.selector);
-    hasher_call.update(poseidon_hash_many(&call.calldata));
-    hasher_call.finalize()
+/// SNIP-12 typed data of an outside execution, in the revision 1 encoding of SNIP-9 version 2.
+pub fn outside_execution_typed_data(
+    outside_execution: &OutsideExecution,
+    chain_id: Felt,
+) -> Result<TypedData, t9n::txn_validation::errors::Error> {
+    let calls: Vec<_> = outside_execution
+        .calls
+        .iter()
+        .map(|call| json!({ "To": call.to, "Selector": call.selector, "Calldata": call.calldata }))
+        .collect();
+
+    Ok(serde_json::from_value(json!({
+        "types": {
+            "StarknetDomain": [
+                { "name": "name", "type": "shortstring" },
+                { "name": "version", "type": "shortstring" },
+                { "name": "chainId", "type": "shortstring" },
+                { "name": "revision", "type": "shortstring" }
+            ],
+            "OutsideExecution": [
+                { "name": "Caller", "type": "ContractAddress" },
+                { "name": "Nonce", "type": "felt" },
+                { "name": "Execute After", "type": "u128" },
+                { "name": "Execute Before", "type": "u128" },
+                { "name": "Calls", "type": "Call*" }
+            ],
+            "Call": [
+                { "name": "To", "type": "ContractAddress" },
+                { "name": "Selector", "type": "selector" },
+                { "name": "Calldata", "type": "felt*" }
+            ]
+        },
+        "primaryType": "OutsideExecution",
+        "domain": {
+            "name": "Account.execute_from_outside",
+            "version": "2",
+            "chainId": chain_id,
+            "revision": "1"
+        },
+        "message": {
+            "Caller": outside_execution.caller,
+            "Nonce": outside_execution.nonce,
+            "Execute After": outside_execution.execute_after,
+            "Execute Before": outside_execution.execute_before,
+            "Calls": calls
+        }
+    }))?)
 }
 
 pub async fn prepare_outside_execution(
@@ -77,13 +72,7 @@ pub async fn prepare_outside_execution(
     signer_private_key: Felt,
     chain_id: Felt,
 ) -> Result<Vec<Felt>, OpenRpcTestGenError> {
-    let mut final_hasher = PoseidonHasher::new();
-    final_hasher.update(Felt::from_bytes_be_slice(b"StarkNet Message"));
-    final_hasher.update(get_starknet_domain_hash(chain_id));
-    final_hasher.update(signer_address);
-    final_hasher.update(get_outside_execution_hash(outside_execution));
-
-    let hash = final_hasher.finalize();
+    let hash = outside_execution_typed_data(outside_execution, chain_id)?.message_hash(signer_address)?;
 
     let starknet::core::crypto::ExtendedSignature { r, s, v: _ } = ecdsa_sign(&signer_private_key, &hash)?;
 
@@ -107,3 +96,34 @@ pub async fn get_current_timestamp<P: Provider>(provider: P) -> Result<u64, Open
 
     Ok(timestamp)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_data_has_the_snip9_type_hashes() {
+        let outside_execution = OutsideExecution {
+            caller: Felt::ONE,
+            nonce: Felt::TWO,
+            execute_after: 0,
+            execute_before: 1,
+            calls: vec![],
+        };
+        let typed_data = outside_execution_typed_data(&outside_execution, Felt::THREE).unwrap();
+        let type_hash = |type_name| typed_data.type_hash(type_name).unwrap();
+
+        assert_eq!(
+            type_hash("StarknetDomain"),
+            Felt::from_hex_unchecked("0x1ff2f602e42168014d405a94f75e8a93d640751d71d16311266e140d8b0a210")
+        );
+        assert_eq!(
+            type_hash("OutsideExecution"),
+            Felt::from_hex_unchecked("0x312b56c05a7965066ddbda31c016d8d05afc305071c0ca3cdc2192c3c2f1f0f")
+        );
+        assert_eq!(
+            type_hash("Call"),
+            Felt::from_hex_unchecked("0x3635c7f2a7ba93844c0d064e18e487f35ab90f7c39d00f186a781fc3f0c2ca9")
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Artifacts generated to hit the limits nodes enforce on transactions, for boundary tests.
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::ContractClass;
+
+/// Maximum size in bytes of the serialized contract class of a declare transaction, above which
+/// nodes reject it with `CONTRACT_CLASS_SIZE_IS_TOO_LARGE`.
+pub const MAX_CONTRACT_CLASS_OBJECT_SIZE: usize = 4_089_446;
+
+/// Maximum length of the calldata of an invoke transaction accepted by the gateway.
+pub const MAX_CALLDATA_LENGTH: usize = 4_000;
+
+/// Pads the ABI of `class` with whitespace until the serialized class is at least `target_size`
+/// bytes. The ABI stays valid JSON and the program is untouched, so the class is rejected for its
+/// size only.
+pub fn inflate_contract_class(
+    mut class: ContractClass<Felt>,
+    target_size: usize,
+) -> Result<ContractClass<Felt>, serde_json::Error> {
+    let size = serde_json::to_vec(&class)?.len();
+    if size < target_size {
+        let abi = class.abi.get_or_insert_with(|| "[]".to_string());
+        abi.push_str(&" ".repeat(target_size - size));
+    }
+    Ok(class)
+}
+
+/// Calldata of `length` distinct felts.
+pub fn oversized_calldata(length: usize) -> Vec<Felt> {
+    (0..length as u64).map(Felt::from).collect()
+}
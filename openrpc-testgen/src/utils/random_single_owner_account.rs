@@ -1,5 +1,9 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
 use crate::RandomizableAccountsTrait;
 
+use super::account_pool::{ExclusiveAccount, Reservations};
 use super::v7::{
     accounts::{
         account::{Account, ConnectedAccount, ExecutionEncoder},
@@ -13,31 +17,86 @@ use super::v7::{
     },
     signers::local_wallet::LocalWallet,
 };
-use rand::{seq::SliceRandom, thread_rng, Rng};
+use rand::{seq::SliceRandom, thread_rng};
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::BlockId;
 
+static ACCOUNT_WEIGHTS: OnceLock<Vec<u32>> = OnceLock::new();
+
+/// Weights the [RandomSingleOwnerAccount]s created from now on pick their accounts with, one for
+/// the account of each node. Returns `false` if they were already set.
+pub fn set_account_weights(weights: Vec<u32>) -> bool {
+    ACCOUNT_WEIGHTS.set(weights).is_ok()
+}
+
+/// Transactions built on the [RandomSingleOwnerAccount] itself, through its [Account]
+/// implementation, are sent from the account picked last, so that their address, nonce, signature
+/// and provider all come from the same account.
 #[derive(Clone, Debug)]
 pub struct RandomSingleOwnerAccount {
     pub accounts: Vec<SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>>,
+    /// Relative odds of each of [accounts](Self::accounts) being picked, e.g. to send most of the
+    /// transactions to one of the nodes. Accounts without a weight have a weight of 1.
+    pub weights: Vec<u32>,
+    reservations: Reservations,
+    /// Index of the account picked last, shared by the clones.
+    current: Arc<AtomicUsize>,
 }
 
 impl RandomizableAccountsTrait for RandomSingleOwnerAccount {
     fn random_accounts(
         &self,
     ) -> Result<SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>, OpenRpcTestGenError> {
-        let mut rng = thread_rng();
+        if self.accounts.is_empty() {
+            return Err(OpenRpcTestGenError::EmptyUrlList("Accounts list is empty - no urls.".to_string()));
+        }
+        let unreserved: Vec<usize> = (0..self.accounts.len())
+            .filter(|index| !self.reservations.is_reserved(self.accounts[*index].address()))
+            .collect();
+        let index = *unreserved
+            .choose_weighted(&mut thread_rng(), |index| self.weight(*index))
+            .map_err(|e| OpenRpcTestGenError::Other(format!("No account can be picked: {e}")))?;
+        self.current.store(index, Ordering::Relaxed);
+        Ok(self.accounts[index].clone())
+    }
+
+    fn exclusive_account(&self) -> Result<ExclusiveAccount, OpenRpcTestGenError> {
+        let mut candidates = self.accounts.clone();
+        candidates.shuffle(&mut thread_rng());
+        let addresses: Vec<Felt> = self.accounts.iter().map(|account| account.address()).collect();
         let account = self
-            .accounts
-            .choose(&mut rng)
-            .cloned()
-            .ok_or_else(|| OpenRpcTestGenError::EmptyUrlList("Accounts list is empty - no urls.".to_string()))?;
-        Ok(account)
+            .reservations
+            .reserve(&addresses, candidates.iter().map(|account| account.address()))
+            .and_then(|address| candidates.into_iter().find(|account| account.address() == address))
+            .ok_or_else(|| OpenRpcTestGenError::Other("No account can be reserved".to_string()))?;
+        Ok(ExclusiveAccount::new(account, self.reservations.clone()))
     }
 }
+
 impl RandomSingleOwnerAccount {
+    /// Picks its accounts with the weights of [set_account_weights], if any.
     pub fn new(accounts: Vec<SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>>) -> Self {
-        Self { accounts }
+        Self {
+            accounts,
+            weights: ACCOUNT_WEIGHTS.get().cloned().unwrap_or_default(),
+            reservations: Reservations::default(),
+            current: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Consumes the current [RandomSingleOwnerAccount] instance and returns a new one picking its
+    /// accounts with `weights`.
+    pub fn with_weights(self, weights: Vec<u32>) -> Self {
+        Self { weights, ..self }
+    }
+
+    fn weight(&self, index: usize) -> u32 {
+        self.weights.get(index).copied().unwrap_or(1)
+    }
+
+    /// The account picked last, the one transactions built on `self` are sent from.
+    fn current(&self) -> &SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet> {
+        &self.accounts[self.current.load(Ordering::Relaxed)]
     }
 
     pub fn set_block_id(
@@ -53,15 +112,11 @@ impl Account for RandomSingleOwnerAccount {
     type SignError = SignError<<LocalWallet as crate::utils::v7::signers::signer::Signer>::SignError>;
 
     fn address(&self) -> Felt {
-        let random_account = self.random_accounts().unwrap();
-
-        random_account.address()
+        self.current().address()
     }
 
     fn chain_id(&self) -> Felt {
-        let random_account = self.random_accounts().unwrap();
-
-        random_account.chain_id()
+        self.current().chain_id()
     }
 
     async fn sign_execution_v1(
@@ -69,19 +124,15 @@ impl Account for RandomSingleOwnerAccount {
         execution: &crate::utils::v7::accounts::account::RawExecutionV1,
         query_only: bool,
     ) -> Result<Vec<Felt>, Self::SignError> {
-        let random_account = self.random_accounts().unwrap();
-
-        random_account.sign_execution_v1(execution, query_only).await
+        self.current().sign_execution_v1(execution, query_only).await
     }
 
     async fn sign_execution_v3(
         &self,
         execution: &crate::utils::v7::accounts::account::RawExecutionV3,
-        _query_only: bool,
+        query_only: bool,
     ) -> Result<Vec<Felt>, Self::SignError> {
-        let random_account = self.random_accounts().unwrap();
-
-        random_account.sign_execution_v3(execution, _query_only).await
+        self.current().sign_execution_v3(execution, query_only).await
     }
 
     async fn sign_declaration_v2(
@@ -89,9 +140,7 @@ impl Account for RandomSingleOwnerAccount {
         declaration: &crate::utils::v7::accounts::account::RawDeclarationV2,
         query_only: bool,
     ) -> Result<Vec<Felt>, Self::SignError> {
-        let random_account = self.random_accounts().unwrap();
-
-        random_account.sign_declaration_v2(declaration, query_only).await
+        self.current().sign_declaration_v2(declaration, query_only).await
     }
 
     async fn sign_declaration_v3(
@@ -99,23 +148,17 @@ impl Account for RandomSingleOwnerAccount {
         declaration: &crate::utils::v7::accounts::account::RawDeclarationV3,
         query_only: bool,
     ) -> Result<Vec<Felt>, Self::SignError> {
-        let random_account = self.random_accounts().unwrap();
-
-        random_account.sign_declaration_v3(declaration, query_only).await
+        self.current().sign_declaration_v3(declaration, query_only).await
     }
 
     fn is_signer_interactive(&self) -> bool {
-        let random_account = self.random_accounts().unwrap();
-
-        random_account.is_signer_interactive()
+        self.current().is_signer_interactive()
     }
 }
 
 impl ExecutionEncoder for RandomSingleOwnerAccount {
     fn encode_calls(&self, calls: &[Call]) -> Vec<Felt> {
-        let random_account = self.random_accounts().unwrap();
-
-        random_account.encode_calls(calls)
+        self.current().encode_calls(calls)
     }
 }
 
@@ -123,19 +166,75 @@ impl ConnectedAccount for RandomSingleOwnerAccount {
     type Provider = JsonRpcClient<HttpTransport>;
 
     fn provider(&self) -> &Self::Provider {
-        let mut rng = thread_rng();
-        self.accounts[rng.gen_range(0..self.accounts.len())].provider()
+        self.current().provider()
     }
 
     fn block_id(&self) -> BlockId<Felt> {
-        let random_account = self.random_accounts().unwrap();
-
-        random_account.block_id()
+        self.current().block_id()
     }
 
     async fn get_nonce(&self) -> Result<Felt, ProviderError> {
-        let random_account = self.random_accounts().unwrap();
+        self.current().get_nonce().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+    use crate::utils::v7::{accounts::single_owner::ExecutionEncoding, signers::key_pair::SigningKey};
+
+    fn accounts(count: u64) -> Vec<SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>> {
+        (1..=count)
+            .map(|address| {
+                SingleOwnerAccount::new(
+                    JsonRpcClient::new(HttpTransport::new(Url::parse("http://127.0.0.1:5050").unwrap())),
+                    LocalWallet::from(SigningKey::from_secret_scalar(Felt::ONE)),
+                    Felt::from(address),
+                    Felt::ONE,
+                    ExecutionEncoding::New,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn picks_accounts_in_proportion_to_their_weights() {
+        let random = RandomSingleOwnerAccount::new(accounts(3)).with_weights(vec![3, 1, 0]);
+
+        let mut picks = [0u32; 3];
+        for _ in 0..4000 {
+            let address = random.random_accounts().unwrap().address();
+            picks[random.accounts.iter().position(|account| account.address() == address).unwrap()] += 1;
+        }
+
+        assert_eq!(picks[2], 0);
+        let share = f64::from(picks[0]) / 4000.0;
+        assert!((0.7..0.8).contains(&share), "first account picked {share} of the time instead of 0.75");
+    }
+
+    #[test]
+    fn sends_transactions_from_the_account_picked_last() {
+        let random = RandomSingleOwnerAccount::new(accounts(3));
+
+        for _ in 0..10 {
+            let picked = random.random_accounts().unwrap().address();
+            assert_eq!(random.address(), picked);
+            assert_eq!(random.clone().address(), picked);
+        }
+    }
 
-        random_account.get_nonce().await
+    #[test]
+    fn leaves_exclusive_accounts_out_until_dropped() {
+        let random = RandomSingleOwnerAccount::new(accounts(2));
+
+        let exclusive = random.exclusive_account().unwrap();
+        for _ in 0..20 {
+            assert_ne!(random.random_accounts().unwrap().address(), exclusive.address());
+        }
+        let reserved = exclusive.address();
+        drop(exclusive);
+        assert!((0..100).any(|_| random.random_accounts().unwrap().address() == reserved));
     }
 }
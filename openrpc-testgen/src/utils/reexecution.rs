@@ -0,0 +1,208 @@
+//! Re-execution of the blocks a run produced with the t8n engine. Every block is executed again
+//! locally on top of the node's own state as of the previous block, with the gas prices of the
+//! original block, and the resulting receipts are compared with the ones the node reported.
+//!
+//! Only the outcome of the transactions is compared: execution status, whether they reverted,
+//! fees, events and messages to L1. Block info such as numbers, timestamps and the sequencer is
+//! not replayed, so contracts reading it may legitimately diverge. L1 handler transactions, and
+//! transactions the engine can't parse, are skipped, which can make later transactions of the same
+//! block diverge too.
+//!
+//! Blocks and classes are read through a [CachingProvider], its hits and misses are part of the
+//! report.
+
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use starknet_devnet_types::{chain_id::ChainId, rpc::transactions::BroadcastedTransaction, traits::ToHexString};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::BlockId;
+use t8n::{
+    execution_diff::{diff_receipts, normalize_hex},
+    starknet::state::rpc_receipt::RpcReceiptVersion,
+    utils::BlockInput,
+    Executor,
+};
+use thiserror::Error;
+use tracing::{info, warn};
+use url::Url;
+
+use super::secrets::write_redacted;
+use super::v7::providers::caching::{CacheStats, CachingProvider};
+use super::v7::providers::jsonrpc::{HttpTransport, JsonRpcClient};
+use super::v7::providers::provider::{Provider, ProviderError};
+
+#[derive(Debug, Error)]
+pub enum ReexecutionError {
+    #[error("Request {method} failed: {reason}")]
+    Rpc { method: String, reason: String },
+    #[error("Chain id {0} is not supported by the t8n engine")]
+    UnsupportedChainId(Felt),
+    #[error("Re-execution of block {block_number} failed: {reason}")]
+    Execution { block_number: u64, reason: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedTransaction {
+    pub block_number: u64,
+    pub transaction_hash: Felt,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReceiptMismatch {
+    pub block_number: u64,
+    pub transaction_hash: Felt,
+    pub field: String,
+    pub node: Value,
+    pub reexecution: Value,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReexecutionReport {
+    pub blocks: u64,
+    pub transactions: u64,
+    pub cache: CacheStats,
+    pub skipped: Vec<SkippedTransaction>,
+    pub mismatches: Vec<ReceiptMismatch>,
+}
+
+impl ReexecutionReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+}
+
+/// Re-executes the blocks `first_block..=last_block` of the node at `url`.
+pub async fn reexecute_blocks(
+    url: &Url,
+    first_block: u64,
+    last_block: u64,
+) -> Result<ReexecutionReport, ReexecutionError> {
+    let provider = CachingProvider::new(JsonRpcClient::new(HttpTransport::new(url.clone())));
+    let chain_id = chain_id(&provider).await?;
+
+    let mut report = ReexecutionReport::default();
+    // Block 0 has no parent state to execute it on.
+    for block_number in first_block.max(1)..=last_block {
+        let block_id = BlockId::Number(block_number);
+        let block =
+            provider.get_block_with_txs(block_id.clone()).await.map_err(rpc_error("starknet_getBlockWithTxs"))?;
+        let block = serde_json::to_value(block).unwrap_or_default();
+
+        let mut transactions = vec![];
+        let mut hashes = vec![];
+        for mut transaction in block["transactions"].as_array().cloned().unwrap_or_default() {
+            let transaction_hash = felt(&transaction["transaction_hash"]);
+            let skip = |reason: String| SkippedTransaction { block_number, transaction_hash, reason };
+            if transaction["type"] == "L1_HANDLER" {
+                report.skipped.push(skip("L1 handler transactions are not re-executed".to_string()));
+                continue;
+            }
+            if transaction["type"] == "DECLARE" {
+                let class = provider
+                    .get_class(block_id.clone(), felt(&transaction["class_hash"]))
+                    .await
+                    .map_err(rpc_error("starknet_getClass"))?;
+                transaction["contract_class"] = serde_json::to_value(class).unwrap_or_default();
+            }
+            if let Some(fields) = transaction.as_object_mut() {
+                fields.remove("transaction_hash");
+            }
+            match serde_json::from_value::<BroadcastedTransaction>(transaction) {
+                Ok(transaction) => {
+                    transactions.push(transaction);
+                    hashes.push(transaction_hash);
+                }
+                Err(e) => report.skipped.push(skip(format!("Not parsed by the t8n engine: {e}"))),
+            }
+        }
+
+        let gas_prices = serde_json::from_value(json!({
+            "gas_price": block["l1_gas_price"]["price_in_wei"],
+            "data_gas_price": block["l1_data_gas_price"]["price_in_wei"],
+            "strk_gas_price": block["l1_gas_price"]["price_in_fri"],
+            "strk_data_gas_price": block["l1_data_gas_price"]["price_in_fri"],
+        }))
+        .unwrap_or_default();
+
+        let fork_url = url.clone();
+        let local_receipts = tokio::task::spawn_blocking(move || {
+            // Origin state is read with blocking requests, which must not run on the async runtime.
+            let mut executor = Executor::fork(fork_url, block_number - 1, chain_id)?;
+            executor.execute_blocks(vec![BlockInput::WithGasPrices {
+                gas_prices,
+                starknet_version: None,
+                transactions,
+            }])?;
+            executor.rpc_receipts(RpcReceiptVersion::V0_7)
+        })
+        .await
+        .map_err(|e| ReexecutionError::Execution { block_number, reason: e.to_string() })?
+        .map_err(|e| ReexecutionError::Execution { block_number, reason: e.to_string() })?;
+
+        for transaction_hash in hashes {
+            report.transactions += 1;
+            let params = json!({ "transaction_hash": format!("{transaction_hash:#x}") });
+            let node_receipt = provider
+                .send_raw("starknet_getTransactionReceipt", params)
+                .await
+                .map_err(rpc_error("starknet_getTransactionReceipt"))?;
+            let local_receipt =
+                local_receipts.iter().find(|receipt| felt(&receipt["transaction_hash"]) == transaction_hash);
+            let mismatch = |field: &str, node: Value, reexecution: Value| ReceiptMismatch {
+                block_number,
+                transaction_hash,
+                field: field.to_string(),
+                node,
+                reexecution,
+            };
+
+            let Some(local_receipt) = local_receipt else {
+                report.mismatches.push(mismatch("transaction", json!("included"), json!("rejected")));
+                continue;
+            };
+            for (field, _) in diff_receipts(&node_receipt, local_receipt) {
+                let (node, reexecution) = (normalize_hex(&node_receipt[field]), normalize_hex(&local_receipt[field]));
+                report.mismatches.push(mismatch(field, node, reexecution));
+            }
+        }
+        report.blocks += 1;
+    }
+    report.cache = provider.stats();
+
+    info!(
+        "Re-executed {} transactions in {} blocks: {} mismatches, {} skipped, cache {} hits, {} misses",
+        report.transactions,
+        report.blocks,
+        report.mismatches.len(),
+        report.skipped.len(),
+        report.cache.hits,
+        report.cache.misses
+    );
+    for mismatch in &report.mismatches {
+        warn!(
+            "Block {}, transaction {:#x}: {} differs, node {} vs re-execution {}",
+            mismatch.block_number, mismatch.transaction_hash, mismatch.field, mismatch.node, mismatch.reexecution
+        );
+    }
+    Ok(report)
+}
+
+async fn chain_id<P: Provider>(provider: &P) -> Result<ChainId, ReexecutionError> {
+    let chain_id = provider.chain_id().await.map_err(rpc_error("starknet_chainId"))?;
+    [ChainId::Mainnet, ChainId::Testnet]
+        .into_iter()
+        .find(|supported| Felt::from_hex(&supported.to_felt().to_prefixed_hex_str()).ok() == Some(chain_id))
+        .ok_or(ReexecutionError::UnsupportedChainId(chain_id))
+}
+
+fn rpc_error(method: &str) -> impl Fn(ProviderError) -> ReexecutionError + '_ {
+    move |e| ReexecutionError::Rpc { method: method.to_string(), reason: e.to_string() }
+}
+
+fn felt(value: &Value) -> Felt {
+    value.as_str().and_then(|hex| Felt::from_hex(hex).ok()).unwrap_or_default()
+}
@@ -0,0 +1,156 @@
+//! Request timing: once enabled, the HTTP transport records where the time of every JSON-RPC
+//! request goes, by method: serializing the request, connecting and waiting for the first byte of
+//! the response, downloading the body, and decoding and deserializing it. `reqwest` doesn't tell
+//! when the connection of a request is ready, so connecting and waiting are one phase: the node's
+//! processing, plus the TCP and TLS setup of requests that open a new connection. DNS lookups are
+//! recorded separately by host; as `reqwest` resolves once per new connection, their count is the
+//! number of connections opened. Together they tell a slow node from network or client overhead.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use serde::Serialize;
+use tracing::{error, info};
+
+use super::secrets::write_redacted;
+
+static TIMINGS: OnceLock<Mutex<RequestTimingReport>> = OnceLock::new();
+
+/// Starts recording request timings. Returns `false` if tracking was already enabled.
+pub fn enable_request_timing() -> bool {
+    TIMINGS.set(Mutex::new(RequestTimingReport::default())).is_ok()
+}
+
+/// Phases of a single request.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestTiming {
+    pub serialize: Duration,
+    /// From the request being sent until the first byte of the response, including the setup of
+    /// a new connection.
+    pub connect_and_wait: Duration,
+    pub download: Duration,
+    pub deserialize: Duration,
+}
+
+/// Sums of the phases of the requests of a method, in microseconds.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MethodTimings {
+    pub requests: u64,
+    pub serialize_micros: u64,
+    pub connect_and_wait_micros: u64,
+    pub download_micros: u64,
+    pub deserialize_micros: u64,
+    pub slowest_connect_and_wait_micros: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DnsTimings {
+    pub lookups: u64,
+    pub lookup_micros: u64,
+    pub slowest_lookup_micros: u64,
+}
+
+fn record(update: impl FnOnce(&mut RequestTimingReport)) {
+    if let Some(timings) = TIMINGS.get() {
+        update(&mut timings.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+    }
+}
+
+/// Records a request, does nothing unless tracking is enabled.
+pub fn record_request_timing(method: &str, timing: RequestTiming) {
+    record(|report| {
+        let entry = report.methods.entry(method.to_string()).or_default();
+        let connect_and_wait = timing.connect_and_wait.as_micros() as u64;
+        entry.requests += 1;
+        entry.serialize_micros += timing.serialize.as_micros() as u64;
+        entry.connect_and_wait_micros += connect_and_wait;
+        entry.download_micros += timing.download.as_micros() as u64;
+        entry.deserialize_micros += timing.deserialize.as_micros() as u64;
+        entry.slowest_connect_and_wait_micros = entry.slowest_connect_and_wait_micros.max(connect_and_wait);
+    });
+}
+
+fn record_dns_lookup(host: &str, duration: Duration) {
+    record(|report| {
+        let entry = report.dns.entry(host.to_string()).or_default();
+        let micros = duration.as_micros() as u64;
+        entry.lookups += 1;
+        entry.lookup_micros += micros;
+        entry.slowest_lookup_micros = entry.slowest_lookup_micros.max(micros);
+    });
+}
+
+/// DNS resolver of the system that records the time of its lookups, for clients built with
+/// [HttpClientConfig::time_dns_lookups](crate::utils::v7::providers::jsonrpc::HttpClientConfig).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimingResolver;
+
+impl Resolve for TimingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let started = Instant::now();
+            // The port is replaced by the one of the URL.
+            let addrs = tokio::net::lookup_host((name.as_str(), 0)).await?;
+            record_dns_lookup(name.as_str(), started.elapsed());
+            Ok(Box::new(addrs) as Addrs)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RequestTimingReport {
+    pub methods: BTreeMap<String, MethodTimings>,
+    /// DNS lookups by host.
+    pub dns: BTreeMap<String, DnsTimings>,
+}
+
+impl RequestTimingReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        let mean = |micros: u64, count: u64| Duration::from_micros(micros / count.max(1));
+        for (method, timings) in &self.methods {
+            info!(
+                "{}: {} requests, mean serialize {:?}, connect and wait {:?} (slowest {:?}), download {:?}, \
+                 deserialize {:?}",
+                method,
+                timings.requests,
+                mean(timings.serialize_micros, timings.requests),
+                mean(timings.connect_and_wait_micros, timings.requests),
+                Duration::from_micros(timings.slowest_connect_and_wait_micros),
+                mean(timings.download_micros, timings.requests),
+                mean(timings.deserialize_micros, timings.requests)
+            );
+        }
+        for (host, timings) in &self.dns {
+            info!(
+                "DNS {}: {} lookups, mean {:?}, slowest {:?}",
+                host,
+                timings.lookups,
+                mean(timings.lookup_micros, timings.lookups),
+                Duration::from_micros(timings.slowest_lookup_micros)
+            );
+        }
+    }
+}
+
+/// Returns `None` unless tracking is enabled.
+pub fn request_timing_report() -> Option<RequestTimingReport> {
+    Some(TIMINGS.get()?.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone())
+}
+
+/// Logs the request timings and writes them to `path`, if they were recorded.
+pub fn write_timing_report(path: Option<&Path>) {
+    if let Some((path, report)) = path.zip(request_timing_report()) {
+        report.log_summary();
+        if let Err(e) = report.write(path) {
+            error!("Could not write request timing report {}: {}", path.display(), e);
+        }
+    }
+}
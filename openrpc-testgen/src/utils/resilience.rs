@@ -0,0 +1,187 @@
+//! Resilience mode: before each test case every node of the run is checked to be reachable. When
+//! one is not, e.g. because it crashed or is restarting, the run waits for it to come back,
+//! re-validates its chain id and continues, instead of failing every remaining test case with a
+//! connection error. The nodes are checked again after a failed test case, which is retried once if
+//! one of them went down during it.
+//!
+//! An optional [RestartHook] restarts a dockerized node before selected test cases, to test how
+//! the node recovers, in particular its pending transactions, from a crash.
+
+use std::collections::BTreeSet;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use starknet_types_core::felt::Felt;
+use thiserror::Error;
+use tokio::process::Command;
+use tracing::{info, warn};
+use url::Url;
+
+use super::v7::providers::{
+    jsonrpc::{HttpTransport, JsonRpcClient},
+    provider::Provider,
+};
+
+#[derive(Debug, Error)]
+pub enum ResilienceError {
+    #[error("Node {url} did not come back within {}s", max_downtime.as_secs())]
+    NodeUnavailable { url: Url, max_downtime: Duration },
+    #[error("Node {url} chain id changed from {expected:#x} to {actual:#x}")]
+    ChainIdChanged { url: Url, expected: Felt, actual: Felt },
+    #[error("Could not restart container {container}: {reason}")]
+    Restart { container: String, reason: String },
+}
+
+/// Restarts a docker container before the test cases whose `suite::name` contains one of
+/// `before_cases`.
+#[derive(Debug, Clone)]
+pub struct RestartHook {
+    pub container: String,
+    pub before_cases: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResilienceConfig {
+    /// Every node the test cases send requests to.
+    pub urls: Vec<Url>,
+    /// How long the node may be unreachable before the test case fails.
+    pub max_downtime: Duration,
+    pub poll_interval: Duration,
+    pub restart_hook: Option<RestartHook>,
+}
+
+impl ResilienceConfig {
+    pub fn new(urls: Vec<Url>) -> Self {
+        Self { urls, max_downtime: Duration::from_secs(300), poll_interval: Duration::from_secs(2), restart_hook: None }
+    }
+}
+
+/// The configuration, with a provider for each of its nodes.
+static RESILIENCE_CONFIG: OnceLock<(ResilienceConfig, Vec<JsonRpcClient<HttpTransport>>)> = OnceLock::new();
+/// Chain id of the nodes the first time one of them was reached.
+static CHAIN_ID: OnceLock<Felt> = OnceLock::new();
+/// Test cases retried after a node went down during them, each one is retried only once.
+static RETRIED_CASES: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Enables resilience mode. Returns `false` if the configuration has already been set.
+pub fn set_resilience_config(config: ResilienceConfig) -> bool {
+    let providers = config.urls.iter().map(|url| JsonRpcClient::new(HttpTransport::new(url.clone()))).collect();
+    RESILIENCE_CONFIG.set((config, providers)).is_ok()
+}
+
+/// Run before each selected test case, does nothing unless resilience mode is enabled.
+pub async fn before_test_case(suite: &str, name: &str) -> Result<(), ResilienceError> {
+    let Some((config, providers)) = RESILIENCE_CONFIG.get() else {
+        return Ok(());
+    };
+
+    if let Some(hook) = &config.restart_hook {
+        let qualified_name = format!("{suite}::{name}");
+        if hook.before_cases.iter().any(|pattern| qualified_name.contains(pattern.as_str())) {
+            restart_container(&hook.container).await?;
+        }
+    }
+    wait_for_nodes(config, providers).await.map(|_| ())
+}
+
+/// Run after each failed test case. Returns `true` when the case should be run again, because a
+/// node was down after it failed and is back now, and it was not retried already. Always `false`
+/// unless resilience mode is enabled.
+pub async fn retry_after_failure(suite: &str, name: &str) -> bool {
+    let Some((config, providers)) = RESILIENCE_CONFIG.get() else {
+        return false;
+    };
+    match wait_for_nodes(config, providers).await {
+        Ok(true) => {
+            let qualified_name = format!("{suite}::{name}");
+            let retry = RETRIED_CASES.lock().unwrap().insert(qualified_name);
+            if retry {
+                info!("🔁 Retrying test case {}::{}, a node went down during it", suite, name);
+            }
+            retry
+        }
+        Ok(false) => false,
+        Err(e) => {
+            warn!("Nodes not available after test case {}::{} failed: {}", suite, name, e);
+            false
+        }
+    }
+}
+
+/// Restarts the container of the restart hook, for test cases that restart the node themselves.
+/// Returns `false` when no restart hook is configured.
+pub async fn restart_node() -> Result<bool, ResilienceError> {
+    let Some((config, providers)) = RESILIENCE_CONFIG.get() else {
+        return Ok(false);
+    };
+    let Some(hook) = &config.restart_hook else {
+        return Ok(false);
+    };
+    restart_container(&hook.container).await?;
+    wait_for_nodes(config, providers).await?;
+    Ok(true)
+}
+
+async fn restart_container(container: &str) -> Result<(), ResilienceError> {
+    info!("🔁 Restarting container {}", container);
+    let output = Command::new("docker")
+        .args(["restart", container])
+        .output()
+        .await
+        .map_err(|e| ResilienceError::Restart { container: container.to_string(), reason: e.to_string() })?;
+    if !output.status.success() {
+        return Err(ResilienceError::Restart {
+            container: container.to_string(),
+            reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Waits for every node to answer, see [wait_for_node]. Returns whether one of them was down.
+async fn wait_for_nodes(
+    config: &ResilienceConfig,
+    providers: &[JsonRpcClient<HttpTransport>],
+) -> Result<bool, ResilienceError> {
+    let mut was_down = false;
+    for (url, provider) in config.urls.iter().zip(providers) {
+        was_down |= wait_for_node(config, url, provider).await?;
+    }
+    Ok(was_down)
+}
+
+/// Polls the chain id of the node at `url` until it answers, then checks it did not change.
+/// Returns whether the node was down.
+async fn wait_for_node(
+    config: &ResilienceConfig,
+    url: &Url,
+    provider: &JsonRpcClient<HttpTransport>,
+) -> Result<bool, ResilienceError> {
+    let started = Instant::now();
+    let mut reported = false;
+
+    let chain_id = loop {
+        match provider.chain_id().await {
+            Ok(chain_id) => break chain_id,
+            Err(e) if started.elapsed() < config.max_downtime => {
+                if !reported {
+                    warn!("Node {} is unreachable, waiting for it to come back: {}", url, e);
+                    reported = true;
+                }
+                tokio::time::sleep(config.poll_interval).await;
+            }
+            Err(_) => {
+                return Err(ResilienceError::NodeUnavailable { url: url.clone(), max_downtime: config.max_downtime });
+            }
+        }
+    };
+    if reported {
+        info!("Node {} is back after {}s", url, started.elapsed().as_secs());
+    }
+
+    let expected = *CHAIN_ID.get_or_init(|| chain_id);
+    if chain_id != expected {
+        return Err(ResilienceError::ChainIdChanged { url: url.clone(), expected, actual: chain_id });
+    }
+    Ok(reported)
+}
@@ -0,0 +1,126 @@
+//! Resource limits: the `resource_limits` test case has a generator contract emit more and more
+//! events, and write more and more storage cells, in a single invoke until the node rejects the
+//! transaction. The largest accepted and the smallest rejected count of each resource make a
+//! fingerprint of the limits of the node. Every step of the ramp is a transaction, so the case is
+//! opt-in and only runs once [set_resource_limits_config] was called.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use tracing::info;
+
+use super::secrets::write_redacted;
+
+#[derive(Debug, Clone)]
+pub struct ResourceLimitsConfig {
+    /// Count the ramp starts with, doubled at every step.
+    pub start: u32,
+    /// Last count of the ramp, the probe stops there even if the node accepts it.
+    pub max: u32,
+}
+
+static RESOURCE_LIMITS_CONFIG: OnceLock<ResourceLimitsConfig> = OnceLock::new();
+static RESOURCE_LIMITS: Mutex<BTreeMap<GeneratedResource, ResourceLimit>> = Mutex::new(BTreeMap::new());
+
+/// Enables the resource limits test case. Returns `false` if it was already enabled.
+pub fn set_resource_limits_config(config: ResourceLimitsConfig) -> bool {
+    RESOURCE_LIMITS_CONFIG.set(config).is_ok()
+}
+
+pub fn resource_limits_config() -> Option<&'static ResourceLimitsConfig> {
+    RESOURCE_LIMITS_CONFIG.get()
+}
+
+/// Resource the generator contract produces `count` of in a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratedResource {
+    Events,
+    StorageWrites,
+}
+
+impl GeneratedResource {
+    pub const ALL: [Self; 2] = [Self::Events, Self::StorageWrites];
+
+    /// Entrypoint of the generator contract producing the resource.
+    pub fn entrypoint(&self) -> &'static str {
+        match self {
+            Self::Events => "emit_events",
+            Self::StorageWrites => "write_storage",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResourceLimit {
+    pub largest_accepted: Option<u32>,
+    /// `None` if the node accepted every count of the ramp.
+    pub smallest_rejected: Option<u32>,
+    /// Error the transaction with `smallest_rejected` failed with.
+    pub rejection: Option<String>,
+}
+
+/// Counts of the ramp, doubling from `start` and ending with `max`.
+pub fn ramp(start: u32, max: u32) -> Vec<u32> {
+    let mut counts = Vec::new();
+    let mut count = start.max(1);
+    while count < max {
+        counts.push(count);
+        count = count.saturating_mul(2);
+    }
+    counts.push(max);
+    counts
+}
+
+pub fn record_resource_limit(resource: GeneratedResource, limit: ResourceLimit) {
+    RESOURCE_LIMITS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(resource, limit);
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceLimitsReport {
+    pub limits: BTreeMap<GeneratedResource, ResourceLimit>,
+}
+
+impl ResourceLimitsReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        let format_count = |count: Option<u32>| count.map_or("none".to_string(), |count| count.to_string());
+        for (resource, limit) in &self.limits {
+            info!(
+                "{:?} per invoke: largest accepted {}, smallest rejected {}",
+                resource,
+                format_count(limit.largest_accepted),
+                format_count(limit.smallest_rejected)
+            );
+            if let Some(rejection) = &limit.rejection {
+                info!("  rejected with: {}", rejection);
+            }
+        }
+    }
+}
+
+/// Returns `None` unless the resource limits test case is enabled.
+pub fn resource_limits_report() -> Option<ResourceLimitsReport> {
+    resource_limits_config()?;
+    let limits = RESOURCE_LIMITS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    Some(ResourceLimitsReport { limits })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ramp_doubles_up_to_max() {
+        assert_eq!(ramp(16, 100), vec![16, 32, 64, 100]);
+        assert_eq!(ramp(16, 64), vec![16, 32, 64]);
+        assert_eq!(ramp(0, 4), vec![1, 2, 4]);
+        assert_eq!(ramp(8, 4), vec![4]);
+    }
+}
@@ -0,0 +1,212 @@
+//! Normalization of RPC responses before nodes are compared with each other, so that differences
+//! in formatting, or in the order of lists whose order carries no meaning, are not reported as
+//! differences. Normalizers are configured per method, e.g.
+//! `starknet_getStateUpdate=hex,sort-state-diff`.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use super::snapshot::normalize_hex;
+
+/// Method whose normalizers apply to every method without normalizers of its own.
+pub const ALL_METHODS: &str = "*";
+
+/// Lists of a state diff, which the spec doesn't order.
+const STATE_DIFF_LISTS: [&str; 7] = [
+    "storage_diffs",
+    "storage_entries",
+    "deprecated_declared_classes",
+    "declared_classes",
+    "deployed_contracts",
+    "replaced_classes",
+    "nonces",
+];
+
+#[derive(Debug, Clone)]
+pub enum Normalizer {
+    /// Hex strings in lowercase and without leading zeros.
+    Hex,
+    /// Sorts every `events` list, for comparing which events were emitted regardless of order.
+    SortEvents,
+    /// Sorts the lists of state diffs.
+    SortStateDiff,
+    /// Removes a field at any depth, e.g. one only some nodes return.
+    Strip(String),
+    /// Normalization not covered by the other variants, for library users.
+    Custom(fn(Value) -> Value),
+}
+
+impl Normalizer {
+    pub fn apply(&self, value: Value) -> Value {
+        match self {
+            Normalizer::Hex => normalize_hex(&value),
+            Normalizer::SortEvents => sort_lists(value, &["events"]),
+            Normalizer::SortStateDiff => sort_lists(value, &STATE_DIFF_LISTS),
+            Normalizer::Strip(field) => strip_field(value, field),
+            Normalizer::Custom(normalize) => normalize(value),
+        }
+    }
+}
+
+impl Display for Normalizer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Normalizer::Hex => write!(f, "hex"),
+            Normalizer::SortEvents => write!(f, "sort-events"),
+            Normalizer::SortStateDiff => write!(f, "sort-state-diff"),
+            Normalizer::Strip(field) => write!(f, "strip:{field}"),
+            Normalizer::Custom(_) => write!(f, "custom"),
+        }
+    }
+}
+
+impl FromStr for Normalizer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hex" => Ok(Normalizer::Hex),
+            "sort-events" => Ok(Normalizer::SortEvents),
+            "sort-state-diff" => Ok(Normalizer::SortStateDiff),
+            _ => match s.strip_prefix("strip:") {
+                Some(field) if !field.is_empty() => Ok(Normalizer::Strip(field.to_string())),
+                _ => Err(format!(
+                    "Unknown normalizer {s:?}, expected hex, sort-events, sort-state-diff or strip:<field>"
+                )),
+            },
+        }
+    }
+}
+
+/// Normalizers of one method, parsed from `METHOD=NORMALIZER[,NORMALIZER...]`; [ALL_METHODS]
+/// stands for every method and an empty list compares the method's responses as they are.
+#[derive(Debug, Clone)]
+pub struct MethodNormalizers {
+    pub method: String,
+    pub normalizers: Vec<Normalizer>,
+}
+
+impl FromStr for MethodNormalizers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (method, normalizers) =
+            s.split_once('=').ok_or_else(|| format!("Expected METHOD=NORMALIZER[,NORMALIZER...], got {s:?}"))?;
+        let normalizers = normalizers
+            .split(',')
+            .filter(|normalizer| !normalizer.is_empty())
+            .map(Normalizer::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { method: method.to_string(), normalizers })
+    }
+}
+
+/// Normalizers applied, in order, to the responses of each method.
+#[derive(Debug, Clone)]
+pub struct ResponseNormalizers {
+    /// Applied to methods without normalizers of their own.
+    pub default: Vec<Normalizer>,
+    pub methods: HashMap<String, Vec<Normalizer>>,
+}
+
+impl Default for ResponseNormalizers {
+    fn default() -> Self {
+        let methods =
+            HashMap::from([("starknet_getStateUpdate".to_string(), vec![Normalizer::Hex, Normalizer::SortStateDiff])]);
+        Self { default: vec![Normalizer::Hex], methods }
+    }
+}
+
+impl ResponseNormalizers {
+    /// Replaces the normalizers of `method`, or the default ones for [ALL_METHODS].
+    pub fn set(&mut self, method_normalizers: MethodNormalizers) {
+        let MethodNormalizers { method, normalizers } = method_normalizers;
+        if method == ALL_METHODS {
+            self.default = normalizers;
+        } else {
+            self.methods.insert(method, normalizers);
+        }
+    }
+
+    pub fn for_method(&self, method: &str) -> &[Normalizer] {
+        self.methods.get(method).unwrap_or(&self.default)
+    }
+
+    pub fn normalize(&self, method: &str, value: Value) -> Value {
+        self.for_method(method).iter().fold(value, |value, normalizer| normalizer.apply(value))
+    }
+}
+
+/// Sorts, at any depth, the lists in the fields named `lists` by the JSON of their items.
+fn sort_lists(value: Value, lists: &[&str]) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = match sort_lists(value, lists) {
+                        Value::Array(mut items) if lists.contains(&key.as_str()) => {
+                            items.sort_by_cached_key(|item| item.to_string());
+                            Value::Array(items)
+                        }
+                        value => value,
+                    };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(|item| sort_lists(item, lists)).collect()),
+        value => value,
+    }
+}
+
+fn strip_field(value: Value, field: &str) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .filter(|(key, _)| key != field)
+                .map(|(key, value)| (key, strip_field(value, field)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(|item| strip_field(item, field)).collect()),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn normalizes_per_method() {
+        let mut normalizers = ResponseNormalizers::default();
+        normalizers.set("starknet_getBlockWithReceipts=hex,strip:l1_da_mode,sort-events".parse().unwrap());
+
+        let block = json!({
+            "l1_da_mode": "BLOB",
+            "transactions": [{ "receipt": { "events": [{ "from_address": "0x02" }, { "from_address": "0x1" }] } }],
+        });
+        let events = json!([{ "from_address": "0x1" }, { "from_address": "0x2" }]);
+        assert_eq!(
+            normalizers.normalize("starknet_getBlockWithReceipts", block.clone()),
+            json!({ "transactions": [{ "receipt": { "events": events } }] })
+        );
+        assert_eq!(normalizers.normalize("starknet_getBlockWithTxs", block)["l1_da_mode"], json!("BLOB"));
+
+        let state_update = json!({ "state_diff": { "nonces": [{ "nonce": "0x2" }, { "nonce": "0x01" }] } });
+        assert_eq!(
+            normalizers.normalize("starknet_getStateUpdate", state_update),
+            json!({ "state_diff": { "nonces": [{ "nonce": "0x1" }, { "nonce": "0x2" }] } })
+        );
+
+        assert!("starknet_chainId=unknown".parse::<MethodNormalizers>().is_err());
+        normalizers.set("*=".parse().unwrap());
+        assert_eq!(normalizers.normalize("starknet_chainId", json!("0x0A")), json!("0x0A"));
+    }
+}
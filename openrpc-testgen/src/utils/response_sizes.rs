@@ -0,0 +1,78 @@
+//! Response size tracking: once enabled, the HTTP transport records the size of every JSON-RPC
+//! response by method, both as received and once decoded, along with how many of them the node
+//! compressed. It shows which methods would gain from compression and whether the node uses it.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use tracing::info;
+
+use super::secrets::write_redacted;
+
+static SIZES: OnceLock<Mutex<BTreeMap<String, MethodResponseSizes>>> = OnceLock::new();
+
+/// Starts recording response sizes. Returns `false` if tracking was already enabled.
+pub fn enable_response_size_tracking() -> bool {
+    SIZES.set(Mutex::new(BTreeMap::new())).is_ok()
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MethodResponseSizes {
+    pub responses: u64,
+    /// Responses with a `Content-Encoding` other than `identity`.
+    pub compressed_responses: u64,
+    /// Bytes of the bodies as received.
+    pub received_bytes: u64,
+    /// Bytes of the bodies once decoded.
+    pub decoded_bytes: u64,
+    pub largest_decoded_bytes: u64,
+}
+
+/// Records a response, does nothing unless tracking is enabled.
+pub fn record_response_size(method: &str, content_encoding: Option<&str>, received: usize, decoded: usize) {
+    if let Some(sizes) = SIZES.get() {
+        let mut sizes = sizes.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let entry = sizes.entry(method.to_string()).or_default();
+        entry.responses += 1;
+        if content_encoding.is_some_and(|encoding| !encoding.eq_ignore_ascii_case("identity")) {
+            entry.compressed_responses += 1;
+        }
+        entry.received_bytes += received as u64;
+        entry.decoded_bytes += decoded as u64;
+        entry.largest_decoded_bytes = entry.largest_decoded_bytes.max(decoded as u64);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseSizeReport {
+    pub methods: BTreeMap<String, MethodResponseSizes>,
+}
+
+impl ResponseSizeReport {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        for (method, sizes) in &self.methods {
+            info!(
+                "{}: {} responses, {} compressed, {} bytes received, {} bytes decoded, largest {} bytes",
+                method,
+                sizes.responses,
+                sizes.compressed_responses,
+                sizes.received_bytes,
+                sizes.decoded_bytes,
+                sizes.largest_decoded_bytes
+            );
+        }
+    }
+}
+
+/// Returns `None` unless tracking is enabled.
+pub fn response_size_report() -> Option<ResponseSizeReport> {
+    let methods = SIZES.get()?.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    Some(ResponseSizeReport { methods })
+}
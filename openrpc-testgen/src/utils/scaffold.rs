@@ -0,0 +1,259 @@
+//! Scaffolding generated from the official OpenRPC document, keeping the suites in lock-step with
+//! spec releases:
+//! - methods the provider does not implement get a raw JSON-RPC endpoint stub in
+//!   `utils/v7/endpoints/scaffold.rs`,
+//! - methods no test of a suite calls get a test case skeleton registered with `#[openrpc_test]`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// The parts of an OpenRPC document needed for scaffolding.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenRpcDocument {
+    #[serde(default)]
+    pub info: OpenRpcInfo,
+    pub methods: Vec<OpenRpcMethod>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OpenRpcInfo {
+    #[serde(default)]
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenRpcMethod {
+    pub name: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub params: Vec<OpenRpcParam>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenRpcParam {
+    pub name: String,
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl OpenRpcDocument {
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl OpenRpcMethod {
+    /// Rust name of the method, e.g. `get_block_with_tx_hashes` for
+    /// `starknet_getBlockWithTxHashes`.
+    pub fn snake_name(&self) -> String {
+        let name = self.name.split_once('_').map_or(self.name.as_str(), |(_, name)| name);
+        let mut snake = String::with_capacity(name.len() + 8);
+        for c in name.chars() {
+            if c.is_ascii_uppercase() {
+                if !snake.is_empty() {
+                    snake.push('_');
+                }
+                snake.push(c.to_ascii_lowercase());
+            } else {
+                snake.push(c);
+            }
+        }
+        snake
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScaffoldOptions {
+    /// `providers/provider.rs`, whose methods count as implemented endpoints.
+    pub provider_path: PathBuf,
+    /// File the endpoint stubs are (re)written to.
+    pub endpoints_path: PathBuf,
+    /// Suite directory whose sources are searched for calls, and where skeletons are written.
+    pub suite_dir: PathBuf,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ScaffoldReport {
+    /// Methods without a provider implementation, stubbed in the endpoints file.
+    pub stubbed_methods: Vec<String>,
+    /// Methods no test of the suite calls.
+    pub untested_methods: Vec<String>,
+    /// Skeletons written for untested methods; existing files are never overwritten.
+    pub created_tests: Vec<PathBuf>,
+}
+
+impl ScaffoldReport {
+    /// Whether the sources already cover every method of the document.
+    pub fn is_up_to_date(&self) -> bool {
+        self.stubbed_methods.is_empty() && self.untested_methods.is_empty()
+    }
+}
+
+/// Compares the document with the sources without writing anything.
+pub fn check_scaffolding(document: &OpenRpcDocument, options: &ScaffoldOptions) -> io::Result<ScaffoldReport> {
+    let provider_source = fs::read_to_string(&options.provider_path)?;
+    let suite_sources = read_sources(&options.suite_dir)?;
+
+    let mut report = ScaffoldReport::default();
+    for method in &document.methods {
+        let snake_name = method.snake_name();
+        if !provider_source.contains(&format!("fn {snake_name}(")) {
+            report.stubbed_methods.push(method.name.clone());
+        }
+        let is_called = suite_sources.iter().any(|(path, source)| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|stem| stem.starts_with(&format!("test_{snake_name}")))
+                || calls(source, &snake_name)
+        });
+        if !is_called {
+            report.untested_methods.push(method.name.clone());
+        }
+    }
+    Ok(report)
+}
+
+/// Rewrites the endpoint stubs and adds skeletons for untested methods.
+pub fn generate_scaffolding(document: &OpenRpcDocument, options: &ScaffoldOptions) -> io::Result<ScaffoldReport> {
+    let mut report = check_scaffolding(document, options)?;
+
+    let stubbed: Vec<&OpenRpcMethod> =
+        document.methods.iter().filter(|method| report.stubbed_methods.contains(&method.name)).collect();
+    fs::write(&options.endpoints_path, render_endpoint_stubs(&document.info.version, &stubbed))?;
+
+    let suite_name = options
+        .suite_dir
+        .file_name()
+        .and_then(|s| s.to_str())
+        .map(|dir_name| dir_name.trim_start_matches("suite_").to_string())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "suite directory has no name"))?;
+    let suite_struct = find_testsuite_struct(&options.suite_dir.join("mod.rs"))?;
+
+    for method in document.methods.iter().filter(|method| report.untested_methods.contains(&method.name)) {
+        let path = options.suite_dir.join(format!("test_{}.rs", method.snake_name()));
+        if path.exists() {
+            continue;
+        }
+        let is_stubbed = report.stubbed_methods.contains(&method.name);
+        fs::write(&path, render_test_skeleton(method, &suite_name, &suite_struct, is_stubbed))?;
+        report.created_tests.push(path);
+    }
+    Ok(report)
+}
+
+/// Whether `source` calls a function or method named `name`, e.g. `provider.get_nonce(`.
+fn calls(source: &str, name: &str) -> bool {
+    let call = format!("{name}(");
+    source.match_indices(&call).any(|(index, _)| {
+        !source[..index].ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+            && !source[..index].trim_end().ends_with("fn")
+    })
+}
+
+fn read_sources(dir: &Path) -> io::Result<Vec<(PathBuf, String)>> {
+    let mut sources = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            sources.extend(read_sources(&path)?);
+        } else if path.extension().and_then(|s| s.to_str()) == Some("rs") {
+            let source = fs::read_to_string(&path)?;
+            sources.push((path, source));
+        }
+    }
+    Ok(sources)
+}
+
+fn find_testsuite_struct(mod_path: &Path) -> io::Result<String> {
+    fs::read_to_string(mod_path)?
+        .lines()
+        .find_map(|line| line.strip_prefix("pub struct TestSuite"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .map(|rest| format!("TestSuite{}", rest.trim_end_matches(['{', ';'])))
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("no TestSuite struct in {}", mod_path.display()))
+        })
+}
+
+fn param_ident(name: &str) -> String {
+    match name {
+        "type" | "match" | "ref" | "move" | "loop" | "impl" | "trait" | "struct" | "enum" | "mod" => {
+            format!("r#{name}")
+        }
+        _ => name.to_string(),
+    }
+}
+
+fn render_endpoint_stubs(spec_version: &str, methods: &[&OpenRpcMethod]) -> String {
+    let mut out = format!(
+        "// @generated by `openrpc-scaffold` from the OpenRPC document {spec_version}. Do not edit: implement the\n\
+         // methods in the provider instead, after which their stubs are no longer generated.\n"
+    );
+    if methods.is_empty() {
+        return out;
+    }
+
+    out.push_str(
+        "\nuse serde_json::{json, Value};\n\nuse super::errors::OpenRpcTestGenError;\n\
+         use crate::utils::v7::providers::provider::Provider;\n",
+    );
+    for method in methods {
+        let params: Vec<String> = method.params.iter().map(|param| param_ident(&param.name)).collect();
+        let signature: String = params.iter().map(|param| format!(", {param}: Value")).collect();
+        let fields: Vec<String> =
+            method.params.iter().zip(&params).map(|(param, ident)| format!("{:?}: {ident}", param.name)).collect();
+
+        out.push('\n');
+        out.push_str(&format!("/// `{}`", method.name));
+        if let Some(summary) = &method.summary {
+            out.push_str(&format!(": {}", summary.trim()));
+        }
+        out.push('\n');
+        out.push_str(&format!(
+            "pub async fn {}<P: Provider>(provider: &P{signature}) -> Result<Value, OpenRpcTestGenError> {{\n    \
+             Ok(provider.send_raw({:?}, json!({{ {} }})).await?)\n}}\n",
+            method.snake_name(),
+            method.name,
+            fields.join(", ")
+        ));
+    }
+    out
+}
+
+fn render_test_skeleton(method: &OpenRpcMethod, suite_name: &str, suite_struct: &str, is_stubbed: bool) -> String {
+    let snake_name = method.snake_name();
+    let endpoint = if is_stubbed {
+        format!("crate::utils::v7::endpoints::scaffold::{snake_name}")
+    } else {
+        format!("Provider::{snake_name}")
+    };
+    let params: String = method
+        .params
+        .iter()
+        .map(|param| format!("//   - {}{}\n", param.name, if param.required { "" } else { " (optional)" }))
+        .collect();
+
+    format!(
+        "use crate::{{openrpc_test, utils::v7::endpoints::errors::OpenRpcTestGenError, RunnableTrait}};\n\
+         \n\
+         // Scaffolded by `openrpc-scaffold` for `{method_name}`, to be called through `{endpoint}` with:\n\
+         {params}\
+         #[derive(Clone, Debug)]\n\
+         pub struct TestCase {{}}\n\
+         \n\
+         #[openrpc_test(suite = \"{suite_name}\", name = \"{snake_name}\")]\n\
+         impl RunnableTrait for TestCase {{\n    \
+             type Input = super::{suite_struct};\n\
+         \n    \
+             async fn run(_test_input: &Self::Input) -> Result<Self, OpenRpcTestGenError> {{\n        \
+                 Err(OpenRpcTestGenError::Other(\"{method_name} is not covered yet\".to_string()))\n    \
+             }}\n\
+         }}\n",
+        method_name = method.name,
+    )
+}
@@ -0,0 +1,87 @@
+//! Redaction of secrets, e.g. private keys, from logs and reports. Secrets are registered once,
+//! usually when the configuration is loaded, and every occurrence of them is then replaced with
+//! `<redacted>` in what goes through [redact]: the runner's log output and the reports it writes.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use starknet_types_core::felt::Felt;
+
+pub(crate) const REDACTED: &str = "<redacted>";
+
+static SECRETS: OnceLock<Mutex<Vec<String>>> = OnceLock::new();
+
+fn secrets() -> std::sync::MutexGuard<'static, Vec<String>> {
+    SECRETS.get_or_init(Default::default).lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Registers `secret` for redaction. Felts are also redacted in the forms they are formatted in,
+/// hex without leading zeros and decimal.
+pub fn register_secret(secret: &str) {
+    let mut forms = vec![secret.to_string()];
+    let felt = if secret.starts_with("0x") { Felt::from_hex(secret).ok() } else { Felt::from_dec_str(secret).ok() };
+    if let Some(felt) = felt {
+        forms.extend([format!("{felt:#x}"), format!("{felt:x}"), felt.to_string()]);
+    }
+
+    let mut secrets = secrets();
+    // Tiny values such as `0x1` would redact half of every log line.
+    for form in forms.into_iter().filter(|form| form.len() >= 8) {
+        if !secrets.contains(&form) {
+            secrets.push(form);
+        }
+    }
+    // Longer forms first, so that a form containing another one is replaced as a whole.
+    secrets.sort_by_key(|form| std::cmp::Reverse(form.len()));
+}
+
+pub fn redact(text: &str) -> String {
+    secrets().iter().fold(text.to_string(), |text, secret| text.replace(secret.as_str(), REDACTED))
+}
+
+/// Writes `contents` to `path` with the secrets redacted.
+pub fn write_redacted(path: &Path, contents: &str) -> io::Result<()> {
+    fs::write(path, redact(contents))
+}
+
+/// Redacts everything written through it, e.g. the output of the log subscriber.
+#[derive(Debug)]
+pub struct RedactingWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> RedactingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    /// Secrets split across two writes are not redacted, log lines are written at once.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write_all(redact(&String::from_utf8_lossy(buf)).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_every_form_of_a_felt_secret() {
+        register_secret("0x00c5b2fcab997346f3ea1c00b002ecf6f382c5f9c9659a3894eb783c5320f912");
+        let felt = Felt::from_hex_unchecked("0xc5b2fcab997346f3ea1c00b002ecf6f382c5f9c9659a3894eb783c5320f912");
+
+        assert_eq!(redact(&format!("key {felt:#x}")), "key <redacted>");
+        assert_eq!(redact(&format!("key {felt}")), "key <redacted>");
+        assert_eq!(redact("key 0x00c5b2fcab997346f3ea1c00b002ecf6f382c5f9c9659a3894eb783c5320f912"), "key <redacted>");
+        assert_eq!(redact("nothing to hide"), "nothing to hide");
+    }
+}
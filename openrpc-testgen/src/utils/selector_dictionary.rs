@@ -0,0 +1,90 @@
+//! Reverse lookup of entrypoint selectors and event keys to their names, built from the ABIs of
+//! the contract artifacts loaded by the tests. The dictionary of the run is kept by
+//! [display](super::display), which renders selectors and event keys with it.
+
+use std::collections::HashMap;
+
+use starknet_types_core::felt::Felt;
+
+use super::v7::contract::{AbiEntry, AbiEvent, EventFieldKind, SierraClass, TypedAbiEvent};
+use super::v7::endpoints::utils::get_selector_from_name;
+
+/// Names of entrypoint selectors and event keys declared in contract ABIs.
+#[derive(Debug, Default, Clone)]
+pub struct SelectorDictionary {
+    entrypoints: HashMap<Felt, String>,
+    events: HashMap<Felt, String>,
+}
+
+impl SelectorDictionary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ingest_sierra_class(&mut self, class: &SierraClass) {
+        self.ingest_abi(&class.abi);
+    }
+
+    /// Adds the functions, constructors, L1 handlers and events of `abi`, including the ones
+    /// nested in interfaces.
+    pub fn ingest_abi(&mut self, abi: &[AbiEntry]) {
+        for entry in abi {
+            match entry {
+                AbiEntry::Function(function) | AbiEntry::L1Handler(function) => {
+                    insert_name(&mut self.entrypoints, &function.name)
+                }
+                AbiEntry::Constructor(constructor) => insert_name(&mut self.entrypoints, &constructor.name),
+                AbiEntry::Interface(interface) => self.ingest_abi(&interface.items),
+                AbiEntry::Event(event) => self.ingest_event(event),
+                AbiEntry::Struct(_) | AbiEntry::Enum(_) | AbiEntry::Impl(_) => {}
+            }
+        }
+    }
+
+    /// Adds an entrypoint by name, e.g. one of the accounts and tokens whose ABIs aren't loaded.
+    pub fn insert_entrypoint(&mut self, name: &str) {
+        insert_name(&mut self.entrypoints, name);
+    }
+
+    fn ingest_event(&mut self, event: &AbiEvent) {
+        match event {
+            // The first key of a Cairo 2 event is the selector of its variant name in the contract's
+            // `Event` enum, which by convention matches the last segment of the event struct's path.
+            AbiEvent::Typed(TypedAbiEvent::Struct(event)) => insert_name(&mut self.events, last_segment(&event.name)),
+            AbiEvent::Typed(TypedAbiEvent::Enum(event)) => {
+                // Flat variants do not add a key of their own, their inner event's variants do.
+                for variant in event.variants.iter().filter(|variant| !matches!(variant.kind, EventFieldKind::Flat)) {
+                    insert_name(&mut self.events, &variant.name);
+                }
+            }
+            AbiEvent::Untyped(event) => insert_name(&mut self.events, &event.name),
+        }
+    }
+
+    pub fn entrypoint_name(&self, selector: &Felt) -> Option<&str> {
+        self.entrypoints.get(selector).map(String::as_str)
+    }
+
+    /// Name of the event whose first key is `key`.
+    pub fn event_name(&self, key: &Felt) -> Option<&str> {
+        self.events.get(key).map(String::as_str)
+    }
+
+    pub fn entrypoints(&self) -> impl Iterator<Item = (&Felt, &str)> {
+        self.entrypoints.iter().map(|(selector, name)| (selector, name.as_str()))
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = (&Felt, &str)> {
+        self.events.iter().map(|(key, name)| (key, name.as_str()))
+    }
+}
+
+fn insert_name(names: &mut HashMap<Felt, String>, name: &str) {
+    if let Ok(selector) = get_selector_from_name(name) {
+        names.entry(selector).or_insert_with(|| name.to_string());
+    }
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
@@ -0,0 +1,171 @@
+//! Comparison of the state diff of a simulated transaction with the state update of the block the
+//! transaction ends up in. The fee charged by the simulation is only an estimate, so the balance
+//! slots touched by fee transfers are left out of the comparison.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{
+    BlockId, BlockTag, MaybePendingBlockWithTxHashes, MaybePendingStateUpdate, SimulateTransactionsResult, StateDiff,
+    TransactionTrace,
+};
+
+use super::v7::endpoints::errors::NonAsciiNameError;
+use super::v7::endpoints::utils::get_storage_var_address;
+use super::v7::providers::provider::{Provider, ProviderError};
+
+/// Returns the state diff of a simulated transaction, if the node included one in the trace.
+pub fn simulated_state_diff(simulated: &SimulateTransactionsResult<Felt>) -> Option<&StateDiff<Felt>> {
+    match simulated.transaction_trace.as_ref()? {
+        TransactionTrace::Invoke(trace) => trace.state_diff.as_ref(),
+        TransactionTrace::Declare(trace) => trace.state_diff.as_ref(),
+        TransactionTrace::DeployAccount(trace) => trace.state_diff.as_ref(),
+        TransactionTrace::L1Handler(trace) => trace.state_diff.as_ref(),
+    }
+}
+
+/// A single value written by a state diff.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StateDiffEntry {
+    Storage { contract: Felt, key: Felt },
+    Nonce(Felt),
+    DeployedContract(Felt),
+    ReplacedClass(Felt),
+    DeclaredClass(Felt),
+    DeprecatedDeclaredClass(Felt),
+}
+
+impl fmt::Display for StateDiffEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateDiffEntry::Storage { contract, key } => write!(f, "storage {:#x} of {:#x}", key, contract),
+            StateDiffEntry::Nonce(contract) => write!(f, "nonce of {:#x}", contract),
+            StateDiffEntry::DeployedContract(address) => write!(f, "deployed contract {:#x}", address),
+            StateDiffEntry::ReplacedClass(contract) => write!(f, "replaced class of {:#x}", contract),
+            StateDiffEntry::DeclaredClass(class_hash) => write!(f, "declared class {:#x}", class_hash),
+            StateDiffEntry::DeprecatedDeclaredClass(class_hash) => {
+                write!(f, "deprecated declared class {:#x}", class_hash)
+            }
+        }
+    }
+}
+
+/// Flattens a state diff into the values it writes. Deployed and replaced contracts map to their
+/// class hash, declared classes to their compiled class hash and deprecated declared classes to zero.
+pub fn state_diff_entries(state_diff: &StateDiff<Felt>) -> BTreeMap<StateDiffEntry, Felt> {
+    let mut entries = BTreeMap::new();
+    for diff in &state_diff.storage_diffs {
+        for storage_entry in &diff.storage_entries {
+            if let Some(key) = storage_entry.key {
+                entries.insert(
+                    StateDiffEntry::Storage { contract: diff.address, key },
+                    storage_entry.value.unwrap_or_default(),
+                );
+            }
+        }
+    }
+    for nonce in &state_diff.nonces {
+        if let Some(contract) = nonce.contract_address {
+            entries.insert(StateDiffEntry::Nonce(contract), nonce.nonce.unwrap_or_default());
+        }
+    }
+    for deployed in &state_diff.deployed_contracts {
+        entries.insert(StateDiffEntry::DeployedContract(deployed.address), deployed.class_hash);
+    }
+    for replaced in &state_diff.replaced_classes {
+        entries.insert(StateDiffEntry::ReplacedClass(replaced.contract_address), replaced.class_hash);
+    }
+    for declared in &state_diff.declared_classes {
+        if let Some(class_hash) = declared.class_hash {
+            entries.insert(StateDiffEntry::DeclaredClass(class_hash), declared.compiled_class_hash.unwrap_or_default());
+        }
+    }
+    for class_hash in &state_diff.deprecated_declared_classes {
+        entries.insert(StateDiffEntry::DeprecatedDeclaredClass(*class_hash), Felt::ZERO);
+    }
+    entries
+}
+
+/// Storage slots of the `ERC20_balances` of `accounts` in `fee_token`, low and high parts.
+pub fn fee_transfer_entries(fee_token: Felt, accounts: &[Felt]) -> Result<BTreeSet<StateDiffEntry>, NonAsciiNameError> {
+    let mut entries = BTreeSet::new();
+    for account in accounts {
+        let low = get_storage_var_address("ERC20_balances", &[*account])?;
+        entries.insert(StateDiffEntry::Storage { contract: fee_token, key: low });
+        entries.insert(StateDiffEntry::Storage { contract: fee_token, key: low + Felt::ONE });
+    }
+    Ok(entries)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateDiffMismatch {
+    pub entry: StateDiffEntry,
+    pub simulated: Option<Felt>,
+    pub actual: Option<Felt>,
+}
+
+impl fmt::Display for StateDiffMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |value: Option<Felt>| value.map_or_else(|| "missing".to_string(), |value| format!("{:#x}", value));
+        write!(f, "{}: simulated {}, actual {}", self.entry, value(self.simulated), value(self.actual))
+    }
+}
+
+/// Compares a simulated state diff with the state diff of the block including the transaction,
+/// skipping the `ignored` entries. Every simulated value must be in the block with the same value;
+/// the block may write more when other transactions share it, so values only written by the block
+/// count as mismatches only when the transaction is `alone_in_block`.
+pub fn compare_state_diffs(
+    simulated: &StateDiff<Felt>,
+    actual: &StateDiff<Felt>,
+    alone_in_block: bool,
+    ignored: &BTreeSet<StateDiffEntry>,
+) -> Vec<StateDiffMismatch> {
+    let simulated = state_diff_entries(simulated);
+    let mut actual = state_diff_entries(actual);
+
+    let mut mismatches = Vec::new();
+    for (entry, simulated_value) in simulated {
+        let actual_value = actual.remove(&entry);
+        if !ignored.contains(&entry) && actual_value != Some(simulated_value) {
+            mismatches.push(StateDiffMismatch { entry, simulated: Some(simulated_value), actual: actual_value });
+        }
+    }
+    if alone_in_block {
+        mismatches.extend(
+            actual.into_iter().filter(|(entry, _)| !ignored.contains(entry)).map(|(entry, value)| StateDiffMismatch {
+                entry,
+                simulated: None,
+                actual: Some(value),
+            }),
+        );
+    }
+    mismatches
+}
+
+/// Returns the state diff of the latest or pending block including `transaction_hash`, along with
+/// the number of transactions in that block. Returns `None` if neither block includes it.
+pub async fn block_state_diff_of<P: Provider>(
+    provider: &P,
+    transaction_hash: Felt,
+) -> Result<Option<(StateDiff<Felt>, usize)>, ProviderError> {
+    for block_id in [BlockId::Tag(BlockTag::Latest), BlockId::Tag(BlockTag::Pending)] {
+        // The latest block is then queried by number, as another one may be produced in the meantime.
+        let (block_id, transactions) = match provider.get_block_with_tx_hashes(block_id).await? {
+            MaybePendingBlockWithTxHashes::Block(block) => {
+                (BlockId::Number(block.block_header.block_number), block.transactions)
+            }
+            MaybePendingBlockWithTxHashes::Pending(block) => (BlockId::Tag(BlockTag::Pending), block.transactions),
+        };
+        if !transactions.contains(&transaction_hash) {
+            continue;
+        }
+        let state_diff = match provider.get_state_update(block_id).await? {
+            MaybePendingStateUpdate::Block(update) => update.state_diff,
+            MaybePendingStateUpdate::Pending(update) => update.state_diff,
+        };
+        return Ok(Some((state_diff, transactions.len())));
+    }
+    Ok(None)
+}
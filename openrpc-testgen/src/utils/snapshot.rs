@@ -0,0 +1,230 @@
+//! Golden file snapshots of RPC responses. Responses are serialized to JSON, their volatile fields
+//! (hashes, block numbers, timestamps, prices...) are masked, and the result is compared with the
+//! golden file recorded by an earlier run. This flags nodes whose responses change shape across
+//! upgrades even when the typed deserialization still succeeds, e.g. because of new optional fields.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use starknet_types_core::felt::Felt;
+use thiserror::Error;
+use tracing::info;
+
+/// Fields whose values legitimately differ between runs and nodes.
+pub const VOLATILE_FIELDS: &[&str] = &[
+    "block_hash",
+    "block_number",
+    "parent_hash",
+    "new_root",
+    "old_root",
+    "timestamp",
+    "sequencer_address",
+    "starknet_version",
+    "l1_gas_price",
+    "l1_data_gas_price",
+    "transaction_hash",
+    "sender_address",
+    "contract_address",
+    "nonce",
+    "signature",
+    "continuation_token",
+];
+
+const MASK: &str = "<masked>";
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("Snapshot {path} is unusable: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Could not serialize the snapshotted value: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Response does not match snapshot {path}:\n{}", differences.join("\n"))]
+    Mismatch { path: PathBuf, differences: Vec<String> },
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotConfig {
+    /// Directory of the golden files.
+    pub dir: PathBuf,
+    /// Rewrites the golden files with the current responses instead of comparing them.
+    pub update: bool,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self { dir: PathBuf::from("snapshots"), update: false }
+    }
+}
+
+static SNAPSHOT_CONFIG: OnceLock<SnapshotConfig> = OnceLock::new();
+
+/// Returns `false` if the configuration has already been set.
+pub fn set_snapshot_config(config: SnapshotConfig) -> bool {
+    SNAPSHOT_CONFIG.set(config).is_ok()
+}
+
+fn snapshot_config() -> &'static SnapshotConfig {
+    SNAPSHOT_CONFIG.get_or_init(SnapshotConfig::default)
+}
+
+/// Replaces the values of the `masked` fields, at any depth, with a placeholder.
+pub fn mask_fields(value: Value, masked: &[&str]) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| {
+                    let value =
+                        if masked.contains(&key.as_str()) { Value::from(MASK) } else { mask_fields(value, masked) };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(|item| mask_fields(item, masked)).collect()),
+        value => value,
+    }
+}
+
+/// Reduces `value` to its shape: scalars become their JSON type name and arrays the sorted set of
+/// their items' shapes, so that only the structure of a response is compared, not its content.
+pub fn shape(value: &Value) -> Value {
+    match value {
+        Value::Null => Value::from("null"),
+        Value::Bool(_) => Value::from("bool"),
+        Value::Number(_) => Value::from("number"),
+        Value::String(_) => Value::from("string"),
+        Value::Array(items) => {
+            let mut shapes: Vec<Value> = Vec::new();
+            for item_shape in items.iter().map(shape) {
+                if !shapes.contains(&item_shape) {
+                    shapes.push(item_shape);
+                }
+            }
+            shapes.sort_by_key(|shape| shape.to_string());
+            Value::Array(shapes)
+        }
+        Value::Object(object) => {
+            Value::Object(object.iter().map(|(key, value)| (key.clone(), shape(value))).collect::<Map<_, _>>())
+        }
+    }
+}
+
+/// Rewrites hex strings to a canonical form, so that leading zeros and case don't count as
+/// differences.
+pub fn normalize_hex(value: &Value) -> Value {
+    match value {
+        Value::String(hex) if hex.starts_with("0x") => match Felt::from_hex(hex) {
+            Ok(felt) => Value::String(format!("{felt:#x}")),
+            Err(_) => value.clone(),
+        },
+        Value::Array(values) => Value::Array(values.iter().map(normalize_hex).collect()),
+        Value::Object(fields) => {
+            Value::Object(fields.iter().map(|(key, value)| (key.clone(), normalize_hex(value))).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Paths at which `actual` differs from `expected`, e.g. `$.transactions[0].version`.
+pub fn differences(expected: &Value, actual: &Value) -> Vec<String> {
+    let mut differences = Vec::new();
+    collect_differences("$", expected, actual, &mut differences);
+    differences
+}
+
+fn collect_differences(path: &str, expected: &Value, actual: &Value, differences: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                match actual.get(key) {
+                    Some(actual_value) => {
+                        collect_differences(&format!("{path}.{key}"), expected_value, actual_value, differences)
+                    }
+                    None => differences.push(format!("{path}.{key}: missing")),
+                }
+            }
+            for key in actual.keys().filter(|key| !expected.contains_key(*key)) {
+                differences.push(format!("{path}.{key}: unexpected field"));
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (index, (expected_item, actual_item)) in expected.iter().zip(actual).enumerate() {
+                collect_differences(&format!("{path}[{index}]"), expected_item, actual_item, differences);
+            }
+        }
+        (expected, actual) if expected != actual => {
+            differences.push(format!("{path}: expected {expected}, got {actual}"));
+        }
+        _ => {}
+    }
+}
+
+/// Compares `response`, with its [`VOLATILE_FIELDS`] masked, with the golden file `name`. A missing
+/// golden file is recorded from the response.
+pub fn assert_snapshot(name: &str, response: &impl Serialize) -> Result<(), SnapshotError> {
+    let value = mask_fields(serde_json::to_value(response)?, VOLATILE_FIELDS);
+    compare_with_golden_file(name, value)
+}
+
+/// Same as [`assert_snapshot`], comparing only the [`shape`] of the response.
+pub fn assert_shape_snapshot(name: &str, response: &impl Serialize) -> Result<(), SnapshotError> {
+    let value = shape(&serde_json::to_value(response)?);
+    compare_with_golden_file(name, value)
+}
+
+fn compare_with_golden_file(name: &str, value: Value) -> Result<(), SnapshotError> {
+    let config = snapshot_config();
+    let path = config.dir.join(format!("{name}.json"));
+
+    let golden = match fs::read_to_string(&path) {
+        Ok(content) if !config.update => content,
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(SnapshotError::Io { path, source: e }),
+        _ => {
+            fs::create_dir_all(&config.dir).map_err(|source| SnapshotError::Io { path: path.clone(), source })?;
+            fs::write(&path, serde_json::to_string_pretty(&value)? + "\n")
+                .map_err(|source| SnapshotError::Io { path: path.clone(), source })?;
+            info!("Recorded snapshot {}", path.display());
+            return Ok(());
+        }
+    };
+
+    let expected: Value = serde_json::from_str(&golden)
+        .map_err(|e| SnapshotError::Io { path: path.clone(), source: io::Error::new(io::ErrorKind::InvalidData, e) })?;
+    let mismatches = differences(&expected, &value);
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(SnapshotError::Mismatch { path, differences: mismatches })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn masks_volatile_fields_at_any_depth() {
+        let value = json!({ "block_hash": "0x1", "transactions": [{ "transaction_hash": "0x2", "version": "0x3" }] });
+        let masked = mask_fields(value, VOLATILE_FIELDS);
+        assert_eq!(
+            masked,
+            json!({ "block_hash": MASK, "transactions": [{ "transaction_hash": MASK, "version": "0x3" }] })
+        );
+    }
+
+    #[test]
+    fn reports_changed_shapes_only() {
+        let expected = shape(&json!({ "status": "ACCEPTED_ON_L2", "events": [{ "keys": ["0x1"] }] }));
+        let same_shape = shape(&json!({ "status": "REJECTED", "events": [{ "keys": ["0x2", "0x3"] }] }));
+        assert!(differences(&expected, &same_shape).is_empty());
+
+        let new_field = shape(&json!({ "status": "ACCEPTED_ON_L2", "events": [{ "keys": ["0x1"], "index": 0 }] }));
+        assert_eq!(differences(&expected, &new_field), vec!["$.events[0].index: unexpected field"]);
+    }
+}
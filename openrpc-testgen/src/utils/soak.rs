@@ -0,0 +1,464 @@
+//! Soak mode: a light mix of transactions is submitted continuously for hours while invariants of
+//! the node are checked periodically. The report is flushed to disk after every check, so that a
+//! crash of the node, or of the runner, still leaves the findings of the run so far.
+//!
+//! Balance accounting assumes that the soak account is used by nothing else during the run: all of
+//! its transactions are self-transfers, deployments, declarations or calls, so its balances only
+//! ever decrease by the fees it pays, and increase by the top-ups of [SoakConfig::top_up]. Fees are
+//! counted from receipts that may still be pending, and top-ups once they reach the pending block,
+//! so nonces and balances are read at the pending block as well.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{
+    Anonymous, BlockId, BlockTag, CommonReceiptProperties, PriceUnit, TxnFinalityStatus, TxnReceipt,
+};
+use thiserror::Error;
+use tracing::{error, info, warn};
+
+use super::secrets::write_redacted;
+use super::{
+    get_balance::get_balance,
+    v7::{
+        accounts::{
+            account::{Account, ConnectedAccount},
+            call::Call,
+            single_owner::SingleOwnerAccount,
+            top_up::TopUpAccount,
+            utils::faucet::AnyFaucet,
+        },
+        endpoints::{
+            errors::OpenRpcTestGenError,
+            utils::{get_selector_from_name, wait_for_sent_transaction},
+        },
+        providers::{
+            jsonrpc::{HttpTransport, JsonRpcClient},
+            provider::Provider,
+        },
+        signers::local_wallet::LocalWallet,
+    },
+    workload::{Workload, WorkloadTargets, WorkloadTransaction},
+};
+
+type HiveAccount = SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>;
+
+/// Number of the most recent receipts that are fetched again on every check.
+const RECEIPT_SAMPLE_SIZE: usize = 32;
+/// Checks after which a transaction still without a receipt is considered rejected.
+const MAX_UNSETTLED_CHECKS: u32 = 3;
+
+#[derive(Debug, Error)]
+pub enum SoakError {
+    #[error("Could not write soak report {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Could not serialize soak report: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone)]
+pub struct SoakConfig {
+    /// How long transactions are submitted for.
+    pub duration: Duration,
+    /// Pause between two transactions.
+    pub send_interval: Duration,
+    /// Time between two invariant checks.
+    pub check_interval: Duration,
+    /// JSON report, rewritten after every check.
+    pub report_path: PathBuf,
+    pub strk_address: Felt,
+    pub eth_address: Felt,
+    /// Workload sent in a loop instead of the default mix of self-transfers.
+    pub workload: Option<Workload>,
+    /// Contracts and classes used by the transactions of the workload.
+    pub workload_targets: WorkloadTargets,
+    /// The soak account wrapped for topping it up before sending, when it runs low. A top-up counts
+    /// once its funds show in the pending block.
+    pub top_up: Option<TopUpAccount<AnyFaucet>>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Invariant {
+    /// The account nonce never decreases, and increases when transactions were accepted.
+    NonceMonotonicity,
+    /// Fee token balances decrease by exactly the fees of the accepted transactions.
+    BalanceAccounting,
+    /// The latest block number never decreases.
+    BlockNumberMonotonic,
+    /// Receipts of accepted transactions don't disappear, lose finality or change outcome or fee.
+    NoReceiptRegression,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InvariantViolation {
+    pub elapsed_secs: u64,
+    pub invariant: Invariant,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SoakReport {
+    pub started_at_unix_secs: u64,
+    pub elapsed_secs: u64,
+    pub transactions_sent: u64,
+    pub transactions_failed: u64,
+    /// Times the account was topped up from the faucet.
+    pub top_ups: u64,
+    pub checks_run: u64,
+    /// Checks that could not complete because the node could not be queried.
+    pub checks_failed: u64,
+    pub violations: Vec<InvariantViolation>,
+    pub finished: bool,
+}
+
+impl SoakReport {
+    /// Writes the report to `path` through a temporary file, so that it is never left truncated.
+    pub fn flush(&self, path: &Path) -> Result<(), SoakError> {
+        let tmp_path = path.with_extension("json.tmp");
+        write_redacted(&tmp_path, &(serde_json::to_string_pretty(self)? + "\n"))
+            .map_err(|source| SoakError::Io { path: tmp_path.clone(), source })?;
+        fs::rename(&tmp_path, path).map_err(|source| SoakError::Io { path: path.to_path_buf(), source })
+    }
+}
+
+/// Transactions of the mix, sent in turn.
+#[derive(Debug, Copy, Clone)]
+enum SoakTransaction {
+    StrkSelfTransferV3,
+    EthSelfTransferV1,
+    MulticallSelfTransfersV3,
+    Workload(WorkloadTransaction),
+}
+
+impl SoakTransaction {
+    /// The transaction as sent on `lap`, `None` if it can't be sent again.
+    fn for_lap(&self, lap: u64) -> Option<Self> {
+        match self {
+            SoakTransaction::Workload(transaction) => transaction.for_lap(lap).map(SoakTransaction::Workload),
+            _ => Some(*self),
+        }
+    }
+
+    /// Unit of the fees of the transaction: v1 transactions pay in ETH, v3 ones in STRK.
+    fn fee_unit(&self) -> PriceUnit {
+        match self {
+            SoakTransaction::EthSelfTransferV1 => PriceUnit::Wei,
+            _ => PriceUnit::Fri,
+        }
+    }
+}
+
+const TRANSACTION_MIX: [SoakTransaction; 3] = [
+    SoakTransaction::StrkSelfTransferV3,
+    SoakTransaction::EthSelfTransferV1,
+    SoakTransaction::MulticallSelfTransfersV3,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReceiptSnapshot {
+    finality_status: TxnFinalityStatus,
+    succeeded: bool,
+    fee: Felt,
+}
+
+/// State carried from one check to the next.
+#[derive(Debug, Default)]
+struct InvariantState {
+    nonce: Option<Felt>,
+    block_number: Option<u64>,
+    /// Balances by fee token at the last balance check.
+    balances: HashMap<Felt, Felt>,
+    /// Fees by fee token of the transactions accepted since the last balance check.
+    fees_since_check: HashMap<Felt, Felt>,
+    /// Top-ups by fee token since the last balance check.
+    top_ups_since_check: HashMap<Felt, Felt>,
+    /// Accepted transactions since the last nonce check.
+    accepted_since_check: u64,
+    /// Transactions sent whose receipt has not been fetched yet, with the number of checks since.
+    unsettled: Vec<(Felt, u32)>,
+    receipts: VecDeque<(Felt, ReceiptSnapshot)>,
+}
+
+/// Submits the transaction mix from `account` for `config.duration`, checking the invariants every
+/// `config.check_interval` and once more at the end.
+pub async fn run_soak(account: &HiveAccount, config: &SoakConfig) -> Result<SoakReport, SoakError> {
+    let started = Instant::now();
+    let mut report = SoakReport {
+        started_at_unix_secs: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default(),
+        ..Default::default()
+    };
+    let mut state = InvariantState::default();
+
+    run_checks(account, config, &mut state, &mut report, started).await;
+    report.flush(&config.report_path)?;
+
+    let transactions: Vec<SoakTransaction> = match &config.workload {
+        Some(workload) => workload.transactions.iter().copied().map(SoakTransaction::Workload).collect(),
+        None => TRANSACTION_MIX.to_vec(),
+    };
+    let mut next_check = started + config.check_interval;
+    // Transactions that can't be sent twice are left out of the laps after the first, where they
+    // would only fail with errors the run caused itself.
+    'soak: for lap in 0.. {
+        let lap_transactions: Vec<SoakTransaction> =
+            transactions.iter().filter_map(|transaction| transaction.for_lap(lap)).collect();
+        if lap_transactions.is_empty() {
+            info!("Soak workload has nothing left to send after {} laps", lap);
+            break;
+        }
+        for transaction in &lap_transactions {
+            if started.elapsed() >= config.duration {
+                break 'soak;
+            }
+
+            if let Some(top_up) = &config.top_up {
+                let unit = transaction.fee_unit();
+                match top_up.ensure_funded(unit).await {
+                    Ok(0) => {}
+                    Ok(amount) => {
+                        report.top_ups += 1;
+                        *state.top_ups_since_check.entry(fee_token(config, &unit)).or_default() += Felt::from(amount);
+                    }
+                    Err(e) => warn!("Could not top up the soak account: {}", e),
+                }
+            }
+
+            match send(account, config, *transaction).await {
+                Ok(transaction_hash) => {
+                    report.transactions_sent += 1;
+                    // Even when waiting for it fails the transaction may still be accepted later on, its
+                    // receipt is looked for at the next checks.
+                    state.unsettled.push((transaction_hash, 0));
+                    if let Err(e) = wait_for_sent_transaction(transaction_hash, account).await {
+                        report.transactions_failed += 1;
+                        warn!("Soak transaction {:?} {:#x} failed: {}", transaction, transaction_hash, e);
+                    }
+                }
+                Err(e) => {
+                    report.transactions_failed += 1;
+                    warn!("Soak transaction {:?} could not be sent: {}", transaction, e);
+                }
+            }
+
+            if Instant::now() >= next_check {
+                run_checks(account, config, &mut state, &mut report, started).await;
+                report.flush(&config.report_path)?;
+                next_check += config.check_interval;
+            }
+            tokio::time::sleep(config.send_interval).await;
+        }
+    }
+
+    run_checks(account, config, &mut state, &mut report, started).await;
+    report.finished = true;
+    report.flush(&config.report_path)?;
+    Ok(report)
+}
+
+async fn send(
+    account: &HiveAccount,
+    config: &SoakConfig,
+    transaction: SoakTransaction,
+) -> Result<Felt, OpenRpcTestGenError> {
+    let self_transfer = |token: Felt| -> Result<Call, OpenRpcTestGenError> {
+        Ok(Call {
+            to: token,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![account.address(), Felt::ONE, Felt::ZERO],
+        })
+    };
+
+    let transaction_hash = match transaction {
+        SoakTransaction::StrkSelfTransferV3 => {
+            account.execute_v3(vec![self_transfer(config.strk_address)?]).send().await?.transaction_hash
+        }
+        SoakTransaction::EthSelfTransferV1 => {
+            account.execute_v1(vec![self_transfer(config.eth_address)?]).send().await?.transaction_hash
+        }
+        SoakTransaction::MulticallSelfTransfersV3 => {
+            let calls = vec![self_transfer(config.strk_address)?, self_transfer(config.eth_address)?];
+            account.execute_v3(calls).send().await?.transaction_hash
+        }
+        SoakTransaction::Workload(transaction) => transaction.send(account, &config.workload_targets).await?,
+    };
+    Ok(transaction_hash)
+}
+
+async fn run_checks(
+    account: &HiveAccount,
+    config: &SoakConfig,
+    state: &mut InvariantState,
+    report: &mut SoakReport,
+    started: Instant,
+) {
+    let elapsed_secs = started.elapsed().as_secs();
+    report.elapsed_secs = elapsed_secs;
+    report.checks_run += 1;
+
+    match check_invariants(account, config, state).await {
+        Ok(violations) => {
+            for (invariant, message) in violations {
+                error!("Soak invariant {:?} violated: {}", invariant, message);
+                report.violations.push(InvariantViolation { elapsed_secs, invariant, message });
+            }
+        }
+        Err(e) => {
+            report.checks_failed += 1;
+            warn!("Soak invariant check could not complete: {}", e);
+        }
+    }
+    info!(
+        "Soak check after {}s: {} transactions sent, {} failed, {} violations",
+        elapsed_secs,
+        report.transactions_sent,
+        report.transactions_failed,
+        report.violations.len()
+    );
+}
+
+async fn check_invariants(
+    account: &HiveAccount,
+    config: &SoakConfig,
+    state: &mut InvariantState,
+) -> Result<Vec<(Invariant, String)>, OpenRpcTestGenError> {
+    let provider = account.provider();
+    let mut violations = Vec::new();
+
+    // Receipts sampled earlier must not regress.
+    for (transaction_hash, expected) in &state.receipts {
+        match provider.get_transaction_receipt(*transaction_hash).await {
+            Ok(receipt) => match receipt_snapshot(&receipt) {
+                Some(actual) if regressed(expected, &actual) => violations.push((
+                    Invariant::NoReceiptRegression,
+                    format!("Receipt of {transaction_hash:#x} changed from {expected:?} to {actual:?}"),
+                )),
+                Some(_) => {}
+                None => violations.push((
+                    Invariant::NoReceiptRegression,
+                    format!("Receipt of {transaction_hash:#x} is no longer an invoke or declare receipt"),
+                )),
+            },
+            Err(e) => violations.push((
+                Invariant::NoReceiptRegression,
+                format!("Receipt of {transaction_hash:#x} is no longer available: {e}"),
+            )),
+        }
+    }
+
+    // Settle the transactions sent since the last check.
+    let mut still_unsettled = Vec::new();
+    for (transaction_hash, checks) in std::mem::take(&mut state.unsettled) {
+        let Ok(receipt) = provider.get_transaction_receipt(transaction_hash).await else {
+            if checks + 1 < MAX_UNSETTLED_CHECKS {
+                still_unsettled.push((transaction_hash, checks + 1));
+            } else {
+                // The fees of the transaction are unknown, balance accounting starts over.
+                warn!("No receipt for soak transaction {:#x}, considering it rejected", transaction_hash);
+                state.balances.clear();
+                state.fees_since_check.clear();
+                state.top_ups_since_check.clear();
+            }
+            continue;
+        };
+        let Some(snapshot) = receipt_snapshot(&receipt) else {
+            continue;
+        };
+        if let Some(properties) = common_receipt_properties(&receipt) {
+            *state.fees_since_check.entry(fee_token(config, &properties.actual_fee.unit)).or_default() += snapshot.fee;
+        }
+        state.accepted_since_check += 1;
+        state.receipts.push_back((transaction_hash, snapshot));
+        if state.receipts.len() > RECEIPT_SAMPLE_SIZE {
+            state.receipts.pop_front();
+        }
+    }
+    state.unsettled = still_unsettled;
+
+    let block_number = provider.block_number().await?;
+    if let Some(previous) = state.block_number.filter(|previous| block_number < *previous) {
+        violations.push((
+            Invariant::BlockNumberMonotonic,
+            format!("Latest block number went back from {previous} to {block_number}"),
+        ));
+    }
+    state.block_number = Some(block_number);
+
+    let nonce = provider.get_nonce(BlockId::Tag(BlockTag::Pending), account.address()).await?;
+    if let Some(previous) = state.nonce {
+        if nonce < previous {
+            violations
+                .push((Invariant::NonceMonotonicity, format!("Nonce went back from {previous:#x} to {nonce:#x}")));
+        } else if nonce == previous && state.accepted_since_check > 0 {
+            violations.push((
+                Invariant::NonceMonotonicity,
+                format!("Nonce stayed at {nonce:#x} after {} accepted transactions", state.accepted_since_check),
+            ));
+        }
+    }
+    state.nonce = Some(nonce);
+    state.accepted_since_check = 0;
+
+    // Fees of transactions without a receipt yet are unknown, the balances are checked once they
+    // are all settled.
+    if state.unsettled.is_empty() {
+        for token in [config.strk_address, config.eth_address] {
+            let balance = get_balance(provider, account.address(), token, BlockId::Tag(BlockTag::Pending))
+                .await?
+                .first()
+                .copied()
+                .unwrap_or_default();
+            let fees = state.fees_since_check.remove(&token).unwrap_or_default();
+            let top_ups = state.top_ups_since_check.remove(&token).unwrap_or_default();
+            if let Some(previous) = state.balances.insert(token, balance) {
+                if balance + fees != previous + top_ups {
+                    violations.push((
+                        Invariant::BalanceAccounting,
+                        format!(
+                            "Balance of token {token:#x} went from {previous:#x} to {balance:#x}, while {fees:#x} \
+                             was paid in fees and {top_ups:#x} topped up"
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(violations)
+}
+
+fn fee_token(config: &SoakConfig, unit: &PriceUnit) -> Felt {
+    match unit {
+        PriceUnit::Wei => config.eth_address,
+        PriceUnit::Fri => config.strk_address,
+    }
+}
+
+/// Properties of the receipts of the transactions the soak account sends.
+fn common_receipt_properties(receipt: &TxnReceipt<Felt>) -> Option<&CommonReceiptProperties<Felt>> {
+    match receipt {
+        TxnReceipt::Invoke(receipt) => Some(&receipt.common_receipt_properties),
+        TxnReceipt::Declare(receipt) => Some(&receipt.common_receipt_properties),
+        _ => None,
+    }
+}
+
+fn receipt_snapshot(receipt: &TxnReceipt<Felt>) -> Option<ReceiptSnapshot> {
+    common_receipt_properties(receipt).map(|properties| ReceiptSnapshot {
+        finality_status: properties.finality_status.clone(),
+        succeeded: matches!(properties.anon, Anonymous::Successful(_)),
+        fee: properties.actual_fee.amount,
+    })
+}
+
+/// Finality may only progress from L2 to L1, the rest of the receipt must stay the same.
+fn regressed(expected: &ReceiptSnapshot, actual: &ReceiptSnapshot) -> bool {
+    let finality_regressed =
+        expected.finality_status == TxnFinalityStatus::L1 && actual.finality_status == TxnFinalityStatus::L2;
+    finality_regressed || expected.succeeded != actual.succeeded || expected.fee != actual.fee
+}
@@ -0,0 +1,65 @@
+use crypto_utils::protocol::ProtocolVersion;
+use url::Url;
+
+use super::v7::endpoints::{endpoints_functions::spec_version, errors::OpenRpcTestGenError};
+
+/// JSON-RPC specification line a node implements, used to pick which set of types and tests
+/// should be run against it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RpcSpecVersion {
+    /// `0.7.x`, covered by [`crate::utils::v7`] and `test_rpc_endpoints_v0_0_7`.
+    V0_7,
+    /// `0.8.x`, covered by the `suite_openrpc` test suite using [`crate::utils::v8`] types.
+    V0_8,
+}
+
+impl RpcSpecVersion {
+    /// Maps the version string returned by `starknet_specVersion` onto a supported spec line.
+    #[allow(clippy::result_large_err)]
+    pub fn from_reported(version: &str) -> Result<Self, OpenRpcTestGenError> {
+        match parse_reported_version(version) {
+            Some([0, 7, _]) => Ok(Self::V0_7),
+            Some([0, 8, _]) => Ok(Self::V0_8),
+            _ => Err(OpenRpcTestGenError::Other(format!("Unsupported spec version {}", version))),
+        }
+    }
+}
+
+/// `[major, minor, patch]` of a version returned by `starknet_specVersion`. Nodes report it with or
+/// without a `v` prefix and with pre-release suffixes such as `-rc.1`, which are ignored.
+pub fn parse_reported_version(version: &str) -> Option<[u32; 3]> {
+    let version = version.trim().trim_start_matches('v');
+    let release = version.split_once(['-', '+']).map_or(version, |(release, _)| release);
+    // Spec versions have no hotfix component.
+    ProtocolVersion::parse(release).filter(|_| release.matches('.').count() == 2).map(|version| version.release())
+}
+
+/// Queries `starknet_specVersion` and returns the spec line the node at `url` implements.
+pub async fn detect_spec_version(url: Url) -> Result<RpcSpecVersion, OpenRpcTestGenError> {
+    let version = spec_version(url).await?;
+    RpcSpecVersion::from_reported(&version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_reported() {
+        assert_eq!(RpcSpecVersion::from_reported("0.7.1").unwrap(), RpcSpecVersion::V0_7);
+        assert_eq!(RpcSpecVersion::from_reported("0.8.0").unwrap(), RpcSpecVersion::V0_8);
+        assert_eq!(RpcSpecVersion::from_reported("v0.8.0-rc.1").unwrap(), RpcSpecVersion::V0_8);
+        assert!(RpcSpecVersion::from_reported("0.6.0").is_err());
+        assert!(RpcSpecVersion::from_reported("garbage").is_err());
+    }
+
+    #[test]
+    fn test_parse_reported_version() {
+        assert_eq!(parse_reported_version("0.7.1"), Some([0, 7, 1]));
+        assert_eq!(parse_reported_version("v0.8.0-rc.1"), Some([0, 8, 0]));
+        assert_eq!(parse_reported_version(" 0.8.0+build.5 "), Some([0, 8, 0]));
+        assert_eq!(parse_reported_version("0.7"), None);
+        assert_eq!(parse_reported_version("0.7.1.2"), None);
+        assert_eq!(parse_reported_version("0.x.1"), None);
+    }
+}
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use super::network_registry::network_registry;
 use super::v7::{
     accounts::{
         account::{
@@ -35,8 +36,6 @@ use starknet_types_rpc::{BlockId, BlockTag, ContractClass};
 use std::fmt::Debug;
 use url::Url;
 
-const STRK: Felt = Felt::from_hex_unchecked("0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D");
-
 #[derive(Debug)]
 pub struct StarknetHive {
     pub account: SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
@@ -68,7 +67,7 @@ impl StarknetHive {
 
         let transfer_execution = paymaster_account
             .execute_v3(vec![Call {
-                to: STRK,
+                to: network_registry().strk_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account_data.address, transfer_amount, Felt::ZERO],
             }])
@@ -163,7 +162,7 @@ impl StarknetHive {
 
         let transfer_execution = paymaster_account
             .execute_v3(vec![Call {
-                to: STRK,
+                to: network_registry().strk_address,
                 selector: get_selector_from_name("transfer")?,
                 calldata: vec![account_data.address, transfer_amount, Felt::ZERO],
             }])
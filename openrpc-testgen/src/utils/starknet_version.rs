@@ -0,0 +1,66 @@
+//! Starknet protocol versions, as reported in the `starknet_version` of block headers. The spec
+//! version of the node says which fields a block has, the protocol version of the block which
+//! values they may take, so test cases gate assertions on it to run against networks on different
+//! protocol versions.
+
+use std::fmt;
+
+use crypto_utils::protocol::ProtocolVersion;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StarknetVersionError {
+    #[error("Block {block_number} has no starknet_version")]
+    Missing { block_number: u64 },
+    #[error("Invalid starknet_version {0:?}")]
+    Invalid(String),
+}
+
+/// Version `major.minor.patch`, optionally followed by a fourth component for hotfixes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StarknetVersion(ProtocolVersion);
+
+impl StarknetVersion {
+    /// First version pricing the blob data of blocks, with a non-zero `l1_data_gas_price`.
+    pub const L1_DATA_GAS: Self = Self::new(0, 13, 1, 0);
+
+    pub const fn new(major: u32, minor: u32, patch: u32, hotfix: u32) -> Self {
+        Self(ProtocolVersion::new(major, minor, patch, hotfix))
+    }
+
+    pub fn parse(version: &str) -> Result<Self, StarknetVersionError> {
+        ProtocolVersion::parse(version).map(Self).ok_or_else(|| StarknetVersionError::Invalid(version.to_string()))
+    }
+
+    pub fn has_l1_data_gas(&self) -> bool {
+        *self >= Self::L1_DATA_GAS
+    }
+}
+
+impl fmt::Display for StarknetVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_orders_versions() {
+        let version = StarknetVersion::parse("0.13.1.1").unwrap();
+        assert_eq!(version, StarknetVersion::new(0, 13, 1, 1));
+        assert_eq!(version.to_string(), "0.13.1.1");
+        assert_eq!(StarknetVersion::parse("0.13.2").unwrap().to_string(), "0.13.2");
+
+        assert!(StarknetVersion::parse("0.13.0").unwrap() < StarknetVersion::parse("0.13.0.1").unwrap());
+        assert!(StarknetVersion::parse("0.9.1").unwrap() < StarknetVersion::parse("0.13.1").unwrap());
+        assert!(version.has_l1_data_gas());
+        assert!(!StarknetVersion::parse("0.13.0").unwrap().has_l1_data_gas());
+
+        for invalid in ["", "0.13", "0.13.1.1.1", "v0.13.1", "0.13.x"] {
+            assert!(StarknetVersion::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+}
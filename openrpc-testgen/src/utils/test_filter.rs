@@ -0,0 +1,37 @@
+//! Selection of the test cases to run, by `suite::name` as registered with `#[openrpc_test]`.
+
+use std::sync::OnceLock;
+
+static TEST_FILTER: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Runs only the test cases whose `suite::name` contains one of `patterns`; an empty list runs all
+/// of them. Returns `false` if the filter has already been set.
+pub fn set_test_filter(patterns: Vec<String>) -> bool {
+    TEST_FILTER.set(patterns).is_ok()
+}
+
+pub fn is_selected(suite: &str, name: &str) -> bool {
+    match TEST_FILTER.get() {
+        Some(patterns) if !patterns.is_empty() => {
+            let qualified_name = format!("{suite}::{name}");
+            patterns.iter().any(|pattern| qualified_name.contains(pattern.as_str()))
+        }
+        _ => true,
+    }
+}
+
+/// Test case listed by the build script, whether or not the feature of its root suite is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestCaseEntry {
+    /// Top-level suite the case runs in, e.g. `openrpc` for the cases of `suite_openrpc/suite_deploy`.
+    pub root_suite: &'static str,
+    pub suite: &'static str,
+    pub name: &'static str,
+}
+
+include!(concat!(env!("OUT_DIR"), "/test_catalogue.rs"));
+
+/// Test cases of `root_suite` selected by the filter.
+pub fn selected_test_cases(root_suite: &str) -> impl Iterator<Item = &'static TestCaseEntry> + '_ {
+    TEST_CASES.iter().filter(move |entry| entry.root_suite == root_suite && is_selected(entry.suite, entry.name))
+}
@@ -0,0 +1,186 @@
+//! Outcomes of the test cases of a run, for CI systems to read. Once enabled, the generated suite
+//! code records the outcome, duration and captured logs of every test case, and the run can be
+//! written as a JUnit XML report with one `testsuite` per suite, or as JSON for other tools to read
+//! back, such as the aggregation of the runs against several networks.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::secrets::write_redacted;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TestOutcome {
+    Passed,
+    Failed(String),
+    /// Not selected by the filter, or stopped before broadcasting by a dry run.
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCaseResult {
+    pub suite: String,
+    pub name: String,
+    pub outcome: TestOutcome,
+    pub duration: Duration,
+    /// Lines logged while the case ran, see [super::log_capture].
+    pub logs: Vec<String>,
+}
+
+static RESULTS: OnceLock<Mutex<Vec<TestCaseResult>>> = OnceLock::new();
+
+/// Starts recording test case outcomes. Returns `false` if recording was already enabled.
+pub fn enable_test_report() -> bool {
+    RESULTS.set(Mutex::new(Vec::new())).is_ok()
+}
+
+/// Records the outcome of a test case, does nothing unless recording is enabled.
+pub fn record_test_case(suite: &str, name: &str, outcome: TestOutcome, duration: Duration, logs: Vec<String>) {
+    if let Some(results) = RESULTS.get() {
+        let result = TestCaseResult { suite: suite.to_string(), name: name.to_string(), outcome, duration, logs };
+        results.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(result);
+    }
+}
+
+/// Recorded outcomes in the order the cases ran. Returns `None` unless recording is enabled.
+pub fn test_case_results() -> Option<Vec<TestCaseResult>> {
+    Some(RESULTS.get()?.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone())
+}
+
+pub fn write_junit_report(path: &Path, results: &[TestCaseResult]) -> io::Result<()> {
+    write_redacted(path, &junit_xml(results))
+}
+
+pub fn write_json_report(path: &Path, results: &[TestCaseResult]) -> io::Result<()> {
+    write_redacted(path, &(serde_json::to_string_pretty(results)? + "\n"))
+}
+
+/// Reads back a report written by [write_json_report].
+pub fn read_json_report(path: &Path) -> io::Result<Vec<TestCaseResult>> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+fn junit_xml(results: &[TestCaseResult]) -> String {
+    let mut suites: BTreeMap<&str, Vec<&TestCaseResult>> = BTreeMap::new();
+    for result in results {
+        suites.entry(result.suite.as_str()).or_default().push(result);
+    }
+    let failures =
+        |cases: &[&TestCaseResult]| cases.iter().filter(|case| matches!(case.outcome, TestOutcome::Failed(_))).count();
+    let total_time = |cases: &[&TestCaseResult]| cases.iter().map(|case| case.duration.as_secs_f64()).sum::<f64>();
+
+    let all: Vec<&TestCaseResult> = results.iter().collect();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"openrpc-testgen\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+        all.len(),
+        failures(&all),
+        total_time(&all)
+    );
+    for (suite, cases) in suites {
+        let skipped = cases.iter().filter(|case| case.outcome == TestOutcome::Skipped).count();
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+            escape(suite),
+            cases.len(),
+            failures(&cases),
+            skipped,
+            total_time(&cases)
+        );
+        for case in cases {
+            let _ = write!(
+                xml,
+                "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+                escape(suite),
+                escape(&case.name),
+                case.duration.as_secs_f64()
+            );
+            if case.outcome == TestOutcome::Passed && case.logs.is_empty() {
+                xml.push_str("/>\n");
+                continue;
+            }
+            xml.push_str(">\n");
+            match &case.outcome {
+                TestOutcome::Passed => {}
+                TestOutcome::Skipped => xml.push_str("      <skipped/>\n"),
+                TestOutcome::Failed(message) => {
+                    let first_line = message.lines().next().unwrap_or_default();
+                    let _ = writeln!(
+                        xml,
+                        "      <failure message=\"{}\">{}</failure>",
+                        escape(first_line),
+                        escape(message)
+                    );
+                }
+            }
+            if !case.logs.is_empty() {
+                let _ = writeln!(xml, "      <system-out>{}</system-out>", escape(&case.logs.join("\n")));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than tabs and newlines are not allowed in XML 1.0.
+            c if c.is_control() && c != '\t' && c != '\n' && c != '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn junit_groups_cases_by_suite_and_escapes_failures() {
+        let case = |suite: &str, name: &str, outcome| TestCaseResult {
+            suite: suite.to_string(),
+            name: name.to_string(),
+            outcome,
+            duration: Duration::from_millis(1500),
+            logs: Vec::new(),
+        };
+        let xml = junit_xml(&[
+            case("openrpc", "estimate_fee_wei", TestOutcome::Passed),
+            case("deploy", "deploy_v3", TestOutcome::Failed("expected <1> & got \"2\"\nbacktrace".to_string())),
+            case("openrpc", "get_nonce", TestOutcome::Skipped),
+        ]);
+
+        assert!(xml.contains("<testsuites name=\"openrpc-testgen\" tests=\"3\" failures=\"1\" time=\"4.500\">"));
+        assert!(xml.contains("<testsuite name=\"openrpc\" tests=\"2\" failures=\"0\" skipped=\"1\" time=\"3.000\">"));
+        assert!(xml.contains(
+            "<failure message=\"expected &lt;1&gt; &amp; got &quot;2&quot;\">expected &lt;1&gt; &amp; got \
+             &quot;2&quot;\nbacktrace</failure>"
+        ));
+        assert!(xml.find("name=\"deploy\"").unwrap() < xml.find("name=\"openrpc\"").unwrap());
+
+        let logged = TestCaseResult {
+            logs: vec!["INFO sent".to_string(), "ERROR <rejected>".to_string()],
+            ..case("openrpc", "get_class", TestOutcome::Passed)
+        };
+        assert!(junit_xml(&[logged])
+            .contains("<system-out>INFO sent\nERROR &lt;rejected&gt;</system-out>\n    </testcase>"));
+    }
+}
@@ -0,0 +1,258 @@
+//! Traffic capture: once enabled, the HTTP transport records every JSON-RPC exchange, headers and
+//! bodies included, and the run writes them as a HAR file. Browser devtools and most HTTP tools
+//! open HAR files, so the capture of a run with INCOMPATIBLE results can be shared with the node's
+//! team as it is. Registered secrets and sensitive header values are redacted.
+
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::{header::HeaderMap, StatusCode, Url, Version};
+use serde::Serialize;
+use tracing::info;
+
+use super::request_timing::RequestTiming;
+use super::secrets::{write_redacted, REDACTED};
+
+static CAPTURE: OnceLock<Mutex<Vec<HarEntry>>> = OnceLock::new();
+
+/// Starts capturing traffic. Returns `false` if capture was already enabled.
+pub fn enable_traffic_capture() -> bool {
+    CAPTURE.set(Mutex::new(Vec::new())).is_ok()
+}
+
+/// Whether exchanges are captured, so that the transport only copies what it captures when needed.
+pub fn traffic_capture_enabled() -> bool {
+    CAPTURE.get().is_some()
+}
+
+/// A request and its response, as sent and received.
+#[derive(Debug)]
+pub struct CapturedExchange<'a> {
+    pub started: SystemTime,
+    /// JSON-RPC method, kept as the comment of the entry.
+    pub method: &'a str,
+    pub url: &'a Url,
+    pub request_headers: &'a HeaderMap,
+    /// Body before compression.
+    pub request_body: &'a str,
+    pub version: Version,
+    pub status: StatusCode,
+    pub response_headers: &'a HeaderMap,
+    /// Body once decoded.
+    pub response_body: &'a str,
+    /// Bytes of the body as received.
+    pub received_bytes: usize,
+    pub timing: RequestTiming,
+}
+
+/// Records an exchange, does nothing unless capture is enabled.
+pub fn record_exchange(exchange: CapturedExchange<'_>) {
+    let Some(capture) = CAPTURE.get() else {
+        return;
+    };
+    let http_version = format!("{:?}", exchange.version);
+    let timings = HarTimings {
+        send: millis(exchange.timing.serialize),
+        wait: millis(exchange.timing.connect_and_wait),
+        receive: millis(exchange.timing.download),
+    };
+    let entry = HarEntry {
+        started_date_time: iso_8601(exchange.started),
+        time: timings.send + timings.wait + timings.receive,
+        request: HarRequest {
+            method: "POST",
+            url: exchange.url.to_string(),
+            http_version: http_version.clone(),
+            headers: har_headers(exchange.request_headers),
+            query_string: Vec::new(),
+            cookies: Vec::new(),
+            headers_size: -1,
+            body_size: exchange.request_body.len() as i64,
+            post_data: HarPostData { mime_type: "application/json", text: exchange.request_body.to_string() },
+        },
+        response: HarResponse {
+            status: exchange.status.as_u16(),
+            status_text: exchange.status.canonical_reason().unwrap_or_default().to_string(),
+            http_version,
+            headers: har_headers(exchange.response_headers),
+            cookies: Vec::new(),
+            content: HarContent {
+                size: exchange.response_body.len() as i64,
+                mime_type: "application/json",
+                text: exchange.response_body.to_string(),
+            },
+            redirect_url: String::new(),
+            headers_size: -1,
+            body_size: exchange.received_bytes as i64,
+        },
+        cache: HarCache {},
+        timings,
+        comment: exchange.method.to_string(),
+    };
+    capture.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(entry);
+}
+
+/// Traffic of a run in the HAR 1.2 format.
+#[derive(Debug, Clone, Serialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HarLog {
+    pub version: &'static str,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HarCreator {
+    pub name: &'static str,
+    pub version: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    /// Milliseconds from sending the request to the end of the response.
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub cache: HarCache,
+    pub timings: HarTimings,
+    pub comment: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: &'static str,
+    pub url: String,
+    pub http_version: String,
+    pub headers: Vec<HarHeader>,
+    pub query_string: Vec<HarHeader>,
+    pub cookies: Vec<HarHeader>,
+    /// Unknown, `reqwest` doesn't expose the head as sent.
+    pub headers_size: i64,
+    pub body_size: i64,
+    pub post_data: HarPostData,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    pub headers: Vec<HarHeader>,
+    pub cookies: Vec<HarHeader>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HarHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: &'static str,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: &'static str,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HarCache {}
+
+/// Phases of an entry in milliseconds; serializing the request counts as sending it.
+#[derive(Debug, Clone, Serialize)]
+pub struct HarTimings {
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+}
+
+impl Har {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+    }
+
+    pub fn log_summary(&self) {
+        let failed = self.log.entries.iter().filter(|entry| !(200..300).contains(&entry.response.status)).count();
+        info!("Captured {} requests, {} with an error status", self.log.entries.len(), failed);
+    }
+}
+
+/// Returns `None` unless capture is enabled.
+pub fn traffic_capture() -> Option<Har> {
+    let entries = CAPTURE.get()?.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    let creator = HarCreator { name: env!("CARGO_PKG_NAME"), version: env!("CARGO_PKG_VERSION") };
+    Some(Har { log: HarLog { version: "1.2", creator, entries } })
+}
+
+fn har_headers(headers: &HeaderMap) -> Vec<HarHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| HarHeader {
+            name: name.to_string(),
+            value: if value.is_sensitive() {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            },
+        })
+        .collect()
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Formats `time` as an ISO 8601 UTC timestamp with milliseconds, e.g. `2023-11-14T22:13:20.000Z`.
+fn iso_8601(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (hours, minutes, seconds) = (secs % 86_400 / 3600, secs % 3600 / 60, secs % 60);
+
+    // Civil date of the days since 1970-01-01, counted in 400-year eras starting on March 1st.
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+    let year = era * 400 + year_of_era + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}T{hours:02}:{minutes:02}:{seconds:02}.{:03}Z", since_epoch.subsec_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(iso_8601(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(iso_8601(UNIX_EPOCH + Duration::from_millis(1_700_000_000_250)), "2023-11-14T22:13:20.250Z");
+        assert_eq!(iso_8601(UNIX_EPOCH + Duration::from_secs(951_782_400)), "2000-02-29T00:00:00.000Z");
+        assert_eq!(iso_8601(UNIX_EPOCH + Duration::from_secs(951_868_799)), "2000-02-29T23:59:59.000Z");
+    }
+}
@@ -0,0 +1,167 @@
+//! Test data for negative tests: a [TxnTemplate] holds the JSON of a valid `BroadcastedTxn` and
+//! derives broken variants of it through [Mutation]s, e.g. a required field removed, a version the
+//! transaction type does not have, or a felt set to `-1`. Most of these can't be expressed with
+//! the typed transactions, and building them from a valid template keeps everything but the
+//! mutated field valid, so the node has exactly one reason to reject the transaction.
+
+use rand::{seq::SliceRandom, Rng};
+use serde_json::{json, Map, Value};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::BroadcastedTxn;
+
+/// The field modulus `p`, which is not a felt and must be rejected wherever a felt is expected.
+pub const FELT_MODULUS: &str = "0x800000000000011000000000000000000000000000000000000000000000001";
+/// Versions tried by [TxnTemplate::mutations], the ones a transaction type has are skipped.
+const VERSIONS: [u64; 5] = [0, 1, 2, 3, 4];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mutation {
+    RemoveField(String),
+    SetField(String, Value),
+    /// Replaces the `version` of the transaction.
+    Version(Felt),
+    /// Sets a felt field to `-n`, i.e. `p - n`, the encoding negative numbers get in Cairo.
+    NegativeFelt(String, u64),
+    /// Sets a felt field to [FELT_MODULUS].
+    FeltOverflow(String),
+}
+
+impl Mutation {
+    pub fn apply(&self, transaction: &mut Map<String, Value>) {
+        match self {
+            Mutation::RemoveField(field) => {
+                transaction.remove(field);
+            }
+            Mutation::SetField(field, value) => {
+                transaction.insert(field.clone(), value.clone());
+            }
+            Mutation::Version(version) => {
+                transaction.insert("version".to_string(), json!(format!("{version:#x}")));
+            }
+            Mutation::NegativeFelt(field, n) => {
+                transaction.insert(field.clone(), json!(format!("{:#x}", -Felt::from(*n))));
+            }
+            Mutation::FeltOverflow(field) => {
+                transaction.insert(field.clone(), json!(FELT_MODULUS));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TxnTemplate {
+    method: &'static str,
+    param: &'static str,
+    transaction: Map<String, Value>,
+}
+
+impl TxnTemplate {
+    pub fn new(transaction: &BroadcastedTxn<Felt>) -> Result<Self, serde_json::Error> {
+        let (method, param) = match transaction {
+            BroadcastedTxn::Invoke(_) => ("starknet_addInvokeTransaction", "invoke_transaction"),
+            BroadcastedTxn::Declare(_) => ("starknet_addDeclareTransaction", "declare_transaction"),
+            BroadcastedTxn::DeployAccount(_) => ("starknet_addDeployAccountTransaction", "deploy_account_transaction"),
+        };
+        let transaction = match serde_json::to_value(transaction)? {
+            Value::Object(transaction) => transaction,
+            other => {
+                return Err(serde::de::Error::custom(format!("Transaction serialized to a non-object: {other}")));
+            }
+        };
+        Ok(Self { method, param, transaction })
+    }
+
+    /// JSON-RPC method submitting the transaction.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    pub fn transaction(&self) -> &Map<String, Value> {
+        &self.transaction
+    }
+
+    /// The transaction with `mutations` applied in order.
+    pub fn mutate(&self, mutations: &[Mutation]) -> Value {
+        let mut transaction = self.transaction.clone();
+        for mutation in mutations {
+            mutation.apply(&mut transaction);
+        }
+        Value::Object(transaction)
+    }
+
+    /// Parameters of [Self::method] submitting `transaction`.
+    pub fn params(&self, transaction: Value) -> Value {
+        json!({ self.param: transaction })
+    }
+
+    /// Every single mutation of the template: each field removed, each other version, and each
+    /// top-level felt set to `-1` and to [FELT_MODULUS].
+    pub fn mutations(&self) -> Vec<Mutation> {
+        let mut mutations: Vec<Mutation> = self
+            .transaction
+            .keys()
+            .filter(|field| *field != "type")
+            .map(|field| Mutation::RemoveField(field.clone()))
+            .collect();
+
+        let version = self.transaction.get("version").and_then(Value::as_str).and_then(|v| Felt::from_hex(v).ok());
+        mutations.extend(
+            VERSIONS
+                .into_iter()
+                .map(Felt::from)
+                .filter(|candidate| version.is_some_and(|version| version != *candidate))
+                .map(Mutation::Version),
+        );
+
+        for (field, value) in &self.transaction {
+            let is_felt = value.as_str().is_some_and(|value| value.starts_with("0x"));
+            if is_felt && field != "version" {
+                mutations.push(Mutation::NegativeFelt(field.clone(), 1));
+                mutations.push(Mutation::FeltOverflow(field.clone()));
+            }
+        }
+        mutations
+    }
+
+    /// One of [Self::mutations] at random, for fuzzing. `None` for a template without fields.
+    pub fn random_mutation(&self, rng: &mut impl Rng) -> Option<Mutation> {
+        self.mutations().choose(rng).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_types_rpc::v0_7_1::{BroadcastedInvokeTxn, InvokeTxnV1};
+
+    use super::*;
+
+    fn template() -> TxnTemplate {
+        TxnTemplate::new(&BroadcastedTxn::Invoke(BroadcastedInvokeTxn::V1(InvokeTxnV1 {
+            max_fee: Felt::from(1000),
+            signature: vec![Felt::ONE, Felt::TWO],
+            nonce: Felt::ONE,
+            sender_address: Felt::from(0x1234),
+            calldata: vec![],
+        })))
+        .unwrap()
+    }
+
+    #[test]
+    fn mutates_fields_versions_and_felts() {
+        let template = template();
+        assert_eq!(template.method(), "starknet_addInvokeTransaction");
+
+        let mutations = template.mutations();
+        assert!(mutations.contains(&Mutation::RemoveField("nonce".to_string())));
+        assert!(!mutations.contains(&Mutation::RemoveField("type".to_string())));
+        assert!(!mutations.contains(&Mutation::Version(Felt::ONE)));
+        assert!(mutations.contains(&Mutation::Version(Felt::THREE)));
+
+        let negative = template.mutate(&[Mutation::NegativeFelt("nonce".to_string(), 1)]);
+        assert_eq!(negative["nonce"], json!("0x800000000000011000000000000000000000000000000000000000000000000"));
+        let removed = template.mutate(&[Mutation::RemoveField("signature".to_string())]);
+        assert!(removed.get("signature").is_none());
+        assert_eq!(removed["sender_address"], template.transaction()["sender_address"]);
+        assert!(template.params(removed).get("invoke_transaction").is_some());
+    }
+}
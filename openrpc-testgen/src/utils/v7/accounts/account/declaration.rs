@@ -1,15 +1,18 @@
+use crate::utils::fee_accuracy::{record_fee_estimate, TransactionKind};
+use crate::utils::fee_overrides::fee_overrides;
 use crate::utils::v7::accounts::account::ContractClassHasher;
 
 use crate::utils::v7::{accounts::errors::NotPreparedError, providers::provider::Provider};
 
-use crypto_utils::curve::signer::compute_hash_on_elements;
+use crypto_utils::protocol::{target_constants, transaction_version, Resource};
+use starknet_hive_hashing::resource_bounds::ResourceBoundsBuilder;
+use starknet_hive_hashing::transaction::{declare_v2_hash, declare_v3_hash, V1Fields, V3Fields};
 use starknet_types_core::felt::Felt;
-use starknet_types_core::hash::{Poseidon, StarkHash};
 use starknet_types_rpc::v0_7_1::{
     BroadcastedDeclareTxn, BroadcastedDeclareTxnV2, BroadcastedDeclareTxnV3, BroadcastedTxn, ClassAndTxnHash,
     ContractClass, FeeEstimate, SimulateTransactionsResult, SimulationFlag,
 };
-use starknet_types_rpc::{DaMode, MaybePendingBlockWithTxHashes, ResourceBounds, ResourceBoundsMapping};
+use starknet_types_rpc::{DaMode, MaybePendingBlockWithTxHashes};
 use std::sync::Arc;
 
 use super::{
@@ -17,15 +20,17 @@ use super::{
     PreparedDeclarationV3, RawDeclarationV2, RawDeclarationV3,
 };
 
-/// Cairo string for "declare"
-const PREFIX_DECLARE: Felt =
-    Felt::from_raw([191557713328401194, 18446744073709551615, 18446744073709551615, 17542456862011667323]);
-
-/// 2 ^ 128 + 2
-const QUERY_VERSION_TWO: Felt = Felt::from_raw([576460752142433232, 18446744073709551584, 17407, 18446744073700081601]);
 impl<'a, A> DeclarationV2<'a, A> {
     pub fn new(contract_class: Arc<ContractClass<Felt>>, compiled_class_hash: Felt, account: &'a A) -> Self {
-        Self { account, contract_class, compiled_class_hash, nonce: None, max_fee: None, fee_estimate_multiplier: 1.1 }
+        let overrides = fee_overrides();
+        Self {
+            account,
+            contract_class,
+            compiled_class_hash,
+            nonce: None,
+            max_fee: overrides.max_fee,
+            fee_estimate_multiplier: overrides.fee_estimate_multiplier,
+        }
     }
 
     pub fn nonce(self, nonce: Felt) -> Self {
@@ -71,6 +76,16 @@ where
         self.estimate_fee_with_nonce(nonce).await
     }
 
+    pub async fn estimate_fee_skip_signature(&self) -> Result<FeeEstimate<Felt>, AccountError<A::SignError>> {
+        // Resolves nonce
+        let nonce = match self.nonce {
+            Some(value) => value,
+            None => self.account.get_nonce().await.map_err(AccountError::Provider)?,
+        };
+
+        self.estimate_fee_with_nonce_skip_signature(nonce).await
+    }
+
     pub async fn simulate(
         &self,
         skip_validate: bool,
@@ -86,10 +101,29 @@ where
     }
 
     pub async fn send(&self) -> Result<ClassAndTxnHash<Felt>, AccountError<A::SignError>> {
-        self.prepare().await?.send().await
+        let (prepared, fee_estimate) = self.prepare_with_fee_estimate().await?;
+        let result = prepared.send().await?;
+        if let Some(fee_estimate) = fee_estimate {
+            record_fee_estimate(TransactionKind::DeclareV2, result.transaction_hash, &fee_estimate);
+        }
+        Ok(result)
+    }
+
+    pub async fn send_with_custom_signature(
+        &self,
+        signature: Vec<Felt>,
+    ) -> Result<ClassAndTxnHash<Felt>, AccountError<A::SignError>> {
+        self.prepare().await?.send_with_custom_signature(signature).await
     }
 
     pub async fn prepare(&self) -> Result<PreparedDeclarationV2<'_, A>, AccountError<A::SignError>> {
+        self.prepare_with_fee_estimate().await.map(|(prepared, _)| prepared)
+    }
+
+    /// Also returns the fee estimate the transaction is prepared with, when fees were estimated.
+    async fn prepare_with_fee_estimate(
+        &self,
+    ) -> Result<(PreparedDeclarationV2<'_, A>, Option<FeeEstimate<Felt>>), AccountError<A::SignError>> {
         // Resolves nonce
         let nonce = match self.nonce {
             Some(value) => value,
@@ -97,8 +131,8 @@ where
         };
 
         // Resolves max_fee
-        let max_fee = match self.max_fee {
-            Some(value) => value,
+        let (max_fee, fee_estimate) = match self.max_fee {
+            Some(value) => (value, None),
             None => {
                 // Obtain the fee estimate
                 let fee_estimate = self.estimate_fee_with_nonce(nonce).await?;
@@ -114,11 +148,12 @@ where
                 let overall_fee_u64 = u64::from_le_bytes(overall_fee_bytes[..8].try_into().unwrap());
 
                 // Perform necessary operations on overall_fee_u64 and convert to f64 then to u64
-                (((overall_fee_u64 as f64) * self.fee_estimate_multiplier) as u64).into()
+                let max_fee: Felt = (((overall_fee_u64 as f64) * self.fee_estimate_multiplier) as u64).into();
+                (max_fee, Some(fee_estimate))
             }
         };
 
-        Ok(PreparedDeclarationV2 {
+        let prepared = PreparedDeclarationV2 {
             account: self.account,
             inner: RawDeclarationV2 {
                 contract_class: self.contract_class.clone(),
@@ -126,7 +161,9 @@ where
                 nonce,
                 max_fee,
             },
-        })
+        };
+
+        Ok((prepared, fee_estimate))
     }
 
     async fn estimate_fee_with_nonce(&self, nonce: Felt) -> Result<FeeEstimate<Felt>, AccountError<A::SignError>> {
@@ -141,12 +178,12 @@ where
             },
         };
 
-        let declare = prepared.get_declare_request(true, skip_signature).await?; // TODO: query only has to be false
+        let declare = prepared.get_broadcasted_declare(true, skip_signature).await?;
 
         self.account
             .provider()
             .estimate_fee_single(
-                BroadcastedTxn::Declare(BroadcastedDeclareTxn::QueryV2(declare)),
+                declare,
                 vec![], // TODO: put back if needed
                 self.account.block_id(),
             )
@@ -154,6 +191,34 @@ where
             .map_err(AccountError::Provider)
     }
 
+    async fn estimate_fee_with_nonce_skip_signature(
+        &self,
+        nonce: Felt,
+    ) -> Result<FeeEstimate<Felt>, AccountError<A::SignError>> {
+        let prepared = PreparedDeclarationV2 {
+            account: self.account,
+            inner: RawDeclarationV2 {
+                contract_class: self.contract_class.clone(),
+                compiled_class_hash: self.compiled_class_hash,
+                nonce,
+                max_fee: Felt::ZERO,
+            },
+        };
+
+        let declare = prepared.get_broadcasted_declare(true, true).await?;
+
+        self.account
+            .provider()
+            .estimate_fee_single(
+                declare,
+                // Validation would fail since real signature was not requested
+                vec!["SKIP_VALIDATE".to_string()],
+                self.account.block_id(),
+            )
+            .await
+            .map_err(AccountError::Provider)
+    }
+
     async fn simulate_with_nonce(
         &self,
         nonce: Felt,
@@ -179,7 +244,7 @@ where
                 max_fee: self.max_fee.unwrap_or_default(),
             },
         };
-        let declare = prepared.get_declare_request(true, skip_signature).await?;
+        let declare = prepared.get_broadcasted_declare(true, skip_signature).await?;
 
         let mut flags = vec![];
 
@@ -192,11 +257,7 @@ where
 
         self.account
             .provider()
-            .simulate_transaction(
-                self.account.block_id(),
-                BroadcastedTxn::Declare(BroadcastedDeclareTxn::V2(declare)),
-                flags,
-            )
+            .simulate_transaction(self.account.block_id(), declare, flags)
             .await
             .map_err(AccountError::Provider)
     }
@@ -204,15 +265,16 @@ where
 
 impl<'a, A> DeclarationV3<'a, A> {
     pub fn new(contract_class: ContractClass<Felt>, compiled_class_hash: Felt, account: &'a A) -> Self {
+        let overrides = fee_overrides();
         Self {
             account,
             contract_class,
             compiled_class_hash,
             nonce: None,
-            gas: None,
-            gas_price: None,
-            gas_estimate_multiplier: 1.5,
-            gas_price_estimate_multiplier: 1.5,
+            gas: overrides.gas,
+            gas_price: overrides.gas_price,
+            gas_estimate_multiplier: overrides.gas_estimate_multiplier,
+            gas_price_estimate_multiplier: overrides.gas_price_estimate_multiplier,
         }
     }
 
@@ -295,10 +357,29 @@ where
     }
 
     pub async fn send(&self) -> Result<ClassAndTxnHash<Felt>, AccountError<A::SignError>> {
-        self.prepare().await?.send().await
+        let (prepared, fee_estimate) = self.prepare_with_fee_estimate().await?;
+        let result = prepared.send().await?;
+        if let Some(fee_estimate) = fee_estimate {
+            record_fee_estimate(TransactionKind::DeclareV3, result.transaction_hash, &fee_estimate);
+        }
+        Ok(result)
+    }
+
+    pub async fn send_with_custom_signature(
+        &self,
+        signature: Vec<Felt>,
+    ) -> Result<ClassAndTxnHash<Felt>, AccountError<A::SignError>> {
+        self.prepare().await?.send_with_custom_signature(signature).await
     }
 
     pub async fn prepare(&self) -> Result<PreparedDeclarationV3<'_, A>, AccountError<A::SignError>> {
+        self.prepare_with_fee_estimate().await.map(|(prepared, _)| prepared)
+    }
+
+    /// Also returns the fee estimate the transaction is prepared with, when fees were estimated.
+    async fn prepare_with_fee_estimate(
+        &self,
+    ) -> Result<(PreparedDeclarationV3<'_, A>, Option<FeeEstimate<Felt>>), AccountError<A::SignError>> {
         // Resolves nonce
         let nonce = match self.nonce {
             Some(value) => value,
@@ -306,8 +387,8 @@ where
         };
 
         // Resolves fee settings
-        let (gas, gas_price) = match (self.gas, self.gas_price) {
-            (Some(gas), Some(gas_price)) => (gas, gas_price),
+        let (gas, gas_price, fee_estimate) = match (self.gas, self.gas_price) {
+            (Some(gas), Some(gas_price)) => (gas, gas_price, None),
             (Some(gas), _) => {
                 // When `gas` is specified, we only need the L1 gas price in FRI. By specifying a
                 // a `gas` value, the user might be trying to avoid a full fee estimation (e.g.
@@ -337,7 +418,7 @@ where
                 let block_l1_gas_price = u64::from_le_bytes(block_l1_gas_price_bytes[..8].try_into().unwrap());
 
                 let gas_price = ((block_l1_gas_price as f64) * self.gas_price_estimate_multiplier) as u128;
-                (gas, gas_price)
+                (gas, gas_price, None)
             }
             // We have to perform fee estimation as long as gas is not specified
             _ => {
@@ -375,11 +456,11 @@ where
                     }
                 };
 
-                (gas, gas_price)
+                (gas, gas_price, Some(fee_estimate))
             }
         };
 
-        Ok(PreparedDeclarationV3 {
+        let prepared = PreparedDeclarationV3 {
             account: self.account,
             inner: RawDeclarationV3 {
                 contract_class: self.contract_class.clone(),
@@ -388,7 +469,9 @@ where
                 gas,
                 gas_price,
             },
-        })
+        };
+
+        Ok((prepared, fee_estimate))
     }
 
     async fn estimate_fee_with_nonce(&self, nonce: Felt) -> Result<FeeEstimate<Felt>, AccountError<A::SignError>> {
@@ -404,13 +487,13 @@ where
             },
         };
 
-        let declare = prepared.get_declare_request(true, skip_signature).await?;
+        let declare = prepared.get_broadcasted_declare(true, skip_signature).await?;
 
         let result = self
             .account
             .provider()
             .estimate_fee_single(
-                BroadcastedTxn::Declare(BroadcastedDeclareTxn::V3(declare)),
+                declare,
                 if skip_signature {
                     // Validation would fail since real signature was not requested
                     vec!["SKIP_VALIDATE".to_string()]
@@ -444,13 +527,13 @@ where
             },
         };
 
-        let declare = prepared.get_declare_request(true, skip_signature).await?;
+        let declare = prepared.get_broadcasted_declare(true, skip_signature).await?;
 
         let result = self
             .account
             .provider()
             .estimate_fee_single(
-                BroadcastedTxn::Declare(BroadcastedDeclareTxn::V3(declare)),
+                declare,
                 if skip_signature {
                     // Validation would fail since real signature was not requested
                     vec!["SKIP_VALIDATE".to_string()]
@@ -494,7 +577,7 @@ where
                 gas_price: self.gas_price.unwrap_or_default(),
             },
         };
-        let declare = prepared.get_declare_request(true, skip_signature).await?;
+        let declare = prepared.get_broadcasted_declare(true, skip_signature).await?;
 
         let mut flags = vec![];
 
@@ -507,11 +590,7 @@ where
 
         self.account
             .provider()
-            .simulate_transaction(
-                self.account.block_id(),
-                BroadcastedTxn::Declare(BroadcastedDeclareTxn::V3(declare)),
-                flags,
-            )
+            .simulate_transaction(self.account.block_id(), declare, flags)
             .await
             .map_err(AccountError::Provider)
     }
@@ -519,17 +598,14 @@ where
 
 impl RawDeclarationV2 {
     pub fn transaction_hash(&self, chain_id: Felt, address: Felt, query_only: bool) -> Felt {
-        compute_hash_on_elements(&[
-            PREFIX_DECLARE,
-            if query_only { QUERY_VERSION_TWO } else { Felt::TWO }, // version
-            address,
-            Felt::ZERO, // entry_point_selector
-            compute_hash_on_elements(&[self.contract_class.class_hash()]),
-            self.max_fee,
+        let fields = V1Fields {
+            version: transaction_version(2, query_only),
+            sender_address: address,
+            max_fee: self.max_fee,
             chain_id,
-            self.nonce,
-            self.compiled_class_hash,
-        ])
+            nonce: self.nonce,
+        };
+        declare_v2_hash(&fields, self.contract_class.class_hash(), self.compiled_class_hash)
     }
 
     pub fn contract_class(&self) -> &ContractClass<Felt> {
@@ -550,49 +626,20 @@ impl RawDeclarationV2 {
 }
 
 impl RawDeclarationV3 {
-    pub fn transaction_hash(&self, chain_id: Felt, address: Felt, _query_only: bool) -> Felt {
-        // Main data vector to collect all elements for hashing
-        let mut data = vec![PREFIX_DECLARE, Felt::THREE, address];
-
-        // Fee data collection
-        let mut fee_data = vec![Felt::ZERO]; // Hard-coded fee market
-
-        // First L1 gas resource buffer
-        let mut resource_buffer = [
-            0, 0, b'L', b'1', b'_', b'G', b'A', b'S', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0,
-        ];
-        resource_buffer[8..(8 + 8)].copy_from_slice(&self.gas.to_be_bytes());
-        resource_buffer[(8 + 8)..].copy_from_slice(&self.gas_price.to_be_bytes());
-        fee_data.push(Felt::from_bytes_be(&resource_buffer));
-
-        // Second L2 gas resource buffer
-        let resource_buffer = [
-            0, 0, b'L', b'2', b'_', b'G', b'A', b'S', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0,
-        ];
-        fee_data.push(Felt::from_bytes_be(&resource_buffer));
-
-        // Hash the fee data and add it to main data
-        data.push(Poseidon::hash_array(&fee_data));
-
-        // Hard-coded empty `paymaster_data`
-        data.push(Poseidon::hash_array(&[]));
-
-        // Remaining transaction fields
-        data.push(chain_id);
-        data.push(self.nonce);
-        data.push(Felt::ZERO); // Hard-coded L1 DA mode for nonce and fee
-
-        // Hard-coded empty `account_deployment_data`
-        data.push(Poseidon::hash_array(&[]));
-
-        // Contract class and compiled class hashes
-        data.push(self.contract_class.class_hash());
-        data.push(self.compiled_class_hash);
-
-        // Final hash computation
-        Poseidon::hash_array(&data)
+    pub fn transaction_hash(&self, chain_id: Felt, address: Felt, query_only: bool) -> Felt {
+        // Hard-coded zero tip, empty `paymaster_data` and `account_deployment_data`, L1 DA mode for
+        // nonce and fee
+        let fields = V3Fields {
+            version: transaction_version(3, query_only),
+            sender_address: address,
+            fee_data_hash: target_constants().fee_data_hash(Felt::ZERO, &[(Resource::L1Gas, self.gas, self.gas_price)]),
+            paymaster_data: &[],
+            chain_id,
+            nonce: self.nonce,
+            nonce_data_availability_mode: DaMode::L1,
+            fee_data_availability_mode: DaMode::L1,
+        };
+        declare_v3_hash(&fields, &[], self.contract_class.class_hash(), self.compiled_class_hash)
     }
 
     pub fn contract_class(&self) -> &ContractClass<Felt> {
@@ -641,6 +688,19 @@ where
             .map_err(AccountError::Provider)
     }
 
+    pub async fn send_with_custom_signature(
+        &self,
+        signature: Vec<Felt>,
+    ) -> Result<ClassAndTxnHash<Felt>, AccountError<A::SignError>> {
+        let tx_request = self.get_declare_request_with_custom_signature(signature);
+
+        self.account
+            .provider()
+            .add_declare_transaction(BroadcastedTxn::Declare(BroadcastedDeclareTxn::V2(tx_request)))
+            .await
+            .map_err(AccountError::Provider)
+    }
+
     pub async fn send_from_request(
         &self,
         tx_request: BroadcastedDeclareTxnV2<Felt>,
@@ -652,6 +712,41 @@ where
             .map_err(AccountError::Provider)
     }
 
+    /// Simulates the declaration with the nonce and `max_fee` it is prepared with.
+    pub async fn simulate(
+        &self,
+        skip_validate: bool,
+        skip_fee_charge: bool,
+    ) -> Result<SimulateTransactionsResult<Felt>, AccountError<A::SignError>> {
+        // A signature is only requested from interactive signers when it is validated.
+        let skip_signature = self.account.is_signer_interactive() && skip_validate;
+        let declare = self.get_broadcasted_declare(true, skip_signature).await?;
+
+        self.account
+            .provider()
+            .simulate_transaction(self.account.block_id(), declare, simulation_flags(skip_validate, skip_fee_charge))
+            .await
+            .map_err(AccountError::Provider)
+    }
+
+    /// Request signed over the query version of the transaction hash when `query_only` is set, the
+    /// plain version otherwise, and carrying the same version so the node hashes it the way it was
+    /// signed. Query transactions are accepted by fee estimation and simulation only.
+    pub async fn get_broadcasted_declare(
+        &self,
+        query_only: bool,
+        skip_signature: bool,
+    ) -> Result<BroadcastedTxn<Felt>, AccountError<A::SignError>> {
+        let declare = self.get_declare_request(query_only, skip_signature).await?;
+        Ok(BroadcastedTxn::Declare(if query_only {
+            BroadcastedDeclareTxn::QueryV2(declare)
+        } else {
+            BroadcastedDeclareTxn::V2(declare)
+        }))
+    }
+
+    /// Signs over the query version of the transaction hash when `query_only` is set. The returned
+    /// request carries no version, see [Self::get_broadcasted_declare].
     pub async fn get_declare_request(
         &self,
         query_only: bool,
@@ -673,6 +768,17 @@ where
         })
     }
 
+    pub fn get_declare_request_with_custom_signature(&self, signature: Vec<Felt>) -> BroadcastedDeclareTxnV2<Felt> {
+        BroadcastedDeclareTxnV2 {
+            max_fee: self.inner.max_fee,
+            signature,
+            nonce: self.inner.nonce,
+            contract_class: Arc::clone(&self.inner.contract_class).as_ref().clone(),
+            compiled_class_hash: self.inner.compiled_class_hash,
+            sender_address: self.account.address(),
+        }
+    }
+
     pub async fn get_raw_execution(&self) -> &RawDeclarationV2 {
         &self.inner
     }
@@ -702,6 +808,18 @@ where
             .map_err(AccountError::Provider)
     }
 
+    pub async fn send_with_custom_signature(
+        &self,
+        signature: Vec<Felt>,
+    ) -> Result<ClassAndTxnHash<Felt>, AccountError<A::SignError>> {
+        let tx_request = self.get_declare_request_with_custom_signature(signature);
+        self.account
+            .provider()
+            .add_declare_transaction(BroadcastedTxn::Declare(BroadcastedDeclareTxn::V3(tx_request)))
+            .await
+            .map_err(AccountError::Provider)
+    }
+
     pub async fn send_from_request(
         &self,
         tx_request: BroadcastedDeclareTxnV3<Felt>,
@@ -713,6 +831,41 @@ where
             .map_err(AccountError::Provider)
     }
 
+    /// Simulates the declaration with the nonce and resource bounds it is prepared with.
+    pub async fn simulate(
+        &self,
+        skip_validate: bool,
+        skip_fee_charge: bool,
+    ) -> Result<SimulateTransactionsResult<Felt>, AccountError<A::SignError>> {
+        // A signature is only requested from interactive signers when it is validated.
+        let skip_signature = self.account.is_signer_interactive() && skip_validate;
+        let declare = self.get_broadcasted_declare(true, skip_signature).await?;
+
+        self.account
+            .provider()
+            .simulate_transaction(self.account.block_id(), declare, simulation_flags(skip_validate, skip_fee_charge))
+            .await
+            .map_err(AccountError::Provider)
+    }
+
+    /// Request signed over the query version of the transaction hash when `query_only` is set, the
+    /// plain version otherwise, and carrying the same version so the node hashes it the way it was
+    /// signed. Query transactions are accepted by fee estimation and simulation only.
+    pub async fn get_broadcasted_declare(
+        &self,
+        query_only: bool,
+        skip_signature: bool,
+    ) -> Result<BroadcastedTxn<Felt>, AccountError<A::SignError>> {
+        let declare = self.get_declare_request(query_only, skip_signature).await?;
+        Ok(BroadcastedTxn::Declare(if query_only {
+            BroadcastedDeclareTxn::QueryV3(declare)
+        } else {
+            BroadcastedDeclareTxn::V3(declare)
+        }))
+    }
+
+    /// Signs over the query version of the transaction hash when `query_only` is set. The returned
+    /// request carries no version, see [Self::get_broadcasted_declare].
     pub async fn get_declare_request(
         &self,
         query_only: bool,
@@ -728,14 +881,8 @@ where
             },
             nonce: self.inner.nonce,
             contract_class: self.inner.contract_class.clone(),
-            resource_bounds: ResourceBoundsMapping {
-                l1_gas: ResourceBounds {
-                    max_amount: Felt::from_dec_str(&self.inner.gas.to_string()).unwrap().to_hex_string(),
-                    max_price_per_unit: Felt::from_dec_str(&self.inner.gas_price.to_string()).unwrap().to_hex_string(),
-                },
-                // L2 resources are hard-coded to 0
-                l2_gas: ResourceBounds { max_amount: "0x0".to_string(), max_price_per_unit: "0x0".to_string() },
-            },
+            // L2 resources are left at 0
+            resource_bounds: ResourceBoundsBuilder::new().l1_gas(self.inner.gas, self.inner.gas_price).build(),
             // Fee market has not been been activated yet so it's hard-coded to be 0
             tip: Felt::from(0),
             // Hard-coded empty `paymaster_data`
@@ -748,7 +895,40 @@ where
         })
     }
 
+    pub fn get_declare_request_with_custom_signature(&self, signature: Vec<Felt>) -> BroadcastedDeclareTxnV3<Felt> {
+        BroadcastedDeclareTxnV3 {
+            sender_address: self.account.address(),
+            compiled_class_hash: self.inner.compiled_class_hash,
+            signature,
+            nonce: self.inner.nonce,
+            contract_class: self.inner.contract_class.clone(),
+            // L2 resources are left at 0
+            resource_bounds: ResourceBoundsBuilder::new().l1_gas(self.inner.gas, self.inner.gas_price).build(),
+            // Fee market has not been been activated yet so it's hard-coded to be 0
+            tip: Felt::from(0),
+            // Hard-coded empty `paymaster_data`
+            paymaster_data: vec![],
+            // Hard-coded empty `account_deployment_data`
+            account_deployment_data: vec![],
+            // Hard-coded L1 DA mode for nonce and fee
+            nonce_data_availability_mode: DaMode::L1,
+            fee_data_availability_mode: DaMode::L1,
+        }
+    }
+
     pub async fn get_raw_execution(&self) -> &RawDeclarationV3 {
         &self.inner
     }
 }
+
+/// Flags skipping what the simulation of a prepared declaration is asked to skip.
+fn simulation_flags(skip_validate: bool, skip_fee_charge: bool) -> Vec<SimulationFlag> {
+    let mut flags = vec![];
+    if skip_validate {
+        flags.push(SimulationFlag::Validate);
+    }
+    if skip_fee_charge {
+        flags.push(SimulationFlag::FeeCharge);
+    }
+    flags
+}
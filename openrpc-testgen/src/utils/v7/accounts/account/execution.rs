@@ -1,36 +1,45 @@
+use std::sync::Arc;
+
 use starknet_types_core::felt::Felt;
-use starknet_types_core::hash::{Poseidon, StarkHash};
 use starknet_types_rpc::{
     v0_7_1::{
         AddInvokeTransactionResult, BroadcastedInvokeTxn, BroadcastedTxn, FeeEstimate, InvokeTxnV1,
         SimulateTransactionsResult, SimulationFlag,
     },
-    DaMode, InvokeTxnV3, MaybePendingBlockWithTxHashes, ResourceBounds, ResourceBoundsMapping,
+    DaMode, InvokeTxnV3, MaybePendingBlockWithTxHashes,
 };
 
 use super::{
     Account, AccountError, ConnectedAccount, ExecutionEncoder, ExecutionV1, ExecutionV3, PreparedExecutionV1,
     PreparedExecutionV3, RawExecutionV1, RawExecutionV3,
 };
-use crate::utils::v7::{
-    accounts::{call::Call, errors::NotPreparedError},
-    providers::provider::Provider,
+use crate::utils::{
+    fee_accuracy::{record_fee_estimate, TransactionKind},
+    fee_overrides::fee_overrides,
+    v7::{
+        accounts::{
+            call::Call,
+            errors::NotPreparedError,
+            fee_strategy::{FeeStrategy, MarginFeeStrategy},
+        },
+        providers::provider::Provider,
+    },
 };
-use crypto_utils::curve::signer::compute_hash_on_elements;
-
-const PREFIX_INVOKE: Felt =
-    Felt::from_raw([513398556346534256, 18446744073709551615, 18446744073709551615, 18443034532770911073]);
-
-/// 2 ^ 128 + 1
-const QUERY_VERSION_ONE: Felt = Felt::from_raw([576460752142433776, 18446744073709551584, 17407, 18446744073700081633]);
-
-/// 2 ^ 128 + 3
-const QUERY_VERSION_THREE: Felt =
-    Felt::from_raw([576460752142432688, 18446744073709551584, 17407, 18446744073700081569]);
+use crypto_utils::protocol::{target_constants, transaction_version, Resource};
+use starknet_hive_hashing::resource_bounds::ResourceBoundsBuilder;
+use starknet_hive_hashing::transaction::{invoke_v1_hash, invoke_v3_hash, V1Fields, V3Fields};
 
 impl<'a, A> ExecutionV1<'a, A> {
     pub fn new(calls: Vec<Call>, account: &'a A) -> Self {
-        Self { account, calls, nonce: None, max_fee: None, fee_estimate_multiplier: 1.1 }
+        let overrides = fee_overrides();
+        Self {
+            account,
+            calls,
+            nonce: None,
+            max_fee: overrides.max_fee,
+            fee_estimate_multiplier: overrides.fee_estimate_multiplier,
+            fee_strategy: None,
+        }
     }
 
     pub fn nonce(self, nonce: Felt) -> Self {
@@ -45,6 +54,11 @@ impl<'a, A> ExecutionV1<'a, A> {
         Self { fee_estimate_multiplier, ..self }
     }
 
+    /// Derives `max_fee` from the fee estimate with `fee_strategy` instead of the multiplier.
+    pub fn fee_strategy(self, fee_strategy: Arc<dyn FeeStrategy>) -> Self {
+        Self { fee_strategy: Some(fee_strategy), ..self }
+    }
+
     /// Calling this function after manually specifying `nonce` and `max_fee` turns [ExecutionV1] into
     /// [PreparedExecutionV1]. Returns `Err` if either field is `None`.
     pub fn prepared(self) -> Result<PreparedExecutionV1<'a, A>, NotPreparedError> {
@@ -57,14 +71,17 @@ impl<'a, A> ExecutionV1<'a, A> {
 
 impl<'a, A> ExecutionV3<'a, A> {
     pub fn new(calls: Vec<Call>, account: &'a A) -> Self {
+        let overrides = fee_overrides();
         Self {
             account,
             calls,
             nonce: None,
-            gas: None,
-            gas_price: None,
-            gas_estimate_multiplier: 1.5,
-            gas_price_estimate_multiplier: 1.5,
+            gas: overrides.gas,
+            gas_price: overrides.gas_price,
+            gas_estimate_multiplier: overrides.gas_estimate_multiplier,
+            gas_price_estimate_multiplier: overrides.gas_price_estimate_multiplier,
+            fee_strategy: None,
+            tip: 0,
         }
     }
 
@@ -88,6 +105,17 @@ impl<'a, A> ExecutionV3<'a, A> {
         Self { gas_price_estimate_multiplier, ..self }
     }
 
+    /// Derives `gas` and `gas_price` from the estimates with `fee_strategy` instead of the
+    /// multipliers.
+    pub fn fee_strategy(self, fee_strategy: Arc<dyn FeeStrategy>) -> Self {
+        Self { fee_strategy: Some(fee_strategy), ..self }
+    }
+
+    /// Tip offered to the sequencer for prioritizing the transaction, `0` by default.
+    pub fn tip(self, tip: u64) -> Self {
+        Self { tip, ..self }
+    }
+
     /// Calling this function after manually specifying `nonce`, `gas` and `gas_price` turns
     /// [ExecutionV3] into [PreparedExecutionV3]. Returns `Err` if any field is `None`.
     pub fn prepared(self) -> Result<PreparedExecutionV3<'a, A>, NotPreparedError> {
@@ -97,7 +125,7 @@ impl<'a, A> ExecutionV3<'a, A> {
 
         Ok(PreparedExecutionV3 {
             account: self.account,
-            inner: RawExecutionV3 { calls: self.calls, nonce, gas, gas_price },
+            inner: RawExecutionV3 { calls: self.calls, nonce, gas, gas_price, tip: self.tip },
         })
     }
 }
@@ -131,7 +159,12 @@ where
     }
 
     pub async fn send(&self) -> Result<AddInvokeTransactionResult<Felt>, AccountError<A::SignError>> {
-        self.prepare().await?.send().await
+        let (prepared, fee_estimate) = self.prepare_with_fee_estimate().await?;
+        let result = prepared.send().await?;
+        if let Some(fee_estimate) = fee_estimate {
+            record_fee_estimate(TransactionKind::InvokeV1, result.transaction_hash, &fee_estimate);
+        }
+        Ok(result)
     }
 
     pub async fn send_with_custom_signature(
@@ -142,15 +175,25 @@ where
     }
 
     pub async fn prepare(&self) -> Result<PreparedExecutionV1<'a, A>, AccountError<A::SignError>> {
+        self.prepare_with_fee_estimate().await.map(|(prepared, _)| prepared)
+    }
+
+    /// Also returns the fee estimate the transaction is prepared with, when fees were estimated.
+    async fn prepare_with_fee_estimate(
+        &self,
+    ) -> Result<(PreparedExecutionV1<'a, A>, Option<FeeEstimate<Felt>>), AccountError<A::SignError>> {
         // Resolves nonce
         let nonce = match self.nonce {
             Some(value) => value,
             None => self.account.get_nonce().await.map_err(AccountError::Provider)?,
         };
 
+        let default_strategy = MarginFeeStrategy::multipliers(self.fee_estimate_multiplier, 1.0, 1.0);
+        let fee_strategy = self.fee_strategy.as_deref().unwrap_or(&default_strategy);
+
         // Resolves max_fee
-        let max_fee = match self.max_fee {
-            Some(value) => value,
+        let (max_fee, fee_estimate) = match self.max_fee {
+            Some(value) => (value, None),
             None => {
                 // Obtain the fee estimate
                 let fee_estimate = self.estimate_fee_with_nonce(nonce).await?;
@@ -165,15 +208,17 @@ where
                 // Convert the first 8 bytes to u64
                 let overall_fee_u64 = u64::from_le_bytes(overall_fee_bytes[..8].try_into().unwrap());
 
-                // Perform necessary operations on overall_fee_u64 and convert to f64 then to u64
-                (((overall_fee_u64 as f64) * self.fee_estimate_multiplier) as u64).into()
+                let max_fee: Felt = fee_strategy.max_fee(overall_fee_u64).into();
+                (max_fee, Some(fee_estimate))
             }
         };
 
-        Ok(PreparedExecutionV1 {
+        let prepared = PreparedExecutionV1 {
             account: self.account,
             inner: RawExecutionV1 { calls: self.calls.clone(), nonce, max_fee },
-        })
+        };
+
+        Ok((prepared, fee_estimate))
     }
 
     async fn estimate_fee_with_nonce(&self, nonce: Felt) -> Result<FeeEstimate<Felt>, AccountError<A::SignError>> {
@@ -183,15 +228,11 @@ where
             account: self.account,
             inner: RawExecutionV1 { calls: self.calls.clone(), nonce, max_fee: Felt::ZERO },
         };
-        let invoke = prepared.get_invoke_request(true, skip_signature).await.map_err(AccountError::Signing)?;
+        let invoke = prepared.get_broadcasted_invoke(true, skip_signature).await.map_err(AccountError::Signing)?;
 
         self.account
             .provider()
-            .estimate_fee_single(
-                BroadcastedTxn::Invoke(BroadcastedInvokeTxn::V1(invoke)),
-                vec![],
-                self.account.block_id(),
-            )
+            .estimate_fee_single(invoke, vec![], self.account.block_id())
             .await
             .map_err(AccountError::Provider)
     }
@@ -216,7 +257,7 @@ where
             account: self.account,
             inner: RawExecutionV1 { calls: self.calls.clone(), nonce, max_fee: self.max_fee.unwrap_or_default() },
         };
-        let invoke = prepared.get_invoke_request(true, skip_signature).await.map_err(AccountError::Signing)?;
+        let invoke = prepared.get_broadcasted_invoke(true, skip_signature).await.map_err(AccountError::Signing)?;
 
         let mut flags = vec![];
 
@@ -229,11 +270,7 @@ where
 
         self.account
             .provider()
-            .simulate_transaction(
-                self.account.block_id(),
-                BroadcastedTxn::Invoke(BroadcastedInvokeTxn::V1(invoke)),
-                flags,
-            )
+            .simulate_transaction(self.account.block_id(), invoke, flags)
             .await
             .map_err(AccountError::Provider)
     }
@@ -263,6 +300,22 @@ where
         self.estimate_fee_with_nonce_skip_signature(nonce).await
     }
 
+    /// The signed transaction [estimate_fee](Self::estimate_fee) submits, to be estimated along
+    /// with other transactions in a single `starknet_estimateFee` request.
+    pub async fn fee_estimate_request(&self) -> Result<BroadcastedTxn<Felt>, AccountError<A::SignError>> {
+        // Resolves nonce
+        let nonce = match self.nonce {
+            Some(value) => value,
+            None => self.account.get_nonce().await.map_err(AccountError::Provider)?,
+        };
+
+        let prepared = PreparedExecutionV3 {
+            account: self.account,
+            inner: RawExecutionV3 { calls: self.calls.clone(), nonce, gas: 0, gas_price: 0, tip: self.tip },
+        };
+        prepared.get_broadcasted_invoke(true, false).await.map_err(AccountError::Signing)
+    }
+
     pub async fn simulate(
         &self,
         skip_validate: bool,
@@ -278,7 +331,12 @@ where
     }
 
     pub async fn send(&self) -> Result<AddInvokeTransactionResult<Felt>, AccountError<A::SignError>> {
-        self.prepare().await?.send().await
+        let (prepared, fee_estimate) = self.prepare_with_fee_estimate().await?;
+        let result = prepared.send().await?;
+        if let Some(fee_estimate) = fee_estimate {
+            record_fee_estimate(TransactionKind::InvokeV3, result.transaction_hash, &fee_estimate);
+        }
+        Ok(result)
     }
 
     pub async fn send_with_custom_signature(
@@ -289,15 +347,26 @@ where
     }
 
     pub async fn prepare(&self) -> Result<PreparedExecutionV3<'a, A>, AccountError<A::SignError>> {
+        self.prepare_with_fee_estimate().await.map(|(prepared, _)| prepared)
+    }
+
+    /// Also returns the fee estimate the transaction is prepared with, when fees were estimated.
+    async fn prepare_with_fee_estimate(
+        &self,
+    ) -> Result<(PreparedExecutionV3<'a, A>, Option<FeeEstimate<Felt>>), AccountError<A::SignError>> {
         // Resolves nonce
         let nonce = match self.nonce {
             Some(value) => value,
             None => self.account.get_nonce().await.map_err(AccountError::Provider)?,
         };
 
+        let default_strategy =
+            MarginFeeStrategy::multipliers(1.0, self.gas_estimate_multiplier, self.gas_price_estimate_multiplier);
+        let fee_strategy = self.fee_strategy.as_deref().unwrap_or(&default_strategy);
+
         // Resolves fee settings
-        let (gas, gas_price) = match (self.gas, self.gas_price) {
-            (Some(gas), Some(gas_price)) => (gas, gas_price),
+        let (gas, gas_price, fee_estimate) = match (self.gas, self.gas_price) {
+            (Some(gas), Some(gas_price)) => (gas, gas_price, None),
             (Some(gas), _) => {
                 // When `gas` is specified, we only need the L1 gas price in FRI. By specifying a
                 // a `gas` value, the user might be trying to avoid a full fee estimation (e.g.
@@ -326,8 +395,8 @@ where
                 }
                 let block_l1_gas_price = u64::from_le_bytes(block_l1_gas_price_bytes[..8].try_into().unwrap());
 
-                let gas_price = ((block_l1_gas_price as f64) * self.gas_price_estimate_multiplier) as u128;
-                (gas, gas_price)
+                let gas_price = fee_strategy.gas_price(block_l1_gas_price);
+                (gas, gas_price, None)
             }
             // We have to perform fee estimation as long as gas is not specified
             _ => {
@@ -347,7 +416,7 @@ where
                         }
                         let gas_price = u64::from_le_bytes(gas_price_bytes[..8].try_into().unwrap());
 
-                        ((overall_fee.div_ceil(gas_price) as f64) * self.gas_estimate_multiplier) as u64
+                        fee_strategy.gas(overall_fee.div_ceil(gas_price))
                     }
                 };
 
@@ -360,18 +429,20 @@ where
                         }
                         let gas_price = u64::from_le_bytes(gas_price_bytes[..8].try_into().unwrap());
 
-                        ((gas_price as f64) * self.gas_price_estimate_multiplier) as u128
+                        fee_strategy.gas_price(gas_price)
                     }
                 };
 
-                (gas, gas_price)
+                (gas, gas_price, Some(fee_estimate))
             }
         };
 
-        Ok(PreparedExecutionV3 {
+        let prepared = PreparedExecutionV3 {
             account: self.account,
-            inner: RawExecutionV3 { calls: self.calls.clone(), nonce, gas, gas_price },
-        })
+            inner: RawExecutionV3 { calls: self.calls.clone(), nonce, gas, gas_price, tip: self.tip },
+        };
+
+        Ok((prepared, fee_estimate))
     }
 
     async fn estimate_fee_with_nonce(&self, nonce: Felt) -> Result<FeeEstimate<Felt>, AccountError<A::SignError>> {
@@ -379,14 +450,14 @@ where
 
         let prepared = PreparedExecutionV3 {
             account: self.account,
-            inner: RawExecutionV3 { calls: self.calls.clone(), nonce, gas: 0, gas_price: 0 },
+            inner: RawExecutionV3 { calls: self.calls.clone(), nonce, gas: 0, gas_price: 0, tip: self.tip },
         };
-        let invoke = prepared.get_invoke_request(false, skip_signature).await.map_err(AccountError::Signing)?;
+        let invoke = prepared.get_broadcasted_invoke(true, skip_signature).await.map_err(AccountError::Signing)?;
 
         self.account
             .provider()
             .estimate_fee_single(
-                BroadcastedTxn::Invoke(BroadcastedInvokeTxn::V3(invoke)),
+                invoke,
                 if skip_signature {
                     // Validation would fail since real signature was not requested
                     vec!["SKIP_VALIDATE".to_string()]
@@ -408,14 +479,14 @@ where
 
         let prepared = PreparedExecutionV3 {
             account: self.account,
-            inner: RawExecutionV3 { calls: self.calls.clone(), nonce, gas: 0, gas_price: 0 },
+            inner: RawExecutionV3 { calls: self.calls.clone(), nonce, gas: 0, gas_price: 0, tip: self.tip },
         };
-        let invoke = prepared.get_invoke_request(true, skip_signature).await.map_err(AccountError::Signing)?;
+        let invoke = prepared.get_broadcasted_invoke(true, skip_signature).await.map_err(AccountError::Signing)?;
 
         self.account
             .provider()
             .estimate_fee_single(
-                BroadcastedTxn::Invoke(BroadcastedInvokeTxn::V3(invoke)),
+                invoke,
                 if skip_signature {
                     // Validation would fail since real signature was not requested
                     vec!["SKIP_VALIDATE".to_string()]
@@ -452,9 +523,10 @@ where
                 nonce,
                 gas: self.gas.unwrap_or_default(),
                 gas_price: self.gas_price.unwrap_or_default(),
+                tip: self.tip,
             },
         };
-        let invoke = prepared.get_invoke_request(false, skip_signature).await.map_err(AccountError::Signing)?;
+        let invoke = prepared.get_broadcasted_invoke(true, skip_signature).await.map_err(AccountError::Signing)?;
 
         let mut flags = vec![];
 
@@ -467,11 +539,7 @@ where
 
         self.account
             .provider()
-            .simulate_transaction(
-                self.account.block_id(),
-                BroadcastedTxn::Invoke(BroadcastedInvokeTxn::V3(invoke)),
-                flags,
-            )
+            .simulate_transaction(self.account.block_id(), invoke, flags)
             .await
             .map_err(AccountError::Provider)
     }
@@ -482,16 +550,14 @@ impl RawExecutionV1 {
     where
         E: ExecutionEncoder,
     {
-        compute_hash_on_elements(&[
-            PREFIX_INVOKE,
-            if query_only { QUERY_VERSION_ONE } else { Felt::ONE }, // version
-            address,
-            Felt::ZERO, // entry_point_selector
-            compute_hash_on_elements(&encoder.encode_calls(&self.calls)),
-            self.max_fee,
+        let fields = V1Fields {
+            version: transaction_version(1, query_only),
+            sender_address: address,
+            max_fee: self.max_fee,
             chain_id,
-            self.nonce,
-        ])
+            nonce: self.nonce,
+        };
+        invoke_v1_hash(&fields, &encoder.encode_calls(&self.calls))
     }
 
     pub fn calls(&self) -> &[Call] {
@@ -512,48 +578,19 @@ impl RawExecutionV3 {
     where
         E: ExecutionEncoder,
     {
-        // Main data vector to collect all elements for hashing
-        let mut data = vec![PREFIX_INVOKE, if query_only { QUERY_VERSION_THREE } else { Felt::THREE }, address];
-
-        // Fee data collection
-        let mut fee_data = vec![Felt::ZERO]; // Hard-coded fee market
-
-        // First L1 gas resource buffer
-        let mut resource_buffer = [
-            0, 0, b'L', b'1', b'_', b'G', b'A', b'S', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0,
-        ];
-        resource_buffer[8..(8 + 8)].copy_from_slice(&self.gas.to_be_bytes());
-        resource_buffer[(8 + 8)..].copy_from_slice(&self.gas_price.to_be_bytes());
-        fee_data.push(Felt::from_bytes_be(&resource_buffer));
-
-        // Second L2 gas resource buffer
-        let resource_buffer = [
-            0, 0, b'L', b'2', b'_', b'G', b'A', b'S', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0,
-        ];
-        fee_data.push(Felt::from_bytes_be(&resource_buffer));
-
-        // Hash the fee data and add it to main data
-        data.push(Poseidon::hash_array(&fee_data));
-
-        // Hard-coded empty `paymaster_data`
-        data.push(Poseidon::hash_array(&[]));
-
-        // Remaining transaction fields
-        data.push(chain_id);
-        data.push(self.nonce);
-        data.push(Felt::ZERO); // Hard-coded L1 DA mode for nonce and fee
-
-        // Hard-coded empty `account_deployment_data`
-        data.push(Poseidon::hash_array(&[]));
-
-        // Calldata hashing
-        let calldata_elements: Vec<Felt> = encoder.encode_calls(&self.calls);
-        data.push(Poseidon::hash_array(&calldata_elements));
-
-        // Final hash computation
-        Poseidon::hash_array(&data)
+        // Hard-coded empty `paymaster_data` and `account_deployment_data`, L1 DA mode for nonce and fee
+        let fields = V3Fields {
+            version: transaction_version(3, query_only),
+            sender_address: address,
+            fee_data_hash: target_constants()
+                .fee_data_hash(Felt::from(self.tip), &[(Resource::L1Gas, self.gas, self.gas_price)]),
+            paymaster_data: &[],
+            chain_id,
+            nonce: self.nonce,
+            nonce_data_availability_mode: DaMode::L1,
+            fee_data_availability_mode: DaMode::L1,
+        };
+        invoke_v3_hash(&fields, &[], &encoder.encode_calls(&self.calls))
     }
 
     pub fn calls(&self) -> &[Call] {
@@ -571,6 +608,10 @@ impl RawExecutionV3 {
     pub fn gas_price(&self) -> u128 {
         self.gas_price
     }
+
+    pub fn tip(&self) -> u64 {
+        self.tip
+    }
 }
 impl<A> PreparedExecutionV1<'_, A>
 where
@@ -625,6 +666,24 @@ where
     // The `simulate` function is temporarily removed until it's supported in [Provider]
     // TODO: add `simulate` back once transaction simulation in supported
 
+    /// Request signed over the query version of the transaction hash when `query_only` is set, the
+    /// plain version otherwise, and carrying the same version so the node hashes it the way it was
+    /// signed. Query transactions are accepted by fee estimation and simulation only.
+    pub async fn get_broadcasted_invoke(
+        &self,
+        query_only: bool,
+        skip_signature: bool,
+    ) -> Result<BroadcastedTxn<Felt>, A::SignError> {
+        let invoke = self.get_invoke_request(query_only, skip_signature).await?;
+        Ok(BroadcastedTxn::Invoke(if query_only {
+            BroadcastedInvokeTxn::QueryV1(invoke)
+        } else {
+            BroadcastedInvokeTxn::V1(invoke)
+        }))
+    }
+
+    /// Signs over the query version of the transaction hash when `query_only` is set. The returned
+    /// request carries no version, see [Self::get_broadcasted_invoke].
     pub async fn get_invoke_request(
         &self,
         query_only: bool,
@@ -701,6 +760,24 @@ where
     // The `simulate` function is temporarily removed until it's supported in [Provider]
     // TODO: add `simulate` back once transaction simulation in supported
 
+    /// Request signed over the query version of the transaction hash when `query_only` is set, the
+    /// plain version otherwise, and carrying the same version so the node hashes it the way it was
+    /// signed. Query transactions are accepted by fee estimation and simulation only.
+    pub async fn get_broadcasted_invoke(
+        &self,
+        query_only: bool,
+        skip_signature: bool,
+    ) -> Result<BroadcastedTxn<Felt>, A::SignError> {
+        let invoke = self.get_invoke_request(query_only, skip_signature).await?;
+        Ok(BroadcastedTxn::Invoke(if query_only {
+            BroadcastedInvokeTxn::QueryV3(invoke)
+        } else {
+            BroadcastedInvokeTxn::V3(invoke)
+        }))
+    }
+
+    /// Signs over the query version of the transaction hash when `query_only` is set. The returned
+    /// request carries no version, see [Self::get_broadcasted_invoke].
     pub async fn get_invoke_request(
         &self,
         query_only: bool,
@@ -715,16 +792,9 @@ where
                 self.account.sign_execution_v3(&self.inner, query_only).await?
             },
             nonce: self.inner.nonce,
-            resource_bounds: ResourceBoundsMapping {
-                l1_gas: ResourceBounds {
-                    max_amount: Felt::from_dec_str(&self.inner.gas.to_string()).unwrap().to_hex_string(),
-                    max_price_per_unit: Felt::from_dec_str(&self.inner.gas_price.to_string()).unwrap().to_hex_string(),
-                },
-                // L2 resources are hard-coded to 0
-                l2_gas: ResourceBounds { max_amount: "0x0".to_string(), max_price_per_unit: "0x0".to_string() },
-            },
-            // Fee market has not been been activated yet so it's hard-coded to be 0
-            tip: Felt::ZERO,
+            // L2 resources are left at 0
+            resource_bounds: ResourceBoundsBuilder::new().l1_gas(self.inner.gas, self.inner.gas_price).build(),
+            tip: Felt::from(self.inner.tip),
             // Hard-coded empty `paymaster_data`
             paymaster_data: vec![],
             // Hard-coded empty `account_deployment_data`
@@ -744,16 +814,9 @@ where
             calldata: self.account.encode_calls(&self.inner.calls),
             signature,
             nonce: self.inner.nonce,
-            resource_bounds: ResourceBoundsMapping {
-                l1_gas: ResourceBounds {
-                    max_amount: Felt::from_dec_str(&self.inner.gas.to_string()).unwrap().to_hex_string(),
-                    max_price_per_unit: Felt::from_dec_str(&self.inner.gas_price.to_string()).unwrap().to_hex_string(),
-                },
-                // L2 resources are hard-coded to 0
-                l2_gas: ResourceBounds { max_amount: "0x0".to_string(), max_price_per_unit: "0x0".to_string() },
-            },
-            // Fee market has not been been activated yet so it's hard-coded to be 0
-            tip: Felt::ZERO,
+            // L2 resources are left at 0
+            resource_bounds: ResourceBoundsBuilder::new().l1_gas(self.inner.gas, self.inner.gas_price).build(),
+            tip: Felt::from(self.inner.tip),
             // Hard-coded empty `paymaster_data`
             paymaster_data: vec![],
             // Hard-coded empty `account_deployment_data`
@@ -2,26 +2,24 @@ use crate::utils::v7::providers::provider::{Provider, ProviderError};
 
 use auto_impl::auto_impl;
 
-use sha3::{Digest, Keccak256};
-
 use std::fmt::Debug;
 
-use starknet_types_core::felt::{Felt, NonZeroFelt};
-use starknet_types_core::hash::{Poseidon, StarkHash};
-use starknet_types_rpc::v0_7_1::{BlockId, BlockTag, ContractClass, SierraEntryPoint};
+use starknet_hive_hashing::class::sierra_class_hash;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::{BlockId, BlockTag, ContractClass};
 use std::{error::Error, sync::Arc};
 
 use super::{
     call::Call,
     errors::{CompressProgramError, ComputeClassHashError},
+    fee_strategy::FeeStrategy,
 };
 
 mod declaration;
 mod execution;
 
-// 2 ** 251 - 256
-const ADDR_BOUND: NonZeroFelt =
-    NonZeroFelt::from_raw([576459263475590224, 18446744073709255680, 160989183, 18446743986131443745]);
+pub use starknet_hive_hashing::address::normalize_address;
+pub use starknet_hive_hashing::keccak::starknet_keccak;
 
 /// Converts Cairo short string to [Felt].
 pub fn cairo_short_string_to_felt(str: &str) -> Result<Felt, CairoShortStringToFeltError> {
@@ -145,6 +143,7 @@ pub struct ExecutionV1<'a, A> {
     nonce: Option<Felt>,
     max_fee: Option<Felt>,
     fee_estimate_multiplier: f64,
+    fee_strategy: Option<Arc<dyn FeeStrategy>>,
 }
 
 /// Abstraction over `INVOKE` transactions from accounts for invoking contracts. This struct uses
@@ -163,6 +162,8 @@ pub struct ExecutionV3<'a, A> {
     gas_price: Option<u128>,
     gas_estimate_multiplier: f64,
     gas_price_estimate_multiplier: f64,
+    fee_strategy: Option<Arc<dyn FeeStrategy>>,
+    tip: u64,
 }
 
 /// Abstraction over `DECLARE` transactions from accounts for invoking contracts. This struct uses
@@ -215,6 +216,7 @@ pub struct RawExecutionV3 {
     nonce: Felt,
     gas: u64,
     gas_price: u128,
+    tip: u64,
 }
 
 /// [DeclarationV2] but with `nonce` and `max_fee` already determined.
@@ -226,65 +228,14 @@ pub struct RawDeclarationV2 {
     max_fee: Felt,
 }
 
-const PREFIX_CONTRACT_CLASS_V0_1_0: Felt =
-    Felt::from_raw([37302452645455172, 18446734822722598327, 15539482671244488427, 5800711240972404213]);
 pub trait ContractClassHasher {
     fn class_hash(&self) -> Felt;
 }
 
 impl ContractClassHasher for ContractClass<Felt> {
     fn class_hash(&self) -> Felt {
-        let data = vec![
-            PREFIX_CONTRACT_CLASS_V0_1_0,
-            hash_entrypoints(&self.entry_points_by_type.external),
-            hash_entrypoints(&self.entry_points_by_type.l1_handler),
-            hash_entrypoints(&self.entry_points_by_type.constructor),
-            starknet_keccak(self.abi.clone().expect("Abi expected").as_bytes()),
-            Poseidon::hash_array(&self.sierra_program),
-        ];
-
-        normalize_address(Poseidon::hash_array(&data))
-    }
-}
-
-pub fn normalize_address(address: Felt) -> Felt {
-    address.mod_floor(&ADDR_BOUND)
-}
-
-pub fn hash_entrypoints(entrypoints: &[SierraEntryPoint<Felt>]) -> Felt {
-    let mut data = Vec::new();
-
-    for entry in entrypoints.iter() {
-        data.push(entry.selector);
-        data.push(entry.function_idx.into());
+        sierra_class_hash(&self.entry_points_by_type, self.abi.as_deref().expect("Abi expected"), &self.sierra_program)
     }
-
-    Poseidon::hash_array(&data)
-}
-
-// pub fn starknet_keccak(data: &[u8]) -> Felt {
-//     let mut hasher = Keccak256::new();
-//     hasher.update(data);
-//     let mut hash = hasher.finalize();
-
-//     // Remove the first 6 bits
-//     hash[0] &= 0b00000011;
-
-//     // Because we know hash is always 32 bytes
-//     Felt::from_bytes_be(unsafe { &*(hash[..].as_ptr() as *const [u8; 32]) })
-// }
-
-pub fn starknet_keccak(data: &[u8]) -> Felt {
-    let mut hasher = Keccak256::new();
-    hasher.update(data);
-    let hash = hasher.finalize();
-
-    // Convert hash to big-endian integer and mask to 250 bits
-    let mut hash_bytes = [0u8; 32];
-    hash_bytes.copy_from_slice(&hash[..32]);
-    hash_bytes[0] &= 0b00000011; // Ensure only the lowest 250 bits are kept
-
-    Felt::from_bytes_be(&hash_bytes)
 }
 
 /// [DeclarationV3] but with `nonce`, `gas` and `gas_price` already determined.
@@ -2,18 +2,31 @@ use starknet_types_core::felt::Felt;
 use tracing::debug;
 
 use crate::utils::v7::{
-    accounts::errors::CreationError,
+    accounts::{errors::CreationError, factory::argent::argent_constructor_calldata},
     providers::jsonrpc::{HttpTransport, JsonRpcClient},
 };
 
 use super::{
-    helpers::{extract_or_generate_salt, generate_account, OZ_CLASS_HASH},
+    helpers::{extract_or_generate_salt, generate_account, ARGENT_CLASS_HASH, BRAAVOS_BASE_CLASS_HASH, OZ_CLASS_HASH},
     structs::GenerateAccountResponse,
 };
 
 #[derive(Clone, Copy, Debug)]
 pub enum AccountType {
     Oz,
+    Argent,
+    Braavos,
+}
+
+impl AccountType {
+    /// Constructor calldata of an account of this type owned by `public_key`, the calldata its
+    /// [AccountFactory](crate::utils::v7::accounts::factory::AccountFactory) deploys it with.
+    pub fn constructor_calldata(&self, public_key: Felt) -> Vec<Felt> {
+        match self {
+            AccountType::Oz | AccountType::Braavos => vec![public_key],
+            AccountType::Argent => argent_constructor_calldata(public_key, None),
+        }
+    }
 }
 
 pub async fn create_account(
@@ -25,8 +38,68 @@ pub async fn create_account(
     let salt = extract_or_generate_salt(salt);
     let class_hash = class_hash.unwrap_or_else(|| match account_type {
         AccountType::Oz => Felt::from_hex(OZ_CLASS_HASH).unwrap(),
+        AccountType::Argent => Felt::from_hex(ARGENT_CLASS_HASH).unwrap(),
+        AccountType::Braavos => Felt::from_hex(BRAAVOS_BASE_CLASS_HASH).unwrap(),
     });
-    debug!("Account OZ Class Hash: {:?}", class_hash);
+    debug!("Account {:?} Class Hash: {:?}", account_type, class_hash);
     let account_response = generate_account(provider, salt, class_hash, &account_type).await?;
     Ok(account_response)
 }
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+    use crate::utils::v7::{
+        accounts::factory::{
+            argent::ArgentAccountFactory, braavos::BraavosAccountFactory, open_zeppelin::OpenZeppelinAccountFactory,
+            AccountFactory,
+        },
+        signers::{key_pair::SigningKey, local_wallet::LocalWallet},
+    };
+
+    fn signer() -> LocalWallet {
+        LocalWallet::from(SigningKey::from_secret_scalar(Felt::from(0x1234)))
+    }
+
+    fn provider() -> JsonRpcClient<HttpTransport> {
+        JsonRpcClient::new(HttpTransport::new(Url::parse("http://127.0.0.1:5050").unwrap()))
+    }
+
+    fn public_key() -> Felt {
+        SigningKey::from_secret_scalar(Felt::from(0x1234)).verifying_key().scalar()
+    }
+
+    #[tokio::test]
+    async fn oz_constructor_takes_the_public_key() {
+        let factory = OpenZeppelinAccountFactory::new(Felt::ONE, Felt::TWO, signer(), provider()).await.unwrap();
+
+        assert_eq!(AccountType::Oz.constructor_calldata(public_key()), vec![public_key()]);
+        assert_eq!(factory.calldata(), AccountType::Oz.constructor_calldata(public_key()));
+    }
+
+    #[tokio::test]
+    async fn argent_constructor_takes_a_starknet_owner_and_an_optional_guardian() {
+        let factory = ArgentAccountFactory::new(Felt::ONE, Felt::TWO, None, signer(), provider()).await.unwrap();
+
+        // `Signer::Starknet(owner)`, then `Option::None` for the guardian.
+        let expected = vec![Felt::ZERO, public_key(), Felt::ONE];
+        assert_eq!(AccountType::Argent.constructor_calldata(public_key()), expected);
+        assert_eq!(factory.calldata(), expected);
+
+        let guardian = Felt::from(0x5678);
+        let factory =
+            ArgentAccountFactory::new(Felt::ONE, Felt::TWO, Some(guardian), signer(), provider()).await.unwrap();
+        assert_eq!(factory.calldata(), vec![Felt::ZERO, public_key(), Felt::ZERO, Felt::ZERO, guardian]);
+    }
+
+    #[tokio::test]
+    async fn braavos_constructor_takes_the_public_key() {
+        let factory =
+            BraavosAccountFactory::new(Felt::ONE, Felt::from(3), Felt::TWO, signer(), provider()).await.unwrap();
+
+        assert_eq!(AccountType::Braavos.constructor_calldata(public_key()), vec![public_key()]);
+        assert_eq!(factory.calldata(), AccountType::Braavos.constructor_calldata(public_key()));
+    }
+}
@@ -1,7 +1,10 @@
 use crate::utils::v7::{
     accounts::{
         errors::CreationError,
-        factory::{open_zeppelin::OpenZeppelinAccountFactory, AccountDeploymentV3, AccountFactory},
+        factory::{
+            argent::ArgentAccountFactory, braavos::BraavosAccountFactory, open_zeppelin::OpenZeppelinAccountFactory,
+            AccountDeploymentV3, AccountFactory,
+        },
     },
     providers::{
         jsonrpc::{HttpTransport, JsonRpcClient, StarknetError},
@@ -16,6 +19,12 @@ use starknet_types_rpc::v0_7_1::{BlockId, BlockTag, FeeEstimate};
 use super::{create::AccountType, structs::GenerateAccountResponse};
 
 pub const OZ_CLASS_HASH: &str = "0x61dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f";
+/// Argent X v0.4.0 account class, declared under the same hash on mainnet and Sepolia.
+pub const ARGENT_CLASS_HASH: &str = "0x036078334509b514626504edc9fb252328d1a240e4e948bef8d0c08dff45927f";
+/// Braavos base account class used for deployment, declared on mainnet and Sepolia.
+pub const BRAAVOS_BASE_CLASS_HASH: &str = "0x013bfe114fb1cf405bfc3a7f8dbe2d91db146c17521d40dcf57e16d6b59fa8e6";
+/// Braavos account implementation the base class upgrades to during deployment.
+pub const BRAAVOS_CLASS_HASH: &str = "0x00816dd0297efc55dc1e7559020a3a825e81ef734b558f03c83325d4da7e6253";
 
 pub fn extract_or_generate_salt(salt: Option<Felt>) -> Felt {
     salt.unwrap_or(Felt::from(OsRng.next_u64()))
@@ -51,12 +60,23 @@ pub async fn generate_account(
             let factory = OpenZeppelinAccountFactory::new(class_hash, chain_id, signer, provider).await.unwrap();
             get_address_and_deployment_fee(factory, salt).await?
         }
+        AccountType::Argent => {
+            let factory = ArgentAccountFactory::new(class_hash, chain_id, None, signer, provider).await.unwrap();
+            get_address_and_deployment_fee(factory, salt).await?
+        }
+        AccountType::Braavos => {
+            let factory =
+                BraavosAccountFactory::new(class_hash, Felt::from_hex(BRAAVOS_CLASS_HASH)?, chain_id, signer, provider)
+                    .await
+                    .unwrap();
+            get_address_and_deployment_fee(factory, salt).await?
+        }
     };
     let account_response = GenerateAccountResponse {
         signing_key,
         address,
         deployed: false,
-        account_type: AccountType::Oz,
+        account_type: *account_type,
         class_hash,
         salt,
         max_fee: Felt::from_dec_str(&fee_estimate.overall_fee.to_string()).unwrap(),
@@ -1,3 +1,4 @@
+use starknet_hive_hashing::address::contract_address;
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{
     v0_7_1::{BlockId, BlockTag, TxnHash},
@@ -7,10 +8,7 @@ use starknet_types_rpc::{
 
 use crate::utils::v7::{
     self,
-    accounts::{
-        creation::{create::AccountType, structs::GenerateAccountResponse},
-        errors::CreationError,
-    },
+    accounts::{creation::structs::GenerateAccountResponse, errors::CreationError},
     providers::{
         jsonrpc::{HttpTransport, JsonRpcClient},
         provider::Provider,
@@ -19,12 +17,13 @@ use crate::utils::v7::{
 
 use super::{
     helpers::{
-        get_contract_address, get_deployment_request, get_deployment_result, get_estimate_fee_deployment_result,
+        get_deployment_request, get_deployment_result, get_estimate_fee_deployment_result,
         simulate_get_deployment_result,
     },
     structs::WaitForTx,
 };
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DeployAccountVersion {
     V1,
     V3,
@@ -42,14 +41,12 @@ pub async fn deploy_account(
         return Ok(Felt::ZERO);
     }
     let public_key = account_data.signing_key.verifying_key();
-    let address = match account_data.account_type {
-        AccountType::Oz => get_contract_address(
-            account_data.salt,
-            account_data.class_hash,
-            &[public_key.scalar(), Felt::ZERO],
-            Felt::ZERO,
-        ),
-    };
+    let address = contract_address(
+        account_data.salt,
+        account_data.class_hash,
+        &account_data.account_type.constructor_calldata(public_key.scalar()),
+        Felt::ZERO,
+    );
 
     let result = if provider.get_class_hash_at(BlockId::Tag(BlockTag::Pending), address).await.is_ok() {
         Felt::ZERO
@@ -1,14 +1,15 @@
-use crypto_utils::curve::signer::compute_hash_on_elements;
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{DeployAccountTxn, FeeEstimate, SimulateTransactionsResult};
 
 use crate::utils::v7::{
     self,
     accounts::{
-        account::normalize_address,
-        creation::create::AccountType,
+        creation::{create::AccountType, helpers::BRAAVOS_CLASS_HASH},
         errors::CreationError,
-        factory::{open_zeppelin::OpenZeppelinAccountFactory, AccountFactory, AccountFactoryError},
+        factory::{
+            argent::ArgentAccountFactory, braavos::BraavosAccountFactory, open_zeppelin::OpenZeppelinAccountFactory,
+            AccountFactory, AccountFactoryError,
+        },
     },
     providers::jsonrpc::{HttpTransport, JsonRpcClient},
     signers::{key_pair::SigningKey, local_wallet::LocalWallet},
@@ -16,28 +17,6 @@ use crate::utils::v7::{
 
 use super::{deploy::DeployAccountVersion, structs::WaitForTx};
 
-// Cairo string of "STARKNET_CONTRACT_ADDRESS"
-const CONTRACT_ADDRESS_PREFIX: Felt =
-    Felt::from_raw([533439743893157637, 8635008616843941496, 17289941567720117366, 3829237882463328880]);
-
-/// Computes the target contract address of a "native" contract deployment. Use
-/// `get_udc_deployed_address` instead if you want to compute the target address for deployments
-/// through the Universal Deployer Contract.
-pub fn get_contract_address(
-    salt: Felt,
-    class_hash: Felt,
-    constructor_calldata: &[Felt],
-    deployer_address: Felt,
-) -> Felt {
-    normalize_address(compute_hash_on_elements(&[
-        CONTRACT_ADDRESS_PREFIX,
-        deployer_address,
-        salt,
-        class_hash,
-        compute_hash_on_elements(constructor_calldata),
-    ]))
-}
-
 #[allow(clippy::too_many_arguments)]
 pub async fn get_estimate_fee_deployment_result(
     provider: &JsonRpcClient<HttpTransport>,
@@ -54,20 +33,22 @@ pub async fn get_estimate_fee_deployment_result(
     FeeEstimate<Felt>,
     crate::utils::v7::accounts::factory::AccountFactoryError<v7::signers::local_wallet::SignError>,
 > {
+    let signer = LocalWallet::from_signing_key(signing_key);
     match account_type {
         AccountType::Oz => {
-            estimate_fee_deploy_oz_account(
-                provider,
-                class_hash,
-                signing_key,
-                salt,
-                chain_id,
-                max_fee,
-                wait_config,
-                skip_validate,
-                version,
-            )
-            .await
+            let factory = OpenZeppelinAccountFactory::new(class_hash, chain_id, signer, provider).await.unwrap();
+            estimate_fee_deployment(factory, provider, salt, max_fee, wait_config, class_hash, skip_validate, version)
+                .await
+        }
+        AccountType::Argent => {
+            let factory = ArgentAccountFactory::new(class_hash, chain_id, None, signer, provider).await.unwrap();
+            estimate_fee_deployment(factory, provider, salt, max_fee, wait_config, class_hash, skip_validate, version)
+                .await
+        }
+        AccountType::Braavos => {
+            let factory = braavos_factory(class_hash, chain_id, signer, provider).await;
+            estimate_fee_deployment(factory, provider, salt, max_fee, wait_config, class_hash, skip_validate, version)
+                .await
         }
     }
 }
@@ -89,16 +70,47 @@ pub async fn simulate_get_deployment_result(
     SimulateTransactionsResult<Felt>,
     crate::utils::v7::accounts::factory::AccountFactoryError<v7::signers::local_wallet::SignError>,
 > {
+    let signer = LocalWallet::from_signing_key(signing_key);
     match account_type {
         AccountType::Oz => {
-            simulate_deploy_oz_account(
+            let factory = OpenZeppelinAccountFactory::new(class_hash, chain_id, signer, provider).await.unwrap();
+            simulate_deployment(
+                factory,
                 provider,
+                salt,
+                max_fee,
+                wait_config,
                 class_hash,
-                signing_key,
+                skip_validate,
+                skip_fee_charge,
+                version,
+            )
+            .await
+        }
+        AccountType::Argent => {
+            let factory = ArgentAccountFactory::new(class_hash, chain_id, None, signer, provider).await.unwrap();
+            simulate_deployment(
+                factory,
+                provider,
                 salt,
-                chain_id,
                 max_fee,
                 wait_config,
+                class_hash,
+                skip_validate,
+                skip_fee_charge,
+                version,
+            )
+            .await
+        }
+        AccountType::Braavos => {
+            let factory = braavos_factory(class_hash, chain_id, signer, provider).await;
+            simulate_deployment(
+                factory,
+                provider,
+                salt,
+                max_fee,
+                wait_config,
+                class_hash,
                 skip_validate,
                 skip_fee_charge,
                 version,
@@ -120,90 +132,23 @@ pub async fn get_deployment_result(
     wait_config: WaitForTx,
     version: DeployAccountVersion,
 ) -> Result<Felt, CreationError> {
+    let signer = LocalWallet::from_signing_key(signing_key);
     match account_type {
         AccountType::Oz => {
-            deploy_oz_account(provider, class_hash, signing_key, salt, chain_id, max_fee, wait_config, version).await
+            let factory = OpenZeppelinAccountFactory::new(class_hash, chain_id, signer, provider).await.unwrap();
+            deploy_account(factory, provider, salt, max_fee, wait_config, class_hash, version).await
+        }
+        AccountType::Argent => {
+            let factory = ArgentAccountFactory::new(class_hash, chain_id, None, signer, provider).await.unwrap();
+            deploy_account(factory, provider, salt, max_fee, wait_config, class_hash, version).await
+        }
+        AccountType::Braavos => {
+            let factory = braavos_factory(class_hash, chain_id, signer, provider).await;
+            deploy_account(factory, provider, salt, max_fee, wait_config, class_hash, version).await
         }
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn deploy_oz_account(
-    provider: &JsonRpcClient<HttpTransport>,
-    class_hash: Felt,
-    signing_key: SigningKey,
-    salt: Felt,
-    chain_id: Felt,
-    max_fee: Option<Felt>,
-    wait_config: WaitForTx,
-    version: DeployAccountVersion,
-) -> Result<Felt, CreationError> {
-    let factory =
-        OpenZeppelinAccountFactory::new(class_hash, chain_id, LocalWallet::from_signing_key(signing_key), provider)
-            .await
-            .unwrap();
-
-    deploy_account(factory, provider, salt, max_fee, wait_config, class_hash, version).await
-}
-
-#[allow(clippy::too_many_arguments)]
-async fn simulate_deploy_oz_account(
-    provider: &JsonRpcClient<HttpTransport>,
-    class_hash: Felt,
-    signing_key: SigningKey,
-    salt: Felt,
-    chain_id: Felt,
-    max_fee: Option<Felt>,
-    wait_config: WaitForTx,
-    skip_validate: bool,
-    skip_fee_charge: bool,
-    version: DeployAccountVersion,
-) -> Result<
-    SimulateTransactionsResult<Felt>,
-    crate::utils::v7::accounts::factory::AccountFactoryError<v7::signers::local_wallet::SignError>,
-> {
-    let factory =
-        OpenZeppelinAccountFactory::new(class_hash, chain_id, LocalWallet::from_signing_key(signing_key), provider)
-            .await
-            .unwrap();
-
-    simulate_deployment(
-        factory,
-        provider,
-        salt,
-        max_fee,
-        wait_config,
-        class_hash,
-        skip_validate,
-        skip_fee_charge,
-        version,
-    )
-    .await
-}
-
-#[allow(clippy::too_many_arguments)]
-async fn estimate_fee_deploy_oz_account(
-    provider: &JsonRpcClient<HttpTransport>,
-    class_hash: Felt,
-    signing_key: SigningKey,
-    salt: Felt,
-    chain_id: Felt,
-    max_fee: Option<Felt>,
-    wait_config: WaitForTx,
-    skip_validate: bool,
-    version: DeployAccountVersion,
-) -> Result<
-    FeeEstimate<Felt>,
-    crate::utils::v7::accounts::factory::AccountFactoryError<v7::signers::local_wallet::SignError>,
-> {
-    let factory =
-        OpenZeppelinAccountFactory::new(class_hash, chain_id, LocalWallet::from_signing_key(signing_key), provider)
-            .await
-            .unwrap();
-
-    estimate_fee_deployment(factory, provider, salt, max_fee, wait_config, class_hash, skip_validate, version).await
-}
-
 #[allow(clippy::too_many_arguments)]
 pub async fn get_deployment_request(
     provider: &JsonRpcClient<HttpTransport>,
@@ -216,15 +161,34 @@ pub async fn get_deployment_request(
     wait_config: WaitForTx,
     version: DeployAccountVersion,
 ) -> Result<DeployAccountTxn<Felt>, CreationError> {
-    let factory =
-        OpenZeppelinAccountFactory::new(class_hash, chain_id, LocalWallet::from_signing_key(signing_key), provider)
-            .await
-            .unwrap();
+    let signer = LocalWallet::from_signing_key(signing_key);
     match account_type {
-        AccountType::Oz => deploy_acc_request(factory, provider, salt, max_fee, wait_config, class_hash, version).await,
+        AccountType::Oz => {
+            let factory = OpenZeppelinAccountFactory::new(class_hash, chain_id, signer, provider).await.unwrap();
+            deploy_acc_request(factory, provider, salt, max_fee, wait_config, class_hash, version).await
+        }
+        AccountType::Argent => {
+            let factory = ArgentAccountFactory::new(class_hash, chain_id, None, signer, provider).await.unwrap();
+            deploy_acc_request(factory, provider, salt, max_fee, wait_config, class_hash, version).await
+        }
+        AccountType::Braavos => {
+            let factory = braavos_factory(class_hash, chain_id, signer, provider).await;
+            deploy_acc_request(factory, provider, salt, max_fee, wait_config, class_hash, version).await
+        }
     }
 }
 
+async fn braavos_factory(
+    class_hash: Felt,
+    chain_id: Felt,
+    signer: LocalWallet,
+    provider: &JsonRpcClient<HttpTransport>,
+) -> BraavosAccountFactory<LocalWallet, &JsonRpcClient<HttpTransport>> {
+    BraavosAccountFactory::new(class_hash, Felt::from_hex_unchecked(BRAAVOS_CLASS_HASH), chain_id, signer, provider)
+        .await
+        .unwrap()
+}
+
 #[allow(unused_variables)]
 async fn deploy_account<T>(
     account_factory: T,
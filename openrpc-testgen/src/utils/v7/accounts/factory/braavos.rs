@@ -0,0 +1,143 @@
+use crate::utils::v7::providers::provider::Provider;
+use crate::utils::v7::signers::signer::Signer;
+
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash};
+use starknet_types_rpc::v0_7_1::{BlockId, BlockTag};
+
+use super::{
+    AccountFactory, PreparedAccountDeploymentV1, PreparedAccountDeploymentV3, RawAccountDeploymentV1,
+    RawAccountDeploymentV3,
+};
+
+/// [AccountFactory] implementation for deploying Braavos account contracts.
+///
+/// Braavos accounts are deployed from a base class which upgrades itself to
+/// `implementation_class_hash` inside the constructor, so the deployment signature carries an
+/// additional auxiliary payload signed by the same key.
+pub struct BraavosAccountFactory<S, P> {
+    class_hash: Felt,
+    implementation_class_hash: Felt,
+    chain_id: Felt,
+    public_key: Felt,
+    signer: S,
+    provider: P,
+    block_id: BlockId<Felt>,
+}
+
+impl<S, P> BraavosAccountFactory<S, P>
+where
+    S: Signer,
+{
+    pub async fn new(
+        class_hash: Felt,
+        implementation_class_hash: Felt,
+        chain_id: Felt,
+        signer: S,
+        provider: P,
+    ) -> Result<Self, S::GetPublicKeyError> {
+        let public_key = signer.get_public_key().await?;
+        Ok(Self {
+            class_hash,
+            implementation_class_hash,
+            chain_id,
+            public_key: public_key.scalar(),
+            signer,
+            provider,
+            block_id: BlockId::Tag(BlockTag::Pending),
+        })
+    }
+
+    pub fn set_block_id(&mut self, block_id: BlockId<Felt>) -> &Self {
+        self.block_id = block_id;
+        self
+    }
+
+    async fn sign_with_aux_data(&self, tx_hash: Felt) -> Result<Vec<Felt>, S::SignError> {
+        let signature = self.signer.sign_hash(&tx_hash).await?;
+
+        let mut aux_data = vec![
+            // account_implementation
+            self.implementation_class_hash,
+            // signer_type
+            Felt::ZERO,
+            // secp256r1_signer.x.low
+            Felt::ZERO,
+            // secp256r1_signer.x.high
+            Felt::ZERO,
+            // secp256r1_signer.y.low
+            Felt::ZERO,
+            // secp256r1_signer.y.high
+            Felt::ZERO,
+            // multisig_threshold
+            Felt::ZERO,
+            // withdrawal_limit_low
+            Felt::ZERO,
+            // fee_rate
+            Felt::ZERO,
+            // stark_fee_rate
+            Felt::ZERO,
+            // chain_id
+            self.chain_id,
+        ];
+        let aux_signature = self.signer.sign_hash(&Poseidon::hash_array(&aux_data)).await?;
+
+        let mut full_signature = vec![signature.r, signature.s];
+        full_signature.append(&mut aux_data);
+        full_signature.push(aux_signature.r);
+        full_signature.push(aux_signature.s);
+
+        Ok(full_signature)
+    }
+}
+
+impl<S, P> AccountFactory for BraavosAccountFactory<S, P>
+where
+    S: Signer + Sync + Send,
+    P: Provider + Sync + Send,
+{
+    type Provider = P;
+    type SignError = S::SignError;
+
+    fn class_hash(&self) -> Felt {
+        self.class_hash
+    }
+
+    fn calldata(&self) -> Vec<Felt> {
+        vec![self.public_key]
+    }
+
+    fn chain_id(&self) -> Felt {
+        self.chain_id
+    }
+
+    fn provider(&self) -> &Self::Provider {
+        &self.provider
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        self.signer.is_interactive()
+    }
+
+    fn block_id(&self) -> BlockId<Felt> {
+        self.block_id.clone()
+    }
+
+    async fn sign_deployment_v1(
+        &self,
+        deployment: &RawAccountDeploymentV1,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        let tx_hash = PreparedAccountDeploymentV1::from_raw(deployment.clone(), self).transaction_hash(query_only);
+        self.sign_with_aux_data(tx_hash).await
+    }
+
+    async fn sign_deployment_v3(
+        &self,
+        deployment: &RawAccountDeploymentV3,
+        _query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        let tx_hash = PreparedAccountDeploymentV3::from_raw(deployment.clone(), self).transaction_hash(false);
+        self.sign_with_aux_data(tx_hash).await
+    }
+}
@@ -2,24 +2,27 @@ use super::errors::NotPreparedError;
 
 use serde::{Deserialize, Serialize};
 
-use crypto_utils::curve::signer::compute_hash_on_elements;
+use crypto_utils::protocol::{target_constants, Resource};
+use starknet_hive_hashing::address::contract_address;
+use starknet_hive_hashing::resource_bounds::ResourceBoundsBuilder;
+use starknet_hive_hashing::transaction::{deploy_account_v1_hash, deploy_account_v3_hash, V1Fields, V3Fields};
 use starknet_types_core::felt::Felt;
-use starknet_types_core::felt::NonZeroFelt;
-use starknet_types_core::hash::{Poseidon, StarkHash};
 use starknet_types_rpc::v0_7_1::{
     BlockId, BlockTag, BroadcastedDeployAccountTxn, BroadcastedTxn, ContractAndTxnHash, DeployAccountTxnV1,
     FeeEstimate, SimulateTransactionsResult, SimulationFlag,
 };
-use starknet_types_rpc::{
-    DaMode, DeployAccountTxnV3, MaybePendingBlockWithTxHashes, ResourceBounds, ResourceBoundsMapping,
-};
+use starknet_types_rpc::{DaMode, DeployAccountTxnV3, MaybePendingBlockWithTxHashes};
 
+use crate::utils::fee_accuracy::{record_fee_estimate, TransactionKind};
+use crate::utils::fee_overrides::fee_overrides;
 use crate::utils::v7::providers::{
     jsonrpc::StarknetError,
     provider::{Provider, ProviderError},
 };
 use std::error::Error;
 
+pub mod argent;
+pub mod braavos;
 pub mod open_zeppelin;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -30,18 +33,6 @@ pub enum DataAvailabilityMode {
     L2,
 }
 
-/// Cairo string for "deploy_account"
-const PREFIX_DEPLOY_ACCOUNT: Felt =
-    Felt::from_raw([461298303000467581, 18446744073709551615, 18443211694809419988, 3350261884043292318]);
-
-/// Cairo string for "STARKNET_CONTRACT_ADDRESS"
-const PREFIX_CONTRACT_ADDRESS: Felt =
-    Felt::from_raw([533439743893157637, 8635008616843941496, 17289941567720117366, 3829237882463328880]);
-
-// 2 ** 251 - 256
-const ADDR_BOUND: NonZeroFelt =
-    NonZeroFelt::from_raw([576459263475590224, 18446744073709255680, 160989183, 18446743986131443745]);
-
 /// This trait enables deploying account contracts using the `DeployAccount` transaction type.
 pub trait AccountFactory: Sized {
     type Provider: Provider + Sync;
@@ -173,7 +164,14 @@ pub enum AccountFactoryError<S> {
 }
 impl<'f, F> AccountDeploymentV1<'f, F> {
     pub fn new(salt: Felt, factory: &'f F) -> Self {
-        Self { factory, salt, nonce: None, max_fee: None, fee_estimate_multiplier: 1.1 }
+        let overrides = fee_overrides();
+        Self {
+            factory,
+            salt,
+            nonce: None,
+            max_fee: overrides.max_fee,
+            fee_estimate_multiplier: overrides.fee_estimate_multiplier,
+        }
     }
 
     pub fn nonce(self, nonce: Felt) -> Self {
@@ -203,14 +201,15 @@ impl<'f, F> AccountDeploymentV1<'f, F> {
 }
 impl<'f, F> AccountDeploymentV3<'f, F> {
     pub fn new(salt: Felt, factory: &'f F) -> Self {
+        let overrides = fee_overrides();
         Self {
             factory,
             salt,
             nonce: None,
-            gas: None,
-            gas_price: None,
-            gas_estimate_multiplier: 1.5,
-            gas_price_estimate_multiplier: 1.5,
+            gas: overrides.gas,
+            gas_price: overrides.gas_price,
+            gas_estimate_multiplier: overrides.gas_estimate_multiplier,
+            gas_price_estimate_multiplier: overrides.gas_price_estimate_multiplier,
         }
     }
 
@@ -255,7 +254,7 @@ where
 {
     /// Locally calculates the target deployment address.
     pub fn address(&self) -> Felt {
-        calculate_contract_address(self.salt, self.factory.class_hash(), &self.factory.calldata())
+        contract_address(self.salt, self.factory.class_hash(), &self.factory.calldata(), Felt::ZERO)
     }
 
     pub async fn fetch_nonce(&self) -> Result<Felt, ProviderError> {
@@ -291,10 +290,23 @@ where
     }
 
     pub async fn send(&self) -> Result<ContractAndTxnHash<Felt>, AccountFactoryError<F::SignError>> {
-        self.prepare().await?.send().await
+        let (prepared, fee_estimate) = self.prepare_with_fee_estimate().await?;
+        let result = prepared.send().await?;
+        if let Some(fee_estimate) = fee_estimate {
+            record_fee_estimate(TransactionKind::DeployAccountV1, result.transaction_hash, &fee_estimate);
+        }
+        Ok(result)
     }
 
     pub async fn prepare(&self) -> Result<PreparedAccountDeploymentV1<'f, F>, AccountFactoryError<F::SignError>> {
+        self.prepare_with_fee_estimate().await.map(|(prepared, _)| prepared)
+    }
+
+    /// Also returns the fee estimate the transaction is prepared with, when fees were estimated.
+    async fn prepare_with_fee_estimate(
+        &self,
+    ) -> Result<(PreparedAccountDeploymentV1<'f, F>, Option<FeeEstimate<Felt>>), AccountFactoryError<F::SignError>>
+    {
         // Resolves nonce
         let nonce = match self.nonce {
             Some(value) => value,
@@ -302,8 +314,8 @@ where
         };
 
         // Resolves max_fee
-        let max_fee = match self.max_fee {
-            Some(value) => value,
+        let (max_fee, fee_estimate) = match self.max_fee {
+            Some(value) => (value, None),
             None => {
                 // TODO: remove this when a proper u64 conversion is implemented for `Felt`
                 // Obtain the fee estimate
@@ -320,7 +332,8 @@ where
                 let overall_fee_u64 = u64::from_le_bytes(overall_fee_bytes[..8].try_into().unwrap());
 
                 // Perform necessary operations on overall_fee_u64 and convert to f64 then to u64
-                (((overall_fee_u64 as f64) * self.fee_estimate_multiplier) as u64).into()
+                let max_fee: Felt = (((overall_fee_u64 as f64) * self.fee_estimate_multiplier) as u64).into();
+                (max_fee, Some(fee_estimate))
             }
         };
         let res: PreparedAccountDeploymentV1<F> = PreparedAccountDeploymentV1 {
@@ -328,7 +341,7 @@ where
             inner: RawAccountDeploymentV1 { salt: self.salt, nonce, max_fee },
         };
 
-        Ok(res)
+        Ok((res, fee_estimate))
     }
 
     async fn estimate_fee_with_nonce(
@@ -404,7 +417,7 @@ where
 {
     /// Locally calculates the target deployment address.
     pub fn address(&self) -> Felt {
-        calculate_contract_address(self.salt, self.factory.class_hash(), &self.factory.calldata())
+        contract_address(self.salt, self.factory.class_hash(), &self.factory.calldata(), Felt::ZERO)
     }
 
     pub async fn fetch_nonce(&self) -> Result<Felt, ProviderError> {
@@ -450,10 +463,23 @@ where
     }
 
     pub async fn send(&self) -> Result<ContractAndTxnHash<Felt>, AccountFactoryError<F::SignError>> {
-        self.prepare().await?.send().await
+        let (prepared, fee_estimate) = self.prepare_with_fee_estimate().await?;
+        let result = prepared.send().await?;
+        if let Some(fee_estimate) = fee_estimate {
+            record_fee_estimate(TransactionKind::DeployAccountV3, result.transaction_hash, &fee_estimate);
+        }
+        Ok(result)
     }
 
     pub async fn prepare(&self) -> Result<PreparedAccountDeploymentV3<'f, F>, AccountFactoryError<F::SignError>> {
+        self.prepare_with_fee_estimate().await.map(|(prepared, _)| prepared)
+    }
+
+    /// Also returns the fee estimate the transaction is prepared with, when fees were estimated.
+    async fn prepare_with_fee_estimate(
+        &self,
+    ) -> Result<(PreparedAccountDeploymentV3<'f, F>, Option<FeeEstimate<Felt>>), AccountFactoryError<F::SignError>>
+    {
         // Resolves nonce
         let nonce = match self.nonce {
             Some(value) => value,
@@ -461,8 +487,8 @@ where
         };
 
         // Resolves fee settings
-        let (gas, gas_price) = match (self.gas, self.gas_price) {
-            (Some(gas), Some(gas_price)) => (gas, gas_price),
+        let (gas, gas_price, fee_estimate) = match (self.gas, self.gas_price) {
+            (Some(gas), Some(gas_price)) => (gas, gas_price, None),
             (Some(gas), _) => {
                 // When `gas` is specified, we only need the L1 gas price in FRI. By specifying a
                 // a `gas` value, the user might be trying to avoid a full fee estimation (e.g.
@@ -495,7 +521,7 @@ where
 
                 let gas_price = ((block_l1_gas_price as f64) * self.gas_price_estimate_multiplier) as u128;
 
-                (gas, gas_price)
+                (gas, gas_price, None)
             }
             // We have to perform fee estimation as long as gas is not specified
             _ => {
@@ -533,14 +559,16 @@ where
                     }
                 };
 
-                (gas, gas_price)
+                (gas, gas_price, Some(fee_estimate))
             }
         };
 
-        Ok(PreparedAccountDeploymentV3 {
+        let prepared = PreparedAccountDeploymentV3 {
             factory: self.factory,
             inner: RawAccountDeploymentV3 { salt: self.salt, nonce, gas, gas_price },
-        })
+        };
+
+        Ok((prepared, fee_estimate))
     }
 
     async fn estimate_fee_with_nonce(
@@ -698,23 +726,18 @@ where
 {
     /// Locally calculates the target deployment address.
     pub fn address(&self) -> Felt {
-        calculate_contract_address(self.inner.salt, self.factory.class_hash(), &self.factory.calldata())
+        contract_address(self.inner.salt, self.factory.class_hash(), &self.factory.calldata(), Felt::ZERO)
     }
 
     pub fn transaction_hash(&self, _query_only: bool) -> Felt {
-        let mut calldata_to_hash = vec![self.factory.class_hash(), self.inner.salt];
-        calldata_to_hash.append(&mut self.factory.calldata());
-
-        compute_hash_on_elements(&[
-            PREFIX_DEPLOY_ACCOUNT,
-            Felt::ONE,
-            self.address(),
-            Felt::ZERO, // entry_point_selector
-            compute_hash_on_elements(&calldata_to_hash),
-            self.inner.max_fee,
-            self.factory.chain_id(),
-            self.inner.nonce,
-        ])
+        let fields = V1Fields {
+            version: Felt::ONE,
+            sender_address: self.address(),
+            max_fee: self.inner.max_fee,
+            chain_id: self.factory.chain_id(),
+            nonce: self.inner.nonce,
+        };
+        deploy_account_v1_hash(&fields, &self.factory.calldata(), self.factory.class_hash(), self.inner.salt)
     }
 
     pub async fn send(&self) -> Result<ContractAndTxnHash<Felt>, AccountFactoryError<F::SignError>> {
@@ -754,53 +777,23 @@ where
 {
     /// Locally calculates the target deployment address.
     pub fn address(&self) -> Felt {
-        calculate_contract_address(self.inner.salt, self.factory.class_hash(), &self.factory.calldata())
+        contract_address(self.inner.salt, self.factory.class_hash(), &self.factory.calldata(), Felt::ZERO)
     }
 
     pub fn transaction_hash(&self, _query_only: bool) -> Felt {
-        // Main data vector to collect all elements for hashing
-        let mut data = vec![PREFIX_DEPLOY_ACCOUNT, Felt::THREE, self.address()];
-
-        // Fee data collection
-        let mut fee_data = vec![Felt::ZERO]; // Hard-coded fee market
-
-        // First L1 gas resource buffer
-        let mut resource_buffer = [
-            0, 0, b'L', b'1', b'_', b'G', b'A', b'S', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0,
-        ];
-        resource_buffer[8..(8 + 8)].copy_from_slice(&self.inner.gas.to_be_bytes());
-        resource_buffer[(8 + 8)..].copy_from_slice(&self.inner.gas_price.to_be_bytes());
-        fee_data.push(Felt::from_bytes_be(&resource_buffer));
-
-        // Second L2 gas resource buffer
-        let resource_buffer = [
-            0, 0, b'L', b'2', b'_', b'G', b'A', b'S', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0,
-        ];
-        fee_data.push(Felt::from_bytes_be(&resource_buffer));
-
-        // Hash the fee data and add it to main data
-        data.push(Poseidon::hash_array(&fee_data));
-
-        // Hard-coded empty `paymaster_data`
-        data.push(Poseidon::hash_array(&[]));
-
-        // Remaining transaction fields
-        data.push(self.factory.chain_id());
-        data.push(self.inner.nonce);
-        data.push(Felt::ZERO); // Hard-coded L1 DA mode for nonce and fee
-
-        // Calldata hashing
-        let calldata_elements: Vec<Felt> = self.factory.calldata();
-        data.push(Poseidon::hash_array(&calldata_elements));
-
-        // Final elements
-        data.push(self.factory.class_hash());
-        data.push(self.inner.salt);
-
-        // Final hash computation
-        Poseidon::hash_array(&data)
+        // Hard-coded zero tip, empty `paymaster_data` and L1 DA mode for nonce and fee
+        let fields = V3Fields {
+            version: Felt::THREE,
+            sender_address: self.address(),
+            fee_data_hash: target_constants()
+                .fee_data_hash(Felt::ZERO, &[(Resource::L1Gas, self.inner.gas, self.inner.gas_price)]),
+            paymaster_data: &[],
+            chain_id: self.factory.chain_id(),
+            nonce: self.inner.nonce,
+            nonce_data_availability_mode: DaMode::L1,
+            fee_data_availability_mode: DaMode::L1,
+        };
+        deploy_account_v3_hash(&fields, &self.factory.calldata(), self.factory.class_hash(), self.inner.salt)
     }
 
     pub async fn send(&self) -> Result<ContractAndTxnHash<Felt>, AccountFactoryError<F::SignError>> {
@@ -827,14 +820,8 @@ where
             contract_address_salt: self.inner.salt,
             constructor_calldata: self.factory.calldata(),
             class_hash: self.factory.class_hash(),
-            resource_bounds: ResourceBoundsMapping {
-                l1_gas: ResourceBounds {
-                    max_amount: Felt::from_dec_str(&self.inner.gas.to_string()).unwrap().to_hex_string(),
-                    max_price_per_unit: Felt::from_dec_str(&self.inner.gas_price.to_string()).unwrap().to_hex_string(),
-                },
-                // L2 resources are hard-coded to 0
-                l2_gas: ResourceBounds { max_amount: "0x0".to_string(), max_price_per_unit: "0x0".to_string() },
-            },
+            // L2 resources are left at 0
+            resource_bounds: ResourceBoundsBuilder::new().l1_gas(self.inner.gas, self.inner.gas_price).build(),
             // Fee market has not been been activated yet so it's hard-coded to be 0
             tip: Felt::ZERO,
             // Hard-coded empty `paymaster_data`
@@ -846,14 +833,3 @@ where
         })
     }
 }
-
-fn calculate_contract_address(salt: Felt, class_hash: Felt, constructor_calldata: &[Felt]) -> Felt {
-    compute_hash_on_elements(&[
-        PREFIX_CONTRACT_ADDRESS,
-        Felt::ZERO,
-        salt,
-        class_hash,
-        compute_hash_on_elements(constructor_calldata),
-    ])
-    .mod_floor(&ADDR_BOUND)
-}
@@ -0,0 +1,97 @@
+use std::fmt::Debug;
+
+/// Turns fee estimates into the fee bounds a transaction is sent with. Executions use a
+/// [MarginFeeStrategy] built from their estimate multipliers unless one is set with
+/// `fee_strategy`.
+pub trait FeeStrategy: Debug + Send + Sync {
+    /// `max_fee` of a V1 transaction whose overall fee is estimated at `overall_fee`.
+    fn max_fee(&self, overall_fee: u64) -> u64;
+
+    /// Gas bound of a V3 transaction whose gas is estimated at `gas`.
+    fn gas(&self, gas: u64) -> u64;
+
+    /// Gas price bound of a V3 transaction, from the estimated gas price or, when the gas is set
+    /// manually, the L1 gas price of the block.
+    fn gas_price(&self, gas_price: u64) -> u128;
+}
+
+/// Margin added to the estimate of one resource: the estimate is multiplied, then increased by an
+/// absolute buffer, and raised to the minimum if still below it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResourceMargin {
+    pub multiplier: f64,
+    pub buffer: u128,
+    pub minimum: u128,
+}
+
+impl ResourceMargin {
+    pub fn multiplier(multiplier: f64) -> Self {
+        Self { multiplier, buffer: 0, minimum: 0 }
+    }
+
+    pub fn buffer(self, buffer: u128) -> Self {
+        Self { buffer, ..self }
+    }
+
+    pub fn minimum(self, minimum: u128) -> Self {
+        Self { minimum, ..self }
+    }
+
+    pub fn apply(&self, estimate: u64) -> u128 {
+        (((estimate as f64) * self.multiplier) as u128).saturating_add(self.buffer).max(self.minimum)
+    }
+}
+
+/// Applies a separate [ResourceMargin] to the fee of V1 transactions, and to the gas and the gas
+/// price of V3 transactions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarginFeeStrategy {
+    pub max_fee: ResourceMargin,
+    pub gas: ResourceMargin,
+    pub gas_price: ResourceMargin,
+}
+
+impl MarginFeeStrategy {
+    /// Multiplies the estimates, the way executions do when no strategy is set.
+    pub fn multipliers(fee_estimate_multiplier: f64, gas_multiplier: f64, gas_price_multiplier: f64) -> Self {
+        Self {
+            max_fee: ResourceMargin::multiplier(fee_estimate_multiplier),
+            gas: ResourceMargin::multiplier(gas_multiplier),
+            gas_price: ResourceMargin::multiplier(gas_price_multiplier),
+        }
+    }
+}
+
+impl FeeStrategy for MarginFeeStrategy {
+    fn max_fee(&self, overall_fee: u64) -> u64 {
+        self.max_fee.apply(overall_fee).try_into().unwrap_or(u64::MAX)
+    }
+
+    fn gas(&self, gas: u64) -> u64 {
+        self.gas.apply(gas).try_into().unwrap_or(u64::MAX)
+    }
+
+    fn gas_price(&self, gas_price: u64) -> u128 {
+        self.gas_price.apply(gas_price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn margins_apply_multiplier_then_buffer_then_minimum() {
+        let strategy = MarginFeeStrategy {
+            max_fee: ResourceMargin::multiplier(1.1),
+            gas: ResourceMargin::multiplier(1.0).buffer(5_000),
+            gas_price: ResourceMargin::multiplier(1.5).minimum(1_000),
+        };
+
+        assert_eq!(strategy.max_fee(1_000), 1_100);
+        assert_eq!(strategy.gas(20_000), 25_000);
+        assert_eq!(strategy.gas_price(100), 1_000);
+        assert_eq!(strategy.gas_price(10_000), 15_000);
+        assert_eq!(strategy.gas(u64::MAX), u64::MAX);
+    }
+}
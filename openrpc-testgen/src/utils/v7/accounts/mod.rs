@@ -4,5 +4,8 @@ pub mod creation;
 pub mod deployment;
 pub mod errors;
 pub mod factory;
+pub mod fee_strategy;
 pub mod single_owner;
+pub mod top_up;
 pub mod utils;
+pub mod wallet;
@@ -12,6 +12,10 @@ use super::{
     errors::ComputeClassHashError,
 };
 
+/// Selector for entrypoint `__validate__`.
+const SELECTOR_VALIDATE: Felt =
+    Felt::from_hex_unchecked("0x162da33a4585851fe8d3af3c2a9c60b557814e221e0d4f30ff0b2189d9c7775");
+
 #[derive(Debug, Clone)]
 pub struct SingleOwnerAccount<P, S>
 where
@@ -24,6 +28,7 @@ where
     chain_id: Felt,
     block_id: BlockId<Felt>,
     encoding: ExecutionEncoding,
+    entrypoint: Option<CustomEntrypoint>,
 }
 #[derive(Debug, thiserror::Error)]
 pub enum SignError<S> {
@@ -41,6 +46,87 @@ pub enum ExecutionEncoding {
     Legacy,
     /// Encode `__execute__` calldata in Cairo (1) style, where each call is self-contained.
     New,
+    /// Same as [`ExecutionEncoding::Legacy`], without the leading number of calls, for Cairo 0
+    /// accounts that read the call array length from the `call_array` argument itself.
+    LegacyWithoutCallCount,
+}
+
+impl ExecutionEncoding {
+    /// Encodes `calls` as the calldata of an `__execute__`-like entrypoint.
+    pub fn encode(&self, calls: &[Call]) -> Vec<Felt> {
+        let mut execute_calldata: Vec<Felt> = match self {
+            ExecutionEncoding::LegacyWithoutCallCount => vec![],
+            ExecutionEncoding::Legacy | ExecutionEncoding::New => vec![calls.len().into()],
+        };
+
+        match self {
+            ExecutionEncoding::Legacy | ExecutionEncoding::LegacyWithoutCallCount => {
+                let mut concated_calldata: Vec<Felt> = vec![];
+                for call in calls.iter() {
+                    execute_calldata.push(call.to); // to
+                    execute_calldata.push(call.selector); // selector
+                    execute_calldata.push(concated_calldata.len().into()); // data_offset
+                    execute_calldata.push(call.calldata.len().into()); // data_len
+
+                    for item in call.calldata.iter() {
+                        concated_calldata.push(*item);
+                    }
+                }
+
+                execute_calldata.push(concated_calldata.len().into()); // calldata_len
+                execute_calldata.extend_from_slice(&concated_calldata);
+            }
+            ExecutionEncoding::New => {
+                for call in calls.iter() {
+                    execute_calldata.push(call.to); // to
+                    execute_calldata.push(call.selector); // selector
+
+                    execute_calldata.push(call.calldata.len().into()); // calldata.len()
+                    execute_calldata.extend_from_slice(&call.calldata);
+                }
+            }
+        }
+
+        execute_calldata
+    }
+}
+
+/// A custom entrypoint the calls of an execution are routed through, such as
+/// `execute_from_outside_v2` or a meta-transaction wrapper.
+///
+/// `__execute__` then receives a single call of `selector` on `to`, whose calldata is `prefix`,
+/// the calls encoded with `encoding` and `suffix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomEntrypoint {
+    pub to: Felt,
+    pub selector: Felt,
+    pub prefix: Vec<Felt>,
+    pub suffix: Vec<Felt>,
+    pub encoding: ExecutionEncoding,
+}
+
+impl CustomEntrypoint {
+    pub fn new(to: Felt, selector: Felt, encoding: ExecutionEncoding) -> Self {
+        Self { to, selector, prefix: vec![], suffix: vec![], encoding }
+    }
+
+    /// Calldata placed before the encoded calls, e.g. the header of an outside execution.
+    pub fn with_prefix(self, prefix: Vec<Felt>) -> Self {
+        Self { prefix, ..self }
+    }
+
+    /// Calldata placed after the encoded calls, e.g. a length-prefixed signature.
+    pub fn with_suffix(self, suffix: Vec<Felt>) -> Self {
+        Self { suffix, ..self }
+    }
+
+    /// Wraps `calls` into the single call of this entrypoint.
+    pub fn wrap(&self, calls: &[Call]) -> Call {
+        let mut calldata = self.prefix.clone();
+        calldata.extend(self.encoding.encode(calls));
+        calldata.extend_from_slice(&self.suffix);
+        Call { to: self.to, selector: self.selector, calldata }
+    }
 }
 
 impl<P, S> SingleOwnerAccount<P, S>
@@ -58,13 +144,39 @@ where
     /// * `chain_id`: Network chain ID.
     /// * `encoding`: How `__execute__` calldata should be encoded.
     pub fn new(provider: P, signer: S, address: Felt, chain_id: Felt, encoding: ExecutionEncoding) -> Self {
-        Self { provider, signer, address, chain_id, block_id: BlockId::Tag(BlockTag::Pending), encoding }
+        Self {
+            provider,
+            signer,
+            address,
+            chain_id,
+            block_id: BlockId::Tag(BlockTag::Pending),
+            encoding,
+            entrypoint: None,
+        }
     }
 
     pub fn set_block_id(&mut self, block_id: BlockId<Felt>) -> &Self {
         self.block_id = block_id;
         self
     }
+
+    pub fn set_encoding(&mut self, encoding: ExecutionEncoding) -> &Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Routes the calls of every following execution through `entrypoint`, or calls them directly
+    /// from `__execute__` again with `None`.
+    pub fn set_custom_entrypoint(&mut self, entrypoint: Option<CustomEntrypoint>) -> &Self {
+        self.entrypoint = entrypoint;
+        self
+    }
+
+    /// Call of the account's `__validate__` with the calldata `__execute__` would receive for
+    /// `calls`, to check validation alone, e.g. with `starknet_call`.
+    pub fn validate_call(&self, calls: &[Call]) -> Call {
+        Call { to: self.address, selector: SELECTOR_VALIDATE, calldata: self.encode_calls(calls) }
+    }
 }
 
 impl<P, S> Account for SingleOwnerAccount<P, S>
@@ -85,9 +197,9 @@ where
     async fn sign_execution_v1(
         &self,
         execution: &RawExecutionV1,
-        _query_only: bool,
+        query_only: bool,
     ) -> Result<Vec<Felt>, Self::SignError> {
-        let tx_hash = execution.transaction_hash(self.chain_id, self.address, false, self);
+        let tx_hash = execution.transaction_hash(self.chain_id, self.address, query_only, self);
         let signature = self.signer.sign_hash(&tx_hash).await.map_err(SignError::Signer)?;
 
         Ok(vec![signature.r, signature.s])
@@ -96,9 +208,9 @@ where
     async fn sign_execution_v3(
         &self,
         execution: &RawExecutionV3,
-        _query_only: bool,
+        query_only: bool,
     ) -> Result<Vec<Felt>, Self::SignError> {
-        let tx_hash = execution.transaction_hash(self.chain_id, self.address, false, self);
+        let tx_hash = execution.transaction_hash(self.chain_id, self.address, query_only, self);
         let signature = self.signer.sign_hash(&tx_hash).await.map_err(SignError::Signer)?;
 
         Ok(vec![signature.r, signature.s])
@@ -137,37 +249,10 @@ where
     S: Signer + Send,
 {
     fn encode_calls(&self, calls: &[Call]) -> Vec<Felt> {
-        let mut execute_calldata: Vec<Felt> = vec![calls.len().into()];
-
-        match self.encoding {
-            ExecutionEncoding::Legacy => {
-                let mut concated_calldata: Vec<Felt> = vec![];
-                for call in calls.iter() {
-                    execute_calldata.push(call.to); // to
-                    execute_calldata.push(call.selector); // selector
-                    execute_calldata.push(concated_calldata.len().into()); // data_offset
-                    execute_calldata.push(call.calldata.len().into()); // data_len
-
-                    for item in call.calldata.iter() {
-                        concated_calldata.push(*item);
-                    }
-                }
-
-                execute_calldata.push(concated_calldata.len().into()); // calldata_len
-                execute_calldata.extend_from_slice(&concated_calldata);
-            }
-            ExecutionEncoding::New => {
-                for call in calls.iter() {
-                    execute_calldata.push(call.to); // to
-                    execute_calldata.push(call.selector); // selector
-
-                    execute_calldata.push(call.calldata.len().into()); // calldata.len()
-                    execute_calldata.extend_from_slice(&call.calldata);
-                }
-            }
+        match &self.entrypoint {
+            Some(entrypoint) => self.encoding.encode(&[entrypoint.wrap(calls)]),
+            None => self.encoding.encode(calls),
         }
-
-        execute_calldata
     }
 }
 
@@ -186,3 +271,40 @@ where
         self.block_id.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calls() -> Vec<Call> {
+        vec![
+            Call { to: Felt::from(1), selector: Felt::from(2), calldata: vec![Felt::from(3), Felt::from(4)] },
+            Call { to: Felt::from(5), selector: Felt::from(6), calldata: vec![Felt::from(7)] },
+        ]
+    }
+
+    fn felts(values: &[u64]) -> Vec<Felt> {
+        values.iter().map(|value| Felt::from(*value)).collect()
+    }
+
+    #[test]
+    fn encodes_legacy_calls_with_and_without_call_count() {
+        let encoded = ExecutionEncoding::Legacy.encode(&calls());
+        assert_eq!(encoded, felts(&[2, 1, 2, 0, 2, 5, 6, 2, 1, 3, 3, 4, 7]));
+
+        let encoded = ExecutionEncoding::LegacyWithoutCallCount.encode(&calls());
+        assert_eq!(encoded, felts(&[1, 2, 0, 2, 5, 6, 2, 1, 3, 3, 4, 7]));
+    }
+
+    #[test]
+    fn wraps_calls_into_custom_entrypoint() {
+        let entrypoint = CustomEntrypoint::new(Felt::from(8), Felt::from(9), ExecutionEncoding::New)
+            .with_prefix(felts(&[10]))
+            .with_suffix(felts(&[1, 11]));
+
+        let call = entrypoint.wrap(&calls());
+        assert_eq!(call.to, Felt::from(8));
+        assert_eq!(call.selector, Felt::from(9));
+        assert_eq!(call.calldata, felts(&[10, 2, 1, 2, 2, 3, 4, 5, 6, 1, 7, 1, 11]));
+    }
+}
@@ -0,0 +1,239 @@
+//! Account decorator keeping the fee token balance of an account above a threshold, so that long
+//! load and soak runs don't stop halfway with `InsufficientAccountBalance`. Senders call
+//! [TopUpAccount::ensure_funded] before sending a transaction, which tops the balance up from a
+//! [Faucet] when it runs low and waits for the funds to arrive. Signing never contacts the faucet.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::{BlockId, BlockTag, PriceUnit};
+use tokio::sync::Mutex;
+use tracing::info;
+
+use crate::utils::{
+    get_balance::get_balance,
+    v7::{
+        providers::jsonrpc::{HttpTransport, JsonRpcClient},
+        signers::local_wallet::LocalWallet,
+    },
+};
+
+use super::{
+    account::{
+        Account, ConnectedAccount, ExecutionEncoder, RawDeclarationV2, RawDeclarationV3, RawExecutionV1, RawExecutionV3,
+    },
+    call::Call,
+    single_owner::SingleOwnerAccount,
+    utils::faucet::{Faucet, FaucetError},
+};
+
+type InnerAccount = SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>;
+type InnerSignError = <InnerAccount as Account>::SignError;
+
+/// How long a top-up may take to show in the balance of the account.
+pub const DEFAULT_FUNDING_TIMEOUT: Duration = Duration::from_secs(120);
+/// Pause between two balance reads while waiting for a top-up.
+const FUNDING_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// When and by how much the balance of one fee token is topped up.
+#[derive(Debug, Clone)]
+pub struct TopUpPolicy {
+    pub token_address: Felt,
+    /// Unit of the fees paid in this token, [PriceUnit::Wei] for ETH and [PriceUnit::Fri] for STRK.
+    pub unit: PriceUnit,
+    /// The account is topped up when its balance drops below this amount.
+    pub threshold: u128,
+    /// Amount requested from the faucet on each top-up.
+    pub amount: u128,
+}
+
+impl TopUpPolicy {
+    pub fn eth(token_address: Felt, threshold: u128, amount: u128) -> Self {
+        Self { token_address, unit: PriceUnit::Wei, threshold, amount }
+    }
+
+    pub fn strk(token_address: Felt, threshold: u128, amount: u128) -> Self {
+        Self { token_address, unit: PriceUnit::Fri, threshold, amount }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TopUpError {
+    #[error("Could not read the balance of {address:#x}: {reason}")]
+    Balance { address: Felt, reason: String },
+    #[error("Could not top up {address:#x}: {source}")]
+    Faucet { address: Felt, source: FaucetError },
+    #[error("Balance of {address:#x} is still {balance} after {waited:?}, expected at least {expected}")]
+    NotFunded { address: Felt, balance: u128, expected: u128, waited: Duration },
+}
+
+/// Wraps an account, topping up its fee token balance whenever it is below the threshold of the
+/// [TopUpPolicy] of the unit fees are paid in: v1 invokes and v2 declares pay in ETH, v3
+/// transactions in STRK. Tokens without a policy are never topped up. Signing is delegated to the
+/// inner account as is.
+#[derive(Debug, Clone)]
+pub struct TopUpAccount<F> {
+    inner: InnerAccount,
+    faucet: F,
+    policies: Vec<TopUpPolicy>,
+    funding_timeout: Duration,
+    /// Serializes top-ups, so that concurrent senders don't all fund the account at once.
+    top_up_lock: Arc<Mutex<()>>,
+}
+
+impl<F> TopUpAccount<F>
+where
+    F: Faucet + Sync + Send,
+{
+    pub fn new(inner: InnerAccount, faucet: F) -> Self {
+        Self {
+            inner,
+            faucet,
+            policies: Vec::new(),
+            funding_timeout: DEFAULT_FUNDING_TIMEOUT,
+            top_up_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub fn with_funding_timeout(mut self, funding_timeout: Duration) -> Self {
+        self.funding_timeout = funding_timeout;
+        self
+    }
+
+    pub fn with_policy(mut self, policy: TopUpPolicy) -> Self {
+        self.policies.retain(|existing| existing.unit != policy.unit);
+        self.policies.push(policy);
+        self
+    }
+
+    pub fn inner(&self) -> &InnerAccount {
+        &self.inner
+    }
+
+    /// Tops up the balance of the token fees are paid in with `unit`, if it is below the threshold,
+    /// and waits until the balance reaches the threshold, or has grown by the amount requested if
+    /// that is not enough. Returns the amount requested from the faucet, 0 when the balance was
+    /// high enough.
+    pub async fn ensure_funded(&self, unit: PriceUnit) -> Result<u128, TopUpError> {
+        let Some(policy) = self.policies.iter().find(|policy| policy.unit == unit) else {
+            return Ok(0);
+        };
+        let address = self.inner.address();
+
+        let _guard = self.top_up_lock.lock().await;
+        let balance = self.balance(policy).await?;
+        if balance >= policy.threshold {
+            return Ok(0);
+        }
+
+        info!("💧 Topping up {:#x} with {} {:?}, balance is {}", address, policy.amount, unit, balance);
+        self.faucet
+            .fund(address, policy.amount, unit)
+            .await
+            .map_err(|source| TopUpError::Faucet { address, source })?;
+
+        // Some faucets answer before the funds are transferred, e.g. when they only submit the
+        // transfer transaction.
+        let expected = policy.threshold.min(balance.saturating_add(policy.amount));
+        let started = Instant::now();
+        loop {
+            let balance = self.balance(policy).await?;
+            if balance >= expected {
+                return Ok(policy.amount);
+            }
+            if started.elapsed() >= self.funding_timeout {
+                return Err(TopUpError::NotFunded { address, balance, expected, waited: started.elapsed() });
+            }
+            tokio::time::sleep(FUNDING_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn balance(&self, policy: &TopUpPolicy) -> Result<u128, TopUpError> {
+        let address = self.inner.address();
+        let balance =
+            get_balance(self.inner.provider(), address, policy.token_address, BlockId::Tag(BlockTag::Pending))
+                .await
+                .map_err(|e| TopUpError::Balance { address, reason: e.to_string() })?;
+
+        // `balance_of` returns an u256 as its low and high 128 bits.
+        match balance.as_slice() {
+            [_, high] if *high != Felt::ZERO => Ok(u128::MAX),
+            [low, _] => u128::try_from(*low)
+                .map_err(|_| TopUpError::Balance { address, reason: format!("invalid low part {low:#x}") }),
+            _ => Err(TopUpError::Balance { address, reason: format!("unexpected balance_of result {balance:?}") }),
+        }
+    }
+}
+
+impl<F> Account for TopUpAccount<F>
+where
+    F: Faucet + Sync + Send,
+{
+    type SignError = InnerSignError;
+
+    fn address(&self) -> Felt {
+        self.inner.address()
+    }
+
+    fn chain_id(&self) -> Felt {
+        self.inner.chain_id()
+    }
+
+    async fn sign_execution_v1(
+        &self,
+        execution: &RawExecutionV1,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        self.inner.sign_execution_v1(execution, query_only).await
+    }
+
+    async fn sign_execution_v3(
+        &self,
+        execution: &RawExecutionV3,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        self.inner.sign_execution_v3(execution, query_only).await
+    }
+
+    async fn sign_declaration_v2(
+        &self,
+        declaration: &RawDeclarationV2,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        self.inner.sign_declaration_v2(declaration, query_only).await
+    }
+
+    async fn sign_declaration_v3(
+        &self,
+        declaration: &RawDeclarationV3,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        self.inner.sign_declaration_v3(declaration, query_only).await
+    }
+
+    fn is_signer_interactive(&self) -> bool {
+        self.inner.is_signer_interactive()
+    }
+}
+
+impl<F> ExecutionEncoder for TopUpAccount<F> {
+    fn encode_calls(&self, calls: &[Call]) -> Vec<Felt> {
+        self.inner.encode_calls(calls)
+    }
+}
+
+impl<F> ConnectedAccount for TopUpAccount<F>
+where
+    F: Faucet + Sync + Send,
+{
+    type Provider = JsonRpcClient<HttpTransport>;
+
+    fn provider(&self) -> &Self::Provider {
+        self.inner.provider()
+    }
+
+    fn block_id(&self) -> BlockId<Felt> {
+        self.inner.block_id()
+    }
+}
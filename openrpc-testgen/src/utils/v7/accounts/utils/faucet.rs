@@ -0,0 +1,206 @@
+use std::future::Future;
+
+use reqwest::StatusCode;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::PriceUnit;
+use thiserror::Error;
+use url::Url;
+
+use crate::utils::v7::{
+    accounts::{account::Account, call::Call, creation::structs::MintRequest2, single_owner::SingleOwnerAccount},
+    endpoints::utils::{get_selector_from_name, wait_for_sent_transaction},
+    providers::jsonrpc::{transports::shared_client, HttpTransport, JsonRpcClient},
+    signers::local_wallet::LocalWallet,
+};
+
+use super::mint::{mint, MintError};
+
+#[derive(Error, Debug)]
+pub enum FaucetError {
+    #[error(transparent)]
+    Mint(#[from] MintError),
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error("Faucet responded with status {status_code}: {message:?}")]
+    ResponseStatusError { status_code: StatusCode, message: Option<String> },
+
+    #[error("Funding transfer failed: {0}")]
+    Transfer(String),
+}
+
+/// Source of funds used to prefund freshly created accounts before they are deployed.
+pub trait Faucet {
+    /// Requests `amount` of the token identified by `unit` to be sent to `address`.
+    fn fund(
+        &self,
+        address: Felt,
+        amount: u128,
+        unit: PriceUnit,
+    ) -> impl Future<Output = Result<(), FaucetError>> + Send;
+}
+
+/// Faucet backed by the devnet `/mint` endpoint.
+#[derive(Clone, Debug)]
+pub struct DevnetFaucet {
+    pub base_url: Url,
+}
+
+impl DevnetFaucet {
+    pub fn new(base_url: Url) -> Self {
+        Self { base_url }
+    }
+}
+
+impl Faucet for DevnetFaucet {
+    async fn fund(&self, address: Felt, amount: u128, unit: PriceUnit) -> Result<(), FaucetError> {
+        mint(self.base_url.clone(), &MintRequest2 { amount, address, unit }).await?;
+        Ok(())
+    }
+}
+
+/// Faucet HTTP API taking the body of the devnet `/mint` endpoint, e.g. a hosted faucet of a
+/// devnet-based testnet.
+///
+/// The request is sent as `POST <url>` with a JSON body `{ "address", "amount", "unit" }`, where
+/// `unit` is `WEI` for ETH or `FRI` for STRK. When an API key is configured it is passed in
+/// `api_key_header`.
+#[derive(Clone, Debug)]
+pub struct HttpFaucet {
+    pub url: Url,
+    pub api_key: Option<String>,
+    pub api_key_header: String,
+}
+
+impl HttpFaucet {
+    pub fn new(url: Url) -> Self {
+        Self { url, api_key: None, api_key_header: "x-api-key".to_string() }
+    }
+
+    pub fn with_api_key(self, api_key: String) -> Self {
+        Self { api_key: Some(api_key), ..self }
+    }
+
+    pub fn with_api_key_header(self, api_key_header: String) -> Self {
+        Self { api_key_header, ..self }
+    }
+}
+
+impl Faucet for HttpFaucet {
+    async fn fund(&self, address: Felt, amount: u128, unit: PriceUnit) -> Result<(), FaucetError> {
+        let body = MintRequest2 { amount, address, unit };
+
+        let mut request = shared_client().post(self.url.clone()).header("Content-type", "application/json").json(&body);
+        if let Some(api_key) = &self.api_key {
+            request = request.header(self.api_key_header.as_str(), api_key);
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            let status_code = response.status();
+            let message = response.text().await.ok();
+            return Err(FaucetError::ResponseStatusError { status_code, message });
+        }
+        Ok(())
+    }
+}
+
+/// Faucets the runners can be pointed at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FaucetKind {
+    /// The `/mint` endpoint of a devnet, see [DevnetFaucet].
+    Devnet,
+    /// A testnet faucet HTTP API, see [HttpFaucet].
+    Http,
+}
+
+/// One of the faucets of [FaucetKind], for funding flows configured at run time.
+#[derive(Clone, Debug)]
+pub enum AnyFaucet {
+    Devnet(DevnetFaucet),
+    Http(HttpFaucet),
+}
+
+impl AnyFaucet {
+    pub fn new(kind: FaucetKind, url: Url, api_key: Option<String>, api_key_header: String) -> Self {
+        match kind {
+            FaucetKind::Devnet => AnyFaucet::Devnet(DevnetFaucet::new(url)),
+            FaucetKind::Http => {
+                let faucet = HttpFaucet::new(url).with_api_key_header(api_key_header);
+                AnyFaucet::Http(match api_key {
+                    Some(api_key) => faucet.with_api_key(api_key),
+                    None => faucet,
+                })
+            }
+        }
+    }
+}
+
+impl Faucet for AnyFaucet {
+    async fn fund(&self, address: Felt, amount: u128, unit: PriceUnit) -> Result<(), FaucetError> {
+        match self {
+            AnyFaucet::Devnet(faucet) => faucet.fund(address, amount, unit).await,
+            AnyFaucet::Http(faucet) => faucet.fund(address, amount, unit).await,
+        }
+    }
+}
+
+/// Faucet backed by an already funded account, which sends `transfer`s of the fee tokens.
+#[derive(Clone, Debug)]
+pub struct AccountFaucet {
+    pub account: SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
+    pub eth_address: Felt,
+    pub strk_address: Felt,
+}
+
+impl AccountFaucet {
+    pub fn new(
+        account: SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
+        eth_address: Felt,
+        strk_address: Felt,
+    ) -> Self {
+        Self { account, eth_address, strk_address }
+    }
+}
+
+impl Faucet for AccountFaucet {
+    async fn fund(&self, address: Felt, amount: u128, unit: PriceUnit) -> Result<(), FaucetError> {
+        let token = match unit {
+            PriceUnit::Wei => self.eth_address,
+            PriceUnit::Fri => self.strk_address,
+        };
+        let selector = get_selector_from_name("transfer").map_err(|e| FaucetError::Transfer(e.to_string()))?;
+        let transfer = Call { to: token, selector, calldata: vec![address, Felt::from(amount), Felt::ZERO] };
+
+        let result =
+            self.account.execute_v3(vec![transfer]).send().await.map_err(|e| FaucetError::Transfer(e.to_string()))?;
+        wait_for_sent_transaction(result.transaction_hash, &self.account)
+            .await
+            .map_err(|e| FaucetError::Transfer(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::mock_server::MockServer;
+
+    #[tokio::test]
+    async fn http_faucet_posts_the_devnet_mint_body() {
+        let faucet_api = MockServer::start(|_| json!({})).await;
+        let faucet = HttpFaucet::new(faucet_api.url.join("faucet").unwrap()).with_api_key("key".to_string());
+
+        faucet.fund(Felt::from(0x123), 1000, PriceUnit::Fri).await.unwrap();
+        faucet.fund(Felt::from(0x123), 2000, PriceUnit::Wei).await.unwrap();
+
+        let requests = faucet_api.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].path, "/faucet");
+        assert_eq!(requests[0].body, json!({ "address": "0x123", "amount": 1000, "unit": "FRI" }));
+        assert_eq!(requests[1].body, json!({ "address": "0x123", "amount": 2000, "unit": "WEI" }));
+    }
+}
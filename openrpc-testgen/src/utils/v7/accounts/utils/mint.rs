@@ -1,9 +1,12 @@
-use reqwest::{Client, StatusCode};
+use reqwest::StatusCode;
 
 use thiserror::Error;
 use url::Url;
 
-use crate::utils::v7::accounts::creation::structs::{MintRequest2, MintResponse};
+use crate::utils::v7::{
+    accounts::creation::structs::{MintRequest2, MintResponse},
+    providers::jsonrpc::transports::shared_client,
+};
 
 #[derive(Error, Debug)]
 pub enum MintError {
@@ -29,7 +32,7 @@ pub async fn mint(base_url: Url, mint_request: &MintRequest2) -> Result<MintResp
     };
 
     let response =
-        Client::new().post(mint_url).header("Content-type", "application/json").json(mint_request).send().await?;
+        shared_client().post(mint_url).header("Content-type", "application/json").json(mint_request).send().await?;
 
     if !response.status().is_success() {
         let status_code = response.status();
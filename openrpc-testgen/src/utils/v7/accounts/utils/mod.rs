@@ -1 +1,3 @@
+pub mod faucet;
 pub mod mint;
+pub mod predeployed;
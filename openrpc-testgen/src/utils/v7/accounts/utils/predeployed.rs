@@ -0,0 +1,200 @@
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use starknet_types_core::felt::Felt;
+use thiserror::Error;
+use url::Url;
+
+use crate::utils::v7::{
+    accounts::single_owner::{ExecutionEncoding, SingleOwnerAccount},
+    providers::{
+        jsonrpc::{transports::shared_client, HttpTransport, JsonRpcClient},
+        provider::{Provider, ProviderError},
+    },
+    signers::{key_pair::SigningKey, local_wallet::LocalWallet},
+};
+
+#[derive(Error, Debug)]
+pub enum PredeployedAccountsError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error("Response Status Error")]
+    ResponseStatusError { status_code: StatusCode, message: Option<String> },
+
+    #[error("Error parsing response")]
+    ResponseParseError,
+
+    #[error(transparent)]
+    JoinUrlError(#[from] url::ParseError),
+
+    #[error(transparent)]
+    ProviderError(#[from] ProviderError),
+
+    #[error("Node did not return any predeployed accounts with a private key")]
+    NoAccounts,
+}
+
+/// Predeployed account as reported by a local network.
+///
+/// Devnet returns `snake_case` fields from `GET /predeployed_accounts`, while Katana returns
+/// `camelCase` fields from `dev_predeployedAccounts`, so both spellings are accepted.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PredeployedAccount {
+    pub address: Felt,
+    #[serde(alias = "publicKey")]
+    pub public_key: Felt,
+    #[serde(alias = "privateKey", default)]
+    pub private_key: Option<Felt>,
+    #[serde(alias = "initial_balance", default)]
+    pub balance: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct KatanaPredeployedAccountsResponse {
+    result: Vec<PredeployedAccount>,
+}
+
+/// Fetches the predeployed accounts of a local network.
+///
+/// Devnet's `GET /predeployed_accounts` endpoint is tried first; if it is not available the Katana
+/// `dev_predeployedAccounts` JSON-RPC method is used instead.
+pub async fn fetch_predeployed_accounts(base_url: Url) -> Result<Vec<PredeployedAccount>, PredeployedAccountsError> {
+    let client = shared_client();
+
+    let devnet_url = base_url.join("predeployed_accounts")?;
+    let response = client.get(devnet_url).send().await?;
+    if response.status().is_success() {
+        if let Ok(accounts) = response.json::<Vec<PredeployedAccount>>().await {
+            return Ok(accounts);
+        }
+    }
+
+    let request_body = json!({ "jsonrpc": "2.0", "id": 1, "method": "dev_predeployedAccounts", "params": [] });
+    let response = client.post(base_url).header("Content-type", "application/json").json(&request_body).send().await?;
+
+    if !response.status().is_success() {
+        let status_code = response.status();
+        let message = response.text().await.ok();
+        return Err(PredeployedAccountsError::ResponseStatusError { status_code, message });
+    }
+
+    let response = response
+        .json::<KatanaPredeployedAccountsResponse>()
+        .await
+        .map_err(|_| PredeployedAccountsError::ResponseParseError)?;
+    Ok(response.result)
+}
+
+/// Fetches the predeployed accounts of a local network and wraps them into ready-to-use
+/// [`SingleOwnerAccount`]s connected to the node at `url`. Accounts returned without a private key
+/// can't sign and are left out.
+pub async fn predeployed_single_owner_accounts(
+    url: Url,
+) -> Result<Vec<SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>>, PredeployedAccountsError> {
+    let accounts: Vec<(Felt, Felt)> = fetch_predeployed_accounts(url.clone())
+        .await?
+        .into_iter()
+        .filter_map(|account| account.private_key.map(|private_key| (account.address, private_key)))
+        .collect();
+    if accounts.is_empty() {
+        return Err(PredeployedAccountsError::NoAccounts);
+    }
+
+    let provider = JsonRpcClient::new(HttpTransport::new(url));
+    let chain_id = provider.chain_id().await?;
+
+    Ok(accounts
+        .into_iter()
+        .map(|(address, private_key)| {
+            SingleOwnerAccount::new(
+                provider.clone(),
+                LocalWallet::from(SigningKey::from_secret_scalar(private_key)),
+                address,
+                chain_id,
+                ExecutionEncoding::New,
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{mock_server::MockServer, v7::accounts::account::Account};
+
+    #[test]
+    fn parses_devnet_predeployed_accounts() {
+        let response = json!([{
+            "initial_balance": "1000000000000000000000",
+            "address": "0x64b48806902a367c8598f4f95c305e8c1a1acba5f082d294a43793113115691",
+            "public_key": "0x39d9e6ce352ad4530a0ef5d5a18fd3303c3606a7fa6ac5b620020ad681cc33b",
+            "private_key": "0x71d7bb07b9a64f6f78ac4c816aff4da9"
+        }]);
+        let accounts: Vec<PredeployedAccount> = serde_json::from_value(response).unwrap();
+
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(
+            accounts[0].address,
+            Felt::from_hex_unchecked("0x64b48806902a367c8598f4f95c305e8c1a1acba5f082d294a43793113115691")
+        );
+        assert_eq!(accounts[0].private_key, Some(Felt::from_hex_unchecked("0x71d7bb07b9a64f6f78ac4c816aff4da9")));
+        assert_eq!(accounts[0].balance, Some(json!("1000000000000000000000")));
+    }
+
+    #[test]
+    fn parses_katana_predeployed_accounts() {
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": [
+                {
+                    "address": "0x127fd5f1fe78a71f8bcd1fec63e3fe2f0486b6ecd5c86a0466c3a21fa5cfcec",
+                    "publicKey": "0x33246ce85ebdc292e6a5c5b4dd51fab2757be34b8ffda847ca6925edf31cb67",
+                    "privateKey": "0xc5b2fcab997346f3ea1c00b002ecf6f382c5f9c9659a3894eb783c5320f912"
+                },
+                {
+                    "address": "0x13d9ee239f33fea4f8785b9e3870ade909e20a9599ae7cd62c1c292b73af1b7",
+                    "publicKey": "0x4c339f18b9d1b95b64a6d378abd1480b2e0d5d5bd33cd0828cbce4d65c27284"
+                }
+            ]
+        });
+        let response: KatanaPredeployedAccountsResponse = serde_json::from_value(response).unwrap();
+
+        assert_eq!(response.result.len(), 2);
+        assert_eq!(
+            response.result[0].public_key,
+            Felt::from_hex_unchecked("0x33246ce85ebdc292e6a5c5b4dd51fab2757be34b8ffda847ca6925edf31cb67")
+        );
+        assert_eq!(
+            response.result[0].private_key,
+            Some(Felt::from_hex_unchecked("0xc5b2fcab997346f3ea1c00b002ecf6f382c5f9c9659a3894eb783c5320f912"))
+        );
+        assert_eq!(response.result[1].private_key, None);
+        assert_eq!(response.result[1].balance, None);
+    }
+
+    #[tokio::test]
+    async fn skips_predeployed_accounts_without_private_key() {
+        let node = MockServer::start(|request| match request.path.as_str() {
+            "/predeployed_accounts" => json!([
+                {
+                    "address": "0x1",
+                    "public_key": "0x11",
+                },
+                {
+                    "address": "0x2",
+                    "public_key": "0x22",
+                    "private_key": "0x222"
+                }
+            ]),
+            _ => json!({ "jsonrpc": "2.0", "id": request.body["id"], "result": "0x534e5f5345504f4c4941" }),
+        })
+        .await;
+
+        let accounts = predeployed_single_owner_accounts(node.url.clone()).await.unwrap();
+
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].address(), Felt::TWO);
+    }
+}
@@ -0,0 +1,243 @@
+//! Account whose signatures come from an external signing service, e.g. one backed by a KMS or an
+//! HSM, so that the private key of the account never has to be handed to the test runner.
+//!
+//! The account computes the hash of every transaction itself and POSTs it to the service as JSON:
+//!
+//! ```json
+//! {
+//!   "address": "0x...",
+//!   "chain_id": "0x534e5f5345504f4c4941",
+//!   "transaction_type": "INVOKE_V3",
+//!   "transaction_hash": "0x...",
+//!   "query_only": false
+//! }
+//! ```
+//!
+//! `transaction_type` is one of `INVOKE_V1`, `INVOKE_V3`, `DECLARE_V2` and `DECLARE_V3`, and
+//! `query_only` is set for the query versions signed for fee estimation and simulation only. The
+//! service answers with a `2xx` status and the signature as the transaction's `signature` field
+//! expects it, usually `[r, s]`:
+//!
+//! ```json
+//! { "signature": ["0x...", "0x..."] }
+//! ```
+//!
+//! Any other status fails the signing with the body of the response as the reason. With an auth
+//! token set, requests carry it as `Authorization: Bearer <token>`.
+
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::{BlockId, BlockTag};
+use url::Url;
+
+use super::{
+    account::{
+        Account, ConnectedAccount, ExecutionEncoder, RawDeclarationV2, RawDeclarationV3, RawExecutionV1, RawExecutionV3,
+    },
+    call::Call,
+    single_owner::ExecutionEncoding,
+};
+use crate::utils::v7::providers::{jsonrpc::transports::shared_client, provider::Provider};
+
+#[derive(Debug, thiserror::Error)]
+pub enum WalletSignError {
+    #[error("Signing service unreachable: {0}")]
+    Request(reqwest::Error),
+    #[error("Signing service responded with {status}: {body}")]
+    Status { status: StatusCode, body: String },
+    #[error("Invalid response of the signing service: {0}")]
+    Response(reqwest::Error),
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SignRequest {
+    address: Felt,
+    chain_id: Felt,
+    transaction_type: &'static str,
+    transaction_hash: Felt,
+    query_only: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SignResponse {
+    signature: Vec<Felt>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WalletAccount<P> {
+    provider: P,
+    client: Client,
+    signer_url: Url,
+    auth_token: Option<String>,
+    address: Felt,
+    chain_id: Felt,
+    block_id: BlockId<Felt>,
+    encoding: ExecutionEncoding,
+}
+
+impl<P> WalletAccount<P>
+where
+    P: Provider + Sync + Send,
+{
+    /// Creates an account at `address` whose transactions are signed by the service at
+    /// `signer_url`.
+    pub fn new(provider: P, signer_url: Url, address: Felt, chain_id: Felt, encoding: ExecutionEncoding) -> Self {
+        Self {
+            provider,
+            client: shared_client(),
+            signer_url,
+            auth_token: None,
+            address,
+            chain_id,
+            block_id: BlockId::Tag(BlockTag::Pending),
+            encoding,
+        }
+    }
+
+    /// Consumes the current [WalletAccount] instance and returns a new one authenticating to the
+    /// signing service with `token`.
+    pub fn with_auth_token(self, token: String) -> Self {
+        Self { auth_token: Some(token), ..self }
+    }
+
+    pub fn set_block_id(&mut self, block_id: BlockId<Felt>) -> &Self {
+        self.block_id = block_id;
+        self
+    }
+
+    async fn sign(
+        &self,
+        transaction_type: &'static str,
+        transaction_hash: Felt,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, WalletSignError> {
+        let request = SignRequest {
+            address: self.address,
+            chain_id: self.chain_id,
+            transaction_type,
+            transaction_hash,
+            query_only,
+        };
+        let mut request = self.client.post(self.signer_url.clone()).json(&request);
+        if let Some(token) = &self.auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await.map_err(WalletSignError::Request)?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(WalletSignError::Status { status, body });
+        }
+        let response: SignResponse = response.json().await.map_err(WalletSignError::Response)?;
+        Ok(response.signature)
+    }
+}
+
+impl<P> Account for WalletAccount<P>
+where
+    P: Provider + Sync + Send,
+{
+    type SignError = WalletSignError;
+
+    fn address(&self) -> Felt {
+        self.address
+    }
+
+    fn chain_id(&self) -> Felt {
+        self.chain_id
+    }
+
+    async fn sign_execution_v1(
+        &self,
+        execution: &RawExecutionV1,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        let tx_hash = execution.transaction_hash(self.chain_id, self.address, query_only, self);
+        self.sign("INVOKE_V1", tx_hash, query_only).await
+    }
+
+    async fn sign_execution_v3(
+        &self,
+        execution: &RawExecutionV3,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        let tx_hash = execution.transaction_hash(self.chain_id, self.address, query_only, self);
+        self.sign("INVOKE_V3", tx_hash, query_only).await
+    }
+
+    async fn sign_declaration_v2(
+        &self,
+        declaration: &RawDeclarationV2,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        let tx_hash = declaration.transaction_hash(self.chain_id, self.address, query_only);
+        self.sign("DECLARE_V2", tx_hash, query_only).await
+    }
+
+    async fn sign_declaration_v3(
+        &self,
+        declaration: &RawDeclarationV3,
+        query_only: bool,
+    ) -> Result<Vec<Felt>, Self::SignError> {
+        let tx_hash = declaration.transaction_hash(self.chain_id, self.address, query_only);
+        self.sign("DECLARE_V3", tx_hash, query_only).await
+    }
+
+    /// Every signature is a round trip to the service, so fee estimates skip it unless validation
+    /// is requested.
+    fn is_signer_interactive(&self) -> bool {
+        true
+    }
+}
+
+impl<P> ExecutionEncoder for WalletAccount<P> {
+    fn encode_calls(&self, calls: &[Call]) -> Vec<Felt> {
+        self.encoding.encode(calls)
+    }
+}
+
+impl<P> ConnectedAccount for WalletAccount<P>
+where
+    P: Provider + Sync + Send,
+{
+    type Provider = P;
+
+    fn provider(&self) -> &Self::Provider {
+        &self.provider
+    }
+
+    fn block_id(&self) -> BlockId<Felt> {
+        self.block_id.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_sign_requests_as_documented() {
+        let request = SignRequest {
+            address: Felt::from(0x1234),
+            chain_id: Felt::from_hex("0x534e5f5345504f4c4941").unwrap(),
+            transaction_type: "INVOKE_V3",
+            transaction_hash: Felt::from(0xabcd),
+            query_only: false,
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "address": "0x1234",
+                "chain_id": "0x534e5f5345504f4c4941",
+                "transaction_type": "INVOKE_V3",
+                "transaction_hash": "0xabcd",
+                "query_only": false,
+            })
+        );
+
+        let response: SignResponse = serde_json::from_str(r#"{ "signature": ["0x1", "0x2"] }"#).unwrap();
+        assert_eq!(response.signature, vec![Felt::ONE, Felt::TWO]);
+    }
+}
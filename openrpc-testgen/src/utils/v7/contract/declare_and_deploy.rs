@@ -12,32 +12,41 @@ use crate::utils::v7::{
         creation::{
             create::{create_account, AccountType},
             helpers::get_chain_id,
-            structs::MintRequest2,
         },
         deployment::{
             deploy::{deploy_account, DeployAccountVersion},
             structs::{ValidatedWaitParams, WaitForTx},
         },
         single_owner::{ExecutionEncoding, SingleOwnerAccount},
-        utils::mint::mint,
+        utils::faucet::Faucet,
     },
     endpoints::{declare_contract::declare_contract, deploy_contract::deploy_contract},
     providers::jsonrpc::{HttpTransport, JsonRpcClient},
     signers::local_wallet::LocalWallet,
 };
 
-pub async fn decalare_and_deploy(url: Url, sierra_path: PathBuf, casm_path: PathBuf) -> Result<(), String> {
+/// STRK requested for the new account, 10 STRK in fri: enough to deploy it, then declare and deploy
+/// a contract, without draining rate-limited testnet faucets.
+const ACCOUNT_FUNDING_FRI: u128 = 10_000_000_000_000_000_000;
+
+/// Creates, funds and deploys a new account of `account_type`, then declares the class of
+/// `sierra_path` from it and deploys an instance of it.
+pub async fn decalare_and_deploy<F: Faucet>(
+    url: Url,
+    sierra_path: PathBuf,
+    casm_path: PathBuf,
+    account_type: AccountType,
+    faucet: &F,
+) -> Result<(), String> {
     let provider = JsonRpcClient::new(HttpTransport::new(url.clone()));
-    let create_acc_data = match create_account(&provider, AccountType::Oz, Option::None, Option::None).await {
+    let create_acc_data = match create_account(&provider, account_type, Option::None, Option::None).await {
         Ok(value) => value,
         Err(e) => {
             return Err(e.to_string());
         }
     };
 
-    match mint(url.clone(), &MintRequest2 { amount: u128::MAX, address: create_acc_data.address, unit: PriceUnit::Fri })
-        .await
-    {
+    match faucet.fund(create_acc_data.address, ACCOUNT_FUNDING_FRI, PriceUnit::Fri).await {
         Ok(_) => {}
         Err(e) => {
             return Err(e.to_string());
@@ -69,7 +78,7 @@ pub async fn decalare_and_deploy(url: Url, sierra_path: PathBuf, casm_path: Path
 
     let class_hash = declare_contract(&account, sierra_path, casm_path).await.unwrap();
 
-    deploy_contract(&account, class_hash).await;
+    deploy_contract(&account, class_hash, vec![], None).await.map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -1,6 +1,8 @@
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::v0_7_1::{AddInvokeTransactionResult, FeeEstimate, SimulateTransactionsResult};
 
+use crate::utils::fee_overrides::fee_overrides;
+use crate::utils::network_registry::network_registry;
 use crate::utils::v7::accounts::{
     account::{
         Account, AccountError, ConnectedAccount, ExecutionV1, ExecutionV3, PreparedExecutionV1, PreparedExecutionV3,
@@ -17,9 +19,6 @@ use super::helpers::{get_udc_deployed_address, UdcUniqueSettings, UdcUniqueness}
 //     starknet_utils::{get_udc_deployed_address, UdcUniqueSettings, UdcUniqueness},
 // };
 
-/// The default UDC address: 0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf.
-const UDC_ADDRESS: Felt =
-    Felt::from_raw([121672436446604875, 9333317513348225193, 15685625669053253235, 15144800532519055890]);
 /// Selector for entrypoint `deployContract`.
 const SELECTOR_DEPLOYCONTRACT: Felt =
     Felt::from_raw([469988280392664069, 1439621915307882061, 1265649739554438882, 18249998464715511309]);
@@ -63,8 +62,9 @@ pub struct DeploymentV3<'f, A> {
 }
 
 impl<A> ContractFactory<A> {
+    /// Deploys through the UDC of the network of the run.
     pub fn new(class_hash: Felt, account: A) -> Self {
-        Self::new_with_udc(class_hash, account, UDC_ADDRESS)
+        Self::new_with_udc(class_hash, account, network_registry().udc_address)
     }
 
     pub fn new_with_udc(class_hash: Felt, account: A, udc_address: Felt) -> Self {
@@ -77,28 +77,30 @@ where
     A: Account,
 {
     pub fn deploy_v1(&self, constructor_calldata: Vec<Felt>, salt: Felt, unique: bool) -> DeploymentV1<A> {
+        let overrides = fee_overrides();
         DeploymentV1 {
             factory: self,
             constructor_calldata,
             salt,
             unique,
             nonce: None,
-            max_fee: None,
-            fee_estimate_multiplier: 1.1,
+            max_fee: overrides.max_fee,
+            fee_estimate_multiplier: overrides.fee_estimate_multiplier,
         }
     }
 
     pub fn deploy_v3(&self, constructor_calldata: Vec<Felt>, salt: Felt, unique: bool) -> DeploymentV3<A> {
+        let overrides = fee_overrides();
         DeploymentV3 {
             factory: self,
             constructor_calldata,
             salt,
             unique,
             nonce: None,
-            gas: None,
-            gas_price: None,
-            gas_estimate_multiplier: 1.5,
-            gas_price_estimate_multiplier: 1.5,
+            gas: overrides.gas,
+            gas_price: overrides.gas_price,
+            gas_estimate_multiplier: overrides.gas_estimate_multiplier,
+            gas_price_estimate_multiplier: overrides.gas_price_estimate_multiplier,
         }
     }
 
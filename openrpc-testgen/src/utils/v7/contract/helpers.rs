@@ -1,8 +1,7 @@
+use starknet_hive_hashing::address::contract_address;
 use starknet_types_core::felt::Felt;
 use starknet_types_core::hash::{Pedersen, StarkHash};
 
-use crate::utils::v7::accounts::deployment::helpers::get_contract_address;
-
 /// Computes the target contract address for deployments through the Universal Deploy Contract.
 pub fn get_udc_deployed_address(
     salt: Felt,
@@ -11,10 +10,10 @@ pub fn get_udc_deployed_address(
     constructor_calldata: &[Felt],
 ) -> Felt {
     match uniqueness {
-        UdcUniqueness::NotUnique => get_contract_address(salt, class_hash, constructor_calldata, Felt::ZERO),
+        UdcUniqueness::NotUnique => contract_address(salt, class_hash, constructor_calldata, Felt::ZERO),
         UdcUniqueness::Unique(settings) => {
             let unique_salt = Pedersen::hash(&settings.deployer_address, &salt);
-            get_contract_address(unique_salt, class_hash, constructor_calldata, settings.udc_contract_address)
+            contract_address(unique_salt, class_hash, constructor_calldata, settings.udc_contract_address)
         }
     }
 }
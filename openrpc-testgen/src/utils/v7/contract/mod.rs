@@ -7,23 +7,18 @@ use serde_json_pythonic::to_string_pythonic;
 use serde_with::serde_as;
 
 use super::{
-    accounts::account::{cairo_short_string_to_felt, normalize_address, starknet_keccak, CairoShortStringToFeltError},
+    accounts::account::{cairo_short_string_to_felt, CairoShortStringToFeltError},
     contract::unsigned_felt::UfeHex,
 };
+use crypto_utils::hash::{poseidon_hash_many, PoseidonHasher};
+use crypto_utils::protocol::PREFIX_COMPILED_CLASS_V1;
+use starknet_hive_hashing::class::sierra_class_hash;
 use starknet_types_core::felt::Felt;
 use starknet_types_core::hash::{Poseidon, StarkHash};
 use starknet_types_rpc::v0_7_1::{ContractClass, DeprecatedContractClass};
 
 use std::boxed;
 
-/// Cairo string for "CONTRACT_CLASS_V0.1.0"
-const PREFIX_CONTRACT_CLASS_V0_1_0: Felt =
-    Felt::from_raw([37302452645455172, 18446734822722598327, 15539482671244488427, 5800711240972404213]);
-
-/// Cairo string for `COMPILED_CLASS_V1`
-const PREFIX_COMPILED_CLASS_V1: Felt =
-    Felt::from_raw([324306817650036332, 18446744073709549462, 1609463842841646376, 2291010424822318237]);
-
 #[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 #[allow(clippy::large_enum_variant)]
@@ -373,7 +368,7 @@ pub use errors::{
     PcOutOfRangeError,
 };
 
-use starknet_types_rpc::v0_7_1::{EntryPointsByType, SierraEntryPoint};
+use starknet_types_rpc::v0_7_1::EntryPointsByType;
 
 pub trait HashAndFlatten {
     fn class_hash(&self) -> Result<Felt, ComputeClassHashError>;
@@ -386,16 +381,7 @@ impl HashAndFlatten for SierraClass {
         let abi_str = to_string_pythonic(&self.abi)
             .map_err(|err| ComputeClassHashError::Json(JsonError { message: format!("{}", err) }))?;
 
-        let data = vec![
-            PREFIX_CONTRACT_CLASS_V0_1_0,
-            hash_sierra_entrypoints(&self.entry_points_by_type.external),
-            hash_sierra_entrypoints(&self.entry_points_by_type.l1_handler),
-            hash_sierra_entrypoints(&self.entry_points_by_type.constructor),
-            starknet_keccak(abi_str.as_bytes()),
-            Poseidon::hash_array(&self.sierra_program),
-        ];
-
-        Ok(normalize_address(Poseidon::hash_array(&data)))
+        Ok(sierra_class_hash(&self.entry_points_by_type, &abi_str, &self.sierra_program))
     }
 
     fn flatten(self) -> Result<ContractClass<Felt>, JsonError> {
@@ -424,7 +410,7 @@ impl CompiledClass {
 
         // Bytecode hash calculation
         let bytecode_hash = if self.bytecode_segment_lengths.is_empty() {
-            Poseidon::hash_array(&self.bytecode)
+            poseidon_hash_many(&self.bytecode)
         } else {
             let mut rev_visited_pcs: Vec<u64> = (0..(self.bytecode.len() as u64)).rev().collect();
 
@@ -455,21 +441,21 @@ impl CompiledClass {
     }
 
     fn hash_entrypoints(entrypoints: &[CompiledClassEntrypoint]) -> Result<Felt, CairoShortStringToFeltError> {
-        let mut data = Vec::new();
+        let mut hasher = PoseidonHasher::new();
+        let mut builtins_hasher = PoseidonHasher::new();
 
         for entry in entrypoints {
-            data.push(entry.selector);
-            data.push(entry.offset.into());
+            hasher.update(entry.selector);
+            hasher.update(entry.offset.into());
 
-            let mut builtin_data = Vec::new();
             for builtin in &entry.builtins {
-                builtin_data.push(cairo_short_string_to_felt(builtin)?);
+                builtins_hasher.update(cairo_short_string_to_felt(builtin)?);
             }
 
-            data.push(Poseidon::hash_array(&builtin_data));
+            hasher.update(builtins_hasher.finalize_reset());
         }
 
-        Ok(Poseidon::hash_array(&data))
+        Ok(hasher.finalize())
     }
 
     // Direct translation of `_create_bytecode_segment_structure_inner` from `cairo-lang` v0.13.1.
@@ -719,14 +705,3 @@ impl<'de> Deserialize<'de> for IntOrList {
         deserializer.deserialize_any(IntOrListVisitor)
     }
 }
-
-fn hash_sierra_entrypoints(entrypoints: &[SierraEntryPoint<Felt>]) -> Felt {
-    let mut data = Vec::new();
-
-    for entry in entrypoints.iter() {
-        data.push(entry.selector);
-        data.push(entry.function_idx.into());
-    }
-
-    Poseidon::hash_array(&data)
-}
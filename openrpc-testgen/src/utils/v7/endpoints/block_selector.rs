@@ -0,0 +1,52 @@
+//! Block the compatibility checks read state at. Nodes serve tags, numbers and hashes through
+//! different code paths, so the whole run can be repeated for each of them.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::{BlockId, BlockTag};
+
+use super::errors::OpenRpcTestGenError;
+use crate::utils::v7::providers::provider::Provider;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BlockSelector {
+    /// The `latest` tag.
+    #[default]
+    Latest,
+    /// The `pending` tag.
+    Pending,
+    /// The number of the latest block at the time of the query.
+    Number,
+    /// The hash of the latest block at the time of the query.
+    Hash,
+}
+
+impl BlockSelector {
+    /// Block id to query, resolving [`BlockSelector::Number`] and [`BlockSelector::Hash`] from the
+    /// node's current latest block, so that state written by earlier checks is visible.
+    pub async fn resolve<P: Provider>(&self, provider: &P) -> Result<BlockId<Felt>, OpenRpcTestGenError> {
+        Ok(match self {
+            BlockSelector::Latest => BlockId::Tag(BlockTag::Latest),
+            BlockSelector::Pending => BlockId::Tag(BlockTag::Pending),
+            BlockSelector::Number => BlockId::Number(provider.block_hash_and_number().await?.block_number),
+            BlockSelector::Hash => BlockId::Hash(provider.block_hash_and_number().await?.block_hash),
+        })
+    }
+
+    /// Whether the node may answer with a pending block.
+    pub fn allows_pending(&self) -> bool {
+        matches!(self, BlockSelector::Pending)
+    }
+}
+
+impl Display for BlockSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            BlockSelector::Latest => write!(f, "latest"),
+            BlockSelector::Pending => write!(f, "pending"),
+            BlockSelector::Number => write!(f, "number"),
+            BlockSelector::Hash => write!(f, "hash"),
+        }
+    }
+}
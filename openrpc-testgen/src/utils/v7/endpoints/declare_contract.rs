@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::utils::display::register_sierra_class;
 use crate::utils::v7::accounts::account::{Account, AccountError};
 use crate::utils::v7::contract::{self, HashAndFlatten};
 use crate::utils::v7::providers::provider::ProviderError;
@@ -113,6 +114,7 @@ pub async fn get_compiled_contract(
     file.read_to_string(&mut casm).await.map_err(|e| RunnerError::ReadFileError(e.to_string()))?;
 
     let contract_artifact: SierraClass = serde_json::from_str(&sierra)?;
+    register_sierra_class(&contract_artifact);
     let compiled_class: CompiledClass = serde_json::from_str(&casm)?;
 
     let casm_class_hash = compiled_class.class_hash()?;
@@ -154,6 +156,7 @@ pub async fn get_compiled_contract_from_string(
     casm: String,
 ) -> Result<(ContractClass<Felt>, TxnHash<Felt>), RunnerError> {
     let contract_artifact: SierraClass = serde_json::from_str(&sierra)?;
+    register_sierra_class(&contract_artifact);
     let compiled_class: CompiledClass = serde_json::from_str(&casm)?;
 
     let casm_class_hash = compiled_class.class_hash()?;
@@ -179,6 +182,7 @@ fn get_flattened_class(artifact_path: &PathBuf) -> Result<ContractClass<Felt>, R
         }
     })?;
     let contract_artifact: SierraClass = serde_json::from_reader(&file)?;
+    register_sierra_class(&contract_artifact);
     Ok(contract_artifact.clone().flatten()?)
 }
 
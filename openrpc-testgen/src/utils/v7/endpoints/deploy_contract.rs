@@ -1,20 +1,28 @@
-use std::fmt::Debug;
-
 use rand::{rngs::StdRng, RngCore, SeedableRng};
 use starknet_types_core::felt::Felt;
 use starknet_types_core::felt::FromStrError;
-use starknet_types_rpc::v0_7_1::AddInvokeTransactionResult;
+use starknet_types_rpc::TxnReceipt;
 
 use thiserror::Error;
 
-use crate::utils::v7::{
-    accounts::single_owner::SingleOwnerAccount,
-    contract::factory::ContractFactory,
-    providers::provider::{Provider, ProviderError},
-    signers::local_wallet::LocalWallet,
+use crate::utils::{
+    network_registry::network_registry,
+    v7::{
+        accounts::{account::ConnectedAccount, single_owner::SingleOwnerAccount},
+        contract::factory::ContractFactory,
+        providers::{
+            jsonrpc::{HttpTransport, JsonRpcClient},
+            provider::{Provider, ProviderError},
+        },
+        signers::local_wallet::LocalWallet,
+    },
 };
 
-use super::declare_contract::RunnerError;
+use super::{
+    declare_contract::RunnerError,
+    errors::OpenRpcTestGenError,
+    utils::{get_selector_from_name, wait_for_sent_transaction},
+};
 
 #[derive(Error, Debug)]
 pub enum DeployError {
@@ -29,16 +37,65 @@ pub enum DeployError {
 
     #[error(transparent)]
     Runner(#[from] RunnerError),
+
+    #[error("Receipt of deployment {0:#x} has no ContractDeployed event of the UDC")]
+    MissingDeployedEvent(Felt),
+
+    #[error("UDC deployed the contract at {emitted:#x}, while its address was computed as {computed:#x}")]
+    AddressMismatch { computed: Felt, emitted: Felt },
+}
+
+/// Contract deployed through the UDC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeployedContract {
+    pub transaction_hash: Felt,
+    pub address: Felt,
 }
 
-pub async fn deploy_contract<P: Provider + Send + Sync + Debug>(
-    account: &SingleOwnerAccount<P, LocalWallet>,
+/// Deploys the class through the UDC with the constructor calldata and the salt, a random one
+/// unless given, and waits for the deployment. The address the UDC reports in its
+/// `ContractDeployed` event must be the one computed locally.
+pub async fn deploy_contract(
+    account: &SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
     class_hash: Felt,
-) -> AddInvokeTransactionResult<Felt> {
+    constructor_calldata: Vec<Felt>,
+    salt: Option<Felt>,
+) -> Result<DeployedContract, OpenRpcTestGenError> {
+    let salt = salt.unwrap_or_else(|| {
+        let mut salt_buffer = [0u8; 32];
+        StdRng::from_entropy().fill_bytes(&mut salt_buffer[1..]);
+        Felt::from_bytes_be(&salt_buffer)
+    });
+
     let factory = ContractFactory::new(class_hash, account);
-    let mut salt_buffer = [0u8; 32];
-    let mut rng = StdRng::from_entropy();
-    rng.fill_bytes(&mut salt_buffer[1..]);
+    let deployment = factory.deploy_v3(constructor_calldata, salt, true);
+    let computed = deployment.deployed_address();
+    let transaction_hash = deployment.send().await?.transaction_hash;
+    wait_for_sent_transaction(transaction_hash, account).await?;
+
+    let emitted = deployed_address_from_event(account.provider(), transaction_hash).await?;
+    if emitted != computed {
+        return Err(DeployError::AddressMismatch { computed, emitted }.into());
+    }
+
+    Ok(DeployedContract { transaction_hash, address: computed })
+}
 
-    factory.deploy_v3(vec![], Felt::from_bytes_be(&salt_buffer), true).send().await.unwrap()
+/// Address in the `ContractDeployed` event the UDC emitted in the deployment transaction.
+pub async fn deployed_address_from_event<P: Provider>(
+    provider: &P,
+    transaction_hash: Felt,
+) -> Result<Felt, OpenRpcTestGenError> {
+    let contract_deployed = get_selector_from_name("ContractDeployed")?;
+    let events = match provider.get_transaction_receipt(transaction_hash).await? {
+        TxnReceipt::Invoke(receipt) => receipt.common_receipt_properties.events,
+        _ => return Err(DeployError::MissingDeployedEvent(transaction_hash).into()),
+    };
+    events
+        .iter()
+        .find(|event| {
+            event.from_address == network_registry().udc_address && event.keys.first() == Some(&contract_deployed)
+        })
+        .and_then(|event| event.data.first().copied())
+        .ok_or_else(|| DeployError::MissingDeployedEvent(transaction_hash).into())
 }
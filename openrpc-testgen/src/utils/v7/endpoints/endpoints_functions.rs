@@ -11,10 +11,10 @@ use starknet_types_core::{
 };
 use starknet_types_rpc::{
     v0_7_1::{
-        AddInvokeTransactionResult, BlockId, BlockTag, BlockWithTxHashes, BlockWithTxs, ContractClass,
-        DeployAccountTxn, DeployAccountTxnV3, FeeEstimate, FunctionCall, InvokeTxn, InvokeTxnV1,
-        MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs, MaybePendingStateUpdate, StateUpdate, Txn,
-        TxnExecutionStatus, TxnReceipt, TxnStatus,
+        AddInvokeTransactionResult, BlockHashAndNumber, BlockId, BlockTag, ContractClass, DeployAccountTxn,
+        DeployAccountTxnV3, FeeEstimate, FunctionCall, InvokeTxn, InvokeTxnV1, MaybePendingBlockWithTxHashes,
+        MaybePendingBlockWithTxs, MaybePendingStateUpdate, SyncingStatus, Txn, TxnExecutionStatus, TxnReceipt,
+        TxnStatus,
     },
     DeclareTxn, DeployTxn, InvokeTxnReceipt, MsgFromL1,
 };
@@ -22,6 +22,7 @@ use starknet_types_rpc::{
 use tracing::{info, warn};
 use url::Url;
 
+use crate::utils::spec_version::parse_reported_version;
 use crate::utils::v7::{
     accounts::{
         account::{Account, AccountError, ConnectedAccount},
@@ -46,6 +47,7 @@ use crate::utils::v7::{
 };
 
 use super::{
+    block_selector::BlockSelector,
     declare_contract::{parse_class_hash_from_error, RunnerError},
     errors::OpenRpcTestGenError,
     utils::{
@@ -1029,6 +1031,95 @@ pub async fn chain_id(url: Url) -> Result<Felt, OpenRpcTestGenError> {
     }
 }
 
+pub async fn block_hash_and_number(url: Url) -> Result<BlockHashAndNumber<Felt>, OpenRpcTestGenError> {
+    let client = JsonRpcClient::new(HttpTransport::new(url.clone()));
+
+    // A new block may be produced between the requests, so retry a few times until the
+    // three endpoints agree on the latest block.
+    let mut last_mismatch = String::new();
+    for _ in 0..3 {
+        let block_hash_and_number = client.block_hash_and_number().await?;
+        let block_number = client.block_number().await?;
+        let latest_block = match client.get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest)).await? {
+            MaybePendingBlockWithTxHashes::Block(block) => block,
+            _ => {
+                return Err(OpenRpcTestGenError::Other("unexpected block response type".to_string()));
+            }
+        };
+
+        if block_hash_and_number.block_number != block_number
+            || block_hash_and_number.block_number != latest_block.block_header.block_number
+        {
+            last_mismatch = format!(
+                "block number mismatch: blockHashAndNumber {}, blockNumber {}, getBlockWithTxHashes(latest) {}",
+                block_hash_and_number.block_number, block_number, latest_block.block_header.block_number
+            );
+            continue;
+        }
+
+        if block_hash_and_number.block_hash != latest_block.block_header.block_hash {
+            return Err(OpenRpcTestGenError::Other(format!(
+                "block hash mismatch: blockHashAndNumber {:#x}, getBlockWithTxHashes(latest) {:#x}",
+                block_hash_and_number.block_hash, latest_block.block_header.block_hash
+            )));
+        }
+
+        return Ok(block_hash_and_number);
+    }
+
+    Err(OpenRpcTestGenError::Other(last_mismatch))
+}
+
+pub async fn syncing(url: Url) -> Result<SyncingStatus<Felt>, OpenRpcTestGenError> {
+    let client = JsonRpcClient::new(HttpTransport::new(url.clone()));
+
+    let syncing_status = client.syncing().await?;
+
+    if let SyncingStatus::Syncing(status) = &syncing_status {
+        if status.starting_block_num > status.current_block_num || status.current_block_num > status.highest_block_num {
+            return Err(OpenRpcTestGenError::Other(format!(
+                "inconsistent syncing block numbers: starting {}, current {}, highest {}",
+                status.starting_block_num, status.current_block_num, status.highest_block_num
+            )));
+        }
+    }
+
+    Ok(syncing_status)
+}
+
+pub async fn spec_version(url: Url) -> Result<String, OpenRpcTestGenError> {
+    let client = JsonRpcClient::new(HttpTransport::new(url.clone()));
+
+    let spec_version = client.spec_version().await?;
+
+    if parse_reported_version(&spec_version).is_none() {
+        return Err(OpenRpcTestGenError::Other(format!(
+            "spec version {} is not in the MAJOR.MINOR.PATCH format",
+            spec_version
+        )));
+    }
+
+    Ok(spec_version)
+}
+
+pub async fn get_nonce(url: Url, account_address: Option<Felt>) -> Result<Felt, OpenRpcTestGenError> {
+    let client = JsonRpcClient::new(HttpTransport::new(url.clone()));
+    let account_address =
+        account_address.ok_or_else(|| OpenRpcTestGenError::InvalidInput("account_address".to_string()))?;
+
+    let latest_nonce = client.get_nonce(BlockId::Tag(BlockTag::Latest), account_address).await?;
+    let pending_nonce = client.get_nonce(BlockId::Tag(BlockTag::Pending), account_address).await?;
+
+    if pending_nonce < latest_nonce {
+        return Err(OpenRpcTestGenError::Other(format!(
+            "pending nonce {:#x} is lower than latest nonce {:#x}",
+            pending_nonce, latest_nonce
+        )));
+    }
+
+    Ok(latest_nonce)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn call(
     url: Url,
@@ -1326,65 +1417,116 @@ pub async fn estimate_message_fee(
     Ok(estimate)
 }
 
-pub async fn get_block_transaction_count(url: Url) -> Result<u64, OpenRpcTestGenError> {
+pub async fn get_block_transaction_count(url: Url, block_selector: BlockSelector) -> Result<u64, OpenRpcTestGenError> {
     let client = JsonRpcClient::new(HttpTransport::new(url.clone()));
-    let count = client.get_block_transaction_count(BlockId::Tag(BlockTag::Latest)).await?;
+    let block_id = block_selector.resolve(&client).await?;
+    let count = client.get_block_transaction_count(block_id).await?;
     Ok(count)
 }
 
-pub async fn get_block_with_tx_hashes(url: Url) -> Result<BlockWithTxHashes<Felt>, OpenRpcTestGenError> {
+pub async fn get_block_with_tx_hashes(
+    url: Url,
+    block_selector: BlockSelector,
+) -> Result<MaybePendingBlockWithTxHashes<Felt>, OpenRpcTestGenError> {
     let client = JsonRpcClient::new(HttpTransport::new(url.clone()));
+    let block_id = block_selector.resolve(&client).await?;
 
-    let block = client.get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest)).await?;
+    let block = client.get_block_with_tx_hashes(block_id.clone()).await?;
 
-    let response = match block {
-        MaybePendingBlockWithTxHashes::Block(block) => block,
-        _ => {
-            return Err(OpenRpcTestGenError::Other("unexpected block response type".to_string()));
+    match &block {
+        MaybePendingBlockWithTxHashes::Block(block) => {
+            check_block_matches(&block_id, block.block_header.block_hash, block.block_header.block_number)?
         }
-    };
-    Ok(response)
+        MaybePendingBlockWithTxHashes::Pending(_) if block_selector.allows_pending() => {}
+        MaybePendingBlockWithTxHashes::Pending(_) => {
+            return Err(OpenRpcTestGenError::Other(format!("unexpected pending block for {block_selector} block")));
+        }
+    }
+    Ok(block)
 }
 
-pub async fn get_block_with_txs(url: Url) -> Result<BlockWithTxs<Felt>, OpenRpcTestGenError> {
+pub async fn get_block_with_txs(
+    url: Url,
+    block_selector: BlockSelector,
+) -> Result<MaybePendingBlockWithTxs<Felt>, OpenRpcTestGenError> {
     let client = JsonRpcClient::new(HttpTransport::new(url.clone()));
+    let block_id = block_selector.resolve(&client).await?;
 
-    let block = client.get_block_with_txs(BlockId::Tag(BlockTag::Latest)).await?;
+    let block = client.get_block_with_txs(block_id.clone()).await?;
 
-    let block = match block {
-        MaybePendingBlockWithTxs::Block(block) => block,
-        _ => {
-            return Err(OpenRpcTestGenError::Other("unexpected block response type".to_string()));
+    match &block {
+        MaybePendingBlockWithTxs::Block(block) => {
+            check_block_matches(&block_id, block.block_header.block_hash, block.block_header.block_number)?
         }
-    };
-
+        MaybePendingBlockWithTxs::Pending(_) if block_selector.allows_pending() => {}
+        MaybePendingBlockWithTxs::Pending(_) => {
+            return Err(OpenRpcTestGenError::Other(format!("unexpected pending block for {block_selector} block")));
+        }
+    }
     Ok(block)
 }
 
-pub async fn get_state_update(url: Url) -> Result<StateUpdate<Felt>, OpenRpcTestGenError> {
+pub async fn get_state_update(
+    url: Url,
+    block_selector: BlockSelector,
+) -> Result<MaybePendingStateUpdate<Felt>, OpenRpcTestGenError> {
     let client = JsonRpcClient::new(HttpTransport::new(url.clone()));
-
-    let state: MaybePendingStateUpdate<Felt> = client.get_state_update(BlockId::Tag(BlockTag::Latest)).await?;
-
-    let state = match state {
-        MaybePendingStateUpdate::Block(state) => state,
-        _ => {
-            return Err(OpenRpcTestGenError::Other("unexpected block response type".to_string()));
+    let block_id = block_selector.resolve(&client).await?;
+
+    let state = client.get_state_update(block_id.clone()).await?;
+
+    match &state {
+        MaybePendingStateUpdate::Block(state) => {
+            if let BlockId::Hash(block_hash) = block_id {
+                if state.block_hash != block_hash {
+                    return Err(OpenRpcTestGenError::Other(format!(
+                        "state update of block {:#x} returned for block {:#x}",
+                        state.block_hash, block_hash
+                    )));
+                }
+            }
         }
-    };
-
+        MaybePendingStateUpdate::Pending(_) if block_selector.allows_pending() => {}
+        MaybePendingStateUpdate::Pending(_) => {
+            return Err(OpenRpcTestGenError::Other(format!(
+                "unexpected pending state update for {block_selector} block"
+            )));
+        }
+    }
     Ok(state)
 }
 
-pub async fn get_storage_at(url: Url, erc20_eth_contract_address: Option<Felt>) -> Result<Felt, OpenRpcTestGenError> {
+/// Checks that a block returned for an explicit number or hash is the requested one.
+fn check_block_matches(
+    block_id: &BlockId<Felt>,
+    block_hash: Felt,
+    block_number: u64,
+) -> Result<(), OpenRpcTestGenError> {
+    match block_id {
+        BlockId::Number(number) if *number != block_number => {
+            Err(OpenRpcTestGenError::Other(format!("block {block_number} returned for block {number}")))
+        }
+        BlockId::Hash(hash) if *hash != block_hash => {
+            Err(OpenRpcTestGenError::Other(format!("block {block_hash:#x} returned for block {hash:#x}")))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub async fn get_storage_at(
+    url: Url,
+    erc20_eth_contract_address: Option<Felt>,
+    block_selector: BlockSelector,
+) -> Result<Felt, OpenRpcTestGenError> {
     let client = JsonRpcClient::new(HttpTransport::new(url.clone()));
     let erc20_eth_address = match erc20_eth_contract_address {
         Some(address) => address,
         None => Felt::from_hex("049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7")?,
     };
     let key: Felt = Felt::from_hex("0000000000000000000000000000000000000000000000000000000000000001")?;
+    let block_id = block_selector.resolve(&client).await?;
     // Checks L2 ETH balance via storage taking advantage of implementation detail
-    let storage_value = client.get_storage_at(erc20_eth_address, key, BlockId::Tag(BlockTag::Latest)).await?;
+    let storage_value = client.get_storage_at(erc20_eth_address, key, block_id).await?;
     Ok(storage_value)
 }
 
@@ -2080,6 +2222,7 @@ pub async fn get_class(
     erc20_strk_contract_address: Option<Felt>,
     erc20_eth_contract_address: Option<Felt>,
     amount_per_test: Option<Felt>,
+    block_selector: BlockSelector,
 ) -> Result<ContractClass<Felt>, OpenRpcTestGenError> {
     let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(sierra_path, casm_path).await?;
 
@@ -2164,7 +2307,8 @@ pub async fn get_class(
             }
         };
 
-    let contract_class = account.provider().get_class(BlockId::Tag(BlockTag::Latest), declare_contract_hash?).await?;
+    let block_id = block_selector.resolve(account.provider()).await?;
+    let contract_class = account.provider().get_class(block_id, declare_contract_hash?).await?;
 
     Ok(contract_class)
 }
@@ -2180,6 +2324,7 @@ pub async fn get_class_hash_at(
     erc20_strk_contract_address: Option<Felt>,
     erc20_eth_contract_address: Option<Felt>,
     amount_per_test: Option<Felt>,
+    block_selector: BlockSelector,
 ) -> Result<Felt, OpenRpcTestGenError> {
     let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(sierra_path, casm_path).await?;
 
@@ -2302,8 +2447,8 @@ pub async fn get_class_hash_at(
             return Err(OpenRpcTestGenError::CallError(CallError::UnexpectedReceiptType));
         }
     };
-    let contract_class_hash =
-        account.provider().get_class_hash_at(BlockId::Tag(BlockTag::Pending), contract_address).await?;
+    let block_id = block_selector.resolve(account.provider()).await?;
+    let contract_class_hash = account.provider().get_class_hash_at(block_id, contract_address).await?;
 
     Ok(contract_class_hash)
 }
@@ -2319,6 +2464,7 @@ pub async fn get_class_at(
     erc20_strk_contract_address: Option<Felt>,
     erc20_eth_contract_address: Option<Felt>,
     amount_per_test: Option<Felt>,
+    block_selector: BlockSelector,
 ) -> Result<ContractClass<Felt>, OpenRpcTestGenError> {
     let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(sierra_path, casm_path).await?;
 
@@ -2442,7 +2588,8 @@ pub async fn get_class_at(
         }
     };
 
-    let contract_class = account.provider().get_class_at(BlockId::Tag(BlockTag::Pending), contract_address).await?;
+    let block_id = block_selector.resolve(account.provider()).await?;
+    let contract_class = account.provider().get_class_at(block_id, contract_address).await?;
 
     Ok(contract_class)
 }
@@ -5,8 +5,10 @@ use crate::{
         conversions::errors::ConversionsError,
         v7::{
             accounts::{
-                account::AccountError, errors::CreationError, single_owner::SignError as SingleOwnerSignError,
-                utils::mint::MintError,
+                account::AccountError,
+                errors::CreationError,
+                single_owner::SignError as SingleOwnerSignError,
+                utils::{faucet::FaucetError, mint::MintError, predeployed::PredeployedAccountsError},
             },
             providers::provider::ProviderError,
             signers::local_wallet::SignError,
@@ -34,6 +36,10 @@ pub enum OpenRpcTestGenError {
     #[error(transparent)]
     MintError(#[from] MintError),
     #[error(transparent)]
+    FaucetError(#[from] FaucetError),
+    #[error(transparent)]
+    PredeployedAccountsError(#[from] PredeployedAccountsError),
+    #[error(transparent)]
     SignError(#[from] SignError),
     #[error(transparent)]
     GetPublicKeyError(#[from] crate::utils::v7::signers::local_wallet::Infallible),
@@ -91,6 +97,22 @@ pub enum OpenRpcTestGenError {
     TestSuiteFailure { failed_tests: HashMap<String, String> },
     #[error(transparent)]
     Proof(#[from] ProofError),
+    #[error(transparent)]
+    EventBackfill(#[from] crate::utils::event_backfill::EventBackfillError),
+    #[error(transparent)]
+    Snapshot(#[from] crate::utils::snapshot::SnapshotError),
+    #[error(transparent)]
+    Resilience(#[from] crate::utils::resilience::ResilienceError),
+    #[error(transparent)]
+    ChainId(#[from] crate::utils::chain_id::ChainIdError),
+    #[error(transparent)]
+    DryRun(#[from] crate::utils::dry_run::DryRunError),
+    #[error(transparent)]
+    Deploy(#[from] super::deploy_contract::DeployError),
+    #[error(transparent)]
+    StarknetVersion(#[from] crate::utils::starknet_version::StarknetVersionError),
+    #[error(transparent)]
+    Workload(#[from] crate::utils::workload::WorkloadError),
 }
 
 #[derive(PartialEq, Eq, Debug, Error)]
@@ -1,24 +1,28 @@
+pub mod block_selector;
 pub mod declare_contract;
 pub mod deploy_contract;
 pub mod endpoints_functions;
 pub mod errors;
+pub mod scaffold;
 pub mod utils;
 
+use block_selector::BlockSelector;
 use colored::*;
 use endpoints_functions::{
     add_declare_transaction_v2, add_declare_transaction_v3, add_invoke_transaction_v1, add_invoke_transaction_v3,
-    block_number, call, chain_id, estimate_message_fee, get_block_transaction_count, get_block_with_tx_hashes,
-    get_block_with_txs, get_class, get_class_at, get_class_hash_at, get_state_update, get_storage_at,
-    get_transaction_by_block_id_and_index, get_transaction_by_hash_deploy_acc, get_transaction_by_hash_invoke,
-    get_transaction_by_hash_non_existent_tx, get_transaction_receipt, get_transaction_status_succeeded,
-    invoke_contract_v1, invoke_contract_v3,
+    block_hash_and_number, block_number, call, chain_id, estimate_message_fee, get_block_transaction_count,
+    get_block_with_tx_hashes, get_block_with_txs, get_class, get_class_at, get_class_hash_at, get_nonce,
+    get_state_update, get_storage_at, get_transaction_by_block_id_and_index, get_transaction_by_hash_deploy_acc,
+    get_transaction_by_hash_invoke, get_transaction_by_hash_non_existent_tx, get_transaction_receipt,
+    get_transaction_status_succeeded, invoke_contract_v1, invoke_contract_v3, spec_version, syncing,
 };
 use errors::OpenRpcTestGenError;
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::{
     v0_7_1::{
-        AddInvokeTransactionResult, BlockWithTxHashes, BlockWithTxs, ContractClass, DeployAccountTxnV3, InvokeTxnV1,
-        StateUpdate, Txn, TxnStatus,
+        AddInvokeTransactionResult, BlockHashAndNumber, ContractClass, DeployAccountTxnV3, InvokeTxnV1,
+        MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs, MaybePendingStateUpdate, SyncingStatus, Txn,
+        TxnStatus,
     },
     FeeEstimate, InvokeTxnReceipt,
 };
@@ -28,16 +32,21 @@ use url::Url;
 
 pub struct Rpc {
     pub url: Url,
+    /// Block the endpoints reading state are queried at.
+    pub block_selector: BlockSelector,
 }
 
 impl Rpc {
     #[allow(clippy::result_large_err)]
     pub fn new(url: Url) -> Result<Self, OpenRpcTestGenError> {
-        Ok(Self { url })
+        Ok(Self { url, block_selector: BlockSelector::default() })
     }
     pub fn set_url(&mut self, new_url: Url) {
         self.url = new_url;
     }
+    pub fn set_block_selector(&mut self, block_selector: BlockSelector) {
+        self.block_selector = block_selector;
+    }
 }
 
 pub trait RpcEndpoints {
@@ -134,6 +143,19 @@ pub trait RpcEndpoints {
 
     fn block_number(&self) -> impl std::future::Future<Output = Result<u64, OpenRpcTestGenError>>;
 
+    fn block_hash_and_number(
+        &self,
+    ) -> impl std::future::Future<Output = Result<BlockHashAndNumber<Felt>, OpenRpcTestGenError>>;
+
+    fn get_nonce(
+        &self,
+        account_address: Option<Felt>,
+    ) -> impl std::future::Future<Output = Result<Felt, OpenRpcTestGenError>>;
+
+    fn syncing(&self) -> impl std::future::Future<Output = Result<SyncingStatus<Felt>, OpenRpcTestGenError>>;
+
+    fn spec_version(&self) -> impl std::future::Future<Output = Result<String, OpenRpcTestGenError>>;
+
     fn chain_id(&self) -> impl std::future::Future<Output = Result<Felt, OpenRpcTestGenError>>;
 
     #[allow(clippy::too_many_arguments)]
@@ -166,11 +188,15 @@ pub trait RpcEndpoints {
 
     fn get_block_with_tx_hashes(
         &self,
-    ) -> impl std::future::Future<Output = Result<BlockWithTxHashes<Felt>, OpenRpcTestGenError>>;
+    ) -> impl std::future::Future<Output = Result<MaybePendingBlockWithTxHashes<Felt>, OpenRpcTestGenError>>;
 
-    fn get_block_with_txs(&self) -> impl std::future::Future<Output = Result<BlockWithTxs<Felt>, OpenRpcTestGenError>>;
+    fn get_block_with_txs(
+        &self,
+    ) -> impl std::future::Future<Output = Result<MaybePendingBlockWithTxs<Felt>, OpenRpcTestGenError>>;
 
-    fn get_state_update(&self) -> impl std::future::Future<Output = Result<StateUpdate<Felt>, OpenRpcTestGenError>>;
+    fn get_state_update(
+        &self,
+    ) -> impl std::future::Future<Output = Result<MaybePendingStateUpdate<Felt>, OpenRpcTestGenError>>;
 
     fn get_storage_at(
         &self,
@@ -476,6 +502,22 @@ impl RpcEndpoints for Rpc {
         block_number(self.url.clone()).await
     }
 
+    async fn block_hash_and_number(&self) -> Result<BlockHashAndNumber<Felt>, OpenRpcTestGenError> {
+        block_hash_and_number(self.url.clone()).await
+    }
+
+    async fn get_nonce(&self, account_address: Option<Felt>) -> Result<Felt, OpenRpcTestGenError> {
+        get_nonce(self.url.clone(), account_address).await
+    }
+
+    async fn syncing(&self) -> Result<SyncingStatus<Felt>, OpenRpcTestGenError> {
+        syncing(self.url.clone()).await
+    }
+
+    async fn spec_version(&self) -> Result<String, OpenRpcTestGenError> {
+        spec_version(self.url.clone()).await
+    }
+
     async fn chain_id(&self) -> Result<Felt, OpenRpcTestGenError> {
         chain_id(self.url.clone()).await
     }
@@ -531,19 +573,19 @@ impl RpcEndpoints for Rpc {
     }
 
     async fn get_block_transaction_count(&self) -> Result<u64, OpenRpcTestGenError> {
-        get_block_transaction_count(self.url.clone()).await
+        get_block_transaction_count(self.url.clone(), self.block_selector).await
     }
 
-    async fn get_block_with_tx_hashes(&self) -> Result<BlockWithTxHashes<Felt>, OpenRpcTestGenError> {
-        get_block_with_tx_hashes(self.url.clone()).await
+    async fn get_block_with_tx_hashes(&self) -> Result<MaybePendingBlockWithTxHashes<Felt>, OpenRpcTestGenError> {
+        get_block_with_tx_hashes(self.url.clone(), self.block_selector).await
     }
 
-    async fn get_block_with_txs(&self) -> Result<BlockWithTxs<Felt>, OpenRpcTestGenError> {
-        get_block_with_txs(self.url.clone()).await
+    async fn get_block_with_txs(&self) -> Result<MaybePendingBlockWithTxs<Felt>, OpenRpcTestGenError> {
+        get_block_with_txs(self.url.clone(), self.block_selector).await
     }
 
-    async fn get_state_update(&self) -> Result<StateUpdate<Felt>, OpenRpcTestGenError> {
-        get_state_update(self.url.clone()).await
+    async fn get_state_update(&self) -> Result<MaybePendingStateUpdate<Felt>, OpenRpcTestGenError> {
+        get_state_update(self.url.clone(), self.block_selector).await
     }
 
     async fn get_storage_at(
@@ -551,7 +593,7 @@ impl RpcEndpoints for Rpc {
 
         erc20_eth_contract_address: Option<Felt>,
     ) -> Result<starknet_types_core::felt::Felt, OpenRpcTestGenError> {
-        get_storage_at(self.url.clone(), erc20_eth_contract_address).await
+        get_storage_at(self.url.clone(), erc20_eth_contract_address, self.block_selector).await
     }
 
     async fn get_transaction_status_succeeded(
@@ -725,6 +767,7 @@ impl RpcEndpoints for Rpc {
             erc20_strk_contract_address,
             erc20_eth_contract_address,
             amount_per_test,
+            self.block_selector,
         )
         .await
     }
@@ -751,6 +794,7 @@ impl RpcEndpoints for Rpc {
             erc20_strk_contract_address,
             erc20_eth_contract_address,
             amount_per_test,
+            self.block_selector,
         )
         .await
     }
@@ -777,6 +821,7 @@ impl RpcEndpoints for Rpc {
             erc20_strk_contract_address,
             erc20_eth_contract_address,
             amount_per_test,
+            self.block_selector,
         )
         .await
     }
@@ -795,10 +840,12 @@ pub async fn test_rpc_endpoints_v0_0_7(
     erc20_strk_contract_address: Option<Felt>,
     erc20_eth_contract_address: Option<Felt>,
     amount_per_test: Option<Felt>,
+    block_selector: BlockSelector,
 ) -> Result<(), OpenRpcTestGenError> {
-    info!("{}", "⌛ Testing Rpc V7 endpoints -- START ⌛".yellow());
+    info!("{}", format!("⌛ Testing Rpc V7 endpoints at {block_selector} block -- START ⌛").yellow());
 
-    let rpc = Rpc::new(url.clone())?;
+    let mut rpc = Rpc::new(url.clone())?;
+    rpc.set_block_selector(block_selector);
     // match rpc
     //     .invoke_contract_erc20_transfer(
     //         sierra_path,
@@ -963,6 +1010,39 @@ pub async fn test_rpc_endpoints_v0_0_7(
         Err(e) => error!("{} {} {}", "✗ Rpc chain_id INCOMPATIBLE:".red(), e.to_string().red(), "✗".red()),
     }
 
+    match rpc.block_hash_and_number().await {
+        Ok(_) => {
+            info!("{} {}", "\n✓ Rpc block_hash_and_number COMPATIBLE".green(), "✓".green())
+        }
+        Err(e) => {
+            error!("{} {} {}", "✗ Rpc block_hash_and_number INCOMPATIBLE:".red(), e.to_string().red(), "✗".red())
+        }
+    }
+
+    match rpc.get_nonce(account_address).await {
+        Ok(_) => {
+            info!("{} {}", "\n✓ Rpc get_nonce COMPATIBLE".green(), "✓".green())
+        }
+        Err(e) => error!("{} {} {}", "✗ Rpc get_nonce INCOMPATIBLE:".red(), e.to_string().red(), "✗".red()),
+    }
+
+    match rpc.syncing().await {
+        Ok(SyncingStatus::NotSyncing) => {
+            info!("{} {}", "\n✓ Rpc syncing (not syncing) COMPATIBLE".green(), "✓".green())
+        }
+        Ok(SyncingStatus::Syncing(_)) => {
+            info!("{} {}", "\n✓ Rpc syncing (syncing) COMPATIBLE".green(), "✓".green())
+        }
+        Err(e) => error!("{} {} {}", "✗ Rpc syncing INCOMPATIBLE:".red(), e.to_string().red(), "✗".red()),
+    }
+
+    match rpc.spec_version().await {
+        Ok(_) => {
+            info!("{} {}", "\n✓ Rpc spec_version COMPATIBLE".green(), "✓".green())
+        }
+        Err(e) => error!("{} {} {}", "✗ Rpc spec_version INCOMPATIBLE:".red(), e.to_string().red(), "✗".red()),
+    }
+
     match rpc
         .call(
             sierra_path,
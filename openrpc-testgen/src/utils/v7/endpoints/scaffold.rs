@@ -0,0 +1,2 @@
+// @generated by `openrpc-scaffold` from the OpenRPC document. Do not edit: implement the
+// methods in the provider instead, after which their stubs are no longer generated.
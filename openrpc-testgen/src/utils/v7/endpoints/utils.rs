@@ -1,10 +1,12 @@
 use std::time::Duration;
 
+use crate::utils::display::register_sierra_class;
+use crate::utils::inclusion_latency::record_accepted_on_l2;
 use crate::utils::v7::accounts::account::Account;
 use crate::utils::v7::accounts::account::ConnectedAccount;
 use crate::utils::v7::accounts::call::Call;
 use crate::utils::v7::accounts::single_owner::SingleOwnerAccount;
-use crate::utils::v7::providers::jsonrpc::{HttpTransport, JsonRpcClient};
+use crate::utils::v7::providers::jsonrpc::{transports::shared_client, HttpTransport, JsonRpcClient};
 use crate::utils::v7::providers::provider::Provider;
 use crate::utils::v7::signers::local_wallet::LocalWallet;
 use crate::utils::v7::{
@@ -12,7 +14,6 @@ use crate::utils::v7::{
     contract::{CompiledClass, HashAndFlatten, SierraClass},
     endpoints::errors::OpenRpcTestGenError,
 };
-use reqwest::Client;
 use starknet_types_core::felt::Felt;
 use starknet_types_core::hash::{Pedersen, StarkHash};
 use starknet_types_rpc::v0_7_1::{ContractClass, TxnHash};
@@ -52,6 +53,7 @@ pub async fn get_compiled_contract(
     file.read_to_string(&mut casm).await.map_err(|e| RunnerError::ReadFileError(e.to_string()))?;
 
     let contract_artifact: SierraClass = serde_json::from_str(&sierra)?;
+    register_sierra_class(&contract_artifact);
 
     let compiled_class: CompiledClass = serde_json::from_str(&casm)?;
 
@@ -63,7 +65,7 @@ pub async fn get_compiled_contract(
 }
 
 pub async fn restart_devnet(url: Url) -> Result<(), OpenRpcTestGenError> {
-    let client = Client::new();
+    let client = shared_client();
     let url = url.join("/restart")?;
     let response = client.post(url).send().await?;
     if response.status().is_success() {
@@ -147,6 +149,13 @@ pub fn validate_inputs(
 }
 
 use starknet_types_rpc::MaybePendingBlockWithTxHashes;
+/// Logs within a `wait_for_transaction` span with the hash as field, so that they can be told apart
+/// from those of the other transactions of a test case.
+#[tracing::instrument(
+    name = "wait_for_transaction",
+    skip_all,
+    fields(transaction_hash = %format!("{:#x}", transaction_hash))
+)]
 pub async fn wait_for_sent_transaction(
     transaction_hash: Felt,
     user_passed_account: &SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
@@ -154,7 +163,7 @@ pub async fn wait_for_sent_transaction(
     let start_fetching = std::time::Instant::now();
     let wait_for = Duration::from_secs(60);
 
-    info!("⏳ Waiting for transaction: {:?} to be mined.", transaction_hash);
+    info!("⏳ Waiting for the transaction to be mined.");
 
     loop {
         if start_fetching.elapsed() > wait_for {
@@ -168,22 +177,23 @@ pub async fn wait_for_sent_transaction(
         let status = match user_passed_account.provider().get_transaction_status(transaction_hash).await {
             Ok(status) => status,
             Err(_e) => {
-                info!("Error while checking status for transaction: {:?}. Retrying...", transaction_hash);
+                info!("Error while checking the status of the transaction. Retrying...");
                 tokio::time::sleep(Duration::from_secs(1)).await;
                 continue;
             }
         };
 
+        if matches!(status.finality_status, TxnStatus::AcceptedOnL2 | TxnStatus::AcceptedOnL1) {
+            record_accepted_on_l2(transaction_hash);
+        }
+
         match status {
             TxnFinalityAndExecutionStatus {
                 finality_status: TxnStatus::AcceptedOnL2,
                 execution_status: Some(TxnExecutionStatus::Succeeded),
                 ..
             } => {
-                info!(
-                    "Transaction {:?} status: AcceptedOnL2 and Succeeded. Checking block inclusion...",
-                    transaction_hash
-                );
+                info!("Transaction status: AcceptedOnL2 and Succeeded. Checking block inclusion...");
 
                 // Check if the transaction is in the pending block
                 let in_pending = match user_passed_account
@@ -206,23 +216,17 @@ pub async fn wait_for_sent_transaction(
                 };
 
                 if in_pending && !in_latest {
-                    info!(
-                        "Transaction {:?} is in Pending block but not yet in Latest block. Retrying...",
-                        transaction_hash
-                    );
+                    info!("Transaction is in Pending block but not yet in Latest block. Retrying...");
                     tokio::time::sleep(Duration::from_secs(2)).await;
                     continue;
                 }
 
                 if in_latest && !in_pending {
-                    info!(
-                        "✅ Transaction {:?} confirmed in Latest block and not in Pending. Finishing...",
-                        transaction_hash
-                    );
+                    info!("✅ Transaction confirmed in Latest block and not in Pending. Finishing...");
                     return Ok(status);
                 }
 
-                info!("Transaction {:?} is neither in Latest nor finalized. Retrying...", transaction_hash);
+                info!("Transaction is neither in Latest nor finalized. Retrying...");
                 tokio::time::sleep(Duration::from_secs(2)).await;
                 continue;
             }
@@ -231,15 +235,15 @@ pub async fn wait_for_sent_transaction(
                 execution_status: Some(TxnExecutionStatus::Reverted),
                 ..
             } => {
-                info!("❌ Transaction {:?} reverted on L2. Stopping...", transaction_hash);
+                info!("❌ Transaction reverted on L2. Stopping...");
                 return Err(OpenRpcTestGenError::TransactionFailed(transaction_hash.to_string()));
             }
             TxnFinalityAndExecutionStatus { finality_status: TxnStatus::Rejected, .. } => {
-                info!("❌ Transaction {:?} rejected. Stopping...", transaction_hash);
+                info!("❌ Transaction rejected. Stopping...");
                 return Err(OpenRpcTestGenError::TransactionRejected(transaction_hash.to_string()));
             }
             TxnFinalityAndExecutionStatus { finality_status: TxnStatus::Received, .. } => {
-                info!("🛎️ Transaction {:?} received. Retrying...", transaction_hash);
+                info!("🛎️ Transaction received. Retrying...");
                 tokio::time::sleep(Duration::from_secs(2)).await;
                 continue;
             }
@@ -249,7 +253,7 @@ pub async fn wait_for_sent_transaction(
             }
 
             _ => {
-                info!("⏳ Transaction {} status not finalized. Retrying...", transaction_hash);
+                info!("⏳ Transaction status not finalized. Retrying...");
                 tokio::time::sleep(Duration::from_secs(2)).await;
                 continue;
             }
@@ -285,3 +289,25 @@ pub async fn setup_generated_account(
     wait_for_sent_transaction(transfer_execution.transaction_hash, &user_passed_account).await?;
     Ok(())
 }
+
+/// Declares the class of `artifact` (path without the `.contract_class.json` suffix) unless an
+/// earlier run already did, and returns its class hash.
+pub async fn declare_if_missing(
+    account: &SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
+    artifact: &str,
+) -> Result<Felt, OpenRpcTestGenError> {
+    let sierra_path = format!("{artifact}.contract_class.json");
+    let casm_path = format!("{artifact}.compiled_contract_class.json");
+
+    let sierra_file =
+        std::fs::File::open(&sierra_path).map_err(|e| RunnerError::ReadFileError(format!("{sierra_path}: {e}")))?;
+    let sierra_class: SierraClass = serde_json::from_reader(sierra_file).map_err(RunnerError::from)?;
+    let class_hash = sierra_class.class_hash().map_err(RunnerError::from)?;
+
+    if account.provider().get_class(BlockId::Tag(BlockTag::Pending), class_hash).await.is_err() {
+        let (flattened_sierra_class, compiled_class_hash) = get_compiled_contract(&sierra_path, &casm_path).await?;
+        let declaration = account.declare_v3(flattened_sierra_class, compiled_class_hash).send().await?;
+        wait_for_sent_transaction(declaration.transaction_hash, account).await?;
+    }
+    Ok(class_hash)
+}
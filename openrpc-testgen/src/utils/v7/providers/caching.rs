@@ -0,0 +1,476 @@
+//! A [Provider] decorator memoizing the answers to queries whose result can no longer change:
+//! classes at blocks after one they were returned at, blocks deep enough below the latest block or
+//! accepted on L1, and the transactions of those blocks. Raw requests for a final block by number
+//! are cached as well.
+//! Everything else goes to the inner provider every time, so the decorator only pays off for runs
+//! reading the same history over and over, e.g. walking blocks.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::Value;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{
+    v0_7_1::{
+        AddInvokeTransactionResult, BlockHashAndNumber, BlockId, BlockStatus, BroadcastedTxn, ClassAndTxnHash,
+        ContractAndTxnHash, ContractClass, EventFilterWithPageRequest, EventsChunk, FeeEstimate, FunctionCall,
+        MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs, MaybePendingStateUpdate, MsgFromL1,
+        SimulateTransactionsResult, SimulationFlag, SyncingStatus, TraceBlockTransactionsResult, TransactionTrace, Txn,
+        TxnFinalityAndExecutionStatus, TxnReceipt, TxnStatus,
+    },
+    BlockWithReceipts,
+};
+
+use super::provider::{Provider, ProviderError};
+use crate::utils::v8::types::{ContractStorageKeysItem, GetStorageProofResult};
+
+/// Blocks this far below the latest block are considered final.
+pub const DEFAULT_CONFIRMATIONS: u64 = 10;
+
+/// Raw requests whose result only depends on the block they name.
+const RAW_BLOCK_METHODS: [&str; 6] = [
+    "starknet_getBlockTransactionCount",
+    "starknet_getBlockWithReceipts",
+    "starknet_getBlockWithTxHashes",
+    "starknet_getBlockWithTxs",
+    "starknet_getStateUpdate",
+    "starknet_traceBlockTransactions",
+];
+
+#[derive(Debug, Default)]
+struct Cache {
+    /// Classes by hash, with the earliest block number they were returned at, `None` if they were
+    /// only returned at a tag.
+    classes: HashMap<Felt, (Option<u64>, ContractClass<Felt>)>,
+    blocks: HashMap<u64, MaybePendingBlockWithTxs<Felt>>,
+    block_numbers: HashMap<Felt, u64>,
+    transactions: HashMap<Felt, Txn<Felt>>,
+    /// Transactions seen `ACCEPTED_ON_L1`, whose content is cached at the next request.
+    final_transactions: HashSet<Felt>,
+    /// Results of raw requests by method and parameters.
+    raw: HashMap<(String, String), Value>,
+}
+
+impl Cache {
+    /// Number of the block `block_id` names, if it is a number or the hash of a cached block.
+    fn block_number(&self, block_id: &BlockId<Felt>) -> Option<u64> {
+        match block_id {
+            BlockId::Number(block_number) => Some(*block_number),
+            BlockId::Hash(block_hash) => self.block_numbers.get(block_hash).copied(),
+            BlockId::Tag(_) => None,
+        }
+    }
+}
+
+/// Hits and misses of the cached queries, the other queries are not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug)]
+pub struct CachingProvider<P> {
+    inner: P,
+    confirmations: u64,
+    /// Highest block number the inner provider reported.
+    latest_block: AtomicU64,
+    cache: Mutex<Cache>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<P> CachingProvider<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            confirmations: DEFAULT_CONFIRMATIONS,
+            latest_block: AtomicU64::new(0),
+            cache: Mutex::new(Cache::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Consumes the current [CachingProvider] instance and returns a new one considering blocks
+    /// final `confirmations` blocks below the latest block.
+    pub fn with_confirmations(self, confirmations: u64) -> Self {
+        Self { confirmations, ..self }
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats { hits: self.hits.load(Ordering::Relaxed), misses: self.misses.load(Ordering::Relaxed) }
+    }
+
+    fn cache(&self) -> std::sync::MutexGuard<'_, Cache> {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn count(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn observe_block_number(&self, block_number: u64) {
+        self.latest_block.fetch_max(block_number, Ordering::Relaxed);
+    }
+
+    fn cached_block(&self, block_id: &BlockId<Felt>) -> Option<MaybePendingBlockWithTxs<Felt>> {
+        let cache = self.cache();
+        let block_number = cache.block_number(block_id)?;
+        cache.blocks.get(&block_number).cloned()
+    }
+
+    /// Caches the block and its transactions if it is final.
+    fn store_block(&self, block: &MaybePendingBlockWithTxs<Felt>) {
+        let MaybePendingBlockWithTxs::Block(block_with_txs) = block else {
+            return;
+        };
+        let block_number = block_with_txs.block_header.block_number;
+        self.observe_block_number(block_number);
+        if !is_final(
+            &block_with_txs.status,
+            block_number,
+            self.latest_block.load(Ordering::Relaxed),
+            self.confirmations,
+        ) {
+            return;
+        }
+        let mut cache = self.cache();
+        for transaction in &block_with_txs.transactions {
+            cache.transactions.insert(transaction.transaction_hash, transaction.transaction.clone());
+        }
+        cache.block_numbers.insert(block_with_txs.block_header.block_hash, block_number);
+        cache.blocks.insert(block_number, block.clone());
+    }
+}
+
+impl std::ops::AddAssign for CacheStats {
+    fn add_assign(&mut self, other: Self) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+    }
+}
+
+/// Whether a block can no longer change: accepted on L1, or `confirmations` below the latest block.
+fn is_final(status: &BlockStatus, block_number: u64, latest_block: u64, confirmations: u64) -> bool {
+    match status {
+        BlockStatus::AcceptedOnL1 => true,
+        BlockStatus::AcceptedOnL2 => block_number.saturating_add(confirmations) <= latest_block,
+        _ => false,
+    }
+}
+
+/// Whether a class returned at block `seen_at` exists at `block_id`, whose number is `requested` if
+/// known. Declared classes stay declared, so they exist at the tags, and at every block after one
+/// they were returned at.
+fn class_exists_at(seen_at: Option<u64>, block_id: &BlockId<Felt>, requested: Option<u64>) -> bool {
+    match block_id {
+        BlockId::Tag(_) => true,
+        _ => matches!((seen_at, requested), (Some(seen_at), Some(requested)) if seen_at <= requested),
+    }
+}
+
+/// Number of the block a raw request of [RAW_BLOCK_METHODS] names, with positional or named
+/// parameters.
+fn raw_block_number(method: &str, params: &Value) -> Option<u64> {
+    if !RAW_BLOCK_METHODS.contains(&method) {
+        return None;
+    }
+    let block_id = params.get("block_id").or_else(|| params.get(0))?;
+    block_id.get("block_number")?.as_u64()
+}
+
+impl<P> Provider for CachingProvider<P>
+where
+    P: Provider + Sync,
+{
+    async fn spec_version(&self) -> Result<String, ProviderError> {
+        self.inner.spec_version().await
+    }
+
+    async fn get_block_with_tx_hashes(
+        &self,
+        block_id: BlockId<Felt>,
+    ) -> Result<MaybePendingBlockWithTxHashes<Felt>, ProviderError> {
+        self.inner.get_block_with_tx_hashes(block_id).await
+    }
+
+    async fn get_block_with_txs(
+        &self,
+        block_id: BlockId<Felt>,
+    ) -> Result<MaybePendingBlockWithTxs<Felt>, ProviderError> {
+        if let Some(block) = self.cached_block(&block_id) {
+            self.count(true);
+            return Ok(block);
+        }
+        let cacheable = !matches!(block_id, BlockId::Tag(_));
+        let block = self.inner.get_block_with_txs(block_id).await?;
+        if cacheable {
+            self.count(false);
+            self.store_block(&block);
+        }
+        Ok(block)
+    }
+
+    async fn get_block_with_receipts(&self, block_id: BlockId<Felt>) -> Result<BlockWithReceipts<Felt>, ProviderError> {
+        self.inner.get_block_with_receipts(block_id).await
+    }
+
+    async fn get_state_update(&self, block_id: BlockId<Felt>) -> Result<MaybePendingStateUpdate<Felt>, ProviderError> {
+        self.inner.get_state_update(block_id).await
+    }
+
+    async fn get_storage_at(
+        &self,
+        contract_address: Felt,
+        key: Felt,
+        block_id: BlockId<Felt>,
+    ) -> Result<Felt, ProviderError> {
+        self.inner.get_storage_at(contract_address, key, block_id).await
+    }
+
+    async fn get_storage_proof(
+        &self,
+        block_id: BlockId<Felt>,
+        class_hashes: Option<Vec<Felt>>,
+        contract_addresses: Option<Vec<Felt>>,
+        contracts_storage_keys: Option<Vec<ContractStorageKeysItem>>,
+    ) -> Result<GetStorageProofResult, ProviderError> {
+        self.inner.get_storage_proof(block_id, class_hashes, contract_addresses, contracts_storage_keys).await
+    }
+
+    async fn get_transaction_status(
+        &self,
+        transaction_hash: Felt,
+    ) -> Result<TxnFinalityAndExecutionStatus, ProviderError> {
+        let status = self.inner.get_transaction_status(transaction_hash).await?;
+        if matches!(status.finality_status, TxnStatus::AcceptedOnL1) {
+            self.cache().final_transactions.insert(transaction_hash);
+        }
+        Ok(status)
+    }
+
+    async fn get_transaction_by_hash(&self, transaction_hash: Felt) -> Result<Txn<Felt>, ProviderError> {
+        if let Some(transaction) = self.cache().transactions.get(&transaction_hash).cloned() {
+            self.count(true);
+            return Ok(transaction);
+        }
+        self.count(false);
+        let transaction = self.inner.get_transaction_by_hash(transaction_hash).await?;
+        let mut cache = self.cache();
+        if cache.final_transactions.contains(&transaction_hash) {
+            cache.transactions.insert(transaction_hash, transaction.clone());
+        }
+        Ok(transaction)
+    }
+
+    async fn get_transaction_by_block_id_and_index(
+        &self,
+        block_id: BlockId<Felt>,
+        index: u64,
+    ) -> Result<Txn<Felt>, ProviderError> {
+        self.inner.get_transaction_by_block_id_and_index(block_id, index).await
+    }
+
+    async fn get_transaction_receipt(&self, transaction_hash: Felt) -> Result<TxnReceipt<Felt>, ProviderError> {
+        self.inner.get_transaction_receipt(transaction_hash).await
+    }
+
+    /// A cached class is only returned at the latest and pending blocks, and at blocks not before
+    /// one it was returned at, so that asking for it before its declaration still fails.
+    async fn get_class(&self, block_id: BlockId<Felt>, class_hash: Felt) -> Result<ContractClass<Felt>, ProviderError> {
+        let (requested, cached) = {
+            let cache = self.cache();
+            (cache.block_number(&block_id), cache.classes.get(&class_hash).cloned())
+        };
+        if let Some((seen_at, class)) = cached {
+            if class_exists_at(seen_at, &block_id, requested) {
+                self.count(true);
+                return Ok(class);
+            }
+        }
+        self.count(false);
+        let class = self.inner.get_class(block_id, class_hash).await?;
+        let mut cache = self.cache();
+        let (seen_at, _) = cache.classes.entry(class_hash).or_insert((requested, class.clone()));
+        *seen_at = match (*seen_at, requested) {
+            (Some(seen_at), Some(requested)) => Some(seen_at.min(requested)),
+            (seen_at, requested) => seen_at.or(requested),
+        };
+        Ok(class)
+    }
+
+    async fn get_class_hash_at(&self, block_id: BlockId<Felt>, contract_address: Felt) -> Result<Felt, ProviderError> {
+        self.inner.get_class_hash_at(block_id, contract_address).await
+    }
+
+    async fn get_class_at(
+        &self,
+        block_id: BlockId<Felt>,
+        contract_address: Felt,
+    ) -> Result<ContractClass<Felt>, ProviderError> {
+        self.inner.get_class_at(block_id, contract_address).await
+    }
+
+    async fn get_block_transaction_count(&self, block_id: BlockId<Felt>) -> Result<u64, ProviderError> {
+        self.inner.get_block_transaction_count(block_id).await
+    }
+
+    async fn call(&self, request: FunctionCall<Felt>, block_id: BlockId<Felt>) -> Result<Vec<Felt>, ProviderError> {
+        self.inner.call(request, block_id).await
+    }
+
+    async fn estimate_fee(
+        &self,
+        request: Vec<BroadcastedTxn<Felt>>,
+        simulation_flags: Vec<String>,
+        block_id: BlockId<Felt>,
+    ) -> Result<Vec<FeeEstimate<Felt>>, ProviderError> {
+        self.inner.estimate_fee(request, simulation_flags, block_id).await
+    }
+
+    async fn estimate_message_fee(
+        &self,
+        message: MsgFromL1<Felt>,
+        block_id: BlockId<Felt>,
+    ) -> Result<FeeEstimate<Felt>, ProviderError> {
+        self.inner.estimate_message_fee(message, block_id).await
+    }
+
+    async fn block_number(&self) -> Result<u64, ProviderError> {
+        let block_number = self.inner.block_number().await?;
+        self.observe_block_number(block_number);
+        Ok(block_number)
+    }
+
+    async fn block_hash_and_number(&self) -> Result<BlockHashAndNumber<Felt>, ProviderError> {
+        let block_hash_and_number = self.inner.block_hash_and_number().await?;
+        self.observe_block_number(block_hash_and_number.block_number);
+        Ok(block_hash_and_number)
+    }
+
+    async fn chain_id(&self) -> Result<Felt, ProviderError> {
+        self.inner.chain_id().await
+    }
+
+    async fn syncing(&self) -> Result<SyncingStatus<Felt>, ProviderError> {
+        self.inner.syncing().await
+    }
+
+    async fn get_events(&self, filter: EventFilterWithPageRequest<Felt>) -> Result<EventsChunk<Felt>, ProviderError> {
+        self.inner.get_events(filter).await
+    }
+
+    async fn get_nonce(&self, block_id: BlockId<Felt>, contract_address: Felt) -> Result<Felt, ProviderError> {
+        self.inner.get_nonce(block_id, contract_address).await
+    }
+
+    async fn add_invoke_transaction(
+        &self,
+        invoke_transaction: BroadcastedTxn<Felt>,
+    ) -> Result<AddInvokeTransactionResult<Felt>, ProviderError> {
+        self.inner.add_invoke_transaction(invoke_transaction).await
+    }
+
+    async fn add_declare_transaction(
+        &self,
+        declare_transaction: BroadcastedTxn<Felt>,
+    ) -> Result<ClassAndTxnHash<Felt>, ProviderError> {
+        self.inner.add_declare_transaction(declare_transaction).await
+    }
+
+    async fn add_deploy_account_transaction(
+        &self,
+        deploy_account_transaction: BroadcastedTxn<Felt>,
+    ) -> Result<ContractAndTxnHash<Felt>, ProviderError> {
+        self.inner.add_deploy_account_transaction(deploy_account_transaction).await
+    }
+
+    async fn trace_transaction(&self, transaction_hash: Felt) -> Result<TransactionTrace<Felt>, ProviderError> {
+        self.inner.trace_transaction(transaction_hash).await
+    }
+
+    async fn simulate_transactions(
+        &self,
+        block_id: BlockId<Felt>,
+        transactions: Vec<BroadcastedTxn<Felt>>,
+        simulation_flags: Vec<SimulationFlag>,
+    ) -> Result<Vec<SimulateTransactionsResult<Felt>>, ProviderError> {
+        self.inner.simulate_transactions(block_id, transactions, simulation_flags).await
+    }
+
+    async fn trace_block_transactions(
+        &self,
+        block_id: BlockId<Felt>,
+    ) -> Result<Vec<TraceBlockTransactionsResult<Felt>>, ProviderError> {
+        self.inner.trace_block_transactions(block_id).await
+    }
+
+    /// Caches requests of [RAW_BLOCK_METHODS] naming a block by number, once that block is
+    /// `confirmations` below the latest block.
+    async fn send_raw(&self, method: &str, params: Value) -> Result<Value, ProviderError> {
+        let Some(block_number) = raw_block_number(method, &params) else {
+            return self.inner.send_raw(method, params).await;
+        };
+        let key = (method.to_string(), params.to_string());
+        if let Some(result) = self.cache().raw.get(&key).cloned() {
+            self.count(true);
+            return Ok(result);
+        }
+        self.count(false);
+        let result = self.inner.send_raw(method, params).await?;
+        let latest_block = self.latest_block.load(Ordering::Relaxed);
+        if is_final(&BlockStatus::AcceptedOnL2, block_number, latest_block, self.confirmations) {
+            self.cache().raw.insert(key, result.clone());
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet_types_rpc::v0_7_1::BlockTag;
+
+    #[test]
+    fn only_deep_or_l1_accepted_blocks_are_final() {
+        assert!(is_final(&BlockStatus::AcceptedOnL2, 90, 100, 10));
+        assert!(!is_final(&BlockStatus::AcceptedOnL2, 91, 100, 10));
+        // Nothing is final before the latest block is known.
+        assert!(!is_final(&BlockStatus::AcceptedOnL2, 0, 0, 10));
+        assert!(is_final(&BlockStatus::AcceptedOnL1, 100, 100, 10));
+        assert!(!is_final(&BlockStatus::Pending, 0, 100, 10));
+        assert!(!is_final(&BlockStatus::Rejected, 0, 100, 10));
+    }
+
+    #[test]
+    fn classes_are_only_served_from_the_block_they_were_seen_at() {
+        let latest = BlockId::Tag(BlockTag::Latest);
+        assert!(class_exists_at(None, &latest, None));
+        assert!(class_exists_at(Some(5), &latest, None));
+        assert!(class_exists_at(Some(5), &BlockId::Number(5), Some(5)));
+        assert!(class_exists_at(Some(5), &BlockId::Number(6), Some(6)));
+        assert!(!class_exists_at(Some(5), &BlockId::Number(4), Some(4)));
+        // Only seen at a tag, the block it was declared at is unknown.
+        assert!(!class_exists_at(None, &BlockId::Number(100), Some(100)));
+        // The number of a block hash is only known once the block is cached.
+        assert!(!class_exists_at(Some(5), &BlockId::Hash(Felt::ONE), None));
+    }
+
+    #[test]
+    fn raw_requests_are_cached_by_block_number_only() {
+        let named = serde_json::json!({ "block_id": { "block_number": 7 } });
+        assert_eq!(raw_block_number("starknet_getStateUpdate", &named), Some(7));
+        let positional = serde_json::json!([{ "block_number": 7 }]);
+        assert_eq!(raw_block_number("starknet_getBlockWithReceipts", &positional), Some(7));
+        assert_eq!(raw_block_number("starknet_getStateUpdate", &serde_json::json!({ "block_id": "latest" })), None);
+        assert_eq!(raw_block_number("starknet_getNonce", &named), None);
+    }
+}
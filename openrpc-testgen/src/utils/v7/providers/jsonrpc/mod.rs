@@ -1,5 +1,9 @@
 pub mod transports;
 use super::provider::{Provider, ProviderError, ProviderImplError};
+use crate::utils::class_verification::record_class_hash;
+use crate::utils::declared_classes::record_declared_class;
+use crate::utils::dry_run::{is_dry_run, stop_broadcast};
+use crate::utils::inclusion_latency::record_submission;
 use crate::utils::v8::types::{ContractStorageKeysItem, GetStorageProofParams, GetStorageProofResult};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use starknet_types_core::felt::Felt as FeltPrimitive;
@@ -21,7 +25,8 @@ use starknet_types_rpc::{
     BlockWithReceipts, GetBlockWithReceiptsParams,
 };
 use std::{any::Any, error::Error, fmt::Display};
-pub use transports::{HttpTransport, JsonRpcTransport};
+use tracing::info;
+pub use transports::{HttpClientConfig, HttpTransport, HttpTransportConfig, JsonRpcTransport};
 
 #[derive(Debug, Clone)]
 pub struct JsonRpcClient<T> {
@@ -174,10 +179,22 @@ struct Felt(pub FeltPrimitive);
 #[derive(Serialize, Deserialize)]
 struct FeltArray(pub Vec<FeltPrimitive>);
 
+/// Block fields of a `starknet_getTransactionReceipt` response, which [TxnReceipt] leaves out. Both
+/// are missing while the transaction is pending.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ReceiptBlockInfo {
+    pub block_hash: Option<FeltPrimitive>,
+    pub block_number: Option<u64>,
+}
+
 impl<T> JsonRpcClient<T> {
     pub fn new(transport: T) -> Self {
         Self { transport }
     }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
 }
 
 impl<T> JsonRpcClient<T>
@@ -191,12 +208,24 @@ where
     {
         match self.transport.send_request(method, params).await.map_err(JsonRpcClientError::Transport)? {
             JsonRpcResponse::Success { result, .. } => Ok(result),
-            JsonRpcResponse::Error { error, .. } => Err(match TryInto::<StarknetError>::try_into(&error) {
-                Ok(error) => ProviderError::StarknetError(error),
-                Err(_) => JsonRpcClientError::<T::Error>::JsonRpc(error).into(),
-            }),
+            JsonRpcResponse::Error { error, .. } => Err(Self::provider_error(error)),
+        }
+    }
+
+    fn provider_error(error: JsonRpcError) -> ProviderError {
+        match TryInto::<StarknetError>::try_into(&error) {
+            Ok(error) => ProviderError::StarknetError(error),
+            Err(_) => JsonRpcClientError::<T::Error>::JsonRpc(error).into(),
         }
     }
+
+    /// Block the receipt of `transaction_hash` is in, see [ReceiptBlockInfo].
+    pub async fn get_transaction_receipt_block_info(
+        &self,
+        transaction_hash: TxnHash<FeltPrimitive>,
+    ) -> Result<ReceiptBlockInfo, ProviderError> {
+        self.send_request(JsonRpcMethod::GetTransactionReceipt, GetTransactionReceiptParams { transaction_hash }).await
+    }
 }
 
 impl<T> Provider for JsonRpcClient<T>
@@ -312,6 +341,7 @@ where
         block_id: BlockId<FeltPrimitive>,
         class_hash: FeltPrimitive,
     ) -> Result<ContractClass<FeltPrimitive>, ProviderError> {
+        record_class_hash(class_hash);
         self.send_request(JsonRpcMethod::GetClass, GetClassParams { block_id, class_hash }).await
     }
 
@@ -321,10 +351,12 @@ where
         block_id: BlockId<FeltPrimitive>,
         contract_address: FeltPrimitive,
     ) -> Result<FeltPrimitive, ProviderError> {
-        Ok(self
+        let class_hash = self
             .send_request::<_, Felt>(JsonRpcMethod::GetClassHashAt, GetClassHashAtParams { block_id, contract_address })
             .await?
-            .0)
+            .0;
+        record_class_hash(class_hash);
+        Ok(class_hash)
     }
 
     /// Get the contract class definition in the given block at the given address
@@ -418,7 +450,15 @@ where
         &self,
         invoke_transaction: BroadcastedTxn<FeltPrimitive>,
     ) -> Result<AddInvokeTransactionResult<FeltPrimitive>, ProviderError> {
-        self.send_request(JsonRpcMethod::AddInvokeTransaction, AddInvokeTransactionParams { invoke_transaction }).await
+        if is_dry_run() {
+            return Err(stop_broadcast(self, invoke_transaction).await);
+        }
+        let result: AddInvokeTransactionResult<FeltPrimitive> = self
+            .send_request(JsonRpcMethod::AddInvokeTransaction, AddInvokeTransactionParams { invoke_transaction })
+            .await?;
+        record_submission(result.transaction_hash);
+        info!(transaction_hash = %format!("{:#x}", result.transaction_hash), "Invoke transaction submitted");
+        Ok(result)
     }
 
     /// Submit a new transaction to be added to the chain
@@ -426,8 +466,21 @@ where
         &self,
         declare_transaction: BroadcastedTxn<FeltPrimitive>,
     ) -> Result<ClassAndTxnHash<FeltPrimitive>, ProviderError> {
-        self.send_request(JsonRpcMethod::AddDeclareTransaction, AddDeclareTransactionParams { declare_transaction })
-            .await
+        if is_dry_run() {
+            return Err(stop_broadcast(self, declare_transaction).await);
+        }
+        let result: ClassAndTxnHash<FeltPrimitive> = self
+            .send_request(JsonRpcMethod::AddDeclareTransaction, AddDeclareTransactionParams { declare_transaction })
+            .await?;
+        record_submission(result.transaction_hash);
+        info!(
+            transaction_hash = %format!("{:#x}", result.transaction_hash),
+            class_hash = %format!("{:#x}", result.class_hash),
+            "Declare transaction submitted"
+        );
+        record_class_hash(result.class_hash);
+        record_declared_class(result.class_hash, result.transaction_hash);
+        Ok(result)
     }
 
     /// Submit a new deploy account transaction
@@ -435,11 +488,22 @@ where
         &self,
         deploy_account_transaction: BroadcastedTxn<FeltPrimitive>,
     ) -> Result<ContractAndTxnHash<FeltPrimitive>, ProviderError> {
-        self.send_request(
-            JsonRpcMethod::AddDeployAccountTransaction,
-            AddDeployAccountTransactionParams { deploy_account_transaction },
-        )
-        .await
+        if is_dry_run() {
+            return Err(stop_broadcast(self, deploy_account_transaction).await);
+        }
+        let result: ContractAndTxnHash<FeltPrimitive> = self
+            .send_request(
+                JsonRpcMethod::AddDeployAccountTransaction,
+                AddDeployAccountTransactionParams { deploy_account_transaction },
+            )
+            .await?;
+        record_submission(result.transaction_hash);
+        info!(
+            transaction_hash = %format!("{:#x}", result.transaction_hash),
+            contract_address = %format!("{:#x}", result.contract_address),
+            "Deploy account transaction submitted"
+        );
+        Ok(result)
     }
 
     /// For a given executed transaction, return the trace of its execution, including internal
@@ -483,6 +547,13 @@ where
         .await
     }
 
+    async fn send_raw(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value, ProviderError> {
+        match self.transport.send_raw_request(method, params).await.map_err(JsonRpcClientError::Transport)? {
+            JsonRpcResponse::Success { result, .. } => Ok(result),
+            JsonRpcResponse::Error { error, .. } => Err(Self::provider_error(error)),
+        }
+    }
+
     #[doc = " Same as [estimate_fee], but only with one estimate."]
     async fn estimate_fee_single(
         &self,
@@ -4,7 +4,7 @@ use auto_impl::auto_impl;
 use serde::{de::DeserializeOwned, Serialize};
 use std::error::Error;
 
-pub use http::HttpTransport;
+pub use http::{set_shared_client_config, shared_client, HttpClientConfig, HttpTransport, HttpTransportConfig};
 
 use crate::utils::v7::providers::jsonrpc::{JsonRpcMethod, JsonRpcResponse};
 
@@ -20,4 +20,11 @@ pub trait JsonRpcTransport {
     where
         P: Serialize + Send + Sync,
         R: DeserializeOwned;
+
+    /// Same as [JsonRpcTransport::send_request], for a method named at runtime.
+    fn send_raw_request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> impl std::future::Future<Output = Result<JsonRpcResponse<serde_json::Value>, Self::Error>> + Send;
 }
@@ -232,6 +232,15 @@ pub trait Provider {
         block_id: BlockId<Felt>,
     ) -> impl std::future::Future<Output = Result<Vec<TraceBlockTransactionsResult<Felt>>, ProviderError>>;
 
+    /// Sends `method` with `params` as is and returns the untyped result, for methods without typed
+    /// bindings, e.g. experimental or node-specific ones, or to look at a response before it is
+    /// deserialized. Errors are reported as for the typed methods.
+    fn send_raw(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> impl std::future::Future<Output = Result<serde_json::Value, ProviderError>>;
+
     /// Same as [simulate_transactions], but only with one simulation.
     fn simulate_transaction(
         &self,
@@ -0,0 +1,88 @@
+//! [RawSigner] backed by an asymmetric `ECC_SECG_P256K1` or `ECC_NIST_P256` key in AWS KMS, so
+//! that CI runs sign with a centrally managed key which never leaves the service.
+
+use aws_config::BehaviorVersion;
+use aws_sdk_kms::{
+    error::DisplayErrorContext,
+    primitives::Blob,
+    types::{KeySpec, MessageType, SigningAlgorithmSpec},
+    Client,
+};
+use crypto_utils::curve::secp::SecpPublicKey;
+use starknet_types_core::felt::Felt;
+
+use super::{
+    kms::{KmsCurve, KmsSignerError},
+    signer::RawSigner,
+};
+
+#[derive(Debug, Clone)]
+pub struct AwsKmsSigner {
+    client: Client,
+    key_id: String,
+    curve: KmsCurve,
+    public_key: SecpPublicKey,
+}
+
+impl AwsKmsSigner {
+    /// Signer for `key_id` (a key id, key ARN or alias) with the credentials and region resolved
+    /// from the environment the way the AWS CLI resolves them.
+    pub async fn from_env(key_id: impl Into<String>) -> Result<Self, KmsSignerError> {
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+        Self::new(Client::new(&config), key_id).await
+    }
+
+    /// Fetches the public key of `key_id`, which also tells the curve the key is on.
+    pub async fn new(client: Client, key_id: impl Into<String>) -> Result<Self, KmsSignerError> {
+        let key_id = key_id.into();
+        let output = client
+            .get_public_key()
+            .key_id(&key_id)
+            .send()
+            .await
+            .map_err(|e| KmsSignerError::Service(DisplayErrorContext(e).to_string()))?;
+
+        let curve = match output.key_spec() {
+            Some(KeySpec::EccSecgP256K1) => KmsCurve::Secp256k1,
+            Some(KeySpec::EccNistP256) => KmsCurve::Secp256r1,
+            spec => return Err(KmsSignerError::UnsupportedKey { key: key_id, spec: format!("{spec:?}") }),
+        };
+        let public_key = output.public_key().ok_or(KmsSignerError::MissingField("public key"))?;
+        let public_key = curve.public_key_from_der(public_key.as_ref())?;
+
+        Ok(Self { client, key_id, curve, public_key })
+    }
+
+    pub fn curve(&self) -> KmsCurve {
+        self.curve
+    }
+
+    pub fn public_key(&self) -> SecpPublicKey {
+        self.public_key
+    }
+}
+
+impl RawSigner for AwsKmsSigner {
+    type SignError = KmsSignerError;
+
+    fn public_key_felts(&self) -> Vec<Felt> {
+        self.public_key.to_felts()
+    }
+
+    async fn sign_hash_raw(&self, hash: &Felt) -> Result<Vec<Felt>, Self::SignError> {
+        // The transaction hash is signed as is, like the local secp wallets sign it.
+        let output = self
+            .client
+            .sign()
+            .key_id(&self.key_id)
+            .message(Blob::new(hash.to_bytes_be().to_vec()))
+            .message_type(MessageType::Digest)
+            .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256)
+            .send()
+            .await
+            .map_err(|e| KmsSignerError::Service(DisplayErrorContext(e).to_string()))?;
+
+        let signature = output.signature().ok_or(KmsSignerError::MissingField("signature"))?;
+        Ok(self.curve.signature_felts(&self.public_key, hash, signature.as_ref())?)
+    }
+}
@@ -0,0 +1,231 @@
+//! [RawSigner] backed by an asymmetric `EC_SIGN_SECP256K1_SHA256` or `EC_SIGN_P256_SHA256` key
+//! version in GCP Cloud KMS, talking to the Cloud KMS REST API. Access tokens come from an
+//! [AccessTokenProvider], and are refreshed when they expire or Cloud KMS rejects them.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use crypto_utils::curve::secp::SecpPublicKey;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use starknet_types_core::felt::Felt;
+use tokio::{process::Command, sync::Mutex};
+
+use super::{
+    kms::{KmsCurve, KmsSignerError},
+    signer::RawSigner,
+};
+use crate::utils::{secrets::register_secret, v7::providers::jsonrpc::transports::shared_client};
+
+const CLOUD_KMS_URL: &str = "https://cloudkms.googleapis.com/v1";
+const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+/// Tokens are refreshed this long before they expire, so that none expires during a request.
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Deserialize)]
+struct PublicKeyResponse {
+    pem: String,
+    algorithm: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsymmetricSignResponse {
+    signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// OAuth 2.0 access token allowed to use the key.
+#[derive(Debug, Clone)]
+pub struct AccessToken {
+    pub token: String,
+    /// When the token expires, if known. Tokens without an expiry are refreshed when Cloud KMS
+    /// rejects them.
+    pub expires_at: Option<Instant>,
+}
+
+/// Source of the access tokens of a [GcpKmsSigner], asked for a new one whenever the current one
+/// expires or is rejected, as tokens expire after an hour by default.
+pub trait AccessTokenProvider {
+    fn access_token(&self) -> impl Future<Output = Result<AccessToken, KmsSignerError>> + Send;
+}
+
+/// A fixed token, e.g. one passed on the command line, which can't be refreshed.
+#[derive(Debug, Clone)]
+pub struct StaticAccessToken(pub String);
+
+impl AccessTokenProvider for StaticAccessToken {
+    async fn access_token(&self) -> Result<AccessToken, KmsSignerError> {
+        Ok(AccessToken { token: self.0.clone(), expires_at: None })
+    }
+}
+
+/// Runs a shell command printing a token, e.g. `gcloud auth print-access-token`.
+#[derive(Debug, Clone)]
+pub struct CommandAccessToken(pub String);
+
+impl AccessTokenProvider for CommandAccessToken {
+    async fn access_token(&self) -> Result<AccessToken, KmsSignerError> {
+        let output = Command::new("sh")
+            .args(["-c", &self.0])
+            .output()
+            .await
+            .map_err(|e| KmsSignerError::AccessToken(format!("{}: {e}", self.0)))?;
+        if !output.status.success() {
+            let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(KmsSignerError::AccessToken(format!("{}: {reason}", self.0)));
+        }
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(AccessToken { token, expires_at: None })
+    }
+}
+
+/// Token of the service account of the GCE instance or GKE pod the run is on, from the metadata
+/// server.
+#[derive(Debug, Clone, Default)]
+pub struct MetadataServerAccessToken;
+
+impl AccessTokenProvider for MetadataServerAccessToken {
+    async fn access_token(&self) -> Result<AccessToken, KmsSignerError> {
+        let response = shared_client()
+            .get(METADATA_TOKEN_URL)
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await
+            .map_err(|e| KmsSignerError::AccessToken(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(KmsSignerError::AccessToken(format!("metadata server answered {}", response.status())));
+        }
+        let response: MetadataTokenResponse =
+            response.json().await.map_err(|e| KmsSignerError::AccessToken(e.to_string()))?;
+        let expires_at = Instant::now() + Duration::from_secs(response.expires_in);
+        Ok(AccessToken { token: response.access_token, expires_at: Some(expires_at) })
+    }
+}
+
+/// Access token of the signer, shared by its clones and refreshed by the first of them to need it.
+#[derive(Debug)]
+struct CachedToken<P> {
+    provider: P,
+    token: Mutex<Option<AccessToken>>,
+}
+
+impl<P: AccessTokenProvider> CachedToken<P> {
+    async fn get(&self, refresh: bool) -> Result<String, KmsSignerError> {
+        let mut token = self.token.lock().await;
+        let expired = |token: &AccessToken| {
+            token.expires_at.is_some_and(|expires_at| expires_at <= Instant::now() + TOKEN_EXPIRY_MARGIN)
+        };
+        match token.as_ref() {
+            Some(current) if !refresh && !expired(current) => Ok(current.token.clone()),
+            _ => {
+                let fresh = self.provider.access_token().await?;
+                register_secret(&fresh.token);
+                Ok(token.insert(fresh).token.clone())
+            }
+        }
+    }
+
+    /// Sends the request built with the current token, once more with a fresh token if it is
+    /// rejected.
+    async fn send<T: DeserializeOwned>(&self, request: impl Fn(&str) -> RequestBuilder) -> Result<T, KmsSignerError> {
+        let response = send(request(&self.get(false).await?)).await;
+        match response {
+            Err(KmsSignerError::Unauthorized(_)) => send(request(&self.get(true).await?)).await,
+            response => response,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GcpKmsSigner<P = StaticAccessToken> {
+    client: Client,
+    key_version: String,
+    token: Arc<CachedToken<P>>,
+    curve: KmsCurve,
+    public_key: SecpPublicKey,
+}
+
+impl<P: AccessTokenProvider> GcpKmsSigner<P> {
+    /// Signer for `key_version`, the resource name
+    /// `projects/*/locations/*/keyRings/*/cryptoKeys/*/cryptoKeyVersions/*`, authenticating with the
+    /// tokens of `token_provider`.
+    pub async fn new(key_version: impl Into<String>, token_provider: P) -> Result<Self, KmsSignerError> {
+        let key_version = key_version.into();
+        let token = Arc::new(CachedToken { provider: token_provider, token: Mutex::new(None) });
+        let client = shared_client();
+
+        let url = format!("{CLOUD_KMS_URL}/{key_version}/publicKey");
+        let response: PublicKeyResponse = token.send(|access_token| client.get(&url).bearer_auth(access_token)).await?;
+        let curve = match response.algorithm.as_str() {
+            "EC_SIGN_SECP256K1_SHA256" => KmsCurve::Secp256k1,
+            "EC_SIGN_P256_SHA256" => KmsCurve::Secp256r1,
+            _ => return Err(KmsSignerError::UnsupportedKey { key: key_version, spec: response.algorithm }),
+        };
+        let public_key = curve.public_key_from_der(&pem_to_der(&response.pem)?)?;
+
+        Ok(Self { client, key_version, token, curve, public_key })
+    }
+
+    pub fn curve(&self) -> KmsCurve {
+        self.curve
+    }
+
+    pub fn public_key(&self) -> SecpPublicKey {
+        self.public_key
+    }
+}
+
+impl<P> RawSigner for GcpKmsSigner<P>
+where
+    P: AccessTokenProvider + Send + Sync,
+{
+    type SignError = KmsSignerError;
+
+    fn public_key_felts(&self) -> Vec<Felt> {
+        self.public_key.to_felts()
+    }
+
+    async fn sign_hash_raw(&self, hash: &Felt) -> Result<Vec<Felt>, Self::SignError> {
+        // Passed as the SHA-256 digest so the transaction hash is signed as is, like the local secp
+        // wallets sign it.
+        let url = format!("{CLOUD_KMS_URL}/{}:asymmetricSign", self.key_version);
+        let body = json!({ "digest": { "sha256": STANDARD.encode(hash.to_bytes_be()) } });
+        let response: AsymmetricSignResponse =
+            self.token.send(|access_token| self.client.post(&url).bearer_auth(access_token).json(&body)).await?;
+
+        let signature = decode_base64(&response.signature)?;
+        Ok(self.curve.signature_felts(&self.public_key, hash, &signature)?)
+    }
+}
+
+async fn send<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, KmsSignerError> {
+    let response = request.send().await.map_err(|e| KmsSignerError::Service(e.to_string()))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(match status {
+            StatusCode::UNAUTHORIZED => KmsSignerError::Unauthorized(body),
+            _ => KmsSignerError::Service(format!("{status}: {body}")),
+        });
+    }
+    response.json().await.map_err(|e| KmsSignerError::Service(e.to_string()))
+}
+
+/// DER contents of a PEM block, i.e. the base64 between the `-----BEGIN`/`-----END` lines.
+fn pem_to_der(pem: &str) -> Result<Vec<u8>, KmsSignerError> {
+    let base64: String = pem.lines().filter(|line| !line.starts_with("-----")).map(str::trim).collect();
+    decode_base64(&base64)
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, KmsSignerError> {
+    STANDARD.decode(value).map_err(|e| KmsSignerError::Service(format!("Invalid base64 in response: {e}")))
+}
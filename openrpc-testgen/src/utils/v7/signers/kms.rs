@@ -0,0 +1,51 @@
+//! Pieces shared by the signers backed by cloud key management services. Neither AWS KMS nor GCP
+//! Cloud KMS offers the Stark curve, so their keys sign for accounts validating secp256k1 or
+//! secp256r1 signatures through [RawSigner](super::signer::RawSigner), with the public key and
+//! signature in the same layout as [Secp256k1Wallet](super::secp_wallet::Secp256k1Wallet) and
+//! [Secp256r1Wallet](super::secp_wallet::Secp256r1Wallet).
+
+use crypto_utils::curve::secp::{
+    secp256k1_public_key_from_der, secp256k1_signature_from_der, secp256r1_public_key_from_der,
+    secp256r1_signature_from_der, SecpError, SecpPublicKey,
+};
+use starknet_types_core::felt::Felt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KmsCurve {
+    Secp256k1,
+    Secp256r1,
+}
+
+impl KmsCurve {
+    /// Public key of a DER encoded `SubjectPublicKeyInfo`, as both services export them.
+    pub fn public_key_from_der(self, spki: &[u8]) -> Result<SecpPublicKey, SecpError> {
+        match self {
+            KmsCurve::Secp256k1 => secp256k1_public_key_from_der(spki),
+            KmsCurve::Secp256r1 => secp256r1_public_key_from_der(spki),
+        }
+    }
+
+    /// `signature` field of a transaction with hash `hash`, from the DER signature of the service.
+    pub fn signature_felts(self, public_key: &SecpPublicKey, hash: &Felt, der: &[u8]) -> Result<Vec<Felt>, SecpError> {
+        match self {
+            KmsCurve::Secp256k1 => Ok(secp256k1_signature_from_der(public_key, hash, der)?.to_felts(true)),
+            KmsCurve::Secp256r1 => Ok(secp256r1_signature_from_der(public_key, hash, der)?.to_felts(false)),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KmsSignerError {
+    #[error("Key management service request failed: {0}")]
+    Service(String),
+    #[error("Key management service rejected the credentials: {0}")]
+    Unauthorized(String),
+    #[error("Could not get an access token: {0}")]
+    AccessToken(String),
+    #[error("Key {key} is a {spec} key, only secp256k1 and secp256r1 ECDSA keys can sign transactions")]
+    UnsupportedKey { key: String, spec: String },
+    #[error("Key management service response has no {0}")]
+    MissingField(&'static str),
+    #[error(transparent)]
+    Secp(#[from] SecpError),
+}
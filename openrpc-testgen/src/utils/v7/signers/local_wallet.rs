@@ -1,6 +1,6 @@
 use super::{
     key_pair::{SigningKey, VerifyingKey},
-    signer::Signer,
+    signer::{RawSigner, Signer},
 };
 // use starknet_types_core::curve::{EcdsaSignError, Signature};
 use crypto_utils::curve::signer::{EcdsaSignError, Signature};
@@ -52,6 +52,19 @@ impl Signer for LocalWallet {
     }
 }
 
+impl RawSigner for LocalWallet {
+    type SignError = SignError;
+
+    fn public_key_felts(&self) -> Vec<Felt> {
+        vec![self.private_key.verifying_key().scalar()]
+    }
+
+    async fn sign_hash_raw(&self, hash: &Felt) -> Result<Vec<Felt>, Self::SignError> {
+        let signature = self.private_key.sign(hash)?;
+        Ok(vec![signature.r, signature.s])
+    }
+}
+
 impl From<SigningKey> for LocalWallet {
     fn from(value: SigningKey) -> Self {
         Self { private_key: value }
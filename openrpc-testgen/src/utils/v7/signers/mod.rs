@@ -1,3 +1,10 @@
+#[cfg(feature = "aws_kms")]
+pub mod aws_kms;
+#[cfg(feature = "gcp_kms")]
+pub mod gcp_kms;
 pub mod key_pair;
+#[cfg(any(feature = "aws_kms", feature = "gcp_kms"))]
+pub mod kms;
 pub mod local_wallet;
+pub mod secp_wallet;
 pub mod signer;
@@ -0,0 +1,90 @@
+use super::signer::RawSigner;
+use crypto_utils::curve::secp::{
+    secp256k1_get_public_key, secp256k1_sign, secp256r1_get_public_key, secp256r1_sign, SecpError, SecpPublicKey,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use starknet_types_core::felt::Felt;
+
+/// Ethereum-style signer for accounts validating secp256k1 signatures. The signature is
+/// `[r.low, r.high, s.low, s.high, y_parity]`.
+#[derive(Debug, Clone)]
+pub struct Secp256k1Wallet {
+    private_key: [u8; 32],
+    public_key: SecpPublicKey,
+}
+
+/// Signer for accounts validating secp256r1 signatures, such as hardware signers of Braavos
+/// accounts. The signature is `[r.low, r.high, s.low, s.high]`.
+#[derive(Debug, Clone)]
+pub struct Secp256r1Wallet {
+    private_key: [u8; 32],
+    public_key: SecpPublicKey,
+}
+
+impl Secp256k1Wallet {
+    pub fn from_private_key(private_key: [u8; 32]) -> Result<Self, SecpError> {
+        Ok(Self { private_key, public_key: secp256k1_get_public_key(&private_key)? })
+    }
+
+    pub fn from_random() -> Self {
+        loop {
+            if let Ok(wallet) = Self::from_private_key(random_private_key()) {
+                return wallet;
+            }
+        }
+    }
+
+    pub fn public_key(&self) -> SecpPublicKey {
+        self.public_key
+    }
+}
+
+impl Secp256r1Wallet {
+    pub fn from_private_key(private_key: [u8; 32]) -> Result<Self, SecpError> {
+        Ok(Self { private_key, public_key: secp256r1_get_public_key(&private_key)? })
+    }
+
+    pub fn from_random() -> Self {
+        loop {
+            if let Ok(wallet) = Self::from_private_key(random_private_key()) {
+                return wallet;
+            }
+        }
+    }
+
+    pub fn public_key(&self) -> SecpPublicKey {
+        self.public_key
+    }
+}
+
+impl RawSigner for Secp256k1Wallet {
+    type SignError = SecpError;
+
+    fn public_key_felts(&self) -> Vec<Felt> {
+        self.public_key.to_felts()
+    }
+
+    async fn sign_hash_raw(&self, hash: &Felt) -> Result<Vec<Felt>, Self::SignError> {
+        Ok(secp256k1_sign(&self.private_key, hash)?.to_felts(true))
+    }
+}
+
+impl RawSigner for Secp256r1Wallet {
+    type SignError = SecpError;
+
+    fn public_key_felts(&self) -> Vec<Felt> {
+        self.public_key.to_felts()
+    }
+
+    async fn sign_hash_raw(&self, hash: &Felt) -> Result<Vec<Felt>, Self::SignError> {
+        Ok(secp256r1_sign(&self.private_key, hash)?.to_felts(false))
+    }
+}
+
+/// Random 32 bytes; out of range keys (probability ~2^-128) are rejected by the caller.
+fn random_private_key() -> [u8; 32] {
+    let mut rng = StdRng::from_entropy();
+    let mut private_key = [0u8; 32];
+    rng.fill(&mut private_key);
+    private_key
+}
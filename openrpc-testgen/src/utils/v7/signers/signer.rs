@@ -26,3 +26,19 @@ pub trait Signer {
     /// accurate results to minimize signing requests.
     fn is_interactive(&self) -> bool;
 }
+
+/// Signer for account contracts whose signature and public key are not a single Stark `[r, s]`
+/// pair and scalar, e.g. accounts validating secp256k1 or secp256r1 signatures.
+#[auto_impl(&, Box, Arc)]
+pub trait RawSigner {
+    type SignError: Error + Send + Sync;
+
+    /// Public key as passed to the account contract constructor.
+    fn public_key_felts(&self) -> Vec<Felt>;
+
+    /// Signature of the hash as passed in the transaction's `signature` field.
+    fn sign_hash_raw(
+        &self,
+        hash: &Felt,
+    ) -> impl std::future::Future<Output = Result<Vec<Felt>, Self::SignError>> + Send;
+}
@@ -0,0 +1,177 @@
+//! Cross-version replays: the same logical call is executed through an invoke v1 and an invoke v3,
+//! each against a fresh instance of the target contract, so that the effects on both instances can
+//! be compared. Apart from the token the fee is paid in, nodes must treat both versions alike.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{Anonymous, TransactionTrace, TxnReceipt};
+
+use super::v7::{
+    accounts::{
+        account::{Account, ConnectedAccount},
+        call::Call,
+        single_owner::SingleOwnerAccount,
+    },
+    contract::factory::ContractFactory,
+    endpoints::{errors::OpenRpcTestGenError, utils::wait_for_sent_transaction},
+    providers::{
+        jsonrpc::{HttpTransport, JsonRpcClient},
+        provider::Provider,
+    },
+    signers::local_wallet::LocalWallet,
+};
+
+type HiveAccount = SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InvokeVersion {
+    V1,
+    V3,
+}
+
+/// Effects of a transaction on the contract it targets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContractEffects {
+    pub succeeded: bool,
+    /// Storage values written to the contract, by key.
+    pub storage: BTreeMap<Felt, Felt>,
+    /// Keys and data of the events emitted by the contract, in emission order.
+    pub events: Vec<(Vec<Felt>, Vec<Felt>)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionReplay {
+    pub v1: ContractEffects,
+    pub v3: ContractEffects,
+}
+
+impl VersionReplay {
+    /// Descriptions of the effects that differ between both versions, empty when they match.
+    pub fn differences(&self) -> Vec<String> {
+        let mut differences = Vec::new();
+        if self.v1.succeeded != self.v3.succeeded {
+            differences.push(format!("succeeded: v1 {}, v3 {}", self.v1.succeeded, self.v3.succeeded));
+        }
+        if self.v1.storage != self.v3.storage {
+            differences.push(format!("storage diff: v1 {:?}, v3 {:?}", self.v1.storage, self.v3.storage));
+        }
+        if self.v1.events != self.v3.events {
+            differences.push(format!("events: v1 {:?}, v3 {:?}", self.v1.events, self.v3.events));
+        }
+        differences
+    }
+}
+
+/// Deploys two instances of `class_hash`, invokes the call built by `make_call` for the first one
+/// through an invoke v1 and for the second one through an invoke v3, and collects the effects of
+/// both transactions on their instance.
+pub async fn replay_v1_v3(
+    account: &HiveAccount,
+    class_hash: Felt,
+    constructor_calldata: Vec<Felt>,
+    make_call: impl Fn(Felt) -> Result<Call, OpenRpcTestGenError>,
+) -> Result<VersionReplay, OpenRpcTestGenError> {
+    let v1_instance = deploy_fresh_instance(account, class_hash, constructor_calldata.clone()).await?;
+    let v3_instance = deploy_fresh_instance(account, class_hash, constructor_calldata).await?;
+
+    Ok(VersionReplay {
+        v1: invoke_and_collect(account, InvokeVersion::V1, make_call(v1_instance)?).await?,
+        v3: invoke_and_collect(account, InvokeVersion::V3, make_call(v3_instance)?).await?,
+    })
+}
+
+pub async fn deploy_fresh_instance(
+    account: &HiveAccount,
+    class_hash: Felt,
+    constructor_calldata: Vec<Felt>,
+) -> Result<Felt, OpenRpcTestGenError> {
+    let mut salt_buffer = [0u8; 32];
+    StdRng::from_entropy().fill_bytes(&mut salt_buffer[1..]);
+
+    let factory = ContractFactory::new(class_hash, account.clone());
+    let deployment = factory.deploy_v3(constructor_calldata, Felt::from_bytes_be(&salt_buffer), true);
+    let result = deployment.send().await?;
+    wait_for_sent_transaction(result.transaction_hash, account).await?;
+
+    Ok(deployment.deployed_address())
+}
+
+/// Sends `call` with the given transaction version and collects its effects on `call.to`, from the
+/// receipt's events and the state diff of the transaction trace.
+pub async fn invoke_and_collect(
+    account: &HiveAccount,
+    version: InvokeVersion,
+    call: Call,
+) -> Result<ContractEffects, OpenRpcTestGenError> {
+    let target = call.to;
+    let transaction_hash = match version {
+        InvokeVersion::V1 => account.execute_v1(vec![call]).send().await?.transaction_hash,
+        InvokeVersion::V3 => account.execute_v3(vec![call]).send().await?.transaction_hash,
+    };
+
+    let provider = account.provider();
+    let receipt = match wait_for_receipt(account, transaction_hash).await? {
+        TxnReceipt::Invoke(receipt) => receipt.common_receipt_properties,
+        _ => return Err(OpenRpcTestGenError::Other("Expected an invoke transaction receipt".to_string())),
+    };
+    let events = receipt
+        .events
+        .iter()
+        .filter(|event| event.from_address == target)
+        .map(|event| (event.keys.clone(), event.data.clone()))
+        .collect();
+
+    let state_diff = match provider.trace_transaction(transaction_hash).await? {
+        TransactionTrace::Invoke(trace) => trace.state_diff,
+        _ => return Err(OpenRpcTestGenError::Other("Expected an invoke transaction trace".to_string())),
+    };
+    let storage = state_diff
+        .iter()
+        .flat_map(|state_diff| state_diff.storage_diffs.iter())
+        .filter(|diff| diff.address == target)
+        .flat_map(|diff| diff.storage_entries.iter())
+        .filter_map(|entry| Some((entry.key?, entry.value?)))
+        .collect();
+
+    Ok(ContractEffects { succeeded: matches!(receipt.anon, Anonymous::Successful(_)), storage, events })
+}
+
+/// Polls the receipt of `transaction_hash` until the node has one. Unlike
+/// [wait_for_sent_transaction], a reverted transaction is not an error, as its effects are compared
+/// too.
+async fn wait_for_receipt(
+    account: &HiveAccount,
+    transaction_hash: Felt,
+) -> Result<TxnReceipt<Felt>, OpenRpcTestGenError> {
+    let start_fetching = Instant::now();
+    loop {
+        match account.provider().get_transaction_receipt(transaction_hash).await {
+            Ok(receipt) => return Ok(receipt),
+            Err(_) if start_fetching.elapsed() < Duration::from_secs(60) => {
+                tokio::time::sleep(Duration::from_secs(2)).await;
+            }
+            Err(e) => {
+                return Err(OpenRpcTestGenError::Timeout(format!(
+                    "No receipt for transaction {transaction_hash:#x} in 60 seconds: {e}"
+                )))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_revert_of_one_version_is_a_difference() {
+        let reverted = ContractEffects::default();
+        let succeeded = ContractEffects { succeeded: true, ..Default::default() };
+        let replay = VersionReplay { v1: succeeded.clone(), v3: reverted };
+        assert_eq!(replay.differences(), vec!["succeeded: v1 true, v3 false".to_string()]);
+        assert!(VersionReplay { v1: succeeded.clone(), v3: succeeded }.differences().is_empty());
+    }
+}
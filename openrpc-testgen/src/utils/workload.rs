@@ -0,0 +1,398 @@
+//! Deterministic synthetic workloads: a seeded stream of transfers, deploys, declares and heavy
+//! computations mixed at configurable ratios. Soak mode sends a workload to a node, and it can be
+//! signed offline into a transaction file for t8n. A workload serializes to JSON with every
+//! transaction it generated, so that a run can be replayed with exactly the same transactions,
+//! regardless of the random number generator of the build that replays it.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use starknet_types_core::{
+    felt::Felt,
+    hash::{Poseidon, StarkHash},
+};
+use starknet_types_rpc::v0_7_1::{BroadcastedDeclareTxn, BroadcastedTxn, ContractClass};
+use thiserror::Error;
+
+use super::secrets::write_redacted;
+use super::v7::{
+    accounts::{account::Account, call::Call, errors::NotPreparedError, single_owner::SingleOwnerAccount},
+    endpoints::{
+        errors::{NonAsciiNameError, OpenRpcTestGenError},
+        utils::get_selector_from_name,
+    },
+    providers::jsonrpc::{HttpTransport, JsonRpcClient},
+    signers::local_wallet::LocalWallet,
+};
+
+type HiveAccount = SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>;
+
+/// Largest amount of a transfer, in fri.
+const MAX_TRANSFER_AMOUNT: u64 = 1000;
+/// Largest argument passed to the heavy computation entry point.
+const MAX_HEAVY_COMPUTATION_ITERATIONS: u32 = 64;
+
+#[derive(Debug, Error)]
+pub enum WorkloadError {
+    #[error("Could not access workload {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Invalid workload: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("The workload has {0} transactions, but no target is configured for them")]
+    MissingTarget(WorkloadKind),
+    #[error(transparent)]
+    Selector(#[from] NonAsciiNameError),
+    #[error(transparent)]
+    NotPrepared(#[from] NotPreparedError),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WorkloadKind {
+    Transfer,
+    Deploy,
+    Declare,
+    HeavyComputation,
+}
+
+impl Display for WorkloadKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            WorkloadKind::Transfer => write!(f, "transfer"),
+            WorkloadKind::Deploy => write!(f, "deploy"),
+            WorkloadKind::Declare => write!(f, "declare"),
+            WorkloadKind::HeavyComputation => write!(f, "heavy-computation"),
+        }
+    }
+}
+
+impl FromStr for WorkloadKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "transfer" => Ok(WorkloadKind::Transfer),
+            "deploy" => Ok(WorkloadKind::Deploy),
+            "declare" => Ok(WorkloadKind::Declare),
+            "heavy-computation" => Ok(WorkloadKind::HeavyComputation),
+            _ => {
+                Err(format!("Unknown transaction kind {s:?}, expected transfer, deploy, declare or heavy-computation"))
+            }
+        }
+    }
+}
+
+/// Relative weights of the kinds of transactions, parsed from `KIND=WEIGHT[,KIND=WEIGHT...]`, e.g.
+/// `transfer=70,deploy=20,heavy-computation=10`. Kinds left out have a weight of zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkloadMix {
+    pub transfer: u32,
+    pub deploy: u32,
+    pub declare: u32,
+    pub heavy_computation: u32,
+}
+
+impl Default for WorkloadMix {
+    fn default() -> Self {
+        Self { transfer: 1, deploy: 0, declare: 0, heavy_computation: 0 }
+    }
+}
+
+impl WorkloadMix {
+    fn weights(&self) -> [(WorkloadKind, u32); 4] {
+        [
+            (WorkloadKind::Transfer, self.transfer),
+            (WorkloadKind::Deploy, self.deploy),
+            (WorkloadKind::Declare, self.declare),
+            (WorkloadKind::HeavyComputation, self.heavy_computation),
+        ]
+    }
+}
+
+impl FromStr for WorkloadMix {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mix = Self { transfer: 0, deploy: 0, declare: 0, heavy_computation: 0 };
+        for entry in s.split(',').filter(|entry| !entry.is_empty()) {
+            let (kind, weight) = entry.split_once('=').ok_or_else(|| format!("Expected KIND=WEIGHT, got {entry:?}"))?;
+            let weight = weight.parse().map_err(|e| format!("Invalid weight of {kind}: {e}"))?;
+            match kind.parse()? {
+                WorkloadKind::Transfer => mix.transfer = weight,
+                WorkloadKind::Deploy => mix.deploy = weight,
+                WorkloadKind::Declare => mix.declare = weight,
+                WorkloadKind::HeavyComputation => mix.heavy_computation = weight,
+            }
+        }
+        if mix.weights().iter().all(|(_, weight)| *weight == 0) {
+            return Err(format!("Mix {s:?} has no transaction kind with a positive weight"));
+        }
+        Ok(mix)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkloadConfig {
+    pub seed: u64,
+    /// Number of transactions generated.
+    pub length: usize,
+    pub mix: WorkloadMix,
+    /// Classes available to declare transactions. A class can only be declared once, so once they
+    /// are all used up the declares of the mix are replaced by the other kinds.
+    pub declarable_classes: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum WorkloadTransaction {
+    /// STRK transfer to the sender itself, which leaves its balance unchanged but for the fee.
+    Transfer { amount: u64 },
+    /// Deployment of [WorkloadTargets::deploy_class_hash] through the UDC.
+    Deploy { salt: Felt },
+    /// Declaration of the class at `class_index` of [WorkloadTargets::declare_classes].
+    Declare { class_index: usize },
+    /// Call to [WorkloadTargets::heavy_computation] with `iterations` as its only argument.
+    HeavyComputation { iterations: u32 },
+}
+
+impl WorkloadTransaction {
+    pub fn kind(&self) -> WorkloadKind {
+        match self {
+            WorkloadTransaction::Transfer { .. } => WorkloadKind::Transfer,
+            WorkloadTransaction::Deploy { .. } => WorkloadKind::Deploy,
+            WorkloadTransaction::Declare { .. } => WorkloadKind::Declare,
+            WorkloadTransaction::HeavyComputation { .. } => WorkloadKind::HeavyComputation,
+        }
+    }
+
+    /// The transaction as sent on `lap` of a workload sent in a loop, `None` if it can't be sent
+    /// again. Deployments get a salt of their own on every lap after the first, as the address of
+    /// the first is taken, and a class is declared on the first lap only.
+    pub fn for_lap(&self, lap: u64) -> Option<Self> {
+        match self {
+            _ if lap == 0 => Some(*self),
+            WorkloadTransaction::Deploy { salt } => {
+                Some(WorkloadTransaction::Deploy { salt: Poseidon::hash(salt, &Felt::from(lap)) })
+            }
+            WorkloadTransaction::Declare { .. } => None,
+            _ => Some(*self),
+        }
+    }
+
+    /// Calls of the transaction when sent by `sender`, none for declarations.
+    pub fn calls(&self, sender: Felt, targets: &WorkloadTargets) -> Result<Vec<Call>, WorkloadError> {
+        let call = match self {
+            WorkloadTransaction::Transfer { amount } => Call {
+                to: targets.strk_address,
+                selector: get_selector_from_name("transfer")?,
+                calldata: vec![sender, Felt::from(*amount), Felt::ZERO],
+            },
+            WorkloadTransaction::Deploy { salt } => {
+                let class_hash = targets.deploy_class_hash.ok_or(WorkloadError::MissingTarget(self.kind()))?;
+                Call {
+                    to: targets.udc_address,
+                    selector: get_selector_from_name("deployContract")?,
+                    // Not unique and without constructor calldata
+                    calldata: vec![class_hash, *salt, Felt::ZERO, Felt::ZERO],
+                }
+            }
+            WorkloadTransaction::Declare { .. } => return Ok(vec![]),
+            WorkloadTransaction::HeavyComputation { iterations } => {
+                let (to, selector) = targets.heavy_computation.ok_or(WorkloadError::MissingTarget(self.kind()))?;
+                Call { to, selector, calldata: vec![Felt::from(*iterations)] }
+            }
+        };
+        Ok(vec![call])
+    }
+
+    /// Sends the transaction from `account` with estimated fees, returning its hash.
+    pub async fn send(&self, account: &HiveAccount, targets: &WorkloadTargets) -> Result<Felt, OpenRpcTestGenError> {
+        let transaction_hash = match self {
+            WorkloadTransaction::Declare { class_index } => {
+                let (contract_class, compiled_class_hash) = targets.declare_class(*class_index)?;
+                account.declare_v3(contract_class.clone(), *compiled_class_hash).send().await?.transaction_hash
+            }
+            _ => account.execute_v3(self.calls(account.address(), targets)?).send().await?.transaction_hash,
+        };
+        Ok(transaction_hash)
+    }
+}
+
+/// Contracts and classes the transactions of a workload use. Only the targets of the kinds in the
+/// mix are needed.
+#[derive(Debug, Clone)]
+pub struct WorkloadTargets {
+    pub strk_address: Felt,
+    pub udc_address: Felt,
+    /// Class deployed by deploy transactions, whose constructor takes no arguments.
+    pub deploy_class_hash: Option<Felt>,
+    /// Classes declared by declare transactions, with their compiled class hashes.
+    pub declare_classes: Vec<(ContractClass<Felt>, Felt)>,
+    /// Contract address and entry point selector of heavy computations, e.g. `write_storage` of the
+    /// ResourceGenerator contract.
+    pub heavy_computation: Option<(Felt, Felt)>,
+}
+
+impl WorkloadTargets {
+    pub fn new(strk_address: Felt, udc_address: Felt) -> Self {
+        Self { strk_address, udc_address, deploy_class_hash: None, declare_classes: vec![], heavy_computation: None }
+    }
+
+    fn declare_class(&self, class_index: usize) -> Result<&(ContractClass<Felt>, Felt), WorkloadError> {
+        self.declare_classes.get(class_index).ok_or(WorkloadError::MissingTarget(WorkloadKind::Declare))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workload {
+    pub config: WorkloadConfig,
+    pub transactions: Vec<WorkloadTransaction>,
+}
+
+impl Workload {
+    pub fn generate(config: WorkloadConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let mut declared = 0;
+        let mut transactions = Vec::with_capacity(config.length);
+
+        for _ in 0..config.length {
+            let weights = config.mix.weights().map(|(kind, weight)| match kind {
+                WorkloadKind::Declare if declared >= config.declarable_classes => (kind, 0),
+                _ => (kind, weight),
+            });
+            let total: u32 = weights.iter().map(|(_, weight)| weight).sum();
+            // Transfers stand in for a mix of only declares whose classes are used up.
+            let mut pick = if total == 0 { 0 } else { rng.gen_range(0..total) };
+            let mut kind = WorkloadKind::Transfer;
+            for (candidate, weight) in weights {
+                if pick < weight {
+                    kind = candidate;
+                    break;
+                }
+                pick -= weight;
+            }
+
+            transactions.push(match kind {
+                WorkloadKind::Transfer => {
+                    WorkloadTransaction::Transfer { amount: rng.gen_range(1..=MAX_TRANSFER_AMOUNT) }
+                }
+                WorkloadKind::Deploy => WorkloadTransaction::Deploy { salt: Felt::from(rng.gen::<u128>()) },
+                WorkloadKind::Declare => {
+                    declared += 1;
+                    WorkloadTransaction::Declare { class_index: declared - 1 }
+                }
+                WorkloadKind::HeavyComputation => WorkloadTransaction::HeavyComputation {
+                    iterations: rng.gen_range(1..=MAX_HEAVY_COMPUTATION_ITERATIONS),
+                },
+            });
+        }
+
+        Self { config, transactions }
+    }
+
+    pub fn read(path: &Path) -> Result<Self, WorkloadError> {
+        let contents =
+            fs::read_to_string(path).map_err(|source| WorkloadError::Io { path: path.to_path_buf(), source })?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), WorkloadError> {
+        write_redacted(path, &(serde_json::to_string_pretty(self)? + "\n"))
+            .map_err(|source| WorkloadError::Io { path: path.to_path_buf(), source })
+    }
+
+    /// Signs every transaction of the workload from `account` without contacting a node, with
+    /// consecutive nonces from `nonce` and the same L1 gas bounds, as a transaction file for t8n.
+    pub async fn t8n_transactions(
+        &self,
+        account: &HiveAccount,
+        targets: &WorkloadTargets,
+        nonce: Felt,
+        gas: u64,
+        gas_price: u128,
+    ) -> Result<Vec<BroadcastedTxn<Felt>>, OpenRpcTestGenError> {
+        let mut transactions = Vec::with_capacity(self.transactions.len());
+        for (transaction, nonce) in self.transactions.iter().zip((0u64..).map(|offset| nonce + Felt::from(offset))) {
+            let broadcasted = match transaction {
+                WorkloadTransaction::Declare { class_index } => {
+                    let (contract_class, compiled_class_hash) = targets.declare_class(*class_index)?;
+                    let declaration = account
+                        .declare_v3(contract_class.clone(), *compiled_class_hash)
+                        .nonce(nonce)
+                        .gas(gas)
+                        .gas_price(gas_price);
+                    let declare =
+                        declaration.prepared().map_err(WorkloadError::from)?.get_declare_request(false, false).await?;
+                    BroadcastedTxn::Declare(BroadcastedDeclareTxn::V3(declare))
+                }
+                _ => {
+                    let calls = transaction.calls(account.address(), targets)?;
+                    let execution = account.execute_v3(calls).nonce(nonce).gas(gas).gas_price(gas_price);
+                    execution.prepared().map_err(WorkloadError::from)?.get_broadcasted_invoke(false, false).await?
+                }
+            };
+            transactions.push(broadcasted);
+        }
+        Ok(transactions)
+    }
+}
+
+/// Writes `transactions` as a t8n transaction file, executed by t8n in a single block.
+pub fn write_t8n_transactions(path: &Path, transactions: &[BroadcastedTxn<Felt>]) -> Result<(), WorkloadError> {
+    write_redacted(path, &(serde_json::to_string_pretty(transactions)? + "\n"))
+        .map_err(|source| WorkloadError::Io { path: path.to_path_buf(), source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_replayable_workloads() {
+        let mix: WorkloadMix = "transfer=2,declare=5,heavy-computation=3".parse().unwrap();
+        let config = WorkloadConfig { seed: 42, length: 64, mix, declarable_classes: 2 };
+        let workload = Workload::generate(config);
+
+        assert_eq!(workload, Workload::generate(config));
+        assert_ne!(workload.transactions, Workload::generate(WorkloadConfig { seed: 43, ..config }).transactions);
+        let declares: Vec<_> =
+            workload.transactions.iter().filter(|transaction| transaction.kind() == WorkloadKind::Declare).collect();
+        assert_eq!(
+            declares,
+            [&WorkloadTransaction::Declare { class_index: 0 }, &WorkloadTransaction::Declare { class_index: 1 }]
+        );
+        assert!(workload.transactions.iter().all(|transaction| transaction.kind() != WorkloadKind::Deploy));
+
+        let replayed: Workload = serde_json::from_str(&serde_json::to_string(&workload).unwrap()).unwrap();
+        assert_eq!(replayed, workload);
+
+        assert!("deploy=0".parse::<WorkloadMix>().is_err());
+        assert!("transfer=1,unknown=1".parse::<WorkloadMix>().is_err());
+    }
+
+    #[test]
+    fn resends_only_what_can_be_sent_again() {
+        let deploy = WorkloadTransaction::Deploy { salt: Felt::from(7u8) };
+        let declare = WorkloadTransaction::Declare { class_index: 0 };
+        let transfer = WorkloadTransaction::Transfer { amount: 5 };
+
+        assert_eq!(deploy.for_lap(0), Some(deploy));
+        let salts: Vec<_> = (1..4)
+            .map(|lap| match deploy.for_lap(lap) {
+                Some(WorkloadTransaction::Deploy { salt }) => salt,
+                other => panic!("Deploy became {other:?}"),
+            })
+            .collect();
+        assert!(!salts.contains(&Felt::from(7u8)));
+        assert!(salts[0] != salts[1] && salts[1] != salts[2] && salts[0] != salts[2]);
+
+        assert_eq!(declare.for_lap(0), Some(declare));
+        assert_eq!(declare.for_lap(1), None);
+        assert_eq!(transfer.for_lap(3), Some(transfer));
+    }
+}
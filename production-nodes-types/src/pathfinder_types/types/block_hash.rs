@@ -221,15 +221,15 @@ pub fn calculate_event_commitment(transaction_events: &Vec<(TransactionHash, Vec
 /// Calculate the hash of an event.
 /// [Reference code from StarkWare](https://github.com/starkware-libs/starknet-api/blob/5565e5282f5fead364a41e49c173940fd83dee00/src/block_hash/event_commitment.rs#L33).
 fn calculate_event_hash(event: &Event, transaction_hash: Felt) -> Felt {
-    let mut data = vec![event.from_address, transaction_hash, (event.keys.len() as u64).into()];
+    let mut hasher = PoseidonHasher::new();
+    hasher.update(event.from_address);
+    hasher.update(transaction_hash);
 
-    // Add each key to the vector
-    data.extend(event.keys.iter().copied());
+    hasher.update((event.keys.len() as u64).into());
+    hasher.update_many(&event.keys);
 
-    // Add the data length and each data element to the vector
-    data.push((event.data.len() as u64).into());
-    data.extend(event.data.iter().copied());
+    hasher.update((event.data.len() as u64).into());
+    hasher.update_many(&event.data);
 
-    // Compute the final hash
-    Poseidon::hash_array(&data)
+    hasher.finalize()
 }
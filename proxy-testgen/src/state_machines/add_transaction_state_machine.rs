@@ -14,7 +14,7 @@ use std::io::Read;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
-use t9n::txn_validation::validate::validate_txn_json;
+use t9n::txn_validation::{signature::SignatureScheme, validate::validate_txn_json};
 use thiserror::Error;
 
 const SN_SEPOLIA: &str = "0x534e5f5345504f4c4941";
@@ -217,7 +217,7 @@ pub fn validate_request(request: String) -> Result<String, ValidationError> {
 
     let path = PathBuf::from("target/shared/request_txn.json");
 
-    match validate_txn_json(&path, None, SN_SEPOLIA) {
+    match validate_txn_json(&path, None, SN_SEPOLIA, SignatureScheme::Stark, None) {
         Result::Ok(json_result) => {
             if let Some(hash) = json_result.get("hash").and_then(|v| v.as_str()) {
                 Result::Ok(hash.to_string())
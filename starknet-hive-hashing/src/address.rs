@@ -0,0 +1,54 @@
+use crypto_utils::curve::signer::compute_hash_on_elements;
+use crypto_utils::protocol::{ADDR_BOUND, PREFIX_CONTRACT_ADDRESS};
+use starknet_types_core::felt::Felt;
+
+/// Reduces `address` below `2 ** 251 - 256`, the bound of contract addresses and storage keys.
+pub fn normalize_address(address: Felt) -> Felt {
+    address.mod_floor(&ADDR_BOUND)
+}
+
+/// Address of a contract deployed by `deployer_address`, zero for deploy-account transactions and
+/// the `deploy_syscall` with `deploy_from_zero`.
+pub fn contract_address(salt: Felt, class_hash: Felt, constructor_calldata: &[Felt], deployer_address: Felt) -> Felt {
+    normalize_address(compute_hash_on_elements(&[
+        PREFIX_CONTRACT_ADDRESS,
+        deployer_address,
+        salt,
+        class_hash,
+        compute_hash_on_elements(constructor_calldata),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn felt() -> impl Strategy<Value = Felt> {
+        any::<[u8; 32]>().prop_map(|bytes| Felt::from_bytes_be(&bytes))
+    }
+
+    proptest! {
+        #[test]
+        fn normalization_is_idempotent(address in felt()) {
+            let normalized = normalize_address(address);
+            prop_assert_eq!(normalize_address(normalized), normalized);
+            // 2 ** 251 - 256
+            prop_assert!(normalized < Felt::from_hex_unchecked("0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00"));
+        }
+
+        #[test]
+        fn addresses_depend_on_every_input(
+            salt in felt(),
+            class_hash in felt(),
+            calldata in vec(felt(), 0..4),
+        ) {
+            let address = contract_address(salt, class_hash, &calldata, Felt::ZERO);
+            prop_assert_eq!(address, normalize_address(address));
+            prop_assert_ne!(address, contract_address(salt + Felt::ONE, class_hash, &calldata, Felt::ZERO));
+            prop_assert_ne!(address, contract_address(salt, class_hash, &calldata, Felt::ONE));
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crypto_utils::hash::{poseidon_hash_many, PoseidonHasher};
+use crypto_utils::protocol::PREFIX_CONTRACT_CLASS_V0_1_0;
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash};
+use starknet_types_rpc::v0_7_1::{EntryPointsByType, SierraEntryPoint};
+
+use crate::address::normalize_address;
+use crate::keccak::starknet_keccak;
+
+/// Hash of a Sierra class. `abi` is the ABI as it is declared, a JSON string whose exact
+/// formatting is part of the hash.
+pub fn sierra_class_hash(entry_points_by_type: &EntryPointsByType<Felt>, abi: &str, sierra_program: &[Felt]) -> Felt {
+    let data = [
+        PREFIX_CONTRACT_CLASS_V0_1_0,
+        hash_sierra_entrypoints(&entry_points_by_type.external),
+        hash_sierra_entrypoints(&entry_points_by_type.l1_handler),
+        hash_sierra_entrypoints(&entry_points_by_type.constructor),
+        starknet_keccak(abi.as_bytes()),
+        poseidon_hash_many(sierra_program),
+    ];
+
+    normalize_address(Poseidon::hash_array(&data))
+}
+
+pub fn hash_sierra_entrypoints(entrypoints: &[SierraEntryPoint<Felt>]) -> Felt {
+    let mut hasher = PoseidonHasher::new();
+    for entry in entrypoints {
+        hasher.update(entry.selector);
+        hasher.update(entry.function_idx.into());
+    }
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::value::RawValue;
+
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct SierraArtifact<'a> {
+        sierra_program: Vec<Felt>,
+        entry_points_by_type: EntryPointsByType<Felt>,
+        #[serde(borrow)]
+        abi: &'a RawValue,
+    }
+
+    /// `json` formatted the way the ABI of a class is declared, by Python's `json.dumps`: no
+    /// whitespace but a space after separators, keys in their original order.
+    fn declared_abi(json: &str) -> String {
+        let mut formatted = String::with_capacity(json.len());
+        let (mut in_string, mut escaped) = (false, false);
+        for c in json.chars() {
+            if !in_string && c.is_whitespace() {
+                continue;
+            }
+            formatted.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_string => escaped = true,
+                '"' => in_string = !in_string,
+                ',' | ':' if !in_string => formatted.push(' '),
+                _ => {}
+            }
+        }
+        formatted
+    }
+
+    #[test]
+    fn oz_account_matches_its_declared_class_hash() {
+        // OpenZeppelin account 0.8.1, declared on mainnet and sepolia and predeployed by devnet.
+        let artifact: SierraArtifact = serde_json::from_str(include_str!(
+            "../../t8n/src/accounts_artifacts/OpenZeppelin/0.8.1/Account.cairo/Account.sierra"
+        ))
+        .unwrap();
+        assert_eq!(
+            sierra_class_hash(
+                &artifact.entry_points_by_type,
+                &declared_abi(artifact.abi.get()),
+                &artifact.sierra_program
+            ),
+            Felt::from_hex_unchecked("0x61dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f")
+        );
+    }
+}
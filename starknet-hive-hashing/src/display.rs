@@ -0,0 +1,51 @@
+//! Human-readable rendering of felts for assertion messages and reports: short hex, and names next
+//! to the felts they were resolved from. Resolving names is left to the tools, which know the
+//! contracts they loaded.
+
+use starknet_types_core::felt::Felt;
+
+/// Renders a felt as hex, keeping the `0x` and 6 leading digits and the 8 trailing digits of long
+/// values: `0x4718f5…287c938d`.
+pub fn short_hex(felt: &Felt) -> String {
+    let hex = felt.to_hex_string();
+    if hex.len() <= 18 {
+        hex
+    } else {
+        format!("{}…{}", &hex[..8], &hex[hex.len() - 8..])
+    }
+}
+
+/// Renders a felt as `name (0x…)` when `name` is given, as short hex otherwise, e.g. an address
+/// or a selector resolved to a name.
+pub fn display_named(name: Option<&str>, felt: &Felt) -> String {
+    match name {
+        Some(name) => format!("{name} ({})", short_hex(felt)),
+        None => short_hex(felt),
+    }
+}
+
+/// Renders a list of felts as short hex, e.g. calldata in assertion messages.
+pub fn display_felts(felts: &[Felt]) -> String {
+    format!("[{}]", felts.iter().map(short_hex).collect::<Vec<_>>().join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRK_ADDRESS: Felt =
+        Felt::from_hex_unchecked("0x4718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
+
+    #[test]
+    fn long_felts_keep_their_ends() {
+        assert_eq!(short_hex(&STRK_ADDRESS), "0x4718f5…287c938d");
+        assert_eq!(short_hex(&Felt::from_hex_unchecked("0x1234567890abcdef")), "0x1234567890abcdef");
+        assert_eq!(display_felts(&[Felt::ONE, STRK_ADDRESS]), "[0x1, 0x4718f5…287c938d]");
+    }
+
+    #[test]
+    fn names_precede_the_felt() {
+        assert_eq!(display_named(Some("STRK"), &STRK_ADDRESS), "STRK (0x4718f5…287c938d)");
+        assert_eq!(display_named(None, &Felt::from_hex_unchecked("0x4d15b1a7")), "0x4d15b1a7");
+    }
+}
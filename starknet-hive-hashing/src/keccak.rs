@@ -0,0 +1,35 @@
+use sha3::{Digest, Keccak256};
+use starknet_types_core::felt::Felt;
+
+/// Keccak-256 of `data` truncated to its 250 low bits, as selectors and storage variables use.
+pub fn starknet_keccak(data: &[u8]) -> Felt {
+    let mut hash: [u8; 32] = Keccak256::digest(data).into();
+    hash[0] &= 0b00000011;
+    Felt::from_bytes_be(&hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn event_selectors() {
+        assert_eq!(
+            starknet_keccak(b"Transfer"),
+            Felt::from_hex_unchecked("0x99cd8bde557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e6196e9")
+        );
+        assert_eq!(
+            starknet_keccak(b"transfer"),
+            Felt::from_hex_unchecked("0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e")
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn hashes_fit_in_250_bits(data in proptest::collection::vec(any::<u8>(), 0..256)) {
+            prop_assert!(starknet_keccak(&data).to_bytes_be()[0] <= 0b00000011);
+        }
+    }
+}
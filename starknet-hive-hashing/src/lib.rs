@@ -0,0 +1,12 @@
+//! Hashes of the Starknet protocol shared by the tools of the workspace: contract addresses, Sierra
+//! class hashes, `starknet_keccak` and transaction hashes. Transaction hashes are built from their
+//! fields rather than from the RPC types, so that both the transactions a test account prepares
+//! and the transactions read from JSON files are hashed by the same code. [display] renders felts
+//! as short hex for the messages of the tools.
+
+pub mod address;
+pub mod class;
+pub mod display;
+pub mod keccak;
+pub mod resource_bounds;
+pub mod transaction;
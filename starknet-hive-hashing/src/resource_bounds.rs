@@ -0,0 +1,200 @@
+use crypto_utils::protocol::{Resource, VersionedConstants};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{ResourceBounds, ResourceBoundsMapping};
+
+/// Resource bounds of V3 transactions. The RPC types carry the bounds as hex strings, this
+/// builder takes them as numbers in the ranges of the protocol, `u64` amounts and `u128` prices,
+/// and serializes them the way nodes expect.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceBoundsBuilder {
+    l1_gas: (u64, u128),
+    l2_gas: (u64, u128),
+    l1_data_gas: (u64, u128),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ResourceBoundsError {
+    #[error("{field} {value} is not a hex number of at most {bits} bits")]
+    OutOfRange { field: &'static str, value: String, bits: u32 },
+}
+
+impl ResourceBoundsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn l1_gas(self, max_amount: u64, max_price_per_unit: u128) -> Self {
+        Self { l1_gas: (max_amount, max_price_per_unit), ..self }
+    }
+
+    /// L2 gas is not priced yet, its bounds are 0 unless set.
+    pub fn l2_gas(self, max_amount: u64, max_price_per_unit: u128) -> Self {
+        Self { l2_gas: (max_amount, max_price_per_unit), ..self }
+    }
+
+    /// L1 data gas is only bounded from protocol 0.13.4 on, its bounds are 0 unless set.
+    pub fn l1_data_gas(self, max_amount: u64, max_price_per_unit: u128) -> Self {
+        Self { l1_data_gas: (max_amount, max_price_per_unit), ..self }
+    }
+
+    /// Parses bounds coming from a node or a test vector, checking that every value is in range.
+    pub fn parse(mapping: &ResourceBoundsMapping) -> Result<Self, ResourceBoundsError> {
+        Ok(Self {
+            l1_gas: parse_bounds(("l1_gas.max_amount", "l1_gas.max_price_per_unit"), &mapping.l1_gas)?,
+            l2_gas: parse_bounds(("l2_gas.max_amount", "l2_gas.max_price_per_unit"), &mapping.l2_gas)?,
+            ..Self::default()
+        })
+    }
+
+    /// Parses the L1 data gas bounds, which the v0.7 RPC mapping has no field for.
+    pub fn parse_l1_data_gas(self, bounds: &ResourceBounds) -> Result<Self, ResourceBoundsError> {
+        Ok(Self {
+            l1_data_gas: parse_bounds(("l1_data_gas.max_amount", "l1_data_gas.max_price_per_unit"), bounds)?,
+            ..self
+        })
+    }
+
+    /// Most the transaction can be charged, `None` if it doesn't fit a `u128`.
+    pub fn max_fee(&self) -> Option<u128> {
+        let fee = |(max_amount, max_price_per_unit): (u64, u128)| (max_amount as u128).checked_mul(max_price_per_unit);
+        fee(self.l1_gas)?.checked_add(fee(self.l2_gas)?)?.checked_add(fee(self.l1_data_gas)?)
+    }
+
+    /// Hash of the tip and the bounds of the resources `constants` bound, see
+    /// [VersionedConstants::fee_data_hash].
+    pub fn fee_data_hash(&self, constants: &VersionedConstants, tip: Felt) -> Felt {
+        let (l1_gas, l2_gas, l1_data_gas) = (self.l1_gas, self.l2_gas, self.l1_data_gas);
+        constants.fee_data_hash(
+            tip,
+            &[
+                (Resource::L1Gas, l1_gas.0, l1_gas.1),
+                (Resource::L2Gas, l2_gas.0, l2_gas.1),
+                (Resource::L1DataGas, l1_data_gas.0, l1_data_gas.1),
+            ],
+        )
+    }
+
+    /// Bounds in the v0.7 RPC mapping, which leaves out the L1 data gas ones.
+    pub fn build(self) -> ResourceBoundsMapping {
+        let bounds = |(max_amount, max_price_per_unit): (u64, u128)| ResourceBounds {
+            max_amount: format!("{max_amount:#x}"),
+            max_price_per_unit: format!("{max_price_per_unit:#x}"),
+        };
+        ResourceBoundsMapping { l1_gas: bounds(self.l1_gas), l2_gas: bounds(self.l2_gas) }
+    }
+}
+
+fn parse_bounds(
+    (amount_field, price_field): (&'static str, &'static str),
+    bounds: &ResourceBounds,
+) -> Result<(u64, u128), ResourceBoundsError> {
+    Ok((
+        parse_hex(amount_field, &bounds.max_amount, u64::BITS)? as u64,
+        parse_hex(price_field, &bounds.max_price_per_unit, u128::BITS)?,
+    ))
+}
+
+fn parse_hex(field: &'static str, value: &str, bits: u32) -> Result<u128, ResourceBoundsError> {
+    let out_of_range = || ResourceBoundsError::OutOfRange { field, value: value.to_string(), bits };
+    let digits = value.strip_prefix("0x").ok_or_else(out_of_range)?;
+    let parsed = u128::from_str_radix(digits, 16).map_err(|_| out_of_range())?;
+    if bits < u128::BITS && parsed >> bits != 0 {
+        return Err(out_of_range());
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::{invoke_v3_hash, V3Fields};
+    use crypto_utils::protocol::versioned_constants;
+    use starknet_types_core::hash::{Poseidon, StarkHash};
+    use starknet_types_rpc::DaMode;
+
+    #[test]
+    fn build_serializes_hex_without_padding() {
+        let mapping = ResourceBoundsBuilder::new().l1_gas(0x2710, 0x174876e800).build();
+        assert_eq!(mapping.l1_gas.max_amount, "0x2710");
+        assert_eq!(mapping.l1_gas.max_price_per_unit, "0x174876e800");
+        assert_eq!(mapping.l2_gas.max_amount, "0x0");
+        assert_eq!(mapping.l2_gas.max_price_per_unit, "0x0");
+    }
+
+    #[test]
+    fn parse_round_trips_and_rejects_out_of_range_amounts() {
+        let builder = ResourceBoundsBuilder::new().l1_gas(u64::MAX, u128::MAX);
+        assert_eq!(ResourceBoundsBuilder::parse(&builder.build()).unwrap(), builder);
+
+        let mut mapping = builder.build();
+        mapping.l1_gas.max_amount = "0x10000000000000000".to_string();
+        assert!(ResourceBoundsBuilder::parse(&mapping).is_err());
+        mapping.l1_gas.max_amount = "2710".to_string();
+        assert!(ResourceBoundsBuilder::parse(&mapping).is_err());
+    }
+
+    #[test]
+    fn l1_data_gas_is_hashed_from_the_protocol_version_bounding_it() {
+        let bounds = ResourceBounds { max_amount: "0x80".to_string(), max_price_per_unit: "0x3".to_string() };
+        let builder = ResourceBoundsBuilder::new().l1_gas(0x10, 0x20).parse_l1_data_gas(&bounds).unwrap();
+        assert_eq!(builder, ResourceBoundsBuilder::new().l1_gas(0x10, 0x20).l1_data_gas(0x80, 3));
+
+        let l1_gas = Resource::L1Gas.bounds(0x10, 0x20);
+        let l2_gas = Resource::L2Gas.bounds(0, 0);
+        let l1_data_gas = Resource::L1DataGas.bounds(0x80, 3);
+        assert_eq!(
+            builder.fee_data_hash(versioned_constants([0, 13, 2]), Felt::ONE),
+            Poseidon::hash_array(&[Felt::ONE, l1_gas, l2_gas])
+        );
+        assert_eq!(
+            builder.fee_data_hash(versioned_constants([0, 13, 4]), Felt::ONE),
+            Poseidon::hash_array(&[Felt::ONE, l1_gas, l2_gas, l1_data_gas])
+        );
+    }
+
+    #[test]
+    fn mainnet_bounds_hash_to_the_mainnet_transaction() {
+        // Bounds of transaction 0x1d47...2219 of mainnet block 636864 (protocol 0.13.1.1), as nodes
+        // return them.
+        let bounds = |max_amount: &str, max_price_per_unit: &str| ResourceBounds {
+            max_amount: max_amount.to_string(),
+            max_price_per_unit: max_price_per_unit.to_string(),
+        };
+        let mapping = ResourceBoundsMapping { l1_gas: bounds("0xa9e", "0x7f2a1ad4f2f1"), l2_gas: bounds("0x0", "0x0") };
+        let builder = ResourceBoundsBuilder::parse(&mapping).unwrap();
+        assert_eq!(builder, ResourceBoundsBuilder::new().l1_gas(0xa9e, 0x7f2a1ad4f2f1));
+
+        let fields = V3Fields {
+            version: Felt::THREE,
+            sender_address: Felt::from_hex_unchecked(
+                "0x69c0f9bcd79697bdceaf7748e3ff8f34aa39e4063ce44896af664c0c96f6c10",
+            ),
+            fee_data_hash: builder.fee_data_hash(versioned_constants([0, 13, 1]), Felt::ZERO),
+            paymaster_data: &[],
+            chain_id: Felt::from_hex_unchecked("0x534e5f4d41494e"),
+            nonce: Felt::from(0x9du64),
+            nonce_data_availability_mode: DaMode::L1,
+            fee_data_availability_mode: DaMode::L1,
+        };
+        let calldata = [
+            "0x1",
+            "0x4c0a5193d58f74fbace4b74dcf65481e734ed1714121bdc571da345540efa05",
+            "0x3943907ef0ef6f9d2e2408b05e520a66daaf74293dbf665e5a20b117676170e",
+            "0x2",
+            "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7",
+            "0x16345785d8a0000",
+        ]
+        .map(Felt::from_hex_unchecked);
+        assert_eq!(
+            invoke_v3_hash(&fields, &[], &calldata),
+            Felt::from_hex_unchecked("0x1d4735f4ba73a67be2f648d9b21cab3783383b8c229566b46b027c46012219")
+        );
+    }
+
+    #[test]
+    fn max_fee_overflow_is_none() {
+        assert_eq!(ResourceBoundsBuilder::new().l1_gas(10, 3).l2_gas(1, 2).max_fee(), Some(32));
+        assert_eq!(ResourceBoundsBuilder::new().l1_gas(2, u128::MAX).max_fee(), None);
+        assert_eq!(ResourceBoundsBuilder::new().l1_gas(10, 3).l1_data_gas(2, 5).max_fee(), Some(40));
+    }
+}
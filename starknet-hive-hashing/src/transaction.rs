@@ -0,0 +1,411 @@
+//! Transaction hashes, Pedersen based up to V2 and Poseidon based from V3 on (SNIP-8). The fee data
+//! hash of V3 transactions is taken already computed, as the resources it covers depend on the
+//! protocol version, see [`crypto_utils::protocol::VersionedConstants::fee_data_hash`].
+
+use crypto_utils::curve::signer::compute_hash_on_elements;
+use crypto_utils::protocol::{DATA_AVAILABILITY_MODE_BITS, PREFIX_DECLARE, PREFIX_DEPLOY_ACCOUNT, PREFIX_INVOKE};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash};
+use starknet_types_rpc::DaMode;
+
+/// Fields every Pedersen based transaction hash starts with.
+#[derive(Debug, Clone, Copy)]
+pub struct V1Fields {
+    /// Version of the transaction, offset by `2 ** 128` for queries.
+    pub version: Felt,
+    /// Sender of the transaction, the address of the account being deployed for deploy-account
+    /// transactions.
+    pub sender_address: Felt,
+    pub max_fee: Felt,
+    pub chain_id: Felt,
+    pub nonce: Felt,
+}
+
+/// Fields every V3 transaction hash starts with.
+#[derive(Debug, Clone)]
+pub struct V3Fields<'a> {
+    /// Version of the transaction, offset by `2 ** 128` for queries.
+    pub version: Felt,
+    /// Sender of the transaction, the address of the account being deployed for deploy-account
+    /// transactions.
+    pub sender_address: Felt,
+    /// `h(tip, resource_bounds_for_fee)` of SNIP-8.
+    pub fee_data_hash: Felt,
+    pub paymaster_data: &'a [Felt],
+    pub chain_id: Felt,
+    pub nonce: Felt,
+    pub nonce_data_availability_mode: DaMode,
+    pub fee_data_availability_mode: DaMode,
+}
+
+/// Legacy invoke, the entry point is called directly on the contract and there is no nonce.
+pub fn invoke_v0_hash(
+    contract_address: Felt,
+    entry_point_selector: Felt,
+    calldata: &[Felt],
+    max_fee: Felt,
+    chain_id: Felt,
+) -> Felt {
+    compute_hash_on_elements(&[
+        PREFIX_INVOKE,
+        Felt::ZERO, // version
+        contract_address,
+        entry_point_selector,
+        compute_hash_on_elements(calldata),
+        max_fee,
+        chain_id,
+    ])
+}
+
+pub fn invoke_v1_hash(fields: &V1Fields, calldata: &[Felt]) -> Felt {
+    compute_hash_on_elements(&[
+        PREFIX_INVOKE,
+        fields.version,
+        fields.sender_address,
+        Felt::ZERO, // entry_point_selector
+        compute_hash_on_elements(calldata),
+        fields.max_fee,
+        fields.chain_id,
+        fields.nonce,
+    ])
+}
+
+pub fn declare_v2_hash(fields: &V1Fields, class_hash: Felt, compiled_class_hash: Felt) -> Felt {
+    compute_hash_on_elements(&[
+        PREFIX_DECLARE,
+        fields.version,
+        fields.sender_address,
+        Felt::ZERO, // entry_point_selector
+        compute_hash_on_elements(&[class_hash]),
+        fields.max_fee,
+        fields.chain_id,
+        fields.nonce,
+        compiled_class_hash,
+    ])
+}
+
+/// Hash of a V1 deploy-account transaction, `fields.sender_address` being the address of the
+/// account it deploys.
+pub fn deploy_account_v1_hash(fields: &V1Fields, constructor_calldata: &[Felt], class_hash: Felt, salt: Felt) -> Felt {
+    let mut calldata_to_hash = vec![class_hash, salt];
+    calldata_to_hash.extend_from_slice(constructor_calldata);
+
+    compute_hash_on_elements(&[
+        PREFIX_DEPLOY_ACCOUNT,
+        fields.version,
+        fields.sender_address,
+        Felt::ZERO, // entry_point_selector
+        compute_hash_on_elements(&calldata_to_hash),
+        fields.max_fee,
+        fields.chain_id,
+        fields.nonce,
+    ])
+}
+
+pub fn invoke_v3_hash(fields: &V3Fields<'_>, account_deployment_data: &[Felt], calldata: &[Felt]) -> Felt {
+    v3_hash(PREFIX_INVOKE, fields, &[Poseidon::hash_array(account_deployment_data), Poseidon::hash_array(calldata)])
+}
+
+pub fn declare_v3_hash(
+    fields: &V3Fields<'_>,
+    account_deployment_data: &[Felt],
+    class_hash: Felt,
+    compiled_class_hash: Felt,
+) -> Felt {
+    v3_hash(PREFIX_DECLARE, fields, &[Poseidon::hash_array(account_deployment_data), class_hash, compiled_class_hash])
+}
+
+/// Hash of a V3 deploy-account transaction, `fields.sender_address` being the address of the
+/// account it deploys.
+pub fn deploy_account_v3_hash(
+    fields: &V3Fields<'_>,
+    constructor_calldata: &[Felt],
+    class_hash: Felt,
+    salt: Felt,
+) -> Felt {
+    v3_hash(PREFIX_DEPLOY_ACCOUNT, fields, &[Poseidon::hash_array(constructor_calldata), class_hash, salt])
+}
+
+/// Nonce data availability mode next to the fee one, as hashed by V3 transactions.
+pub fn data_availability_modes(nonce_mode: &DaMode, fee_mode: &DaMode) -> Felt {
+    let mode = |mode: &DaMode| match mode {
+        DaMode::L1 => 0u64,
+        DaMode::L2 => 1u64,
+    };
+    Felt::from((mode(nonce_mode) << DATA_AVAILABILITY_MODE_BITS) + mode(fee_mode))
+}
+
+fn v3_hash(prefix: Felt, fields: &V3Fields<'_>, tail: &[Felt]) -> Felt {
+    let mut data = vec![
+        prefix,
+        fields.version,
+        fields.sender_address,
+        fields.fee_data_hash,
+        Poseidon::hash_array(fields.paymaster_data),
+        fields.chain_id,
+        fields.nonce,
+        data_availability_modes(&fields.nonce_data_availability_mode, &fields.fee_data_availability_mode),
+    ];
+    data.extend_from_slice(tail);
+    Poseidon::hash_array(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto_utils::protocol::versioned_constants;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::address::contract_address;
+    use crate::resource_bounds::ResourceBoundsBuilder;
+
+    /// Chain id of Starknet mainnet, `SN_MAIN`.
+    const MAINNET: Felt = Felt::from_hex_unchecked("0x534e5f4d41494e");
+
+    // Versions of the queries estimating and simulating the mainnet transactions below, offset by
+    // 2 ** 128. Queries never make it to a block, their hashes are pinned to catch a change of the
+    // offset or of the fields it is hashed with.
+    const QUERY_V1: Felt = Felt::from_hex_unchecked("0x100000000000000000000000000000001");
+    const QUERY_V2: Felt = Felt::from_hex_unchecked("0x100000000000000000000000000000002");
+    const QUERY_V3: Felt = Felt::from_hex_unchecked("0x100000000000000000000000000000003");
+
+    fn felts(hexes: &[&str]) -> Vec<Felt> {
+        hexes.iter().map(|hex| Felt::from_hex_unchecked(hex)).collect()
+    }
+
+    /// Fields of V3 transactions of mainnet block 636864 (protocol 0.13.1.1), paying for L1 gas only.
+    fn mainnet_v3_fields(sender_address: Felt, nonce: u64, l1_gas: (u64, u128)) -> V3Fields<'static> {
+        V3Fields {
+            version: Felt::THREE,
+            sender_address,
+            fee_data_hash: ResourceBoundsBuilder::new()
+                .l1_gas(l1_gas.0, l1_gas.1)
+                .fee_data_hash(versioned_constants([0, 13, 1]), Felt::ZERO),
+            paymaster_data: &[],
+            chain_id: MAINNET,
+            nonce: Felt::from(nonce),
+            nonce_data_availability_mode: DaMode::L1,
+            fee_data_availability_mode: DaMode::L1,
+        }
+    }
+
+    fn felt() -> impl Strategy<Value = Felt> {
+        any::<[u8; 32]>().prop_map(|bytes| Felt::from_bytes_be(&bytes))
+    }
+
+    fn v1_fields() -> impl Strategy<Value = V1Fields> {
+        (felt(), felt(), felt(), felt(), felt()).prop_map(|(version, sender_address, max_fee, chain_id, nonce)| {
+            V1Fields { version, sender_address, max_fee, chain_id, nonce }
+        })
+    }
+
+    #[test]
+    fn data_availability_modes_are_packed() {
+        assert_eq!(data_availability_modes(&DaMode::L1, &DaMode::L1), Felt::ZERO);
+        assert_eq!(data_availability_modes(&DaMode::L1, &DaMode::L2), Felt::ONE);
+        assert_eq!(data_availability_modes(&DaMode::L2, &DaMode::L1), Felt::from(1u64 << 32));
+    }
+
+    #[test]
+    fn invoke_v1_matches_mainnet() {
+        // Transaction 0x215b...0e35 of mainnet block 636864.
+        let fields = V1Fields {
+            version: Felt::ONE,
+            sender_address: Felt::from_hex_unchecked(
+                "0x6f7afd58d20aedbdb694ff539d3280ae497c1a510caddcc6a06c97eebd001dc",
+            ),
+            max_fee: Felt::from_hex_unchecked("0x7f49b0d6d7c"),
+            chain_id: MAINNET,
+            nonce: Felt::from(0x62u64),
+        };
+        let calldata = felts(&[
+            "0x2",
+            "0x68f5c6a61780768455de69077e07e89787839bf8166decfbf92b645209c0fb8",
+            "0x219209e083275171774dab1df80982e9df2096516f06319c5c6d71ae0a8480c",
+            "0x3",
+            "0x1114c7103e12c2b2ecbd3a2472ba9c48ddcbf702b1c242dd570057e26212111",
+            "0xb67495",
+            "0x0",
+            "0x1114c7103e12c2b2ecbd3a2472ba9c48ddcbf702b1c242dd570057e26212111",
+            "0x15543c3708653cda9d418b4ccd3be11368e40636c10c44b18cfe756b6d88b29",
+            "0x7",
+            "0x30baaaf1b243f6e74c656f98dcb24b98687dcbe783d25f35854148c4c602d41",
+            "0x0",
+            "0xb67495",
+            "0x0",
+            "0x1",
+            "0x3a1045717884ca9abbc2e",
+            "0x0",
+        ]);
+        assert_eq!(
+            invoke_v1_hash(&fields, &calldata),
+            Felt::from_hex_unchecked("0x215b2e7efdedc5d9c056dd6a691b8117f292997d2cc8c15a9cebfa90620e35")
+        );
+
+        let query = V1Fields { version: QUERY_V1, ..fields };
+        assert_eq!(
+            invoke_v1_hash(&query, &calldata),
+            Felt::from_hex_unchecked("0x34cc38c334e26a3761851fdf631e3bcbc1ff6e8c5af1bcc21642f0023ce07aa")
+        );
+    }
+
+    #[test]
+    fn invoke_v3_matches_mainnet() {
+        // Transaction 0x1d47...2219 of mainnet block 636864.
+        let fields = mainnet_v3_fields(
+            Felt::from_hex_unchecked("0x69c0f9bcd79697bdceaf7748e3ff8f34aa39e4063ce44896af664c0c96f6c10"),
+            0x9d,
+            (0xa9e, 0x7f2a1ad4f2f1),
+        );
+        let calldata = felts(&[
+            "0x1",
+            "0x4c0a5193d58f74fbace4b74dcf65481e734ed1714121bdc571da345540efa05",
+            "0x3943907ef0ef6f9d2e2408b05e520a66daaf74293dbf665e5a20b117676170e",
+            "0x2",
+            "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7",
+            "0x16345785d8a0000",
+        ]);
+        assert_eq!(
+            invoke_v3_hash(&fields, &[], &calldata),
+            Felt::from_hex_unchecked("0x1d4735f4ba73a67be2f648d9b21cab3783383b8c229566b46b027c46012219")
+        );
+
+        let query = V3Fields { version: QUERY_V3, ..fields };
+        assert_eq!(
+            invoke_v3_hash(&query, &[], &calldata),
+            Felt::from_hex_unchecked("0x63548034064bb4bc4aff845f0350e286e695225480627c1048d3216735bc9af")
+        );
+    }
+
+    #[test]
+    fn declare_v2_matches_mainnet() {
+        // Transaction 0x7deb...e062 of mainnet block 446864.
+        let fields = V1Fields {
+            version: Felt::TWO,
+            sender_address: Felt::from_hex_unchecked(
+                "0x75341b8090a4257f22dafffe3a4cb882006bd26302720d6a80a1fde154a3430",
+            ),
+            max_fee: Felt::from_hex_unchecked("0xb48df232e93750"),
+            chain_id: MAINNET,
+            nonce: Felt::from(0x1f9u64),
+        };
+        let class_hash = Felt::from_hex_unchecked("0x4d90a3b52871831b34bc936d9aee304b7205202e649dceef5ee4392659ab33");
+        let compiled_class_hash =
+            Felt::from_hex_unchecked("0x3c1296b5f7e6a30bc0167bf30e0700eebb2e9a06228e24cc3ad386502125bcf");
+        assert_eq!(
+            declare_v2_hash(&fields, class_hash, compiled_class_hash),
+            Felt::from_hex_unchecked("0x7debe525c66a929048236c8f6da5903e4f141e5cb5e6cb23e9af33ecaabe062")
+        );
+
+        let query = V1Fields { version: QUERY_V2, ..fields };
+        assert_eq!(
+            declare_v2_hash(&query, class_hash, compiled_class_hash),
+            Felt::from_hex_unchecked("0x147b15cef8590ac21e43629bc7c99a8570b08a2b5675c4ee7cc2931e834b032")
+        );
+    }
+
+    #[test]
+    fn declare_v3_matches_mainnet() {
+        // Transaction 0x50ca...29fb of mainnet block 630721.
+        let fields = mainnet_v3_fields(
+            Felt::from_hex_unchecked("0x46d3a562c606077c14c3479946e4145b7a372538875eb4e635e758fcd1d2c80"),
+            0x3,
+            (0xe38c2, 0x24e2649cc098),
+        );
+        let class_hash = Felt::from_hex_unchecked("0x7a9d1cd5dcf3d47b10e638eb1330d101f7d471f08eff9664b42dfa90f6973bf");
+        let compiled_class_hash =
+            Felt::from_hex_unchecked("0x4fc6444f59a2fb0b2c67e1e22caba0edc3b48727e8a9cc19e59e5c3e5dc8270");
+        assert_eq!(
+            declare_v3_hash(&fields, &[], class_hash, compiled_class_hash),
+            Felt::from_hex_unchecked("0x50ca961fd1b5f7f1ea1e8620ac980cc3c3fb4e434f0661c3a2d50893082b9fb")
+        );
+
+        let query = V3Fields { version: QUERY_V3, ..fields };
+        assert_eq!(
+            declare_v3_hash(&query, &[], class_hash, compiled_class_hash),
+            Felt::from_hex_unchecked("0x71aa91a887080fa2fb1e473713123dca182eae42433c5422bbada8125819dc9")
+        );
+    }
+
+    #[test]
+    fn deploy_account_v1_matches_mainnet() {
+        // Transaction 0x40e7...c5e4 of mainnet block 636864, deploying a Braavos account.
+        let class_hash = Felt::from_hex_unchecked("0x13bfe114fb1cf405bfc3a7f8dbe2d91db146c17521d40dcf57e16d6b59fa8e6");
+        let salt = Felt::from_hex_unchecked("0x54c617a2e91df5344958e0eb2c30c58a1134b3f8e59e88deba60a24f95c0a2c");
+        let address = contract_address(salt, class_hash, &[salt], Felt::ZERO);
+        assert_eq!(
+            address,
+            Felt::from_hex_unchecked("0x2aaedc4cc67a5ad0134868689f49b9c8838db640a5c84a13e6cccc828c21aa")
+        );
+        let fields = V1Fields {
+            version: Felt::ONE,
+            sender_address: address,
+            max_fee: Felt::from_hex_unchecked("0x3a23c71d8b9"),
+            chain_id: MAINNET,
+            nonce: Felt::ZERO,
+        };
+        assert_eq!(
+            deploy_account_v1_hash(&fields, &[salt], class_hash, salt),
+            Felt::from_hex_unchecked("0x40e7ac7efc374f3d1241c6f991de2ea534d84e8be307420658353527226c5e4")
+        );
+
+        let query = V1Fields { version: QUERY_V1, ..fields };
+        assert_eq!(
+            deploy_account_v1_hash(&query, &[salt], class_hash, salt),
+            Felt::from_hex_unchecked("0xbdaff4caa84e6d32cfd7ff9b51c04d398df9bfeaa5ba3d4fafd327e4e8840e")
+        );
+    }
+
+    #[test]
+    fn deploy_account_v3_matches_mainnet() {
+        // Transaction 0x25cb...cf15 of mainnet block 636864, deploying a Braavos account.
+        let class_hash = Felt::from_hex_unchecked("0x13bfe114fb1cf405bfc3a7f8dbe2d91db146c17521d40dcf57e16d6b59fa8e6");
+        let salt = Felt::from_hex_unchecked("0x1f218cfa725d1679ff028c53d094a765dfd1de632787cab37ffe10f3915dcc7");
+        let address = contract_address(salt, class_hash, &[salt], Felt::ZERO);
+        assert_eq!(
+            address,
+            Felt::from_hex_unchecked("0x2ef67bd3f91cb3c6f5bdaf8468752a40fb3bdeb880779d36ec3c43736002158")
+        );
+        let fields = mainnet_v3_fields(address, 0, (0x4c, 0x8ab967606cb9));
+        assert_eq!(
+            deploy_account_v3_hash(&fields, &[salt], class_hash, salt),
+            Felt::from_hex_unchecked("0x25cbbc1e197e1e6dd8886b4b359c2dbe26245a282547624d7e54e7c3e2cf15")
+        );
+
+        let query = V3Fields { version: QUERY_V3, ..fields };
+        assert_eq!(
+            deploy_account_v3_hash(&query, &[salt], class_hash, salt),
+            Felt::from_hex_unchecked("0xe035fefb0a5f799d4c4b651f86937a848e1394fb161c80148024e143f6839c")
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn transaction_types_do_not_collide(
+            fields in v1_fields(),
+            class_hash in felt(),
+            calldata in vec(felt(), 0..4),
+        ) {
+            let invoke = invoke_v1_hash(&fields, &calldata);
+            prop_assert_ne!(invoke, invoke_v1_hash(&V1Fields { nonce: fields.nonce + Felt::ONE, ..fields }, &calldata));
+            prop_assert_ne!(invoke, declare_v2_hash(&fields, class_hash, Felt::ZERO));
+        }
+
+        #[test]
+        fn v3_hashes_cover_the_data_availability_modes(fields in v1_fields(), calldata in vec(felt(), 0..4)) {
+            let l1 = V3Fields {
+                version: Felt::THREE,
+                sender_address: fields.sender_address,
+                fee_data_hash: fields.max_fee,
+                paymaster_data: &[],
+                chain_id: fields.chain_id,
+                nonce: fields.nonce,
+                nonce_data_availability_mode: DaMode::L1,
+                fee_data_availability_mode: DaMode::L1,
+            };
+            let l2 = V3Fields { fee_data_availability_mode: DaMode::L2, ..l1.clone() };
+            prop_assert_ne!(invoke_v3_hash(&l1, &[], &calldata), invoke_v3_hash(&l2, &[], &calldata));
+        }
+    }
+}
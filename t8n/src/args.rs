@@ -1,19 +1,138 @@
-use clap::Parser;
+use clap::{Args as ClapArgs, Parser, Subcommand};
+
+use crate::starknet::state::rpc_receipt::RpcReceiptVersion;
 use std::path::PathBuf;
+use url::Url;
 
 #[derive(Parser, Debug, Clone)]
 #[command(version, about, long_about = None)]
 pub struct Args {
-    #[arg(long, short, env, required_unless_present = "forwarded_state")]
-    pub acc_path: Option<PathBuf>, // Optional when forwarded_state is true
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Executes transactions on top of a fresh state with predeployed accounts and outputs the resulting state.
+    Transition(TransitionArgs),
+    /// Executes transactions on top of the state produced by a previous t8n run and outputs the resulting state.
+    Fork(ForkArgs),
+    /// Executes transactions and outputs only their traces.
+    Trace(TraceArgs),
+    /// Re-executes a range of historical blocks of a node and compares the receipts with the node's.
+    VerifyRange(VerifyRangeArgs),
+}
+
+/// Input transactions and output destination shared by all subcommands.
+///
+/// A missing path or `-` means stdin for input and stdout for output, so t8n can be used in shell pipelines.
+#[derive(ClapArgs, Debug, Clone)]
+pub struct IoArgs {
+    #[arg(long, short, env)]
+    pub txns_path: Option<PathBuf>,
+
+    #[arg(long, short, env)]
+    pub output_path: Option<PathBuf>,
+
+    /// Also writes a full state snapshot, which can later be loaded with `--snapshot-path`.
+    #[arg(long, short, env)]
+    pub dump_path: Option<PathBuf>,
 
+    /// Also writes the receipts of all executed transactions in the OpenRPC receipt schema.
     #[arg(long, short, env)]
-    pub txns_path: PathBuf,
+    pub receipts_path: Option<PathBuf>,
 
-    #[arg(long, short, env, default_value = "./target/t8n/output.json")]
-    pub state_path: PathBuf,
+    /// OpenRPC spec version of the receipts written to `--receipts-path`.
+    #[arg(long, env, value_enum, default_value_t)]
+    pub receipts_version: RpcReceiptVersion,
+
+    /// Versioned constants files of the blockifier, named after their Starknet version, e.g.
+    /// `0.13.1.json`. Blocks given with a `starknet_version` are executed with the rules of the
+    /// newest version that is not newer.
+    #[arg(long, env)]
+    pub vm_versions_dir: Option<PathBuf>,
+}
+
+/// Sources of a fresh initial state. At least one of `acc_path` and `genesis_path` is required.
+#[derive(ClapArgs, Debug, Clone, Default)]
+pub struct GenesisArgs {
+    #[arg(long, short, env)]
+    pub acc_path: Option<PathBuf>,
+
+    /// Genesis specification with predeployed accounts, token balances and storage overrides.
+    #[arg(long, short, env)]
+    pub genesis_path: Option<PathBuf>,
+
+    /// Directory of Sierra and Cairo 0 class artifacts to pre-declare in the genesis state.
+    #[arg(long, short, env)]
+    pub classes_dir: Option<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct TransitionArgs {
+    #[command(flatten)]
+    pub genesis: GenesisArgs,
+
+    #[command(flatten)]
+    pub io: IoArgs,
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct ForkArgs {
+    /// State output of a previous t8n run.
+    #[arg(long, short, env, required_unless_present = "snapshot_path", conflicts_with = "snapshot_path")]
+    pub state_path: Option<PathBuf>,
+
+    /// Full state snapshot dumped by a previous t8n run with `--dump-path`.
+    #[arg(long, env)]
+    pub snapshot_path: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub io: IoArgs,
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct TraceArgs {
+    /// Used unless `--state-path` or `--snapshot-path` is given.
+    #[command(flatten)]
+    pub genesis: GenesisArgs,
+
+    /// State output of a previous t8n run; when set, traces are produced on top of it.
+    #[arg(long, short, env, conflicts_with_all = ["snapshot_path", "acc_path", "genesis_path", "classes_dir"])]
+    pub state_path: Option<PathBuf>,
+
+    /// Full state snapshot dumped by a previous t8n run; when set, traces are produced on top of it.
+    #[arg(long, env, conflicts_with_all = ["acc_path", "genesis_path", "classes_dir"])]
+    pub snapshot_path: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub io: IoArgs,
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct VerifyRangeArgs {
+    /// First block to verify; the state is forked from the node at the block before it.
+    #[arg(long)]
+    pub from: u64,
+
+    /// Last block to verify.
+    #[arg(long)]
+    pub to: u64,
+
+    /// JSON-RPC endpoint of the node whose blocks are verified.
+    #[arg(long, env)]
+    pub rpc: Url,
+
+    /// Progress of the verification, written after every block and resumed from when present.
+    #[arg(long, env)]
+    pub checkpoint_path: Option<PathBuf>,
+
+    /// Report of the verification; stdout when not set.
+    #[arg(long, short, env)]
+    pub output_path: Option<PathBuf>,
 
-    /// This parameter allows the program to accept input state from the output of a previous t8n run (which is state).
-    #[arg(long, short)]
-    pub forwarded_state: bool,
+    /// Versioned constants files of the blockifier, named after their Starknet version; every block
+    /// is executed with the rules of the newest version that is not newer than the block's.
+    #[arg(long, env)]
+    pub vm_versions_dir: Option<PathBuf>,
 }
@@ -0,0 +1,276 @@
+//! Human-readable differences between the receipts of a node and of their re-execution. Instead of
+//! two JSON values, a divergence lists the paths of the fields that differ with both values, and
+//! for events and messages the call of the node's trace that emitted them.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Receipt fields that must be the same on the node and in the re-execution.
+pub const COMPARED_FIELDS: [&str; 4] = ["execution_status", "actual_fee", "events", "messages_sent"];
+
+/// Invocations at the root of a transaction trace, in the order their events are emitted.
+const ROOT_INVOCATIONS: [&str; 4] =
+    ["validate_invocation", "execute_invocation", "constructor_invocation", "fee_transfer_invocation"];
+
+/// Kind of a divergence, from the most to the least severe. Each one has an exit code of its own,
+/// the most severe divergence of a run picking the exit code of the run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DivergenceCategory {
+    /// Included by the node, rejected by the re-execution.
+    Rejected,
+    /// Succeeded on one side and reverted on the other.
+    ExecutionStatus,
+    Events,
+    Messages,
+    Fee,
+}
+
+impl DivergenceCategory {
+    /// Category of a divergence of the receipt field `field`.
+    pub fn of_field(field: &str) -> Self {
+        match field {
+            "execution_status" | "revert_reason" => Self::ExecutionStatus,
+            "events" => Self::Events,
+            "messages_sent" => Self::Messages,
+            "actual_fee" => Self::Fee,
+            _ => Self::Rejected,
+        }
+    }
+
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Rejected => 2,
+            Self::ExecutionStatus => 3,
+            Self::Events => 4,
+            Self::Messages => 5,
+            Self::Fee => 6,
+        }
+    }
+}
+
+impl fmt::Display for DivergenceCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Rejected => "rejected",
+            Self::ExecutionStatus => "execution status",
+            Self::Events => "events",
+            Self::Messages => "messages",
+            Self::Fee => "fee",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A field that differs, `None` standing for a field or array element missing on that side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldDifference {
+    /// Path of the field in the receipt, e.g. `events[1].data[0]`.
+    pub path: String,
+    pub node: Option<Value>,
+    pub reexecution: Option<Value>,
+    /// Call that emitted the event or message of the field in the node's trace, e.g.
+    /// `execute_invocation.calls[0] (contract 0x1, selector 0x2)`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl fmt::Display for FieldDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |value: &Option<Value>| value.as_ref().map_or_else(|| "missing".to_string(), Value::to_string);
+        write!(f, "{}: node {}, re-execution {}", self.path, value(&self.node), value(&self.reexecution))?;
+        if let Some(location) = &self.location {
+            write!(f, ", emitted by {location}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Fields of [COMPARED_FIELDS] and `revert_reason` that differ between the receipt of a transaction
+/// on the node and in its re-execution, with their differences. Revert reasons are worded
+/// differently by every VM, only whether there is one is compared.
+pub fn diff_receipts(node: &Value, reexecution: &Value) -> Vec<(&'static str, Vec<FieldDifference>)> {
+    let mut fields: Vec<_> = COMPARED_FIELDS
+        .into_iter()
+        .map(|field| (field, diff_values(field, &normalize_hex(&node[field]), &normalize_hex(&reexecution[field]))))
+        .filter(|(_, differences)| !differences.is_empty())
+        .collect();
+    if node.get("revert_reason").is_some() != reexecution.get("revert_reason").is_some() {
+        let difference = FieldDifference {
+            path: "revert_reason".to_string(),
+            node: node.get("revert_reason").cloned(),
+            reexecution: reexecution.get("revert_reason").cloned(),
+            location: None,
+        };
+        fields.push(("revert_reason", vec![difference]));
+    }
+    fields
+}
+
+/// Leaf fields that differ between `node` and `reexecution`, with paths starting at `path`.
+pub fn diff_values(path: &str, node: &Value, reexecution: &Value) -> Vec<FieldDifference> {
+    let mut differences = Vec::new();
+    collect_differences(path.to_string(), Some(node), Some(reexecution), &mut differences);
+    differences
+}
+
+fn collect_differences(
+    path: String,
+    node: Option<&Value>,
+    reexecution: Option<&Value>,
+    differences: &mut Vec<FieldDifference>,
+) {
+    match (node, reexecution) {
+        (Some(Value::Object(node)), Some(Value::Object(reexecution))) => {
+            let mut keys: Vec<_> =
+                node.keys().chain(reexecution.keys().filter(|key| !node.contains_key(*key))).collect();
+            keys.sort();
+            for key in keys {
+                collect_differences(format!("{path}.{key}"), node.get(key), reexecution.get(key), differences);
+            }
+        }
+        (Some(Value::Array(node)), Some(Value::Array(reexecution))) => {
+            for index in 0..node.len().max(reexecution.len()) {
+                collect_differences(format!("{path}[{index}]"), node.get(index), reexecution.get(index), differences);
+            }
+        }
+        (node, reexecution) if node != reexecution => differences.push(FieldDifference {
+            path,
+            node: node.cloned(),
+            reexecution: reexecution.cloned(),
+            location: None,
+        }),
+        _ => {}
+    }
+}
+
+/// Lowercases the hex strings of `value` and strips their leading zeros, as nodes pad felts
+/// differently.
+pub fn normalize_hex(value: &Value) -> Value {
+    match value {
+        Value::String(string) => match string.strip_prefix("0x").or_else(|| string.strip_prefix("0X")) {
+            Some(digits) if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit()) => {
+                let digits = digits.trim_start_matches('0').to_lowercase();
+                Value::String(format!("0x{}", if digits.is_empty() { "0" } else { &digits }))
+            }
+            _ => value.clone(),
+        },
+        Value::Array(values) => Value::Array(values.iter().map(normalize_hex).collect()),
+        Value::Object(fields) => {
+            Value::Object(fields.iter().map(|(key, value)| (key.clone(), normalize_hex(value))).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Calls of a transaction trace emitting the items of `list`, `events` or `messages`, in the order
+/// they are emitted, so that the n-th event of a receipt was emitted by the n-th call returned.
+pub fn emitting_calls(trace: &Value, list: &str) -> Vec<String> {
+    let mut emitted = Vec::new();
+    for root in ROOT_INVOCATIONS {
+        collect_emitting_calls(&trace[root], root.to_string(), list, &mut emitted);
+    }
+    emitted.sort_by_key(|(order, _)| *order);
+    emitted.into_iter().map(|(_, location)| location).collect()
+}
+
+fn collect_emitting_calls(invocation: &Value, path: String, list: &str, emitted: &mut Vec<(u64, String)>) {
+    // A reverted execute invocation only holds the revert reason.
+    if !invocation.is_object() || invocation.get("revert_reason").is_some() {
+        return;
+    }
+    let location = format!(
+        "{} (contract {}, selector {})",
+        path,
+        invocation["contract_address"].as_str().unwrap_or("?"),
+        invocation["entry_point_selector"].as_str().unwrap_or("?")
+    );
+    for item in invocation[list].as_array().into_iter().flatten() {
+        emitted.push((item["order"].as_u64().unwrap_or_default(), location.clone()));
+    }
+    for (index, call) in invocation["calls"].as_array().into_iter().flatten().enumerate() {
+        collect_emitting_calls(call, format!("{path}.calls[{index}]"), list, emitted);
+    }
+}
+
+/// Index of the event or message a difference of the `events` or `messages_sent` field is in.
+pub fn emitted_item_index(path: &str) -> Option<usize> {
+    let rest = path.strip_prefix("events[").or_else(|| path.strip_prefix("messages_sent["))?;
+    rest.split(']').next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn differences_have_field_paths() {
+        let node = json!({ "events": [{ "data": ["0x1", "0x2"] }], "unit": "FRI" });
+        let reexecution = json!({ "events": [{ "data": ["0x1", "0x3"] }, { "data": [] }], "unit": "FRI" });
+        let differences = diff_values("receipt", &node, &reexecution);
+        let paths: Vec<_> = differences.iter().map(|difference| difference.path.as_str()).collect();
+        assert_eq!(paths, ["receipt.events[0].data[1]", "receipt.events[1]"]);
+        assert_eq!(differences[1].node, None);
+        assert_eq!(differences[0].to_string(), "receipt.events[0].data[1]: node \"0x2\", re-execution \"0x3\"");
+    }
+
+    #[test]
+    fn receipts_differ_in_normalized_fields_and_revert_presence() {
+        let node = json!({
+            "execution_status": "REVERTED",
+            "actual_fee": { "amount": "0x00AB", "unit": "FRI" },
+            "events": [],
+            "messages_sent": [],
+            "revert_reason": "Error in the called contract"
+        });
+        let mut reexecution = node.clone();
+        reexecution["actual_fee"]["amount"] = json!("0xab");
+        reexecution["revert_reason"] = json!("Execution failed");
+        assert!(diff_receipts(&node, &reexecution).is_empty());
+
+        reexecution["execution_status"] = json!("SUCCEEDED");
+        reexecution.as_object_mut().unwrap().remove("revert_reason");
+        let fields: Vec<_> = diff_receipts(&node, &reexecution).into_iter().map(|(field, _)| field).collect();
+        assert_eq!(fields, ["execution_status", "revert_reason"]);
+    }
+
+    #[test]
+    fn hex_strings_are_normalized() {
+        let value = json!({ "actual_fee": { "amount": "0x00Ab", "unit": "FRI" }, "events": [{ "data": ["0x000"] }] });
+        assert_eq!(
+            normalize_hex(&value),
+            json!({ "actual_fee": { "amount": "0xab", "unit": "FRI" }, "events": [{ "data": ["0x0"] }] })
+        );
+    }
+
+    #[test]
+    fn events_are_located_in_the_call_tree() {
+        let trace = json!({
+            "execute_invocation": {
+                "contract_address": "0xa",
+                "entry_point_selector": "0x1",
+                "events": [{ "order": 2 }],
+                "calls": [{ "contract_address": "0xb", "entry_point_selector": "0x2", "events": [{ "order": 0 }] }]
+            },
+            "fee_transfer_invocation": {
+                "contract_address": "0xc",
+                "entry_point_selector": "0x3",
+                "events": [{ "order": 3 }]
+            }
+        });
+        assert_eq!(
+            emitting_calls(&trace, "events"),
+            [
+                "execute_invocation.calls[0] (contract 0xb, selector 0x2)",
+                "execute_invocation (contract 0xa, selector 0x1)",
+                "fee_transfer_invocation (contract 0xc, selector 0x3)",
+            ]
+        );
+        assert_eq!(emitted_item_index("events[12].keys[0]"), Some(12));
+        assert_eq!(emitted_item_index("actual_fee.amount"), None);
+    }
+}
@@ -0,0 +1,133 @@
+use std::path::Path;
+
+use serde_json::Value;
+use starknet_devnet_types::chain_id::ChainId;
+use starknet_devnet_types::rpc::transactions::{BlockTransactionTrace, BroadcastedTransaction};
+use url::Url;
+
+use crate::starknet::state::{
+    errors::{DevnetResult, Error},
+    genesis::Genesis,
+    rpc_receipt::RpcReceiptVersion,
+    snapshot::StateSnapshot,
+    starknet_blocks::ExecutedBlock,
+    starknet_config::{ForkConfig, StarknetConfig},
+    starknet_state::StateWithBlockNumber,
+    vm_versions::VmVersions,
+    Starknet,
+};
+use crate::utils::{
+    add_transaction_receipts, collect_rpc_receipts, collect_transaction_traces, handle_blocks, BlockInput,
+    TransactionsInput,
+};
+
+/// In-process t8n state machine: sets up a state, executes transactions in blocks and extracts
+/// receipts, traces and snapshots, exactly as the `t8n` binary does.
+///
+/// ```ignore
+/// let mut executor = Executor::new(Some(Path::new("acc.json")))?;
+/// executor.execute_block(transactions)?;
+/// let receipts = executor.rpc_receipts(RpcReceiptVersion::V0_7)?;
+/// ```
+#[derive(Debug)]
+pub struct Executor {
+    starknet: Starknet,
+}
+
+impl Executor {
+    /// Fresh state with the default configuration, predeploying the user accounts from `acc_path`
+    /// when given.
+    pub fn new(acc_path: Option<&Path>) -> DevnetResult<Self> {
+        Ok(Self { starknet: Starknet::new(&StarknetConfig::default(), acc_path)? })
+    }
+
+    /// State output of a previous t8n run.
+    pub fn from_state(state: StateWithBlockNumber) -> DevnetResult<Self> {
+        Ok(Self { starknet: Starknet::from_init_state(state)? })
+    }
+
+    /// State of the node at `url` as of block `block_number`, read from the node when first
+    /// accessed. Block info such as numbers and timestamps is not taken from the node.
+    pub fn fork(url: Url, block_number: u64, chain_id: ChainId) -> DevnetResult<Self> {
+        let config = StarknetConfig {
+            chain_id,
+            fork_config: ForkConfig { url: Some(url), block_number: Some(block_number) },
+            ..StarknetConfig::default()
+        };
+        Ok(Self { starknet: Starknet::new(&config, None)? })
+    }
+
+    pub fn from_snapshot(snapshot: StateSnapshot) -> DevnetResult<Self> {
+        Ok(Self { starknet: Starknet::from_snapshot(snapshot)? })
+    }
+
+    pub fn from_starknet(starknet: Starknet) -> Self {
+        Self { starknet }
+    }
+
+    /// Pre-declares every class artifact found in `dir`, see [`Starknet::predeclare_classes_from_dir`].
+    pub fn with_classes_dir(mut self, dir: &Path) -> DevnetResult<Self> {
+        self.starknet.predeclare_classes_from_dir(dir)?;
+        Ok(self)
+    }
+
+    pub fn with_genesis(mut self, genesis: &Genesis) -> DevnetResult<Self> {
+        self.starknet.apply_genesis(genesis)?;
+        Ok(self)
+    }
+
+    /// Loads the rules of the Starknet versions in `dir`, see [`VmVersions::from_dir`], to execute
+    /// blocks given with a `starknet_version` with.
+    pub fn with_vm_versions(mut self, dir: &Path) -> DevnetResult<Self> {
+        self.starknet.vm_versions = VmVersions::from_dir(dir)?;
+        Ok(self)
+    }
+
+    /// Executes the transactions in a single new block and returns its outcome. Transactions that
+    /// fail validation are logged and left out of the block, as in the binary.
+    pub fn execute_block(&mut self, transactions: Vec<BroadcastedTransaction>) -> DevnetResult<&ExecutedBlock> {
+        self.execute_blocks(vec![BlockInput::Transactions(transactions)])?;
+        self.starknet.executed_blocks.last().ok_or(Error::NoBlock)
+    }
+
+    /// Executes each group of transactions in its own block and returns the outcomes of the new
+    /// blocks.
+    pub fn execute_blocks(&mut self, blocks: Vec<BlockInput>) -> DevnetResult<&[ExecutedBlock]> {
+        let executed_before = self.starknet.executed_blocks.len();
+        handle_blocks(&mut self.starknet, blocks)?;
+        add_transaction_receipts(&mut self.starknet)?;
+        Ok(&self.starknet.executed_blocks[executed_before..])
+    }
+
+    /// Executes transactions in the JSON format of the `--txns-path` input, e.g. transactions
+    /// fetched from a node and re-serialized.
+    pub fn execute_json(&mut self, input: Value) -> DevnetResult<&[ExecutedBlock]> {
+        let input: TransactionsInput = serde_json::from_value(input)?;
+        self.execute_blocks(input.into_blocks())
+    }
+
+    /// Receipts of all executed transactions in the `starknet_getTransactionReceipt` schema.
+    pub fn rpc_receipts(&self, version: RpcReceiptVersion) -> DevnetResult<Vec<Value>> {
+        collect_rpc_receipts(&self.starknet, version)
+    }
+
+    pub fn traces(&self) -> Vec<BlockTransactionTrace> {
+        collect_transaction_traces(&self.starknet)
+    }
+
+    pub fn snapshot(&self) -> DevnetResult<StateSnapshot> {
+        StateSnapshot::from_starknet(&self.starknet)
+    }
+
+    pub fn starknet(&self) -> &Starknet {
+        &self.starknet
+    }
+
+    pub fn starknet_mut(&mut self) -> &mut Starknet {
+        &mut self.starknet
+    }
+
+    pub fn into_starknet(self) -> Starknet {
+        self.starknet
+    }
+}
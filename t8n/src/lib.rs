@@ -0,0 +1,8 @@
+pub mod args;
+pub mod execution_diff;
+pub mod executor;
+pub mod starknet;
+pub mod utils;
+pub mod verify_range;
+
+pub use executor::Executor;
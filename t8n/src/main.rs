@@ -1,35 +1,99 @@
-pub mod args;
-pub mod starknet;
-pub mod utils;
-
-use crate::starknet::state::errors::Error;
-use args::Args;
 use clap::Parser;
-use starknet::state::{starknet_config::StarknetConfig, starknet_state::StateWithBlockNumber, Starknet};
-use utils::{
-    add_transaction_receipts, handle_transactions, read_state_file, read_transactions_file, write_result_state_file,
+use std::path::Path;
+use t8n::args::{Args, Command, GenesisArgs, IoArgs};
+use t8n::starknet::state::{errors::Error, genesis::Genesis, snapshot::StateSnapshot, vm_versions::VmVersions};
+use t8n::utils::{
+    ensure_single_stdin_input, read_json_input, read_state_file, read_transactions_file, write_json_output,
 };
+use t8n::verify_range::verify_range;
+use t8n::Executor;
+
+fn initialize_executor(
+    genesis_args: &GenesisArgs,
+    state_path: Option<&Path>,
+    snapshot_path: Option<&Path>,
+) -> Result<Executor, Error> {
+    match (state_path, snapshot_path) {
+        (Some(state_path), _) => Executor::from_state(read_state_file(state_path)?),
+        (None, Some(snapshot_path)) => {
+            Executor::from_snapshot(StateSnapshot::from_json(read_json_input(Some(snapshot_path))?)?)
+        }
+        (None, None) => {
+            if genesis_args.acc_path.is_none() && genesis_args.genesis_path.is_none() {
+                return Err(Error::AccPathNotProvided);
+            }
 
-fn initialize_starknet(args: &Args) -> Result<Starknet, Error> {
-    if args.forwarded_state {
-        let state_with_block_number: StateWithBlockNumber = read_state_file(&args.state_path)?;
-        Starknet::from_init_state(state_with_block_number)
-    } else {
-        Starknet::new(&StarknetConfig::default(), args.acc_path.as_ref().ok_or(Error::AccPathNotProvided)?)
+            let mut executor = Executor::new(genesis_args.acc_path.as_deref())?;
+            if let Some(classes_dir) = genesis_args.classes_dir.as_deref() {
+                executor = executor.with_classes_dir(classes_dir)?;
+            }
+            if let Some(genesis_path) = genesis_args.genesis_path.as_deref() {
+                executor = executor.with_genesis(&Genesis::from_file(genesis_path)?)?;
+            }
+            Ok(executor)
+        }
     }
 }
 
-fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).init();
+fn execute(executor: &mut Executor, io: &IoArgs) -> Result<(), Error> {
+    if let Some(vm_versions_dir) = io.vm_versions_dir.as_deref() {
+        executor.starknet_mut().vm_versions = VmVersions::from_dir(vm_versions_dir)?;
+    }
+    executor.execute_blocks(read_transactions_file(io.txns_path.as_deref())?.into_blocks())?;
 
-    let args = Args::parse();
-    let mut starknet = initialize_starknet(&args)?;
+    if let Some(receipts_path) = io.receipts_path.as_deref() {
+        write_json_output(Some(receipts_path), &executor.rpc_receipts(io.receipts_version)?)?;
+    }
+    if let Some(dump_path) = io.dump_path.as_deref() {
+        write_json_output(Some(dump_path), &executor.snapshot()?.to_canonical_json()?)?;
+    }
+    Ok(())
+}
 
-    let transactions = read_transactions_file(&args.txns_path)?;
+fn main() -> Result<(), Error> {
+    // Logs go to stderr so that stdout only carries the JSON output.
+    tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).with_writer(std::io::stderr).init();
 
-    handle_transactions(&mut starknet, transactions)?;
-    add_transaction_receipts(&mut starknet)?;
-    write_result_state_file(&args.state_path, &starknet)?;
+    let args = Args::parse();
+    match args.command {
+        Command::Transition(args) => {
+            let mut executor = initialize_executor(&args.genesis, None, None)?;
+            execute(&mut executor, &args.io)?;
+            write_json_output(args.io.output_path.as_deref(), executor.starknet())?;
+        }
+        Command::Fork(args) => {
+            let input_path = args.state_path.as_deref().or(args.snapshot_path.as_deref());
+            ensure_single_stdin_input(input_path, args.io.txns_path.as_deref())?;
+            let mut executor = initialize_executor(
+                &GenesisArgs::default(),
+                args.state_path.as_deref(),
+                args.snapshot_path.as_deref(),
+            )?;
+            execute(&mut executor, &args.io)?;
+            write_json_output(args.io.output_path.as_deref(), executor.starknet())?;
+        }
+        Command::Trace(args) => {
+            let input_path = args.state_path.as_deref().or(args.snapshot_path.as_deref());
+            ensure_single_stdin_input(input_path, args.io.txns_path.as_deref())?;
+            let mut executor =
+                initialize_executor(&args.genesis, args.state_path.as_deref(), args.snapshot_path.as_deref())?;
+            execute(&mut executor, &args.io)?;
+            write_json_output(args.io.output_path.as_deref(), &executor.traces())?;
+        }
+        Command::VerifyRange(args) => {
+            let report = verify_range(
+                &args.rpc,
+                args.from,
+                args.to,
+                args.checkpoint_path.as_deref(),
+                args.vm_versions_dir.as_deref(),
+            )?;
+            write_json_output(args.output_path.as_deref(), &report)?;
+            if let Some(divergence) = report.divergence() {
+                std::process::exit(divergence.exit_code());
+            }
+        }
+    }
 
     Ok(())
 }
@@ -0,0 +1,79 @@
+use std::{fs, path::Path};
+
+use serde_json::Value;
+use starknet_devnet_types::{
+    contract_class::{Cairo0ContractClass, Cairo0Json, ContractClass},
+    traits::HashProducer,
+};
+use tracing::info;
+
+use super::{
+    errors::{DevnetResult, Error},
+    starknet_state::CustomState,
+    types::ClassHash,
+    Starknet,
+};
+
+/// Kind of a class artifact, detected from its content rather than its file name.
+enum ArtifactKind {
+    Sierra,
+    Cairo0,
+    /// CASM is compiled from the Sierra artifact during declaration, so standalone CASM files are
+    /// skipped.
+    Casm,
+    Unknown,
+}
+
+fn artifact_kind(artifact: &Value) -> ArtifactKind {
+    if artifact.get("sierra_program").is_some() {
+        ArtifactKind::Sierra
+    } else if artifact.get("bytecode").is_some() {
+        ArtifactKind::Casm
+    } else if artifact.get("program").is_some() && artifact.get("entry_points_by_type").is_some() {
+        ArtifactKind::Cairo0
+    } else {
+        ArtifactKind::Unknown
+    }
+}
+
+/// Loads every Sierra and Cairo 0 class artifact found in `dir` (non-recursively), sorted by file
+/// name, together with its class hash.
+pub fn load_classes_from_dir(dir: &Path) -> DevnetResult<Vec<(ClassHash, ContractClass)>> {
+    let mut paths = fs::read_dir(dir)
+        .map_err(|source| Error::ReadFileError { source, path: dir.display().to_string() })?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.retain(|path| path.extension().is_some_and(|extension| extension == "json"));
+    paths.sort();
+
+    let mut classes = vec![];
+    for path in paths {
+        let raw = fs::read_to_string(&path)
+            .map_err(|source| Error::ReadFileError { source, path: path.display().to_string() })?;
+        let artifact: Value = serde_json::from_str(&raw)?;
+
+        let contract_class = match artifact_kind(&artifact) {
+            ArtifactKind::Sierra => ContractClass::Cairo1(ContractClass::cairo_1_from_sierra_json_str(&raw)?),
+            ArtifactKind::Cairo0 => {
+                ContractClass::Cairo0(Cairo0ContractClass::RawJson(Cairo0Json::raw_json_from_json_str(&raw)?))
+            }
+            ArtifactKind::Casm | ArtifactKind::Unknown => continue,
+        };
+        let class_hash = contract_class.generate_hash()?;
+        info!("Loaded class {} from {:?}", class_hash.to_prefixed_hex_str(), path);
+        classes.push((class_hash, contract_class));
+    }
+
+    Ok(classes)
+}
+
+impl Starknet {
+    /// Pre-declares every class artifact found in `dir` into the current state, so transactions
+    /// can reference those classes without declaring them first.
+    pub fn predeclare_classes_from_dir(&mut self, dir: &Path) -> DevnetResult<()> {
+        for (class_hash, contract_class) in load_classes_from_dir(dir)? {
+            self.state.predeclare_contract_class(class_hash, contract_class)?;
+        }
+        Ok(())
+    }
+}
@@ -12,6 +12,7 @@ use starknet_devnet_types::traits::ToHexString;
 use tracing::info;
 
 use super::starknet_config::ForkConfig;
+use crate::utils::blocking_http_client;
 
 #[derive(thiserror::Error, Debug)]
 enum OriginError {
@@ -35,7 +36,7 @@ struct BlockingOriginReader {
 
 impl BlockingOriginReader {
     fn new(url: url::Url, block_number: u64) -> Self {
-        Self { url, block_number, client: reqwest::blocking::Client::new() }
+        Self { url, block_number, client: blocking_http_client() }
     }
 
     fn send_body(&self, method: &str, mut params: serde_json::Value) -> Result<serde_json::Value, OriginError> {
@@ -7,7 +7,7 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("Account path not provided")]
+    #[error("Neither account path nor genesis path provided")]
     AccPathNotProvided,
     #[error(transparent)]
     StarknetApiError(#[from] starknet_api::StarknetApiError),
@@ -51,6 +51,8 @@ pub enum Error {
     UnsupportedTransactionType,
     #[error("{msg}")]
     UnsupportedAction { msg: String },
+    #[error("No VM version is old enough for Starknet {starknet_version}")]
+    NoVmVersion { starknet_version: String },
     #[error("Unexpected internal error: {msg}")]
     UnexpectedInternalError { msg: String },
     #[error("Failed to load ContractClass: {0}")]
@@ -71,6 +73,10 @@ pub enum Error {
     MessagingError(#[from] MessagingError),
     #[error("Transaction has no trace")]
     NoTransactionTrace,
+    #[error("Unsupported state snapshot version {found}, expected {expected}")]
+    UnsupportedSnapshotVersion { found: u64, expected: u32 },
+    #[error("Request {method} to the node failed: {reason}")]
+    RpcError { method: String, reason: String },
 }
 
 #[derive(Debug, Error)]
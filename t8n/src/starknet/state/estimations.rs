@@ -11,7 +11,6 @@ use starknet_devnet_types::rpc::transactions::BroadcastedTransaction;
 use starknet_rs_core::types::{BlockId, MsgFromL1, PriceUnit};
 
 use super::errors::{DevnetResult, Error};
-use super::utils::get_versioned_constants;
 use super::Starknet;
 
 pub fn estimate_fee(
@@ -94,8 +93,10 @@ fn estimate_transaction_fee<S: StateReader>(
         return Err(Error::ExecutionError { revert_error });
     }
 
-    let gas_vector =
-        fee_utils::calculate_tx_gas_vector(&transaction_execution_info.actual_resources, &get_versioned_constants())?;
+    let gas_vector = fee_utils::calculate_tx_gas_vector(
+        &transaction_execution_info.actual_resources,
+        block_context.versioned_constants(),
+    )?;
 
     let total_fee = fee_utils::get_fee_by_gas_vector(block_context.block_info(), gas_vector, &fee_type);
 
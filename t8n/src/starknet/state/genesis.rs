@@ -0,0 +1,158 @@
+use std::{fs::File, io::BufReader, path::Path, sync::Arc};
+
+use blockifier::{abi::sierra_types::next_storage_key, state::state_api::State};
+use serde::Deserialize;
+use starknet_api::{
+    core::calculate_contract_address,
+    transaction::{Calldata, ContractAddressSalt},
+};
+use starknet_devnet_types::{
+    contract_address::ContractAddress,
+    felt::{split_biguint, Felt},
+    num_bigint::BigUint,
+    patricia_key::PatriciaKey,
+};
+
+use super::{
+    account::UserAccount,
+    constants::{ETH_ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS},
+    errors::{DevnetResult, Error, StateError},
+    starknet_config::BlockGasPrices,
+    starknet_state::CustomStateReader,
+    traits::Deployed,
+    utils::get_storage_var_address,
+    Starknet,
+};
+
+/// Salt used to compute the address of genesis accounts that specify neither `address` nor `salt`.
+const DEFAULT_ACCOUNT_SALT: u128 = 20;
+
+/// Genesis specification of a t8n run: predeployed accounts, token balances and raw storage
+/// overrides applied on top of the initial state, for reproducible test environments.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Genesis {
+    /// Gas prices of the first block.
+    #[serde(default)]
+    pub gas_prices: BlockGasPrices,
+    #[serde(default)]
+    pub accounts: Vec<GenesisAccount>,
+    #[serde(default)]
+    pub balances: Vec<GenesisBalance>,
+    #[serde(default)]
+    pub storage: Vec<GenesisStorageEntry>,
+}
+
+/// Predeployed account. The address is either given explicitly or computed from `salt`, the class
+/// hash and the public key as constructor calldata.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisAccount {
+    pub public_key: Felt,
+    /// Class of the account, which must already be declared; defaults to the configured account
+    /// class.
+    pub class_hash: Option<Felt>,
+    pub address: Option<Felt>,
+    pub salt: Option<Felt>,
+    /// Initial ETH and STRK balance.
+    #[serde(default)]
+    pub balance: Felt,
+}
+
+/// ERC20 balance of `address`. `token` is `ETH`, `STRK` or the address of a deployed ERC20 contract.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisBalance {
+    pub token: String,
+    pub address: Felt,
+    pub amount: Felt,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisStorageEntry {
+    pub contract_address: Felt,
+    pub key: Felt,
+    pub value: Felt,
+}
+
+impl Genesis {
+    pub fn from_file(path: &Path) -> DevnetResult<Self> {
+        let file =
+            File::open(path).map_err(|source| Error::ReadFileError { source, path: path.display().to_string() })?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+}
+
+impl GenesisAccount {
+    fn address(&self, class_hash: Felt) -> DevnetResult<ContractAddress> {
+        if let Some(address) = self.address {
+            return Ok(ContractAddress::new(address)?);
+        }
+
+        let salt = self.salt.unwrap_or(Felt::from(DEFAULT_ACCOUNT_SALT));
+        let address = calculate_contract_address(
+            ContractAddressSalt(salt.into()),
+            class_hash.into(),
+            &Calldata(Arc::new(vec![self.public_key.into()])),
+            starknet_api::core::ContractAddress::default(),
+        )?;
+        Ok(ContractAddress::from(address))
+    }
+}
+
+fn token_address(token: &str) -> DevnetResult<ContractAddress> {
+    let address = match token {
+        "ETH" => ETH_ERC20_CONTRACT_ADDRESS,
+        "STRK" => STRK_ERC20_CONTRACT_ADDRESS,
+        address => address,
+    };
+    Ok(ContractAddress::new(Felt::from_prefixed_hex_str(address)?)?)
+}
+
+impl Starknet {
+    /// Applies the genesis specification directly to the most underlying state.
+    pub fn apply_genesis(&mut self, genesis: &Genesis) -> DevnetResult<()> {
+        for account in genesis.accounts.iter() {
+            let class_hash = account.class_hash.unwrap_or(self.config.account_contract_class_hash);
+            let contract_class = self
+                .state
+                .get_rpc_contract_class(&class_hash)
+                .cloned()
+                .ok_or(Error::StateError(StateError::NoneClassHash(class_hash)))?;
+
+            let user_account = UserAccount::new(
+                account.balance.into(),
+                account.public_key,
+                account.address(class_hash)?,
+                class_hash,
+                contract_class,
+                self.predeployed_accounts.eth_fee_token_address,
+                self.predeployed_accounts.strk_fee_token_address,
+            )?;
+            user_account.deploy(&mut self.state)?;
+            self.predeployed_accounts.accounts.push(user_account);
+        }
+
+        for balance in genesis.balances.iter() {
+            let token_address: starknet_api::core::ContractAddress = token_address(&balance.token)?.try_into()?;
+            let balance_key_low = get_storage_var_address("ERC20_balances", &[balance.address])?;
+            let balance_key_high = next_storage_key(&balance_key_low.try_into()?)?;
+
+            let amount: BigUint = balance.amount.into();
+            let (high, low) = split_biguint(amount)?;
+            self.state.state.state.set_storage_at(token_address, balance_key_low.try_into()?, low.into())?;
+            self.state.state.state.set_storage_at(token_address, balance_key_high, high.into())?;
+        }
+
+        for entry in genesis.storage.iter() {
+            self.state.state.state.set_storage_at(
+                ContractAddress::new(entry.contract_address)?.try_into()?,
+                PatriciaKey::new(entry.key)?.try_into()?,
+                entry.value.into(),
+            )?;
+        }
+
+        self.set_next_block_gas_prices(&genesis.gas_prices)
+    }
+}
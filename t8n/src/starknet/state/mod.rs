@@ -3,6 +3,7 @@ pub mod add_declare_transaction;
 pub mod add_deploy_account_transaction;
 pub mod add_invoke_transaction;
 pub mod add_l1_handler_transaction;
+pub mod classes_dir;
 pub mod constants;
 pub mod contract_class_choice;
 pub mod defaulter;
@@ -11,10 +12,13 @@ pub mod dump;
 pub mod errors;
 pub mod estimations;
 pub mod events;
+pub mod genesis;
 pub mod get_class_impls;
 pub mod predeployed;
 pub mod predeployed_accounts;
 pub mod raw_execution;
+pub mod rpc_receipt;
+pub mod snapshot;
 pub mod starknet_blocks;
 pub mod starknet_config;
 pub mod starknet_state;
@@ -26,6 +30,7 @@ pub mod traits;
 pub mod transaction_trace;
 pub mod types;
 pub mod utils;
+pub mod vm_versions;
 use std::{num::NonZeroU128, sync::Arc};
 
 use super::messaging::MessagingBroker;
@@ -45,7 +50,7 @@ use blockifier::{
 use constants::{
     CHARGEABLE_ACCOUNT_ADDRESS, CHARGEABLE_ACCOUNT_PRIVATE_KEY, DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_DATA_GAS_PRICE,
     DEVNET_DEFAULT_GAS_PRICE, DEVNET_DEFAULT_STARTING_BLOCK_NUMBER, ETH_ERC20_CONTRACT_ADDRESS, ETH_ERC20_NAME,
-    ETH_ERC20_SYMBOL, STRK_ERC20_CONTRACT_ADDRESS, STRK_ERC20_NAME, STRK_ERC20_SYMBOL,
+    ETH_ERC20_SYMBOL, STARKNET_VERSION, STRK_ERC20_CONTRACT_ADDRESS, STRK_ERC20_NAME, STRK_ERC20_SYMBOL,
 };
 use contract_class_choice::AccountContractClassChoice;
 use defaulter::StarknetDefaulter;
@@ -55,13 +60,14 @@ use predeployed::initialize_erc20_at_address;
 use predeployed_accounts::UserDeployedAccounts;
 use raw_execution::{Call, RawExecution};
 use serde::Serialize;
+use snapshot::TransactionHistory;
 use starknet_api::{
     block::{BlockNumber, BlockStatus, BlockTimestamp, GasPrice, GasPricePerToken},
     core::SequencerContractAddress,
     transaction::Fee,
 };
-use starknet_blocks::{StarknetBlock, StarknetBlocks};
-use starknet_config::{StarknetConfig, StateArchiveCapacity};
+use starknet_blocks::{ExecutedBlock, StarknetBlock, StarknetBlocks};
+use starknet_config::{BlockGasPrices, StarknetConfig, StateArchiveCapacity};
 use starknet_devnet_types::{
     chain_id::ChainId,
     contract_address::ContractAddress,
@@ -96,10 +102,11 @@ use state_diff::StateDiff;
 use state_update::StateUpdate;
 use std::path::Path;
 
-use tracing::{error, info};
+use tracing::{debug, error, info};
 use traits::{Deployed, HashIdentified, HashIdentifiedMut, UserAccountGenerator};
 use transaction_trace::create_trace;
 use utils::get_versioned_constants;
+use vm_versions::VmVersions;
 
 #[derive(Debug, Serialize)]
 pub struct Starknet {
@@ -124,6 +131,17 @@ pub struct Starknet {
     #[serde(skip_serializing)]
     pub dump_events: Vec<DumpEvent>,
     pub transaction_receipts: Vec<TransactionReceipt>,
+    /// Blocks executed in this run, in execution order.
+    pub executed_blocks: Vec<ExecutedBlock>,
+    /// Transactions executed before the state was loaded from a snapshot.
+    #[serde(skip_serializing)]
+    pub transaction_history: TransactionHistory,
+    /// Rules blocks are executed with, by Starknet version; the built-in ones when empty.
+    #[serde(skip_serializing)]
+    pub vm_versions: VmVersions,
+    /// Starknet version of the rules the pending block is executed with.
+    #[serde(skip_serializing)]
+    pub starknet_version: String,
 }
 
 impl Default for Starknet {
@@ -147,12 +165,17 @@ impl Default for Starknet {
             messaging: Default::default(),
             dump_events: Default::default(),
             transaction_receipts: Default::default(),
+            executed_blocks: Default::default(),
+            transaction_history: Default::default(),
+            vm_versions: Default::default(),
+            starknet_version: STARKNET_VERSION.to_string(),
         }
     }
 }
 
 impl Starknet {
-    pub fn new(config: &StarknetConfig, acc_path: &Path) -> DevnetResult<Self> {
+    /// Creates the initial state. User accounts are predeployed from `acc_path` when given.
+    pub fn new(config: &StarknetConfig, acc_path: Option<&Path>) -> DevnetResult<Self> {
         let defaulter = StarknetDefaulter::new(config.fork_config.clone());
         let mut state = StarknetState::new(defaulter);
 
@@ -166,29 +189,37 @@ impl Starknet {
         let eth_erc20_fee_contract = predeployed::create_erc20_at_address(ETH_ERC20_CONTRACT_ADDRESS)?;
         let strk_erc20_fee_contract = predeployed::create_erc20_at_address(STRK_ERC20_CONTRACT_ADDRESS)?;
 
-        let udc_contract = predeployed::create_udc()?;
-        udc_contract.deploy(&mut state)?;
+        // When forking, the origin already has these contracts, local copies would shadow them.
+        let forking = config.fork_config.url.is_some();
+        if !forking {
+            let udc_contract = predeployed::create_udc()?;
+            udc_contract.deploy(&mut state)?;
 
-        eth_erc20_fee_contract.deploy(&mut state)?;
-        initialize_erc20_at_address(&mut state, ETH_ERC20_CONTRACT_ADDRESS, ETH_ERC20_NAME, ETH_ERC20_SYMBOL)?;
+            eth_erc20_fee_contract.deploy(&mut state)?;
+            initialize_erc20_at_address(&mut state, ETH_ERC20_CONTRACT_ADDRESS, ETH_ERC20_NAME, ETH_ERC20_SYMBOL)?;
 
-        strk_erc20_fee_contract.deploy(&mut state)?;
-        initialize_erc20_at_address(&mut state, STRK_ERC20_CONTRACT_ADDRESS, STRK_ERC20_NAME, STRK_ERC20_SYMBOL)?;
+            strk_erc20_fee_contract.deploy(&mut state)?;
+            initialize_erc20_at_address(&mut state, STRK_ERC20_CONTRACT_ADDRESS, STRK_ERC20_NAME, STRK_ERC20_SYMBOL)?;
+        }
 
         let mut predeployed_accounts =
             UserDeployedAccounts::new(eth_erc20_fee_contract.get_address(), strk_erc20_fee_contract.get_address());
 
-        let accounts = predeployed_accounts
-            .generate_accounts(acc_path, config.account_contract_class_hash, &config.account_contract_class)
-            .unwrap();
+        if let Some(acc_path) = acc_path {
+            let accounts = predeployed_accounts
+                .generate_accounts(acc_path, config.account_contract_class_hash, &config.account_contract_class)
+                .unwrap();
 
-        for account in accounts {
-            account.deploy(&mut state)?;
+            for account in accounts {
+                account.deploy(&mut state)?;
+            }
         }
 
-        let chargeable_account =
-            Account::new_chargeable(eth_erc20_fee_contract.get_address(), strk_erc20_fee_contract.get_address())?;
-        chargeable_account.deploy(&mut state)?;
+        if !forking {
+            let chargeable_account =
+                Account::new_chargeable(eth_erc20_fee_contract.get_address(), strk_erc20_fee_contract.get_address())?;
+            chargeable_account.deploy(&mut state)?;
+        }
 
         state.commit_with_diff()?;
 
@@ -215,6 +246,10 @@ impl Starknet {
             messaging: Default::default(),
             dump_events: Default::default(),
             transaction_receipts: Default::default(),
+            executed_blocks: Default::default(),
+            transaction_history: Default::default(),
+            vm_versions: Default::default(),
+            starknet_version: STARKNET_VERSION.to_string(),
         };
 
         this.restart_pending_block()?;
@@ -260,6 +295,10 @@ impl Starknet {
             messaging: Default::default(),
             dump_events: Default::default(),
             transaction_receipts: Default::default(),
+            executed_blocks: Default::default(),
+            transaction_history: Default::default(),
+            vm_versions: Default::default(),
+            starknet_version: STARKNET_VERSION.to_string(),
         };
 
         this.restart_pending_block()?;
@@ -284,7 +323,7 @@ impl Starknet {
 
     pub fn restart(&mut self, acc_path: &Path) -> DevnetResult<()> {
         self.config.re_execute_on_init = false;
-        *self = Starknet::new(&self.config, acc_path)?;
+        *self = Starknet::new(&self.config, Some(acc_path))?;
         info!("Starknet Devnet restarted");
 
         Ok(())
@@ -462,7 +501,12 @@ impl Starknet {
         let state_diff = self.state.diff_trace()?;
 
         let trace = create_trace(&mut self.state.state, transaction.get_type(), &tx_info, state_diff.clone().into())?;
-        let transaction_to_add = StarknetTransaction::create_accepted(transaction, tx_info, trace);
+        let transaction_to_add = StarknetTransaction::create_accepted(
+            transaction,
+            tx_info,
+            trace,
+            self.block_context.versioned_constants(),
+        )?;
 
         // add accepted transaction to pending block
         self.blocks.pending_block.add_transaction(*transaction_hash);
@@ -518,7 +562,72 @@ impl Starknet {
         block_info.block_number = block_info.block_number.next();
         // TODO: update block_context via preferred method in the documentation
         *block_context =
-            BlockContext::new_unchecked(&block_info, block_context.chain_info(), &get_versioned_constants());
+            BlockContext::new_unchecked(&block_info, block_context.chain_info(), block_context.versioned_constants());
+    }
+
+    /// Sets the gas prices used by the pending block and the blocks after it. Must be called before
+    /// any transaction is added to the pending block.
+    pub fn set_next_block_gas_prices(&mut self, prices: &BlockGasPrices) -> DevnetResult<()> {
+        if !self.pending_block().get_transactions().is_empty() {
+            return Err(Error::UnsupportedAction {
+                msg: "Gas prices cannot be changed once the pending block has transactions".to_string(),
+            });
+        }
+
+        let mut block_info = self.block_context.block_info().clone();
+        let gas_prices = &mut block_info.gas_prices;
+        if let Some(gas_price) = prices.gas_price {
+            gas_prices.eth_l1_gas_price = gas_price;
+        }
+        if let Some(data_gas_price) = prices.data_gas_price {
+            gas_prices.eth_l1_data_gas_price = data_gas_price;
+        }
+        if let Some(strk_gas_price) = prices.strk_gas_price {
+            gas_prices.strk_l1_gas_price = strk_gas_price;
+        }
+        if let Some(strk_data_gas_price) = prices.strk_data_gas_price {
+            gas_prices.strk_l1_data_gas_price = strk_data_gas_price;
+        }
+
+        self.block_context = BlockContext::new_unchecked(
+            &block_info,
+            self.block_context.chain_info(),
+            self.block_context.versioned_constants(),
+        );
+        self.restart_pending_block()
+    }
+
+    /// Executes the pending block and the blocks after it with the rules of `starknet_version`, see
+    /// [VmVersions], or with the built-in rules when it is `None`. Fails if none of the loaded
+    /// versions is old enough. Must be called before any transaction is added to the pending block.
+    pub fn set_next_block_starknet_version(&mut self, starknet_version: Option<&str>) -> DevnetResult<()> {
+        if self.vm_versions.is_empty() {
+            return Ok(());
+        }
+        if !self.pending_block().get_transactions().is_empty() {
+            return Err(Error::UnsupportedAction {
+                msg: "The Starknet version cannot be changed once the pending block has transactions".to_string(),
+            });
+        }
+        let (version, constants) = match starknet_version {
+            Some(starknet_version) => {
+                let (version, constants) = self
+                    .vm_versions
+                    .select(starknet_version)
+                    .ok_or_else(|| Error::NoVmVersion { starknet_version: starknet_version.to_string() })?;
+                debug!("Executing a block of Starknet {} with the VM version of {}", starknet_version, version);
+                (version.to_string(), constants.clone())
+            }
+            None => {
+                debug!("Executing a block of an unknown Starknet version with the built-in VM version");
+                (STARKNET_VERSION.to_string(), get_versioned_constants())
+            }
+        };
+
+        self.starknet_version = version;
+        self.block_context =
+            BlockContext::new_unchecked(self.block_context.block_info(), self.block_context.chain_info(), &constants);
+        self.restart_pending_block()
     }
 
     fn update_block_context_block_timestamp(block_context: &mut BlockContext, block_timestamp: BlockTimestamp) {
@@ -527,7 +636,7 @@ impl Starknet {
 
         // TODO: update block_context via preferred method in the documentation
         *block_context =
-            BlockContext::new_unchecked(&block_info, block_context.chain_info(), &get_versioned_constants());
+            BlockContext::new_unchecked(&block_info, block_context.chain_info(), block_context.versioned_constants());
     }
 
     pub fn pending_block(&self) -> &StarknetBlock {
@@ -548,6 +657,7 @@ impl Starknet {
             price_in_wei: GasPrice(self.block_context.block_info().gas_prices.eth_l1_data_gas_price.get()),
         };
         block.header.sequencer = SequencerContractAddress(self.block_context.block_info().sequencer_address);
+        block.starknet_version = self.starknet_version.clone();
 
         self.blocks.pending_block = block;
 
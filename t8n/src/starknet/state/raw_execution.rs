@@ -1,13 +1,11 @@
 /// Copied from https://github.com/xJonathanLEI/starknet-rs/
-use starknet_rs_core::{crypto::pedersen_hash, types::FieldElement};
+use crypto_utils::curve::signer::compute_hash_on_elements;
+use crypto_utils::protocol::PREFIX_INVOKE;
+use starknet_rs_core::types::FieldElement;
+use starknet_types_core::felt::Felt;
 
 use super::constants::SUPPORTED_TX_VERSION;
 
-/// Cairo string for "invoke"
-const PREFIX_INVOKE: FieldElement =
-    FieldElement::from_mont([18443034532770911073, 18446744073709551615, 18446744073709551615, 513398556346534256]);
-// TODO try using TransactionHashPrefix instead
-
 #[derive(Debug, Clone)]
 pub struct Call {
     pub to: FieldElement,
@@ -22,15 +20,12 @@ pub struct RawExecution {
     pub max_fee: FieldElement,
 }
 
-pub fn compute_hash_on_elements(data: &[FieldElement]) -> FieldElement {
-    let mut current_hash = FieldElement::ZERO;
-
-    for item in data.iter() {
-        current_hash = pedersen_hash(&current_hash, item);
-    }
+fn to_felt(element: &FieldElement) -> Felt {
+    Felt::from_bytes_be(&element.to_bytes_be())
+}
 
-    let data_len = FieldElement::from(data.len());
-    pedersen_hash(&current_hash, &data_len)
+fn to_field_element(felt: Felt) -> FieldElement {
+    FieldElement::from_bytes_be(&felt.to_bytes_be()).expect("felts are below the field modulus")
 }
 
 impl RawExecution {
@@ -56,15 +51,16 @@ impl RawExecution {
     }
 
     pub fn transaction_hash(&self, chain_id: FieldElement, address: FieldElement) -> FieldElement {
-        compute_hash_on_elements(&[
+        let calldata: Vec<Felt> = self.raw_calldata().iter().map(to_felt).collect();
+        to_field_element(compute_hash_on_elements(&[
             PREFIX_INVOKE,
-            FieldElement::from(SUPPORTED_TX_VERSION), // version
-            address,
-            FieldElement::ZERO, // entry_point_selector
-            compute_hash_on_elements(&self.raw_calldata()),
-            self.max_fee,
-            chain_id,
-            self.nonce,
-        ])
+            Felt::from(SUPPORTED_TX_VERSION), // version
+            to_felt(&address),
+            Felt::ZERO, // entry_point_selector
+            compute_hash_on_elements(&calldata),
+            to_felt(&self.max_fee),
+            to_felt(&chain_id),
+            to_felt(&self.nonce),
+        ]))
     }
 }
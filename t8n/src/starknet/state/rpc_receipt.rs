@@ -0,0 +1,192 @@
+use blockifier::transaction::objects::GasVector;
+use serde_json::{Map, Value};
+use starknet_devnet_types::rpc::transactions::Transaction;
+
+use super::{
+    errors::{DevnetResult, Error},
+    starknet_transactions::StarknetTransaction,
+};
+
+/// OpenRPC spec version of the emitted receipts.
+#[derive(Default, Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum RpcReceiptVersion {
+    #[default]
+    #[clap(name = "0.7")]
+    V0_7,
+    #[clap(name = "0.8")]
+    V0_8,
+}
+
+/// Fields shared by receipts of every transaction type, in the order of the spec.
+const COMMON_RECEIPT_FIELDS: [&str; 8] = [
+    "transaction_hash",
+    "actual_fee",
+    "finality_status",
+    "execution_status",
+    "block_hash",
+    "block_number",
+    "messages_sent",
+    "events",
+];
+
+impl StarknetTransaction {
+    /// Returns the receipt exactly in the `starknet_getTransactionReceipt` schema of the given spec
+    /// version, so it can be diffed directly against responses of live nodes.
+    ///
+    /// Devnet receipts differ from the spec, e.g. invoke transactions deploying through the UDC are
+    /// reported as `DEPLOY` receipts with a `contract_address`.
+    pub fn get_rpc_receipt(&self, version: RpcReceiptVersion) -> DevnetResult<Value> {
+        let receipt = match serde_json::to_value(self.get_receipt()?)? {
+            Value::Object(receipt) => receipt,
+            _ => return Err(Error::SerializationError { origin: "Receipt is not a JSON object".to_string() }),
+        };
+
+        let r#type = match &self.inner.transaction {
+            Transaction::Declare(_) => "DECLARE",
+            Transaction::DeployAccount(_) => "DEPLOY_ACCOUNT",
+            Transaction::Invoke(_) => "INVOKE",
+            Transaction::L1Handler(_) => "L1_HANDLER",
+            Transaction::Deploy(_) => "DEPLOY",
+        };
+
+        Ok(rpc_receipt(r#type, &receipt, version, &self.gas_vector))
+    }
+}
+
+/// Converts the devnet `receipt` of a transaction of type `r#type` to the spec of `version`.
+fn rpc_receipt(
+    r#type: &str,
+    receipt: &Map<String, Value>,
+    version: RpcReceiptVersion,
+    gas_vector: &GasVector,
+) -> Value {
+    let mut rpc_receipt = Map::new();
+    rpc_receipt.insert("type".to_string(), Value::from(r#type));
+    for field in COMMON_RECEIPT_FIELDS {
+        if let Some(value) = receipt.get(field).filter(|value| !value.is_null()) {
+            rpc_receipt.insert(field.to_string(), value.clone());
+        }
+    }
+
+    if receipt.get("execution_status").and_then(Value::as_str) == Some("REVERTED") {
+        if let Some(revert_reason) = receipt.get("revert_reason") {
+            rpc_receipt.insert("revert_reason".to_string(), revert_reason.clone());
+        }
+    }
+
+    match r#type {
+        "DEPLOY_ACCOUNT" | "DEPLOY" => {
+            if let Some(contract_address) = receipt.get("contract_address") {
+                rpc_receipt.insert("contract_address".to_string(), contract_address.clone());
+            }
+        }
+        "L1_HANDLER" => {
+            if let Some(message_hash) = receipt.get("message_hash") {
+                rpc_receipt.insert("message_hash".to_string(), message_hash.clone());
+            }
+        }
+        _ => {}
+    }
+
+    let execution_resources = match version {
+        RpcReceiptVersion::V0_7 => receipt.get("execution_resources").map(without_nulls).unwrap_or(Value::Null),
+        RpcReceiptVersion::V0_8 => execution_resources_v0_8(gas_vector),
+    };
+    rpc_receipt.insert("execution_resources".to_string(), execution_resources);
+
+    Value::Object(rpc_receipt)
+}
+
+/// Builds the v0.8 `execution_resources` from the gas vector of the execution, computed with the
+/// constants of its block.
+///
+/// `l2_gas` is always zero: the blockifier t8n is built with predates the L2 gas of Starknet
+/// 0.13.4 and doesn't charge it, and nodes report zero L2 gas for the blocks of the versions before
+/// it too.
+fn execution_resources_v0_8(gas_vector: &GasVector) -> Value {
+    serde_json::json!({
+        "l1_gas": gas_vector.l1_gas,
+        "l1_data_gas": gas_vector.l1_data_gas,
+        "l2_gas": 0,
+    })
+}
+
+fn without_nulls(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key.clone(), without_nulls(value)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn devnet_receipt() -> Map<String, Value> {
+        let receipt = json!({
+            "type": "DEPLOY",
+            "transaction_hash": "0x1",
+            "actual_fee": { "amount": "0x64", "unit": "WEI" },
+            "finality_status": "ACCEPTED_ON_L2",
+            "execution_status": "SUCCEEDED",
+            "block_hash": null,
+            "block_number": null,
+            "messages_sent": [],
+            "events": [{ "from_address": "0x2", "keys": ["0x3"], "data": [] }],
+            "revert_reason": "ignored when succeeded",
+            "contract_address": "0x4",
+            "execution_resources": {
+                "steps": 1000,
+                "memory_holes": null,
+                "pedersen_builtin_applications": 3,
+                "data_availability": { "l1_gas": 0, "l1_data_gas": 128 }
+            }
+        });
+        receipt.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn v0_7_receipts_follow_the_spec_of_their_type() {
+        let gas_vector = GasVector { l1_gas: 5, l1_data_gas: 128 };
+        let receipt = rpc_receipt("INVOKE", &devnet_receipt(), RpcReceiptVersion::V0_7, &gas_vector);
+        assert_eq!(
+            receipt,
+            json!({
+                "type": "INVOKE",
+                "transaction_hash": "0x1",
+                "actual_fee": { "amount": "0x64", "unit": "WEI" },
+                "finality_status": "ACCEPTED_ON_L2",
+                "execution_status": "SUCCEEDED",
+                "messages_sent": [],
+                "events": [{ "from_address": "0x2", "keys": ["0x3"], "data": [] }],
+                "execution_resources": {
+                    "steps": 1000,
+                    "pedersen_builtin_applications": 3,
+                    "data_availability": { "l1_gas": 0, "l1_data_gas": 128 }
+                }
+            })
+        );
+
+        let mut reverted = devnet_receipt();
+        reverted.insert("execution_status".to_string(), json!("REVERTED"));
+        let receipt = rpc_receipt("DEPLOY_ACCOUNT", &reverted, RpcReceiptVersion::V0_7, &gas_vector);
+        assert_eq!(receipt["revert_reason"], "ignored when succeeded");
+        assert_eq!(receipt["contract_address"], "0x4");
+    }
+
+    #[test]
+    fn v0_8_receipts_report_the_gas_of_the_execution() {
+        let gas_vector = GasVector { l1_gas: 5, l1_data_gas: 128 };
+        let receipt = rpc_receipt("INVOKE", &devnet_receipt(), RpcReceiptVersion::V0_8, &gas_vector);
+        assert_eq!(receipt["execution_resources"], json!({ "l1_gas": 5, "l1_data_gas": 128, "l2_gas": 0 }));
+        assert_eq!(receipt["actual_fee"], json!({ "amount": "0x64", "unit": "WEI" }));
+        assert!(receipt.get("contract_address").is_none());
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{
+    errors::{DevnetResult, Error},
+    starknet_blocks::StarknetBlock,
+    starknet_state::{StarknetState, StateWithBlockNumber},
+    Starknet,
+};
+
+/// Version of the [`StateSnapshot`] schema. Bump it whenever the layout of the snapshot changes.
+pub const STATE_SNAPSHOT_VERSION: u32 = 1;
+
+/// Fields holding arrays collected from hash maps, whose order carries no meaning: those of RPC
+/// state diffs and of [StateDiff](super::state_diff::StateDiff).
+const UNORDERED_ARRAYS: [&str; 9] = [
+    "storage_diffs",
+    "storage_entries",
+    "nonces",
+    "deployed_contracts",
+    "declared_classes",
+    "deprecated_declared_classes",
+    "replaced_classes",
+    "declared_contracts",
+    "cairo_0_declared_contracts",
+];
+
+/// Transactions and receipts executed before the snapshot was taken.
+///
+/// They are kept as raw JSON, since they are only carried over into the next snapshot and are never
+/// re-executed.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionHistory {
+    pub transactions: Vec<Value>,
+    pub transaction_receipts: Vec<Value>,
+}
+
+/// Full state of a t8n run: declared classes (including CASM), storage, nonces, balances (as ERC20
+/// storage), blocks, transactions and receipts.
+///
+/// A snapshot is dumped after a run and can be loaded to continue execution on top of it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub version: u32,
+    pub state: StarknetState,
+    pub blocks: Vec<StarknetBlock>,
+    #[serde(flatten)]
+    pub history: TransactionHistory,
+}
+
+impl StateSnapshot {
+    pub fn from_starknet(starknet: &Starknet) -> DevnetResult<Self> {
+        let state = serde_json::from_value(serde_json::to_value(&starknet.state)?)?;
+
+        let mut history = starknet.transaction_history.clone();
+        for (_, transaction) in starknet.transactions.iter() {
+            history.transactions.push(serde_json::to_value(&transaction.inner)?);
+        }
+        for receipt in starknet.transaction_receipts.iter() {
+            history.transaction_receipts.push(serde_json::to_value(receipt)?);
+        }
+
+        Ok(Self { version: STATE_SNAPSHOT_VERSION, state, blocks: starknet.blocks.get_all_blocks(), history })
+    }
+
+    /// Serializes the snapshot with all object keys sorted, as well as the arrays of
+    /// [UNORDERED_ARRAYS], so the same state always produces the same output regardless of the
+    /// iteration order of the underlying hash maps.
+    pub fn to_canonical_json(&self) -> DevnetResult<Value> {
+        Ok(canonicalize(serde_json::to_value(self)?))
+    }
+
+    pub fn from_json(value: Value) -> DevnetResult<Self> {
+        let version = value.get("version").and_then(Value::as_u64).ok_or_else(|| Error::DeserializationError {
+            origin: "State snapshot is missing the `version` field".to_string(),
+        })?;
+        if version != u64::from(STATE_SNAPSHOT_VERSION) {
+            return Err(Error::UnsupportedSnapshotVersion { found: version, expected: STATE_SNAPSHOT_VERSION });
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+impl Starknet {
+    pub fn from_snapshot(snapshot: StateSnapshot) -> DevnetResult<Self> {
+        let block_number = snapshot.blocks.last().map(|block| block.block_number()).ok_or(Error::NoBlock)?;
+
+        let mut this = Self::from_init_state(StateWithBlockNumber { state: snapshot.state, block_number })?;
+        this.blocks.restore(snapshot.blocks);
+        this.transaction_history = snapshot.history;
+
+        Ok(this)
+    }
+}
+
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let sorted: BTreeMap<String, Value> = map
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = canonicalize(value);
+                    match value {
+                        Value::Array(mut values) if UNORDERED_ARRAYS.contains(&key.as_str()) => {
+                            values.sort_by_cached_key(Value::to_string);
+                            (key, Value::Array(values))
+                        }
+                        value => (key, value),
+                    }
+                })
+                .collect();
+            Value::Object(sorted.into_iter().collect::<Map<String, Value>>())
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(canonicalize).collect()),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use blockifier::state::state_api::{State, StateReader};
+    use serde_json::json;
+    use starknet_api::{
+        core::{ContractAddress, Nonce},
+        patricia_key, stark_felt,
+        state::StorageKey,
+    };
+
+    use super::*;
+    use crate::starknet::state::starknet_config::StarknetConfig;
+
+    fn starknet() -> Starknet {
+        let acc_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/starknet/input/acc.json");
+        Starknet::new(&StarknetConfig::default(), Some(&acc_path)).unwrap()
+    }
+
+    #[test]
+    fn snapshot_round_trip_is_deterministic() {
+        let dumped = StateSnapshot::from_starknet(&starknet()).unwrap().to_canonical_json().unwrap();
+
+        let loaded = Starknet::from_snapshot(StateSnapshot::from_json(dumped.clone()).unwrap()).unwrap();
+        let reloaded = StateSnapshot::from_starknet(&loaded).unwrap().to_canonical_json().unwrap();
+
+        assert_eq!(serde_json::to_string(&dumped).unwrap(), serde_json::to_string(&reloaded).unwrap());
+    }
+
+    #[test]
+    fn snapshot_round_trip_keeps_storage_and_nonces() {
+        let mut starknet = starknet();
+        let entries: Vec<_> = (1u32..=5)
+            .map(|n| (ContractAddress(patricia_key!(0x100u32 + n)), StorageKey(patricia_key!(n)), stark_felt!(7 * n)))
+            .collect();
+        for (address, key, value) in &entries {
+            starknet.state.set_storage_at(*address, *key, *value).unwrap();
+            starknet.state.increment_nonce(*address).unwrap();
+        }
+        let diff = starknet.state.commit_with_diff().unwrap();
+        starknet.generate_new_block(diff).unwrap();
+
+        let dumped = StateSnapshot::from_starknet(&starknet).unwrap().to_canonical_json().unwrap();
+        let mut loaded = Starknet::from_snapshot(StateSnapshot::from_json(dumped.clone()).unwrap()).unwrap();
+        for (address, key, value) in &entries {
+            assert_eq!(loaded.state.get_storage_at(*address, *key).unwrap(), *value);
+            assert_eq!(loaded.state.get_nonce_at(*address).unwrap(), Nonce(stark_felt!(1u32)));
+        }
+
+        let reloaded = StateSnapshot::from_starknet(&loaded).unwrap().to_canonical_json().unwrap();
+        assert_eq!(serde_json::to_string(&dumped).unwrap(), serde_json::to_string(&reloaded).unwrap());
+    }
+
+    #[test]
+    fn state_diff_arrays_are_sorted() {
+        let events = json!([{ "data": ["0x2"] }, { "data": ["0x1"] }]);
+        let receipt = |nonces: Value| json!({ "state_diff": { "nonces": nonces }, "events": events.clone() });
+        let first = json!({ "contract_address": "0x1", "nonce": "0x1" });
+        let second = json!({ "contract_address": "0x2", "nonce": "0x1" });
+
+        let sorted = canonicalize(receipt(json!([second.clone(), first.clone()])));
+        assert_eq!(sorted, canonicalize(receipt(json!([first.clone(), second.clone()]))));
+        assert_eq!(sorted["state_diff"]["nonces"], json!([first, second]));
+        // Arrays whose order matters, such as events, are left as they are.
+        assert_eq!(sorted["events"], events);
+    }
+
+    #[test]
+    fn snapshot_with_unknown_version_is_rejected() {
+        let mut dumped = StateSnapshot::from_starknet(&starknet()).unwrap().to_canonical_json().unwrap();
+        dumped["version"] = Value::from(STATE_SNAPSHOT_VERSION + 1);
+
+        assert!(matches!(StateSnapshot::from_json(dumped), Err(Error::UnsupportedSnapshotVersion { .. })));
+    }
+}
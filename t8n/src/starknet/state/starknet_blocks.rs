@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use indexmap::IndexMap;
-use serde::{ser::SerializeStruct, Serialize, Serializer};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 use starknet_api::hash::StarkFelt;
 use starknet_api::{
     block::{BlockHeader, BlockNumber, BlockStatus, BlockTimestamp},
@@ -12,7 +12,10 @@ use starknet_api::{
 use starknet_devnet_types::{
     contract_address::ContractAddress,
     felt::{BlockHash, Felt, TransactionHash},
-    rpc::block::{BlockHeader as TypesBlockHeader, ResourcePrice},
+    rpc::{
+        block::{BlockHeader as TypesBlockHeader, ResourcePrice},
+        transaction_receipt::TransactionReceipt,
+    },
     traits::HashProducer,
 };
 use starknet_rs_core::types::BlockId;
@@ -121,6 +124,22 @@ impl StarknetBlocks {
         self.hash_to_state.insert(block_hash, state);
     }
 
+    /// Returns clones of all blocks in ascending order of block number
+    pub fn get_all_blocks(&self) -> Vec<StarknetBlock> {
+        self.num_to_hash.values().filter_map(|hash| self.hash_to_block.get(hash)).cloned().collect()
+    }
+
+    /// Restores already produced blocks, e.g. when loading a state snapshot. Block hashes are kept
+    /// as they are, without recomputing parent hashes.
+    pub fn restore(&mut self, blocks: Vec<StarknetBlock>) {
+        for block in blocks {
+            let hash = block.block_hash();
+            self.num_to_hash.insert(block.block_number(), hash);
+            self.hash_to_block.insert(hash, block);
+            self.last_block_hash = Some(hash);
+        }
+    }
+
     pub fn get_by_block_id(&self, block_id: &BlockId) -> Option<&StarknetBlock> {
         match block_id {
             BlockId::Hash(hash) => self.get_by_hash(Felt::from(hash)),
@@ -187,11 +206,28 @@ impl StarknetBlocks {
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+/// Outcome of a single block executed by t8n: the data needed to compute its state root and the
+/// receipts of its transactions.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutedBlock {
+    pub block_number: BlockNumber,
+    pub block_hash: BlockHash,
+    pub state_diff: StateDiff,
+    pub transaction_receipts: Vec<TransactionReceipt>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct StarknetBlock {
     pub(crate) header: BlockHeader,
     transaction_hashes: Vec<TransactionHash>,
     pub(crate) status: BlockStatus,
+    /// Starknet version of the rules the block was executed with.
+    #[serde(default = "default_starknet_version")]
+    pub(crate) starknet_version: String,
+}
+
+fn default_starknet_version() -> String {
+    STARKNET_VERSION.to_string()
 }
 
 impl From<&StarknetBlock> for TypesBlockHeader {
@@ -203,7 +239,7 @@ impl From<&StarknetBlock> for TypesBlockHeader {
             sequencer_address: value.sequencer_address(),
             new_root: value.new_root(),
             timestamp: value.timestamp(),
-            starknet_version: STARKNET_VERSION.to_string(),
+            starknet_version: value.starknet_version.clone(),
             l1_gas_price: ResourcePrice {
                 price_in_fri: value.header.l1_gas_price.price_in_fri.0.into(),
                 price_in_wei: value.header.l1_gas_price.price_in_wei.0.into(),
@@ -226,6 +262,10 @@ impl StarknetBlock {
         &self.transaction_hashes
     }
 
+    pub fn starknet_version(&self) -> &str {
+        &self.starknet_version
+    }
+
     pub fn status(&self) -> &BlockStatus {
         &self.status
     }
@@ -263,6 +303,7 @@ impl StarknetBlock {
             header: BlockHeader { l1_da_mode: L1DataAvailabilityMode::Blob, ..BlockHeader::default() },
             status: BlockStatus::Pending,
             transaction_hashes: Vec::new(),
+            starknet_version: default_starknet_version(),
         }
     }
 
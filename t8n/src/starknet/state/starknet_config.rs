@@ -1,6 +1,6 @@
 use std::num::NonZeroU128;
 
-use serde::Serialize;
+use serde::{de, Deserialize, Deserializer, Serialize};
 use starknet_devnet_types::{
     chain_id::ChainId, contract_class::ContractClass, felt::Felt, rpc::state::Balance, traits::HashProducer,
 };
@@ -74,3 +74,41 @@ impl Default for StarknetConfig {
         }
     }
 }
+
+/// Gas prices of a single block, used to re-execute transactions with the prices of the original
+/// block. Prices that are not set are carried over from the previous block.
+///
+/// Prices can be given either as numbers or as hex strings, as returned by the RPC block header.
+/// L2 gas is not priced by the execution engine used by t8n, so it cannot be configured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockGasPrices {
+    #[serde(default, deserialize_with = "deserialize_gas_price")]
+    pub gas_price: Option<NonZeroU128>,
+    #[serde(default, deserialize_with = "deserialize_gas_price")]
+    pub data_gas_price: Option<NonZeroU128>,
+    #[serde(default, deserialize_with = "deserialize_gas_price")]
+    pub strk_gas_price: Option<NonZeroU128>,
+    #[serde(default, deserialize_with = "deserialize_gas_price")]
+    pub strk_data_gas_price: Option<NonZeroU128>,
+}
+
+fn deserialize_gas_price<'de, D>(deserializer: D) -> Result<Option<NonZeroU128>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum GasPrice {
+        Number(u64),
+        String(String),
+    }
+
+    let price = match GasPrice::deserialize(deserializer)? {
+        GasPrice::Number(price) => u128::from(price),
+        GasPrice::String(price) => match price.strip_prefix("0x") {
+            Some(hex) => u128::from_str_radix(hex, 16).map_err(de::Error::custom)?,
+            None => price.parse().map_err(de::Error::custom)?,
+        },
+    };
+    NonZeroU128::new(price).map(Some).ok_or_else(|| de::Error::custom("gas price must be non-zero"))
+}
@@ -1,4 +1,9 @@
-use blockifier::{execution::call_info::CallInfo, transaction::objects::TransactionExecutionInfo};
+use blockifier::{
+    execution::call_info::CallInfo,
+    fee::fee_utils,
+    transaction::objects::{GasVector, TransactionExecutionInfo},
+    versioned_constants::VersionedConstants,
+};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize, Serializer};
 use starknet_api::block::BlockNumber;
@@ -83,17 +88,23 @@ pub struct StarknetTransaction {
     pub(crate) block_number: Option<BlockNumber>,
     #[serde(skip)]
     pub(crate) execution_info: TransactionExecutionInfo,
+    /// Gas the execution consumed, computed with the constants of the block it was executed in.
+    #[serde(skip)]
+    pub(crate) gas_vector: GasVector,
     #[serde(skip)]
     pub(crate) trace: Option<TransactionTrace>,
 }
 
 impl StarknetTransaction {
+    /// `versioned_constants` are the ones of the block the transaction was executed in.
     pub fn create_accepted(
         transaction: &TransactionWithHash,
         execution_info: TransactionExecutionInfo,
         trace: TransactionTrace,
-    ) -> Self {
-        Self {
+        versioned_constants: &VersionedConstants,
+    ) -> DevnetResult<Self> {
+        let gas_vector = fee_utils::calculate_tx_gas_vector(&execution_info.actual_resources, versioned_constants)?;
+        Ok(Self {
             finality_status: TransactionFinalityStatus::AcceptedOnL2,
             execution_result: match execution_info.is_reverted() {
                 true => ExecutionResult::Reverted {
@@ -105,8 +116,9 @@ impl StarknetTransaction {
             block_hash: None,
             block_number: None,
             execution_info,
+            gas_vector,
             trace: Some(trace),
-        }
+        })
     }
 
     pub fn get_events(&self) -> Vec<Event> {
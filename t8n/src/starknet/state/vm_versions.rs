@@ -0,0 +1,89 @@
+//! Versioned constants of the Starknet versions t8n executes blocks under.
+//!
+//! The blockifier t8n is built with only embeds the constants of its own Starknet version, and the
+//! branch it is pinned to has no cargo features selecting older ones. The constants of the other
+//! versions are instead read at runtime from a directory of `versioned_constants.json` files, so
+//! supporting a version takes adding its file rather than rebuilding t8n.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use blockifier::versioned_constants::VersionedConstants;
+use crypto_utils::protocol::ProtocolVersion;
+use tracing::{info, warn};
+
+use super::errors::{DevnetResult, Error};
+use crate::utils::read_json_input;
+
+/// Protocol rules of the blockifier for each Starknet version: resource costs, limits and the
+/// other versioned constants that changed between versions. Historical blocks are executed with
+/// the rules of the version they were produced under, the newest loaded version that is not newer.
+#[derive(Debug, Default)]
+pub struct VmVersions {
+    versions: BTreeMap<ProtocolVersion, (String, VersionedConstants)>,
+}
+
+impl VmVersions {
+    /// Loads the versioned constants files of `dir`, each named after its Starknet version, e.g.
+    /// `0.13.1.json` with the content of the blockifier's `versioned_constants.json` of that version.
+    pub fn from_dir(dir: &Path) -> DevnetResult<Self> {
+        let mut versions = BTreeMap::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(version) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let key = ProtocolVersion::parse(version).ok_or_else(|| Error::DeserializationError {
+                origin: format!("{} is not named after a Starknet version, e.g. 0.13.1.json", path.display()),
+            })?;
+            let constants: VersionedConstants = read_json_input(Some(&path))?;
+            versions.insert(key, (version.to_string(), constants));
+        }
+        info!(
+            "Loaded the VM versions of Starknet {:?}",
+            versions.values().map(|(version, _)| version).collect::<Vec<_>>()
+        );
+        Ok(Self { versions })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.versions.is_empty()
+    }
+
+    /// Rules to execute a block of `starknet_version` with, along with the version they are of. A
+    /// version newer than every loaded one gets the newest rules, with a warning, as they may be
+    /// missing changes of the newer version.
+    pub fn select(&self, starknet_version: &str) -> Option<(&str, &VersionedConstants)> {
+        let key = ProtocolVersion::parse(starknet_version)?;
+        let (selected, (version, constants)) = self.versions.range(..=key).next_back()?;
+        if self.versions.keys().next_back() == Some(selected) && *selected < key {
+            warn!(
+                "Starknet {} is newer than every loaded VM version, executing it with the rules of {}",
+                starknet_version, version
+            );
+        }
+        Some((version.as_str(), constants))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_use_the_newest_version_not_newer_than_theirs() {
+        let mut versions = VmVersions::default();
+        for version in ["0.13.0", "0.13.2"] {
+            let constants = VersionedConstants::create_for_testing();
+            versions.versions.insert(ProtocolVersion::parse(version).unwrap(), (version.to_string(), constants));
+        }
+        assert_eq!(versions.select("0.13.1.1").map(|(version, _)| version), Some("0.13.0"));
+        assert_eq!(versions.select("0.13.2").map(|(version, _)| version), Some("0.13.2"));
+        assert_eq!(versions.select("0.14.0").map(|(version, _)| version), Some("0.13.2"));
+        assert_eq!(versions.select("0.12.3").map(|(version, _)| version), None);
+    }
+}
@@ -2,23 +2,72 @@ use crate::starknet::state::add_declare_transaction::add_declare_transaction;
 use crate::starknet::state::add_deploy_account_transaction::add_deploy_account_transaction;
 use crate::starknet::state::add_invoke_transaction::add_invoke_transaction;
 use crate::starknet::state::errors::Error;
+use crate::starknet::state::rpc_receipt::RpcReceiptVersion;
+use crate::starknet::state::starknet_blocks::ExecutedBlock;
+use crate::starknet::state::starknet_config::BlockGasPrices;
 use crate::starknet::state::starknet_state::{StateWithBlock, StateWithBlockNumber};
+use crate::starknet::state::traits::HashIdentified;
 use crate::starknet::state::Starknet;
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
 use starknet_devnet_types::rpc::transaction_receipt::TransactionReceipt;
-use starknet_devnet_types::rpc::transactions::BroadcastedTransaction;
-use std::path::PathBuf;
+use starknet_devnet_types::rpc::transactions::{BlockTransactionTrace, BroadcastedTransaction};
+use std::path::Path;
+use std::sync::OnceLock;
 use std::{
     fs::{self, File},
-    io::BufReader,
+    io::{self, BufReader, BufWriter, Write},
 };
 use tracing::{error, info};
 
-pub fn read_state_file(file_path: &PathBuf) -> Result<StateWithBlockNumber, Error> {
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
+/// Blocking HTTP client of all the requests to nodes, shared so that their connections are reused.
+pub fn blocking_http_client() -> reqwest::blocking::Client {
+    static CLIENT: OnceLock<reqwest::blocking::Client> = OnceLock::new();
+    CLIENT.get_or_init(reqwest::blocking::Client::new).clone()
+}
+
+/// Returns `true` when the path is absent or `-`, meaning stdin/stdout should be used instead of a file.
+fn is_std_stream(file_path: Option<&Path>) -> bool {
+    file_path.map_or(true, |path| path == Path::new("-"))
+}
 
-    let state_with_block: StateWithBlock = serde_json::from_reader(reader)?;
+/// Reads JSON from `file_path`, or from stdin when the path is absent or `-`.
+pub fn read_json_input<T: DeserializeOwned>(file_path: Option<&Path>) -> Result<T, Error> {
+    match file_path {
+        Some(path) if !is_std_stream(file_path) => {
+            let reader = BufReader::new(File::open(path)?);
+            Ok(serde_json::from_reader(reader)?)
+        }
+        _ => Ok(serde_json::from_reader(BufReader::new(io::stdin().lock()))?),
+    }
+}
+
+/// Writes `data` as JSON into `file_path`, or to stdout when the path is absent or `-`.
+pub fn write_json_output<T: Serialize>(file_path: Option<&Path>, data: &T) -> Result<(), Error> {
+    match file_path {
+        Some(path) if !is_std_stream(file_path) => write_result_state_file(path, data),
+        _ => {
+            let mut writer = BufWriter::new(io::stdout().lock());
+            serde_json::to_writer_pretty(&mut writer, data)?;
+            writeln!(writer)?;
+            writer.flush()?;
+            Ok(())
+        }
+    }
+}
+
+/// Ensures that at most one of the inputs is read from stdin.
+pub fn ensure_single_stdin_input(state_path: Option<&Path>, txns_path: Option<&Path>) -> Result<(), Error> {
+    if state_path.is_some() && is_std_stream(state_path) && is_std_stream(txns_path) {
+        return Err(Error::UnsupportedAction {
+            msg: "State and transactions cannot both be read from stdin; provide --txns-path".to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn read_state_file(file_path: &Path) -> Result<StateWithBlockNumber, Error> {
+    let state_with_block: StateWithBlock = read_json_input(Some(file_path))?;
     let state_with_block_number = StateWithBlockNumber {
         state: state_with_block.state,
         block_number: state_with_block.blocks.header.block_number,
@@ -26,11 +75,58 @@ pub fn read_state_file(file_path: &PathBuf) -> Result<StateWithBlockNumber, Erro
     Ok(state_with_block_number)
 }
 
-pub fn read_transactions_file(file_path: &PathBuf) -> Result<Vec<BroadcastedTransaction>, Error> {
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-    let transactions: Vec<BroadcastedTransaction> = serde_json::from_reader(reader)?;
-    Ok(transactions)
+/// Transaction input of a t8n run.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum TransactionsInput {
+    /// Plain list of transactions, all executed in a single block.
+    Transactions(Vec<BroadcastedTransaction>),
+    /// Transactions grouped into consecutive blocks, e.g. `{ "blocks": [[tx, tx], [tx]] }`.
+    Blocks { blocks: Vec<BlockInput> },
+}
+
+/// Transactions of a single block, optionally with the block's gas prices and the Starknet version
+/// whose rules it is executed with.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum BlockInput {
+    Transactions(Vec<BroadcastedTransaction>),
+    WithGasPrices {
+        #[serde(default)]
+        gas_prices: BlockGasPrices,
+        #[serde(default)]
+        starknet_version: Option<String>,
+        transactions: Vec<BroadcastedTransaction>,
+    },
+}
+
+impl TransactionsInput {
+    pub fn into_blocks(self) -> Vec<BlockInput> {
+        match self {
+            TransactionsInput::Transactions(transactions) => vec![BlockInput::Transactions(transactions)],
+            TransactionsInput::Blocks { blocks } => blocks,
+        }
+    }
+}
+
+pub fn read_transactions_file(file_path: Option<&Path>) -> Result<TransactionsInput, Error> {
+    read_json_input(file_path)
+}
+
+pub fn collect_rpc_receipts(starknet: &Starknet, version: RpcReceiptVersion) -> Result<Vec<Value>, Error> {
+    starknet.transactions.iter().map(|(_, transaction)| transaction.get_rpc_receipt(version)).collect()
+}
+
+pub fn collect_transaction_traces(starknet: &Starknet) -> Vec<BlockTransactionTrace> {
+    starknet
+        .transactions
+        .iter()
+        .filter_map(|(transaction_hash, transaction)| {
+            transaction
+                .get_trace()
+                .map(|trace_root| BlockTransactionTrace { transaction_hash: *transaction_hash, trace_root })
+        })
+        .collect()
 }
 
 pub fn add_transaction_receipts(starknet: &mut Starknet) -> Result<(), Error> {
@@ -73,11 +169,38 @@ pub fn handle_transactions(starknet: &mut Starknet, transactions: Vec<Broadcaste
         }
     }
     let state_diff = starknet.state.commit_with_diff()?;
-    starknet.generate_new_block(state_diff.clone())?;
+    let block_hash = starknet.generate_new_block(state_diff.clone())?;
+
+    let block = starknet.blocks.get_by_hash(block_hash).ok_or(Error::NoBlock)?;
+    let block_number = block.block_number();
+    let transaction_receipts = block
+        .get_transactions()
+        .iter()
+        .map(|transaction_hash| starknet.get_transaction_receipt_by_hash(transaction_hash))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    starknet.executed_blocks.push(ExecutedBlock { block_number, block_hash, state_diff, transaction_receipts });
+    Ok(())
+}
+
+/// Executes each group of transactions in its own block, in order.
+pub fn handle_blocks(starknet: &mut Starknet, blocks: Vec<BlockInput>) -> Result<(), Error> {
+    for (block_index, block) in blocks.into_iter().enumerate() {
+        let transactions = match block {
+            BlockInput::Transactions(transactions) => transactions,
+            BlockInput::WithGasPrices { gas_prices, starknet_version, transactions } => {
+                starknet.set_next_block_gas_prices(&gas_prices)?;
+                starknet.set_next_block_starknet_version(starknet_version.as_deref())?;
+                transactions
+            }
+        };
+        info!("Executing block {} with {} transactions", block_index, transactions.len());
+        handle_transactions(starknet, transactions)?;
+    }
     Ok(())
 }
 
-pub fn write_result_state_file<T: Serialize>(file_path: &PathBuf, data: &T) -> Result<(), Error> {
+pub fn write_result_state_file<T: Serialize>(file_path: &Path, data: &T) -> Result<(), Error> {
     if let Some(parent) = std::path::Path::new(file_path).parent() {
         fs::create_dir_all(parent)?;
     }
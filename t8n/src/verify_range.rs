@@ -0,0 +1,379 @@
+//! Verification of a range of historical blocks of a node: the state is forked from the node at the
+//! block before the range, and the blocks are re-executed one after the other on top of it, each
+//! with the gas prices of the original block. The receipts of every block are compared with the
+//! ones the node reported, so the node and the engine must agree on the outcome of every
+//! transaction.
+//!
+//! Progress is written to a checkpoint after every block. An interrupted verification resumes from
+//! the checkpoint, forking the state again at the last verified block.
+
+use std::fmt;
+use std::fs;
+use std::num::NonZeroU128;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use starknet_devnet_types::{
+    chain_id::ChainId, felt::Felt, rpc::transactions::BroadcastedTransaction, traits::ToHexString,
+};
+use tracing::{info, warn};
+use url::Url;
+
+use crate::execution_diff::{
+    diff_receipts, diff_values, emitted_item_index, emitting_calls, normalize_hex, DivergenceCategory, FieldDifference,
+};
+use crate::starknet::state::errors::{DevnetResult, Error};
+use crate::starknet::state::rpc_receipt::RpcReceiptVersion;
+use crate::starknet::state::starknet_config::BlockGasPrices;
+use crate::utils::{blocking_http_client, read_json_input, BlockInput};
+use crate::Executor;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedTransaction {
+    pub block_number: u64,
+    pub transaction_hash: String,
+    pub reason: String,
+}
+
+/// A receipt field of a transaction that differs between the node and the re-execution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceiptMismatch {
+    pub block_number: u64,
+    pub transaction_hash: String,
+    pub field: String,
+    pub category: DivergenceCategory,
+    pub differences: Vec<FieldDifference>,
+}
+
+impl fmt::Display for ReceiptMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Block {}, transaction {}: {} differs ({} divergence)",
+            self.block_number, self.transaction_hash, self.field, self.category
+        )?;
+        for difference in &self.differences {
+            write!(f, "\n  {difference}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of the verification so far, which is also its checkpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeReport {
+    pub from: u64,
+    pub to: u64,
+    /// First block that is not verified yet, past `to` once the range is verified.
+    pub next_block: u64,
+    pub transactions: u64,
+    pub skipped: Vec<SkippedTransaction>,
+    pub mismatches: Vec<ReceiptMismatch>,
+}
+
+impl RangeReport {
+    fn new(from: u64, to: u64) -> Self {
+        Self { from, to, next_block: from, transactions: 0, skipped: Vec::new(), mismatches: Vec::new() }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.next_block > self.to
+    }
+
+    /// Most severe category of the mismatches, if any.
+    pub fn divergence(&self) -> Option<DivergenceCategory> {
+        self.mismatches.iter().map(|mismatch| mismatch.category).min()
+    }
+}
+
+/// Re-executes the blocks `from..=to` of the node at `url`, resuming from `checkpoint_path` when it
+/// holds the progress of the same range, and writing the progress there after every block. Blocks
+/// are executed with the rules of their Starknet version among those of `vm_versions_dir`, if set.
+pub fn verify_range(
+    url: &Url,
+    from: u64,
+    to: u64,
+    checkpoint_path: Option<&Path>,
+    vm_versions_dir: Option<&Path>,
+) -> DevnetResult<RangeReport> {
+    if from == 0 || from > to {
+        return Err(Error::UnsupportedAction {
+            msg: format!("Range {from}..={to} must be non-empty and start after block 0, which has no parent state"),
+        });
+    }
+
+    let mut report = match checkpoint_path.filter(|path| path.exists()) {
+        Some(path) => {
+            let report: RangeReport = read_json_input(Some(path))?;
+            if (report.from, report.to) != (from, to) {
+                return Err(Error::UnsupportedAction {
+                    msg: format!(
+                        "Checkpoint {} is of blocks {}..={}, not {from}..={to}",
+                        path.display(),
+                        report.from,
+                        report.to
+                    ),
+                });
+            }
+            info!("Resuming the verification of blocks {}..={} at block {}", from, to, report.next_block);
+            report
+        }
+        None => RangeReport::new(from, to),
+    };
+    if report.is_complete() {
+        return Ok(report);
+    }
+
+    let node = Node::new(url.clone());
+    let mut executor = Executor::fork(url.clone(), report.next_block - 1, node.chain_id()?)?;
+    if let Some(dir) = vm_versions_dir {
+        executor = executor.with_vm_versions(dir)?;
+    }
+    while !report.is_complete() {
+        verify_block(&node, &mut executor, &mut report)?;
+        report.next_block += 1;
+        if let Some(path) = checkpoint_path {
+            write_checkpoint(path, &report)?;
+        }
+        info!(
+            "Verified block {} ({}/{}), {} mismatches so far",
+            report.next_block - 1,
+            report.next_block - from,
+            to - from + 1,
+            report.mismatches.len()
+        );
+    }
+
+    info!(
+        "Re-executed {} transactions in blocks {}..={}: {} mismatches, {} skipped",
+        report.transactions,
+        from,
+        to,
+        report.mismatches.len(),
+        report.skipped.len()
+    );
+    for mismatch in &report.mismatches {
+        warn!("{}", mismatch);
+    }
+    Ok(report)
+}
+
+fn verify_block(node: &Node, executor: &mut Executor, report: &mut RangeReport) -> DevnetResult<()> {
+    let block_number = report.next_block;
+    let block_id = json!({ "block_number": block_number });
+    let block = node.request("starknet_getBlockWithTxs", json!({ "block_id": block_id }))?;
+
+    let mut transactions = vec![];
+    let mut hashes = vec![];
+    for mut transaction in block["transactions"].as_array().cloned().unwrap_or_default() {
+        let transaction_hash = hex(&transaction["transaction_hash"]);
+        let skip =
+            |reason: String| SkippedTransaction { block_number, transaction_hash: transaction_hash.clone(), reason };
+        if transaction["type"] == "L1_HANDLER" {
+            report.skipped.push(skip("L1 handler transactions are not re-executed".to_string()));
+            continue;
+        }
+        if transaction["type"] == "DECLARE" {
+            let params = json!({ "block_id": block_id, "class_hash": transaction["class_hash"] });
+            transaction["contract_class"] = node.request("starknet_getClass", params)?;
+        }
+        if let Some(fields) = transaction.as_object_mut() {
+            fields.remove("transaction_hash");
+        }
+        match serde_json::from_value::<BroadcastedTransaction>(transaction) {
+            Ok(transaction) => {
+                transactions.push(transaction);
+                hashes.push(transaction_hash);
+            }
+            Err(e) => report.skipped.push(skip(format!("Not parsed by the t8n engine: {e}"))),
+        }
+    }
+
+    let gas_prices = block_gas_prices(&block)?;
+    let starknet_version = block["starknet_version"].as_str().map(str::to_string);
+    let executed =
+        executor.execute_blocks(vec![BlockInput::WithGasPrices { gas_prices, starknet_version, transactions }])?;
+    let executed_transactions: usize = executed.iter().map(|block| block.transaction_receipts.len()).sum();
+
+    // The transactions of the block are the last ones executed.
+    let local_receipts = executor
+        .starknet()
+        .transactions
+        .iter()
+        .rev()
+        .take(executed_transactions)
+        .map(|(_, transaction)| transaction.get_rpc_receipt(RpcReceiptVersion::V0_7))
+        .collect::<DevnetResult<Vec<_>>>()?;
+
+    for transaction_hash in hashes {
+        report.transactions += 1;
+        let node_receipt =
+            node.request("starknet_getTransactionReceipt", json!({ "transaction_hash": transaction_hash }))?;
+        let local_receipt = local_receipts.iter().find(|receipt| hex(&receipt["transaction_hash"]) == transaction_hash);
+        let mismatch = |field: &str, differences: Vec<FieldDifference>| ReceiptMismatch {
+            block_number,
+            transaction_hash: transaction_hash.clone(),
+            field: field.to_string(),
+            category: DivergenceCategory::of_field(field),
+            differences,
+        };
+
+        let Some(local_receipt) = local_receipt else {
+            let differences = diff_values("transaction", &json!("included"), &json!("rejected"));
+            report.mismatches.push(mismatch("transaction", differences));
+            continue;
+        };
+        let fields = diff_receipts(&node_receipt, local_receipt);
+        // The node's trace locates diverging events and messages, it is only fetched when they diverge.
+        let node_trace = if fields.iter().any(|(field, _)| emitted_list(field).is_some()) {
+            Some(node.request("starknet_traceTransaction", json!({ "transaction_hash": transaction_hash }))?)
+        } else {
+            None
+        };
+        for (field, mut differences) in fields {
+            if let (Some(list), Some(trace)) = (emitted_list(field), &node_trace) {
+                let calls = emitting_calls(trace, list);
+                for difference in &mut differences {
+                    difference.location =
+                        emitted_item_index(&difference.path).and_then(|index| calls.get(index)).cloned();
+                }
+            }
+            report.mismatches.push(mismatch(field, differences));
+        }
+    }
+    Ok(())
+}
+
+/// List of the trace invocations holding the items of a receipt field, if it holds emitted items.
+fn emitted_list(field: &str) -> Option<&'static str> {
+    match field {
+        "events" => Some("events"),
+        "messages_sent" => Some("messages"),
+        _ => None,
+    }
+}
+
+/// Writes the checkpoint next to its path first, so that an interruption never leaves it half written.
+fn write_checkpoint(path: &Path, report: &RangeReport) -> DevnetResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let partial = path.with_extension("partial");
+    fs::write(&partial, serde_json::to_string_pretty(report)?)?;
+    fs::rename(&partial, path)?;
+    Ok(())
+}
+
+/// JSON-RPC client of the node whose blocks are verified. Requests are blocking, as are the ones
+/// of the forked state.
+struct Node {
+    url: Url,
+    client: reqwest::blocking::Client,
+}
+
+impl Node {
+    fn new(url: Url) -> Self {
+        Self { url, client: blocking_http_client() }
+    }
+
+    fn request(&self, method: &str, params: Value) -> DevnetResult<Value> {
+        let rpc_error = |reason: String| Error::RpcError { method: method.to_string(), reason };
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response: Value = self
+            .client
+            .post(self.url.clone())
+            .json(&request)
+            .send()
+            .and_then(|response| response.json())
+            .map_err(|e| rpc_error(e.to_string()))?;
+        if let Some(error) = response.get("error") {
+            return Err(rpc_error(error.to_string()));
+        }
+        Ok(response["result"].take())
+    }
+
+    fn chain_id(&self) -> DevnetResult<ChainId> {
+        chain_id_from_hex(&hex(&self.request("starknet_chainId", json!([]))?))
+    }
+}
+
+/// Chain id of a node, known chains by their variant and any other chain, e.g. a devnet or an
+/// app-chain, by its felt.
+fn chain_id_from_hex(chain_id: &str) -> DevnetResult<ChainId> {
+    let known = [ChainId::Mainnet, ChainId::Testnet]
+        .into_iter()
+        .find(|known| hex(&json!(known.to_felt().to_prefixed_hex_str())) == chain_id);
+    match known {
+        Some(known) => Ok(known),
+        None => Ok(ChainId::Custom(Felt::from_prefixed_hex_str(chain_id)?)),
+    }
+}
+
+/// Gas prices of a block header, each price on its own: a price that is missing, null or zero, e.g.
+/// the data gas price of blocks before 0.13.1, is left to the previous block.
+fn block_gas_prices(block: &Value) -> DevnetResult<BlockGasPrices> {
+    Ok(BlockGasPrices {
+        gas_price: gas_price(&block["l1_gas_price"]["price_in_wei"])?,
+        data_gas_price: gas_price(&block["l1_data_gas_price"]["price_in_wei"])?,
+        strk_gas_price: gas_price(&block["l1_gas_price"]["price_in_fri"])?,
+        strk_data_gas_price: gas_price(&block["l1_data_gas_price"]["price_in_fri"])?,
+    })
+}
+
+fn gas_price(price: &Value) -> DevnetResult<Option<NonZeroU128>> {
+    let parsed = match price {
+        Value::Null => return Ok(None),
+        Value::Number(number) => number.as_u64().map(u128::from),
+        Value::String(string) => match string.strip_prefix("0x") {
+            Some(hex) => u128::from_str_radix(hex, 16).ok(),
+            None => string.parse().ok(),
+        },
+        _ => None,
+    };
+    parsed
+        .map(NonZeroU128::new)
+        .ok_or_else(|| Error::DeserializationError { origin: format!("{price} is not a gas price") })
+}
+
+/// Hex string of a felt without leading zeros, or the empty string if `value` is not one.
+fn hex(value: &Value) -> String {
+    match normalize_hex(value) {
+        Value::String(hex) if hex.starts_with("0x") => hex,
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn felts_are_read_as_normalized_hex() {
+        assert_eq!(hex(&json!("0x0534e5f5345504f4c4941")), "0x534e5f5345504f4c4941");
+        assert_eq!(hex(&json!("SUCCEEDED")), "");
+    }
+
+    #[test]
+    fn gas_prices_are_parsed_one_by_one() {
+        let block = json!({
+            "l1_gas_price": { "price_in_wei": "0x3b9aca00", "price_in_fri": 0 },
+            "l1_data_gas_price": { "price_in_wei": "1", "price_in_fri": null },
+        });
+        let prices = block_gas_prices(&block).unwrap();
+        assert_eq!(prices.gas_price, NonZeroU128::new(1_000_000_000));
+        assert_eq!(prices.data_gas_price, NonZeroU128::new(1));
+        assert_eq!(prices.strk_gas_price, None);
+        assert_eq!(prices.strk_data_gas_price, None);
+
+        let block = json!({ "l1_gas_price": { "price_in_wei": "0xnope" } });
+        assert!(matches!(block_gas_prices(&block), Err(Error::DeserializationError { .. })));
+    }
+
+    #[test]
+    fn custom_chain_ids_are_kept() {
+        assert!(matches!(chain_id_from_hex("0x534e5f4d41494e"), Ok(ChainId::Mainnet)));
+        let devnet = chain_id_from_hex("0x4d41444152415f4445564e4554").unwrap();
+        assert_eq!(devnet.to_felt().to_prefixed_hex_str(), "0x4d41444152415f4445564e4554");
+    }
+}
@@ -1,14 +1,128 @@
-use clap::Parser;
+use crate::txn_validation::signature::SignatureScheme;
+use clap::{CommandFactory, Parser, Subcommand};
+use crypto_utils::protocol::ProtocolVersion;
 use std::path::PathBuf;
 
 #[derive(Parser)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Args {
-    #[arg(short, long, env)]
-    pub file_path: PathBuf,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Transaction JSON file; in batch mode a directory of transaction files or a JSON-lines file
+    /// (`-` for stdin). Required unless a subcommand is given.
+    #[arg(short, long, env, required = true)]
+    pub file_path: Option<PathBuf>,
 
     #[arg(short, long, env)]
     pub public_key: Option<String>,
 
+    /// Required unless a subcommand is given.
+    #[arg(short, long, env, required = true)]
+    pub chain_id: Option<String>,
+
+    /// Signature scheme of the account that signed the transaction.
+    #[arg(short, long, env, value_enum, default_value_t = SignatureScheme::Stark)]
+    pub scheme: SignatureScheme,
+
+    /// Validate many transactions at once and print a summary report with a verdict per transaction.
     #[arg(short, long, env)]
-    pub chain_id: String,
+    pub batch: bool,
+
+    /// Protocol version, e.g. `0.13.4`, whose resources V3 transactions are hashed with. By default
+    /// transactions with `l1_data_gas` bounds are hashed with the first version bounding it.
+    #[arg(long, env, value_parser = parse_protocol_version_arg)]
+    pub protocol_version: Option<[u32; 3]>,
+
+    /// Address the deploy-account transaction is expected to deploy the account at; validation fails
+    /// when the address derived from the transaction differs.
+    #[arg(short, long, env, conflicts_with = "batch")]
+    pub expected_address: Option<String>,
+}
+
+impl Args {
+    /// Transaction file and chain id of the transaction validation, which only subcommands go
+    /// without.
+    pub fn transaction_inputs(&self) -> Result<(&PathBuf, &str), clap::Error> {
+        let missing = |arg: &str| {
+            Args::command().error(
+                clap::error::ErrorKind::MissingRequiredArgument,
+                format!("{arg} is required to validate a transaction"),
+            )
+        };
+        let file_path = self.file_path.as_ref().ok_or_else(|| missing("--file-path"))?;
+        let chain_id = self.chain_id.as_deref().ok_or_else(|| missing("--chain-id"))?;
+        Ok((file_path, chain_id))
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Verify the signature of a SNIP-12 off-chain message instead of a transaction.
+    Message(MessageArgs),
+}
+
+#[derive(clap::Args)]
+pub struct MessageArgs {
+    /// Typed data JSON file of the message, as passed to `signTypedData`.
+    #[arg(long, env)]
+    pub typed_data: PathBuf,
+
+    /// Address of the account that signed the message, part of the message hash.
+    #[arg(long, env)]
+    pub account_address: String,
+
+    /// Signature returned by the wallet, comma separated.
+    #[arg(long, env, value_delimiter = ',')]
+    pub signature: Vec<String>,
+
+    #[arg(long, env)]
+    pub public_key: Option<String>,
+
+    /// Signature scheme of the account that signed the message.
+    #[arg(long, env, value_enum, default_value_t = SignatureScheme::Stark)]
+    pub scheme: SignatureScheme,
+}
+
+fn parse_protocol_version_arg(version: &str) -> Result<[u32; 3], String> {
+    ProtocolVersion::parse(version)
+        .map(|version| version.release())
+        .ok_or_else(|| format!("{version} is not a protocol version such as 0.13.4"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_subcommand_needs_no_transaction_inputs() {
+        let args = Args::try_parse_from([
+            "t9n",
+            "message",
+            "--typed-data",
+            "message.json",
+            "--account-address",
+            "0x1",
+            "--signature",
+            "0x2,0x3",
+        ])
+        .unwrap();
+
+        let Some(Command::Message(message)) = &args.command else {
+            panic!("message subcommand not parsed");
+        };
+        assert_eq!(message.signature, ["0x2", "0x3"]);
+        assert!(args.transaction_inputs().is_err());
+    }
+
+    #[test]
+    fn transaction_validation_requires_the_file_and_chain_id() {
+        assert!(Args::try_parse_from(["t9n", "--chain-id", "0x534e5f5345504f4c4941"]).is_err());
+
+        let args =
+            Args::try_parse_from(["t9n", "--file-path", "txn.json", "--chain-id", "0x534e5f5345504f4c4941"]).unwrap();
+        let (file_path, chain_id) = args.transaction_inputs().unwrap();
+        assert_eq!(file_path, &PathBuf::from("txn.json"));
+        assert_eq!(chain_id, "0x534e5f5345504f4c4941");
+    }
 }
@@ -1,2 +1,3 @@
 pub mod txn_hashes;
 pub mod txn_validation;
+pub mod typed_data;
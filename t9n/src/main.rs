@@ -1,13 +1,49 @@
 pub mod args;
 pub mod txn_hashes;
 pub mod txn_validation;
-use args::Args;
+pub mod typed_data;
+use args::{Args, Command};
 use clap::Parser;
-use txn_validation::validate::validate_txn_json;
+use txn_validation::batch::validate_batch;
+use txn_validation::validate::{validate_message_json, validate_txn_json, verify_deploy_account_address};
 
 fn main() {
     let args = Args::parse();
-    match validate_txn_json(&args.file_path, args.public_key.as_deref(), &args.chain_id) {
+    if let Some(Command::Message(message)) = &args.command {
+        match validate_message_json(
+            &message.typed_data,
+            &message.account_address,
+            &message.signature,
+            message.public_key.as_deref(),
+            message.scheme,
+        ) {
+            Ok(json_result) => println!("Validation successful: {}", json_result),
+            Err(e) => println!("Validation error: {}", e),
+        }
+        return;
+    }
+    let (file_path, chain_id) = args.transaction_inputs().unwrap_or_else(|e| e.exit());
+    if args.batch {
+        match validate_batch(file_path, args.public_key.as_deref(), chain_id, args.scheme, args.protocol_version) {
+            Ok(report) => match serde_json::to_string_pretty(&report) {
+                Ok(report) => println!("{}", report),
+                Err(e) => println!("Validation error: {}", e),
+            },
+            Err(e) => {
+                println!("Validation error: {}", e);
+            }
+        }
+        return;
+    }
+
+    if let Some(expected_address) = args.expected_address.as_deref() {
+        if let Err(e) = verify_deploy_account_address(file_path, expected_address) {
+            println!("Validation error: {}", e);
+            return;
+        }
+    }
+
+    match validate_txn_json(file_path, args.public_key.as_deref(), chain_id, args.scheme, args.protocol_version) {
         Ok(json_result) => {
             println!("Validation successful: {}", json_result);
         }
@@ -2,3 +2,4 @@ pub mod constants;
 pub mod declare_hash;
 pub mod deploy_account;
 pub mod invoke_hash;
+pub mod resource_bounds;
@@ -0,0 +1,82 @@
+use crate::txn_validation::errors::Error;
+use crypto_utils::protocol::{
+    first_version_bounding, target_constants, versioned_constants, Resource, VersionedConstants,
+};
+use serde_json::Value;
+use starknet_hive_hashing::resource_bounds::ResourceBoundsBuilder;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::starknet_api_openrpc::{ResourceBounds, ResourceBoundsMapping};
+
+/// Fee market a V3 transaction is hashed in: the constants of its protocol version, and its L1
+/// data gas bounds, which the v0.7 RPC types drop when the transaction is deserialized.
+#[derive(Debug, Clone)]
+pub struct FeeMarket {
+    pub constants: &'static VersionedConstants,
+    pub l1_data_gas: Option<ResourceBounds>,
+}
+
+impl Default for FeeMarket {
+    /// The protocol version the transactions are verified against, without L1 data gas bounds.
+    fn default() -> Self {
+        Self { constants: target_constants(), l1_data_gas: None }
+    }
+}
+
+impl FeeMarket {
+    /// Fee market of the transaction JSON `txn`. Without `protocol_version`, transactions bounding
+    /// L1 data gas are hashed with the first protocol version that does, the others with the
+    /// version the transactions are verified against.
+    pub fn of_transaction(txn: &Value, protocol_version: Option<[u32; 3]>) -> Result<Self, Error> {
+        let l1_data_gas: Option<ResourceBounds> = txn
+            .get("resource_bounds")
+            .and_then(|resource_bounds| resource_bounds.get("l1_data_gas"))
+            .map(|bounds| serde_json::from_value(bounds.clone()))
+            .transpose()?;
+        let constants = match protocol_version {
+            Some(version) => versioned_constants(version),
+            None => match l1_data_gas.as_ref().and_then(|_| first_version_bounding(Resource::L1DataGas)) {
+                Some(version) => versioned_constants(version),
+                None => target_constants(),
+            },
+        };
+        Ok(Self { constants, l1_data_gas })
+    }
+
+    /// Returns `h(tip, resource_bounds_for_fee)` from SNIP-8, with the resources of the protocol
+    /// version of the fee market.
+    pub fn fee_data_hash(&self, tip: Felt, resource_bounds: &ResourceBoundsMapping) -> Result<Felt, Error> {
+        let mut bounds = ResourceBoundsBuilder::parse(resource_bounds)?;
+        if let Some(l1_data_gas) = &self.l1_data_gas {
+            bounds = bounds.parse_l1_data_gas(l1_data_gas)?;
+        }
+        Ok(bounds.fee_data_hash(self.constants, tip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn txn(resource_bounds: Value) -> Value {
+        json!({ "type": "INVOKE", "version": "0x3", "resource_bounds": resource_bounds })
+    }
+
+    #[test]
+    fn l1_data_gas_bounds_select_the_version_bounding_them() {
+        let bounds = json!({ "max_amount": "0x10", "max_price_per_unit": "0x20" });
+        let without = txn(json!({ "l1_gas": bounds, "l2_gas": bounds }));
+        let with = txn(json!({ "l1_gas": bounds, "l2_gas": bounds, "l1_data_gas": bounds }));
+
+        assert_eq!(FeeMarket::of_transaction(&without, None).unwrap().constants, target_constants());
+        assert_eq!(FeeMarket::of_transaction(&with, None).unwrap().constants.since, [0, 13, 4]);
+        assert_eq!(FeeMarket::of_transaction(&with, Some([0, 13, 2])).unwrap().constants.since, [0, 13, 2]);
+
+        let mapping: ResourceBoundsMapping = serde_json::from_value(with["resource_bounds"].clone()).unwrap();
+        let hash = |fee_market: FeeMarket| fee_market.fee_data_hash(Felt::ZERO, &mapping).unwrap();
+        assert_ne!(
+            hash(FeeMarket::of_transaction(&with, None).unwrap()),
+            hash(FeeMarket::of_transaction(&without, None).unwrap())
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use super::signature::SignatureScheme;
+use super::validate::validate_txn_value;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// Verdict for a single transaction of a batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct TxnVerdict {
+    /// File name for directory input, `line <n>` (1-based) for JSON-lines input.
+    pub source: String,
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<Value>,
+    /// Address the account of a deploy-account transaction is deployed at.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchReport {
+    pub total: usize,
+    pub valid: usize,
+    pub invalid: usize,
+    pub results: Vec<TxnVerdict>,
+}
+
+/// Entry of a batch input: either a bare transaction or a transaction with the public key of its
+/// signer, `{ "public_key": "0x...", "transaction": { ... } }`.
+struct BatchEntry {
+    source: String,
+    transaction: Result<Value, String>,
+}
+
+/// Validates every transaction of `path`, which is either a directory of transaction JSON files
+/// (processed in file name order) or a JSON-lines file, `-` meaning stdin.
+///
+/// `public_key` is used for entries that do not carry their own key, and `protocol_version` as in
+/// [`validate_txn_value`]. A single invalid entry does not stop the batch, it is only reported in
+/// its verdict.
+pub fn validate_batch(
+    path: &Path,
+    public_key: Option<&str>,
+    chain_id: &str,
+    scheme: SignatureScheme,
+    protocol_version: Option<[u32; 3]>,
+) -> io::Result<BatchReport> {
+    let entries = if path.is_dir() { read_directory(path)? } else { read_json_lines(path)? };
+    Ok(validate_entries(entries, public_key, chain_id, scheme, protocol_version))
+}
+
+fn validate_entries(
+    entries: Vec<BatchEntry>,
+    public_key: Option<&str>,
+    chain_id: &str,
+    scheme: SignatureScheme,
+    protocol_version: Option<[u32; 3]>,
+) -> BatchReport {
+    let mut report = BatchReport::default();
+    for entry in entries {
+        let verdict = match entry.transaction {
+            Ok(value) => {
+                let (value, entry_public_key) = split_public_key(value);
+                let public_key = entry_public_key.as_deref().or(public_key);
+                match validate_txn_value(value, public_key, chain_id, scheme, protocol_version) {
+                    Ok(result) => TxnVerdict {
+                        source: entry.source,
+                        valid: true,
+                        hash: result.get("hash").cloned(),
+                        contract_address: result.get("contract_address").cloned(),
+                        error: None,
+                    },
+                    Err(e) => TxnVerdict {
+                        source: entry.source,
+                        valid: false,
+                        hash: None,
+                        contract_address: None,
+                        error: Some(error_value(&e)),
+                    },
+                }
+            }
+            Err(e) => TxnVerdict {
+                source: entry.source,
+                valid: false,
+                hash: None,
+                contract_address: None,
+                error: Some(Value::String(e)),
+            },
+        };
+
+        if verdict.valid {
+            report.valid += 1;
+        } else {
+            report.invalid += 1;
+        }
+        report.results.push(verdict);
+    }
+    report.total = report.results.len();
+
+    report
+}
+
+fn read_directory(dir: &Path) -> io::Result<Vec<BatchEntry>> {
+    let mut paths = fs::read_dir(dir)?.map(|entry| entry.map(|entry| entry.path())).collect::<Result<Vec<_>, _>>()?;
+    paths.retain(|path| path.extension().is_some_and(|extension| extension == "json"));
+    paths.sort();
+
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let transaction = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|raw| serde_json::from_str(&raw).map_err(|e| e.to_string()));
+            let source = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            BatchEntry { source, transaction }
+        })
+        .collect())
+}
+
+fn read_json_lines(path: &Path) -> io::Result<Vec<BatchEntry>> {
+    if path == Path::new("-") {
+        parse_json_lines(BufReader::new(io::stdin().lock()))
+    } else {
+        parse_json_lines(BufReader::new(File::open(path)?))
+    }
+}
+
+fn parse_json_lines(reader: impl BufRead) -> io::Result<Vec<BatchEntry>> {
+    let mut entries = vec![];
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let transaction = serde_json::from_str(&line).map_err(|e| e.to_string());
+        entries.push(BatchEntry { source: format!("line {}", index + 1), transaction });
+    }
+    Ok(entries)
+}
+
+fn split_public_key(value: Value) -> (Value, Option<String>) {
+    match value {
+        Value::Object(mut entry) if entry.contains_key("transaction") => {
+            let public_key = entry.remove("public_key").and_then(|key| key.as_str().map(str::to_string));
+            (entry.remove("transaction").unwrap_or_default(), public_key)
+        }
+        value => (value, None),
+    }
+}
+
+/// Validation errors carry a JSON object as their message; keep it structured in the report.
+fn error_value(error: &serde_json::Error) -> Value {
+    let message = error.to_string();
+    serde_json::from_str(&message).unwrap_or(Value::String(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Cursor;
+
+    const PUBLIC_KEY: &str = "0x39d9e6ce352ad4530a0ef5d5a18fd3303c3606a7fa6ac5b620020ad681cc33b";
+    /// Key of another account, which the examples' signatures do not verify under.
+    const OTHER_PUBLIC_KEY: &str = "0x539751391da90f5789033ecf54ba0bdb4cbad7f92068418e22951e9973c05ea";
+    const CHAIN_ID: &str = "0x534e5f5345504f4c4941";
+
+    fn example(name: &str) -> Value {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/invoke").join(name);
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn sources(report: &BatchReport) -> Vec<&str> {
+        report.results.iter().map(|verdict| verdict.source.as_str()).collect()
+    }
+
+    #[test]
+    fn directory_entries_are_validated_in_file_name_order() {
+        let dir = std::env::temp_dir().join(format!("t9n-batch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("b.json"), example("invoke_txn_v3.json").to_string()).unwrap();
+        fs::write(dir.join("a.json"), example("invoke_txn_v1.json").to_string()).unwrap();
+        fs::write(dir.join("c.json"), "{").unwrap();
+        fs::write(dir.join("notes.txt"), "not a transaction").unwrap();
+
+        let report = validate_batch(&dir, Some(PUBLIC_KEY), CHAIN_ID, SignatureScheme::Stark, None);
+        fs::remove_dir_all(&dir).unwrap();
+        let report = report.unwrap();
+
+        assert_eq!(sources(&report), ["a.json", "b.json", "c.json"]);
+        assert_eq!((report.total, report.valid, report.invalid), (3, 2, 1));
+        assert!(report.results[0].hash.is_some() && report.results[1].hash.is_some());
+        assert!(!report.results[2].valid && report.results[2].error.is_some());
+    }
+
+    #[test]
+    fn json_lines_entries_carry_their_own_public_key() {
+        let lines = [
+            json!({ "public_key": PUBLIC_KEY, "transaction": example("invoke_txn_v1.json") }).to_string(),
+            String::new(),
+            example("invoke_txn_v3.json").to_string(),
+            "not json".to_string(),
+        ]
+        .join("\n");
+
+        let entries = parse_json_lines(Cursor::new(lines)).unwrap();
+        let report = validate_entries(entries, Some(OTHER_PUBLIC_KEY), CHAIN_ID, SignatureScheme::Stark, None);
+
+        assert_eq!(sources(&report), ["line 1", "line 3", "line 4"]);
+        assert_eq!((report.total, report.valid, report.invalid), (3, 1, 2));
+        assert!(report.results[0].valid);
+        assert_eq!(report.results[1].error.as_ref().unwrap()["error"], "Signature is invalid");
+        assert!(report.results[2].error.as_ref().unwrap().is_string());
+    }
+
+    #[test]
+    fn envelopes_are_split_from_bare_transactions() {
+        let transaction = json!({ "type": "INVOKE" });
+        let envelope = json!({ "public_key": "0x1", "transaction": transaction });
+
+        assert_eq!(split_public_key(envelope), (transaction.clone(), Some("0x1".to_string())));
+        assert_eq!(split_public_key(transaction.clone()), (transaction, None));
+    }
+}
@@ -1,5 +1,6 @@
 use crypto_utils::curve::signer::{RecoverError, VerifyError};
 use serde_json;
+use starknet_hive_hashing::resource_bounds::ResourceBoundsError;
 use std::num::ParseIntError;
 use thiserror::Error;
 
@@ -14,7 +15,25 @@ pub enum Error {
     #[error("Resource name is not a string")]
     ResourceNameError,
     #[error(transparent)]
+    ResourceBoundsError(#[from] ResourceBoundsError),
+    #[error(transparent)]
     VerifyError(#[from] VerifyError),
     #[error(transparent)]
     RecoverError(#[from] RecoverError),
+    #[error(transparent)]
+    EcdsaError(#[from] k256::ecdsa::Error),
+    #[error("Invalid signature length: expected {expected} elements, got {actual}")]
+    InvalidSignatureLength { expected: String, actual: usize },
+    #[error("Signature element is not a valid u256 limb")]
+    InvalidU256Limb,
+    #[error("Invalid recovery id")]
+    InvalidRecoveryId,
+    #[error("Invalid public key")]
+    InvalidPublicKey,
+    #[error("Public key is required for this signature scheme")]
+    PublicKeyRequired,
+    #[error("Invalid transaction schema: {0}")]
+    SchemaError(String),
+    #[error("Invalid typed data: {0}")]
+    TypedDataError(String),
 }
@@ -1,6 +1,7 @@
 use super::errors::Error;
+use super::signature::{verify_signature, SignatureScheme};
 use crate::txn_hashes::invoke_hash::{calculate_invoke_v1_hash, calculate_invoke_v3_hash};
-use crypto_utils::curve::signer::{recover, verify};
+use crate::txn_hashes::resource_bounds::FeeMarket;
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::v0_7_1::starknet_api_openrpc::*;
 
@@ -13,18 +14,8 @@ pub fn verify_invoke_v1_signature(
 
     let msg_hash = calculate_invoke_v1_hash(txn, &chain_id)?;
 
-    let r_bytes = txn.signature[0];
-    let s_bytes = txn.signature[1];
-
-    let stark_key = match public_key {
-        Some(public_key) => Felt::from_hex_unchecked(public_key),
-        None => recover(&msg_hash, &r_bytes, &s_bytes, &Felt::ONE)?,
-    };
-
-    match verify(&stark_key, &msg_hash, &r_bytes, &s_bytes) {
-        Ok(is_valid) => Ok((is_valid, msg_hash)),
-        Err(e) => Err(Error::VerifyError(e)),
-    }
+    let is_valid = verify_signature(SignatureScheme::Stark, &msg_hash, &txn.signature, public_key)?;
+    Ok((is_valid, msg_hash))
 }
 
 pub fn verify_invoke_v3_signature(
@@ -34,18 +25,8 @@ pub fn verify_invoke_v3_signature(
 ) -> Result<(bool, Felt), Error> {
     let chain_id = Felt::from_hex_unchecked(chain_id_input);
 
-    let msg_hash = calculate_invoke_v3_hash(txn, &chain_id)?;
-
-    let r_bytes = txn.signature[0];
-    let s_bytes = txn.signature[1];
-
-    let stark_key = match public_key {
-        Some(public_key) => Felt::from_hex_unchecked(public_key),
-        None => recover(&msg_hash, &r_bytes, &s_bytes, &Felt::ONE)?,
-    };
+    let msg_hash = calculate_invoke_v3_hash(txn, &chain_id, &FeeMarket::default())?;
 
-    match verify(&stark_key, &msg_hash, &r_bytes, &s_bytes) {
-        Ok(is_valid) => Ok((is_valid, msg_hash)),
-        Err(e) => Err(Error::VerifyError(e)),
-    }
+    let is_valid = verify_signature(SignatureScheme::Stark, &msg_hash, &txn.signature, public_key)?;
+    Ok((is_valid, msg_hash))
 }
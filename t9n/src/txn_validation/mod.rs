@@ -1,5 +1,8 @@
+pub mod batch;
 pub mod declare;
 pub mod deploy_account;
 pub mod errors;
 pub mod invoke;
+pub mod schema;
+pub mod signature;
 pub mod validate;
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::errors::Error;
+use jsonschema::JSONSchema;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Broadcasted transaction schemas of the Starknet JSON-RPC spec v0.7.1.
+const BROADCASTED_TXN_SCHEMA: &str = include_str!("../../schemas/broadcasted_txn.json");
+
+/// Schema definition of each supported transaction type and normalized version.
+const TXN_DEFINITIONS: [(&str, &str, &str); 7] = [
+    ("INVOKE", "0x0", "INVOKE_TXN_V0"),
+    ("INVOKE", "0x1", "INVOKE_TXN_V1"),
+    ("INVOKE", "0x3", "INVOKE_TXN_V3"),
+    ("DECLARE", "0x2", "BROADCASTED_DECLARE_TXN_V2"),
+    ("DECLARE", "0x3", "BROADCASTED_DECLARE_TXN_V3"),
+    ("DEPLOY_ACCOUNT", "0x1", "DEPLOY_ACCOUNT_TXN_V1"),
+    ("DEPLOY_ACCOUNT", "0x3", "DEPLOY_ACCOUNT_TXN_V3"),
+];
+
+/// Transaction schemas compiled on first use, by definition name.
+static COMPILED_SCHEMAS: OnceLock<Result<HashMap<&'static str, JSONSchema>, String>> = OnceLock::new();
+
+/// Single field-level violation of the transaction schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending field, e.g. `/resource_bounds/l1_gas/max_amount`.
+    pub path: String,
+    pub message: String,
+}
+
+/// Returns the name of the schema definition matching the transaction type and the normalized
+/// version, or `None` if the transaction is not supported.
+pub fn schema_definition(txn_type: &str, version: &str) -> Option<&'static str> {
+    TXN_DEFINITIONS
+        .iter()
+        .find(|(definition_type, definition_version, _)| *definition_type == txn_type && *definition_version == version)
+        .map(|(_, _, definition)| *definition)
+}
+
+fn compile_schemas() -> Result<HashMap<&'static str, JSONSchema>, String> {
+    let schema: Value = serde_json::from_str(BROADCASTED_TXN_SCHEMA).map_err(|e| e.to_string())?;
+    TXN_DEFINITIONS
+        .iter()
+        .map(|(_, _, definition)| {
+            let schema = json!({
+                "$ref": format!("#/definitions/{definition}"),
+                "definitions": schema["definitions"],
+            });
+            let compiled = JSONSchema::compile(&schema).map_err(|e| format!("{definition}: {e}"))?;
+            Ok((*definition, compiled))
+        })
+        .collect()
+}
+
+/// Validates the transaction JSON against the given schema definition and returns all violations,
+/// so they can be reported at once before the transaction is hashed.
+pub fn validate_txn_schema(txn: &Value, definition: &str) -> Result<Vec<SchemaViolation>, Error> {
+    let schemas = COMPILED_SCHEMAS.get_or_init(compile_schemas).as_ref().map_err(|e| Error::SchemaError(e.clone()))?;
+    let compiled = schemas
+        .get(definition)
+        .ok_or_else(|| Error::SchemaError(format!("Unknown transaction schema definition {definition}")))?;
+
+    let violations = match compiled.validate(txn) {
+        Ok(()) => vec![],
+        Err(errors) => errors
+            .map(|error| SchemaViolation { path: error.instance_path.to_string(), message: error.to_string() })
+            .collect(),
+    };
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invoke_v3() -> Value {
+        serde_json::from_str(include_str!("../../examples/invoke/invoke_txn_v3.json")).unwrap()
+    }
+
+    #[test]
+    fn every_supported_transaction_has_a_compiled_schema() {
+        for (txn_type, version, definition) in TXN_DEFINITIONS {
+            assert_eq!(schema_definition(txn_type, version), Some(definition));
+            assert!(validate_txn_schema(&json!({}), definition).is_ok());
+        }
+        assert_eq!(schema_definition("DEPLOY", "0x0"), None);
+        assert!(validate_txn_schema(&json!({}), "DEPLOY_TXN").is_err());
+    }
+
+    #[test]
+    fn valid_transaction_has_no_violations() {
+        assert_eq!(validate_txn_schema(&invoke_v3(), "INVOKE_TXN_V3").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn all_violations_are_reported() {
+        let mut txn = invoke_v3();
+        txn.as_object_mut().unwrap().remove("tip");
+        txn["nonce"] = json!(1);
+        txn["resource_bounds"]["l1_gas"]["max_amount"] = json!("0xZZ");
+
+        let violations = validate_txn_schema(&txn, "INVOKE_TXN_V3").unwrap();
+        let mut paths: Vec<_> = violations.iter().map(|violation| violation.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, ["", "/nonce", "/resource_bounds/l1_gas/max_amount"]);
+        assert!(violations.iter().any(|violation| violation.message.contains("\"tip\" is a required property")));
+    }
+}
@@ -0,0 +1,173 @@
+use super::errors::Error;
+use crypto_utils::curve::signer::{recover, verify};
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use starknet_types_core::felt::Felt;
+
+/// Signature scheme of the account contract that signed the transaction.
+#[derive(Default, Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum SignatureScheme {
+    /// Stark curve ECDSA, `[r, s]`.
+    #[default]
+    Stark,
+    /// Ethereum-style secp256k1 ECDSA, `[r.low, r.high, s.low, s.high]` optionally followed by the
+    /// recovery parity `v`. The public key is required: a key recovered from the signature would
+    /// verify any signature.
+    Secp256k1,
+    /// secp256r1 ECDSA as used by hardware (WebAuthn/passkey) signers, `[r.low, r.high, s.low,
+    /// s.high]`. The public key is required.
+    Secp256r1,
+}
+
+/// Verifies `signature` over the transaction hash. For secp schemes the hash is used as the 32-byte
+/// big-endian prehash, as account contracts do, and the public key is a SEC1 encoded point.
+pub fn verify_signature(
+    scheme: SignatureScheme,
+    msg_hash: &Felt,
+    signature: &[Felt],
+    public_key: Option<&str>,
+) -> Result<bool, Error> {
+    match scheme {
+        SignatureScheme::Stark => verify_stark_signature(msg_hash, signature, public_key),
+        SignatureScheme::Secp256k1 => verify_secp256k1_signature(msg_hash, signature, public_key),
+        SignatureScheme::Secp256r1 => verify_secp256r1_signature(msg_hash, signature, public_key),
+    }
+}
+
+fn verify_stark_signature(msg_hash: &Felt, signature: &[Felt], public_key: Option<&str>) -> Result<bool, Error> {
+    let [r_bytes, s_bytes] = signature_elements::<2>(signature)?;
+
+    let stark_key = match public_key {
+        Some(public_key) => Felt::from_hex_unchecked(public_key),
+        None => recover(msg_hash, &r_bytes, &s_bytes, &Felt::ONE)?,
+    };
+
+    Ok(verify(&stark_key, msg_hash, &r_bytes, &s_bytes)?)
+}
+
+fn verify_secp256k1_signature(msg_hash: &Felt, signature: &[Felt], public_key: Option<&str>) -> Result<bool, Error> {
+    let (r_low, r_high, s_low, s_high) = match signature {
+        [r_low, r_high, s_low, s_high] => (r_low, r_high, s_low, s_high),
+        [r_low, r_high, s_low, s_high, v] => {
+            parity(v)?;
+            (r_low, r_high, s_low, s_high)
+        }
+        _ => return Err(Error::InvalidSignatureLength { expected: "4 or 5".to_string(), actual: signature.len() }),
+    };
+    let signature = k256::ecdsa::Signature::from_scalars(u256_bytes(r_low, r_high)?, u256_bytes(s_low, s_high)?)?;
+    // Account contracts accept both low and high `s`, while the verifier only accepts low `s`.
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    let public_key = public_key.ok_or(Error::PublicKeyRequired)?;
+    let verifying_key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&decode_public_key(public_key)?)?;
+
+    Ok(verifying_key.verify_prehash(&msg_hash.to_bytes_be(), &signature).is_ok())
+}
+
+fn verify_secp256r1_signature(msg_hash: &Felt, signature: &[Felt], public_key: Option<&str>) -> Result<bool, Error> {
+    let [r_low, r_high, s_low, s_high] = signature_elements::<4>(signature)?;
+    let signature = p256::ecdsa::Signature::from_scalars(u256_bytes(&r_low, &r_high)?, u256_bytes(&s_low, &s_high)?)?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    let public_key = public_key.ok_or(Error::PublicKeyRequired)?;
+    let verifying_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&decode_public_key(public_key)?)?;
+
+    Ok(verifying_key.verify_prehash(&msg_hash.to_bytes_be(), &signature).is_ok())
+}
+
+fn signature_elements<const N: usize>(signature: &[Felt]) -> Result<[Felt; N], Error> {
+    signature.try_into().map_err(|_| Error::InvalidSignatureLength { expected: N.to_string(), actual: signature.len() })
+}
+
+/// Joins the `low` and `high` 128-bit limbs of a u256 into its 32-byte big-endian representation.
+fn u256_bytes(low: &Felt, high: &Felt) -> Result<[u8; 32], Error> {
+    let low = low.to_bytes_be();
+    let high = high.to_bytes_be();
+    if low[..16].iter().chain(high[..16].iter()).any(|byte| *byte != 0) {
+        return Err(Error::InvalidU256Limb);
+    }
+
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(&high[16..]);
+    bytes[16..].copy_from_slice(&low[16..]);
+    Ok(bytes)
+}
+
+/// Accepts both `0`/`1` and Ethereum's legacy `27`/`28`, returning the parity as `0` or `1`.
+fn parity(v: &Felt) -> Result<u8, Error> {
+    match u8::try_from(*v).map_err(|_| Error::InvalidRecoveryId)? {
+        v @ (0 | 1) => Ok(v),
+        v @ (27 | 28) => Ok(v - 27),
+        _ => Err(Error::InvalidRecoveryId),
+    }
+}
+
+fn decode_public_key(public_key: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(public_key.trim_start_matches("0x")).map_err(|_| Error::InvalidPublicKey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_utils::curve::secp::{
+        secp256k1_get_public_key, secp256k1_sign, secp256r1_get_public_key, secp256r1_sign,
+    };
+
+    const PRIVATE_KEY: [u8; 32] = [1; 32];
+    const OTHER_PRIVATE_KEY: [u8; 32] = [2; 32];
+
+    fn msg_hash() -> Felt {
+        Felt::from_hex_unchecked("0x2d6479c0758efbb5aa07d35ed5454d728637fceab7ba544d3ea95403a5630a8")
+    }
+
+    #[test]
+    fn secp256k1_signature_round_trips() {
+        let signature = secp256k1_sign(&PRIVATE_KEY, &msg_hash()).unwrap().to_felts(true);
+        let public_key = hex::encode(secp256k1_get_public_key(&PRIVATE_KEY).unwrap().to_sec1_bytes());
+        let other_public_key = hex::encode(secp256k1_get_public_key(&OTHER_PRIVATE_KEY).unwrap().to_sec1_bytes());
+
+        let verify = |public_key| verify_signature(SignatureScheme::Secp256k1, &msg_hash(), &signature, public_key);
+        assert!(verify(Some(&public_key)).unwrap());
+        assert!(verify_signature(SignatureScheme::Secp256k1, &msg_hash(), &signature[..4], Some(&public_key)).unwrap());
+        assert!(!verify(Some(&other_public_key)).unwrap());
+        assert!(matches!(verify(None), Err(Error::PublicKeyRequired)));
+        assert!(!verify_signature(SignatureScheme::Secp256k1, &Felt::ONE, &signature, Some(&public_key)).unwrap());
+    }
+
+    #[test]
+    fn secp256r1_signature_round_trips() {
+        let signature = secp256r1_sign(&PRIVATE_KEY, &msg_hash()).unwrap().to_felts(false);
+        let public_key = hex::encode(secp256r1_get_public_key(&PRIVATE_KEY).unwrap().to_sec1_bytes());
+        let other_public_key = hex::encode(secp256r1_get_public_key(&OTHER_PRIVATE_KEY).unwrap().to_sec1_bytes());
+
+        let verify = |public_key| verify_signature(SignatureScheme::Secp256r1, &msg_hash(), &signature, public_key);
+        assert!(verify(Some(&public_key)).unwrap());
+        assert!(!verify(Some(&other_public_key)).unwrap());
+        assert!(matches!(verify(None), Err(Error::PublicKeyRequired)));
+    }
+
+    #[test]
+    fn malformed_secp256k1_signatures_are_rejected() {
+        let signature = secp256k1_sign(&PRIVATE_KEY, &msg_hash()).unwrap().to_felts(true);
+        let public_key = hex::encode(secp256k1_get_public_key(&PRIVATE_KEY).unwrap().to_sec1_bytes());
+        let verify = |signature: &[Felt]| {
+            verify_signature(SignatureScheme::Secp256k1, &msg_hash(), signature, Some(&public_key))
+        };
+
+        let Err(Error::InvalidSignatureLength { expected, actual: 3 }) = verify(&signature[..3]) else {
+            panic!("a 3 element signature is not rejected for its length");
+        };
+        assert_eq!(expected, "4 or 5");
+
+        let mut wide_limb = signature.clone();
+        wide_limb[0] = Felt::TWO.pow(128u128);
+        assert!(matches!(verify(&wide_limb), Err(Error::InvalidU256Limb)));
+
+        let mut bad_parity = signature.clone();
+        bad_parity[4] = Felt::TWO;
+        assert!(matches!(verify(&bad_parity), Err(Error::InvalidRecoveryId)));
+
+        let mut legacy_parity = signature.clone();
+        legacy_parity[4] += Felt::from(27);
+        assert!(verify(&legacy_parity).unwrap());
+    }
+}
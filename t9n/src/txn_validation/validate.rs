@@ -1,17 +1,33 @@
-use crate::txn_validation::declare::*;
-use crate::txn_validation::deploy_account::*;
-use crate::txn_validation::invoke::*;
+use crate::txn_hashes::declare_hash::{calculate_declare_v2_hash, calculate_declare_v3_hash};
+use crate::txn_hashes::deploy_account::{
+    calculate_deploy_account_address, calculate_deploy_account_v1_hash, calculate_deploy_v3_transaction_hash,
+};
+use crate::txn_hashes::invoke_hash::{calculate_invoke_v0_hash, calculate_invoke_v1_hash, calculate_invoke_v3_hash};
+use crate::txn_hashes::resource_bounds::FeeMarket;
+use crate::txn_validation::errors::Error as TxnValidationError;
+use crate::txn_validation::schema::{schema_definition, validate_txn_schema};
+use crate::txn_validation::signature::{verify_signature, SignatureScheme};
+use crate::typed_data::TypedData;
 use serde::de::Error;
+use serde::Deserialize;
 use serde_json::json;
 use serde_json::Result as SerdeResult;
 use serde_json::{from_reader, from_value, Value};
 use starknet_types_core::felt::Felt;
 use starknet_types_rpc::v0_7_1::starknet_api_openrpc::*;
-use starknet_types_rpc::DeployAccountTxn;
 use std::fs::File;
 use std::path::PathBuf;
 
-pub fn validate_txn_json(file_path: &PathBuf, public_key: Option<&str>, chain_id: &str) -> SerdeResult<Value> {
+/// Validates the transaction of `file_path`. V3 transactions are hashed with the resources of
+/// `protocol_version`, or of the version their bounds imply when it is not set, see
+/// [`FeeMarket::of_transaction`].
+pub fn validate_txn_json(
+    file_path: &PathBuf,
+    public_key: Option<&str>,
+    chain_id: &str,
+    scheme: SignatureScheme,
+    protocol_version: Option<[u32; 3]>,
+) -> SerdeResult<Value> {
     let file = File::open(file_path).map_err(|e| {
         let error_response = json!({
             "error": "File not found",
@@ -22,6 +38,17 @@ pub fn validate_txn_json(file_path: &PathBuf, public_key: Option<&str>, chain_id
 
     let value: Value = from_reader(file)?;
 
+    validate_txn_value(value, public_key, chain_id, scheme, protocol_version)
+}
+
+/// Validates an already parsed transaction, see [`validate_txn_json`].
+pub fn validate_txn_value(
+    value: Value,
+    public_key: Option<&str>,
+    chain_id: &str,
+    scheme: SignatureScheme,
+    protocol_version: Option<[u32; 3]>,
+) -> SerdeResult<Value> {
     let txn_type = value
         .get("type")
         .ok_or_else(|| {
@@ -56,164 +83,212 @@ pub fn validate_txn_json(file_path: &PathBuf, public_key: Option<&str>, chain_id
 
     let trimmed_version = txn_version.trim_start_matches("0x").trim_start_matches("0");
 
-    let formatted_version = format!("0x{}", trimmed_version);
+    // Version zero is trimmed down to nothing.
+    let formatted_version =
+        if trimmed_version.is_empty() { "0x0".to_string() } else { format!("0x{}", trimmed_version) };
 
     let version = formatted_version.as_str();
 
+    if let Some(definition) = schema_definition(txn_type, version) {
+        let violations = validate_txn_schema(&value, definition).map_err(|e| {
+            serde_json::Error::custom(json!({ "error": format!("Schema validation failed: {:?}", e) }).to_string())
+        })?;
+        if !violations.is_empty() {
+            let error_response = json!({
+                "error": "Transaction does not match the schema",
+                "violations": violations,
+            });
+            return Err(serde_json::Error::custom(error_response.to_string()));
+        }
+    }
+
+    let chain_id = Felt::from_hex_unchecked(chain_id);
+    let fee_market = FeeMarket::of_transaction(&value, protocol_version).map_err(|e| {
+        serde_json::Error::custom(json!({ "error": format!("Invalid resource bounds: {}", e) }).to_string())
+    })?;
+
     match txn_type {
         "INVOKE" => match version {
+            "0x0" => {
+                let txn: InvokeTxnV0<Felt> = from_value(value)?;
+                verification_result(calculate_invoke_v0_hash(&txn, &chain_id), &txn.signature, public_key, scheme, None)
+            }
             "0x1" => {
-                let txn: InvokeTxnV1<Felt> = serde_json::from_value(value)?;
-                match verify_invoke_v1_signature(&txn, public_key, chain_id) {
-                    Ok((is_valid, hash)) => {
-                        if is_valid {
-                            Ok(json!({ "hash": hash}))
-                        } else {
-                            Err(serde_json::Error::custom(
-                                json!({
-                                    "error": "Signature is invalid",
-                                    "hash": hash,
-                                })
-                                .to_string(),
-                            ))
-                        }
-                    }
-                    Err(e) => Err(serde_json::Error::custom(
-                        json!({
-                            "error": format!("Signature verification failed: {:?}", e),
-                        })
-                        .to_string(),
-                    )),
-                }
+                let txn: InvokeTxnV1<Felt> = from_value(value)?;
+                verification_result(calculate_invoke_v1_hash(&txn, &chain_id), &txn.signature, public_key, scheme, None)
             }
             "0x3" => {
                 let txn: InvokeTxnV3<Felt> = from_value(value)?;
-                match verify_invoke_v3_signature(&txn, public_key, chain_id) {
-                    Ok((is_valid, hash)) => {
-                        if is_valid {
-                            Ok(json!({ "hash": hash }))
-                        } else {
-                            Err(serde_json::Error::custom(
-                                json!({
-                                    "error": "Signature is invalid",
-                                    "hash": hash,
-                                })
-                                .to_string(),
-                            ))
-                        }
-                    }
-                    Err(e) => Err(serde_json::Error::custom(
-                        json!({
-                            "error": format!("Signature verification failed: {:?}", e),
-                        })
-                        .to_string(),
-                    )),
-                }
+                verification_result(
+                    calculate_invoke_v3_hash(&txn, &chain_id, &fee_market),
+                    &txn.signature,
+                    public_key,
+                    scheme,
+                    None,
+                )
             }
             _ => Err(serde_json::Error::custom("Unsupported version")),
         },
         "DECLARE" => match version {
             "0x2" => {
                 let txn: BroadcastedDeclareTxnV2<Felt> = from_value(value)?;
-                match verify_declare_v2_signature(&txn, public_key, chain_id) {
-                    Ok((is_valid, hash)) => {
-                        if is_valid {
-                            Ok(json!({ "hash": hash }))
-                        } else {
-                            Err(serde_json::Error::custom(
-                                json!({
-                                    "error": "Signature is invalid",
-                                    "hash": hash,
-                                })
-                                .to_string(),
-                            ))
-                        }
-                    }
-                    Err(e) => Err(serde_json::Error::custom(
-                        json!({
-                            "error": format!("Signature verification failed: {:?}", e),
-                        })
-                        .to_string(),
-                    )),
-                }
+                verification_result(
+                    calculate_declare_v2_hash(&txn, &chain_id),
+                    &txn.signature,
+                    public_key,
+                    scheme,
+                    None,
+                )
             }
             "0x3" => {
                 let txn: BroadcastedDeclareTxnV3<Felt> = from_value(value)?;
-                match verify_declare_v3_signature(&txn, public_key, chain_id) {
-                    Ok((is_valid, hash)) => {
-                        if is_valid {
-                            Ok(json!({ "hash": hash }))
-                        } else {
-                            Err(serde_json::Error::custom(
-                                json!({
-                                    "error": "Signature is invalid",
-                                    "hash": hash,
-                                })
-                                .to_string(),
-                            ))
-                        }
-                    }
-                    Err(e) => Err(serde_json::Error::custom(
-                        json!({
-                            "error": format!("Signature verification failed: {:?}", e),
-                        })
-                        .to_string(),
-                    )),
-                }
+                verification_result(
+                    calculate_declare_v3_hash(&txn, &chain_id, &fee_market),
+                    &txn.signature,
+                    public_key,
+                    scheme,
+                    None,
+                )
             }
             _ => Err(serde_json::Error::custom("Unsupported version")),
         },
         "DEPLOY_ACCOUNT" => match version {
             "0x1" => {
                 let txn: DeployAccountTxnV1<Felt> = from_value(value)?;
-                match verify_deploy_account_signature(DeployAccountTxn::V1(txn), public_key, chain_id) {
-                    Ok((is_valid, hash)) => {
-                        if is_valid {
-                            Ok(json!({ "hash": hash }))
-                        } else {
-                            Err(serde_json::Error::custom(
-                                json!({
-                                    "error": "Signature is invalid",
-                                    "hash": hash,
-                                })
-                                .to_string(),
-                            ))
-                        }
-                    }
-                    Err(e) => Err(serde_json::Error::custom(
-                        json!({
-                            "error": format!("Signature verification failed: {:?}", e),
-                        })
-                        .to_string(),
-                    )),
-                }
+                let contract_address = calculate_deploy_account_address(
+                    txn.contract_address_salt,
+                    txn.class_hash,
+                    &txn.constructor_calldata,
+                );
+                verification_result(
+                    calculate_deploy_account_v1_hash(&txn, &chain_id),
+                    &txn.signature,
+                    public_key,
+                    scheme,
+                    Some(contract_address),
+                )
             }
             "0x3" => {
                 let txn: DeployAccountTxnV3<Felt> = from_value(value)?;
-                match verify_deploy_account_signature(DeployAccountTxn::V3(txn), public_key, chain_id) {
-                    Ok((is_valid, hash)) => {
-                        if is_valid {
-                            Ok(json!({ "hash": hash }))
-                        } else {
-                            Err(serde_json::Error::custom(
-                                json!({
-                                    "error": "Signature is invalid",
-                                    "hash": hash,
-                                })
-                                .to_string(),
-                            ))
-                        }
-                    }
-                    Err(e) => Err(serde_json::Error::custom(
-                        json!({
-                            "error": format!("Signature verification failed: {:?}", e),
-                        })
-                        .to_string(),
-                    )),
-                }
+                let contract_address = calculate_deploy_account_address(
+                    txn.contract_address_salt,
+                    txn.class_hash,
+                    &txn.constructor_calldata,
+                );
+                verification_result(
+                    calculate_deploy_v3_transaction_hash(&txn, &chain_id, &fee_market),
+                    &txn.signature,
+                    public_key,
+                    scheme,
+                    Some(contract_address),
+                )
             }
             _ => Err(serde_json::Error::custom("Unsupported version")),
         },
         _ => Err(Error::custom("Invalid or missing transaction type")),
     }
 }
+
+/// Result of the signature verification. The address a deploy-account transaction deploys the
+/// account at is part of its hash, so it is reported with invalid signatures too.
+fn verification_result(
+    msg_hash: Result<Felt, TxnValidationError>,
+    signature: &[Felt],
+    public_key: Option<&str>,
+    scheme: SignatureScheme,
+    contract_address: Option<Felt>,
+) -> SerdeResult<Value> {
+    let verification = msg_hash
+        .and_then(|hash| verify_signature(scheme, &hash, signature, public_key).map(|is_valid| (is_valid, hash)));
+    let with_address = |mut result: Value| {
+        if let Some(contract_address) = contract_address {
+            result["contract_address"] = json!(contract_address);
+        }
+        result
+    };
+
+    match verification {
+        Ok((true, hash)) => Ok(with_address(json!({ "hash": hash }))),
+        Ok((false, hash)) => Err(serde_json::Error::custom(
+            with_address(json!({
+                "error": "Signature is invalid",
+                "hash": hash,
+            }))
+            .to_string(),
+        )),
+        Err(e) => Err(serde_json::Error::custom(
+            json!({
+                "error": format!("Signature verification failed: {:?}", e),
+            })
+            .to_string(),
+        )),
+    }
+}
+
+/// Verifies the signature of `account_address` over the SNIP-12 typed data message of `file_path`.
+/// The signature is the one returned by the wallet, which the account checks in `is_valid_signature`.
+pub fn validate_message_json(
+    file_path: &PathBuf,
+    account_address: &str,
+    signature: &[String],
+    public_key: Option<&str>,
+    scheme: SignatureScheme,
+) -> SerdeResult<Value> {
+    let file = File::open(file_path).map_err(|e| {
+        serde_json::Error::custom(json!({ "error": "File not found", "details": e.to_string() }).to_string())
+    })?;
+    let typed_data: TypedData = from_reader(file)?;
+
+    let parse_felt = |value: &str| {
+        Felt::from_hex(value).map_err(|e| {
+            Error::custom(
+                json!({ "error": "Invalid field element", "value": value, "details": e.to_string() }).to_string(),
+            )
+        })
+    };
+    let account_address = parse_felt(account_address)?;
+    let signature = signature.iter().map(|element| parse_felt(element)).collect::<SerdeResult<Vec<_>>>()?;
+
+    verification_result(typed_data.message_hash(account_address), &signature, public_key, scheme, None)
+}
+
+/// Fields of a deploy-account transaction the address of the account is derived from.
+#[derive(Deserialize)]
+struct DeployAccountAddressFields {
+    class_hash: Felt,
+    contract_address_salt: Felt,
+    constructor_calldata: Vec<Felt>,
+}
+
+/// Checks that the deploy-account transaction of `file_path` deploys the account at `expected`, and
+/// returns the address it deploys it at. The derivation takes the salt, the class hash and the
+/// constructor calldata, with a zero deployer address.
+pub fn verify_deploy_account_address(file_path: &PathBuf, expected: &str) -> SerdeResult<Felt> {
+    let file = File::open(file_path).map_err(|e| {
+        serde_json::Error::custom(json!({ "error": "File not found", "details": e.to_string() }).to_string())
+    })?;
+    let value: Value = from_reader(file)?;
+    if value.get("type").and_then(Value::as_str) != Some("DEPLOY_ACCOUNT") {
+        return Err(Error::custom(
+            json!({ "error": "Expected address given for a non deploy-account transaction" }).to_string(),
+        ));
+    }
+
+    let fields: DeployAccountAddressFields = from_value(value)?;
+    let contract_address =
+        calculate_deploy_account_address(fields.contract_address_salt, fields.class_hash, &fields.constructor_calldata);
+    let expected = Felt::from_hex(expected).map_err(|e| {
+        Error::custom(json!({ "error": "Invalid expected address", "details": e.to_string() }).to_string())
+    })?;
+    if contract_address != expected {
+        return Err(Error::custom(
+            json!({
+                "error": "Contract address mismatch",
+                "expected": expected,
+                "contract_address": contract_address,
+            })
+            .to_string(),
+        ));
+    }
+    Ok(contract_address)
+}
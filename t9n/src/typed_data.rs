@@ -0,0 +1,655 @@
+//! SNIP-12 typed data: the hash of off-chain messages that Starknet wallets sign, in the legacy
+//! revision 0 (Pedersen, `StarkNetDomain`) and in revision 1 (Poseidon, `StarknetDomain`).
+
+use crate::txn_validation::errors::Error;
+use crypto_utils::curve::signer::compute_hash_on_elements;
+use crypto_utils::hash::poseidon_hash_many;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use starknet_hive_hashing::keccak::starknet_keccak;
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+use std::collections::BTreeMap;
+
+/// Prefix of every message hash, `'StarkNet Message'` as a short string.
+const MESSAGE_PREFIX: &str = "StarkNet Message";
+
+/// Revision of the SNIP-12 encoding, picked from the domain of the typed data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Revision {
+    Legacy,
+    Active,
+}
+
+impl Revision {
+    fn domain_type(self) -> &'static str {
+        match self {
+            Revision::Legacy => "StarkNetDomain",
+            Revision::Active => "StarknetDomain",
+        }
+    }
+
+    fn hash_elements(self, elements: &[Felt]) -> Felt {
+        match self {
+            Revision::Legacy => compute_hash_on_elements(elements),
+            Revision::Active => poseidon_hash_many(elements),
+        }
+    }
+
+    /// Hash of two merkle tree nodes, which are sorted so that proofs do not carry their order.
+    fn hash_pair(self, a: Felt, b: Felt) -> Felt {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        match self {
+            Revision::Legacy => Pedersen::hash(&low, &high),
+            Revision::Active => Poseidon::hash(&low, &high),
+        }
+    }
+
+    fn escape(self, name: &str) -> String {
+        match self {
+            Revision::Legacy => name.to_string(),
+            Revision::Active => format!("\"{name}\""),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+    /// Leaf type of a `merkletree` field, enum type of an `enum` field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains: Option<String>,
+}
+
+/// Typed data as passed to `signTypedData` by wallets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypeField>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+impl TypedData {
+    /// Revision the typed data is encoded with: 1 when it uses `StarknetDomain` with revision 1,
+    /// 0 when it uses `StarkNetDomain` without a revision or with revision 0.
+    pub fn revision(&self) -> Result<Revision, Error> {
+        let revision = self.domain.get("revision").map(felt_value).transpose()?;
+        if self.types.contains_key("StarknetDomain") && revision == Some(Felt::ONE) {
+            Ok(Revision::Active)
+        } else if self.types.contains_key("StarkNetDomain") && revision.unwrap_or_default() == Felt::ZERO {
+            Ok(Revision::Legacy)
+        } else {
+            Err(Error::TypedDataError("domain matches no SNIP-12 revision".to_string()))
+        }
+    }
+
+    /// Hash of the message signed by `account_address`, what the account verifies the signature of.
+    pub fn message_hash(&self, account_address: Felt) -> Result<Felt, Error> {
+        let encoder = Encoder::new(self)?;
+        let revision = encoder.revision;
+        let domain_hash = encoder.struct_hash(revision.domain_type(), &self.domain)?;
+        let message_hash = encoder.struct_hash(&self.primary_type, &self.message)?;
+        Ok(revision.hash_elements(&[short_string(MESSAGE_PREFIX)?, domain_hash, account_address, message_hash]))
+    }
+
+    /// Hash of the encoding of `type_name` and of the types it depends on.
+    pub fn type_hash(&self, type_name: &str) -> Result<Felt, Error> {
+        Ok(starknet_keccak(Encoder::new(self)?.encode_type(type_name).as_bytes()))
+    }
+}
+
+/// Types of a typed data along with the types preset by its revision.
+struct Encoder {
+    revision: Revision,
+    types: BTreeMap<String, Vec<TypeField>>,
+}
+
+impl Encoder {
+    fn new(typed_data: &TypedData) -> Result<Self, Error> {
+        let revision = typed_data.revision()?;
+        let mut types = typed_data.types.clone();
+        if revision == Revision::Active {
+            for (name, fields) in preset_types() {
+                types.entry(name.to_string()).or_insert(fields);
+            }
+        }
+        Ok(Self { revision, types })
+    }
+
+    fn fields(&self, type_name: &str) -> Result<&[TypeField], Error> {
+        self.types
+            .get(type_name)
+            .map(Vec::as_slice)
+            .ok_or_else(|| Error::TypedDataError(format!("type {type_name} is not defined")))
+    }
+
+    /// `type_name` followed by the types it depends on in alphabetical order, e.g.
+    /// `Mail(from:Person,to:Person)Person(name:felt)`, with names quoted in revision 1.
+    fn encode_type(&self, type_name: &str) -> String {
+        let mut dependencies = Vec::new();
+        self.collect_dependencies(type_name, None, &mut dependencies);
+        if dependencies.len() > 1 {
+            dependencies[1..].sort();
+        }
+
+        let escape = |name: &str| self.revision.escape(name);
+        let mut encoded = String::new();
+        for dependency in dependencies {
+            let fields: Vec<String> = self.types[&dependency]
+                .iter()
+                .map(|field| {
+                    let target = match (&field.contains, field.r#type.as_str()) {
+                        (Some(contains), "enum") if self.revision == Revision::Active => contains.as_str(),
+                        _ => field.r#type.as_str(),
+                    };
+                    let type_string = match tuple_types(target) {
+                        Some(elements) => format!(
+                            "({})",
+                            elements
+                                .split(',')
+                                .map(|element| if element.is_empty() { String::new() } else { escape(element) })
+                                .collect::<Vec<_>>()
+                                .join(",")
+                        ),
+                        None => escape(target),
+                    };
+                    format!("{}:{}", escape(&field.name), type_string)
+                })
+                .collect();
+            encoded.push_str(&format!("{}({})", escape(&dependency), fields.join(",")));
+        }
+        encoded
+    }
+
+    fn collect_dependencies(&self, type_name: &str, contains: Option<&str>, dependencies: &mut Vec<String>) {
+        let candidates: Vec<&str> = if let Some(element) = type_name.strip_suffix('*') {
+            vec![element]
+        } else if self.revision == Revision::Active && type_name == "enum" {
+            contains.into_iter().collect()
+        } else if let Some(elements) = tuple_types(type_name).filter(|_| self.revision == Revision::Active) {
+            elements.split(',').map(|element| element.trim_end_matches('*')).collect()
+        } else {
+            vec![type_name]
+        };
+
+        for candidate in candidates {
+            if dependencies.iter().any(|dependency| dependency == candidate) {
+                continue;
+            }
+            let Some(fields) = self.types.get(candidate) else {
+                continue;
+            };
+            dependencies.push(candidate.to_string());
+            for field in fields {
+                self.collect_dependencies(&field.r#type, field.contains.as_deref(), dependencies);
+            }
+        }
+    }
+
+    fn struct_hash(&self, type_name: &str, data: &Value) -> Result<Felt, Error> {
+        let mut elements = vec![starknet_keccak(self.encode_type(type_name).as_bytes())];
+        for field in self.fields(type_name)? {
+            let value = data
+                .get(&field.name)
+                .filter(|value| !value.is_null() || field.r#type == "enum")
+                .ok_or_else(|| Error::TypedDataError(format!("missing data for {type_name}.{}", field.name)))?;
+            elements.push(self.encode_value(&field.r#type, field.contains.as_deref(), value)?);
+        }
+        Ok(self.revision.hash_elements(&elements))
+    }
+
+    fn encode_value(&self, type_name: &str, contains: Option<&str>, value: &Value) -> Result<Felt, Error> {
+        if self.types.contains_key(type_name) {
+            return self.struct_hash(type_name, value);
+        }
+        if let Some(element_type) = type_name.strip_suffix('*') {
+            let elements = array(value, type_name)?
+                .iter()
+                .map(|element| self.encode_value(element_type, None, element))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(self.revision.hash_elements(&elements));
+        }
+
+        let active = self.revision == Revision::Active;
+        match type_name {
+            "enum" if active => self.encode_enum(contains, value),
+            "merkletree" => {
+                let leaf_type = contains
+                    .ok_or_else(|| Error::TypedDataError("merkletree field without a leaf type".to_string()))?;
+                let leaves = array(value, type_name)?
+                    .iter()
+                    .map(|leaf| self.encode_value(leaf_type, None, leaf))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(self.merkle_root(leaves))
+            }
+            "selector" => match value.as_str() {
+                Some(name) if !is_number(name) => Ok(starknet_keccak(name.as_bytes())),
+                _ => felt_value(value),
+            },
+            "string" if active => {
+                let text = value.as_str().ok_or_else(|| Error::TypedDataError(format!("{value} is not a string")))?;
+                Ok(self.revision.hash_elements(&byte_array(text)))
+            }
+            "u128" | "timestamp" if active => bounded(felt_value(value)?, Felt::from(u128::MAX), type_name),
+            "bool" if active => bounded(felt_value(value)?, Felt::ONE, type_name),
+            "i128" if active => i128_value(value),
+            "felt" | "shortstring" | "ContractAddress" | "ClassHash" => felt_value(value),
+            _ if active => Err(Error::TypedDataError(format!("unsupported type {type_name}"))),
+            _ => felt_value(value),
+        }
+    }
+
+    /// Hash of the index of the variant followed by its encoded values, `{ "Variant": [values] }`.
+    fn encode_enum(&self, contains: Option<&str>, value: &Value) -> Result<Felt, Error> {
+        let enum_type = contains.ok_or_else(|| Error::TypedDataError("enum field without a type".to_string()))?;
+        let (variant, values) = value
+            .as_object()
+            .and_then(|variant| variant.iter().next())
+            .ok_or_else(|| Error::TypedDataError(format!("{value} is not a variant of {enum_type}")))?;
+        let (index, variant_type) = self
+            .fields(enum_type)?
+            .iter()
+            .enumerate()
+            .find(|(_, field)| &field.name == variant)
+            .ok_or_else(|| Error::TypedDataError(format!("{variant} is not a variant of {enum_type}")))?;
+
+        let mut elements = vec![Felt::from(index)];
+        let element_types = tuple_types(&variant_type.r#type).unwrap_or_default();
+        for (position, element_type) in element_types.split(',').filter(|element| !element.is_empty()).enumerate() {
+            let element = values
+                .get(position)
+                .ok_or_else(|| Error::TypedDataError(format!("missing value {position} of {enum_type}.{variant}")))?;
+            elements.push(self.encode_value(element_type, None, element)?);
+        }
+        Ok(self.revision.hash_elements(&elements))
+    }
+
+    /// Root of the merkle tree of `leaves`, an odd node being hashed with zero.
+    fn merkle_root(&self, mut leaves: Vec<Felt>) -> Felt {
+        if leaves.is_empty() {
+            return Felt::ZERO;
+        }
+        while leaves.len() > 1 {
+            leaves = leaves
+                .chunks(2)
+                .map(|pair| self.revision.hash_pair(pair[0], pair.get(1).copied().unwrap_or_default()))
+                .collect();
+        }
+        leaves[0]
+    }
+}
+
+/// Types every revision 1 typed data can use without defining them.
+fn preset_types() -> [(&'static str, Vec<TypeField>); 3] {
+    let field =
+        |name: &str, r#type: &str| TypeField { name: name.to_string(), r#type: r#type.to_string(), contains: None };
+    [
+        ("u256", vec![field("low", "u128"), field("high", "u128")]),
+        ("TokenAmount", vec![field("token_address", "ContractAddress"), field("amount", "u256")]),
+        ("NftId", vec![field("collection_address", "ContractAddress"), field("token_id", "u256")]),
+    ]
+}
+
+/// Types of a tuple type such as `(felt,u128)`, the variants of an enum.
+fn tuple_types(type_name: &str) -> Option<&str> {
+    type_name.strip_prefix('(')?.strip_suffix(')')
+}
+
+fn array<'a>(value: &'a Value, type_name: &str) -> Result<&'a Vec<Value>, Error> {
+    value.as_array().ok_or_else(|| Error::TypedDataError(format!("{value} is not an array of {type_name}")))
+}
+
+fn is_number(text: &str) -> bool {
+    text.starts_with("0x") || (!text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit()))
+}
+
+/// Field element of a value: a number, a boolean, a hex or decimal string or a short string.
+fn felt_value(value: &Value) -> Result<Felt, Error> {
+    match value {
+        Value::Bool(value) => Ok(Felt::from(*value as u8)),
+        Value::Number(number) => number
+            .as_u64()
+            .map(Felt::from)
+            .ok_or_else(|| Error::TypedDataError(format!("{number} is not a field element"))),
+        Value::String(text) if text.starts_with("0x") => {
+            Felt::from_hex(text).map_err(|_| Error::TypedDataError(format!("{text} is not a field element")))
+        }
+        Value::String(text) if is_number(text) => {
+            Felt::from_dec_str(text).map_err(|_| Error::TypedDataError(format!("{text} is not a field element")))
+        }
+        Value::String(text) => short_string(text),
+        _ => Err(Error::TypedDataError(format!("{value} is not a field element"))),
+    }
+}
+
+fn i128_value(value: &Value) -> Result<Felt, Error> {
+    let number = match value {
+        Value::Number(number) => number.as_i64().map(i128::from),
+        Value::String(text) => text.parse::<i128>().ok(),
+        _ => None,
+    };
+    match number {
+        Some(number) => Ok(Felt::from(number)),
+        None => bounded(felt_value(value)?, Felt::from(i128::MAX), "i128"),
+    }
+}
+
+fn bounded(value: Felt, max: Felt, type_name: &str) -> Result<Felt, Error> {
+    if value > max {
+        return Err(Error::TypedDataError(format!("{value:#x} is out of the range of {type_name}")));
+    }
+    Ok(value)
+}
+
+/// Cairo short string of at most 31 ASCII characters.
+fn short_string(text: &str) -> Result<Felt, Error> {
+    if text.len() > 31 || !text.is_ascii() {
+        return Err(Error::TypedDataError(format!("{text} is not a short string")));
+    }
+    Ok(Felt::from_bytes_be_slice(text.as_bytes()))
+}
+
+/// Serialized Cairo `ByteArray` of `text`: the number of full 31-byte words, the words, the pending
+/// word and its length.
+fn byte_array(text: &str) -> Vec<Felt> {
+    let chunks: Vec<&[u8]> = text.as_bytes().chunks(31).collect();
+    let (words, pending) = match chunks.split_last() {
+        Some((last, words)) if last.len() < 31 => (words, *last),
+        _ => (chunks.as_slice(), &[][..]),
+    };
+
+    let mut elements = vec![Felt::from(words.len())];
+    elements.extend(words.iter().map(|word| Felt::from_bytes_be_slice(word)));
+    elements.push(Felt::from_bytes_be_slice(pending));
+    elements.push(Felt::from(pending.len()));
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn typed_data(value: Value) -> TypedData {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn felt(hex: &str) -> Felt {
+        Felt::from_hex(hex).unwrap()
+    }
+
+    fn legacy_domain_types() -> Value {
+        json!([
+            { "name": "name", "type": "felt" },
+            { "name": "version", "type": "felt" },
+            { "name": "chainId", "type": "felt" }
+        ])
+    }
+
+    fn active_domain_types() -> Value {
+        json!([
+            { "name": "name", "type": "shortstring" },
+            { "name": "version", "type": "shortstring" },
+            { "name": "chainId", "type": "shortstring" },
+            { "name": "revision", "type": "shortstring" }
+        ])
+    }
+
+    fn active_domain() -> Value {
+        json!({ "name": "StarkNet Mail", "version": "1", "chainId": "1", "revision": "1" })
+    }
+
+    /// `baseExample` of the starknet.js typed data tests.
+    fn mail() -> TypedData {
+        typed_data(json!({
+            "types": {
+                "StarkNetDomain": legacy_domain_types(),
+                "Person": [{ "name": "name", "type": "felt" }, { "name": "wallet", "type": "felt" }],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "felt" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": { "name": "StarkNet Mail", "version": "1", "chainId": 1 },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!"
+            }
+        }))
+    }
+
+    /// Session of three policies, a merkle tree with an odd number of leaves.
+    fn session(types: Value, domain: Value) -> TypedData {
+        let policies: Vec<Value> =
+            ["0x1", "0x2", "0x3"].map(|address| json!({ "contractAddress": address, "selector": "transfer" })).into();
+        typed_data(json!({
+            "types": types,
+            "primaryType": "Session",
+            "domain": domain,
+            "message": { "key": "0x0", "expires": "0x0", "root": policies }
+        }))
+    }
+
+    #[test]
+    fn legacy_revision_matches_reference() {
+        let mail = mail();
+        assert_eq!(mail.revision().unwrap(), Revision::Legacy);
+
+        let encoder = Encoder::new(&mail).unwrap();
+        assert_eq!(
+            encoder.encode_type("Mail"),
+            "Mail(from:Person,to:Person,contents:felt)Person(name:felt,wallet:felt)"
+        );
+        assert_eq!(
+            mail.type_hash("StarkNetDomain").unwrap(),
+            felt("0x1bfc207425a47a5dfa1a50a4f5241203f50624ca5fdf5e18755765416b8e288")
+        );
+        assert_eq!(
+            mail.type_hash("Mail").unwrap(),
+            felt("0x13d89452df9512bf750f539ba3001b945576243288137ddb6c788457d4b2f79")
+        );
+        assert_eq!(
+            mail.message_hash(felt("0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826")).unwrap(),
+            felt("0x6fcff244f63e38b9d88b9e3378d44757710d1b244282b435cb472053c8d78d0")
+        );
+    }
+
+    #[test]
+    fn active_revision_matches_reference() {
+        let base = typed_data(json!({
+            "types": {
+                "StarknetDomain": active_domain_types(),
+                "Example": [
+                    { "name": "n0", "type": "felt" },
+                    { "name": "n1", "type": "bool" },
+                    { "name": "n2", "type": "string" },
+                    { "name": "n3", "type": "selector" },
+                    { "name": "n4", "type": "u128" },
+                    { "name": "n5", "type": "i128" },
+                    { "name": "n6", "type": "ContractAddress" },
+                    { "name": "n7", "type": "ClassHash" },
+                    { "name": "n8", "type": "timestamp" },
+                    { "name": "n9", "type": "shortstring" }
+                ]
+            },
+            "primaryType": "Example",
+            "domain": active_domain(),
+            "message": {
+                "n0": "0x3e8",
+                "n1": true,
+                "n2": "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed do eiusmod tempor incididunt ut \
+                       labore et dolore magna aliqua.",
+                "n3": "transfer",
+                "n4": "0x3e8",
+                "n5": "-170141183460469231731687303715884105727",
+                "n6": "0x3e8",
+                "n7": "0x3e8",
+                "n8": 1000,
+                "n9": "transfer"
+            }
+        }));
+        assert_eq!(base.revision().unwrap(), Revision::Active);
+        assert_eq!(
+            base.type_hash("StarknetDomain").unwrap(),
+            felt("0x1ff2f602e42168014d405a94f75e8a93d640751d71d16311266e140d8b0a210")
+        );
+        assert_eq!(
+            base.type_hash("Example").unwrap(),
+            felt("0x1f94cd0be8b4097a41486170fdf09a4cd23aefbc74bb2344718562994c2c111")
+        );
+        assert_eq!(
+            base.message_hash(felt("0x123")).unwrap(),
+            felt("0x4dea8aee7ff80e6fa6920e89c6548aa6291b30215baeebf889ac950a53578f1")
+        );
+    }
+
+    #[test]
+    fn enums_match_reference() {
+        let example = typed_data(json!({
+            "types": {
+                "StarknetDomain": active_domain_types(),
+                "Example": [
+                    { "name": "someEnum1", "type": "enum", "contains": "EnumA" },
+                    { "name": "someEnum2", "type": "enum", "contains": "EnumB" }
+                ],
+                "EnumA": [
+                    { "name": "Variant 1", "type": "()" },
+                    { "name": "Variant 2", "type": "(u128,u128*)" },
+                    { "name": "Variant 3", "type": "(u128)" }
+                ],
+                "EnumB": [{ "name": "Variant 1", "type": "()" }, { "name": "Variant 2", "type": "(u128)" }]
+            },
+            "primaryType": "Example",
+            "domain": active_domain(),
+            "message": { "someEnum1": { "Variant 2": [2, [0, 1]] }, "someEnum2": { "Variant 1": [] } }
+        }));
+
+        let encoder = Encoder::new(&example).unwrap();
+        assert_eq!(
+            encoder.encode_type("Example"),
+            r#""Example"("someEnum1":"EnumA","someEnum2":"EnumB")"EnumA"("Variant 1":(),"Variant 2":("u128","u128*"),"#
+                .to_owned()
+                + r#""Variant 3":("u128"))"EnumB"("Variant 1":(),"Variant 2":("u128"))"#
+        );
+        assert_eq!(
+            example.type_hash("Example").unwrap(),
+            felt("0x8eb4aeac64b707f3e843284c4258df6df1f0f7fd38dcffdd8a153a495cd351")
+        );
+        assert_eq!(
+            example.message_hash(felt("0x123")).unwrap(),
+            felt("0x2f01ee9a0b4e06298ada84980d69c2c051606430231780b39ba2817bec37d7c")
+        );
+    }
+
+    #[test]
+    fn presets_match_reference() {
+        let example = typed_data(json!({
+            "types": {
+                "StarknetDomain": active_domain_types(),
+                "Example": [{ "name": "n0", "type": "TokenAmount" }, { "name": "n1", "type": "NftId" }]
+            },
+            "primaryType": "Example",
+            "domain": active_domain(),
+            "message": {
+                "n0": { "token_address": "0x0123", "amount": { "low": "123", "high": "0" } },
+                "n1": { "collection_address": "0x0123", "token_id": { "low": "123", "high": "0" } }
+            }
+        }));
+
+        let encoder = Encoder::new(&example).unwrap();
+        assert_eq!(
+            encoder.encode_type("Example"),
+            r#""Example"("n0":"TokenAmount","n1":"NftId")"NftId"("collection_address":"ContractAddress","#.to_owned()
+                + r#""token_id":"u256")"TokenAmount"("token_address":"ContractAddress","amount":"u256")"#
+                + r#""u256"("low":"u128","high":"u128")"#
+        );
+        assert_eq!(
+            example.type_hash("Example").unwrap(),
+            felt("0x1a25a8bb84b761090b1fadaebe762c4b679b0d8883d2bedda695ea340839a55")
+        );
+        assert_eq!(
+            example.message_hash(felt("0x123")).unwrap(),
+            felt("0x763ae191242182d6f1ac125d0b631e0ee958f74d0073da882d3d4ed9b872909")
+        );
+    }
+
+    // No reference publishes merkle tree hashes over these leaves, the expected values were
+    // computed with an independent implementation of SNIP-12.
+    #[test]
+    fn merkle_trees_match_reference() {
+        let legacy = session(
+            json!({
+                "StarkNetDomain": legacy_domain_types(),
+                "Session": [
+                    { "name": "key", "type": "felt" },
+                    { "name": "expires", "type": "felt" },
+                    { "name": "root", "type": "merkletree", "contains": "Policy" }
+                ],
+                "Policy": [{ "name": "contractAddress", "type": "felt" }, { "name": "selector", "type": "selector" }]
+            }),
+            json!({ "name": "StarkNet Mail", "version": "1", "chainId": 1 }),
+        );
+        assert_eq!(
+            Encoder::new(&legacy).unwrap().encode_type("Session"),
+            "Session(key:felt,expires:felt,root:merkletree)"
+        );
+        assert_eq!(
+            legacy.type_hash("Session").unwrap(),
+            felt("0x1aa0e1c56b45cf06a54534fa1707c54e520b842feb21d03b7deddb6f1e340c")
+        );
+        assert_eq!(
+            legacy.message_hash(felt("0x123")).unwrap(),
+            felt("0x76b9f6db4b6807f10e30ceb0d503b4fa40efb6b96fe3caecc5985429e337a91")
+        );
+
+        let active = session(
+            json!({
+                "StarknetDomain": active_domain_types(),
+                "Session": [
+                    { "name": "key", "type": "felt" },
+                    { "name": "expires", "type": "timestamp" },
+                    { "name": "root", "type": "merkletree", "contains": "Policy" }
+                ],
+                "Policy": [
+                    { "name": "contractAddress", "type": "ContractAddress" },
+                    { "name": "selector", "type": "selector" }
+                ]
+            }),
+            active_domain(),
+        );
+        assert_eq!(
+            active.type_hash("Session").unwrap(),
+            felt("0x16538731a9ee1ca7992e3d5178623129daf6b525891d194e896e54eb293d6ad")
+        );
+        assert_eq!(
+            active.message_hash(felt("0x123")).unwrap(),
+            felt("0xf059a1633f37cef7707d5ce4a2a2e84d568d908db4d9e3c3f7d6759d8dcdb2")
+        );
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected() {
+        let example = |field_type: &str, value: Value| {
+            typed_data(json!({
+                "types": {
+                    "StarknetDomain": active_domain_types(),
+                    "Example": [{ "name": "n0", "type": field_type }]
+                },
+                "primaryType": "Example",
+                "domain": active_domain(),
+                "message": { "n0": value }
+            }))
+        };
+
+        assert!(example("u128", json!("0x100000000000000000000000000000000")).message_hash(Felt::ONE).is_err());
+        assert!(example("bool", json!(2)).message_hash(Felt::ONE).is_err());
+        assert!(example("u128", json!("0xffffffffffffffffffffffffffffffff")).message_hash(Felt::ONE).is_ok());
+    }
+}
@@ -0,0 +1,175 @@
+//! Golden vectors of transaction hashes, read from `tests/vectors/<network>.json`. A vector pins its
+//! hash either directly with `transaction_hash`, as returned by `starknet_getTransactionByHash`, or
+//! through the signature of `public_key`, which only verifies over the right hash. Either way a
+//! change to any hash formula fails the vector without touching the network.
+//!
+//! The mainnet vectors are transactions as the feeder gateway served them, with the hash it
+//! reported, covering every transaction type and version t9n hashes.
+
+use serde::Deserialize;
+use serde_json::{from_value, Value};
+use starknet_hive_hashing::transaction::{declare_v2_hash, declare_v3_hash, V1Fields, V3Fields};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::v0_7_1::starknet_api_openrpc::*;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use t9n::txn_hashes::declare_hash::{calculate_declare_v2_hash, calculate_declare_v3_hash};
+use t9n::txn_hashes::deploy_account::{
+    calculate_deploy_account_address, calculate_deploy_account_v1_hash, calculate_deploy_v3_transaction_hash,
+};
+use t9n::txn_hashes::invoke_hash::{calculate_invoke_v0_hash, calculate_invoke_v1_hash, calculate_invoke_v3_hash};
+use t9n::txn_hashes::resource_bounds::FeeMarket;
+use t9n::txn_validation::signature::{verify_signature, SignatureScheme};
+
+#[derive(Deserialize)]
+struct VectorFile {
+    network: String,
+    chain_id: Felt,
+    vectors: Vec<Vector>,
+}
+
+#[derive(Deserialize)]
+struct Vector {
+    name: String,
+    /// Transaction file relative to the crate, for transactions shared with the examples.
+    file: Option<PathBuf>,
+    transaction: Option<Value>,
+    transaction_hash: Option<Felt>,
+    public_key: Option<String>,
+    /// Address of the deployed account, for deploy-account transactions.
+    contract_address: Option<Felt>,
+}
+
+#[test]
+fn transaction_hashes_match_golden_vectors() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut checked = 0;
+    let mut pinned_by_hash = BTreeSet::new();
+
+    for entry in fs::read_dir(manifest_dir.join("tests/vectors")).expect("vectors directory") {
+        let path = entry.expect("vector file").path();
+        let file: VectorFile = serde_json::from_str(&fs::read_to_string(&path).expect("read vectors"))
+            .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+
+        for vector in file.vectors {
+            let name = format!("{} {}", file.network, vector.name);
+            let mut transaction = match (vector.file, vector.transaction) {
+                (Some(file), None) => {
+                    serde_json::from_str(&fs::read_to_string(manifest_dir.join(file)).expect("read transaction"))
+                        .expect("transaction json")
+                }
+                (None, Some(transaction)) => transaction,
+                _ => panic!("{name}: expected exactly one of `file` and `transaction`"),
+            };
+
+            // Transactions copied from a node carry their own hash.
+            let embedded_hash = transaction.as_object_mut().and_then(|txn| txn.remove("transaction_hash"));
+            let expected_hash = vector
+                .transaction_hash
+                .or_else(|| embedded_hash.map(|hash| from_value(hash).expect("transaction hash")));
+            assert!(
+                expected_hash.is_some() || vector.public_key.is_some(),
+                "{name}: pin the hash with `transaction_hash` or `public_key`"
+            );
+
+            let hash = transaction_hash(&transaction, &file.chain_id);
+            if let Some(expected_hash) = expected_hash {
+                assert_eq!(hash, expected_hash, "{name}: transaction hash");
+                pinned_by_hash.insert((
+                    transaction["type"].as_str().map(str::to_owned),
+                    transaction["version"].as_str().map(str::to_owned),
+                ));
+            }
+            if let Some(public_key) = &vector.public_key {
+                let signature: Vec<Felt> = from_value(transaction["signature"].clone()).expect("signature");
+                let valid = verify_signature(SignatureScheme::Stark, &hash, &signature, Some(public_key))
+                    .unwrap_or_else(|e| panic!("{name}: {e}"));
+                assert!(valid, "{name}: signature does not verify over {hash:#x}");
+            }
+            if let Some(expected_address) = vector.contract_address {
+                let salt = from_value(transaction["contract_address_salt"].clone()).expect("salt");
+                let class_hash = from_value(transaction["class_hash"].clone()).expect("class hash");
+                let calldata: Vec<Felt> =
+                    from_value(transaction["constructor_calldata"].clone()).expect("constructor calldata");
+                assert_eq!(
+                    calculate_deploy_account_address(salt, class_hash, &calldata),
+                    expected_address,
+                    "{name}: contract address"
+                );
+            }
+            checked += 1;
+        }
+    }
+
+    assert!(checked > 0, "no golden vectors found");
+    for (txn_type, version) in SUPPORTED {
+        assert!(
+            pinned_by_hash.contains(&(Some(txn_type.to_owned()), Some(version.to_owned()))),
+            "no vector pins the hash of {txn_type} {version} transactions"
+        );
+    }
+}
+
+/// Transaction types and versions t9n hashes, each needing a vector with a known hash.
+const SUPPORTED: [(&str, &str); 7] = [
+    ("INVOKE", "0x0"),
+    ("INVOKE", "0x1"),
+    ("INVOKE", "0x3"),
+    ("DECLARE", "0x2"),
+    ("DECLARE", "0x3"),
+    ("DEPLOY_ACCOUNT", "0x1"),
+    ("DEPLOY_ACCOUNT", "0x3"),
+];
+
+/// Hashes the transaction with the t9n implementation of its type and version. Declarations as
+/// returned by nodes only carry the class hash and are hashed with the shared functions directly.
+fn transaction_hash(transaction: &Value, chain_id: &Felt) -> Felt {
+    let txn_type = transaction["type"].as_str().expect("transaction type");
+    let version = transaction["version"].as_str().expect("transaction version");
+    let value = transaction.clone();
+    let fee_market = FeeMarket::of_transaction(transaction, None).expect("resource bounds");
+
+    let hash = match (txn_type, version) {
+        ("INVOKE", "0x0") => calculate_invoke_v0_hash(&from_value(value).unwrap(), chain_id),
+        ("INVOKE", "0x1") => calculate_invoke_v1_hash(&from_value(value).unwrap(), chain_id),
+        ("INVOKE", "0x3") => calculate_invoke_v3_hash(&from_value(value).unwrap(), chain_id, &fee_market),
+        ("DECLARE", "0x2") if transaction.get("contract_class").is_some() => {
+            calculate_declare_v2_hash(&from_value(value).unwrap(), chain_id)
+        }
+        ("DECLARE", "0x2") => {
+            let txn: DeclareTxnV2<Felt> = from_value(value).unwrap();
+            let fields = V1Fields {
+                version: Felt::TWO,
+                sender_address: txn.sender_address,
+                max_fee: txn.max_fee,
+                chain_id: *chain_id,
+                nonce: txn.nonce,
+            };
+            Ok(declare_v2_hash(&fields, txn.class_hash, txn.compiled_class_hash))
+        }
+        ("DECLARE", "0x3") if transaction.get("contract_class").is_some() => {
+            calculate_declare_v3_hash(&from_value(value).unwrap(), chain_id, &fee_market)
+        }
+        ("DECLARE", "0x3") => {
+            let txn: DeclareTxnV3<Felt> = from_value(value).unwrap();
+            let fields = V3Fields {
+                version: Felt::THREE,
+                sender_address: txn.sender_address,
+                fee_data_hash: fee_market.fee_data_hash(txn.tip, &txn.resource_bounds).unwrap(),
+                paymaster_data: &txn.paymaster_data,
+                chain_id: *chain_id,
+                nonce: txn.nonce,
+                nonce_data_availability_mode: txn.nonce_data_availability_mode.clone(),
+                fee_data_availability_mode: txn.fee_data_availability_mode.clone(),
+            };
+            Ok(declare_v3_hash(&fields, &txn.account_deployment_data, txn.class_hash, txn.compiled_class_hash))
+        }
+        ("DEPLOY_ACCOUNT", "0x1") => calculate_deploy_account_v1_hash(&from_value(value).unwrap(), chain_id),
+        ("DEPLOY_ACCOUNT", "0x3") => {
+            calculate_deploy_v3_transaction_hash(&from_value(value).unwrap(), chain_id, &fee_market)
+        }
+        _ => panic!("unsupported {txn_type} transaction version {version}"),
+    };
+    hash.expect("transaction hash")
+}